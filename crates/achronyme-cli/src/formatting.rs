@@ -282,7 +282,7 @@ fn normalize_operators(line: &str) -> String {
             }
 
             // Binary arithmetic operators
-            '+' | '*' | '/' | '%' | '^' => {
+            '+' | '*' | '/' | '%' | '^' | '@' => {
                 let is_unary = result.is_empty()
                     || result.ends_with('(')
                     || result.ends_with('[')
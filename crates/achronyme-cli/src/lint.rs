@@ -14,25 +14,17 @@ pub struct LintError {
 pub fn check_errors(source: &str) -> Vec<LintError> {
     let mut errors = Vec::new();
 
-    // Try to parse the code
-    match achronyme_parser::parse(source) {
-        Ok(_) => {
-            // No parse errors
-        }
-        Err(parse_error) => {
-            // Extract error information
-            let error_msg = parse_error.to_string();
-
-            // Try to extract line and column information from the error message
-            let (line, column) = extract_line_column_from_error(&error_msg, source);
-
-            errors.push(LintError {
-                line,
-                column,
-                message: error_msg.clone(),
-                severity: "error".to_string(),
-            });
-        }
+    // Every syntax error, not just the first: the recovering parser skips
+    // over each broken statement and keeps going
+    for error in achronyme_parser::parse_recovering(source).errors {
+        let before = &source[..error.span.start];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        errors.push(LintError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: format!("Syntax error: {}", error.message),
+            severity: "error".to_string(),
+        });
     }
 
     // Additional linting checks
@@ -41,19 +33,6 @@ pub fn check_errors(source: &str) -> Vec<LintError> {
     errors
 }
 
-/// Extract line and column from pest error message
-fn extract_line_column_from_error(_error_msg: &str, source: &str) -> (usize, usize) {
-    // Try to parse error message for line/column info
-    // Pest errors typically contain position information
-    // Format might be: " at line X, column Y" or similar
-
-    // Default: find the position of "EOI" or last character
-    let lines: Vec<&str> = source.lines().collect();
-
-    // If we can't parse the error, return the end of file
-    (lines.len().max(1), 1)
-}
-
 /// Perform additional linting checks
 fn additional_checks(source: &str, errors: &mut Vec<LintError>) {
     // Check for common issues
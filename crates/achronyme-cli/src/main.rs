@@ -50,6 +50,9 @@ enum Commands {
     Check {
         /// File to check
         file: String,
+        /// Also check type annotations statically
+        #[arg(long)]
+        types: bool,
     },
     /// Inspect compiled module (show statistics and metadata)
     Inspect {
@@ -83,10 +86,14 @@ enum Commands {
         #[arg(long)]
         json: bool,
     },
-    /// List symbols in a file
+    /// List symbols in a file, or the builtin functions
     Symbols {
         /// File to scan
-        file: String,
+        #[arg(required_unless_present = "builtins")]
+        file: Option<String>,
+        /// List the builtin functions with their signatures instead
+        #[arg(long, conflicts_with = "file")]
+        builtins: bool,
         /// Output as JSON
         #[arg(long)]
         json: bool,
@@ -111,12 +118,15 @@ async fn main() {
                 run_file(&file, debug).await
             }
             Commands::Eval { expression } => run_expression(&expression).await,
-            Commands::Check { file } => check_command(&file),
+            Commands::Check { file, types } => check_command(&file, types),
             Commands::Inspect { file, verbose } => inspect_command(&file, verbose),
             Commands::Disassemble { file } => disassemble_command(&file),
             Commands::Format { file, check, diff } => format_command(&file, check, diff),
             Commands::Lint { file, json } => lint_command(&file, json),
-            Commands::Symbols { file, json } => symbols_command(&file, json),
+            Commands::Symbols { file, json, .. } => match file {
+                Some(file) => symbols_command(&file, json),
+                None => builtin_symbols_command(json),
+            },
         }
         return;
     }
@@ -148,7 +158,7 @@ async fn main() {
     }
 }
 
-fn check_command(filename: &str) {
+fn check_command(filename: &str, types: bool) {
     let contents = match fs::read_to_string(filename) {
         Ok(contents) => contents,
         Err(err) => {
@@ -175,7 +185,7 @@ fn check_command(filename: &str) {
     match compiler.compile(&ast) {
         Ok(_module) => {
             println!("✓ Compilation OK");
-            println!("\nFile '{}' is ready to execute", filename);
+            print_warnings(&compiler);
         }
         Err(err) => {
             eprintln!("✗ Compilation error in '{}':", filename);
@@ -183,6 +193,48 @@ fn check_command(filename: &str) {
             std::process::exit(1);
         }
     }
+
+    // Step 3: Type check
+    if types {
+        check_types(filename, &contents);
+    }
+    println!("\nFile '{}' is ready to execute", filename);
+}
+
+fn print_warnings(compiler: &achronyme_vm::Compiler) {
+    for warning in compiler.warnings() {
+        eprintln!("warning: {}", warning);
+    }
+}
+
+fn check_types(filename: &str, contents: &str) {
+    let checked = achronyme_lsp_core::SemanticIndex::build(contents)
+        .and_then(|index| achronyme_lsp_core::TypeCheck::run(contents, &index));
+    let errors = match checked {
+        Ok(checked) => checked.errors().to_vec(),
+        Err(err) => {
+            eprintln!("✗ Type check failed in '{}':", filename);
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
+    if errors.is_empty() {
+        println!("✓ Types OK");
+        return;
+    }
+
+    eprintln!("✗ Type errors in '{}':", filename);
+    for error in errors {
+        let before = &contents[..error.span.start];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        eprintln!(
+            "  {}:{}: {}",
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+            error.message
+        );
+    }
+    std::process::exit(1);
 }
 
 fn inspect_command(filename: &str, verbose: bool) {
@@ -314,7 +366,10 @@ async fn run_file(filename: &str, debug_bytecode: bool) {
     // Compile
     let mut compiler = achronyme_vm::Compiler::new(filename.to_string());
     let module = match compiler.compile(&ast) {
-        Ok(module) => module,
+        Ok(module) => {
+            print_warnings(&compiler);
+            module
+        }
         Err(err) => {
             eprintln!("Compile error: {}", err);
             std::process::exit(1);
@@ -350,7 +405,10 @@ async fn run_expression(expr: &str) {
     // Compile
     let mut compiler = achronyme_vm::Compiler::new("<eval>".to_string());
     let module = match compiler.compile(&ast) {
-        Ok(module) => module,
+        Ok(module) => {
+            print_warnings(&compiler);
+            module
+        }
         Err(err) => {
             eprintln!("Compile error: {}", err);
             std::process::exit(1);
@@ -529,6 +587,11 @@ fn format_value(value: &achronyme_types::value::Value) -> String {
             let state = state_rc.read();
             format!("Signal({})", format_value(&state.value))
         }
+        Value::Effect(_) => "<effect>".to_string(),
+        Value::Task(_) => "<task>".to_string(),
+        Value::DataFrame(df) => df.to_string(),
+        Value::Bytes(bytes) => format!("<bytes: {}>", bytes.len()),
+        Value::Variant(variant) => variant.format_with(format_value),
     }
 }
 
@@ -679,3 +742,28 @@ fn symbols_command(filename: &str, json_output: bool) {
         }
     }
 }
+
+fn builtin_symbols_command(json_output: bool) {
+    let builtins = symbols::builtin_symbols();
+
+    if json_output {
+        match serde_json::to_string_pretty(&builtins) {
+            Ok(json) => println!("{}", json),
+            Err(err) => {
+                eprintln!("Error serializing JSON: {}", err);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    println!("Builtin functions ({}):", builtins.len());
+    let mut category = "";
+    for builtin in &builtins {
+        if builtin.category != category {
+            category = &builtin.category;
+            println!("\n{}", symbols::category_title(category));
+        }
+        println!("  {}", builtin.signature);
+    }
+}
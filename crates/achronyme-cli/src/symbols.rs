@@ -1,6 +1,8 @@
 /// Symbol extraction module for Achronyme code
 /// Extracts let/mut/type declarations and other symbols from AST
 use achronyme_parser::AstNode;
+use achronyme_vm::builtins::create_builtin_registry;
+use achronyme_vm::builtins::docs::Category;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -10,6 +12,46 @@ pub struct Symbol {
     pub line: usize,
 }
 
+/// A builtin function as listed by `symbols --builtins`
+#[derive(Debug, Serialize, Clone)]
+pub struct BuiltinSymbol {
+    pub name: String,
+    pub category: String,
+    pub signature: String,
+    pub summary: String,
+    pub params: Vec<String>,
+    pub returns: String,
+    pub examples: Vec<String>,
+}
+
+/// Every registered builtin with its documentation, grouped by category
+pub fn builtin_symbols() -> Vec<BuiltinSymbol> {
+    let registry = create_builtin_registry();
+    let mut docs: Vec<_> = registry.functions.iter().filter_map(|m| m.doc).collect();
+    docs.sort_by_key(|doc| doc.category);
+
+    docs.into_iter()
+        .map(|doc| BuiltinSymbol {
+            name: doc.name.to_string(),
+            category: doc.category.id().to_string(),
+            signature: doc.signature(),
+            summary: doc.summary.to_string(),
+            params: doc.params.iter().map(|p| p.label()).collect(),
+            returns: doc.returns.to_string(),
+            examples: doc.examples.iter().map(|e| e.to_string()).collect(),
+        })
+        .collect()
+}
+
+/// Title of a category id produced by `builtin_symbols`
+pub fn category_title(id: &str) -> &'static str {
+    Category::ALL
+        .iter()
+        .find(|c| c.id() == id)
+        .map(|c| c.title())
+        .unwrap_or("Other")
+}
+
 /// Extract symbols from parsed AST
 pub fn extract_symbols(ast: &[AstNode], source: &str) -> Vec<Symbol> {
     let mut symbols = Vec::new();
@@ -243,6 +285,8 @@ fn collect_symbols(node: &AstNode, symbols: &mut Vec<Symbol>, lines: &[&str]) {
 
         AstNode::Continue => {}
 
+        AstNode::Error { .. } => {}
+
         AstNode::InterpolatedString { parts } => {
             for part in parts {
                 match part {
@@ -277,6 +321,7 @@ fn collect_symbols(node: &AstNode, symbols: &mut Vec<Symbol>, lines: &[&str]) {
         AstNode::Import { .. } => {}
         AstNode::Export { .. } => {}
         AstNode::TypeAlias { .. } => {}
+        AstNode::DataType { .. } => {}
 
         // Base cases - no nested symbols
         AstNode::Number(_)
@@ -294,7 +339,8 @@ fn collect_symbols(node: &AstNode, symbols: &mut Vec<Symbol>, lines: &[&str]) {
 fn extract_names_from_pattern(pattern: &achronyme_parser::Pattern) -> Vec<String> {
     let mut names = Vec::new();
     match pattern {
-        achronyme_parser::Pattern::Record { fields } => {
+        achronyme_parser::Pattern::Record { fields }
+        | achronyme_parser::Pattern::Variant { fields, .. } => {
             for (field_name, _, _) in fields {
                 names.push(field_name.clone());
             }
@@ -8,7 +8,7 @@ use std::cell::RefCell;
 // SAFETY: This implies that the VM and the GUI render loop are running on the
 // same thread (which is true for LocalSet architecture).
 thread_local! {
-    static CURRENT_UI: RefCell<Option<*mut Ui>> = RefCell::new(None);
+    static CURRENT_UI: RefCell<Option<*mut Ui>> = const { RefCell::new(None) };
 }
 
 /// Executes a closure with the given UI context active.
@@ -38,8 +38,5 @@ where
 /// Retrieves the current active UI context.
 /// Returns None if called outside a render loop.
 pub fn get_ui<'a>() -> Option<&'a mut Ui> {
-    CURRENT_UI.with(|cell| match *cell.borrow() {
-        Some(ptr) => unsafe { Some(&mut *ptr) },
-        None => None,
-    })
+    CURRENT_UI.with(|cell| cell.borrow().map(|ptr| unsafe { &mut *ptr }))
 }
@@ -1,9 +1,21 @@
 use crate::bridge;
+use crate::harness;
 use crate::layout::{LayoutContext, SizingStrategy};
-use crate::style::StyleConfig;
+use crate::style::{self, StyleConfig};
+use achronyme_types::dataframe::{Cell, Column};
+use achronyme_types::sync::RwLockReadGuard;
+use achronyme_types::tensor::RealTensor;
 use achronyme_types::value::Value;
-use egui::{FontFamily, RichText};
+use egui::load::{SizedTexture, TexturePoll};
+use egui::{
+    Align2, Color32, FontFamily, Id, Rect, RichText, Sense, Stroke, TextStyle, TextureHandle,
+};
 use egui_plot::{Legend, Line, Plot, PlotPoints, Points};
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Renders a styled label: ui.label("Hello", "text-red-500 font-bold")
 pub fn label(text: &str, style: &Value) {
@@ -33,7 +45,8 @@ pub fn label(text: &str, style: &Value) {
             rich_text = rich_text.family(FontFamily::Monospace);
         }
 
-        ui.label(rich_text);
+        let response = ui.label(rich_text);
+        harness::record("label", Some(text), None, response.rect);
     }
 }
 
@@ -42,34 +55,38 @@ pub fn label(text: &str, style: &Value) {
 pub fn button(text: &str, style: &Value) -> bool {
     if let Some(ui) = bridge::get_ui() {
         let config = StyleConfig::from_value(style);
-
-        // Apply text styles to the button label
-        let mut rich_text = RichText::new(text);
-        if let Some(color) = config.effective_text_color() {
-            rich_text = rich_text.color(color);
-        }
-        if let Some(size) = config.font_size {
-            rich_text = rich_text.size(size);
-        }
-        if config.font_bold {
-            rich_text = rich_text.strong();
-        }
-        if config.font_italic {
-            rich_text = rich_text.italics();
-        }
-
-        let mut btn = egui::Button::new(rich_text);
+        let mut btn = egui::Button::new(button_text(text, &config));
 
         if let Some(bg) = config.background_color {
             btn = btn.fill(bg);
         }
 
-        ui.add(btn).clicked()
+        let response = ui.add(btn);
+        harness::record("button", Some(text), None, response.rect);
+        response.clicked()
     } else {
         false
     }
 }
 
+/// Apply text styles to a button label
+fn button_text(text: &str, config: &StyleConfig) -> RichText {
+    let mut rich_text = RichText::new(text);
+    if let Some(color) = config.effective_text_color() {
+        rich_text = rich_text.color(color);
+    }
+    if let Some(size) = config.font_size {
+        rich_text = rich_text.size(size);
+    }
+    if config.font_bold {
+        rich_text = rich_text.strong();
+    }
+    if config.font_italic {
+        rich_text = rich_text.italics();
+    }
+    rich_text
+}
+
 /// Renders a container (Box/Div): ui.box({ style: "...", children: () => { ... } })
 pub fn container<F>(style: &Value, children: F)
 where
@@ -77,6 +94,8 @@ where
 {
     if let Some(ui) = bridge::get_ui() {
         let config = StyleConfig::from_value(style);
+        harness::record("box", None, None, ui.available_rect_before_wrap());
+        let children = move || harness::nested(children);
 
         // Build context from current UI state for layout decisions
         let ctx = LayoutContext::from_ui(ui, false);
@@ -182,7 +201,10 @@ pub fn tabs(titles: &[String], current_index: usize, _style: &Value) -> Option<u
         let mut new_index = None;
         ui.horizontal(|ui| {
             for (i, title) in titles.iter().enumerate() {
-                if ui.selectable_label(current_index == i, title).clicked() {
+                let response = ui.selectable_label(current_index == i, title);
+                let selected = (current_index == i).then(|| "selected".to_string());
+                harness::record("tab", Some(title), selected, response.rect);
+                if response.clicked() {
                     new_index = Some(i);
                 }
             }
@@ -199,6 +221,12 @@ pub fn text_input(text: &mut String, style: &Value) -> bool {
         // TODO: Apply styles to text input (frame, text color, etc)
         // For now standard
         let response = ui.text_edit_singleline(text);
+        harness::record(
+            "text_input",
+            None,
+            Some(format!("{:?}", text)),
+            response.rect,
+        );
         return response.changed();
     }
     false
@@ -208,7 +236,14 @@ pub fn text_input(text: &mut String, style: &Value) -> bool {
 pub fn slider(value: &mut f64, min: f64, max: f64, style: &Value) -> bool {
     if let Some(ui) = bridge::get_ui() {
         let _config = StyleConfig::from_value(style);
+        let slider_width = ui.spacing().slider_width;
         let response = ui.add(egui::Slider::new(value, min..=max));
+        // The response also covers the value box; record just the rail
+        let rail = egui::Rect::from_min_size(
+            response.rect.min,
+            egui::vec2(slider_width, response.rect.height()),
+        );
+        harness::record_slider(*value, min, max, rail);
         return response.changed();
     }
     false
@@ -219,6 +254,12 @@ pub fn checkbox(checked: &mut bool, text: &str, _style: &Value) -> bool {
     if let Some(ui) = bridge::get_ui() {
         // TODO: Style support
         let response = ui.checkbox(checked, text);
+        harness::record(
+            "checkbox",
+            Some(text),
+            Some(checked.to_string()),
+            response.rect,
+        );
         return response.changed();
     }
     false
@@ -229,6 +270,12 @@ pub fn radio(selected: bool, text: &str, _style: &Value) -> bool {
     if let Some(ui) = bridge::get_ui() {
         // TODO: Style support
         let response = ui.radio(selected, text);
+        harness::record(
+            "radio",
+            Some(text),
+            Some(selected.to_string()),
+            response.rect,
+        );
         return response.clicked();
     }
     false
@@ -239,18 +286,23 @@ pub fn combobox(current_value: &mut String, options: &[String], _style: &Value)
     if let Some(ui) = bridge::get_ui() {
         // TODO: Style support
         let mut changed = false;
-        egui::ComboBox::from_id_salt(ui.next_auto_id())
+        let index = harness::record("combobox", None, None, egui::Rect::NOTHING);
+        let response = egui::ComboBox::from_id_salt(ui.next_auto_id())
             .selected_text(current_value.clone())
             .show_ui(ui, |ui| {
-                for opt in options {
-                    if ui
-                        .selectable_value(current_value, opt.clone(), opt.as_str())
-                        .changed()
-                    {
-                        changed = true;
+                harness::nested(|| {
+                    for opt in options {
+                        let response =
+                            ui.selectable_value(current_value, opt.clone(), opt.as_str());
+                        harness::record("option", Some(opt), None, response.rect);
+                        if response.changed() {
+                            changed = true;
+                        }
                     }
-                }
-            });
+                })
+            })
+            .response;
+        harness::complete(index, response.rect, Some(format!("{:?}", current_value)));
         return changed;
     }
     false
@@ -259,14 +311,17 @@ pub fn combobox(current_value: &mut String, options: &[String], _style: &Value)
 /// Renders a progress bar.
 pub fn progress_bar(progress: f32, _style: &Value) {
     if let Some(ui) = bridge::get_ui() {
-        ui.add(egui::ProgressBar::new(progress));
+        let response = ui.add(egui::ProgressBar::new(progress));
+        let value = harness::format_number(progress as f64);
+        harness::record("progress_bar", None, Some(value), response.rect);
     }
 }
 
 /// Renders a separator.
 pub fn separator(_style: &Value) {
     if let Some(ui) = bridge::get_ui() {
-        ui.separator();
+        let response = ui.separator();
+        harness::record("separator", None, None, response.rect);
     }
 }
 
@@ -283,9 +338,20 @@ where
     F: FnOnce(),
 {
     if let Some(ui) = bridge::get_ui() {
-        ui.collapsing(title, |ui| {
-            bridge::with_ui_context(ui, children);
+        let index = harness::record("collapsing", Some(title), None, egui::Rect::NOTHING);
+        let response = ui.collapsing(title, |ui| {
+            bridge::with_ui_context(ui, || harness::nested(children));
         });
+        let state = if response.body_response.is_some() {
+            "open"
+        } else {
+            "closed"
+        };
+        harness::complete(
+            index,
+            response.header_response.rect,
+            Some(state.to_string()),
+        );
     }
 }
 
@@ -295,12 +361,971 @@ where
     F: FnOnce(),
 {
     if let Some(ui) = bridge::get_ui() {
+        harness::record("scroll_area", None, None, ui.available_rect_before_wrap());
         egui::ScrollArea::vertical().show(ui, |ui| {
-            bridge::with_ui_context(ui, children);
+            bridge::with_ui_context(ui, || harness::nested(children));
+        });
+    }
+}
+
+// --- Tables ---
+
+/// A table cell. Numbers sort numerically and before text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableCell {
+    Number(f64),
+    Text(String),
+}
+
+impl TableCell {
+    fn from_value(value: &Value) -> Self {
+        match value {
+            Value::Number(n) => TableCell::Number(*n),
+            Value::String(s) => TableCell::Text(s.clone()),
+            Value::Boolean(b) => TableCell::Text(b.to_string()),
+            Value::Null => TableCell::Text(String::new()),
+            other => TableCell::Text(format!("{:?}", other)),
+        }
+    }
+
+    fn display(&self) -> String {
+        match self {
+            TableCell::Number(n) => harness::format_number(*n),
+            TableCell::Text(s) => s.clone(),
+        }
+    }
+
+    fn compare(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (TableCell::Number(a), TableCell::Number(b)) => a.total_cmp(b),
+            (TableCell::Number(_), TableCell::Text(_)) => Ordering::Less,
+            (TableCell::Text(_), TableCell::Number(_)) => Ordering::Greater,
+            (TableCell::Text(a), TableCell::Text(b)) => a.cmp(b),
+        }
+    }
+}
+
+/// Borrowed view of the data shown by `table`. Cells are read on demand,
+/// so only visible rows (and the sort column) are touched each frame.
+pub struct TableData<'a> {
+    columns: Vec<String>,
+    rows: TableRows<'a>,
+}
+
+enum TableRows<'a> {
+    Frame(Vec<&'a Column>, usize),
+    Records(RwLockReadGuard<'a, Vec<Value>>),
+}
+
+impl<'a> TableData<'a> {
+    /// View a DataFrame or a Vector of Records. `columns` picks and orders
+    /// the columns; otherwise a DataFrame keeps its order and records show
+    /// all their fields sorted by name.
+    pub fn from_value(data: &'a Value, columns: Option<Vec<String>>) -> Result<Self, String> {
+        match data {
+            Value::DataFrame(df) => {
+                let columns = columns.unwrap_or_else(|| df.names().to_vec());
+                let cols = columns
+                    .iter()
+                    .map(|name| {
+                        df.column(name)
+                            .ok_or_else(|| format!("Unknown column '{}'", name))
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(Self {
+                    columns,
+                    rows: TableRows::Frame(cols, df.nrows()),
+                })
+            }
+            Value::Vector(v) => {
+                let rows = v.read();
+                if let Some(other) = rows.iter().find(|row| !matches!(row, Value::Record(_))) {
+                    return Err(format!("table rows must be Records, got {:?}", other));
+                }
+                let mut names = BTreeSet::new();
+                for row in rows.iter() {
+                    if let Value::Record(r) = row {
+                        names.extend(r.read().keys().cloned());
+                    }
+                }
+                let columns = match columns {
+                    Some(columns) => {
+                        if let Some(name) = columns.iter().find(|c| !names.contains(*c)) {
+                            return Err(format!("Unknown column '{}'", name));
+                        }
+                        columns
+                    }
+                    None => names.into_iter().collect(),
+                };
+                Ok(Self {
+                    columns,
+                    rows: TableRows::Records(rows),
+                })
+            }
+            other => Err(format!(
+                "expected a DataFrame or a Vector of Records, got {:?}",
+                other
+            )),
+        }
+    }
+
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.rows {
+            TableRows::Frame(_, n) => *n,
+            TableRows::Records(rows) => rows.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn cell(&self, row: usize, col: usize) -> TableCell {
+        match &self.rows {
+            TableRows::Frame(cols, _) => match cols[col].cell(row) {
+                Cell::Number(n) => TableCell::Number(n),
+                Cell::String(s) => TableCell::Text(s),
+            },
+            TableRows::Records(rows) => match &rows[row] {
+                Value::Record(r) => r
+                    .read()
+                    .get(&self.columns[col])
+                    .map(TableCell::from_value)
+                    .unwrap_or(TableCell::Text(String::new())),
+                _ => TableCell::Text(String::new()),
+            },
+        }
+    }
+
+    /// Row indices in display order
+    fn order(&self, sort: SortState) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        if let Some(col) = sort.column.filter(|c| *c < self.columns.len()) {
+            let keys: Vec<TableCell> = order.iter().map(|&row| self.cell(row, col)).collect();
+            order.sort_by(|&a, &b| {
+                let ord = keys[a].compare(&keys[b]);
+                if sort.descending {
+                    ord.reverse()
+                } else {
+                    ord
+                }
+            });
+        }
+        order
+    }
+}
+
+/// Which column a table is sorted by, kept in egui memory per table
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct SortState {
+    column: Option<usize>,
+    descending: bool,
+}
+
+/// Renders a table with a sortable header (click a column to sort by it,
+/// again to reverse) and virtualized rows.
+/// Returns the index of the row clicked this frame, in `data` order.
+pub fn table(data: &TableData, style: &Value) -> Option<usize> {
+    let ui = bridge::get_ui()?;
+    let config = StyleConfig::from_value(style);
+    let id = ui.next_auto_id();
+    let mut sort = ui.data(|d| d.get_temp::<SortState>(id)).unwrap_or_default();
+
+    let width = match config.width {
+        Some(w) if w.is_finite() => w,
+        _ => ui.available_width(),
+    };
+    let height = match config.height {
+        Some(h) if h.is_finite() => h,
+        Some(_) => ui.available_height(),
+        None => 300.0,
+    };
+    let columns = data.columns();
+    let col_width = width / columns.len().max(1) as f32;
+    let row_height = ui.text_style_height(&TextStyle::Body) + 6.0;
+    let font = TextStyle::Body.resolve(ui.style());
+    let text_color = config
+        .effective_text_color()
+        .unwrap_or_else(|| ui.visuals().text_color());
+    let cell_rect = |row: Rect, col: usize| {
+        Rect::from_min_size(
+            row.min + egui::vec2(col as f32 * col_width, 0.0),
+            egui::vec2(col_width, row_height),
+        )
+    };
+
+    let index = harness::record(
+        "table",
+        None,
+        Some(format!("{} rows", data.len())),
+        Rect::NOTHING,
+    );
+    let mut clicked = None;
+    let response = harness::nested(|| {
+        ui.vertical(|ui| {
+            let (header, _) = ui.allocate_exact_size(egui::vec2(width, row_height), Sense::hover());
+            let header_fill = config
+                .background_color
+                .unwrap_or(ui.visuals().widgets.noninteractive.bg_fill);
+            ui.painter().rect_filled(header, 2.0, header_fill);
+            for (col, name) in columns.iter().enumerate() {
+                let rect = cell_rect(header, col);
+                let response = ui.interact(rect, id.with(("header", col)), Sense::click());
+                if response.clicked() {
+                    sort = SortState {
+                        column: Some(col),
+                        descending: sort.column == Some(col) && !sort.descending,
+                    };
+                }
+                let painter = ui.painter().with_clip_rect(rect);
+                painter.text(
+                    rect.left_center() + egui::vec2(6.0, 0.0),
+                    Align2::LEFT_CENTER,
+                    name,
+                    font.clone(),
+                    ui.visuals().strong_text_color(),
+                );
+                let mut direction = None;
+                if sort.column == Some(col) {
+                    let (tip, base) = if sort.descending {
+                        (3.0, -3.0)
+                    } else {
+                        (-3.0, 3.0)
+                    };
+                    let c = rect.right_center() - egui::vec2(10.0, 0.0);
+                    painter.add(egui::Shape::convex_polygon(
+                        vec![
+                            c + egui::vec2(0.0, tip),
+                            c + egui::vec2(4.0, base),
+                            c + egui::vec2(-4.0, base),
+                        ],
+                        text_color,
+                        Stroke::NONE,
+                    ));
+                    direction = Some(if sort.descending { "desc" } else { "asc" }.to_string());
+                }
+                harness::record("column", Some(name), direction, rect);
+            }
+
+            let order = data.order(sort);
+            egui::ScrollArea::vertical()
+                .id_salt(id.with("rows"))
+                .max_height(height)
+                .auto_shrink([false, true])
+                .show_rows(ui, row_height, order.len(), |ui, range| {
+                    for position in range {
+                        let row = order[position];
+                        let (rect, response) =
+                            ui.allocate_exact_size(egui::vec2(width, row_height), Sense::click());
+                        if response.hovered() {
+                            let fill = ui.visuals().widgets.hovered.weak_bg_fill;
+                            ui.painter().rect_filled(rect, 2.0, fill);
+                        } else if position % 2 == 1 {
+                            ui.painter()
+                                .rect_filled(rect, 0.0, ui.visuals().faint_bg_color);
+                        }
+                        let mut cells = Vec::with_capacity(columns.len());
+                        for col in 0..columns.len() {
+                            let cell = data.cell(row, col);
+                            let text = cell.display();
+                            let rect = cell_rect(rect, col);
+                            let (anchor, pos) = match cell {
+                                TableCell::Number(_) => (
+                                    Align2::RIGHT_CENTER,
+                                    rect.right_center() - egui::vec2(6.0, 0.0),
+                                ),
+                                TableCell::Text(_) => (
+                                    Align2::LEFT_CENTER,
+                                    rect.left_center() + egui::vec2(6.0, 0.0),
+                                ),
+                            };
+                            ui.painter().with_clip_rect(rect).text(
+                                pos,
+                                anchor,
+                                &text,
+                                font.clone(),
+                                text_color,
+                            );
+                            cells.push(text);
+                        }
+                        harness::record("row", Some(&cells.join(" | ")), None, rect);
+                        if response.clicked() {
+                            clicked = Some(row);
+                        }
+                    }
+                });
+        })
+        .response
+    });
+
+    ui.data_mut(|d| d.insert_temp(id, sort));
+    harness::complete(index, response.rect, Some(format!("{} rows", data.len())));
+    clicked
+}
+
+// --- Editing ---
+
+/// Renders a multiline monospace editor (Tab indents).
+/// `h-[..]` caps its height and scrolls. Returns true if changed.
+pub fn code_editor(text: &mut String, style: &Value) -> bool {
+    if let Some(ui) = bridge::get_ui() {
+        let config = StyleConfig::from_value(style);
+        let width = match config.width {
+            Some(w) if w.is_finite() => w,
+            _ => ui.available_width(),
+        };
+        let line_height = ui.text_style_height(&TextStyle::Monospace);
+        let max_height = config.height.filter(|h| h.is_finite());
+        let rows = max_height.map_or(10, |h| ((h / line_height) as usize).max(1));
+
+        let mut edit = egui::TextEdit::multiline(text)
+            .code_editor()
+            .desired_width(width)
+            .desired_rows(rows)
+            .text_color_opt(config.effective_text_color());
+        if let Some(bg) = config.background_color {
+            edit = edit.background_color(bg);
+        }
+        let response = match max_height {
+            Some(h) => {
+                egui::ScrollArea::vertical()
+                    .id_salt(ui.next_auto_id())
+                    .max_height(h)
+                    .show(ui, |ui| ui.add(edit))
+                    .inner
+            }
+            None => ui.add(edit),
+        };
+        harness::record(
+            "code_editor",
+            None,
+            Some(format!("{:?}", text)),
+            response.rect,
+        );
+        return response.changed();
+    }
+    false
+}
+
+/// Renders a color swatch that opens a color picker. `hex` holds the color
+/// as `#rrggbb` (`#rrggbbaa` when translucent). Returns true if changed.
+pub fn color_picker(hex: &mut String, _style: &Value) -> bool {
+    if let Some(ui) = bridge::get_ui() {
+        let mut color = if hex.is_empty() {
+            Color32::WHITE
+        } else {
+            style::parse_hex_color(hex)
+        };
+        let response = ui.color_edit_button_srgba(&mut color);
+        let changed = response.changed();
+        if changed {
+            *hex = color_to_hex(color);
+        }
+        harness::record(
+            "color_picker",
+            None,
+            Some(format!("{:?}", hex)),
+            response.rect,
+        );
+        return changed;
+    }
+    false
+}
+
+fn color_to_hex(color: Color32) -> String {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    if a == 255 {
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    } else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+    }
+}
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Renders a button showing a `YYYY-MM-DD` date that opens a calendar.
+/// Returns true when a day was picked.
+pub fn date_picker(date: &mut String, style: &Value) -> bool {
+    let Some(ui) = bridge::get_ui() else {
+        return false;
+    };
+    let config = StyleConfig::from_value(style);
+    let current = parse_date(date);
+    let text = if current.is_some() {
+        date.clone()
+    } else {
+        "Pick a date".to_string()
+    };
+
+    let index = harness::record("date_picker", None, None, Rect::NOTHING);
+    let response = ui.add(egui::Button::new(button_text(&text, &config)));
+    let popup_id = response.id.with("calendar");
+    let month_id = popup_id.with("month");
+    if response.clicked() {
+        ui.memory_mut(|m| m.toggle_popup(popup_id));
+        let (year, month, _) = current.unwrap_or_else(today);
+        ui.data_mut(|d| d.insert_temp(month_id, (year, month)));
+    }
+
+    let mut changed = false;
+    harness::nested(|| {
+        egui::popup::popup_below_widget(
+            ui,
+            popup_id,
+            &response,
+            egui::PopupCloseBehavior::CloseOnClickOutside,
+            |ui| {
+                ui.set_min_width(220.0);
+                let (mut year, mut month) = ui
+                    .data(|d| d.get_temp::<(i32, u32)>(month_id))
+                    .unwrap_or_else(|| {
+                        let (y, m, _) = today();
+                        (y, m)
+                    });
+                ui.horizontal(|ui| {
+                    let prev = ui.button("<");
+                    harness::record("button", Some("<"), None, prev.rect);
+                    let title = format!("{} {}", MONTHS[month as usize - 1], year);
+                    let label = ui.label(RichText::new(&title).strong());
+                    harness::record("label", Some(&title), None, label.rect);
+                    let next = ui.button(">");
+                    harness::record("button", Some(">"), None, next.rect);
+                    if prev.clicked() {
+                        (year, month) = if month == 1 {
+                            (year - 1, 12)
+                        } else {
+                            (year, month - 1)
+                        };
+                    }
+                    if next.clicked() {
+                        (year, month) = if month == 12 {
+                            (year + 1, 1)
+                        } else {
+                            (year, month + 1)
+                        };
+                    }
+                });
+                ui.data_mut(|d| d.insert_temp(month_id, (year, month)));
+
+                egui::Grid::new(popup_id.with("days")).show(ui, |ui| {
+                    for name in ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"] {
+                        ui.label(RichText::new(name).weak());
+                    }
+                    ui.end_row();
+                    let first = weekday(year, month, 1);
+                    for _ in 0..first {
+                        ui.label("");
+                    }
+                    for day in 1..=days_in_month(year, month) {
+                        let selected = current == Some((year, month, day));
+                        let response = ui.selectable_label(selected, day.to_string());
+                        harness::record("day", Some(&day.to_string()), None, response.rect);
+                        if response.clicked() {
+                            *date = format!("{:04}-{:02}-{:02}", year, month, day);
+                            changed = true;
+                            ui.memory_mut(|m| m.close_popup());
+                        }
+                        if (first + day).is_multiple_of(7) {
+                            ui.end_row();
+                        }
+                    }
+                });
+            },
+        );
+    });
+    harness::complete(index, response.rect, Some(format!("{:?}", date)));
+    changed
+}
+
+/// Parse a `YYYY-MM-DD` date
+fn parse_date(text: &str) -> Option<(i32, u32, u32)> {
+    let mut parts = text.trim().splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    ((1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day))
+        .then_some((year, month, day))
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        _ if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        _ => 28,
+    }
+}
+
+/// Days since 1970-01-01 in the proleptic Gregorian calendar
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year } as i64;
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Inverse of `days_from_civil`
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+    (year, month, day)
+}
+
+/// Day of the week, Monday = 0
+fn weekday(year: i32, month: u32, day: u32) -> u32 {
+    // 1970-01-01 was a Thursday
+    (days_from_civil(year, month, day) + 3).rem_euclid(7) as u32
+}
+
+fn today() -> (i32, u32, u32) {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    civil_from_days((secs / 86_400) as i64)
+}
+
+/// Browsing state of an open file picker
+#[derive(Clone)]
+struct FileBrowser {
+    dir: PathBuf,
+}
+
+/// Renders a button that opens an in-app file browser dialog.
+/// Returns true when a file was picked (its path is stored in `path`).
+pub fn file_picker(path: &mut String, style: &Value) -> bool {
+    let Some(ui) = bridge::get_ui() else {
+        return false;
+    };
+    let config = StyleConfig::from_value(style);
+    let id = ui.next_auto_id();
+    let text = Path::new(path.as_str())
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "Choose file...".to_string());
+
+    let index = harness::record("file_picker", None, None, Rect::NOTHING);
+    let response = ui.add(egui::Button::new(button_text(&text, &config)));
+    let mut browser = ui.data(|d| d.get_temp::<FileBrowser>(id));
+    if response.clicked() {
+        let dir = Path::new(path.as_str())
+            .parent()
+            .filter(|dir| dir.is_dir())
+            .map(Path::to_path_buf)
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_default();
+        browser = Some(FileBrowser { dir });
+    }
+
+    let mut picked = false;
+    if let Some(state) = &mut browser {
+        let mut close = false;
+        let modal = egui::Modal::new(id.with("browser")).show(ui.ctx(), |ui| {
+            ui.set_width(420.0);
+            ui.label(RichText::new(state.dir.display().to_string()).monospace());
+            ui.separator();
+            harness::nested(|| {
+                let mut next_dir = None;
+                egui::ScrollArea::vertical()
+                    .max_height(320.0)
+                    .show(ui, |ui| {
+                        if let Some(parent) = state.dir.parent() {
+                            let response = ui.selectable_label(false, "..");
+                            harness::record("file", Some(".."), None, response.rect);
+                            if response.clicked() {
+                                next_dir = Some(parent.to_path_buf());
+                            }
+                        }
+                        for (name, is_dir) in list_dir(&state.dir) {
+                            let label = if is_dir {
+                                format!("{}/", name)
+                            } else {
+                                name.clone()
+                            };
+                            let response = ui.selectable_label(false, &label);
+                            harness::record("file", Some(&label), None, response.rect);
+                            if response.clicked() {
+                                let target = state.dir.join(&name);
+                                if is_dir {
+                                    next_dir = Some(target);
+                                } else {
+                                    *path = target.display().to_string();
+                                    picked = true;
+                                }
+                            }
+                        }
+                    });
+                if let Some(dir) = next_dir {
+                    state.dir = dir;
+                }
+                ui.separator();
+                let cancel = ui.button("Cancel");
+                harness::record("button", Some("Cancel"), None, cancel.rect);
+                close = cancel.clicked();
+            });
+        });
+        if close || picked || modal.should_close() {
+            browser = None;
+        }
+    }
+    match browser {
+        Some(state) => ui.data_mut(|d| d.insert_temp(id, state)),
+        None => ui.data_mut(|d| d.remove::<FileBrowser>(id)),
+    }
+    harness::complete(index, response.rect, Some(format!("{:?}", path)));
+    picked
+}
+
+/// Directory entries as `(name, is_dir)`, folders first, hidden files skipped
+fn list_dir(dir: &Path) -> Vec<(String, bool)> {
+    let mut entries: Vec<(String, bool)> = std::fs::read_dir(dir)
+        .map(|read| {
+            read.flatten()
+                .map(|entry| {
+                    let name = entry.file_name().to_string_lossy().into_owned();
+                    (name, entry.path().is_dir())
+                })
+                .filter(|(name, _)| !name.starts_with('.'))
+                .collect()
+        })
+        .unwrap_or_default();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    entries
+}
+
+// --- Images ---
+
+/// Pixels for `image`: a PNG, JPEG, WebP, GIF or SVG file, or a Tensor of
+/// values in 0..1 shaped `[h, w]` (grayscale), `[h, w, 3]` (RGB) or
+/// `[h, w, 4]` (RGBA)
+pub enum ImageSource<'a> {
+    File(&'a str),
+    Tensor(&'a RealTensor),
+}
+
+/// Identifies a tensor image's pixels, so its texture is only re-uploaded
+/// when the shape or a value changes
+fn tensor_fingerprint(t: &RealTensor) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.shape().hash(&mut hasher);
+    for v in t.data() {
+        v.to_bits().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Texture for a file image, decoded by the egui_extras loaders. The file
+/// is read on the first frame and again when its modification time changes,
+/// so the image is ready the frame it is first shown.
+fn file_texture(ui: &egui::Ui, id: Id, path: &str) -> Result<SizedTexture, String> {
+    let uri = format!("bytes://{}", path);
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let seen = ui.data(|d| d.get_temp::<Option<SystemTime>>(id));
+    if seen != Some(modified) {
+        let bytes =
+            std::fs::read(path).map_err(|e| format!("cannot load image '{}': {}", path, e))?;
+        ui.ctx().forget_image(&uri);
+        ui.ctx().include_bytes(uri.clone(), bytes);
+        ui.data_mut(|d| d.insert_temp(id, modified));
+    }
+
+    let image = egui::Image::from_uri(&uri).fit_to_original_size(1.0);
+    match image.load_for_size(ui.ctx(), ui.available_size()) {
+        Ok(TexturePoll::Ready { texture }) => Ok(texture),
+        Ok(TexturePoll::Pending { .. }) => Err(format!("cannot load image '{}'", path)),
+        Err(e) => Err(format!("cannot load image '{}': {}", path, e)),
+    }
+}
+
+/// Texture for a tensor image, re-uploaded only when its pixels change
+fn tensor_texture(ui: &egui::Ui, id: Id, t: &RealTensor) -> Result<SizedTexture, String> {
+    let fingerprint = tensor_fingerprint(t);
+    let cached = ui.data(|d| d.get_temp::<(u64, TextureHandle)>(id));
+    let texture = match cached {
+        Some((key, texture)) if key == fingerprint => texture,
+        cached => {
+            let pixels = tensor_image(t)?;
+            let texture = match cached {
+                Some((_, mut texture)) => {
+                    texture.set(pixels, egui::TextureOptions::LINEAR);
+                    texture
+                }
+                None => {
+                    ui.ctx()
+                        .load_texture("achronyme-image", pixels, egui::TextureOptions::LINEAR)
+                }
+            };
+            ui.data_mut(|d| d.insert_temp(id, (fingerprint, texture.clone())));
+            texture
+        }
+    };
+    Ok(SizedTexture::from_handle(&texture))
+}
+
+fn tensor_image(t: &RealTensor) -> Result<egui::ColorImage, String> {
+    let (h, w, channels) = match t.shape() {
+        [h, w] => (*h, *w, 1),
+        [h, w, c @ (3 | 4)] => (*h, *w, *c),
+        shape => {
+            return Err(format!(
+                "image tensor must be [h, w], [h, w, 3] or [h, w, 4], got {:?}",
+                shape
+            ))
+        }
+    };
+    let byte = |v: f64| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let pixels = t
+        .data()
+        .chunks_exact(channels)
+        .map(|px| match px {
+            [v] => Color32::from_gray(byte(*v)),
+            [r, g, b] => Color32::from_rgb(byte(*r), byte(*g), byte(*b)),
+            [r, g, b, a] => Color32::from_rgba_unmultiplied(byte(*r), byte(*g), byte(*b), byte(*a)),
+            _ => unreachable!("chunks have 1, 3 or 4 channels"),
+        })
+        .collect();
+    Ok(egui::ColorImage {
+        size: [w, h],
+        pixels,
+    })
+}
+
+/// Renders an image. `w-[..]`/`h-[..]` size it (keeping the aspect ratio
+/// when only one is given); otherwise it is drawn at its natural size,
+/// shrunk to fit the available width.
+pub fn image(source: &ImageSource, style: &Value) -> Result<(), String> {
+    let Some(ui) = bridge::get_ui() else {
+        return Ok(());
+    };
+    let config = StyleConfig::from_value(style);
+
+    // One texture per widget, replaced when the pixels change
+    let id = ui.next_auto_id();
+    let texture = match source {
+        ImageSource::File(path) => file_texture(ui, id, path)?,
+        ImageSource::Tensor(t) => tensor_texture(ui, id, t)?,
+    };
+
+    let natural = texture.size;
+    let aspect = natural.x / natural.y.max(1.0);
+    let available = ui.available_width();
+    let fixed = |v: Option<f32>, full: f32| v.map(|v| if v.is_finite() { v } else { full });
+    let size = match (
+        fixed(config.width, available),
+        fixed(config.height, ui.available_height()),
+    ) {
+        (Some(w), Some(h)) => egui::vec2(w, h),
+        (Some(w), None) => egui::vec2(w, w / aspect),
+        (None, Some(h)) => egui::vec2(h * aspect, h),
+        (None, None) if natural.x > available => egui::vec2(available, available / aspect),
+        (None, None) => natural,
+    };
+
+    let response = ui.add(egui::Image::new((texture.id, size)).rounding(config.rounding));
+    let label = match source {
+        ImageSource::File(path) => Some(*path),
+        ImageSource::Tensor(_) => None,
+    };
+    let value = format!("{}x{}", natural.x as usize, natural.y as usize);
+    harness::record("image", label, Some(value), response.rect);
+    Ok(())
+}
+
+// --- Menus & dialogs ---
+
+/// Renders a menu bar; `children` adds `menu`s to it.
+pub fn menu_bar<F>(_style: &Value, children: F)
+where
+    F: FnOnce(),
+{
+    if let Some(ui) = bridge::get_ui() {
+        let index = harness::record("menu_bar", None, None, Rect::NOTHING);
+        let response = egui::menu::bar(ui, |ui| {
+            bridge::with_ui_context(ui, || harness::nested(children));
+        })
+        .response;
+        harness::complete(index, response.rect, None);
+    }
+}
+
+/// Renders a drop-down menu; `children` runs while it is open.
+pub fn menu<F>(title: &str, _style: &Value, children: F)
+where
+    F: FnOnce(),
+{
+    if let Some(ui) = bridge::get_ui() {
+        let index = harness::record("menu", Some(title), None, Rect::NOTHING);
+        let response = ui.menu_button(title, |ui| {
+            bridge::with_ui_context(ui, || harness::nested(children));
+        });
+        let state = if response.inner.is_some() {
+            "open"
+        } else {
+            "closed"
+        };
+        harness::complete(index, response.response.rect, Some(state.to_string()));
+    }
+}
+
+/// Renders a menu entry. Returns true if clicked; clicking closes the menu.
+pub fn menu_item(text: &str, style: &Value) -> bool {
+    if let Some(ui) = bridge::get_ui() {
+        let config = StyleConfig::from_value(style);
+        let response = ui.button(button_text(text, &config));
+        harness::record("menu_item", Some(text), None, response.rect);
+        if response.clicked() {
+            ui.close_menu();
+            return true;
+        }
+    }
+    false
+}
+
+/// Renders a modal dialog while `open` is true, blocking the rest of the UI.
+/// Escape or a click outside closes it by setting `open` to false.
+/// Returns true if it was closed this way.
+pub fn modal<F>(open: &mut bool, title: &str, style: &Value, children: F) -> bool
+where
+    F: FnOnce(),
+{
+    let Some(ui) = bridge::get_ui() else {
+        return false;
+    };
+    if !*open {
+        return false;
+    }
+    let config = StyleConfig::from_value(style);
+
+    let index = harness::record("modal", Some(title), None, Rect::NOTHING);
+    let mut modal = egui::Modal::new(ui.id().with(("achronyme_modal", title)));
+    if config.background_color.is_some() {
+        modal = modal.frame(config.to_frame());
+    }
+    let response = modal.show(ui.ctx(), |ui| {
+        if let Some(w) = config.width.filter(|w| w.is_finite()) {
+            ui.set_width(w);
+        }
+        if !title.is_empty() {
+            ui.heading(title);
+            ui.add_space(4.0);
+        }
+        bridge::with_ui_context(ui, || harness::nested(children));
+    });
+    harness::complete(index, response.response.rect, None);
+
+    if response.should_close() {
+        *open = false;
+        return true;
+    }
+    false
+}
+
+// --- Toasts ---
+
+/// How long a toast stays on screen
+const TOAST_SECONDS: f64 = 4.0;
+
+#[derive(Clone)]
+struct Toast {
+    message: String,
+    kind: String,
+    expires: f64,
+}
+
+fn toasts_id() -> Id {
+    Id::new("achronyme_toasts")
+}
+
+/// Queues a notification shown in the top-right corner for a few seconds.
+/// `kind` is "info" (default), "success", "warning" or "error".
+pub fn toast(message: &str, kind: &str) {
+    if let Some(ui) = bridge::get_ui() {
+        let now = ui.input(|i| i.time);
+        let kind = if kind.is_empty() { "info" } else { kind };
+        ui.ctx().data_mut(|d| {
+            d.get_temp_mut_or_default::<Vec<Toast>>(toasts_id())
+                .push(Toast {
+                    message: message.to_string(),
+                    kind: kind.to_string(),
+                    expires: now + TOAST_SECONDS,
+                })
         });
     }
 }
 
+/// Draws the queued toasts and drops expired ones. Runs once per frame,
+/// after the render function.
+pub(crate) fn show_toasts(ctx: &egui::Context) {
+    let now = ctx.input(|i| i.time);
+    let mut toasts: Vec<Toast> = ctx.data(|d| d.get_temp(toasts_id())).unwrap_or_default();
+    toasts.retain(|t| t.expires > now);
+    if toasts.is_empty() {
+        ctx.data_mut(|d| d.remove::<Vec<Toast>>(toasts_id()));
+        return;
+    }
+
+    egui::Area::new(Id::new("achronyme_toast_area"))
+        .order(egui::Order::Foreground)
+        .anchor(Align2::RIGHT_TOP, egui::vec2(-12.0, 12.0))
+        .show(ctx, |ui| {
+            for toast in &toasts {
+                let accent = match toast.kind.as_str() {
+                    "success" => Color32::from_rgb(60, 180, 90),
+                    "warning" => Color32::from_rgb(230, 170, 40),
+                    "error" => Color32::from_rgb(220, 70, 70),
+                    _ => Color32::from_rgb(80, 140, 230),
+                };
+                let response = egui::Frame::popup(ui.style())
+                    .stroke(Stroke::new(1.0, accent))
+                    .show(ui, |ui| {
+                        ui.set_max_width(320.0);
+                        ui.label(&toast.message);
+                    })
+                    .response;
+                harness::record(
+                    "toast",
+                    Some(&toast.message),
+                    Some(toast.kind.clone()),
+                    response.rect,
+                );
+            }
+        });
+
+    // Wake up again when the next one expires
+    let next = toasts
+        .iter()
+        .map(|t| t.expires)
+        .fold(f64::INFINITY, f64::min);
+    ctx.request_repaint_after(Duration::from_secs_f64((next - now).max(0.0)));
+    ctx.data_mut(|d| d.insert_temp(toasts_id(), toasts));
+}
+
 /// Renders a high-performance plot
 pub fn plot(title: &str, options: &Value) {
     if let Some(ui) = bridge::get_ui() {
@@ -324,6 +1349,7 @@ pub fn plot(title: &str, options: &Value) {
             if let Some(Value::Vector(series_vec)) = r.get("series") {
                 let series_list = series_vec.read();
 
+                harness::record("plot", Some(title), None, ui.available_rect_before_wrap());
                 plot.height(height).show(ui, |plot_ui| {
                     for series_val in series_list.iter() {
                         if let Value::Record(s) = series_val {
@@ -352,9 +1378,9 @@ pub fn plot(title: &str, options: &Value) {
                                     .or_else(|| s.get("mode"));
 
                                 let type_str = type_val
-                                    .and_then(|v| match v {
-                                        Value::String(t) => Some(t.as_str()),
-                                        _ => Some("line"),
+                                    .map(|v| match v {
+                                        Value::String(t) => t.as_str(),
+                                        _ => "line",
                                     })
                                     .unwrap_or("line");
 
@@ -386,30 +1412,35 @@ pub fn plot(title: &str, options: &Value) {
             }
         }
 
+        harness::record("plot", Some(title), None, ui.available_rect_before_wrap());
         plot.height(height).show(ui, |_| {});
     }
 }
 
 /// Optimized extraction of points from Achronyme values
 fn extract_plot_points(data: &Value) -> PlotPoints {
+    PlotPoints::new(extract_points(data))
+}
+
+/// Extract `[x, y]` pairs from a Tensor (`[n, 2]` or rank 1) or a Vector
+/// (of pairs, or of numbers plotted against their index)
+pub(crate) fn extract_points(data: &Value) -> Vec<[f64; 2]> {
     // Case 1: Tensor (Fast Path)
     if let Value::Tensor(t) = data {
         let shape = t.shape();
         let raw_data = t.data();
 
         if shape.len() == 2 && shape[1] == 2 {
-            let points: Vec<[f64; 2]> = raw_data
+            return raw_data
                 .chunks_exact(2)
                 .map(|chunk| [chunk[0], chunk[1]])
                 .collect();
-            return PlotPoints::new(points);
         } else if shape.len() == 1 {
-            let points: Vec<[f64; 2]> = raw_data
+            return raw_data
                 .iter()
                 .enumerate()
                 .map(|(i, &y)| [i as f64, y])
                 .collect();
-            return PlotPoints::new(points);
         }
     }
 
@@ -418,7 +1449,7 @@ fn extract_plot_points(data: &Value) -> PlotPoints {
         let v = v_rc.read();
         if let Some(first) = v.first() {
             if let Value::Vector(_) = first {
-                let points: Vec<[f64; 2]> = v
+                return v
                     .iter()
                     .filter_map(|item| {
                         if let Value::Vector(pair) = item {
@@ -432,9 +1463,8 @@ fn extract_plot_points(data: &Value) -> PlotPoints {
                         None
                     })
                     .collect();
-                return PlotPoints::new(points);
             } else if let Value::Number(_) = first {
-                let points: Vec<[f64; 2]> = v
+                return v
                     .iter()
                     .enumerate()
                     .filter_map(|(i, item)| {
@@ -445,10 +1475,66 @@ fn extract_plot_points(data: &Value) -> PlotPoints {
                         }
                     })
                     .collect();
-                return PlotPoints::new(points);
             }
         }
     }
 
-    PlotPoints::new(vec![])
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_civil_day_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        for days in [-719_468, -1, 0, 11_016, 19_782, 60_000] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+        // 2024-02-01 was a Thursday
+        assert_eq!(weekday(2024, 2, 1), 3);
+    }
+
+    #[test]
+    fn test_parse_date_checks_month_length() {
+        assert_eq!(parse_date("2024-02-29"), Some((2024, 2, 29)));
+        assert_eq!(parse_date("2023-02-29"), None);
+        assert_eq!(parse_date("2024-13-01"), None);
+        assert_eq!(parse_date("soon"), None);
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+    }
+
+    #[test]
+    fn test_tensor_image_channels() {
+        let grey = RealTensor::new(vec![0.0, 1.0, 2.0, -1.0], vec![2, 2]).unwrap();
+        let image = tensor_image(&grey).unwrap();
+        assert_eq!(image.size, [2, 2]);
+        assert_eq!(image.pixels[1], Color32::WHITE);
+        assert_eq!(image.pixels[3], Color32::BLACK);
+
+        let rgb = RealTensor::new(vec![1.0, 0.0, 0.0], vec![1, 1, 3]).unwrap();
+        assert_eq!(tensor_image(&rgb).unwrap().pixels[0], Color32::RED);
+
+        let flat = RealTensor::new(vec![0.0; 3], vec![3]).unwrap();
+        assert!(tensor_image(&flat).is_err());
+    }
+
+    #[test]
+    fn test_cells_sort_numbers_before_text() {
+        let mut cells = [
+            TableCell::Text("b".into()),
+            TableCell::Number(10.0),
+            TableCell::Text("a".into()),
+            TableCell::Number(-2.0),
+        ];
+        cells.sort_by(TableCell::compare);
+        assert_eq!(
+            cells.iter().map(TableCell::display).collect::<Vec<_>>(),
+            ["-2", "10", "a", "b"]
+        );
+    }
 }
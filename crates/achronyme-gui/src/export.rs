@@ -0,0 +1,1341 @@
+//! Headless plot export
+//!
+//! Renders the options record understood by [`crate::components::plot`] to
+//! SVG or PNG without a window or GPU. A plot is laid out once into a list of
+//! [`Shape`]s in pixel coordinates; the SVG writer serializes them as markup
+//! and the PNG writer rasterizes them on the CPU with tiny-skia. Text is
+//! measured (and, for PNG, drawn) from the outlines of egui's bundled font,
+//! so both formats share the same layout.
+//!
+//! Besides the `line` and `scatter` series of the live widget, the exporter
+//! supports `bar`, `histogram`, `heatmap` and `contour` series.
+
+use crate::components::extract_points;
+use ab_glyph::{Font, FontRef, OutlineCurve};
+use achronyme_types::value::Value;
+use std::collections::HashMap;
+use std::fmt::Write;
+use tiny_skia::{FillRule, LineCap, LineJoin, Paint, PathBuilder, Pixmap, Stroke, Transform};
+
+type Rgba = [u8; 4];
+
+const PALETTE: [&str; 10] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+    "#bcbd22", "#17becf",
+];
+
+const TEXT_COLOR: Rgba = [0x33, 0x33, 0x33, 0xff];
+const GRID_COLOR: Rgba = [0xe3, 0xe3, 0xe3, 0xff];
+const FRAME_COLOR: Rgba = [0x55, 0x55, 0x55, 0xff];
+
+const TICK_SIZE: f32 = 11.0;
+const LABEL_SIZE: f32 = 13.0;
+const TITLE_SIZE: f32 = 16.0;
+
+/// Output image format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Svg,
+    Png,
+}
+
+impl ImageFormat {
+    /// Use `options.format` if present, otherwise the file extension of `path`
+    pub fn detect(path: &str, options: &Value) -> Result<Self, String> {
+        if let Value::Record(r) = options {
+            match r.read().get("format") {
+                None | Some(Value::Null) => {}
+                Some(Value::String(f)) => return Self::from_name(f),
+                Some(other) => return Err(format!("format must be a String, got {:?}", other)),
+            }
+        }
+        let ext = std::path::Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("");
+        Self::from_name(ext).map_err(|_| {
+            format!(
+                "cannot infer the image format of '{}' (use a .svg or .png extension or set `format`)",
+                path
+            )
+        })
+    }
+
+    fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_ascii_lowercase().as_str() {
+            "svg" => Ok(ImageFormat::Svg),
+            "png" => Ok(ImageFormat::Png),
+            _ => Err(format!(
+                "unsupported image format '{}' (expected \"svg\" or \"png\")",
+                name
+            )),
+        }
+    }
+}
+
+/// Render a plot options record to the bytes of an SVG or PNG file
+pub fn render(options: &Value, format: ImageFormat) -> Result<Vec<u8>, String> {
+    let scene = layout(&PlotSpec::from_value(options)?);
+    match format {
+        ImageFormat::Svg => Ok(scene.to_svg().into_bytes()),
+        ImageFormat::Png => scene.to_png(),
+    }
+}
+
+// --- Options ---
+
+/// Most histogram bins or contour levels a series may ask for
+const MAX_DIVISIONS: usize = 10_000;
+
+/// Read an optional positive integer option no larger than `MAX_DIVISIONS`
+fn division_count(value: Option<f64>, name: &str, default: usize) -> Result<usize, String> {
+    match value {
+        None => Ok(default),
+        Some(n) if n >= 1.0 && n.fract() == 0.0 && n <= MAX_DIVISIONS as f64 => Ok(n as usize),
+        Some(n) => Err(format!(
+            "{} must be an integer from 1 to {}, got {}",
+            name, MAX_DIVISIONS, n
+        )),
+    }
+}
+
+struct PlotSpec {
+    title: Option<String>,
+    x_label: Option<String>,
+    y_label: Option<String>,
+    width: u32,
+    height: u32,
+    legend: bool,
+    background: Rgba,
+    series: Vec<Series>,
+}
+
+struct Series {
+    name: String,
+    color: Option<Rgba>,
+    kind: SeriesKind,
+}
+
+enum SeriesKind {
+    Line {
+        points: Vec<[f64; 2]>,
+        width: f64,
+    },
+    Scatter {
+        points: Vec<[f64; 2]>,
+        radius: f64,
+    },
+    /// Bars and histograms: `(x0, x1, height)` rectangles standing on y = 0
+    Bars(Vec<(f64, f64, f64)>),
+    Heatmap(Grid),
+    Contour {
+        grid: Grid,
+        levels: Vec<f64>,
+    },
+}
+
+/// A matrix of samples; `values[i][j]` sits at `(x0 + j * dx, y0 + i * dy)`
+struct Grid {
+    values: Vec<Vec<f64>>,
+    x0: f64,
+    dx: f64,
+    y0: f64,
+    dy: f64,
+}
+
+impl PlotSpec {
+    fn from_value(options: &Value) -> Result<Self, String> {
+        let Value::Record(r) = options else {
+            return Err(format!("options must be a Record, got {:?}", options));
+        };
+        let r = r.read();
+
+        let mut series = Vec::new();
+        match r.get("series") {
+            None | Some(Value::Null) => {}
+            Some(Value::Vector(list)) => {
+                for item in list.read().iter() {
+                    series.push(Series::from_value(item)?);
+                }
+            }
+            Some(other) => return Err(format!("series must be a Vector, got {:?}", other)),
+        }
+
+        Ok(PlotSpec {
+            title: string_opt(&r, "title"),
+            x_label: string_opt(&r, "x_label"),
+            y_label: string_opt(&r, "y_label"),
+            width: size_opt(&r, "width", 640)?,
+            height: size_opt(&r, "height", 480)?,
+            legend: !matches!(r.get("legend"), Some(Value::Boolean(false))),
+            background: color_opt(&r, "background")?.unwrap_or([0xff; 4]),
+            series,
+        })
+    }
+}
+
+impl Series {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        let Value::Record(s) = value else {
+            return Err(format!("each series must be a Record, got {:?}", value));
+        };
+        let s = s.read();
+        let data = s.get("data").unwrap_or(&Value::Null);
+
+        // Same lookup as the live widget: "type", "kind" or "mode"
+        let kind_name = match s
+            .get("type")
+            .or_else(|| s.get("kind"))
+            .or_else(|| s.get("mode"))
+        {
+            Some(Value::String(k)) => k.as_str(),
+            _ => "line",
+        };
+
+        let kind = match kind_name {
+            "line" => SeriesKind::Line {
+                points: extract_points(data),
+                width: number_opt(&s, "line_width").unwrap_or(1.5),
+            },
+            "scatter" | "points" => SeriesKind::Scatter {
+                points: extract_points(data),
+                radius: number_opt(&s, "radius").unwrap_or(3.0),
+            },
+            "bar" | "bars" => {
+                let width = number_opt(&s, "bar_width").unwrap_or(0.8);
+                SeriesKind::Bars(
+                    extract_points(data)
+                        .into_iter()
+                        .map(|[x, y]| (x - width / 2.0, x + width / 2.0, y))
+                        .collect(),
+                )
+            }
+            "histogram" | "hist" => {
+                let bins = division_count(number_opt(&s, "bins"), "bins", 10)?;
+                SeriesKind::Bars(histogram(&extract_values(data), bins))
+            }
+            "heatmap" => SeriesKind::Heatmap(Grid::from_value(data, &s, 1)?),
+            "contour" => {
+                let grid = Grid::from_value(data, &s, 2)?;
+                let levels = match s.get("levels") {
+                    None | Some(Value::Null) => even_levels(&grid, 8),
+                    Some(Value::Number(n)) => {
+                        even_levels(&grid, division_count(Some(*n), "levels", 8)?)
+                    }
+                    Some(v @ Value::Vector(_)) => extract_values(v),
+                    Some(other) => {
+                        return Err(format!(
+                            "levels must be a count or a Vector of values, got {:?}",
+                            other
+                        ))
+                    }
+                };
+                SeriesKind::Contour { grid, levels }
+            }
+            other => {
+                return Err(format!(
+                    "unknown series type '{}' (expected line, scatter, bar, histogram, heatmap or contour)",
+                    other
+                ))
+            }
+        };
+
+        Ok(Series {
+            name: string_opt(&s, "name").unwrap_or_default(),
+            color: color_opt(&s, "color")?,
+            kind,
+        })
+    }
+}
+
+impl Grid {
+    fn from_value(
+        data: &Value,
+        series: &HashMap<String, Value>,
+        min_size: usize,
+    ) -> Result<Self, String> {
+        let values: Vec<Vec<f64>> = match data {
+            Value::Tensor(t) if t.shape().len() == 2 => {
+                let cols = t.shape()[1];
+                t.data()
+                    .chunks(cols.max(1))
+                    .map(|row| row.to_vec())
+                    .collect()
+            }
+            Value::Vector(rows) => rows.read().iter().map(extract_values).collect(),
+            other => {
+                return Err(format!(
+                    "grid data must be a matrix (rank-2 Tensor or Vector of rows), got {:?}",
+                    other
+                ))
+            }
+        };
+
+        let rows = values.len();
+        let cols = values.first().map_or(0, Vec::len);
+        if rows < min_size || cols < min_size {
+            return Err(format!(
+                "grid data must be at least {}x{}, got {}x{}",
+                min_size, min_size, rows, cols
+            ));
+        }
+        if values.iter().any(|row| row.len() != cols) {
+            return Err("grid rows must all have the same length".to_string());
+        }
+
+        // extent: [x_min, x_max, y_min, y_max] of the sample positions
+        let extent = match series.get("extent") {
+            None | Some(Value::Null) => vec![0.0, (cols - 1) as f64, 0.0, (rows - 1) as f64],
+            Some(v) => {
+                let e = extract_values(v);
+                if e.len() != 4 {
+                    return Err(format!(
+                        "extent must be [x_min, x_max, y_min, y_max], got {:?}",
+                        v
+                    ));
+                }
+                e
+            }
+        };
+        let step = |lo: f64, hi: f64, n: usize| {
+            if n > 1 {
+                (hi - lo) / (n - 1) as f64
+            } else {
+                1.0
+            }
+        };
+
+        Ok(Grid {
+            x0: extent[0],
+            dx: step(extent[0], extent[1], cols),
+            y0: extent[2],
+            dy: step(extent[2], extent[3], rows),
+            values,
+        })
+    }
+
+    fn point(&self, i: f64, j: f64) -> [f64; 2] {
+        [self.x0 + j * self.dx, self.y0 + i * self.dy]
+    }
+
+    fn value_range(&self) -> Option<(f64, f64)> {
+        let finite = self.values.iter().flatten().filter(|v| v.is_finite());
+        finite.fold(None, |acc, &v| match acc {
+            None => Some((v, v)),
+            Some((lo, hi)) => Some((lo.min(v), hi.max(v))),
+        })
+    }
+}
+
+fn string_opt(r: &HashMap<String, Value>, key: &str) -> Option<String> {
+    match r.get(key) {
+        Some(Value::String(s)) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+fn number_opt(r: &HashMap<String, Value>, key: &str) -> Option<f64> {
+    match r.get(key) {
+        Some(Value::Number(n)) => Some(*n),
+        _ => None,
+    }
+}
+
+fn size_opt(r: &HashMap<String, Value>, key: &str, default: u32) -> Result<u32, String> {
+    match r.get(key) {
+        None | Some(Value::Null) => Ok(default),
+        Some(Value::Number(n)) if *n >= 64.0 && *n <= 8192.0 => Ok(*n as u32),
+        Some(other) => Err(format!(
+            "{} must be a Number between 64 and 8192, got {:?}",
+            key, other
+        )),
+    }
+}
+
+fn color_opt(r: &HashMap<String, Value>, key: &str) -> Result<Option<Rgba>, String> {
+    match r.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(c)) => csscolorparser::parse(c)
+            .map(|c| Some(c.to_rgba8()))
+            .map_err(|_| format!("invalid {} '{}'", key, c)),
+        Some(other) => Err(format!("{} must be a String, got {:?}", key, other)),
+    }
+}
+
+/// Flatten numeric data (Tensor or Vector of numbers), dropping non-numbers
+fn extract_values(data: &Value) -> Vec<f64> {
+    match data {
+        Value::Number(n) => vec![*n],
+        Value::Tensor(t) => t.data().to_vec(),
+        Value::Vector(v) => v
+            .read()
+            .iter()
+            .filter_map(|item| match item {
+                Value::Number(n) => Some(*n),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Count `values` into `bins` equal-width bins spanning their range
+fn histogram(values: &[f64], bins: usize) -> Vec<(f64, f64, f64)> {
+    let finite: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    let Some((lo, hi)) = min_max(&finite) else {
+        return Vec::new();
+    };
+    let (lo, hi) = if lo == hi {
+        (lo - 0.5, hi + 0.5)
+    } else {
+        (lo, hi)
+    };
+    let width = (hi - lo) / bins as f64;
+
+    let mut counts = vec![0usize; bins];
+    for v in finite {
+        // The last bin is closed so the maximum lands inside it
+        let bin = (((v - lo) / width) as usize).min(bins - 1);
+        counts[bin] += 1;
+    }
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, c)| (lo + i as f64 * width, lo + (i + 1) as f64 * width, c as f64))
+        .collect()
+}
+
+/// `n` levels evenly spaced strictly inside the grid's value range
+fn even_levels(grid: &Grid, n: usize) -> Vec<f64> {
+    match grid.value_range() {
+        Some((lo, hi)) if lo < hi => (1..=n)
+            .map(|k| lo + (hi - lo) * k as f64 / (n + 1) as f64)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn min_max(values: &[f64]) -> Option<(f64, f64)> {
+    values.iter().fold(None, |acc, &v| match acc {
+        None => Some((v, v)),
+        Some((lo, hi)) => Some((lo.min(v), hi.max(v))),
+    })
+}
+
+/// Marching squares: the segments where the grid crosses `level`
+fn contour_segments(grid: &Grid, level: f64) -> Vec<[[f64; 2]; 2]> {
+    let mut segments = Vec::new();
+    let v = &grid.values;
+    for i in 0..v.len() - 1 {
+        for j in 0..v[0].len() - 1 {
+            // Corners counter-clockwise from the bottom-left
+            let corners = [(i, j), (i, j + 1), (i + 1, j + 1), (i + 1, j)];
+            let values = corners.map(|(ci, cj)| v[ci][cj]);
+            if values.iter().any(|x| !x.is_finite()) {
+                continue;
+            }
+
+            // Edge k joins corner k and corner k + 1
+            let mut crossings = [None; 4];
+            for (k, crossing) in crossings.iter_mut().enumerate() {
+                let (a, b) = (k, (k + 1) % 4);
+                if (values[a] >= level) != (values[b] >= level) {
+                    let t = (level - values[a]) / (values[b] - values[a]);
+                    let (ai, aj) = corners[a];
+                    let (bi, bj) = corners[b];
+                    *crossing = Some(grid.point(
+                        ai as f64 + t * (bi as f64 - ai as f64),
+                        aj as f64 + t * (bj as f64 - aj as f64),
+                    ));
+                }
+            }
+
+            let hits: Vec<[f64; 2]> = crossings.iter().flatten().copied().collect();
+            match hits.len() {
+                2 => segments.push([hits[0], hits[1]]),
+                4 => {
+                    // Saddle: the cell centre decides which corners are cut off
+                    let centre = values.iter().sum::<f64>() / 4.0;
+                    let c = crossings.map(|p| p.unwrap_or_default());
+                    if (centre >= level) == (values[0] >= level) {
+                        segments.push([c[0], c[1]]);
+                        segments.push([c[2], c[3]]);
+                    } else {
+                        segments.push([c[3], c[0]]);
+                        segments.push([c[1], c[2]]);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    segments
+}
+
+/// Approximation of matplotlib's viridis colormap for `t` in [0, 1]
+fn viridis(t: f64) -> Rgba {
+    const STOPS: [[f64; 3]; 5] = [
+        [68.0, 1.0, 84.0],
+        [59.0, 82.0, 139.0],
+        [33.0, 145.0, 140.0],
+        [94.0, 201.0, 98.0],
+        [253.0, 231.0, 37.0],
+    ];
+    let t = if t.is_finite() {
+        t.clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let pos = t * (STOPS.len() - 1) as f64;
+    let k = (pos as usize).min(STOPS.len() - 2);
+    let f = pos - k as f64;
+    let mix = |c: usize| (STOPS[k][c] + (STOPS[k + 1][c] - STOPS[k][c]) * f).round() as u8;
+    [mix(0), mix(1), mix(2), 0xff]
+}
+
+fn palette(index: usize) -> Rgba {
+    csscolorparser::parse(PALETTE[index % PALETTE.len()])
+        .map(|c| c.to_rgba8())
+        .unwrap_or(TEXT_COLOR)
+}
+
+// --- Layout ---
+
+#[derive(Clone, Copy, PartialEq)]
+enum Anchor {
+    Start,
+    Middle,
+    End,
+}
+
+/// A drawing primitive in pixel coordinates (y grows downwards)
+enum Shape {
+    Rect {
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        fill: Option<Rgba>,
+        stroke: Option<(Rgba, f32)>,
+    },
+    Polylines {
+        lines: Vec<Vec<(f32, f32)>>,
+        color: Rgba,
+        width: f32,
+    },
+    Circle {
+        x: f32,
+        y: f32,
+        r: f32,
+        fill: Rgba,
+    },
+    /// `y` is the baseline; `rotated` turns the text 90° counter-clockwise
+    Text {
+        x: f32,
+        y: f32,
+        text: String,
+        size: f32,
+        color: Rgba,
+        anchor: Anchor,
+        rotated: bool,
+    },
+}
+
+struct Scene {
+    width: u32,
+    height: u32,
+    shapes: Vec<Shape>,
+}
+
+/// Data-space bounding box
+struct Bounds {
+    x0: f64,
+    x1: f64,
+    y0: f64,
+    y1: f64,
+}
+
+impl Bounds {
+    fn empty() -> Self {
+        Bounds {
+            x0: f64::INFINITY,
+            x1: f64::NEG_INFINITY,
+            y0: f64::INFINITY,
+            y1: f64::NEG_INFINITY,
+        }
+    }
+
+    fn include(&mut self, [x, y]: [f64; 2]) {
+        if x.is_finite() && y.is_finite() {
+            self.x0 = self.x0.min(x);
+            self.x1 = self.x1.max(x);
+            self.y0 = self.y0.min(y);
+            self.y1 = self.y1.max(y);
+        }
+    }
+
+    /// Pad by a fraction of the span; give degenerate spans a unit width
+    fn finish(mut self, pad: f64) -> Self {
+        if self.x0 > self.x1 {
+            (self.x0, self.x1, self.y0, self.y1) = (0.0, 1.0, 0.0, 1.0);
+        }
+        for (lo, hi) in [(&mut self.x0, &mut self.x1), (&mut self.y0, &mut self.y1)] {
+            if *lo == *hi {
+                *lo -= 0.5;
+                *hi += 0.5;
+            } else {
+                let margin = (*hi - *lo) * pad;
+                *lo -= margin;
+                *hi += margin;
+            }
+        }
+        self
+    }
+}
+
+/// Maps data coordinates into the plot frame
+struct Frame {
+    left: f32,
+    top: f32,
+    width: f32,
+    height: f32,
+    bounds: Bounds,
+}
+
+impl Frame {
+    fn map(&self, [x, y]: [f64; 2]) -> (f32, f32) {
+        let b = &self.bounds;
+        let fx = (x - b.x0) / (b.x1 - b.x0);
+        let fy = (y - b.y0) / (b.y1 - b.y0);
+        (
+            self.left + fx as f32 * self.width,
+            self.top + (1.0 - fy as f32) * self.height,
+        )
+    }
+
+    /// Pixel rectangle spanning two data-space corners
+    fn rect(&self, a: [f64; 2], b: [f64; 2], fill: Rgba) -> Shape {
+        let (ax, ay) = self.map(a);
+        let (bx, by) = self.map(b);
+        Shape::Rect {
+            x: ax.min(bx),
+            y: ay.min(by),
+            w: (ax - bx).abs(),
+            h: (ay - by).abs(),
+            fill: Some(fill),
+            stroke: None,
+        }
+    }
+}
+
+fn data_bounds(series: &[Series]) -> Bounds {
+    let mut bounds = Bounds::empty();
+    let mut pad = 0.0;
+    for s in series {
+        match &s.kind {
+            SeriesKind::Line { points, .. } | SeriesKind::Scatter { points, .. } => {
+                points.iter().for_each(|p| bounds.include(*p));
+                pad = 0.05;
+            }
+            SeriesKind::Bars(bars) => {
+                for &(x0, x1, h) in bars {
+                    bounds.include([x0, 0.0]);
+                    bounds.include([x1, h]);
+                }
+                pad = 0.05;
+            }
+            SeriesKind::Heatmap(grid) => {
+                // Cells are centred on the sample positions
+                let (rows, cols) = (grid.values.len() as f64, grid.values[0].len() as f64);
+                bounds.include(grid.point(-0.5, -0.5));
+                bounds.include(grid.point(rows - 0.5, cols - 0.5));
+            }
+            SeriesKind::Contour { grid, .. } => {
+                let (rows, cols) = (grid.values.len() as f64, grid.values[0].len() as f64);
+                bounds.include(grid.point(0.0, 0.0));
+                bounds.include(grid.point(rows - 1.0, cols - 1.0));
+            }
+        }
+    }
+    bounds.finish(pad)
+}
+
+/// Round tick positions ("nice numbers") covering `[lo, hi]`
+fn ticks(lo: f64, hi: f64, target: usize) -> (Vec<f64>, f64) {
+    let raw = (hi - lo) / target.max(2) as f64;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let step = magnitude
+        * match raw / magnitude {
+            n if n < 1.5 => 1.0,
+            n if n < 3.0 => 2.0,
+            n if n < 7.0 => 5.0,
+            _ => 10.0,
+        };
+    let first = (lo / step).ceil();
+    let count = ((hi + step * 1e-9) / step).floor() - first + 1.0;
+    // Near large values the step can fall below the float spacing, so the
+    // ticks would not advance: label the ends of the range instead
+    if lo + step == lo || hi + step == hi || !(0.0..=1000.0).contains(&count) {
+        return (vec![lo, hi], step);
+    }
+    let ticks = (0..count as usize)
+        .map(|k| {
+            let t = (first + k as f64) * step;
+            // Avoid labels like "-0" or "0.30000000000000004"
+            if t.abs() < step * 1e-9 {
+                0.0
+            } else {
+                t
+            }
+        })
+        .collect();
+    (ticks, step)
+}
+
+fn format_tick(value: f64, step: f64) -> String {
+    if step < 1e-4 || value.abs() >= 1e7 {
+        return format!("{:.2e}", value);
+    }
+    let decimals = if step >= 1.0 {
+        0
+    } else {
+        (-step.log10()).ceil() as usize
+    };
+    format!("{:.*}", decimals, value)
+}
+
+fn layout(spec: &PlotSpec) -> Scene {
+    let font = font();
+    let (width, height) = (spec.width as f32, spec.height as f32);
+    let bounds = data_bounds(&spec.series);
+
+    let (x_ticks, x_step) = ticks(bounds.x0, bounds.x1, (width / 110.0) as usize);
+    let (y_ticks, y_step) = ticks(bounds.y0, bounds.y1, (height / 60.0) as usize);
+    let y_tick_labels: Vec<String> = y_ticks.iter().map(|&t| format_tick(t, y_step)).collect();
+    let widest_y_label = y_tick_labels
+        .iter()
+        .map(|l| text_width(&font, l, TICK_SIZE))
+        .fold(0.0, f32::max);
+
+    let top = 14.0 + if spec.title.is_some() { 26.0 } else { 0.0 };
+    let bottom = 32.0 + if spec.x_label.is_some() { 20.0 } else { 0.0 };
+    let left = 20.0 + widest_y_label + if spec.y_label.is_some() { 22.0 } else { 0.0 };
+    let right = 20.0;
+    let frame = Frame {
+        left,
+        top,
+        width: (width - left - right).max(1.0),
+        height: (height - top - bottom).max(1.0),
+        bounds,
+    };
+    let (frame_bottom, frame_right) = (frame.top + frame.height, frame.left + frame.width);
+
+    let mut shapes = vec![Shape::Rect {
+        x: 0.0,
+        y: 0.0,
+        w: width,
+        h: height,
+        fill: Some(spec.background),
+        stroke: None,
+    }];
+
+    // Grid lines and tick labels
+    let mut grid_lines = Vec::new();
+    for &t in &x_ticks {
+        let (x, _) = frame.map([t, frame.bounds.y0]);
+        grid_lines.push(vec![(x, frame.top), (x, frame_bottom)]);
+        shapes.push(Shape::Text {
+            x,
+            y: frame_bottom + 6.0 + TICK_SIZE,
+            text: format_tick(t, x_step),
+            size: TICK_SIZE,
+            color: TEXT_COLOR,
+            anchor: Anchor::Middle,
+            rotated: false,
+        });
+    }
+    for (&t, label) in y_ticks.iter().zip(y_tick_labels) {
+        let (_, y) = frame.map([frame.bounds.x0, t]);
+        grid_lines.push(vec![(frame.left, y), (frame_right, y)]);
+        shapes.push(Shape::Text {
+            x: frame.left - 6.0,
+            y: y + TICK_SIZE * 0.35,
+            text: label,
+            size: TICK_SIZE,
+            color: TEXT_COLOR,
+            anchor: Anchor::End,
+            rotated: false,
+        });
+    }
+    shapes.push(Shape::Polylines {
+        lines: grid_lines,
+        color: GRID_COLOR,
+        width: 1.0,
+    });
+
+    // Series, in declaration order so later ones draw on top
+    let mut legend = Vec::new();
+    for (index, series) in spec.series.iter().enumerate() {
+        let color = series.color.unwrap_or_else(|| palette(index));
+        let swatch = draw_series(&mut shapes, &frame, series, color);
+        if !series.name.is_empty() {
+            legend.push((series.name.as_str(), swatch));
+        }
+    }
+
+    shapes.push(Shape::Rect {
+        x: frame.left,
+        y: frame.top,
+        w: frame.width,
+        h: frame.height,
+        fill: None,
+        stroke: Some((FRAME_COLOR, 1.0)),
+    });
+
+    if let Some(title) = &spec.title {
+        shapes.push(Shape::Text {
+            x: frame.left + frame.width / 2.0,
+            y: 14.0 + TITLE_SIZE,
+            text: title.clone(),
+            size: TITLE_SIZE,
+            color: TEXT_COLOR,
+            anchor: Anchor::Middle,
+            rotated: false,
+        });
+    }
+    if let Some(label) = &spec.x_label {
+        shapes.push(Shape::Text {
+            x: frame.left + frame.width / 2.0,
+            y: height - 10.0,
+            text: label.clone(),
+            size: LABEL_SIZE,
+            color: TEXT_COLOR,
+            anchor: Anchor::Middle,
+            rotated: false,
+        });
+    }
+    if let Some(label) = &spec.y_label {
+        shapes.push(Shape::Text {
+            x: 10.0 + LABEL_SIZE,
+            y: frame.top + frame.height / 2.0,
+            text: label.clone(),
+            size: LABEL_SIZE,
+            color: TEXT_COLOR,
+            anchor: Anchor::Middle,
+            rotated: true,
+        });
+    }
+
+    if spec.legend && !legend.is_empty() {
+        draw_legend(&mut shapes, &font, &frame, &legend);
+    }
+
+    Scene {
+        width: spec.width,
+        height: spec.height,
+        shapes,
+    }
+}
+
+/// How a series is represented in the legend
+enum Swatch {
+    Line(Rgba),
+    Dot(Rgba),
+    Block(Rgba),
+}
+
+fn draw_series(shapes: &mut Vec<Shape>, frame: &Frame, series: &Series, color: Rgba) -> Swatch {
+    match &series.kind {
+        SeriesKind::Line { points, width } => {
+            // Non-finite points break the line
+            let lines = points
+                .split(|[x, y]| !x.is_finite() || !y.is_finite())
+                .map(|run| run.iter().map(|p| frame.map(*p)).collect())
+                .collect();
+            shapes.push(Shape::Polylines {
+                lines,
+                color,
+                width: *width as f32,
+            });
+            Swatch::Line(color)
+        }
+        SeriesKind::Scatter { points, radius } => {
+            for &p in points {
+                if p[0].is_finite() && p[1].is_finite() {
+                    let (x, y) = frame.map(p);
+                    shapes.push(Shape::Circle {
+                        x,
+                        y,
+                        r: *radius as f32,
+                        fill: color,
+                    });
+                }
+            }
+            Swatch::Dot(color)
+        }
+        SeriesKind::Bars(bars) => {
+            let fill = [color[0], color[1], color[2], 0xd0];
+            for &(x0, x1, h) in bars {
+                if let Shape::Rect { x, y, w, h, .. } = frame.rect([x0, 0.0], [x1, h], fill) {
+                    shapes.push(Shape::Rect {
+                        x,
+                        y,
+                        w,
+                        h,
+                        fill: Some(fill),
+                        stroke: Some((color, 1.0)),
+                    });
+                }
+            }
+            Swatch::Block(fill)
+        }
+        SeriesKind::Heatmap(grid) => {
+            let (lo, hi) = grid.value_range().unwrap_or((0.0, 1.0));
+            for (i, row) in grid.values.iter().enumerate() {
+                for (j, &v) in row.iter().enumerate() {
+                    if !v.is_finite() {
+                        continue;
+                    }
+                    let t = if hi > lo { (v - lo) / (hi - lo) } else { 0.5 };
+                    let (i, j) = (i as f64, j as f64);
+                    shapes.push(frame.rect(
+                        grid.point(i - 0.5, j - 0.5),
+                        grid.point(i + 0.5, j + 0.5),
+                        viridis(t),
+                    ));
+                }
+            }
+            Swatch::Block(viridis(0.5))
+        }
+        SeriesKind::Contour { grid, levels } => {
+            let (lo, hi) = grid.value_range().unwrap_or((0.0, 1.0));
+            for &level in levels {
+                // Without an explicit color, levels are shaded by value
+                let level_color = match series.color {
+                    Some(c) => c,
+                    None if hi > lo => viridis((level - lo) / (hi - lo)),
+                    None => color,
+                };
+                let lines = contour_segments(grid, level)
+                    .into_iter()
+                    .map(|[a, b]| vec![frame.map(a), frame.map(b)])
+                    .collect();
+                shapes.push(Shape::Polylines {
+                    lines,
+                    color: level_color,
+                    width: 1.2,
+                });
+            }
+            Swatch::Line(series.color.unwrap_or(viridis(0.5)))
+        }
+    }
+}
+
+fn draw_legend(shapes: &mut Vec<Shape>, font: &FontRef, frame: &Frame, entries: &[(&str, Swatch)]) {
+    const ROW: f32 = 18.0;
+    const PAD: f32 = 8.0;
+    const SWATCH: f32 = 18.0;
+
+    let text_w = entries
+        .iter()
+        .map(|(name, _)| text_width(font, name, TICK_SIZE))
+        .fold(0.0, f32::max);
+    let w = PAD * 3.0 + SWATCH + text_w;
+    let h = PAD * 2.0 + ROW * entries.len() as f32;
+    let x = frame.left + frame.width - w - 8.0;
+    let y = frame.top + 8.0;
+
+    shapes.push(Shape::Rect {
+        x,
+        y,
+        w,
+        h,
+        fill: Some([0xff, 0xff, 0xff, 0xe6]),
+        stroke: Some((GRID_COLOR, 1.0)),
+    });
+    for (row, (name, swatch)) in entries.iter().enumerate() {
+        let cy = y + PAD + ROW * (row as f32 + 0.5);
+        let sx = x + PAD;
+        shapes.push(match *swatch {
+            Swatch::Line(color) => Shape::Polylines {
+                lines: vec![vec![(sx, cy), (sx + SWATCH, cy)]],
+                color,
+                width: 2.0,
+            },
+            Swatch::Dot(color) => Shape::Circle {
+                x: sx + SWATCH / 2.0,
+                y: cy,
+                r: 4.0,
+                fill: color,
+            },
+            Swatch::Block(color) => Shape::Rect {
+                x: sx + 3.0,
+                y: cy - 6.0,
+                w: SWATCH - 6.0,
+                h: 12.0,
+                fill: Some(color),
+                stroke: None,
+            },
+        });
+        shapes.push(Shape::Text {
+            x: sx + SWATCH + PAD,
+            y: cy + TICK_SIZE * 0.35,
+            text: name.to_string(),
+            size: TICK_SIZE,
+            color: TEXT_COLOR,
+            anchor: Anchor::Start,
+            rotated: false,
+        });
+    }
+}
+
+// --- Text ---
+
+fn font() -> FontRef<'static> {
+    FontRef::try_from_slice(epaint_default_fonts::UBUNTU_LIGHT).expect("bundled font is valid")
+}
+
+fn font_scale(font: &FontRef, size: f32) -> f32 {
+    size / font.units_per_em().unwrap_or(1000.0)
+}
+
+fn text_width(font: &FontRef, text: &str, size: f32) -> f32 {
+    let scale = font_scale(font, size);
+    let mut width = 0.0;
+    let mut prev = None;
+    for ch in text.chars() {
+        let id = font.glyph_id(ch);
+        if let Some(prev) = prev {
+            width += font.kern_unscaled(prev, id) * scale;
+        }
+        width += font.h_advance_unscaled(id) * scale;
+        prev = Some(id);
+    }
+    width
+}
+
+/// Glyph outlines of `text` laid out from the origin along the baseline
+fn text_path(font: &FontRef, text: &str, size: f32) -> Option<tiny_skia::Path> {
+    let scale = font_scale(font, size);
+    let mut pb = PathBuilder::new();
+    let mut pen = 0.0;
+    let mut prev = None;
+    for ch in text.chars() {
+        let id = font.glyph_id(ch);
+        if let Some(prev) = prev {
+            pen += font.kern_unscaled(prev, id) * scale;
+        }
+        if let Some(outline) = font.outline(id) {
+            // Font units are y-up; flip into pixel space
+            let map = |p: ab_glyph::Point| (pen + p.x * scale, -p.y * scale);
+            let mut last = None;
+            for curve in &outline.curves {
+                let (start, end) = match curve {
+                    OutlineCurve::Line(a, b) => (*a, *b),
+                    OutlineCurve::Quad(a, _, c) => (*a, *c),
+                    OutlineCurve::Cubic(a, _, _, d) => (*a, *d),
+                };
+                if last != Some(start) {
+                    if last.is_some() {
+                        pb.close();
+                    }
+                    let (x, y) = map(start);
+                    pb.move_to(x, y);
+                }
+                match curve {
+                    OutlineCurve::Line(_, b) => {
+                        let (x, y) = map(*b);
+                        pb.line_to(x, y);
+                    }
+                    OutlineCurve::Quad(_, b, c) => {
+                        let ((x1, y1), (x, y)) = (map(*b), map(*c));
+                        pb.quad_to(x1, y1, x, y);
+                    }
+                    OutlineCurve::Cubic(_, b, c, d) => {
+                        let ((x1, y1), (x2, y2), (x, y)) = (map(*b), map(*c), map(*d));
+                        pb.cubic_to(x1, y1, x2, y2, x, y);
+                    }
+                }
+                last = Some(end);
+            }
+            if last.is_some() {
+                pb.close();
+            }
+        }
+        pen += font.h_advance_unscaled(id) * scale;
+        prev = Some(id);
+    }
+    pb.finish()
+}
+
+// --- Writers ---
+
+fn svg_paint(attr: &str, c: Rgba) -> String {
+    let mut out = format!(" {}=\"#{:02x}{:02x}{:02x}\"", attr, c[0], c[1], c[2]);
+    if c[3] != 0xff {
+        let _ = write!(out, " {}-opacity=\"{:.3}\"", attr, c[3] as f32 / 255.0);
+    }
+    out
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn paint(c: Rgba) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(c[0], c[1], c[2], c[3]);
+    paint.anti_alias = true;
+    paint
+}
+
+impl Scene {
+    fn to_svg(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">",
+            w = self.width,
+            h = self.height
+        );
+        for shape in &self.shapes {
+            match shape {
+                Shape::Rect {
+                    x,
+                    y,
+                    w,
+                    h,
+                    fill,
+                    stroke,
+                } => {
+                    let _ = write!(
+                        out,
+                        "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\"",
+                        x, y, w, h
+                    );
+                    match fill {
+                        Some(c) => out.push_str(&svg_paint("fill", *c)),
+                        None => out.push_str(" fill=\"none\""),
+                    }
+                    if let Some((c, width)) = stroke {
+                        out.push_str(&svg_paint("stroke", *c));
+                        let _ = write!(out, " stroke-width=\"{}\"", width);
+                    }
+                    out.push_str("/>\n");
+                }
+                Shape::Polylines {
+                    lines,
+                    color,
+                    width,
+                } => {
+                    let mut d = String::new();
+                    for line in lines.iter().filter(|l| l.len() >= 2) {
+                        for (k, (x, y)) in line.iter().enumerate() {
+                            let _ =
+                                write!(d, "{}{:.2} {:.2} ", if k == 0 { 'M' } else { 'L' }, x, y);
+                        }
+                    }
+                    if d.is_empty() {
+                        continue;
+                    }
+                    let _ = writeln!(
+                        out,
+                        "<path d=\"{}\" fill=\"none\"{} stroke-width=\"{}\" stroke-linejoin=\"round\" stroke-linecap=\"round\"/>",
+                        d.trim_end(),
+                        svg_paint("stroke", *color),
+                        width
+                    );
+                }
+                Shape::Circle { x, y, r, fill } => {
+                    let _ = writeln!(
+                        out,
+                        "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{}\"{}/>",
+                        x,
+                        y,
+                        r,
+                        svg_paint("fill", *fill)
+                    );
+                }
+                Shape::Text {
+                    x,
+                    y,
+                    text,
+                    size,
+                    color,
+                    anchor,
+                    rotated,
+                } => {
+                    let anchor = match anchor {
+                        Anchor::Start => "start",
+                        Anchor::Middle => "middle",
+                        Anchor::End => "end",
+                    };
+                    let _ = write!(
+                        out,
+                        "<text x=\"{:.2}\" y=\"{:.2}\" font-family=\"Ubuntu, 'DejaVu Sans', Arial, sans-serif\" font-size=\"{}\" text-anchor=\"{}\"{}",
+                        x,
+                        y,
+                        size,
+                        anchor,
+                        svg_paint("fill", *color)
+                    );
+                    if *rotated {
+                        let _ = write!(out, " transform=\"rotate(-90 {:.2} {:.2})\"", x, y);
+                    }
+                    let _ = writeln!(out, ">{}</text>", xml_escape(text));
+                }
+            }
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    fn to_png(&self) -> Result<Vec<u8>, String> {
+        let mut pixmap = Pixmap::new(self.width, self.height)
+            .ok_or_else(|| format!("invalid image size {}x{}", self.width, self.height))?;
+        let font = font();
+        let id = Transform::identity();
+
+        for shape in &self.shapes {
+            match shape {
+                Shape::Rect {
+                    x,
+                    y,
+                    w,
+                    h,
+                    fill,
+                    stroke,
+                } => {
+                    let Some(rect) = tiny_skia::Rect::from_xywh(*x, *y, w.max(0.5), h.max(0.5))
+                    else {
+                        continue;
+                    };
+                    if let Some(c) = fill {
+                        // Snapped to whole pixels so adjacent heatmap cells
+                        // share an edge instead of leaving seams
+                        let snapped = tiny_skia::Rect::from_ltrb(
+                            x.round(),
+                            y.round(),
+                            (x + w).round().max(x.round() + 1.0),
+                            (y + h).round().max(y.round() + 1.0),
+                        );
+                        if let Some(snapped) = snapped {
+                            let mut paint = paint(*c);
+                            paint.anti_alias = false;
+                            pixmap.fill_rect(snapped, &paint, id, None);
+                        }
+                    }
+                    if let Some((c, width)) = stroke {
+                        let path = PathBuilder::from_rect(rect);
+                        let stroke = Stroke {
+                            width: *width,
+                            ..Stroke::default()
+                        };
+                        pixmap.stroke_path(&path, &paint(*c), &stroke, id, None);
+                    }
+                }
+                Shape::Polylines {
+                    lines,
+                    color,
+                    width,
+                } => {
+                    let mut pb = PathBuilder::new();
+                    for line in lines.iter().filter(|l| l.len() >= 2) {
+                        pb.move_to(line[0].0, line[0].1);
+                        for &(x, y) in &line[1..] {
+                            pb.line_to(x, y);
+                        }
+                    }
+                    if let Some(path) = pb.finish() {
+                        let stroke = Stroke {
+                            width: *width,
+                            line_cap: LineCap::Round,
+                            line_join: LineJoin::Round,
+                            ..Stroke::default()
+                        };
+                        pixmap.stroke_path(&path, &paint(*color), &stroke, id, None);
+                    }
+                }
+                Shape::Circle { x, y, r, fill } => {
+                    if let Some(path) = PathBuilder::from_circle(*x, *y, *r) {
+                        pixmap.fill_path(&path, &paint(*fill), FillRule::Winding, id, None);
+                    }
+                }
+                Shape::Text {
+                    x,
+                    y,
+                    text,
+                    size,
+                    color,
+                    anchor,
+                    rotated,
+                } => {
+                    let Some(path) = text_path(&font, text, *size) else {
+                        continue;
+                    };
+                    let offset = match anchor {
+                        Anchor::Start => 0.0,
+                        Anchor::Middle => text_width(&font, text, *size) / 2.0,
+                        Anchor::End => text_width(&font, text, *size),
+                    };
+                    let transform = if *rotated {
+                        // (u, v) -> (x + v, y - u): a quarter turn counter-clockwise
+                        Transform::from_row(0.0, -1.0, 1.0, 0.0, *x, *y).pre_translate(-offset, 0.0)
+                    } else {
+                        Transform::from_translate(*x - offset, *y)
+                    };
+                    pixmap.fill_path(&path, &paint(*color), FillRule::Winding, transform, None);
+                }
+            }
+        }
+
+        pixmap.encode_png().map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ticks_are_round_and_cover_the_range() {
+        let (t, step) = ticks(-0.05, 1.05, 5);
+        assert_eq!(step, 0.2);
+        assert_eq!(t.first(), Some(&0.0));
+        assert!((t.last().unwrap() - 1.0).abs() < 1e-12);
+        assert_eq!(format_tick(0.4, step), "0.4");
+    }
+
+    #[test]
+    fn ticks_end_when_the_step_is_below_float_spacing() {
+        let (t, _) = ticks(1e16, 1e16 + 4.0, 5);
+        assert_eq!(t, vec![1e16, 1e16 + 4.0]);
+    }
+
+    #[test]
+    fn histogram_keeps_the_maximum_in_the_last_bin() {
+        let bars = histogram(&[0.0, 1.0, 2.0, 3.0, 4.0], 4);
+        let counts: Vec<f64> = bars.iter().map(|b| b.2).collect();
+        assert_eq!(counts, vec![1.0, 1.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn contour_of_a_ramp_is_a_straight_line() {
+        let grid = Grid {
+            values: vec![vec![0.0, 1.0, 2.0]; 3],
+            x0: 0.0,
+            dx: 1.0,
+            y0: 0.0,
+            dy: 1.0,
+        };
+        let segments = contour_segments(&grid, 0.5);
+        assert_eq!(segments.len(), 2);
+        assert!(segments
+            .iter()
+            .flatten()
+            .all(|p| (p[0] - 0.5).abs() < 1e-12));
+    }
+}
@@ -0,0 +1,288 @@
+//! Headless GUI test harness
+//!
+//! [`Harness`] drives a render function without a window: every
+//! [`Harness::step`] runs one `egui::Context` frame, exactly like
+//! [`crate::runner::AchronymeApp`] does inside eframe. While a frame runs, the
+//! components record what they draw into a widget list, which is what
+//! interactions are resolved against and what [`Harness::snapshot`] prints.
+//!
+//! Interactions are injected as real pointer/keyboard events, so they go
+//! through the same egui code paths as a user would. Widgets are addressed
+//! by their label (`"Save"`) or by a per-kind id in draw order
+//! (`"slider#0"`, `"text_input#1"`), which is handy for unlabelled controls.
+
+use crate::runner::{self, Evaluator};
+use achronyme_types::value::Value;
+use anyhow::{anyhow, bail};
+use egui::{Event, Modifiers, PointerButton, Pos2, Rect, Vec2};
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::rc::Rc;
+
+/// A widget drawn during the last frame
+#[derive(Debug, Clone)]
+pub struct WidgetNode {
+    /// Component kind (`"button"`, `"slider"`, ...)
+    pub kind: &'static str,
+    /// Per-kind id in draw order, e.g. `"slider#0"`
+    pub id: String,
+    /// Visible label, if the widget has one
+    pub label: Option<String>,
+    /// Current value, formatted for snapshots
+    pub value: Option<String>,
+    /// Numeric range for sliders, used to aim drags
+    pub range: Option<(f64, f64)>,
+    /// Screen rect the widget responds to
+    pub rect: Rect,
+    /// Nesting depth inside containers
+    pub depth: usize,
+}
+
+#[derive(Default)]
+struct Recorder {
+    nodes: Vec<WidgetNode>,
+    depth: usize,
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Record a widget if a harness frame is running (no-op otherwise)
+pub(crate) fn record(
+    kind: &'static str,
+    label: Option<&str>,
+    value: Option<String>,
+    rect: Rect,
+) -> Option<usize> {
+    RECORDER.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        let recorder = cell.as_mut()?;
+        let index = recorder.nodes.iter().filter(|n| n.kind == kind).count();
+        recorder.nodes.push(WidgetNode {
+            kind,
+            id: format!("{}#{}", kind, index),
+            label: label.map(str::to_string),
+            value,
+            range: None,
+            rect,
+            depth: recorder.depth,
+        });
+        Some(recorder.nodes.len() - 1)
+    })
+}
+
+/// Fill in the rect and value of a widget recorded before its children
+pub(crate) fn complete(index: Option<usize>, rect: Rect, value: Option<String>) {
+    let Some(index) = index else { return };
+    RECORDER.with(|cell| {
+        if let Some(recorder) = cell.borrow_mut().as_mut() {
+            let node = &mut recorder.nodes[index];
+            node.rect = rect;
+            node.value = value;
+        }
+    });
+}
+
+/// Record a slider together with its range
+pub(crate) fn record_slider(value: f64, min: f64, max: f64, rect: Rect) {
+    if let Some(index) = record("slider", None, Some(format_number(value)), rect) {
+        RECORDER.with(|cell| {
+            if let Some(recorder) = cell.borrow_mut().as_mut() {
+                recorder.nodes[index].range = Some((min, max));
+            }
+        });
+    }
+}
+
+/// Run `f` with widgets recorded one level deeper (container children)
+pub(crate) fn nested<R>(f: impl FnOnce() -> R) -> R {
+    let shift = |delta: isize| {
+        RECORDER.with(|cell| {
+            if let Some(recorder) = cell.borrow_mut().as_mut() {
+                recorder.depth = recorder.depth.saturating_add_signed(delta);
+            }
+        })
+    };
+    shift(1);
+    let result = f();
+    shift(-1);
+    result
+}
+
+pub(crate) fn format_number(n: f64) -> String {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        format!("{}", n as i64)
+    } else {
+        format!("{:.4}", n)
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
+}
+
+/// Steps a render function frame by frame without a window
+pub struct Harness {
+    ctx: egui::Context,
+    render_fn: Value,
+    evaluator: Rc<Box<dyn Evaluator>>,
+    size: Vec2,
+    events: Vec<Event>,
+    widgets: Vec<WidgetNode>,
+    frame: u64,
+}
+
+impl Harness {
+    pub fn new(render_fn: Value, evaluator: Rc<Box<dyn Evaluator>>, size: Vec2) -> Self {
+        let ctx = egui::Context::default();
+        runner::setup_custom_fonts(&ctx);
+        egui_extras::install_image_loaders(&ctx);
+        Self {
+            ctx,
+            render_fn,
+            evaluator,
+            size,
+            events: Vec::new(),
+            widgets: Vec::new(),
+            frame: 0,
+        }
+    }
+
+    /// Run one frame, delivering the queued input events
+    pub fn step(&mut self) -> Result<(), anyhow::Error> {
+        let input = egui::RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, self.size)),
+            time: Some(self.frame as f64 / 60.0),
+            events: std::mem::take(&mut self.events),
+            ..Default::default()
+        };
+
+        RECORDER.with(|cell| *cell.borrow_mut() = Some(Recorder::default()));
+        let mut result = Ok(Value::Null);
+        let _ = self.ctx.run(input, |ctx| {
+            result = runner::render_frame(ctx, &self.render_fn, self.evaluator.as_ref().as_ref());
+        });
+        self.widgets = RECORDER
+            .with(|cell| cell.borrow_mut().take())
+            .map(|r| r.nodes)
+            .unwrap_or_default();
+        self.frame += 1;
+        result.map(|_| ())
+    }
+
+    /// Run `frames` frames without input
+    pub fn run(&mut self, frames: usize) -> Result<(), anyhow::Error> {
+        for _ in 0..frames {
+            self.step()?;
+        }
+        Ok(())
+    }
+
+    /// Whether anything asked for another frame since the last one
+    /// (a changed signal the UI reads, an animation, a finished task)
+    pub fn wants_redraw(&self) -> bool {
+        self.ctx.has_requested_repaint()
+    }
+
+    /// Widgets drawn during the last frame, in draw order
+    pub fn widgets(&self) -> &[WidgetNode] {
+        &self.widgets
+    }
+
+    /// Find a widget by label, or by id such as `"slider#0"`
+    pub fn find(&self, target: &str) -> Result<&WidgetNode, anyhow::Error> {
+        self.widgets
+            .iter()
+            .find(|w| w.label.as_deref() == Some(target))
+            .or_else(|| self.widgets.iter().find(|w| w.id == target))
+            .ok_or_else(|| anyhow!("no widget '{}' in the current frame", target))
+    }
+
+    /// Click a widget (press and release over its centre)
+    pub fn click(&mut self, target: &str) -> Result<(), anyhow::Error> {
+        self.ensure_rendered()?;
+        let pos = self.find(target)?.rect.center();
+        self.press_at(pos)?;
+        self.release_at(pos)?;
+        // One more frame so the UI reflects what the click changed
+        self.step()
+    }
+
+    /// Focus a text input by clicking it, then type `text`
+    pub fn type_text(&mut self, target: &str, text: &str) -> Result<(), anyhow::Error> {
+        self.click(target)?;
+        self.events.push(Event::Text(text.to_string()));
+        self.step()?;
+        self.step()
+    }
+
+    /// Drag a slider's handle to the position of `value`
+    pub fn drag_slider(&mut self, target: &str, value: f64) -> Result<(), anyhow::Error> {
+        self.ensure_rendered()?;
+        let widget = self.find(target)?;
+        let Some((min, max)) = widget.range else {
+            bail!("'{}' is a {}, not a slider", target, widget.kind);
+        };
+        // egui keeps the handle centre one handle radius inside the rail ends
+        let rect = widget.rect;
+        let radius = rect.height() / 2.5;
+        let t = if max > min {
+            ((value - min) / (max - min)).clamp(0.0, 1.0) as f32
+        } else {
+            0.0
+        };
+        let x = rect.left() + radius + t * (rect.width() - 2.0 * radius);
+        let pos = Pos2::new(x, rect.center().y);
+        self.press_at(pos)?;
+        self.release_at(pos)?;
+        self.step()
+    }
+
+    /// Indented text dump of the widget tree from the last frame
+    pub fn snapshot(&self) -> String {
+        let mut out = String::new();
+        for w in &self.widgets {
+            let _ = write!(out, "{}{}", "  ".repeat(w.depth), w.kind);
+            match &w.label {
+                Some(label) => {
+                    let _ = write!(out, " {:?}", label);
+                }
+                None => {
+                    let _ = write!(out, " #{}", &w.id[w.kind.len() + 1..]);
+                }
+            }
+            if let Some(value) = &w.value {
+                let _ = write!(out, " = {}", value);
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    fn ensure_rendered(&mut self) -> Result<(), anyhow::Error> {
+        if self.frame == 0 {
+            self.step()?;
+        }
+        Ok(())
+    }
+
+    fn press_at(&mut self, pos: Pos2) -> Result<(), anyhow::Error> {
+        self.events.push(Event::PointerMoved(pos));
+        self.events.push(pointer_button(pos, true));
+        self.step()
+    }
+
+    fn release_at(&mut self, pos: Pos2) -> Result<(), anyhow::Error> {
+        self.events.push(pointer_button(pos, false));
+        self.step()
+    }
+}
+
+fn pointer_button(pos: Pos2, pressed: bool) -> Event {
+    Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Modifiers::default(),
+    }
+}
@@ -110,12 +110,13 @@ impl StyleConfig {
     /// In egui, containers expand to fill available space by default.
     /// We invert egui's default: ShrinkWrap unless explicitly asked to expand.
     pub fn resolve_layout(&self, _ctx: &LayoutContext) -> EguiLayoutPlan {
-        let mut plan = EguiLayoutPlan::default();
-
         // 1. Base layout direction
-        plan.layout = match self.layout_mode {
-            LayoutMode::Horizontal => Layout::left_to_right(Align::Min),
-            LayoutMode::Vertical => Layout::top_down(Align::Min),
+        let mut plan = EguiLayoutPlan {
+            layout: match self.layout_mode {
+                LayoutMode::Horizontal => Layout::left_to_right(Align::Min),
+                LayoutMode::Vertical => Layout::top_down(Align::Min),
+            },
+            ..Default::default()
         };
 
         // 2. Apply alignments to the layout
@@ -190,9 +191,11 @@ mod tests {
 
     #[test]
     fn test_explicit_dimensions_use_fixed() {
-        let mut config = StyleConfig::default();
-        config.width = Some(100.0);
-        config.height = Some(50.0);
+        let config = StyleConfig {
+            width: Some(100.0),
+            height: Some(50.0),
+            ..Default::default()
+        };
 
         let ctx = LayoutContext {
             available_width: Some(800.0),
@@ -214,8 +217,10 @@ mod tests {
 
     #[test]
     fn test_w_full_uses_fixed_with_infinity() {
-        let mut config = StyleConfig::default();
-        config.width = Some(f32::INFINITY); // w-full
+        let config = StyleConfig {
+            width: Some(f32::INFINITY), // w-full
+            ..Default::default()
+        };
 
         let ctx = LayoutContext {
             available_width: Some(800.0),
@@ -234,8 +239,10 @@ mod tests {
 
     #[test]
     fn test_alignments_are_applied() {
-        let mut config = StyleConfig::default();
-        config.cross_align = Some(Align::Center);
+        let config = StyleConfig {
+            cross_align: Some(Align::Center),
+            ..Default::default()
+        };
 
         let ctx = LayoutContext {
             available_width: Some(800.0),
@@ -1,5 +1,7 @@
 pub mod bridge;
 pub mod components;
+pub mod export;
+pub mod harness;
 pub mod layout;
 pub mod runner;
 pub mod style;
@@ -1,7 +1,11 @@
 use crate::bridge;
+use crate::components;
+use achronyme_types::sync::shared;
 use achronyme_types::value::Value;
 pub use eframe;
 pub use eframe::egui;
+use std::collections::HashMap;
+use std::fmt;
 use std::rc::Rc;
 
 /// A trait that abstracts the capability to call an Achronyme function.
@@ -9,6 +13,72 @@ use std::rc::Rc;
 /// without depending on achronyme-vm directly.
 pub trait Evaluator: 'static {
     fn call(&self, func: &Value, args: Vec<Value>) -> Result<Value, anyhow::Error>;
+
+    /// Call the render function for one frame. Evaluators that can tell
+    /// which state the frame read override this to call
+    /// `ctx.request_repaint()` once that state changes; the runner doesn't
+    /// repaint on its own otherwise.
+    fn render(
+        &self,
+        ctx: &egui::Context,
+        render_fn: &Value,
+        args: Vec<Value>,
+    ) -> Result<Value, anyhow::Error> {
+        let _ = ctx;
+        self.call(render_fn, args)
+    }
+}
+
+/// An error raised by the script, with the functions that were running
+/// when it happened (outermost first)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptError {
+    pub message: String,
+    pub stack: Vec<String>,
+}
+
+impl ScriptError {
+    fn from_anyhow(err: &anyhow::Error) -> Self {
+        err.downcast_ref::<ScriptError>()
+            .cloned()
+            .unwrap_or_else(|| ScriptError {
+                message: err.to_string(),
+                stack: Vec::new(),
+            })
+    }
+
+    /// `{message, stack}` record handed to the script's `on_error` hook
+    fn to_value(&self) -> Value {
+        let mut fields = HashMap::new();
+        fields.insert("message".to_string(), Value::String(self.message.clone()));
+        let stack = self.stack.iter().cloned().map(Value::String).collect();
+        fields.insert("stack".to_string(), Value::Vector(shared(stack)));
+        Value::Record(shared(fields))
+    }
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+/// What the runner does when the render function fails
+#[derive(Default)]
+pub struct ErrorHandling {
+    /// Stop calling the render function after an error (until "Retry")
+    pub halt: bool,
+    /// Script callback `(error) => ...`, called once per distinct error
+    pub on_error: Option<Value>,
+}
+
+/// The error currently shown in the overlay
+struct RenderFailure {
+    error: ScriptError,
+    /// Consecutive frames that failed with this same error
+    repeats: usize,
 }
 
 pub struct AchronymeApp {
@@ -16,20 +86,140 @@ pub struct AchronymeApp {
     render_fn: Value,
     /// The evaluator (VM wrapper)
     evaluator: Rc<Box<dyn Evaluator>>,
+    errors: ErrorHandling,
+    failure: Option<RenderFailure>,
+    halted: bool,
+    /// Repaint every frame instead of only on input and state changes
+    continuous: bool,
 }
 
 impl AchronymeApp {
-    pub fn new(render_fn: Value, evaluator: Rc<Box<dyn Evaluator>>) -> Self {
+    pub fn new(render_fn: Value, evaluator: Rc<Box<dyn Evaluator>>, errors: ErrorHandling) -> Self {
         Self {
             render_fn,
             evaluator,
+            errors,
+            failure: None,
+            halted: false,
+            continuous: false,
         }
     }
+
+    /// Redraw as fast as possible, e.g. for UIs that show the current time
+    pub fn continuous(mut self, continuous: bool) -> Self {
+        self.continuous = continuous;
+        self
+    }
+
+    /// Log and hook a render error, skipping repeats of the previous one
+    fn report(&mut self, error: ScriptError) {
+        if let Some(failure) = &mut self.failure {
+            if failure.error == error {
+                failure.repeats += 1;
+                return;
+            }
+        }
+
+        eprintln!("[gui] render error: {}", error.message);
+        for function in error.stack.iter().rev() {
+            eprintln!("[gui]     in {}", function);
+        }
+        if let Some(handler) = &self.errors.on_error {
+            if let Err(e) = self.evaluator.call(handler, vec![error.to_value()]) {
+                eprintln!("[gui] on_error handler failed: {}", e);
+            }
+        }
+        self.failure = Some(RenderFailure { error, repeats: 1 });
+    }
 }
 
 impl eframe::App for AchronymeApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::CentralPanel::default().show(ctx, |ui| {
+        // Input repaints by itself; state changes are requested by the
+        // evaluator (see `Evaluator::render`)
+        self.show(ctx);
+    }
+}
+
+impl AchronymeApp {
+    /// Render one frame: the script's UI, or the error overlay if it failed
+    fn show(&mut self, ctx: &egui::Context) {
+        if !self.halted {
+            match render_frame(ctx, &self.render_fn, self.evaluator.as_ref().as_ref()) {
+                Ok(_) => self.failure = None,
+                Err(e) => {
+                    self.report(ScriptError::from_anyhow(&e));
+                    self.halted = self.errors.halt;
+                }
+            }
+        } else {
+            // Keep the window painted while waiting for "Retry"
+            egui::CentralPanel::default().show(ctx, |_| {});
+        }
+
+        if let Some(failure) = &self.failure {
+            if error_overlay(ctx, failure, self.halted) {
+                self.halted = false;
+                self.failure = None;
+            }
+        }
+
+        if self.continuous {
+            ctx.request_repaint();
+        }
+    }
+}
+
+/// Developer overlay for a failing render function.
+/// Returns true when "Retry" was pressed.
+fn error_overlay(ctx: &egui::Context, failure: &RenderFailure, halted: bool) -> bool {
+    let mut retry = false;
+    egui::Area::new(egui::Id::new("achronyme_error_overlay"))
+        .order(egui::Order::Foreground)
+        .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -12.0))
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style())
+                .fill(egui::Color32::from_rgb(60, 16, 16))
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(220, 70, 70)))
+                .show(ui, |ui| {
+                    ui.set_max_width(ctx.screen_rect().width() - 48.0);
+                    let mut heading = "Render error".to_string();
+                    if failure.repeats > 1 {
+                        heading.push_str(&format!(" (x{})", failure.repeats));
+                    }
+                    ui.label(
+                        egui::RichText::new(heading)
+                            .strong()
+                            .color(egui::Color32::from_rgb(255, 120, 120)),
+                    );
+                    ui.label(egui::RichText::new(&failure.error.message).monospace());
+                    for function in failure.error.stack.iter().rev() {
+                        ui.label(
+                            egui::RichText::new(format!("    in {}", function))
+                                .monospace()
+                                .weak(),
+                        );
+                    }
+                    if halted {
+                        ui.horizontal(|ui| {
+                            ui.label("Rendering halted.");
+                            retry = ui.button("Retry").clicked();
+                        });
+                    }
+                });
+        });
+    retry
+}
+
+/// Run the script's render function inside a central panel for one frame.
+/// Shared by the native app and the headless harness.
+pub(crate) fn render_frame(
+    ctx: &egui::Context,
+    render_fn: &Value,
+    evaluator: &dyn Evaluator,
+) -> Result<Value, anyhow::Error> {
+    let result = egui::CentralPanel::default()
+        .show(ctx, |ui| {
             // Bridge the UI context so native functions can use it
             bridge::with_ui_context(ui, || {
                 // Construct the 'ui' object to pass to the script
@@ -39,14 +229,12 @@ impl eframe::App for AchronymeApp {
                 let ui_arg = Value::Null;
 
                 // Execute the script's render function
-                // We ignore errors for now in the render loop to avoid crashing
-                let _ = self.evaluator.call(&self.render_fn, vec![ui_arg]);
-            });
-        });
-
-        // Request constant repaint for animations and async signal updates
-        ctx.request_repaint();
-    }
+                evaluator.render(ctx, render_fn, vec![ui_arg])
+            })
+        })
+        .inner;
+    components::show_toasts(ctx);
+    result
 }
 
 pub fn run_native(
@@ -54,6 +242,8 @@ pub fn run_native(
     render_fn: Value,
     evaluator: Box<dyn Evaluator>,
     options: eframe::NativeOptions, // Add this parameter
+    errors: ErrorHandling,
+    continuous: bool,
 ) -> eframe::Result<()> {
     let evaluator_rc = Rc::new(evaluator);
 
@@ -62,11 +252,114 @@ pub fn run_native(
         options, // Use passed options
         Box::new(move |cc| {
             setup_custom_fonts(&cc.egui_ctx);
-            Ok(Box::new(AchronymeApp::new(render_fn, evaluator_rc)))
+            egui_extras::install_image_loaders(&cc.egui_ctx);
+            let app = AchronymeApp::new(render_fn, evaluator_rc, errors).continuous(continuous);
+            Ok(Box::new(app))
         }),
     )
 }
 
-fn setup_custom_fonts(ctx: &egui::Context) {
+pub(crate) fn setup_custom_fonts(ctx: &egui::Context) {
     ctx.set_visuals(egui::Visuals::dark());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Fails every render call with a fixed message and records hook calls
+    struct FailingEvaluator {
+        hook_calls: Rc<RefCell<Vec<Value>>>,
+    }
+
+    impl Evaluator for FailingEvaluator {
+        fn call(&self, func: &Value, args: Vec<Value>) -> Result<Value, anyhow::Error> {
+            match func {
+                Value::String(s) if s == "hook" => {
+                    self.hook_calls.borrow_mut().extend(args);
+                    Ok(Value::Null)
+                }
+                _ => Err(anyhow::Error::new(ScriptError {
+                    message: "boom".to_string(),
+                    stack: vec!["<main>".to_string(), "<lambda@3>".to_string()],
+                })),
+            }
+        }
+    }
+
+    /// Renders nothing and never asks for a repaint
+    struct IdleEvaluator;
+
+    impl Evaluator for IdleEvaluator {
+        fn call(&self, _func: &Value, _args: Vec<Value>) -> Result<Value, anyhow::Error> {
+            Ok(Value::Null)
+        }
+    }
+
+    fn app(halt: bool) -> (AchronymeApp, Rc<RefCell<Vec<Value>>>) {
+        let hook_calls = Rc::new(RefCell::new(Vec::new()));
+        let evaluator: Box<dyn Evaluator> = Box::new(FailingEvaluator {
+            hook_calls: hook_calls.clone(),
+        });
+        let errors = ErrorHandling {
+            halt,
+            on_error: Some(Value::String("hook".to_string())),
+        };
+        let app = AchronymeApp::new(Value::Null, Rc::new(evaluator), errors);
+        (app, hook_calls)
+    }
+
+    fn run_frames(app: &mut AchronymeApp, frames: usize) {
+        let ctx = egui::Context::default();
+        for _ in 0..frames {
+            let _ = ctx.run(Default::default(), |ctx| app.show(ctx));
+        }
+    }
+
+    #[test]
+    fn repeated_errors_are_reported_once() {
+        let (mut app, hook_calls) = app(false);
+        run_frames(&mut app, 5);
+
+        let failure = app.failure.as_ref().expect("error should be kept");
+        assert_eq!(failure.error.message, "boom");
+        assert_eq!(failure.repeats, 5);
+        assert!(!app.halted);
+
+        let calls = hook_calls.borrow();
+        assert_eq!(calls.len(), 1);
+        let Value::Record(r) = &calls[0] else {
+            panic!("expected error record, got {:?}", calls[0]);
+        };
+        assert_eq!(
+            r.read().get("message"),
+            Some(&Value::String("boom".to_string()))
+        );
+    }
+
+    #[test]
+    fn halting_stops_calling_the_render_function() {
+        let (mut app, hook_calls) = app(true);
+        run_frames(&mut app, 3);
+
+        assert!(app.halted);
+        assert_eq!(app.failure.as_ref().map(|f| f.repeats), Some(1));
+        assert_eq!(hook_calls.borrow().len(), 1);
+    }
+
+    #[test]
+    fn idle_app_only_repaints_when_continuous() {
+        for continuous in [false, true] {
+            let evaluator: Box<dyn Evaluator> = Box::new(IdleEvaluator);
+            let mut app =
+                AchronymeApp::new(Value::Null, Rc::new(evaluator), ErrorHandling::default())
+                    .continuous(continuous);
+            let ctx = egui::Context::default();
+            for _ in 0..3 {
+                let _ = ctx.run(Default::default(), |ctx| app.show(ctx));
+            }
+            assert_eq!(ctx.has_requested_repaint(), continuous);
+        }
+    }
+}
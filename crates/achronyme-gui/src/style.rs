@@ -51,10 +51,11 @@ impl StyleConfig {
     /// The core parser engine.
     /// Transforms "bg-red-500 p-4" -> StyleConfig
     pub fn parse_tailwind(style_str: &str) -> Self {
-        let mut config = Self::default();
-
         // Default font size (base)
-        config.font_size = Some(14.0);
+        let mut config = Self {
+            font_size: Some(14.0),
+            ..Self::default()
+        };
 
         for token in style_str.split_whitespace() {
             config.apply_token(token);
@@ -7,6 +7,9 @@
 //!
 //! - **Completion**: Provides completion items for functions, keywords, constants, and types
 //! - **Signatures**: Function signature information for signature help
+//! - **Semantic index**: Scope-aware resolution of identifiers to their bindings
+//! - **Type checking**: Static checking of type annotations, with the inferred
+//!   types of unannotated `let`/`mut` bindings
 //!
 //! # Example
 //!
@@ -24,11 +27,24 @@
 //! ```
 
 pub mod completion;
+pub mod semantic;
 pub mod signatures;
+pub mod typecheck;
+mod types;
 
 // Re-export main types for convenience
 pub use completion::{
     get_all_completions, get_constant_completions, get_function_completions,
     get_keyword_completions, get_type_completions, CompletionEntry, CompletionKind,
 };
+pub use semantic::{
+    Access, Call, Export, Import, ImportItem, Namespace, Occurrence, SemanticIndex, Symbol,
+    SymbolId, SymbolKind,
+};
 pub use signatures::{get_all_signatures, get_signature, FunctionSignature, ParameterInfo};
+pub use typecheck::{TypeCheck, TypeError};
+
+/// Whether a name is a predefined constant such as `PI` or `i`
+pub use achronyme_metadata::is_constant;
+/// The argument a `|>` pipeline passes its value as
+pub use achronyme_metadata::pipe_slot;
@@ -0,0 +1,1099 @@
+//! Scope-aware name resolution for Achronyme source
+//!
+//! Builds an index that resolves every identifier to the binding it refers
+//! to, following the language's lexical scoping: `let`/`mut` bindings are
+//! visible from the next statement to the end of their block, lambda
+//! parameters, `for` variables, `catch` variables and match arm bindings
+//! only inside their bodies, and inner bindings shadow outer ones.
+//!
+//! The AST carries no source positions, so the index walks the Pest parse
+//! tree directly. Positions are byte offsets into the source.
+//!
+//! Along the way it notes what editors colour and annotate: record field
+//! names, builtin type names and calls of named functions.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use achronyme_parser::pest_parser::{is_pipe_placeholder, reference_union, Rule, SOCParser};
+use pest::iterators::Pair;
+use pest::Parser;
+
+/// Index of a symbol in [`SemanticIndex::symbols`]
+pub type SymbolId = usize;
+
+/// What introduced a binding
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymbolKind {
+    /// `let x = ...`, including names bound by `let` destructuring
+    Let,
+    /// `mut x = ...`, including names bound by `mut` destructuring
+    Mut,
+    /// Lambda parameter
+    Parameter,
+    /// Name bound by a `match` arm pattern
+    PatternBinding,
+    /// `for (x in ...)` variable
+    LoopVariable,
+    /// `catch (e)` variable
+    CatchVariable,
+    /// `import { x } from "..."`; `aliased` for `import { y as x }`
+    Import { aliased: bool },
+    /// `type Name = ...`
+    TypeAlias,
+    /// `type Name = A { ... } | B`
+    DataType,
+    /// A data type's variant: its constructor, or the value itself when it
+    /// has no fields
+    Variant,
+}
+
+impl SymbolKind {
+    /// Whether the binding can be reassigned
+    pub fn is_mutable(self) -> bool {
+        matches!(self, SymbolKind::Mut)
+    }
+}
+
+/// A binding introduced somewhere in the source
+#[derive(Clone, Debug, PartialEq)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Where the name is written in its declaration
+    pub span: Range<usize>,
+    /// Declared at the top level of the file rather than in a nested scope
+    pub top_level: bool,
+    /// Parameter names, when bound directly to a lambda
+    pub parameters: Option<Vec<String>>,
+    /// The `let`/`mut` keyword of the declaring statement
+    pub keyword: Option<Range<usize>>,
+}
+
+/// How an identifier occurrence uses its symbol
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    /// The name being bound
+    Declaration,
+    Read,
+    /// Target of `=` or a compound assignment
+    Write,
+    /// Listed without an alias in `export { ... }`, so it also names the export
+    Export,
+}
+
+/// One identifier in the source
+#[derive(Clone, Debug, PartialEq)]
+pub struct Occurrence {
+    pub name: String,
+    pub span: Range<usize>,
+    /// The binding it resolves to; `None` for builtins, constants and
+    /// undefined names
+    pub symbol: Option<SymbolId>,
+    pub access: Access,
+    pub namespace: Namespace,
+}
+
+/// A call of a function by name: `name(args)`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Call {
+    /// The function name
+    pub callee: Range<usize>,
+    /// Each argument expression
+    pub arguments: Vec<Range<usize>>,
+}
+
+/// An `import { ... } from "path"` statement
+#[derive(Clone, Debug, PartialEq)]
+pub struct Import {
+    /// The path as written, without quotes
+    pub module: String,
+    /// The string literal, quotes included
+    pub module_span: Range<usize>,
+    pub items: Vec<ImportItem>,
+}
+
+/// One name in an import list
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportItem {
+    /// The name the other module exports
+    pub name: String,
+    pub span: Range<usize>,
+    /// The local binding (the alias, if any)
+    pub symbol: SymbolId,
+}
+
+/// One name in an `export { ... }` list
+#[derive(Clone, Debug, PartialEq)]
+pub struct Export {
+    /// The name importers see (the alias, if any)
+    pub name: String,
+    pub span: Range<usize>,
+    /// The exported binding; `None` if it is undefined
+    pub symbol: Option<SymbolId>,
+}
+
+/// Resolved bindings and identifier occurrences of one source file
+#[derive(Clone, Debug, Default)]
+pub struct SemanticIndex {
+    symbols: Vec<Symbol>,
+    /// Sorted by position
+    occurrences: Vec<Occurrence>,
+    imports: Vec<Import>,
+    exports: Vec<Export>,
+    /// Record field names, sorted by position
+    fields: Vec<Range<usize>>,
+    /// Builtin type names such as `Number` or `Vector`, sorted by position
+    type_names: Vec<Range<usize>>,
+    calls: Vec<Call>,
+}
+
+impl SemanticIndex {
+    /// Parse `source` and resolve its names
+    pub fn build(source: &str) -> Result<Self, String> {
+        let mut pairs =
+            SOCParser::parse(Rule::program, source).map_err(|e| format!("Parse error: {}", e))?;
+        let mut resolver = Resolver::default();
+        if let Some(program) = pairs.next() {
+            resolver.push_scope();
+            resolver.walk(program);
+        }
+        let mut occurrences = resolver.occurrences;
+        occurrences.sort_by_key(|o| o.span.start);
+        let mut fields = resolver.fields;
+        fields.sort_by_key(|span| span.start);
+        let mut type_names = resolver.type_names;
+        type_names.sort_by_key(|span| span.start);
+        let mut calls = resolver.calls;
+        calls.sort_by_key(|call| call.callee.start);
+        Ok(Self {
+            symbols: resolver.symbols,
+            occurrences,
+            imports: resolver.imports,
+            exports: resolver.exports,
+            fields,
+            type_names,
+            calls,
+        })
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    pub fn symbol(&self, id: SymbolId) -> &Symbol {
+        &self.symbols[id]
+    }
+
+    /// Every identifier in source order
+    pub fn occurrences(&self) -> &[Occurrence] {
+        &self.occurrences
+    }
+
+    /// The identifier touching `offset` (a cursor right after a name counts)
+    pub fn occurrence_at(&self, offset: usize) -> Option<&Occurrence> {
+        let end = self.occurrences.partition_point(|o| o.span.start <= offset);
+        self.occurrences[..end]
+            .iter()
+            .rev()
+            .take(2)
+            .find(|o| o.span.contains(&offset) || o.span.end == offset)
+    }
+
+    /// The symbol named at `offset`, if it is a local binding
+    pub fn symbol_at(&self, offset: usize) -> Option<SymbolId> {
+        self.occurrence_at(offset)?.symbol
+    }
+
+    /// Import statements in source order
+    pub fn imports(&self) -> &[Import] {
+        &self.imports
+    }
+
+    /// Exported names in source order
+    pub fn exports(&self) -> &[Export] {
+        &self.exports
+    }
+
+    /// The export importers know as `name`
+    pub fn export(&self, name: &str) -> Option<&Export> {
+        self.exports.iter().find(|e| e.name == name)
+    }
+
+    /// The import list entry touching `offset`, with its statement
+    pub fn import_item_at(&self, offset: usize) -> Option<(&Import, &ImportItem)> {
+        self.imports.iter().find_map(|import| {
+            let item = import
+                .items
+                .iter()
+                .find(|i| i.span.contains(&offset) || i.span.end == offset)?;
+            Some((import, item))
+        })
+    }
+
+    /// All occurrences of a symbol, its declaration included
+    pub fn references(&self, id: SymbolId) -> impl Iterator<Item = &Occurrence> {
+        self.occurrences
+            .iter()
+            .filter(move |o| o.symbol == Some(id))
+    }
+
+    /// Record field names in literals, types, patterns and field access
+    pub fn fields(&self) -> &[Range<usize>] {
+        &self.fields
+    }
+
+    /// Builtin type names in annotations and type patterns
+    pub fn type_names(&self) -> &[Range<usize>] {
+        &self.type_names
+    }
+
+    /// Calls of named functions in source order
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+}
+
+/// Bindings live in two namespaces: values and type aliases
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Namespace {
+    Value,
+    Type,
+}
+
+#[derive(Default)]
+struct Resolver {
+    symbols: Vec<Symbol>,
+    occurrences: Vec<Occurrence>,
+    imports: Vec<Import>,
+    exports: Vec<Export>,
+    fields: Vec<Range<usize>>,
+    type_names: Vec<Range<usize>>,
+    calls: Vec<Call>,
+    scopes: Vec<HashMap<(Namespace, String), SymbolId>>,
+    /// Starts of the `_` arguments that stand for a piped value
+    placeholders: HashSet<usize>,
+}
+
+impl Resolver {
+    fn push_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn scoped(&mut self, f: impl FnOnce(&mut Self)) {
+        self.push_scope();
+        f(self);
+        self.pop_scope();
+    }
+
+    fn declare(&mut self, ident: Pair<Rule>, kind: SymbolKind) -> SymbolId {
+        let namespace = match kind {
+            SymbolKind::TypeAlias | SymbolKind::DataType => Namespace::Type,
+            _ => Namespace::Value,
+        };
+        let name = ident.as_str().to_string();
+        let span = span_of(&ident);
+        let id = self.symbols.len();
+        self.symbols.push(Symbol {
+            name: name.clone(),
+            kind,
+            span: span.clone(),
+            top_level: self.scopes.len() == 1,
+            parameters: None,
+            keyword: None,
+        });
+        self.occurrences.push(Occurrence {
+            name: name.clone(),
+            span,
+            symbol: Some(id),
+            access: Access::Declaration,
+            namespace,
+        });
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert((namespace, name), id);
+        }
+        id
+    }
+
+    fn lookup(&self, namespace: Namespace, name: &str) -> Option<SymbolId> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&(namespace, name.to_string())).copied())
+    }
+
+    fn reference(
+        &mut self,
+        ident: Pair<Rule>,
+        namespace: Namespace,
+        access: Access,
+    ) -> Option<SymbolId> {
+        let name = ident.as_str();
+        let symbol = self.lookup(namespace, name);
+        self.occurrences.push(Occurrence {
+            name: name.to_string(),
+            span: span_of(&ident),
+            symbol,
+            access,
+            namespace,
+        });
+        symbol
+    }
+
+    /// Walk any node, dispatching on the constructs that bind or use names
+    fn walk(&mut self, pair: Pair<Rule>) {
+        match pair.as_rule() {
+            Rule::identifier if self.placeholders.contains(&pair.as_span().start()) => {}
+            Rule::identifier => {
+                self.reference(pair, Namespace::Value, Access::Read);
+            }
+            Rule::pipeline => {
+                for (position, part) in pair
+                    .into_inner()
+                    .filter(|part| part.as_rule() != Rule::pipe_op)
+                    .enumerate()
+                {
+                    let call = (position > 0).then(|| piped_call(part.clone())).flatten();
+                    if let Some(call) = call {
+                        self.placeholders.extend(
+                            call.into_inner()
+                                .filter(is_pipe_placeholder)
+                                .filter_map(|arg| {
+                                    arg.into_inner()
+                                        .flatten()
+                                        .find(|p| p.as_rule() == Rule::identifier)
+                                })
+                                .map(|placeholder| placeholder.as_span().start()),
+                        );
+                    }
+                    self.walk(part);
+                }
+            }
+            Rule::type_reference => {
+                for ident in pair.into_inner() {
+                    self.reference(ident, Namespace::Type, Access::Read);
+                }
+            }
+            Rule::simple_type | Rule::vector_type | Rule::any_type => {
+                self.type_names.push(span_of(&pair));
+            }
+            Rule::tensor_type => {
+                let start = pair.as_span().start();
+                self.type_names.push(start..start + "Tensor".len());
+                self.walk_children(pair);
+            }
+            Rule::postfix_expression => {
+                self.call(&pair);
+                self.walk_children(pair);
+            }
+            Rule::block => self.scoped(|r| r.walk_children(pair)),
+            Rule::let_statement => self.binding_statement(pair, SymbolKind::Let),
+            Rule::mut_statement => self.binding_statement(pair, SymbolKind::Mut),
+            Rule::type_alias_statement => {
+                let mut inner = pair.into_inner();
+                let name = inner.next();
+                // `type Color = Red | Green` names no known type: its names are variants
+                let variants = inner
+                    .clone()
+                    .next()
+                    .and_then(reference_union)
+                    .filter(|names| {
+                        names
+                            .iter()
+                            .all(|n| self.lookup(Namespace::Type, n.as_str()).is_none())
+                    });
+                if let Some(variants) = variants {
+                    if let Some(name) = name {
+                        self.declare(name, SymbolKind::DataType);
+                    }
+                    for variant in variants {
+                        self.declare(variant, SymbolKind::Variant);
+                    }
+                    return;
+                }
+                inner.for_each(|p| self.walk(p));
+                if let Some(name) = name {
+                    self.declare(name, SymbolKind::TypeAlias);
+                }
+            }
+            Rule::adt_statement => {
+                let mut inner = pair.into_inner();
+                // Declared first: variant fields may refer to the type itself
+                if let Some(name) = inner.next() {
+                    self.declare(name, SymbolKind::DataType);
+                }
+                for variant in inner {
+                    let mut parts = variant.into_inner();
+                    let Some(name) = parts.next() else { continue };
+                    let fields = parts.next();
+                    let id = self.declare(name, SymbolKind::Variant);
+                    if let Some(fields) = fields {
+                        // Constructors take the fields in order
+                        let names = fields
+                            .clone()
+                            .into_inner()
+                            .filter_map(|field| {
+                                field.into_inner().find(|p| p.as_rule() == Rule::identifier)
+                            })
+                            .map(|name| name.as_str().to_string())
+                            .collect();
+                        self.symbols[id].parameters = Some(names);
+                        self.walk(fields);
+                    }
+                }
+            }
+            Rule::assignment => self.assignment(pair),
+            Rule::lambda | Rule::async_lambda => self.lambda(pair),
+            Rule::for_in_loop => {
+                let mut inner = pair.into_inner();
+                let (Some(var), Some(iterable)) = (inner.next(), inner.next()) else {
+                    return;
+                };
+                self.walk(iterable);
+                self.scoped(|r| {
+                    r.declare(var, SymbolKind::LoopVariable);
+                    inner.for_each(|p| r.walk(p));
+                });
+            }
+            Rule::try_catch_expr => {
+                let mut inner = pair.into_inner();
+                if let Some(body) = inner.next() {
+                    self.walk(body);
+                }
+                let Some(error) = inner.next() else { return };
+                self.scoped(|r| {
+                    r.declare(error, SymbolKind::CatchVariable);
+                    inner.for_each(|p| r.walk(p));
+                });
+            }
+            Rule::match_arm => self.scoped(|r| {
+                for part in pair.into_inner() {
+                    match part.as_rule() {
+                        Rule::pattern => r.pattern(part, SymbolKind::PatternBinding),
+                        _ => r.walk(part),
+                    }
+                }
+            }),
+            Rule::import_statement => self.import(pair),
+            Rule::export_statement => {
+                for item in pair.into_inner().flat_map(|list| list.into_inner()) {
+                    let mut names = item.into_inner();
+                    let Some(name) = names.next() else { continue };
+                    let alias = names.next();
+                    let access = match alias {
+                        Some(_) => Access::Read,
+                        None => Access::Export,
+                    };
+                    let external = alias.unwrap_or_else(|| name.clone());
+                    // Exports may name a value or a type alias
+                    let namespace = match self.lookup(Namespace::Value, name.as_str()) {
+                        None if self.lookup(Namespace::Type, name.as_str()).is_some() => {
+                            Namespace::Type
+                        }
+                        _ => Namespace::Value,
+                    };
+                    let symbol = self.reference(name, namespace, access);
+                    self.exports.push(Export {
+                        name: external.as_str().to_string(),
+                        span: span_of(&external),
+                        symbol,
+                    });
+                }
+            }
+            // Field names are not variables
+            Rule::record_field | Rule::record_type_field | Rule::field_op => {
+                for part in pair.into_inner() {
+                    match part.as_rule() {
+                        Rule::identifier | Rule::field_identifier => {
+                            self.fields.push(span_of(&part))
+                        }
+                        Rule::mut_keyword | Rule::optional_marker => {}
+                        _ => self.walk(part),
+                    }
+                }
+            }
+            _ => self.walk_children(pair),
+        }
+    }
+
+    fn import(&mut self, pair: Pair<Rule>) {
+        let mut items = Vec::new();
+        let mut module = None;
+        for part in pair.into_inner() {
+            match part.as_rule() {
+                Rule::import_list => {
+                    for item in part.into_inner() {
+                        let mut names = item.into_inner();
+                        let Some(name) = names.next() else { continue };
+                        let symbol = match names.next() {
+                            Some(alias) => {
+                                self.declare(alias, SymbolKind::Import { aliased: true })
+                            }
+                            None => {
+                                self.declare(name.clone(), SymbolKind::Import { aliased: false })
+                            }
+                        };
+                        items.push(ImportItem {
+                            name: name.as_str().to_string(),
+                            span: span_of(&name),
+                            symbol,
+                        });
+                    }
+                }
+                Rule::module_path => module = Some(part),
+                _ => {}
+            }
+        }
+        if let Some(module) = module {
+            let literal = module.as_str();
+            self.imports.push(Import {
+                module: literal.trim_matches('"').to_string(),
+                module_span: span_of(&module),
+                items,
+            });
+        }
+    }
+
+    fn walk_children(&mut self, pair: Pair<Rule>) {
+        for child in pair.into_inner() {
+            self.walk(child);
+        }
+    }
+
+    /// `let`/`mut`: the initializer can't see the new binding
+    fn binding_statement(&mut self, pair: Pair<Rule>, kind: SymbolKind) {
+        // `let` and `mut` are both three letters
+        let start = pair.as_span().start();
+        let keyword = start..start + 3;
+        let mut target = None;
+        let mut initializer = None;
+        for part in pair.into_inner() {
+            match part.as_rule() {
+                Rule::identifier | Rule::destructuring_pattern if target.is_none() => {
+                    target = Some(part)
+                }
+                _ => {
+                    if part.as_rule() == Rule::expr {
+                        initializer = Some(part.clone());
+                    }
+                    self.walk(part);
+                }
+            }
+        }
+        let first = self.symbols.len();
+        match target {
+            Some(ident) if ident.as_rule() == Rule::identifier => {
+                let id = self.declare(ident, kind);
+                self.symbols[id].parameters =
+                    initializer.and_then(as_lambda).map(lambda_parameters);
+            }
+            Some(pattern) => self.pattern(pattern, kind),
+            None => {}
+        }
+        for symbol in &mut self.symbols[first..] {
+            symbol.keyword = Some(keyword.clone());
+        }
+    }
+
+    /// Note `name(args)` calls
+    fn call(&mut self, pair: &Pair<Rule>) {
+        let mut parts = pair.clone().into_inner();
+        let (Some(primary), Some(op)) = (parts.next(), parts.next()) else {
+            return;
+        };
+        let mut atoms = primary.into_inner();
+        let (Some(callee), None) = (atoms.next(), atoms.next()) else {
+            return;
+        };
+        let Some(call) = op.into_inner().next() else {
+            return;
+        };
+        if callee.as_rule() != Rule::identifier || call.as_rule() != Rule::call_op {
+            return;
+        }
+        self.calls.push(Call {
+            callee: span_of(&callee),
+            arguments: call.into_inner().map(|arg| span_of(&arg)).collect(),
+        });
+    }
+
+    fn assignment(&mut self, pair: Pair<Rule>) {
+        let mut inner = pair.into_inner();
+        let Some(target) = inner.next() else { return };
+        // Evaluate the value first, like the compiler does
+        inner.for_each(|p| self.walk(p));
+
+        // `x = ...` writes x; `x.field = ...` and `x[i] = ...` read it
+        let mut parts = target.into_inner();
+        let primary = parts.next();
+        let is_plain = parts.peek().is_none();
+        parts.for_each(|p| self.walk(p));
+        let Some(primary) = primary else { return };
+        let mut atoms = primary.clone().into_inner();
+        match (atoms.next(), atoms.next()) {
+            (Some(ident), None) if ident.as_rule() == Rule::identifier => {
+                let access = if is_plain {
+                    Access::Write
+                } else {
+                    Access::Read
+                };
+                self.reference(ident, Namespace::Value, access);
+            }
+            _ => self.walk(primary),
+        }
+    }
+
+    fn lambda(&mut self, pair: Pair<Rule>) {
+        self.scoped(|r| {
+            for part in pair.into_inner() {
+                match part.as_rule() {
+                    Rule::typed_lambda_params => {
+                        for param in part.into_inner() {
+                            let mut inner = param.into_inner();
+                            let Some(name) = inner.next() else { continue };
+                            // Types and defaults are evaluated before the parameter exists
+                            inner.for_each(|p| r.walk(p));
+                            r.declare(name, SymbolKind::Parameter);
+                        }
+                    }
+                    _ => r.walk(part),
+                }
+            }
+        });
+    }
+
+    /// Declare the names bound by a pattern, walking default values
+    fn pattern(&mut self, pair: Pair<Rule>, kind: SymbolKind) {
+        match pair.as_rule() {
+            Rule::variable_pattern => {
+                if let Some(ident) = pair.into_inner().next() {
+                    // A bare variant name matches the variant instead of binding
+                    let variant = self.lookup(Namespace::Value, ident.as_str());
+                    if variant.is_some_and(|id| self.symbols[id].kind == SymbolKind::Variant) {
+                        self.reference(ident, Namespace::Value, Access::Read);
+                    } else {
+                        self.declare(ident, kind);
+                    }
+                }
+            }
+            Rule::variant_pattern => {
+                let mut inner = pair.into_inner();
+                if let Some(name) = inner.next() {
+                    self.reference(name, Namespace::Value, Access::Read);
+                }
+                inner.for_each(|fields| self.pattern(fields, kind));
+            }
+            Rule::rest_pattern => {
+                if let Some(ident) = pair.into_inner().next() {
+                    self.declare(ident, kind);
+                }
+            }
+            Rule::record_pattern_field => {
+                let mut inner = pair.into_inner();
+                let Some(field) = inner.next() else { return };
+                let mut bound = false;
+                for part in inner {
+                    match part.as_rule() {
+                        // `field: pattern` binds inside the pattern
+                        Rule::pattern => {
+                            bound = true;
+                            self.fields.push(span_of(&field));
+                            self.pattern(part, kind);
+                        }
+                        _ => self.walk(part),
+                    }
+                }
+                // `{ field }` binds the field name itself
+                if !bound {
+                    self.declare(field, kind);
+                }
+            }
+            Rule::pattern_with_default => {
+                let mut defaults = Vec::new();
+                for part in pair.into_inner() {
+                    match part.as_rule() {
+                        Rule::pattern => self.pattern(part, kind),
+                        _ => defaults.push(part),
+                    }
+                }
+                defaults.into_iter().for_each(|p| self.walk(p));
+            }
+            Rule::type_pattern => self.type_names.push(span_of(&pair)),
+            Rule::literal_pattern | Rule::wildcard_pattern => {}
+            _ => {
+                for part in pair.into_inner() {
+                    self.pattern(part, kind);
+                }
+            }
+        }
+    }
+}
+
+/// The lambda an expression consists of, if it is nothing but a lambda
+pub(crate) fn as_lambda(pair: Pair<Rule>) -> Option<Pair<Rule>> {
+    if pair.as_rule() == Rule::lambda {
+        return Some(pair);
+    }
+    // `-f` and `!f` aren't lambdas
+    if pair.as_rule() == Rule::unary && pair.as_str().starts_with(['-', '!']) {
+        return None;
+    }
+    let mut inner = pair.into_inner();
+    match (inner.next(), inner.next()) {
+        (Some(only), None) => as_lambda(only),
+        _ => None,
+    }
+}
+
+/// Parameter names of a lambda
+fn lambda_parameters(lambda: Pair<Rule>) -> Vec<String> {
+    lambda
+        .into_inner()
+        .filter(|part| part.as_rule() == Rule::typed_lambda_params)
+        .flat_map(|params| params.into_inner())
+        .filter_map(|param| param.into_inner().next())
+        .map(|name| name.as_str().to_string())
+        .collect()
+}
+
+/// The call a `|>` passes its value to: the right-hand side when it ends
+/// with a call, like `map(f)` or `obj.method()`
+pub(crate) fn piped_call(function: Pair<Rule>) -> Option<Pair<Rule>> {
+    let mut pair = function;
+    while pair.as_rule() != Rule::postfix_expression {
+        let wraps = match pair.as_rule() {
+            Rule::expr
+            | Rule::pipeline
+            | Rule::standalone_range
+            | Rule::logical_or
+            | Rule::logical_and
+            | Rule::comparison
+            | Rule::additive
+            | Rule::multiplicative
+            | Rule::power
+            | Rule::primary => true,
+            Rule::unary => !pair.as_str().starts_with(['-', '!']),
+            _ => false,
+        };
+        let mut inner = pair.into_inner();
+        pair = match (inner.next(), inner.next()) {
+            (Some(only), None) if wraps => only,
+            _ => return None,
+        };
+    }
+    let op = pair.into_inner().skip(1).last()?.into_inner().next()?;
+    (op.as_rule() == Rule::call_op).then_some(op)
+}
+
+pub(crate) fn span_of(pair: &Pair<Rule>) -> Range<usize> {
+    let span = pair.as_span();
+    span.start()..span.end()
+}
+
+/// Whether `name` can be used as a binding name
+pub fn is_valid_identifier(name: &str) -> bool {
+    SOCParser::parse(Rule::identifier, name).is_ok_and(|pairs| pairs.as_str() == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Resolve the occurrence starting at the `n`th match of `needle`
+    fn resolve(source: &str, needle: &str, n: usize) -> Option<SymbolId> {
+        let offset = source.match_indices(needle).nth(n).unwrap().0;
+        let index = SemanticIndex::build(source).unwrap();
+        index.symbol_at(offset)
+    }
+
+    fn reference_spans(source: &str, offset: usize) -> Vec<Range<usize>> {
+        let index = SemanticIndex::build(source).unwrap();
+        let id = index.symbol_at(offset).unwrap();
+        index.references(id).map(|o| o.span.clone()).collect()
+    }
+
+    #[test]
+    fn test_let_and_use() {
+        let source = "let x = 1\nlet y = x + 1";
+        let index = SemanticIndex::build(source).unwrap();
+        let x = index.symbol_at(4).unwrap();
+        assert_eq!(index.symbol(x).kind, SymbolKind::Let);
+        assert_eq!(index.symbol_at(source.len() - 5), Some(x));
+        assert_eq!(reference_spans(source, 4), vec![4..5, 18..19]);
+    }
+
+    #[test]
+    fn test_shadowing_in_lambda_and_block() {
+        let source = "let x = 1\nlet f = (x) => x * 2\nlet g = do { let x = 3; x }\nx";
+        let outer = resolve(source, "x", 0);
+        assert!(outer.is_some());
+        assert_ne!(resolve(source, "x", 2), outer); // lambda body uses the param
+        assert_eq!(resolve(source, "x", 2), resolve(source, "x", 1));
+        assert_eq!(resolve(source, "x", 4), resolve(source, "x", 3));
+        assert_ne!(resolve(source, "x", 4), outer);
+        assert_eq!(resolve(source, "x", 5), outer); // the block's x is gone
+    }
+
+    #[test]
+    fn test_initializer_does_not_see_its_binding() {
+        let source = "let n = 1\nlet n = n + 1";
+        assert_eq!(resolve(source, "n", 2), resolve(source, "n", 0));
+        assert_ne!(resolve(source, "n", 1), resolve(source, "n", 0));
+    }
+
+    #[test]
+    fn test_strings_comments_and_fields_are_not_references() {
+        let source = "let name = \"name\"\n// name\nlet r = { name: name }\nr.name";
+        assert_eq!(reference_spans(source, 4).len(), 2);
+    }
+
+    #[test]
+    fn test_destructuring_match_for_and_catch() {
+        let source = "let { a, b: [c, ...rest] } = v\n\
+                      match a { { k: z } if (z > c) => z, _ => rest }\n\
+                      for (i in rest) { i }\n\
+                      try { i } catch (e) { e }";
+        let index = SemanticIndex::build(source).unwrap();
+        let kinds: Vec<_> = index
+            .symbols()
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("a", SymbolKind::Let),
+                ("c", SymbolKind::Let),
+                ("rest", SymbolKind::Let),
+                ("z", SymbolKind::PatternBinding),
+                ("i", SymbolKind::LoopVariable),
+                ("e", SymbolKind::CatchVariable),
+            ]
+        );
+        // `b` is a field name, `v` is undefined, the `i` in try is out of scope
+        let unresolved: Vec<_> = index
+            .occurrences()
+            .iter()
+            .filter(|o| o.symbol.is_none())
+            .map(|o| o.name.as_str())
+            .collect();
+        assert_eq!(unresolved, ["v", "i"]);
+        assert_eq!(index.references(3).count(), 3); // z: binding, guard, body
+    }
+
+    #[test]
+    fn test_assignment_imports_exports_and_types() {
+        let source = "import { mean as avg, std } from \"stats\"\n\
+                      type P = { x: Number }\n\
+                      mut total: P = avg([1])\n\
+                      total = std(total)\n\
+                      export { total, P as Point }";
+        let index = SemanticIndex::build(source).unwrap();
+        let total = index
+            .symbols()
+            .iter()
+            .position(|s| s.name == "total")
+            .unwrap();
+        let accesses: Vec<_> = index.references(total).map(|o| o.access).collect();
+        assert_eq!(
+            accesses,
+            [
+                Access::Declaration,
+                Access::Write,
+                Access::Read,
+                Access::Export
+            ]
+        );
+        let p = index.symbols().iter().position(|s| s.name == "P").unwrap();
+        assert_eq!(index.references(p).count(), 3);
+        assert_eq!(
+            index.symbol(index.symbol_at(17).unwrap()).kind,
+            SymbolKind::Import { aliased: true }
+        );
+        assert!(index.occurrences().iter().all(|o| o.name != "mean"));
+    }
+
+    #[test]
+    fn test_pipe_placeholders_are_not_names() {
+        let source = "let f = (a, b) => a\n2 |> f(_, 1) |> f((_), _)\nf(_, 1)";
+        let index = SemanticIndex::build(source).unwrap();
+        let unresolved: Vec<_> = index
+            .occurrences()
+            .iter()
+            .filter(|o| o.symbol.is_none())
+            .map(|o| o.span.start)
+            .collect();
+        // Only the `_` outside a pipeline
+        assert_eq!(unresolved, [source.rfind('_').unwrap()]);
+    }
+
+    #[test]
+    fn test_data_types() {
+        let source = "type Tree = Leaf | Node { left: Tree, value: Number }\n\
+                      let t: Tree = Node(Leaf, 1)\n\
+                      match t { Leaf => 0, Node { left: Leaf, value } => value, n => n }";
+        let index = SemanticIndex::build(source).unwrap();
+        let kinds: Vec<_> = index
+            .symbols()
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("Tree", SymbolKind::DataType),
+                ("Leaf", SymbolKind::Variant),
+                ("Node", SymbolKind::Variant),
+                ("t", SymbolKind::Let),
+                ("value", SymbolKind::PatternBinding),
+                ("n", SymbolKind::PatternBinding),
+            ]
+        );
+        assert_eq!(
+            index.symbol(2).parameters.as_deref(),
+            Some(&["left".to_string(), "value".to_string()][..])
+        );
+        assert_eq!(index.references(0).count(), 3);
+        assert_eq!(index.references(1).count(), 4); // declaration, argument, two patterns
+        assert_eq!(index.references(2).count(), 3);
+        assert!(index.occurrences().iter().all(|o| o.symbol.is_some()));
+    }
+
+    #[test]
+    fn test_union_of_unknown_names_declares_variants() {
+        let source = "type Shape = { r: Number }\n\
+                      type Color = Red | Green\n\
+                      type Both = Shape | Color\n\
+                      match Red { Red => 1, Green => 2 }";
+        let index = SemanticIndex::build(source).unwrap();
+        let kinds: Vec<_> = index
+            .symbols()
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("Shape", SymbolKind::TypeAlias),
+                ("Color", SymbolKind::DataType),
+                ("Red", SymbolKind::Variant),
+                ("Green", SymbolKind::Variant),
+                ("Both", SymbolKind::TypeAlias),
+            ]
+        );
+        assert_eq!(index.references(2).count(), 3);
+        assert!(index.occurrences().iter().all(|o| o.symbol.is_some()));
+    }
+
+    #[test]
+    fn test_import_and_export_tables() {
+        let source = "import { mean as avg, std } from \"./stats\"\n\
+                      let total = avg([1])\n\
+                      let f = () => do { let inner = 1; inner }\n\
+                      export { total as sum, f, missing }";
+        let index = SemanticIndex::build(source).unwrap();
+        let [import] = index.imports() else {
+            panic!("expected one import")
+        };
+        assert_eq!(import.module, "./stats");
+        assert_eq!(&source[import.module_span.clone()], "\"./stats\"");
+        let items: Vec<_> = import
+            .items
+            .iter()
+            .map(|i| (i.name.as_str(), index.symbol(i.symbol).name.as_str()))
+            .collect();
+        assert_eq!(items, [("mean", "avg"), ("std", "std")]);
+        assert_eq!(index.import_item_at(10).unwrap().1.name, "mean");
+
+        let exports: Vec<_> = index
+            .exports()
+            .iter()
+            .map(|e| {
+                (
+                    e.name.as_str(),
+                    e.symbol.map(|id| index.symbol(id).name.as_str()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            exports,
+            [("sum", Some("total")), ("f", Some("f")), ("missing", None)]
+        );
+        assert_eq!(&source[index.export("sum").unwrap().span.clone()], "sum");
+
+        let top_level: Vec<_> = index
+            .symbols()
+            .iter()
+            .filter(|s| s.top_level)
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(top_level, ["avg", "std", "total", "f"]);
+    }
+
+    #[test]
+    fn test_lambda_defaults_see_earlier_params() {
+        let source = "(a, b = a) => a + b";
+        assert_eq!(resolve(source, "a", 1), resolve(source, "a", 0));
+        assert!(resolve(source, "a", 1).is_some());
+    }
+
+    #[test]
+    fn test_lambda_bindings_and_keywords() {
+        let source = "let sq = (x: Number, n = 2) => x^n\nmut m = -sq\nlet { a } = r";
+        let index = SemanticIndex::build(source).unwrap();
+        let parameters: Vec<_> = index
+            .symbols()
+            .iter()
+            .filter(|s| s.kind != SymbolKind::Parameter)
+            .map(|s| (s.name.as_str(), s.parameters.clone()))
+            .collect();
+        assert_eq!(
+            parameters,
+            [
+                ("sq", Some(vec!["x".to_string(), "n".to_string()])),
+                ("m", None),
+                ("a", None),
+            ]
+        );
+        let keywords: Vec<_> = index
+            .symbols()
+            .iter()
+            .filter_map(|s| s.keyword.clone())
+            .map(|keyword| &source[keyword])
+            .collect();
+        assert_eq!(keywords, ["let", "mut", "let"]);
+    }
+
+    #[test]
+    fn test_fields_type_names_and_calls() {
+        let source = "type P = { x: Number, v?: Vector }\n\
+                      let p: P = { x: 1, v: [2] }\n\
+                      let { x: px } = p\n\
+                      match p.v { Vector => max(px, p.x), _ => 0 }";
+        let index = SemanticIndex::build(source).unwrap();
+        let text = |spans: &[Range<usize>]| -> Vec<&str> {
+            spans.iter().map(|span| &source[span.clone()]).collect()
+        };
+        assert_eq!(text(index.fields()), ["x", "v", "x", "v", "x", "v", "x"]);
+        assert_eq!(text(index.type_names()), ["Number", "Vector", "Vector"]);
+        let [call] = index.calls() else {
+            panic!("expected one call")
+        };
+        assert_eq!(&source[call.callee.clone()], "max");
+        assert_eq!(text(&call.arguments), ["px", "p.x"]);
+        let p = index.occurrence_at(source.find("P =").unwrap()).unwrap();
+        assert_eq!(p.namespace, Namespace::Type);
+    }
+
+    #[test]
+    fn test_valid_identifier() {
+        assert!(is_valid_identifier("total_2"));
+        assert!(!is_valid_identifier("let"));
+        assert!(!is_valid_identifier("2x"));
+        assert!(!is_valid_identifier("a b"));
+    }
+}
@@ -0,0 +1,1923 @@
+//! Static checking of gradual type annotations
+//!
+//! Infers the type of every expression from its literals, operators, the
+//! annotations of the bindings it uses and the documented signatures of
+//! builtins, and reports what would fail at runtime: initializers and
+//! assignments that don't match an annotation, calls with the wrong number
+//! or type of arguments, and operators, calls and field access on values
+//! that may be `null`.
+//!
+//! Unannotated parameters and anything else the checker can't tell are
+//! `Any`, which is compatible with every type, so untyped code never
+//! produces errors. `if`, `while`, `&&`, `||` and `match` narrow the
+//! bindings they test: `x != null`, `typeof(x) == "Number"`, a bare `x` and
+//! type patterns such as `Number => ...` all refine `x` where they hold, and
+//! an `if` whose body always returns refines the statements after it.
+//!
+//! Names are resolved with a [`SemanticIndex`] of the same source. Imported
+//! bindings are `Any`; modules are checked one at a time.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ops::Range;
+
+use achronyme_metadata::docs::{self, BuiltinDoc};
+use achronyme_metadata::pipe_slot;
+use achronyme_parser::pest_parser::{is_pipe_placeholder, type_from_pair, Rule, SOCParser};
+use achronyme_parser::{Dimension, TypeAnnotation as Type};
+use pest::iterators::Pair;
+use pest::Parser;
+
+use crate::semantic::{
+    as_lambda, piped_call, span_of, Namespace, SemanticIndex, SymbolId, SymbolKind,
+};
+use crate::types::{self, is_assignable};
+
+/// A statically detected type error
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeError {
+    pub span: Range<usize>,
+    pub message: String,
+}
+
+/// The result of checking one source file
+#[derive(Clone, Debug, Default)]
+pub struct TypeCheck {
+    errors: Vec<TypeError>,
+    inferred: HashMap<SymbolId, Type>,
+}
+
+impl TypeCheck {
+    /// Check `source`, whose names `index` resolves
+    pub fn run(source: &str, index: &SemanticIndex) -> Result<Self, String> {
+        let mut pairs =
+            SOCParser::parse(Rule::program, source).map_err(|e| format!("Parse error: {}", e))?;
+        let mut checker = Checker::new(index);
+        if let Some(program) = pairs.next() {
+            let statements = program
+                .into_inner()
+                .filter(|part| part.as_rule() == Rule::top_level_expr)
+                .flat_map(statements_of)
+                .collect();
+            checker.scoped(Vec::new(), |c| c.statements(statements));
+        }
+        let mut errors = checker.errors;
+        errors.sort_by_key(|error| error.span.start);
+        Ok(Self {
+            errors,
+            inferred: checker.inferred,
+        })
+    }
+
+    /// Errors in source order
+    pub fn errors(&self) -> &[TypeError] {
+        &self.errors
+    }
+
+    /// The type of an unannotated `let`/`mut` binding, from its initializer
+    pub fn inferred_type(&self, id: SymbolId) -> Option<&Type> {
+        self.inferred.get(&id)
+    }
+}
+
+/// Whether a type is worth showing, i.e. says more than `Any`
+pub fn is_informative(ty: &Type) -> bool {
+    types::is_informative(ty)
+}
+
+/// Bindings a condition refines when it holds and when it doesn't
+#[derive(Default)]
+struct Narrowing {
+    when_true: Vec<(SymbolId, Type)>,
+    when_false: Vec<(SymbolId, Type)>,
+}
+
+impl Narrowing {
+    fn negated(self) -> Self {
+        Self {
+            when_true: self.when_false,
+            when_false: self.when_true,
+        }
+    }
+}
+
+/// What the lambda being checked returns
+struct Returns {
+    expected: Option<Type>,
+    found: Vec<Type>,
+}
+
+struct Checker<'a> {
+    index: &'a SemanticIndex,
+    errors: Vec<TypeError>,
+    /// Declared or inferred type of each binding
+    bindings: HashMap<SymbolId, Type>,
+    /// Bindings whose type is written in the source
+    annotated: HashSet<SymbolId>,
+    inferred: HashMap<SymbolId, Type>,
+    aliases: HashMap<SymbolId, Type>,
+    /// How many arguments the lambdas bound to names need
+    required: HashMap<SymbolId, usize>,
+    /// Refined types of bindings, innermost scope last
+    narrowed: Vec<HashMap<SymbolId, Type>>,
+    /// The lambdas being checked, innermost last
+    returns: Vec<Returns>,
+    /// The value a `|>` passes to the call being checked
+    piped: Option<(Type, Range<usize>)>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(index: &'a SemanticIndex) -> Self {
+        Self {
+            index,
+            errors: Vec::new(),
+            bindings: HashMap::new(),
+            annotated: HashSet::new(),
+            inferred: HashMap::new(),
+            aliases: HashMap::new(),
+            required: HashMap::new(),
+            narrowed: Vec::new(),
+            returns: Vec::new(),
+            piped: None,
+        }
+    }
+
+    fn error(&mut self, span: Range<usize>, message: String) {
+        self.errors.push(TypeError { span, message });
+    }
+
+    /// Report `found` where `expected` is wanted, with the message `mismatch` gives
+    fn expect(
+        &mut self,
+        expected: &Type,
+        found: &Type,
+        span: Range<usize>,
+        mismatch: impl FnOnce() -> String,
+    ) {
+        if !is_assignable(expected, found) {
+            self.error(span, mismatch());
+        }
+    }
+
+    /// Run `f` with the bindings in `narrowing` refined
+    fn scoped<T>(&mut self, narrowing: Vec<(SymbolId, Type)>, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.narrowed.push(narrowing.into_iter().collect());
+        let result = f(self);
+        self.narrowed.pop();
+        result
+    }
+
+    /// The symbol an identifier pair resolves to
+    fn symbol(&self, ident: &Pair<Rule>) -> Option<SymbolId> {
+        let span = span_of(ident);
+        self.index
+            .occurrence_at(span.start)
+            .filter(|occurrence| occurrence.span == span)
+            .and_then(|occurrence| occurrence.symbol)
+    }
+
+    /// The current type of a binding
+    fn read(&self, id: SymbolId) -> Type {
+        if let Some(ty) = self.narrowed.iter().rev().find_map(|frame| frame.get(&id)) {
+            return ty.clone();
+        }
+        // An unannotated `mut` may be reassigned anything
+        if self.index.symbol(id).kind == SymbolKind::Mut && !self.annotated.contains(&id) {
+            return Type::Any;
+        }
+        self.bindings.get(&id).cloned().unwrap_or(Type::Any)
+    }
+
+    fn bind(&mut self, ident: &Pair<Rule>, ty: Type) -> Option<SymbolId> {
+        let id = self.symbol(ident)?;
+        self.bindings.insert(id, ty);
+        Some(id)
+    }
+
+    /// A type annotation, with the aliases it names replaced by their types
+    fn annotation(&mut self, pair: Pair<Rule>) -> Type {
+        let span = span_of(&pair);
+        let mut aliases = HashMap::new();
+        let mut unknown = HashSet::new();
+        for occurrence in self.index.occurrences() {
+            if occurrence.namespace == Namespace::Type
+                && span.start <= occurrence.span.start
+                && occurrence.span.end <= span.end
+            {
+                if occurrence.symbol.is_none() {
+                    unknown.insert(occurrence.name.clone());
+                }
+                let ty = occurrence.symbol.and_then(|id| self.aliases.get(&id));
+                aliases.insert(occurrence.name.clone(), ty.cloned().unwrap_or(Type::Any));
+            }
+        }
+
+        // An undeclared element type would silently become `Any`
+        for tensor in pair.clone().into_inner().flatten() {
+            if tensor.as_rule() != Rule::tensor_type {
+                continue;
+            }
+            let Some(element) = tensor
+                .into_inner()
+                .next()
+                .filter(|element| element.as_rule() == Rule::type_annotation)
+            else {
+                continue;
+            };
+            if let Ok(Type::TypeReference(name)) = type_from_pair(element.clone()) {
+                if unknown.contains(&name) && !types::RUNTIME_TYPES.contains(&name.as_str()) {
+                    self.error(
+                        span_of(&element),
+                        format!("Unknown tensor element type '{}'", name),
+                    );
+                }
+            }
+        }
+
+        substitute(type_from_pair(pair).unwrap_or(Type::Any), &aliases)
+    }
+
+    /// Check statements in order; the value is the last one's
+    fn statements(&mut self, statements: Vec<Pair<Rule>>) -> Type {
+        let mut value = Type::Any;
+        for statement in statements {
+            value = self.statement(statement.clone());
+            // `if (x == null) { return ... }` leaves x non-null after it
+            if let Some(facts) = self.early_exit(statement) {
+                if let Some(frame) = self.narrowed.last_mut() {
+                    frame.extend(facts);
+                }
+            }
+        }
+        value
+    }
+
+    fn statement(&mut self, pair: Pair<Rule>) -> Type {
+        let rule = pair.as_rule();
+        let mut inner = pair.clone().into_inner();
+        match rule {
+            Rule::statement => match inner.next() {
+                Some(statement) => self.statement(statement),
+                None => Type::Any,
+            },
+            Rule::let_statement | Rule::mut_statement => {
+                self.binding_statement(pair);
+                Type::Any
+            }
+            Rule::type_alias_statement => {
+                if let (Some(name), Some(annotation)) = (inner.next(), inner.next()) {
+                    let id = self.symbol(&name);
+                    // `type Color = Red | Green` may declare variants instead
+                    if let Some(id) =
+                        id.filter(|&id| self.index.symbol(id).kind == SymbolKind::TypeAlias)
+                    {
+                        let ty = self.annotation(annotation);
+                        self.aliases.insert(id, ty);
+                    }
+                }
+                Type::Any
+            }
+            Rule::adt_statement => {
+                // Constructors take the fields in order; variant values are `Any`
+                for variant in inner.skip(1) {
+                    let mut parts = variant.into_inner();
+                    let (Some(name), Some(fields)) = (parts.next(), parts.next()) else {
+                        continue;
+                    };
+                    let params: Vec<Option<Type>> = fields
+                        .into_inner()
+                        .map(|field| {
+                            field
+                                .into_inner()
+                                .find(|part| part.as_rule() == Rule::type_annotation)
+                                .map(|annotation| self.annotation(annotation))
+                        })
+                        .collect();
+                    if let Some(id) = self.symbol(&name) {
+                        self.required.insert(id, params.len());
+                        self.bindings.insert(
+                            id,
+                            Type::Function {
+                                params,
+                                return_type: Box::new(Type::Any),
+                            },
+                        );
+                    }
+                }
+                Type::Any
+            }
+            Rule::assignment => {
+                self.assignment(pair);
+                Type::Any
+            }
+            Rule::return_statement => {
+                if let Some(expr) = inner.next() {
+                    let span = span_of(&expr);
+                    let ty = self.expr(expr);
+                    self.returned(ty, span);
+                }
+                types::never()
+            }
+            Rule::throw_stmt | Rule::break_statement | Rule::continue_statement => {
+                inner.for_each(|expr| {
+                    self.expr(expr);
+                });
+                types::never()
+            }
+            Rule::expr => self.expr(pair),
+            _ => {
+                self.walk(pair);
+                Type::Any
+            }
+        }
+    }
+
+    /// `let`/`mut`: the binding gets its annotation, or else its initializer's type
+    fn binding_statement(&mut self, pair: Pair<Rule>) {
+        let mut target = None;
+        let mut annotation = None;
+        let mut initializer = None;
+        for part in pair.into_inner() {
+            match part.as_rule() {
+                Rule::type_annotation => annotation = Some(self.annotation(part)),
+                Rule::expr => initializer = Some(part),
+                _ if target.is_none() => target = Some(part),
+                _ => {}
+            }
+        }
+        let Some(initializer) = initializer else {
+            return;
+        };
+        let span = span_of(&initializer);
+        let (value, required) = match as_lambda(initializer.clone()) {
+            Some(lambda) => {
+                let (ty, required) = self.lambda(lambda, annotation.as_ref());
+                (ty, Some(required))
+            }
+            None => (self.expr(initializer), None),
+        };
+        if let Some(declared) = &annotation {
+            self.expect(declared, &value, span, || {
+                format!("Type mismatch: expected {}, found {}", declared, value)
+            });
+        }
+        let Some(target) = target else { return };
+        if target.as_rule() != Rule::identifier {
+            self.pattern(target, annotation.unwrap_or(value));
+            return;
+        }
+        let Some(id) = self.symbol(&target) else {
+            return;
+        };
+        if let Some(required) = required {
+            self.required.insert(id, required);
+        }
+        match annotation {
+            Some(declared) => {
+                self.annotated.insert(id);
+                self.bindings.insert(id, declared);
+            }
+            None => {
+                self.inferred.insert(id, value.clone());
+                self.bindings.insert(id, value);
+            }
+        }
+    }
+
+    /// `x = v`, `x += v`, `r.field = v` and `v[i] = v`
+    fn assignment(&mut self, pair: Pair<Rule>) {
+        let mut inner = pair.into_inner();
+        let Some(target) = inner.next() else { return };
+        let mut operator = None;
+        let mut value = None;
+        for part in inner {
+            match part.as_rule() {
+                Rule::compound_assignment_op => {
+                    operator = Some(part.as_str().trim_end_matches('='))
+                }
+                _ => value = Some(part),
+            }
+        }
+        let Some(value) = value else { return };
+        let value_span = span_of(&value);
+        let value = self.expr(value);
+
+        let mut parts = target.into_inner();
+        let Some(primary) = parts.next() else { return };
+        let ops: Vec<Pair<Rule>> = parts.collect();
+        let Some((last, receiver)) = ops.split_last() else {
+            // A plain name
+            let ident = simple(primary);
+            if ident.as_rule() != Rule::identifier {
+                return;
+            }
+            let Some(id) = self.symbol(&ident) else {
+                return;
+            };
+            let current = self.read(id);
+            let value = match operator {
+                Some(op) => self.arithmetic(op, &current, &value, value_span.clone()),
+                None => value,
+            };
+            if self.annotated.contains(&id) {
+                let declared = self.bindings.get(&id).cloned().unwrap_or(Type::Any);
+                let name = ident.as_str();
+                self.expect(&declared, &value, value_span, || {
+                    format!("Cannot assign {} to '{}' of type {}", value, name, declared)
+                });
+            }
+            // Whatever was learned about the old value no longer holds
+            for frame in &mut self.narrowed {
+                frame.remove(&id);
+            }
+            return;
+        };
+
+        let receiver_type = self.postfix(primary, receiver);
+        let Some(op) = last.clone().into_inner().next() else {
+            return;
+        };
+        match op.as_rule() {
+            Rule::field_op => {
+                let name = op.as_str().trim_start_matches('.').trim();
+                let field = self.field(&receiver_type, name, span_of(&op));
+                let value = match operator {
+                    Some(operator) => self.arithmetic(operator, &field, &value, value_span.clone()),
+                    None => value,
+                };
+                self.expect(&field, &value, value_span, || {
+                    format!(
+                        "Cannot assign {} to field '{}' of type {}",
+                        value, name, field
+                    )
+                });
+            }
+            _ => self.walk(op),
+        }
+    }
+
+    /// Note a `return` in the lambda being checked
+    fn returned(&mut self, ty: Type, span: Range<usize>) {
+        let Some(returns) = self.returns.last_mut() else {
+            return;
+        };
+        returns.found.push(ty.clone());
+        if let Some(expected) = returns.expected.clone() {
+            self.expect(&expected, &ty, span, || {
+                format!("Return type mismatch: expected {}, found {}", expected, ty)
+            });
+        }
+    }
+
+    /// What a statement tells the statements after it, when it is an `if`
+    /// with a branch that always leaves the block
+    fn early_exit(&self, statement: Pair<Rule>) -> Option<Vec<(SymbolId, Type)>> {
+        let if_expr = as_if(statement)?;
+        let mut inner = if_expr.into_inner();
+        let (condition, then) = (inner.next()?, inner.next()?);
+        let otherwise = inner.next();
+        let narrowing = self.narrowing(condition);
+        match otherwise {
+            None if diverges(&then) => Some(narrowing.when_false),
+            Some(otherwise) if otherwise.as_rule() == Rule::block && diverges(&otherwise) => {
+                (!diverges(&then)).then_some(narrowing.when_true)
+            }
+            _ => None,
+        }
+    }
+
+    /// Check the declarations of a destructuring or `match` pattern that
+    /// matches a value of type `ty`
+    fn pattern(&mut self, pair: Pair<Rule>, ty: Type) {
+        match pair.as_rule() {
+            Rule::variable_pattern => {
+                if let Some(ident) = pair.into_inner().next() {
+                    // A bare variant name is a reference, not a binding
+                    let variant = self.symbol(&ident);
+                    if !variant.is_some_and(|id| self.index.symbol(id).kind == SymbolKind::Variant)
+                    {
+                        self.bind(&ident, ty);
+                    }
+                }
+            }
+            // Data types are not tracked yet, so the fields are `Any`
+            Rule::variant_pattern => {
+                for part in pair.into_inner().skip(1) {
+                    self.pattern(part, Type::Any);
+                }
+            }
+            Rule::rest_pattern => {
+                if let Some(ident) = pair.into_inner().next() {
+                    self.bind(&ident, Type::Vector);
+                }
+            }
+            Rule::record_pattern => {
+                for field in pair.into_inner() {
+                    let mut inner = field.into_inner();
+                    let Some(name) = inner.next() else { continue };
+                    let field_type = match &ty {
+                        Type::Record { fields } => fields
+                            .get(name.as_str())
+                            .map(|(_, _, ty)| ty.clone())
+                            .unwrap_or(Type::Any),
+                        _ => Type::Any,
+                    };
+                    let mut bound = false;
+                    for part in inner {
+                        match part.as_rule() {
+                            Rule::pattern => {
+                                bound = true;
+                                self.pattern(part, field_type.clone());
+                            }
+                            _ => {
+                                self.expr(part);
+                            }
+                        }
+                    }
+                    if !bound {
+                        self.bind(&name, field_type);
+                    }
+                }
+            }
+            Rule::vector_pattern => {
+                for element in pair.into_inner().flat_map(|e| e.into_inner()) {
+                    match element.as_rule() {
+                        Rule::rest_pattern => self.pattern(element, Type::Vector),
+                        _ => {
+                            for part in element.into_inner() {
+                                match part.as_rule() {
+                                    Rule::pattern => self.pattern(part, Type::Any),
+                                    _ => {
+                                        self.expr(part);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            Rule::literal_pattern | Rule::wildcard_pattern | Rule::type_pattern => {}
+            _ => {
+                for part in pair.into_inner() {
+                    self.pattern(part, ty.clone());
+                }
+            }
+        }
+    }
+
+    fn expr(&mut self, pair: Pair<Rule>) -> Type {
+        let rule = pair.as_rule();
+        let text = pair.as_str();
+        let span = span_of(&pair);
+        let inner: Vec<Pair<Rule>> = pair.clone().into_inner().collect();
+        match rule {
+            Rule::number | Rule::infinity_literal | Rule::nan_literal_value => Type::Number,
+            Rule::complex => Type::Complex,
+            Rule::boolean => Type::Boolean,
+            Rule::string_literal => Type::String,
+            Rule::interpolated_string => {
+                self.walk(pair);
+                Type::String
+            }
+            Rule::null_literal => Type::Null,
+            Rule::array => {
+                self.walk(pair);
+                Type::Vector
+            }
+            Rule::record => self.record(inner),
+            Rule::identifier => match self.symbol(&pair) {
+                Some(id) => self.read(id),
+                None if text == "i" => Type::Complex,
+                None if crate::is_constant(text) => Type::Number,
+                None => Type::Any,
+            },
+            Rule::self_ref | Rule::rec_ref => Type::Any,
+            Rule::lambda => self.lambda(pair, None).0,
+            Rule::async_lambda => {
+                let (ty, _) = self.lambda(pair, None);
+                match ty {
+                    Type::Function { params, .. } => Type::Function {
+                        params,
+                        return_type: Box::new(Type::TypeReference("Future".to_string())),
+                    },
+                    ty => ty,
+                }
+            }
+            Rule::block => self.scoped(Vec::new(), |c| {
+                let statements = inner.into_iter().flat_map(statements_of).collect();
+                c.statements(statements)
+            }),
+            Rule::generate_block => {
+                // `return` in a generator ends it rather than the lambda around it
+                self.returns.push(Returns {
+                    expected: None,
+                    found: Vec::new(),
+                });
+                self.walk(pair);
+                self.returns.pop();
+                Type::Generator
+            }
+            Rule::async_block => {
+                self.walk(pair);
+                Type::TypeReference("Future".to_string())
+            }
+            Rule::if_expr => self.if_expr(inner),
+            Rule::while_expr => {
+                let mut inner = inner.into_iter();
+                if let (Some(condition), Some(body)) = (inner.next(), inner.next()) {
+                    self.expr(condition.clone());
+                    let narrowing = self.narrowing(condition);
+                    self.scoped(narrowing.when_true, |c| c.expr(body));
+                }
+                Type::Null
+            }
+            Rule::for_in_loop => {
+                let mut inner = inner.into_iter();
+                if let (Some(var), Some(iterable), Some(body)) =
+                    (inner.next(), inner.next(), inner.next())
+                {
+                    // `for (k in 1..n)` counts
+                    let is_range = simple(iterable.clone()).as_rule() == Rule::standalone_range;
+                    let iterable = self.expr(iterable);
+                    let element = match iterable {
+                        _ if is_range => Type::Number,
+                        Type::String => Type::String,
+                        _ => Type::Any,
+                    };
+                    self.bind(&var, element);
+                    self.expr(body);
+                }
+                Type::Null
+            }
+            Rule::try_catch_expr => {
+                let mut inner = inner.into_iter();
+                let (Some(body), Some(error), Some(handler)) =
+                    (inner.next(), inner.next(), inner.next())
+                else {
+                    return Type::Any;
+                };
+                let body = self.expr(body);
+                self.bind(&error, Type::Error);
+                let handler = self.expr(handler);
+                types::join(body, handler)
+            }
+            Rule::match_expr => self.match_expr(inner),
+            Rule::postfix_expression => {
+                let mut inner = inner.into_iter();
+                let Some(primary) = inner.next() else {
+                    return Type::Any;
+                };
+                let ops: Vec<Pair<Rule>> = inner.collect();
+                self.postfix(primary, &ops)
+            }
+            Rule::power if inner.len() == 2 => {
+                let mut inner = inner.into_iter();
+                let (Some(base), Some(exponent)) = (inner.next(), inner.next()) else {
+                    return Type::Any;
+                };
+                let base = self.expr(base);
+                let exponent = self.expr(exponent);
+                self.arithmetic("^", &base, &exponent, span)
+            }
+            Rule::unary if text.starts_with('-') => {
+                let Some(operand) = inner.into_iter().next() else {
+                    return Type::Any;
+                };
+                let operand = self.expr(operand);
+                match types::negate(&operand) {
+                    Some(ty) => ty,
+                    None => {
+                        self.error(span, format!("Cannot negate {}", operand));
+                        Type::Any
+                    }
+                }
+            }
+            Rule::unary if text.starts_with('!') => {
+                self.walk(pair);
+                Type::Boolean
+            }
+            Rule::await_expr => {
+                self.walk(pair);
+                Type::Any
+            }
+            Rule::multiplicative | Rule::additive if inner.len() > 1 => {
+                let start = span.start;
+                let mut inner = inner.into_iter();
+                let Some(first) = inner.next() else {
+                    return Type::Any;
+                };
+                let mut ty = self.expr(first);
+                while let (Some(op), Some(operand)) = (inner.next(), inner.next()) {
+                    let end = operand.as_span().end();
+                    let operand = self.expr(operand);
+                    ty = self.arithmetic(op.as_str(), &ty, &operand, start..end);
+                }
+                ty
+            }
+            Rule::comparison if inner.len() == 3 => {
+                let mut inner = inner.into_iter();
+                let (Some(left), Some(op), Some(right)) =
+                    (inner.next(), inner.next(), inner.next())
+                else {
+                    return Type::Boolean;
+                };
+                let left = self.expr(left);
+                let right = self.expr(right);
+                let op = op.as_str();
+                if !matches!(op, "==" | "!=")
+                    && (!types::is_ordered(&left) || !types::is_ordered(&right))
+                {
+                    self.error(
+                        span,
+                        format!(
+                            "Operator '{}' cannot be applied to {} and {}",
+                            op, left, right
+                        ),
+                    );
+                }
+                Type::Boolean
+            }
+            // Each operand is checked knowing the ones before it held
+            // (`&&`) or didn't (`||`); the value is one of the operands
+            Rule::logical_and | Rule::logical_or if inner.len() > 1 => {
+                let is_and = rule == Rule::logical_and;
+                let mut operands = Vec::new();
+                let mut facts = Vec::new();
+                for operand in inner {
+                    if matches!(
+                        operand.as_rule(),
+                        Rule::logical_and_op | Rule::logical_or_op
+                    ) {
+                        continue;
+                    }
+                    let ty = self.scoped(facts.clone(), |c| c.expr(operand.clone()));
+                    operands.push(ty);
+                    let narrowing = self.scoped(facts.clone(), |c| c.narrowing(operand));
+                    facts.extend(if is_and {
+                        narrowing.when_true
+                    } else {
+                        narrowing.when_false
+                    });
+                }
+                types::union_of(operands)
+            }
+            Rule::pipeline if inner.len() > 1 => {
+                let mut operands = inner.into_iter().filter(|p| p.as_rule() != Rule::pipe_op);
+                let Some(first) = operands.next() else {
+                    return Type::Any;
+                };
+                let mut value = (self.expr(first.clone()), span_of(&first));
+                for function in operands {
+                    let span = span.start..function.as_span().end();
+                    value = (self.pipe(value, function), span);
+                }
+                value.0
+            }
+            Rule::standalone_range if inner.len() > 1 => {
+                self.walk(pair);
+                Type::Vector
+            }
+            _ if inner.len() == 1 => self.expr(inner.into_iter().next().unwrap_or(pair)),
+            _ => {
+                self.walk(pair);
+                Type::Any
+            }
+        }
+    }
+
+    /// Check every expression under a node that has no type of its own
+    fn walk(&mut self, pair: Pair<Rule>) {
+        for child in pair.into_inner() {
+            match child.as_rule() {
+                Rule::expr | Rule::block => {
+                    self.expr(child);
+                }
+                Rule::statement => {
+                    self.statement(child);
+                }
+                _ => self.walk(child),
+            }
+        }
+    }
+
+    fn arithmetic(&mut self, op: &str, left: &Type, right: &Type, span: Range<usize>) -> Type {
+        if let Some(ty) = types::arithmetic(op, left, right) {
+            return ty;
+        }
+        if let Some(message) = types::shape_mismatch(op, left, right) {
+            self.error(span, message);
+            return Type::Any;
+        }
+        let non_null = |ty: &Type| types::narrow(ty, "Null", false);
+        let message = match types::arithmetic(op, &non_null(left), &non_null(right)) {
+            Some(_) => {
+                let nullable = if types::admits_null(left) {
+                    left
+                } else {
+                    right
+                };
+                format!(
+                    "Operator '{}' cannot be applied to a value that may be null ({})",
+                    op, nullable
+                )
+            }
+            None => format!(
+                "Operator '{}' cannot be applied to {} and {}",
+                op, left, right
+            ),
+        };
+        self.error(span, message);
+        Type::Any
+    }
+
+    fn record(&mut self, items: Vec<Pair<Rule>>) -> Type {
+        let mut fields = HashMap::new();
+        let mut known = true;
+        for item in items.into_iter().flat_map(|item| item.into_inner()) {
+            match item.as_rule() {
+                Rule::spread_expr => {
+                    let Some(expr) = item.into_inner().next() else {
+                        continue;
+                    };
+                    match self.expr(expr) {
+                        Type::Record { fields: spread } => fields.extend(spread),
+                        // Fields we can't see
+                        _ => known = false,
+                    }
+                }
+                _ => {
+                    let mut is_mut = false;
+                    let mut name = String::new();
+                    let mut ty = Type::Any;
+                    for part in item.into_inner() {
+                        match part.as_rule() {
+                            Rule::mut_keyword => is_mut = true,
+                            Rule::identifier => name = part.as_str().to_string(),
+                            _ => ty = self.expr(part),
+                        }
+                    }
+                    fields.insert(name, (is_mut, false, ty));
+                }
+            }
+        }
+        if known {
+            Type::Record { fields }
+        } else {
+            Type::TypeReference("Record".to_string())
+        }
+    }
+
+    /// A lambda's function type, and how many arguments it needs; `context`
+    /// is the type it is declared with, if any
+    fn lambda(&mut self, pair: Pair<Rule>, context: Option<&Type>) -> (Type, usize) {
+        let mut params = Vec::new();
+        let mut required = 0;
+        let mut optional_seen = false;
+        let mut expected = None;
+        let mut body = None;
+        for part in pair.into_inner() {
+            match part.as_rule() {
+                Rule::typed_lambda_params => {
+                    for param in part.into_inner() {
+                        let mut inner = param.into_inner();
+                        let Some(name) = inner.next() else { continue };
+                        let mut ty = None;
+                        let mut optional = false;
+                        let mut marked = false;
+                        for detail in inner {
+                            match detail.as_rule() {
+                                Rule::optional_marker => {
+                                    optional = true;
+                                    marked = true;
+                                }
+                                Rule::type_annotation => ty = Some(self.annotation(detail)),
+                                _ => {
+                                    optional = true;
+                                    let span = span_of(&detail);
+                                    let default = self.expr(detail);
+                                    if let Some(declared) = &ty {
+                                        self.expect(declared, &default, span, || {
+                                            format!(
+                                                "Type mismatch: expected {}, found {}",
+                                                declared, default
+                                            )
+                                        });
+                                    }
+                                }
+                            }
+                        }
+                        // `x?: T` may be left out, so it is `T | null`
+                        if marked {
+                            ty = ty.map(|ty| types::join(ty, Type::Null));
+                        }
+                        optional_seen |= optional;
+                        if !optional_seen {
+                            required += 1;
+                        }
+                        // `let f: (Number): Number = x => ...` types x too
+                        let bound = ty.clone().or_else(|| match context {
+                            Some(Type::Function {
+                                params: expected, ..
+                            }) => expected.get(params.len()).cloned().flatten(),
+                            _ => None,
+                        });
+                        if let Some(id) = self.bind(&name, bound.clone().unwrap_or(Type::Any)) {
+                            if bound.is_some() {
+                                self.annotated.insert(id);
+                            }
+                        }
+                        // Passing null for a parameter with a default uses the default
+                        if optional && !marked {
+                            ty = ty.map(|ty| types::join(ty, Type::Null));
+                        }
+                        params.push(ty);
+                    }
+                }
+                Rule::type_annotation => expected = Some(self.annotation(part)),
+                Rule::lambda_body => body = part.into_inner().next(),
+                _ => {}
+            }
+        }
+        self.returns.push(Returns {
+            expected: expected.clone(),
+            found: Vec::new(),
+        });
+        let value = body.map(|body| {
+            let span = span_of(&body);
+            (self.scoped(Vec::new(), |c| c.expr(body)), span)
+        });
+        let returns = self.returns.pop().map(|r| r.found).unwrap_or_default();
+        let return_type = match (expected, value) {
+            (Some(expected), Some((value, span))) => {
+                self.expect(&expected, &value, span, || {
+                    format!(
+                        "Return type mismatch: expected {}, found {}",
+                        expected, value
+                    )
+                });
+                expected
+            }
+            (Some(expected), None) => expected,
+            (None, value) => {
+                let returns = types::union_of(returns.into_iter().chain(value.map(|(ty, _)| ty)));
+                // A body that always throws says nothing about what it returns
+                if types::is_never(&returns) {
+                    Type::Any
+                } else {
+                    returns
+                }
+            }
+        };
+        (
+            Type::Function {
+                params,
+                return_type: Box::new(return_type),
+            },
+            required,
+        )
+    }
+
+    /// `primary` followed by calls, indexing and field access
+    fn postfix(&mut self, primary: Pair<Rule>, ops: &[Pair<Rule>]) -> Type {
+        // Only the last call receives the value of a `|>`
+        let mut piped = self.piped.take();
+        let start = primary.as_span().start();
+        let callee = simple(primary.clone());
+        let callee = (callee.as_rule() == Rule::identifier).then_some(callee);
+        let ops: Vec<Pair<Rule>> = ops
+            .iter()
+            .filter_map(|op| op.clone().into_inner().next())
+            .collect();
+        let mut ops = ops.into_iter().peekable();
+
+        // Builtins aren't values, so they are only ever called by name
+        let builtin = callee
+            .as_ref()
+            .filter(|ident| self.symbol(ident).is_none())
+            .filter(|_| ops.peek().is_some_and(|op| op.as_rule() == Rule::call_op));
+        let mut ty = match builtin {
+            Some(ident) => {
+                let name = ident.as_str();
+                let call = ops.next().unwrap_or_else(|| ident.clone());
+                let span = start..call.as_span().end();
+                let arguments: Vec<Pair<Rule>> = call.into_inner().collect();
+                let piped = piped.take().filter(|_| ops.peek().is_none());
+                match name {
+                    "if" => self.if_call(arguments),
+                    _ => match docs::lookup(name) {
+                        Some(doc) => self.builtin_call(doc, arguments, piped, span),
+                        None => {
+                            arguments.into_iter().for_each(|arg| {
+                                self.expr(arg);
+                            });
+                            Type::Any
+                        }
+                    },
+                }
+            }
+            None => self.expr(primary),
+        };
+
+        let mut name = callee.as_ref().map(|ident| ident.as_str().to_string());
+        let mut required = callee
+            .as_ref()
+            .and_then(|ident| self.symbol(ident))
+            .and_then(|id| self.required.get(&id).copied());
+        while let Some(op) = ops.next() {
+            let span = start..op.as_span().end();
+            ty = match op.as_rule() {
+                Rule::call_op => {
+                    let arguments = op.into_inner().collect();
+                    let piped = piped.take().filter(|_| ops.peek().is_none());
+                    self.call(&ty, name.as_deref(), required, arguments, piped, span)
+                }
+                Rule::field_op => {
+                    let name = op.as_str().trim_start_matches('.').trim();
+                    let field_span = op.into_inner().next().map_or(span, |f| span_of(&f));
+                    self.field(&ty, name, field_span)
+                }
+                _ => {
+                    self.walk(op);
+                    match types::element(&ty) {
+                        Some(element) => element,
+                        None => {
+                            let message = if may_be_null(&ty, |ty| types::element(ty).is_some()) {
+                                format!("Cannot index a value that may be null ({})", ty)
+                            } else {
+                                format!("Cannot index {}", ty)
+                            };
+                            self.error(span, message);
+                            Type::Any
+                        }
+                    }
+                }
+            };
+            name = None;
+            required = None;
+        }
+        ty
+    }
+
+    fn field(&mut self, ty: &Type, name: &str, span: Range<usize>) -> Type {
+        if let Some(field) = types::field(ty, name) {
+            return field;
+        }
+        let message = if may_be_null(ty, |ty| types::field(ty, name).is_some()) {
+            format!(
+                "Cannot access field '{}' of a value that may be null ({})",
+                name, ty
+            )
+        } else {
+            format!("Field '{}' not found in {}", name, ty)
+        };
+        self.error(span, message);
+        Type::Any
+    }
+
+    /// A call of a value of type `ty`; `name` and `required` are known when
+    /// it is a binding of a lambda called by name
+    fn call(
+        &mut self,
+        ty: &Type,
+        name: Option<&str>,
+        required: Option<usize>,
+        arguments: Vec<Pair<Rule>>,
+        piped: Option<(Type, Range<usize>)>,
+        span: Range<usize>,
+    ) -> Type {
+        let arguments = self.arguments(arguments, piped, None);
+        if !types::is_callable(ty) {
+            let message = if may_be_null(ty, types::is_callable) {
+                format!("Cannot call a value that may be null ({})", ty)
+            } else {
+                format!("{} is not a function", ty)
+            };
+            self.error(span, message);
+            return Type::Any;
+        }
+        let Type::Function {
+            params,
+            return_type,
+        } = ty
+        else {
+            return Type::Any;
+        };
+        let name = name.unwrap_or("function");
+        // Parameters that accept null may be left out
+        let required = required.unwrap_or_else(|| {
+            params
+                .iter()
+                .rposition(|param| param.as_ref().is_some_and(|ty| !types::admits_null(ty)))
+                .map_or(0, |last| last + 1)
+        });
+        self.arity(name, required, Some(params.len()), arguments.len(), span);
+        // Shape variables take their sizes from the arguments
+        let mut sizes = types::ShapeBindings::new();
+        for ((argument, _), param) in arguments.iter().zip(params) {
+            if let Some(param) = param {
+                types::bind_dimensions(param, argument, &mut sizes);
+            }
+        }
+        for (position, ((argument, span), param)) in arguments.iter().zip(params).enumerate() {
+            if let Some(param) = param {
+                let expected = types::substitute_dimensions(param, &sizes);
+                self.expect(&expected, argument, span.clone(), || {
+                    format!(
+                        "Argument {} of '{}' expects {}, found {}",
+                        position + 1,
+                        name,
+                        param,
+                        argument
+                    )
+                });
+            }
+        }
+        types::substitute_dimensions(return_type, &sizes)
+    }
+
+    /// The types of a call's arguments. A value piped in with `|>` takes the
+    /// place of the `_` arguments, or goes where `pipe_slot` puts it when
+    /// there are none.
+    fn arguments(
+        &mut self,
+        arguments: Vec<Pair<Rule>>,
+        piped: Option<(Type, Range<usize>)>,
+        builtin: Option<&str>,
+    ) -> Vec<(Type, Range<usize>)> {
+        let has_placeholder = arguments.iter().any(is_pipe_placeholder);
+        let mut typed: Vec<(Type, Range<usize>)> = arguments
+            .into_iter()
+            .map(|arg| {
+                let span = span_of(&arg);
+                match &piped {
+                    Some((value, _)) if is_pipe_placeholder(&arg) => (value.clone(), span),
+                    _ => (self.expr(arg), span),
+                }
+            })
+            .collect();
+        if let Some(piped) = piped.filter(|_| !has_placeholder) {
+            typed.insert(pipe_slot(builtin, typed.len()), piped);
+        }
+        typed
+    }
+
+    /// One step of a pipeline: `value |> function`
+    fn pipe(&mut self, value: (Type, Range<usize>), function: Pair<Rule>) -> Type {
+        if piped_call(function.clone()).is_some() {
+            self.piped = Some(value);
+            let ty = self.expr(function);
+            self.piped = None;
+            return ty;
+        }
+        // `xs |> sum` and `x |> f` call the function with the value alone
+        let span = span_of(&function);
+        let name = as_identifier(function.clone());
+        let builtin = name
+            .as_ref()
+            .filter(|ident| self.symbol(ident).is_none())
+            .and_then(|ident| docs::lookup(ident.as_str()));
+        if let Some(doc) = builtin {
+            return self.builtin_call(doc, Vec::new(), Some(value), span);
+        }
+        let required = name
+            .as_ref()
+            .and_then(|ident| self.symbol(ident))
+            .and_then(|id| self.required.get(&id).copied());
+        let ty = self.expr(function);
+        let name = name.map(|ident| ident.as_str().to_string());
+        self.call(
+            &ty,
+            name.as_deref(),
+            required,
+            Vec::new(),
+            Some(value),
+            span,
+        )
+    }
+
+    /// A call of a builtin, against its documented signature
+    fn builtin_call(
+        &mut self,
+        doc: &BuiltinDoc,
+        arguments: Vec<Pair<Rule>>,
+        piped: Option<(Type, Range<usize>)>,
+        span: Range<usize>,
+    ) -> Type {
+        let new_shape = match doc.name {
+            "reshape" => arguments.get(1).map(literal_shape),
+            _ => None,
+        };
+        let arguments = self.arguments(arguments, piped, Some(doc.name));
+        self.arity(
+            doc.name,
+            doc.min_args(),
+            doc.max_args(),
+            arguments.len(),
+            span.clone(),
+        );
+        for (position, (argument, span)) in arguments.iter().enumerate() {
+            // Further arguments all belong to a variadic last parameter
+            let Some(param) = doc.params.get(position).or_else(|| {
+                doc.params
+                    .last()
+                    .filter(|param| param.kind == docs::ParamKind::Variadic)
+            }) else {
+                break;
+            };
+            let expected = types::builtin_type(param.ty);
+            self.expect(&expected, argument, span.clone(), || {
+                format!(
+                    "Argument {} of '{}' expects {}, found {}",
+                    position + 1,
+                    doc.name,
+                    expected,
+                    argument
+                )
+            });
+        }
+        if let Some(ty) = self.tensor_builtin(doc.name, &arguments, new_shape.flatten(), span) {
+            return ty;
+        }
+        let returns = types::builtin_type(doc.returns);
+        // `sqrt(x: Number | Vector): Number | Vector` returns what it's given
+        let passes_through = doc
+            .params
+            .first()
+            .is_some_and(|param| param.ty == doc.returns && matches!(returns, Type::Union(_)));
+        match arguments.first() {
+            Some((argument, _))
+                if passes_through
+                    && types::is_informative(argument)
+                    && is_assignable(&returns, argument) =>
+            {
+                argument.clone()
+            }
+            // `Number | Complex` depends on the arguments in ways the docs don't say
+            _ if types::members(&types::narrow(&returns, "Null", false)).len() > 1 => Type::Any,
+            _ => returns,
+        }
+    }
+
+    /// `transpose` and `reshape`, whose result shape depends on their
+    /// arguments; `None` for other builtins and arguments of unknown shape
+    fn tensor_builtin(
+        &mut self,
+        name: &str,
+        arguments: &[(Type, Range<usize>)],
+        new_shape: Option<Vec<Dimension>>,
+        span: Range<usize>,
+    ) -> Option<Type> {
+        let (
+            Type::Tensor {
+                element_type,
+                shape,
+            },
+            _,
+        ) = arguments.first()?
+        else {
+            return match name {
+                // `reshape([1, 2, 3, 4], [2, 2])` makes a tensor of a vector
+                "reshape" if arguments.len() == 2 => Some(Type::Tensor {
+                    element_type: Box::new(Type::Any),
+                    shape: new_shape,
+                }),
+                _ => None,
+            };
+        };
+        match name {
+            "transpose" if arguments.len() == 1 => {
+                let dims = shape.as_ref()?;
+                if dims.len() != 2 {
+                    self.error(
+                        span,
+                        format!("'transpose' expects a 2-D tensor, found {}", arguments[0].0),
+                    );
+                }
+                Some(Type::Tensor {
+                    element_type: element_type.clone(),
+                    shape: (dims.len() == 2).then(|| vec![dims[1].clone(), dims[0].clone()]),
+                })
+            }
+            "reshape" if arguments.len() == 2 => {
+                let size = |dims: &[Dimension]| {
+                    dims.iter()
+                        .map(|dim| match dim {
+                            Dimension::Fixed(size) => Some(*size),
+                            _ => None,
+                        })
+                        .product::<Option<usize>>()
+                };
+                if let (Some(from), Some(to)) = (
+                    shape.as_deref().and_then(size),
+                    new_shape.as_deref().and_then(size),
+                ) {
+                    if from != to {
+                        self.error(
+                            span,
+                            format!(
+                                "Cannot reshape {} of {} elements into {} elements",
+                                arguments[0].0, from, to
+                            ),
+                        );
+                    }
+                }
+                Some(Type::Tensor {
+                    element_type: element_type.clone(),
+                    shape: new_shape,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn arity(
+        &mut self,
+        name: &str,
+        min: usize,
+        max: Option<usize>,
+        found: usize,
+        span: Range<usize>,
+    ) {
+        if found >= min && max.is_none_or(|max| found <= max) {
+            return;
+        }
+        let count = |n: usize| match n {
+            1 => "1 argument".to_string(),
+            n => format!("{} arguments", n),
+        };
+        let expected = match max {
+            Some(max) if max == min => count(min),
+            Some(max) if found > max => format!("at most {}", count(max)),
+            _ => format!("at least {}", count(min)),
+        };
+        self.error(
+            span,
+            format!("'{}' expects {}, found {}", name, expected, found),
+        );
+    }
+
+    /// `if(condition, then, else)`
+    fn if_call(&mut self, arguments: Vec<Pair<Rule>>) -> Type {
+        let mut arguments = arguments.into_iter();
+        let Some(condition) = arguments.next() else {
+            return Type::Any;
+        };
+        self.expr(condition.clone());
+        let narrowing = self.narrowing(condition);
+        let then = arguments
+            .next()
+            .map(|then| self.scoped(narrowing.when_true, |c| c.expr(then)));
+        let otherwise = arguments
+            .next()
+            .map(|otherwise| self.scoped(narrowing.when_false, |c| c.expr(otherwise)));
+        match (then, otherwise) {
+            (Some(then), Some(otherwise)) => types::join(then, otherwise),
+            _ => Type::Any,
+        }
+    }
+
+    fn if_expr(&mut self, inner: Vec<Pair<Rule>>) -> Type {
+        let mut inner = inner.into_iter();
+        let (Some(condition), Some(then)) = (inner.next(), inner.next()) else {
+            return Type::Any;
+        };
+        self.expr(condition.clone());
+        let narrowing = self.narrowing(condition);
+        let then = self.scoped(narrowing.when_true, |c| c.expr(then));
+        match inner.next() {
+            Some(otherwise) => {
+                let otherwise = self.scoped(narrowing.when_false, |c| c.expr(otherwise));
+                types::join(then, otherwise)
+            }
+            None => Type::Any,
+        }
+    }
+
+    /// Arms see the scrutinee narrowed by their pattern, and later arms
+    /// without what earlier unguarded ones matched
+    fn match_expr(&mut self, inner: Vec<Pair<Rule>>) -> Type {
+        let mut inner = inner.into_iter();
+        let Some(scrutinee) = inner.next() else {
+            return Type::Any;
+        };
+        let target = as_identifier(scrutinee.clone()).and_then(|ident| self.symbol(&ident));
+        let mut remaining = self.expr(scrutinee);
+        let mut arms = Vec::new();
+        for arm in inner {
+            let mut pattern = None;
+            let mut guard = None;
+            let mut body = None;
+            for part in arm.into_inner() {
+                match part.as_rule() {
+                    Rule::pattern => pattern = Some(part),
+                    Rule::guard_clause => guard = part.into_inner().next(),
+                    _ => body = Some(part),
+                }
+            }
+            let (Some(pattern), Some(body)) = (pattern, body) else {
+                continue;
+            };
+            let (matched, unmatched) = pattern_narrowing(pattern.clone(), &remaining);
+            let facts = target.map(|id| (id, matched.clone())).into_iter().collect();
+            let ty = self.scoped(facts, |c| {
+                c.pattern(pattern, matched);
+                if let Some(guard) = &guard {
+                    c.expr(guard.clone());
+                }
+                c.expr(body)
+            });
+            arms.push(ty);
+            if guard.is_none() {
+                remaining = unmatched;
+            }
+        }
+        types::union_of(arms)
+    }
+
+    /// What a condition says about the bindings it tests
+    fn narrowing(&self, condition: Pair<Rule>) -> Narrowing {
+        let rule = condition.as_rule();
+        let text = condition.as_str();
+        let inner: Vec<Pair<Rule>> = condition.clone().into_inner().collect();
+        match rule {
+            Rule::unary if text.starts_with('!') => match inner.into_iter().next() {
+                Some(operand) => self.narrowing(operand).negated(),
+                None => Narrowing::default(),
+            },
+            Rule::logical_and | Rule::logical_or if inner.len() > 1 => {
+                let mut narrowing = Narrowing::default();
+                for operand in inner {
+                    let operand = self.narrowing(operand);
+                    if rule == Rule::logical_and {
+                        narrowing.when_true.extend(operand.when_true);
+                    } else {
+                        narrowing.when_false.extend(operand.when_false);
+                    }
+                }
+                narrowing
+            }
+            Rule::comparison if inner.len() == 3 => self.comparison_narrowing(&inner),
+            // A bare name holds only if it isn't null
+            Rule::identifier => match self.symbol(&condition) {
+                Some(id) => Narrowing {
+                    when_true: vec![(id, types::narrow(&self.read(id), "Null", false))],
+                    when_false: Vec::new(),
+                },
+                None => Narrowing::default(),
+            },
+            _ if is_transparent(&condition) && inner.len() == 1 => {
+                self.narrowing(inner.into_iter().next().unwrap_or(condition))
+            }
+            _ => Narrowing::default(),
+        }
+    }
+
+    /// `x == null` and `typeof(x) == "Name"`, either way round, or with `!=`
+    fn comparison_narrowing(&self, parts: &[Pair<Rule>]) -> Narrowing {
+        let [left, op, right] = parts else {
+            return Narrowing::default();
+        };
+        let equal = match op.as_str() {
+            "==" => true,
+            "!=" => false,
+            _ => return Narrowing::default(),
+        };
+        let tested = |operand: &Pair<Rule>, other: &Pair<Rule>| -> Option<(SymbolId, &str)> {
+            let other = simple(other.clone());
+            if other.as_rule() == Rule::null_literal {
+                let id = self.symbol(&as_identifier(operand.clone())?)?;
+                return Some((id, "Null"));
+            }
+            if other.as_rule() != Rule::string_literal {
+                return None;
+            }
+            let name = other.as_str().trim_matches('"');
+            let name = types::RUNTIME_TYPES.iter().find(|ty| **ty == name)?;
+            let argument = typeof_argument(operand.clone())?;
+            Some((self.symbol(&argument)?, name))
+        };
+        let Some((id, name)) = tested(left, right).or_else(|| tested(right, left)) else {
+            return Narrowing::default();
+        };
+        let ty = self.read(id);
+        let narrowing = Narrowing {
+            when_true: vec![(id, types::narrow(&ty, name, true))],
+            when_false: vec![(id, types::narrow(&ty, name, false))],
+        };
+        if equal {
+            narrowing
+        } else {
+            narrowing.negated()
+        }
+    }
+}
+
+/// The statements of a `top_level_expr` or block body
+fn statements_of(pair: Pair<Rule>) -> Vec<Pair<Rule>> {
+    match pair.as_rule() {
+        Rule::statement => vec![pair],
+        _ => pair.into_inner().flat_map(statements_of).collect(),
+    }
+}
+
+/// Rules that only wrap the expression inside them when it is alone
+fn is_transparent(pair: &Pair<Rule>) -> bool {
+    match pair.as_rule() {
+        Rule::statement
+        | Rule::expr
+        | Rule::pipeline
+        | Rule::standalone_range
+        | Rule::logical_or
+        | Rule::logical_and
+        | Rule::comparison
+        | Rule::additive
+        | Rule::multiplicative
+        | Rule::power
+        | Rule::postfix_expression
+        | Rule::primary
+        | Rule::control_flow_expr => true,
+        Rule::unary => !pair.as_str().starts_with(['-', '!']),
+        _ => false,
+    }
+}
+
+/// The innermost node an expression consists of alone
+fn simple(pair: Pair<Rule>) -> Pair<Rule> {
+    if !is_transparent(&pair) {
+        return pair;
+    }
+    let mut inner = pair.clone().into_inner();
+    match (inner.next(), inner.next()) {
+        (Some(only), None) => simple(only),
+        _ => pair,
+    }
+}
+
+fn as_identifier(pair: Pair<Rule>) -> Option<Pair<Rule>> {
+    Some(simple(pair)).filter(|pair| pair.as_rule() == Rule::identifier)
+}
+
+fn as_if(pair: Pair<Rule>) -> Option<Pair<Rule>> {
+    Some(simple(pair)).filter(|pair| pair.as_rule() == Rule::if_expr)
+}
+
+/// `x` in `typeof(x)`
+fn typeof_argument(pair: Pair<Rule>) -> Option<Pair<Rule>> {
+    let pair = simple(pair);
+    if pair.as_rule() != Rule::postfix_expression {
+        return None;
+    }
+    let mut inner = pair.into_inner();
+    let (callee, call, None) = (inner.next()?, inner.next()?, inner.next()) else {
+        return None;
+    };
+    if simple(callee).as_str() != "typeof" {
+        return None;
+    }
+    let call = call.into_inner().next()?;
+    let mut arguments = call.into_inner();
+    match (arguments.next(), arguments.next()) {
+        (Some(argument), None) => as_identifier(argument),
+        _ => None,
+    }
+}
+
+/// Whether a block always returns, throws, breaks or continues
+fn diverges(block: &Pair<Rule>) -> bool {
+    let last = block
+        .clone()
+        .into_inner()
+        .flat_map(statements_of)
+        .last()
+        .and_then(|statement| statement.into_inner().next());
+    last.is_some_and(|statement| {
+        matches!(
+            statement.as_rule(),
+            Rule::return_statement
+                | Rule::throw_stmt
+                | Rule::break_statement
+                | Rule::continue_statement
+        )
+    })
+}
+
+/// The scrutinee's type in an arm with this pattern, and in the arms after it
+fn pattern_narrowing(pattern: Pair<Rule>, ty: &Type) -> (Type, Type) {
+    let Some(pattern) = pattern.into_inner().next() else {
+        return (ty.clone(), ty.clone());
+    };
+    let name = match pattern.as_rule() {
+        Rule::type_pattern => pattern.as_str().trim(),
+        Rule::literal_pattern => {
+            let literal = pattern.into_inner().next();
+            match literal.map(|literal| literal.as_rule()) {
+                Some(Rule::null_literal) => "Null",
+                Some(Rule::boolean) => return (types::narrow(ty, "Boolean", true), ty.clone()),
+                Some(Rule::string_literal) => {
+                    return (types::narrow(ty, "String", true), ty.clone())
+                }
+                Some(Rule::number) => return (types::narrow(ty, "Number", true), ty.clone()),
+                _ => return (ty.clone(), ty.clone()),
+            }
+        }
+        Rule::record_pattern => return (types::narrow(ty, "Record", true), ty.clone()),
+        Rule::vector_pattern => return (types::narrow(ty, "Vector", true), ty.clone()),
+        _ => return (ty.clone(), ty.clone()),
+    };
+    (
+        types::narrow(ty, name, true),
+        types::narrow(ty, name, false),
+    )
+}
+
+/// Whether the only thing wrong with `ty` is that it may be null
+fn may_be_null(ty: &Type, fine: impl Fn(&Type) -> bool) -> bool {
+    types::admits_null(ty) && fine(&types::narrow(ty, "Null", false))
+}
+
+/// Replace alias names with their types; unknown names become `Any`
+fn substitute(ty: Type, aliases: &HashMap<String, Type>) -> Type {
+    match ty {
+        Type::TypeReference(name) => aliases.get(&name).cloned().unwrap_or(Type::Any),
+        Type::Union(types) => types::union_of(types.into_iter().map(|t| substitute(t, aliases))),
+        Type::Tensor {
+            element_type,
+            shape,
+        } => Type::Tensor {
+            element_type: Box::new(substitute(*element_type, aliases)),
+            shape,
+        },
+        Type::Record { fields } => Type::Record {
+            fields: fields
+                .into_iter()
+                .map(|(name, (is_mut, optional, ty))| {
+                    (name, (is_mut, optional, substitute(ty, aliases)))
+                })
+                .collect(),
+        },
+        Type::Function {
+            params,
+            return_type,
+        } => Type::Function {
+            params: params
+                .into_iter()
+                .map(|param| param.map(|ty| substitute(ty, aliases)))
+                .collect(),
+            return_type: Box::new(substitute(*return_type, aliases)),
+        },
+        ty => ty,
+    }
+}
+
+/// The dimensions of a shape written as an array literal, like `[2, n]`;
+/// elements other than integer literals can be any size
+fn literal_shape(pair: &Pair<Rule>) -> Option<Vec<Dimension>> {
+    let text = pair.as_str().trim();
+    let items = text.strip_prefix('[')?.strip_suffix(']')?;
+    if items.contains(['[', ']']) {
+        return None;
+    }
+    Some(
+        items
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| item.parse().map_or(Dimension::Unknown, Dimension::Fixed))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors(source: &str) -> Vec<String> {
+        let index = SemanticIndex::build(source).unwrap();
+        TypeCheck::run(source, &index)
+            .unwrap()
+            .errors()
+            .iter()
+            .map(|error| error.message.clone())
+            .collect()
+    }
+
+    /// The inferred type of the binding named `name`, as written
+    fn inferred(source: &str, name: &str) -> Option<String> {
+        let index = SemanticIndex::build(source).unwrap();
+        let check = TypeCheck::run(source, &index).unwrap();
+        let id = index.symbols().iter().position(|s| s.name == name)?;
+        check.inferred_type(id).map(|ty| ty.to_string())
+    }
+
+    #[test]
+    fn test_annotations_and_assignments() {
+        assert_eq!(
+            errors(
+                "let a: Number = \"x\"\nmut b: String = \"y\"\nb = 2\nb += 1\nmut c = 1\nc = \"z\""
+            ),
+            [
+                "Type mismatch: expected Number, found String",
+                "Cannot assign Number to 'b' of type String",
+            ]
+        );
+        assert_eq!(
+            errors(
+                "type Point = { x: Number, mut y?: Number }\nlet p: Point = { x: 1 }\np.y = \"no\""
+            ),
+            ["Cannot assign String to field 'y' of type Number | null"]
+        );
+        // Untyped code has nothing to report
+        assert!(errors("let f = (a, b) => a + b\nf(1, \"x\")\nlet g = x => x.y[0](2)").is_empty());
+    }
+
+    #[test]
+    fn test_calls() {
+        let source = "let f = (x: Number, y = 1, z?: String) => x\n\
+                      f()\nf(1, null)\nf(1, 2, \"a\", 4)\nf(\"1\")\n\
+                      let k: (Number): Number = v => v\nk(true)\n\
+                      sqrt(\"4\")\npow(2)\nmax(1, 2, 3)\nlet n = 1\nn(2)";
+        assert_eq!(
+            errors(source),
+            [
+                "'f' expects at least 1 argument, found 0",
+                "'f' expects at most 3 arguments, found 4",
+                "Argument 1 of 'f' expects Number, found String",
+                "Argument 1 of 'k' expects Number, found Boolean",
+                "Argument 1 of 'sqrt' expects Number | Vector, found String",
+                "'pow' expects 2 arguments, found 1",
+                "Number is not a function",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_variant_constructors() {
+        let source = "type Shape = Circle { r: Number } | Rect { w: Number, h: Number } | Empty\n\
+                      type Color = Red | Green\n\
+                      Rect(1)\nCircle(\"big\")\nCircle(1, 2)\n\
+                      let ok = [Rect(1, 2), Circle(3), Empty, Red]\n\
+                      let sizes = map(Circle, [1, 2])";
+        assert_eq!(
+            errors(source),
+            [
+                "'Rect' expects 2 arguments, found 1",
+                "Argument 1 of 'Circle' expects Number, found String",
+                "'Circle' expects 1 argument, found 2",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pipelines() {
+        let source = "let inc = (x: Number): Number => x + 1\n\
+                      let a = [1, 2] |> map(inc) |> sum()\n\
+                      let b: String = 2 |> pow(_, 3)\n\
+                      \"s\" |> inc\n\
+                      [1] |> pow(2, _)\n\
+                      1 |> pow()\n\
+                      {a: 1} |> json_stringify(true)\n\
+                      true |> take(2)";
+        assert_eq!(
+            errors(source),
+            [
+                "Type mismatch: expected String, found Number",
+                "Argument 1 of 'inc' expects Number, found String",
+                "Argument 2 of 'pow' expects Number, found Vector",
+                "'pow' expects 2 arguments, found 1",
+                "Argument 1 of 'take' expects Vector | Generator | Iterator, found Boolean",
+            ]
+        );
+        assert_eq!(inferred(source, "b"), None);
+    }
+
+    #[test]
+    fn test_lazy_iterators() {
+        let source = "let g = generate { yield 1 }\n\
+                      let doubled = map(x => x * 2, g)\n\
+                      let it = iter(g)\n\
+                      let n: Number = collect(it)\n\
+                      take(true, 2)";
+        assert_eq!(
+            errors(source),
+            [
+                "Type mismatch: expected Number, found Vector",
+                "Argument 1 of 'take' expects Vector | Generator | Iterator, found Boolean",
+            ]
+        );
+        assert_eq!(inferred(source, "it").as_deref(), Some("Iterator"));
+    }
+
+    #[test]
+    fn test_returns() {
+        assert_eq!(
+            errors("let f = (x: Number): String => x\nlet g = (x: Number): Number => do {\n  if (x > 0) { return \"pos\" }\n  x\n}"),
+            [
+                "Return type mismatch: expected String, found Number",
+                "Return type mismatch: expected Number, found String",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_null_checks_narrow() {
+        let source = "let f = (x: Number | null) => x + 1\n\
+                      let g = (x: Number | null) => if (x != null) { x + 1 } else { 0 }\n\
+                      let h = (x: Number | null) => x && x + 1\n\
+                      let k = (x: { a: Number } | null) => do {\n  if (x == null) { return 0 }\n  x.a\n}\n\
+                      let m = (x: { a: Number } | null) => x.a";
+        assert_eq!(
+            errors(source),
+            [
+                "Operator '+' cannot be applied to a value that may be null (Number | null)",
+                "Cannot access field 'a' of a value that may be null ({ a: Number } | null)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_tests_narrow() {
+        let source = "let f = (v: Number | String) => if (typeof(v) == \"Number\") { v - 1 } else { v - 1 }\n\
+                      let g = (v: Number | String) => match v {\n  Number => v - 1,\n  _ => v + \"!\"\n}";
+        assert_eq!(
+            errors(source),
+            ["Operator '-' cannot be applied to String and Number"]
+        );
+    }
+
+    #[test]
+    fn test_operators() {
+        // `d` and `e` are fine: strings concatenate with anything and vectors broadcast
+        assert_eq!(
+            errors("let a = \"s\" - 1\nlet b = true < 2\nlet c = -\"x\"\nlet d = \"a\" * 2 + 1\nlet e = [1] + 2"),
+            [
+                "Operator '-' cannot be applied to String and Number",
+                "Operator '<' cannot be applied to Boolean and Number",
+                "Cannot negate String",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tensor_shapes() {
+        let source = "let mul = (A: Tensor<n, k>, B: Tensor<k, m>): Tensor<n, m> => A @ B\n\
+                      let bad = (A: Tensor<n, k>, B: Tensor<m, k>) => A @ B\n\
+                      let a: Tensor<Number, [2, 3]> = reshape([1, 2, 3, 4, 5, 6], [2, 3])\n\
+                      let b: Tensor<Number, [3, 4]> = reshape(a, [3, 4])\n\
+                      let c: Tensor<Number, [2, 4]> = mul(a, b)\n\
+                      mul(b, a)\n\
+                      let t = transpose(a) + a";
+        assert_eq!(
+            errors(source),
+            [
+                "Matrix product of Tensor<Number, [n, k]> and Tensor<Number, [m, k]>: inner dimensions k and m differ",
+                "Cannot reshape Tensor<Number, [2, 3]> of 6 elements into 12 elements",
+                "Argument 2 of 'mul' expects Tensor<Number, [k, m]>, found Tensor<Number, [2, 3]>",
+                "Cannot broadcast Tensor<Number, [3, 2]> and Tensor<Number, [2, 3]>",
+            ]
+        );
+        let source = "let a: Tensor<Number, [2, 3]> = reshape([1, 2, 3, 4, 5, 6], [2, 3])\n\
+                      let p = a @ transpose(a)\nlet s = a * 2 + reshape([1, 2, 3], [3])";
+        assert_eq!(
+            inferred(source, "p").as_deref(),
+            Some("Tensor<Number, [2, 2]>")
+        );
+        assert_eq!(
+            inferred(source, "s").as_deref(),
+            Some("Tensor<Any, [2, 3]>")
+        );
+    }
+
+    #[test]
+    fn test_single_dimension_tensors() {
+        let source = "let m = reshape([1, 2, 3, 4], [2, 2])\n\
+                      let a: Tensor<3> = reshape([1, 2, 3], [3])\n\
+                      let b: Tensor<n> = m\n\
+                      let c: Tensor<Number, [2]> = a\n\
+                      let d: Tensor<Pointt> = m";
+        assert_eq!(
+            errors(source),
+            [
+                "Type mismatch: expected Tensor<Number, [n]>, found Tensor<Any, [2, 2]>",
+                "Type mismatch: expected Tensor<Number, [2]>, found Tensor<Number, [3]>",
+                "Unknown tensor element type 'Pointt'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inferred_types() {
+        let source = "let n = 2\nlet s = \"a\" + n\nlet f = (x: Number) => x * n\nlet y = f(n)\n\
+                      let r = { a: 1, b: [1] }\nlet q = sqrt(y)\nlet u = foo(1)\nmut m = 1";
+        assert_eq!(inferred(source, "n").as_deref(), Some("Number"));
+        assert_eq!(inferred(source, "s").as_deref(), Some("String"));
+        assert_eq!(inferred(source, "f").as_deref(), Some("(Number): Number"));
+        assert_eq!(inferred(source, "y").as_deref(), Some("Number"));
+        assert_eq!(
+            inferred(source, "r").as_deref(),
+            Some("{ a: Number, b: Vector }")
+        );
+        assert_eq!(inferred(source, "q").as_deref(), Some("Number"));
+        assert_eq!(inferred(source, "u").as_deref(), Some("Any"));
+        assert_eq!(inferred(source, "m").as_deref(), Some("Number"));
+    }
+}
@@ -0,0 +1,703 @@
+//! The type algebra behind the type checker
+//!
+//! Types are the parser's [`TypeAnnotation`]s. `Any` is the dynamic type:
+//! it is compatible with everything in both directions, which is what makes
+//! the checking gradual. Names the annotation grammar doesn't know, such as
+//! `Future` or `DataFrame` in builtin signatures, stay type references that
+//! only match themselves; `Record` among them stands for any record.
+//!
+//! An empty union is the type of an expression that never produces a value,
+//! like a block ending in `return`.
+
+use std::collections::HashMap;
+
+use achronyme_metadata::methods::Receiver;
+use achronyme_parser::{parse_type, Dimension, TypeAnnotation as Type};
+
+/// Names `typeof` reports and type patterns match
+pub(crate) const RUNTIME_TYPES: &[&str] = &[
+    "Number",
+    "Boolean",
+    "String",
+    "Complex",
+    "Vector",
+    "Tensor",
+    "Record",
+    "Function",
+    "Generator",
+    "Iterator",
+    "Error",
+    "Edge",
+    "Null",
+];
+
+/// The type of an expression that never completes
+pub(crate) fn never() -> Type {
+    Type::Union(Vec::new())
+}
+
+pub(crate) fn is_never(ty: &Type) -> bool {
+    matches!(ty, Type::Union(members) if members.is_empty())
+}
+
+/// A type from a builtin's documented signature
+pub(crate) fn builtin_type(source: &str) -> Type {
+    parse_type(source).unwrap_or(Type::Any)
+}
+
+/// The members of a union (flattened), or the type itself
+pub(crate) fn members(ty: &Type) -> Vec<&Type> {
+    match ty {
+        Type::Union(types) => types.iter().flat_map(members).collect(),
+        _ => vec![ty],
+    }
+}
+
+/// The type of a value that has one of `types`
+pub(crate) fn union_of(types: impl IntoIterator<Item = Type>) -> Type {
+    let mut distinct: Vec<Type> = Vec::new();
+    for ty in types {
+        for member in members(&ty) {
+            if *member == Type::Any {
+                return Type::Any;
+            }
+            if !distinct.contains(member) {
+                distinct.push(member.clone());
+            }
+        }
+    }
+    match distinct.len() {
+        1 => distinct.remove(0),
+        _ => Type::Union(distinct),
+    }
+}
+
+pub(crate) fn join(a: Type, b: Type) -> Type {
+    union_of([a, b])
+}
+
+/// Opaque names from builtin signatures, other than `Record`
+fn is_opaque(ty: &Type) -> bool {
+    matches!(ty, Type::TypeReference(name) if name != "Record")
+}
+
+/// Whether a value of type `actual` can be used where `expected` is wanted
+pub(crate) fn is_assignable(expected: &Type, actual: &Type) -> bool {
+    match (expected, actual) {
+        (Type::Any, _) | (_, Type::Any) => true,
+        (_, Type::Union(types)) => types.iter().all(|ty| is_assignable(expected, ty)),
+        (Type::Union(types), _) => types.iter().any(|ty| is_assignable(ty, actual)),
+        (
+            Type::Tensor {
+                element_type: expected_element,
+                shape: expected_shape,
+            },
+            Type::Tensor {
+                element_type: actual_element,
+                shape: actual_shape,
+            },
+        ) => {
+            is_assignable(expected_element, actual_element)
+                && match (expected_shape, actual_shape) {
+                    (Some(expected), Some(actual)) => {
+                        expected.len() == actual.len()
+                            && expected
+                                .iter()
+                                .zip(actual)
+                                .all(|(expected, actual)| dimensions_agree(expected, actual))
+                    }
+                    _ => true,
+                }
+        }
+        // Records are structural: extra fields are fine, missing ones only
+        // if optional
+        (Type::Record { fields: expected }, Type::Record { fields: actual }) => expected
+            .iter()
+            .all(|(name, (_, optional, ty))| match actual.get(name) {
+                Some((_, actual_optional, actual_ty)) => {
+                    (*optional || !actual_optional) && is_assignable(ty, actual_ty)
+                }
+                None => *optional,
+            }),
+        (Type::TypeReference(name), Type::Record { .. })
+        | (Type::Record { .. }, Type::TypeReference(name)) => name == "Record",
+        // Parameters are contravariant, the return type covariant
+        (
+            Type::Function {
+                params: expected_params,
+                return_type: expected_return,
+            },
+            Type::Function {
+                params: actual_params,
+                return_type: actual_return,
+            },
+        ) => {
+            is_assignable(expected_return, actual_return)
+                && expected_params
+                    .iter()
+                    .zip(actual_params)
+                    .all(|params| match params {
+                        (Some(expected), Some(actual)) => is_assignable(actual, expected),
+                        _ => true,
+                    })
+        }
+        (Type::AnyFunction, Type::Function { .. }) | (Type::Function { .. }, Type::AnyFunction) => {
+            true
+        }
+        _ => expected == actual,
+    }
+}
+
+/// Whether two dimensions may be the same size
+///
+/// Two different shape variables are different sizes: a body checked
+/// against `Tensor<n, k>` must work for every `n` and `k`.
+fn dimensions_agree(a: &Dimension, b: &Dimension) -> bool {
+    match (a, b) {
+        (Dimension::Fixed(a), Dimension::Fixed(b)) => a == b,
+        (Dimension::Var(a), Dimension::Var(b)) => a == b,
+        _ => true,
+    }
+}
+
+/// The shape of an element-wise operation on tensors of shapes `left` and
+/// `right`, or `None` if they can't be broadcast together
+///
+/// Shapes line up from the last dimension; a missing or size-1 dimension
+/// stretches to the other.
+pub(crate) fn broadcast(left: &[Dimension], right: &[Dimension]) -> Option<Vec<Dimension>> {
+    let from_end = |shape: &[Dimension], i: usize| {
+        shape
+            .len()
+            .checked_sub(i + 1)
+            .map_or(Dimension::Fixed(1), |at| shape[at].clone())
+    };
+    let mut dims = (0..left.len().max(right.len()))
+        .map(|i| {
+            let (l, r) = (from_end(left, i), from_end(right, i));
+            match (&l, &r) {
+                _ if l == r => Some(l),
+                (Dimension::Fixed(1), _) | (Dimension::Unknown, _) => Some(r),
+                (_, Dimension::Fixed(1)) | (_, Dimension::Unknown) => Some(l),
+                // A variable that isn't 1 must be the fixed size
+                (Dimension::Var(_), Dimension::Fixed(_)) => Some(r),
+                (Dimension::Fixed(_), Dimension::Var(_)) => Some(l),
+                _ => None,
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    dims.reverse();
+    Some(dims)
+}
+
+/// The type of `left @ right`, or `None` if either isn't a 2-D tensor or
+/// their inner dimensions differ
+fn matrix_product(left: &Type, right: &Type) -> Option<Type> {
+    let (
+        Type::Tensor {
+            element_type: left_element,
+            shape: left_shape,
+        },
+        Type::Tensor {
+            element_type: right_element,
+            shape: right_shape,
+        },
+    ) = (left, right)
+    else {
+        return (is_opaque(left) || is_opaque(right)).then_some(Type::Any);
+    };
+    let rank_2 = |shape: &Option<Vec<Dimension>>| shape.as_ref().is_none_or(|dims| dims.len() == 2);
+    if !rank_2(left_shape) || !rank_2(right_shape) {
+        return None;
+    }
+    let shape = match (left_shape, right_shape) {
+        (Some(left), Some(right)) if !dimensions_agree(&left[1], &right[0]) => return None,
+        (Some(left), Some(right)) => Some(vec![left[0].clone(), right[1].clone()]),
+        (Some(left), None) => Some(vec![left[0].clone(), Dimension::Unknown]),
+        (None, Some(right)) => Some(vec![Dimension::Unknown, right[1].clone()]),
+        (None, None) => None,
+    };
+    Some(Type::Tensor {
+        element_type: Box::new(tensor_element(left_element, right_element)),
+        shape,
+    })
+}
+
+/// The element type of arithmetic on tensors (or scalars) of these elements
+fn tensor_element(left: &Type, right: &Type) -> Type {
+    match (left, right) {
+        (Type::Complex, _) | (_, Type::Complex) => Type::Complex,
+        (Type::Number, Type::Number) => Type::Number,
+        _ => Type::Any,
+    }
+}
+
+/// Why `left op right` fails on the shapes of its tensor operands, if it does
+pub(crate) fn shape_mismatch(op: &str, left: &Type, right: &Type) -> Option<String> {
+    members(left).into_iter().find_map(|l| {
+        members(right).into_iter().find_map(|r| {
+            let (
+                Type::Tensor {
+                    shape: Some(ls), ..
+                },
+                Type::Tensor {
+                    shape: Some(rs), ..
+                },
+            ) = (l, r)
+            else {
+                return None;
+            };
+            if op != "@" {
+                return broadcast(ls, rs)
+                    .is_none()
+                    .then(|| format!("Cannot broadcast {} and {}", l, r));
+            }
+            if ls.len() != 2 || rs.len() != 2 {
+                let other = if ls.len() != 2 { l } else { r };
+                return Some(format!("Operator '@' needs 2-D tensors, found {}", other));
+            }
+            (!dimensions_agree(&ls[1], &rs[0])).then(|| {
+                format!(
+                    "Matrix product of {} and {}: inner dimensions {} and {} differ",
+                    l, r, ls[1], rs[0]
+                )
+            })
+        })
+    })
+}
+
+/// Sizes the shape variables of a signature take in one call
+pub(crate) type ShapeBindings = HashMap<String, Dimension>;
+
+/// Bind the shape variables of `param` to the dimensions of `argument`;
+/// the first binding of a variable wins
+pub(crate) fn bind_dimensions(param: &Type, argument: &Type, sizes: &mut ShapeBindings) {
+    let (
+        Type::Tensor {
+            shape: Some(expected),
+            ..
+        },
+        Type::Tensor {
+            shape: Some(actual),
+            ..
+        },
+    ) = (param, argument)
+    else {
+        return;
+    };
+    if expected.len() != actual.len() {
+        return;
+    }
+    for (expected, actual) in expected.iter().zip(actual) {
+        if let Dimension::Var(name) = expected {
+            if *actual != Dimension::Unknown {
+                sizes.entry(name.clone()).or_insert_with(|| actual.clone());
+            }
+        }
+    }
+}
+
+/// `ty` with its shape variables replaced by their sizes in `sizes`;
+/// variables the call didn't bind can be any size
+pub(crate) fn substitute_dimensions(ty: &Type, sizes: &ShapeBindings) -> Type {
+    match ty {
+        Type::Tensor {
+            element_type,
+            shape,
+        } => Type::Tensor {
+            element_type: element_type.clone(),
+            shape: shape.as_ref().map(|dims| {
+                dims.iter()
+                    .map(|dim| match dim {
+                        Dimension::Var(name) => {
+                            sizes.get(name).cloned().unwrap_or(Dimension::Unknown)
+                        }
+                        _ => dim.clone(),
+                    })
+                    .collect()
+            }),
+        },
+        Type::Union(types) => Type::Union(
+            types
+                .iter()
+                .map(|ty| substitute_dimensions(ty, sizes))
+                .collect(),
+        ),
+        Type::Record { fields } => Type::Record {
+            fields: fields
+                .iter()
+                .map(|(name, (is_mut, optional, ty))| {
+                    (
+                        name.clone(),
+                        (*is_mut, *optional, substitute_dimensions(ty, sizes)),
+                    )
+                })
+                .collect(),
+        },
+        Type::Function {
+            params,
+            return_type,
+        } => Type::Function {
+            params: params
+                .iter()
+                .map(|param| param.as_ref().map(|ty| substitute_dimensions(ty, sizes)))
+                .collect(),
+            return_type: Box::new(substitute_dimensions(return_type, sizes)),
+        },
+        _ => ty.clone(),
+    }
+}
+
+/// What `typeof` reports for values of a type that isn't a union, if known
+fn runtime_name(ty: &Type) -> Option<&'static str> {
+    Some(match ty {
+        Type::Number => "Number",
+        Type::Boolean => "Boolean",
+        Type::String => "String",
+        Type::Complex => "Complex",
+        Type::Vector => "Vector",
+        Type::Tensor { .. } => "Tensor",
+        Type::Record { .. } => "Record",
+        Type::TypeReference(name) if name == "Record" => "Record",
+        Type::Function { .. } | Type::AnyFunction => "Function",
+        Type::Generator => "Generator",
+        Type::TypeReference(name) if name == "Iterator" => "Iterator",
+        Type::Error => "Error",
+        Type::Edge => "Edge",
+        Type::Null => "Null",
+        _ => return None,
+    })
+}
+
+/// The type of values `typeof` reports as `name`
+fn from_runtime_name(name: &str) -> Type {
+    match name {
+        "Number" => Type::Number,
+        "Boolean" => Type::Boolean,
+        "String" => Type::String,
+        "Complex" => Type::Complex,
+        "Vector" => Type::Vector,
+        "Tensor" => Type::Tensor {
+            element_type: Box::new(Type::Any),
+            shape: None,
+        },
+        "Function" => Type::AnyFunction,
+        "Generator" => Type::Generator,
+        "Error" => Type::Error,
+        "Edge" => Type::Edge,
+        "Null" => Type::Null,
+        _ => Type::TypeReference(name.to_string()),
+    }
+}
+
+/// `ty` once the value is known to be (`is`) or not to be of runtime type `name`
+///
+/// A narrowing that rules out every member leaves the type alone.
+pub(crate) fn narrow(ty: &Type, name: &str, is: bool) -> Type {
+    let kept: Vec<Type> = members(ty)
+        .into_iter()
+        .filter_map(|member| match runtime_name(member) {
+            Some(runtime) => (is == (runtime == name)).then(|| member.clone()),
+            None if is && *member == Type::Any => Some(from_runtime_name(name)),
+            None => Some(member.clone()),
+        })
+        .collect();
+    if kept.is_empty() {
+        return ty.clone();
+    }
+    union_of(kept)
+}
+
+/// Whether `null` is among the values of a type
+pub(crate) fn admits_null(ty: &Type) -> bool {
+    is_assignable(ty, &Type::Null)
+}
+
+/// The type of `left op right` for `+ - * / % ^ @`, or `None` if some
+/// combination of their members can't be combined
+pub(crate) fn arithmetic(op: &str, left: &Type, right: &Type) -> Option<Type> {
+    let mut results = Vec::new();
+    for l in members(left) {
+        for r in members(right) {
+            results.push(arithmetic_member(op, l, r)?);
+        }
+    }
+    Some(union_of(results))
+}
+
+fn arithmetic_member(op: &str, left: &Type, right: &Type) -> Option<Type> {
+    use Type::*;
+    match (left, right) {
+        (Any, _) | (_, Any) => Some(Any),
+        _ if op == "@" => matrix_product(left, right),
+        // `+` converts the other operand to text; `*` repeats
+        (String, _) | (_, String) if op == "+" => Some(String),
+        (String, Number) | (Number, String) if op == "*" => Some(String),
+        (Complex, _) | (_, Complex) if op == "%" => None,
+        // Tensors combine element-wise with each other and with scalars
+        (Tensor { .. }, _) | (_, Tensor { .. }) if !matches!(op, "+" | "-" | "*" | "/") => None,
+        (
+            Tensor {
+                element_type: left_element,
+                shape: left_shape,
+            },
+            Tensor {
+                element_type: right_element,
+                shape: right_shape,
+            },
+        ) => Some(Tensor {
+            element_type: Box::new(tensor_element(left_element, right_element)),
+            shape: match (left_shape, right_shape) {
+                (Some(left), Some(right)) => Some(broadcast(left, right)?),
+                _ => None,
+            },
+        }),
+        (
+            Tensor {
+                element_type,
+                shape,
+            },
+            Number | Complex,
+        ) => Some(Tensor {
+            element_type: Box::new(tensor_element(element_type, right)),
+            shape: shape.clone(),
+        }),
+        (
+            Number | Complex,
+            Tensor {
+                element_type,
+                shape,
+            },
+        ) => Some(Tensor {
+            element_type: Box::new(tensor_element(left, element_type)),
+            shape: shape.clone(),
+        }),
+        (Vector, Number | Complex | Vector) | (Number | Complex, Vector) => Some(Vector),
+        (Complex, Number | Complex) | (Number, Complex) => Some(Complex),
+        (Number, Number) => Some(Number),
+        _ if is_opaque(left) || is_opaque(right) => Some(Any),
+        _ => None,
+    }
+}
+
+/// The type of `-operand`, or `None` if it isn't numeric
+pub(crate) fn negate(operand: &Type) -> Option<Type> {
+    let results = members(operand)
+        .into_iter()
+        .map(|member| match member {
+            Type::Number | Type::Complex | Type::Vector | Type::Tensor { .. } => {
+                Some(member.clone())
+            }
+            Type::Any => Some(Type::Any),
+            _ if is_opaque(member) => Some(Type::Any),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(union_of(results))
+}
+
+/// Whether `<`, `>`, `<=` and `>=` accept an operand of this type
+pub(crate) fn is_ordered(operand: &Type) -> bool {
+    members(operand).into_iter().all(|member| {
+        matches!(
+            member,
+            Type::Number | Type::Vector | Type::Tensor { .. } | Type::Any
+        ) || is_opaque(member)
+    })
+}
+
+/// Whether values of this type can be called
+pub(crate) fn is_callable(ty: &Type) -> bool {
+    members(ty).into_iter().all(|member| {
+        matches!(
+            member,
+            Type::Function { .. } | Type::AnyFunction | Type::Any
+        ) || is_opaque(member)
+    })
+}
+
+/// The type of `value.name`, or `None` if some member has no such field
+pub(crate) fn field(ty: &Type, name: &str) -> Option<Type> {
+    let results = members(ty)
+        .into_iter()
+        .map(|member| match member {
+            Type::Record { fields } => match fields.get(name) {
+                Some((_, optional, ty)) if *optional => Some(join(ty.clone(), Type::Null)),
+                Some((_, _, ty)) => Some(ty.clone()),
+                None => method(Receiver::Record, name),
+            },
+            Type::Number => method(Receiver::Number, name),
+            Type::Boolean => method(Receiver::Boolean, name),
+            Type::Complex => method(Receiver::Complex, name),
+            Type::String => method(Receiver::String, name),
+            Type::Null => None,
+            _ => Some(Type::Any),
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(union_of(results))
+}
+
+/// A method of a built-in type, such as `s.trim` or `x.abs`, read as a field
+fn method(receiver: Receiver, name: &str) -> Option<Type> {
+    receiver.has_method(name).then_some(Type::AnyFunction)
+}
+
+/// The type of `value[...]`, or `None` if some member can't be indexed
+pub(crate) fn element(ty: &Type) -> Option<Type> {
+    let results = members(ty)
+        .into_iter()
+        .map(|member| match member {
+            Type::String => Some(Type::String),
+            Type::Null
+            | Type::Number
+            | Type::Boolean
+            | Type::Complex
+            | Type::Function { .. }
+            | Type::AnyFunction => None,
+            _ => Some(Type::Any),
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(union_of(results))
+}
+
+/// Whether a type tells more than `Any`, e.g. for a hint
+pub(crate) fn is_informative(ty: &Type) -> bool {
+    match ty {
+        Type::Any => false,
+        Type::Union(members) => !members.is_empty(),
+        Type::Function {
+            params,
+            return_type,
+        } => params.iter().flatten().any(is_informative) || is_informative(return_type),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ty(source: &str) -> Type {
+        parse_type(source).unwrap()
+    }
+
+    #[test]
+    fn test_assignability() {
+        let cases = [
+            ("Number", "Number", true),
+            ("Number", "String", false),
+            ("Number | null", "Number", true),
+            ("Number", "Number | null", false),
+            ("Any", "String", true),
+            ("String", "Any", true),
+            ("{ x: Number }", "{ x: Number, y: String }", true),
+            ("{ x: Number, y?: String }", "{ x: Number }", true),
+            ("{ x: Number }", "{ y: Number }", false),
+            ("{ x: Number }", "{ x: String }", false),
+            ("(Number): Any", "(Any): Number", true),
+            ("(Any): Number", "(Number): String", false),
+            ("Function", "(Number): Number", true),
+            ("Record", "{ x: Number }", true),
+            ("Future", "Future", true),
+            ("Future", "Task", false),
+            ("Tensor<Number, [2, _]>", "Tensor<Number, [2, 3]>", true),
+            ("Tensor<Number, [2, 3]>", "Tensor<Number, [3, 3]>", false),
+            ("Tensor<Number>", "Vector", false),
+            ("Tensor<n, n>", "Tensor<2, 2>", true),
+            ("Tensor<n, k>", "Tensor<k, n>", false),
+            ("Tensor<n, k>", "Tensor<n, _>", true),
+        ];
+        for (expected, actual, assignable) in cases {
+            assert_eq!(
+                is_assignable(&ty(expected), &ty(actual)),
+                assignable,
+                "{} <- {}",
+                expected,
+                actual
+            );
+        }
+        assert!(is_assignable(&Type::Number, &never()));
+    }
+
+    #[test]
+    fn test_union_and_narrowing() {
+        let optional = union_of([Type::Number, Type::String, Type::Number, Type::Null]);
+        assert_eq!(optional, ty("Number | String | null"));
+        assert_eq!(union_of([Type::Number, Type::Any]), Type::Any);
+        assert_eq!(narrow(&optional, "Null", false), ty("Number | String"));
+        assert_eq!(narrow(&optional, "String", true), Type::String);
+        assert_eq!(narrow(&Type::Any, "Number", true), Type::Number);
+        assert_eq!(narrow(&Type::Any, "Null", false), Type::Any);
+        // Nothing left: keep what was known
+        assert_eq!(narrow(&Type::Number, "Null", true), Type::Number);
+    }
+
+    #[test]
+    fn test_operators() {
+        let op = |op: &str, l: &str, r: &str| arithmetic(op, &ty(l), &ty(r));
+        assert_eq!(op("+", "Number", "Number"), Some(Type::Number));
+        assert_eq!(op("*", "Number", "Complex"), Some(Type::Complex));
+        assert_eq!(op("-", "Vector", "Number"), Some(Type::Vector));
+        assert_eq!(op("+", "Boolean", "String"), Some(Type::String));
+        assert_eq!(op("*", "String", "Number"), Some(Type::String));
+        assert_eq!(op("-", "String", "Number"), None);
+        assert_eq!(op("+", "Number | null", "Number"), None);
+        assert_eq!(op("%", "Complex", "Number"), None);
+        assert_eq!(op("+", "Future", "Number"), Some(Type::Any));
+        assert_eq!(negate(&ty("Number | Vector")), Some(ty("Number | Vector")));
+        assert_eq!(negate(&Type::String), None);
+        assert!(is_ordered(&Type::Number));
+        assert!(!is_ordered(&ty("Number | null")));
+    }
+
+    #[test]
+    fn test_tensor_shapes() {
+        let shape = |source: &str| match ty(source) {
+            Type::Tensor { shape, .. } => shape.unwrap(),
+            _ => unreachable!(),
+        };
+        let dims = |source: &str| shape(&format!("Tensor<Number, [{}]>", source));
+        assert_eq!(broadcast(&dims("2, 3"), &dims("3")), Some(dims("2, 3")));
+        assert_eq!(broadcast(&dims("n, 1"), &dims("4")), Some(dims("n, 4")));
+        assert_eq!(broadcast(&dims("_, k"), &dims("2, 1")), Some(dims("2, k")));
+        assert_eq!(broadcast(&dims("2, 3"), &dims("2")), None);
+        assert_eq!(broadcast(&dims("n"), &dims("m")), None);
+
+        let op = |op: &str, l: &str, r: &str| arithmetic(op, &ty(l), &ty(r));
+        assert_eq!(
+            op("@", "Tensor<n, k>", "Tensor<k, 3>"),
+            Some(ty("Tensor<n, 3>"))
+        );
+        assert_eq!(op("@", "Tensor<n, k>", "Tensor<n, k>"), None);
+        assert_eq!(op("@", "Tensor<Number, [2]>", "Tensor<2, 2>"), None);
+        assert_eq!(op("@", "Vector", "Vector"), None);
+        assert_eq!(
+            op("*", "Tensor<2, 3>", "Complex"),
+            Some(ty("Tensor<Complex, [2, 3]>"))
+        );
+        assert_eq!(op("%", "Tensor<2, 3>", "Number"), None);
+
+        let mut sizes = ShapeBindings::new();
+        bind_dimensions(&ty("Tensor<n, k>"), &ty("Tensor<2, _>"), &mut sizes);
+        assert_eq!(
+            substitute_dimensions(&ty("(Tensor<k, n>): Tensor<n, n>"), &sizes),
+            ty("(Tensor<_, 2>): Tensor<2, 2>")
+        );
+    }
+
+    #[test]
+    fn test_fields_and_elements() {
+        let point = ty("{ x: Number, label?: String }");
+        assert_eq!(field(&point, "x"), Some(Type::Number));
+        assert_eq!(field(&point, "label"), Some(ty("String | null")));
+        assert_eq!(field(&point, "z"), None);
+        assert_eq!(field(&ty("{ x: Number } | null"), "x"), None);
+        assert_eq!(field(&Type::Any, "x"), Some(Type::Any));
+        // Methods of built-in types
+        assert_eq!(field(&Type::String, "trim"), Some(Type::AnyFunction));
+        assert_eq!(field(&point, "keys"), Some(Type::AnyFunction));
+        assert_eq!(field(&Type::Number, "length"), None);
+        assert_eq!(element(&Type::String), Some(Type::String));
+        assert_eq!(element(&ty("Vector | null")), None);
+    }
+}
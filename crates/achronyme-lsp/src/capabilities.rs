@@ -1,10 +1,20 @@
 use tower_lsp::lsp_types::*;
 
+use crate::handlers::semantic_tokens;
+
 /// Define the server capabilities for the Achronyme LSP
 pub fn server_capabilities() -> ServerCapabilities {
     ServerCapabilities {
-        // Full text sync - simplest to implement
-        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        // Incremental sync: clients send only the edited ranges; saves
+        // re-check importers
+        text_document_sync: Some(TextDocumentSyncCapability::Options(
+            TextDocumentSyncOptions {
+                open_close: Some(true),
+                change: Some(TextDocumentSyncKind::INCREMENTAL),
+                save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                ..Default::default()
+            },
+        )),
 
         // Hover information (function signatures, type info)
         hover_provider: Some(HoverProviderCapability::Simple(true)),
@@ -15,9 +25,21 @@ pub fn server_capabilities() -> ServerCapabilities {
         // Find references
         references_provider: Some(OneOf::Left(true)),
 
+        // Highlight other occurrences of the symbol under the cursor
+        document_highlight_provider: Some(OneOf::Left(true)),
+
+        // Rename a local symbol and all its references
+        rename_provider: Some(OneOf::Right(RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: Default::default(),
+        })),
+
         // Document symbols (outline)
         document_symbol_provider: Some(OneOf::Left(true)),
 
+        // Top-level symbols of every file in the workspace
+        workspace_symbol_provider: Some(OneOf::Left(true)),
+
         // Basic completion support (to be expanded)
         completion_provider: Some(CompletionOptions {
             trigger_characters: Some(vec![".".to_string(), ":".to_string()]),
@@ -34,6 +56,25 @@ pub fn server_capabilities() -> ServerCapabilities {
         // Document formatting support
         document_formatting_provider: Some(OneOf::Left(true)),
 
+        // Builtins, bindings by mutability, parameters, types and fields
+        semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(
+            SemanticTokensOptions {
+                legend: semantic_tokens::legend(),
+                full: Some(SemanticTokensFullOptions::Bool(true)),
+                range: None,
+                work_done_progress_options: Default::default(),
+            },
+        )),
+
+        // Inferred binding types and parameter names at call sites
+        inlay_hint_provider: Some(OneOf::Left(true)),
+
+        // Quick-fixes for lint diagnostics
+        code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+            code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+            ..Default::default()
+        })),
+
         // Diagnostics are pushed via publishDiagnostics (no special capability needed)
         ..Default::default()
     }
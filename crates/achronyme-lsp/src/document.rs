@@ -1,44 +1,85 @@
+use achronyme_lsp_core::{SemanticIndex, TypeCheck};
 use achronyme_parser::ast::AstNode;
-use achronyme_parser::parse;
+use achronyme_parser::{parse_recovering, SyntaxError};
+use tower_lsp::lsp_types::{Position, Range, TextDocumentContentChangeEvent};
 
 /// Represents an open document in the LSP server
+#[derive(Clone)]
 pub struct Document {
     /// The current text content of the document
     text: String,
     /// Lines of the document (cached for position calculations)
     lines: Vec<String>,
-    /// Parsed AST (if parsing succeeded)
-    ast: Option<Vec<AstNode>>,
-    /// Parse error (if parsing failed)
-    parse_error: Option<String>,
+    /// Byte offset where each line starts
+    line_starts: Vec<usize>,
+    /// Parsed AST; statements that failed to parse are `AstNode::Error`
+    ast: Vec<AstNode>,
+    /// Every syntax error in the document
+    errors: Vec<SyntaxError>,
+    /// Resolved names of the statements that parsed
+    semantic: Option<SemanticIndex>,
+    /// Type errors and inferred types of the statements that parsed
+    types: Option<TypeCheck>,
 }
 
 impl Document {
     pub fn new(text: String) -> Self {
-        let lines = text.lines().map(|s| s.to_string()).collect();
-        let (ast, parse_error) = match parse(&text) {
-            Ok(nodes) => (Some(nodes), None),
-            Err(e) => (None, Some(e)),
+        let mut doc = Self {
+            text: String::new(),
+            lines: Vec::new(),
+            line_starts: Vec::new(),
+            ast: Vec::new(),
+            errors: Vec::new(),
+            semantic: None,
+            types: None,
         };
+        doc.update_text(text);
+        doc
+    }
+
+    pub fn update_text(&mut self, new_text: String) {
+        self.text = new_text;
+        self.index_lines();
+        self.reparse();
+    }
 
-        Self {
-            text,
-            lines,
-            ast,
-            parse_error,
+    /// Apply the edits of a `didChange` notification in order, then reparse.
+    /// A change without a range replaces the whole text.
+    pub fn apply_changes(&mut self, changes: Vec<TextDocumentContentChangeEvent>) {
+        for change in changes {
+            match change.range {
+                Some(range) => {
+                    let start = self.offset_from_position(range.start.line, range.start.character);
+                    let end = self
+                        .offset_from_position(range.end.line, range.end.character)
+                        .max(start);
+                    self.text.replace_range(start..end, &change.text);
+                }
+                None => self.text = change.text,
+            }
+            self.index_lines();
         }
+        self.reparse();
     }
 
-    pub fn update_text(&mut self, new_text: String) {
-        self.lines = new_text.lines().map(|s| s.to_string()).collect();
-        let (ast, parse_error) = match parse(&new_text) {
-            Ok(nodes) => (Some(nodes), None),
-            Err(e) => (None, Some(e)),
-        };
+    fn index_lines(&mut self) {
+        self.lines = self.text.lines().map(|s| s.to_string()).collect();
+        self.line_starts = std::iter::once(0)
+            .chain(self.text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+    }
 
-        self.text = new_text;
-        self.ast = ast;
-        self.parse_error = parse_error;
+    /// Parse with error recovery, so a syntax error only costs the
+    /// statement it is in
+    fn reparse(&mut self) {
+        let recovered = parse_recovering(&self.text);
+        self.semantic = SemanticIndex::build(&recovered.masked).ok();
+        self.types = self
+            .semantic
+            .as_ref()
+            .and_then(|index| TypeCheck::run(&recovered.masked, index).ok());
+        self.ast = recovered.nodes;
+        self.errors = recovered.errors;
     }
 
     pub fn text(&self) -> &str {
@@ -49,12 +90,30 @@ impl Document {
         &self.lines
     }
 
-    pub fn ast(&self) -> Option<&Vec<AstNode>> {
-        self.ast.as_ref()
+    pub fn ast(&self) -> &[AstNode] {
+        &self.ast
+    }
+
+    pub fn syntax_errors(&self) -> &[SyntaxError] {
+        &self.errors
     }
 
-    pub fn parse_error(&self) -> Option<&str> {
-        self.parse_error.as_deref()
+    pub fn semantic(&self) -> Option<&SemanticIndex> {
+        self.semantic.as_ref()
+    }
+
+    pub fn types(&self) -> Option<&TypeCheck> {
+        self.types.as_ref()
+    }
+
+    /// Convert a byte range of the text to an LSP range
+    pub fn range_from_span(&self, span: &std::ops::Range<usize>) -> Range {
+        let (start_line, start_char) = self.position_from_offset(span.start);
+        let (end_line, end_char) = self.position_from_offset(span.end);
+        Range {
+            start: Position::new(start_line, start_char),
+            end: Position::new(end_line, end_char),
+        }
     }
 
     /// Get the word at a given position (line, column)
@@ -93,38 +152,98 @@ impl Document {
         }
     }
 
-    /// Get byte offset from position
-    #[allow(dead_code)]
+    /// Byte offset of an LSP position (`character` counts UTF-16 code
+    /// units); positions past the end of a line or the text are clamped
     pub fn offset_from_position(&self, line: u32, character: u32) -> usize {
-        let mut offset = 0;
-        for (idx, l) in self.lines.iter().enumerate() {
-            if idx == line as usize {
-                offset += character as usize;
-                break;
+        let Some(&start) = self.line_starts.get(line as usize) else {
+            return self.text.len();
+        };
+        let end = self
+            .line_starts
+            .get(line as usize + 1)
+            .map_or(self.text.len(), |next| next - 1);
+        let mut units = 0;
+        for (i, c) in self.text[start..end].char_indices() {
+            if units >= character as usize || c == '\r' {
+                return start + i;
             }
-            offset += l.len() + 1; // +1 for newline
+            units += c.len_utf16();
         }
-        offset
+        end
     }
 
-    /// Get position from byte offset
-    #[allow(dead_code)]
+    /// LSP position (line, UTF-16 character) of a byte offset
     pub fn position_from_offset(&self, offset: usize) -> (u32, u32) {
-        let mut current_offset = 0;
-        for (idx, l) in self.lines.iter().enumerate() {
-            let line_end = current_offset + l.len() + 1; // +1 for newline
-            if offset < line_end {
-                return (idx as u32, (offset - current_offset) as u32);
-            }
-            current_offset = line_end;
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
         }
-        // If offset is beyond the document, return end of last line
-        let last_line = self.lines.len().saturating_sub(1);
-        let last_char = self.lines.get(last_line).map(|l| l.len()).unwrap_or(0);
-        (last_line as u32, last_char as u32)
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let character: usize = self.text[self.line_starts[line]..offset]
+            .chars()
+            .map(char::len_utf16)
+            .sum();
+        (line as u32, character as u32)
     }
 }
 
 fn is_word_char(c: char) -> bool {
     c.is_alphanumeric() || c == '_'
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(range: Option<(u32, u32, u32, u32)>, text: &str) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range: range
+                .map(|(l1, c1, l2, c2)| Range::new(Position::new(l1, c1), Position::new(l2, c2))),
+            range_length: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_incremental_changes() {
+        let mut doc = Document::new("let x = 1\nlet y = 2".to_string());
+        doc.apply_changes(vec![
+            change(Some((1, 4, 1, 5)), "total"),
+            change(Some((0, 8, 0, 9)), "10"),
+            change(Some((1, 13, 1, 13)), "\ntotal + x"),
+        ]);
+        assert_eq!(doc.text(), "let x = 10\nlet total = 2\ntotal + x");
+        assert_eq!(doc.lines().len(), 3);
+        assert!(doc.syntax_errors().is_empty());
+
+        doc.apply_changes(vec![change(None, "let z = 3")]);
+        assert_eq!(doc.text(), "let z = 3");
+    }
+
+    #[test]
+    fn test_positions_count_utf16_units() {
+        // "𝑥" is one char, four bytes and two UTF-16 code units
+        let doc = Document::new("let s = \"𝑥\" + t\r\nlet é = 1".to_string());
+        let t = doc.text().find('t').unwrap();
+        let t = doc.text()[t + 1..].find('t').unwrap() + t + 1;
+        assert_eq!(doc.position_from_offset(t), (0, 15));
+        assert_eq!(doc.offset_from_position(0, 15), t);
+        // Past the end of a line clamps before the \r\n
+        assert_eq!(doc.offset_from_position(0, 99), t + 1);
+        assert_eq!(doc.offset_from_position(1, 5), doc.text().len() - 4);
+        assert_eq!(doc.offset_from_position(7, 0), doc.text().len());
+    }
+
+    #[test]
+    fn test_broken_code_keeps_the_rest_of_the_ast() {
+        let doc = Document::new("let a = 1\nlet b = (\nlet c = a +\nlet d = a".to_string());
+        assert_eq!(doc.syntax_errors().len(), 2);
+        let index = doc.semantic().unwrap();
+        let names: Vec<_> = index.symbols().iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["a", "d"]);
+        let AstNode::Sequence { statements } = &doc.ast()[0] else {
+            panic!("expected a sequence");
+        };
+        assert_eq!(statements.len(), 4);
+    }
+}
@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use std::path::Component;
+
+use achronyme_lsp_core::{get_all_signatures, SemanticIndex};
+use tower_lsp::lsp_types::*;
+
+use crate::document::Document;
+use crate::workspace::Workspace;
+
+/// Misspellings further than this many edits from a builtin get no suggestion
+const MAX_EDIT_DISTANCE: usize = 2;
+
+/// Suggest at most this many builtins for a misspelled name
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Quick-fixes for the lint diagnostics in a code action request
+pub fn code_actions(
+    workspace: &Workspace,
+    doc: &Document,
+    uri: &Url,
+    diagnostics: &[Diagnostic],
+) -> Vec<CodeActionOrCommand> {
+    let Some(index) = doc.semantic() else {
+        return Vec::new();
+    };
+    let mut actions = Vec::new();
+    for diagnostic in diagnostics {
+        if diagnostic.source.as_deref() != Some("achronyme") {
+            continue;
+        }
+        let Some(NumberOrString::String(code)) = &diagnostic.code else {
+            continue;
+        };
+        let start = diagnostic.range.start;
+        let offset = doc.offset_from_position(start.line, start.character);
+        let Some(occurrence) = index.occurrence_at(offset) else {
+            continue;
+        };
+
+        let fixes = match code.as_str() {
+            "immutable-assignment" => occurrence
+                .symbol
+                .and_then(|id| make_mutable(doc, index, id))
+                .into_iter()
+                .collect(),
+            "undefined-name" => {
+                let mut fixes = import_fixes(workspace, doc, index, uri, &occurrence.name);
+                fixes.extend(
+                    similar_builtins(&occurrence.name)
+                        .into_iter()
+                        .map(|builtin| Fix {
+                            title: format!("Replace with `{}`", builtin),
+                            edits: vec![TextEdit {
+                                range: doc.range_from_span(&occurrence.span),
+                                new_text: builtin,
+                            }],
+                        }),
+                );
+                fixes
+            }
+            _ => continue,
+        };
+
+        // A lone fix is safe to apply without asking
+        let preferred = fixes.len() == 1;
+        actions.extend(fixes.into_iter().map(|fix| {
+            CodeActionOrCommand::CodeAction(CodeAction {
+                title: fix.title,
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diagnostic.clone()]),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(HashMap::from([(uri.clone(), fix.edits)])),
+                    ..Default::default()
+                }),
+                is_preferred: Some(preferred),
+                ..Default::default()
+            })
+        }));
+    }
+    actions
+}
+
+struct Fix {
+    title: String,
+    edits: Vec<TextEdit>,
+}
+
+/// Turn the `let` that declared a symbol into `mut`
+fn make_mutable(doc: &Document, index: &SemanticIndex, id: usize) -> Option<Fix> {
+    let symbol = index.symbol(id);
+    let keyword = symbol.keyword.as_ref()?;
+    Some(Fix {
+        title: format!("Make `{}` mutable", symbol.name),
+        edits: vec![TextEdit {
+            range: doc.range_from_span(keyword),
+            new_text: "mut".to_string(),
+        }],
+    })
+}
+
+/// Import `name` from each workspace module that exports it
+fn import_fixes(
+    workspace: &Workspace,
+    doc: &Document,
+    index: &SemanticIndex,
+    uri: &Url,
+    name: &str,
+) -> Vec<Fix> {
+    let mut fixes = Vec::new();
+    for file in workspace.files() {
+        if &file == uri {
+            continue;
+        }
+        let Some(module) = workspace.load(&file) else {
+            continue;
+        };
+        let exports = module
+            .document
+            .semantic()
+            .is_some_and(|exports| exports.export(name).is_some());
+        if !exports {
+            continue;
+        }
+        let Some(path) = import_path(uri, &file) else {
+            continue;
+        };
+
+        // Extend an existing import of the module, or add one after the others
+        let existing = index.imports().iter().find(|import| {
+            !import.items.is_empty()
+                && workspace.resolve(uri, &import.module).as_ref() == Some(&file)
+        });
+        let edit = match existing {
+            Some(import) => {
+                let last = import
+                    .items
+                    .last()
+                    .map(|item| item.span.end)
+                    .unwrap_or_default();
+                let (line, character) = doc.position_from_offset(last);
+                TextEdit {
+                    range: Range::new(
+                        Position::new(line, character),
+                        Position::new(line, character),
+                    ),
+                    new_text: format!(", {}", name),
+                }
+            }
+            None => {
+                let line = index
+                    .imports()
+                    .last()
+                    .map(|import| doc.position_from_offset(import.module_span.end).0 + 1)
+                    .unwrap_or(0);
+                TextEdit {
+                    range: Range::new(Position::new(line, 0), Position::new(line, 0)),
+                    new_text: format!("import {{ {} }} from \"{}\"\n", name, path),
+                }
+            }
+        };
+        fixes.push(Fix {
+            title: format!("Import `{}` from \"{}\"", name, path),
+            edits: vec![edit],
+        });
+    }
+    fixes
+}
+
+/// The relative module path `importer` uses to import `module`
+fn import_path(importer: &Url, module: &Url) -> Option<String> {
+    let importer = importer.to_file_path().ok()?;
+    let module = module.to_file_path().ok()?.with_extension("");
+    let from: Vec<Component> = importer.parent()?.components().collect();
+    let to: Vec<Component> = module.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    if parts.is_empty() {
+        parts.push(".".to_string());
+    }
+    parts.extend(
+        to[common..]
+            .iter()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned()),
+    );
+    Some(parts.join("/"))
+}
+
+/// Builtins within a couple of edits of `name`, closest first
+fn similar_builtins(name: &str) -> Vec<String> {
+    let mut candidates: Vec<(usize, &str)> = get_all_signatures()
+        .keys()
+        .map(|builtin| (edit_distance(name, builtin), builtin.as_str()))
+        .filter(|&(distance, _)| distance <= MAX_EDIT_DISTANCE && distance < name.len())
+        .collect();
+    candidates.sort();
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, builtin)| builtin.to_string())
+        .collect()
+}
+
+/// Levenshtein distance between two names
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::diagnostics::lint_diagnostics;
+    use crate::workspace::tests::Project;
+
+    /// Titles and edits of the fixes for every lint diagnostic
+    fn fixes(project: &Project, path: &str) -> Vec<(String, Vec<TextEdit>)> {
+        let (uri, doc) = project.open(path);
+        let diagnostics = lint_diagnostics(&doc);
+        code_actions(&project.workspace, &doc, &uri, &diagnostics)
+            .into_iter()
+            .map(|action| {
+                let CodeActionOrCommand::CodeAction(action) = action else {
+                    panic!("expected a code action");
+                };
+                let mut changes = action.edit.unwrap().changes.unwrap();
+                (action.title, changes.remove(&uri).unwrap())
+            })
+            .collect()
+    }
+
+    fn edit(line: u32, start: u32, end: u32, text: &str) -> TextEdit {
+        TextEdit {
+            range: Range::new(Position::new(line, start), Position::new(line, end)),
+            new_text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_make_binding_mutable() {
+        let project = Project::new(
+            "actions_mut",
+            &[("main.soc", "let count = 0\ncount = count + 1")],
+        );
+        assert_eq!(
+            fixes(&project, "main.soc"),
+            [(
+                "Make `count` mutable".to_string(),
+                vec![edit(0, 0, 3, "mut")]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_replace_misspelled_builtin() {
+        let project = Project::new(
+            "actions_builtin",
+            &[("main.soc", "sqr(4) + fliter(x => x > 1, [1])")],
+        );
+        let found = fixes(&project, "main.soc");
+        let titles: Vec<_> = found.iter().map(|(title, _)| title.as_str()).collect();
+        assert!(titles.contains(&"Replace with `sqrt`"));
+        assert!(titles.contains(&"Replace with `filter`"));
+        let (_, edits) = found
+            .iter()
+            .find(|(title, _)| title == "Replace with `filter`")
+            .unwrap();
+        assert_eq!(edits, &[edit(0, 9, 15, "filter")]);
+    }
+
+    #[test]
+    fn test_import_unresolved_name() {
+        let project = Project::new(
+            "actions_import",
+            &[
+                ("lib/geometry.soc", "let area = r => PI * r^2\nlet perimeter = r => 2 * PI * r\nexport { area, perimeter }"),
+                ("app/main.soc", "import { area } from \"../lib/geometry\"\nlet a = area(2) + perimeter(2)"),
+                ("app/other.soc", "let x = perimeter(1)"),
+            ],
+        );
+        assert_eq!(
+            fixes(&project, "app/main.soc"),
+            [(
+                "Import `perimeter` from \"../lib/geometry\"".to_string(),
+                vec![edit(0, 13, 13, ", perimeter")]
+            )]
+        );
+
+        let project = Project::new(
+            "actions_import_new",
+            &[
+                ("util.soc", "let double = x => 2 * x\nexport { double }"),
+                ("main.soc", "let y = double(3)"),
+            ],
+        );
+        assert_eq!(
+            fixes(&project, "main.soc"),
+            [(
+                "Import `double` from \"./util\"".to_string(),
+                vec![edit(0, 0, 0, "import { double } from \"./util\"\n")]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("sqrt", "sqrt"), 0);
+        assert_eq!(edit_distance("sqr", "sqrt"), 1);
+        assert_eq!(edit_distance("fliter", "filter"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
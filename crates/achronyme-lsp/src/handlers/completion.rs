@@ -1,10 +1,14 @@
 use achronyme_lsp_core::{
-    get_all_completions, CompletionEntry as CoreCompletionEntry, CompletionKind,
+    get_all_completions, CompletionEntry as CoreCompletionEntry, CompletionKind, SymbolKind,
 };
 use once_cell::sync::Lazy;
-use tower_lsp::lsp_types::*;
+use tower_lsp::lsp_types::{
+    CompletionItem, CompletionItemKind, Documentation, InsertTextFormat, MarkupContent, MarkupKind,
+    Position, Url,
+};
 
 use crate::document::Document;
+use crate::workspace::Workspace;
 
 /// Cached LSP completion items converted from core completion entries
 static BUILTIN_COMPLETIONS: Lazy<Vec<CompletionItem>> = Lazy::new(|| {
@@ -60,7 +64,7 @@ fn convert_to_lsp_completion(entry: &CoreCompletionEntry) -> CompletionItem {
         detail: Some(entry.detail.clone()),
         documentation: Some(Documentation::MarkupContent(MarkupContent {
             kind: MarkupKind::Markdown,
-            value: format_documentation(&entry.documentation, &entry.kind),
+            value: entry.documentation.clone(),
         })),
         insert_text: Some(entry.insert_text.clone()),
         insert_text_format: Some(insert_text_format),
@@ -68,22 +72,13 @@ fn convert_to_lsp_completion(entry: &CoreCompletionEntry) -> CompletionItem {
     }
 }
 
-/// Format documentation for markdown display
-fn format_documentation(doc: &str, kind: &CompletionKind) -> String {
-    match kind {
-        CompletionKind::Function => {
-            // Function docs already contain signature and examples
-            format!(
-                "```\n{}\n```",
-                doc.replace("Example:", "```\n\n**Example:**\n```achronyme")
-            )
-        }
-        _ => doc.to_string(),
-    }
-}
-
 /// Get completion items for a position in the document
-pub fn get_completions(doc: &Document, position: Position) -> Vec<CompletionItem> {
+pub fn get_completions(
+    workspace: &Workspace,
+    doc: &Document,
+    uri: &Url,
+    position: Position,
+) -> Vec<CompletionItem> {
     let context = analyze_completion_context(doc, position);
 
     match context {
@@ -93,9 +88,8 @@ pub fn get_completions(doc: &Document, position: Position) -> Vec<CompletionItem
             vec![]
         }
         CompletionContext::AfterImport => {
-            // After 'import { ': Suggest module exports
-            // For now, return empty - this would need module resolution
-            vec![]
+            // After 'import { ': Suggest the exports of the module not yet listed
+            import_completions(workspace, doc, uri, position)
         }
         CompletionContext::VariableDeclaration => {
             // After 'let ' or 'mut ': User is naming variable, don't suggest
@@ -113,6 +107,71 @@ pub fn get_completions(doc: &Document, position: Position) -> Vec<CompletionItem
     }
 }
 
+/// Exports of the module named by the import list at the cursor
+fn import_completions(
+    workspace: &Workspace,
+    doc: &Document,
+    uri: &Url,
+    position: Position,
+) -> Vec<CompletionItem> {
+    let Some((module_path, listed)) = import_list_at(doc, position) else {
+        return vec![];
+    };
+    let Some(module) = workspace
+        .resolve(uri, &module_path)
+        .and_then(|target| workspace.load(&target))
+    else {
+        return vec![];
+    };
+    let Some(index) = module.document.semantic() else {
+        return vec![];
+    };
+
+    index
+        .exports()
+        .iter()
+        .filter(|export| !listed.contains(&export.name))
+        .map(|export| {
+            let is_type = export
+                .symbol
+                .is_some_and(|id| index.symbol(id).kind == SymbolKind::TypeAlias);
+            CompletionItem {
+                label: export.name.clone(),
+                kind: Some(if is_type {
+                    CompletionItemKind::CLASS
+                } else {
+                    CompletionItemKind::VARIABLE
+                }),
+                detail: Some(format!("exported by \"{}\"", module_path)),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+/// The module path of an `import { ... } from "path"` line with the cursor
+/// inside its braces, and the names listed apart from the one being typed
+fn import_list_at(doc: &Document, position: Position) -> Option<(String, Vec<String>)> {
+    let line = doc.lines().get(position.line as usize)?;
+    let cursor = (position.character as usize).min(line.len());
+    let (before, after) = line.split_at(cursor);
+
+    let open = before.rfind('{')?;
+    let close = after.find('}')?;
+    let rest = after[close + 1..].trim_start().strip_prefix("from")?;
+    let path = rest.trim_start().strip_prefix('"')?;
+    let path = &path[..path.find('"')?];
+
+    let item_name = |item: &str| item.split_whitespace().next().map(str::to_string);
+    let mut listed: Vec<String> = before[open + 1..]
+        .split(',')
+        .filter_map(item_name)
+        .collect();
+    listed.pop_if(|_| !before.trim_end().ends_with(','));
+    listed.extend(after[..close].split(',').skip(1).filter_map(item_name));
+    Some((path.to_string(), listed))
+}
+
 /// Context for completion
 enum CompletionContext {
     AfterDot,
@@ -166,6 +225,7 @@ fn analyze_completion_context(doc: &Document, position: Position) -> CompletionC
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::workspace::tests::Project;
 
     #[test]
     fn test_builtin_completions_count() {
@@ -256,7 +316,8 @@ mod tests {
     #[test]
     fn test_default_completions() {
         let doc = Document::new("".to_string());
-        let completions = get_completions(&doc, Position::new(0, 0));
+        let completions =
+            get_completions(&Workspace::new(), &doc, &test_uri(), Position::new(0, 0));
 
         // Should include all categories
         let has_function = completions
@@ -281,7 +342,8 @@ mod tests {
     #[test]
     fn test_no_completions_after_let() {
         let doc = Document::new("let ".to_string());
-        let completions = get_completions(&doc, Position::new(0, 4));
+        let completions =
+            get_completions(&Workspace::new(), &doc, &test_uri(), Position::new(0, 4));
         assert!(
             completions.is_empty(),
             "Should not suggest completions after 'let '"
@@ -303,28 +365,52 @@ mod tests {
         let completions = &*BUILTIN_COMPLETIONS;
         let names: Vec<&str> = completions.iter().map(|c| c.label.as_str()).collect();
 
-        // Check math functions
-        assert!(names.contains(&"sin"), "Missing sin");
-        assert!(names.contains(&"cos"), "Missing cos");
-        assert!(names.contains(&"sqrt"), "Missing sqrt");
-        assert!(names.contains(&"factorial"), "Missing factorial");
-
-        // Check array functions
-        assert!(names.contains(&"map"), "Missing map");
-        assert!(names.contains(&"filter"), "Missing filter");
-        assert!(names.contains(&"reduce"), "Missing reduce");
-        assert!(names.contains(&"len"), "Missing len");
-
-        // Check DSP functions
-        assert!(names.contains(&"fft"), "Missing fft");
-        assert!(names.contains(&"convolve"), "Missing convolve");
-
-        // Check linear algebra
-        assert!(names.contains(&"det"), "Missing det");
-        assert!(names.contains(&"inv"), "Missing inv");
-
-        // Check graph algorithms
-        assert!(names.contains(&"dijkstra"), "Missing dijkstra");
-        assert!(names.contains(&"bfs"), "Missing bfs");
+        for name in [
+            "sin", "cos", "sqrt", "map", "filter", "reduce", "len", "det", "spawn",
+        ] {
+            assert!(names.contains(&name), "Missing {}", name);
+        }
+
+        // Only registered builtins are offered
+        for name in ["factorial", "fft", "inv", "dijkstra"] {
+            assert!(!names.contains(&name), "Unexpected {}", name);
+        }
+    }
+
+    fn test_uri() -> Url {
+        Url::parse("file:///test.soc").unwrap()
+    }
+
+    #[test]
+    fn test_import_list_completes_module_exports() {
+        let project = Project::new(
+            "completion",
+            &[
+                (
+                    "shapes.soc",
+                    "type Point = { x: Number }\nlet area = r => pi * r^2\nlet origin = { x: 0 }\nexport { Point, area, origin }",
+                ),
+                ("main.soc", "import { area, o } from \"./shapes\""),
+            ],
+        );
+        let (uri, doc) = project.open("main.soc");
+        let labels = |character| -> Vec<(String, Option<CompletionItemKind>)> {
+            get_completions(&project.workspace, &doc, &uri, Position::new(0, character))
+                .into_iter()
+                .map(|item| (item.label, item.kind))
+                .collect()
+        };
+
+        // Typing `o`: `area` is already listed
+        assert_eq!(
+            labels(16),
+            [
+                ("Point".to_string(), Some(CompletionItemKind::CLASS)),
+                ("origin".to_string(), Some(CompletionItemKind::VARIABLE)),
+            ]
+        );
+        // Right after `{`: the item under the cursor is being replaced
+        let names: Vec<_> = labels(8).into_iter().map(|(label, _)| label).collect();
+        assert_eq!(names, ["Point", "area", "origin"]);
     }
 }
@@ -1,110 +1,117 @@
 use tower_lsp::lsp_types::*;
 
 use crate::document::Document;
+use crate::workspace::Workspace;
 
-/// Get the definition location for a symbol at the given position
+/// Get the definition location for a symbol at the given position.
+///
+/// Imported names jump to their definition in the exporting module when
+/// it can be found, and to the import otherwise.
 pub fn get_definition(
+    workspace: &Workspace,
     doc: &Document,
     position: Position,
     uri: Url,
 ) -> Option<GotoDefinitionResponse> {
-    let word = doc.word_at_position(position.line, position.character)?;
-
-    // Search for variable definition in the AST
-    if let Some(ast) = doc.ast() {
-        if let Some((def_line, def_col)) = find_definition_location(ast, &word, doc.text()) {
-            let location = Location {
-                uri,
-                range: Range {
-                    start: Position {
-                        line: def_line,
-                        character: def_col,
-                    },
-                    end: Position {
-                        line: def_line,
-                        character: def_col + word.len() as u32,
-                    },
-                },
-            };
-            return Some(GotoDefinitionResponse::Scalar(location));
-        }
+    let offset = doc.offset_from_position(position.line, position.character);
+    if let Some(found) = workspace.imported_definition(&uri, doc, offset) {
+        let module = &found.module.document;
+        let symbol = module.semantic()?.symbol(found.symbol);
+        return Some(GotoDefinitionResponse::Scalar(Location {
+            uri: found.module.uri,
+            range: module.range_from_span(&symbol.span),
+        }));
     }
 
-    None
-}
-
-/// Find the location (line, column) where a variable is defined
-fn find_definition_location(
-    ast: &[achronyme_parser::ast::AstNode],
-    name: &str,
-    source: &str,
-) -> Option<(u32, u32)> {
-    use achronyme_parser::ast::AstNode;
+    let index = doc.semantic()?;
+    let symbol = index.symbol(index.symbol_at(offset)?);
 
-    // Simple approach: search for "let name" or "mut name" in the source
-    // This is a basic implementation; a proper one would track AST node positions
+    Some(GotoDefinitionResponse::Scalar(Location {
+        uri,
+        range: doc.range_from_span(&symbol.span),
+    }))
+}
 
-    let lines: Vec<&str> = source.lines().collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::tests::Project;
 
-    for (line_idx, line) in lines.iter().enumerate() {
-        // Check for "let name ="
-        if let Some(pos) = line.find(&format!("let {} ", name)) {
-            let name_start = pos + 4; // "let " is 4 chars
-            return Some((line_idx as u32, name_start as u32));
-        }
-        if let Some(pos) = line.find(&format!("let {}:", name)) {
-            let name_start = pos + 4;
-            return Some((line_idx as u32, name_start as u32));
-        }
-        if let Some(pos) = line.find(&format!("let {}=", name)) {
-            let name_start = pos + 4;
-            return Some((line_idx as u32, name_start as u32));
+    fn definition_at(source: &str, line: u32, character: u32) -> Option<Range> {
+        let doc = Document::new(source.to_string());
+        let uri = Url::parse("file:///test.soc").unwrap();
+        let workspace = Workspace::new();
+        match get_definition(&workspace, &doc, Position::new(line, character), uri)? {
+            GotoDefinitionResponse::Scalar(location) => Some(location.range),
+            other => panic!("unexpected response {:?}", other),
         }
+    }
 
-        // Check for "mut name ="
-        if let Some(pos) = line.find(&format!("mut {} ", name)) {
-            let name_start = pos + 4; // "mut " is 4 chars
-            return Some((line_idx as u32, name_start as u32));
-        }
-        if let Some(pos) = line.find(&format!("mut {}:", name)) {
-            let name_start = pos + 4;
-            return Some((line_idx as u32, name_start as u32));
-        }
-        if let Some(pos) = line.find(&format!("mut {}=", name)) {
-            let name_start = pos + 4;
-            return Some((line_idx as u32, name_start as u32));
-        }
+    #[test]
+    fn test_find_let_definition() {
+        let range = definition_at("let x = 42\nlet y = x + 1", 1, 8).unwrap();
+        assert_eq!(range.start, Position::new(0, 4));
+        assert_eq!(range.end, Position::new(0, 5));
     }
 
-    // Also check for function parameters (lambda)
-    for node in ast {
-        if let AstNode::Lambda { params, .. } = node {
-            for (param_name, _, _) in params {
-                if param_name == name {
-                    // Find in source where this parameter is defined
-                    // This is simplified - we'd need span info for accuracy
-                    for (line_idx, line) in lines.iter().enumerate() {
-                        if let Some(pos) = line.find(param_name) {
-                            return Some((line_idx as u32, pos as u32));
-                        }
-                    }
-                }
-            }
-        }
+    #[test]
+    fn test_definition_follows_shadowing() {
+        let source = "let x = 1\nlet f = (x) => x + 1\nx";
+        assert_eq!(
+            definition_at(source, 1, 15).unwrap().start,
+            Position::new(1, 9)
+        );
+        assert_eq!(
+            definition_at(source, 2, 0).unwrap().start,
+            Position::new(0, 4)
+        );
     }
 
-    None
-}
+    #[test]
+    fn test_no_definition_for_builtins() {
+        assert_eq!(definition_at("sin(1)", 0, 1), None);
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_definition_across_files() {
+        let project = Project::new(
+            "definition",
+            &[
+                ("lib/stats.soc", "let helper = 1\nlet mean = xs => sum(xs) / len(xs)\nexport { mean as average }"),
+                ("main.soc", "import { average as avg } from \"lib/stats\"\navg([1, 2])"),
+            ],
+        );
+        let (uri, doc) = project.open("main.soc");
+        let location_at = |line, character| match get_definition(
+            &project.workspace,
+            &doc,
+            Position::new(line, character),
+            uri.clone(),
+        ) {
+            Some(GotoDefinitionResponse::Scalar(location)) => location,
+            other => panic!("unexpected response {:?}", other),
+        };
+
+        // From a use of the alias and from the imported name itself
+        for location in [location_at(1, 1), location_at(0, 11)] {
+            assert_eq!(location.uri, project.uri("lib/stats.soc"));
+            assert_eq!(location.range.start, Position::new(1, 4));
+        }
+    }
 
     #[test]
-    fn test_find_let_definition() {
-        let source = "let x = 42\nlet y = x + 1";
-        let ast = achronyme_parser::parse(source).unwrap();
-        let result = find_definition_location(&ast, "x", source);
-        assert_eq!(result, Some((0, 4)));
+    fn test_unresolved_import_falls_back_to_the_import() {
+        let project = Project::new(
+            "definition_missing",
+            &[("main.soc", "import { f } from \"./nope\"\nf()")],
+        );
+        let (uri, doc) = project.open("main.soc");
+        match get_definition(&project.workspace, &doc, Position::new(1, 0), uri.clone()) {
+            Some(GotoDefinitionResponse::Scalar(location)) => {
+                assert_eq!(location.uri, uri);
+                assert_eq!(location.range.start, Position::new(0, 9));
+            }
+            other => panic!("unexpected response {:?}", other),
+        }
     }
 }
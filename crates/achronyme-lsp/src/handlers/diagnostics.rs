@@ -1,64 +1,136 @@
+use achronyme_lsp_core::{get_signature, is_constant, Access, Namespace, SymbolKind};
 use tower_lsp::lsp_types::*;
 
 use crate::document::Document;
+use crate::workspace::Workspace;
 
-/// Compute diagnostics for a document by parsing it
+/// Report every syntax error in a document
 pub fn compute_diagnostics(doc: &Document) -> Vec<Diagnostic> {
-    let mut diagnostics = Vec::new();
-
-    if let Some(error) = doc.parse_error() {
-        // Parse the error message to extract position information
-        let (line, character, message) = parse_error_message(error);
-
-        diagnostics.push(Diagnostic {
-            range: Range {
-                start: Position { line, character },
-                end: Position {
-                    line,
-                    character: character + 1,
-                },
-            },
+    doc.syntax_errors()
+        .iter()
+        .map(|error| Diagnostic {
+            range: doc.range_from_span(&error.span),
             severity: Some(DiagnosticSeverity::ERROR),
             code: Some(NumberOrString::String("parse-error".to_string())),
             source: Some("achronyme".to_string()),
-            message,
+            message: format!("Syntax error: {}", error.message),
             ..Default::default()
-        });
-    }
+        })
+        .collect()
+}
+
+/// Report imports of missing modules and of names a module doesn't export
+pub fn import_diagnostics(workspace: &Workspace, doc: &Document, uri: &Url) -> Vec<Diagnostic> {
+    let Some(index) = doc.semantic() else {
+        return Vec::new();
+    };
+    let error = |span: &std::ops::Range<usize>, code: &str, message: String| Diagnostic {
+        range: doc.range_from_span(span),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(code.to_string())),
+        source: Some("achronyme".to_string()),
+        message,
+        ..Default::default()
+    };
 
+    let mut diagnostics = Vec::new();
+    for import in index.imports() {
+        let Some(module) = workspace.import_target(uri, import) else {
+            diagnostics.push(error(
+                &import.module_span,
+                "unresolved-module",
+                format!("Cannot find module '{}'", import.module),
+            ));
+            continue;
+        };
+        // A module that doesn't parse has its own diagnostics
+        let Some(exports) = module.document.semantic() else {
+            continue;
+        };
+        for item in &import.items {
+            if exports.export(&item.name).is_none() {
+                diagnostics.push(error(
+                    &item.span,
+                    "unknown-import",
+                    format!(
+                        "Module '{}' has no export named '{}'",
+                        import.module, item.name
+                    ),
+                ));
+            }
+        }
+    }
     diagnostics
 }
 
-/// Parse error message to extract position (line, column) and clean message
-/// Pest error messages typically contain position information like:
-/// "Parse error:  --> 1:10\n  |\n1 | let x = ;\n  |          ^---"
-fn parse_error_message(error: &str) -> (u32, u32, String) {
-    let mut line = 0u32;
-    let mut character = 0u32;
-    let message = error.to_string();
-
-    // Try to extract position from Pest error format
-    // Format: " --> line:column"
-    if let Some(pos_start) = error.find(" --> ") {
-        let pos_str = &error[pos_start + 5..];
-        if let Some(colon_pos) = pos_str.find(':') {
-            let line_str = &pos_str[..colon_pos];
-            let rest = &pos_str[colon_pos + 1..];
+/// Report what the type checker finds: values that don't match their
+/// annotations, calls with the wrong arguments and uses of values that may
+/// be `null`
+pub fn type_diagnostics(doc: &Document) -> Vec<Diagnostic> {
+    let Some(types) = doc.types() else {
+        return Vec::new();
+    };
+    types
+        .errors()
+        .iter()
+        .map(|error| Diagnostic {
+            range: doc.range_from_span(&error.span),
+            severity: Some(DiagnosticSeverity::ERROR),
+            code: Some(NumberOrString::String("type-error".to_string())),
+            source: Some("achronyme".to_string()),
+            message: error.message.clone(),
+            ..Default::default()
+        })
+        .collect()
+}
 
-            // Find end of column number (usually newline or end of string)
-            let col_end = rest.find('\n').unwrap_or(rest.len());
-            let col_str = &rest[..col_end];
+/// Call syntax the parser turns into expressions: `if(c, a, b)`, `piecewise(...)`
+const SPECIAL_FORMS: &[&str] = &["if", "piecewise"];
 
-            if let Ok(l) = line_str.trim().parse::<u32>() {
-                line = l.saturating_sub(1); // LSP uses 0-based line numbers
+/// Report assignments to `let` bindings and names that resolve to nothing
+///
+/// Code actions offer fixes for both (see `code_actions`).
+pub fn lint_diagnostics(doc: &Document) -> Vec<Diagnostic> {
+    let Some(index) = doc.semantic() else {
+        return Vec::new();
+    };
+    let mut diagnostics = Vec::new();
+    for occurrence in index.occurrences() {
+        let (severity, code, message) = match occurrence.symbol {
+            Some(id) if occurrence.access == Access::Write => {
+                if index.symbol(id).kind != SymbolKind::Let {
+                    continue;
+                }
+                (
+                    DiagnosticSeverity::WARNING,
+                    "immutable-assignment",
+                    format!("Cannot assign to immutable variable '{}'", occurrence.name),
+                )
             }
-            if let Ok(c) = col_str.trim().parse::<u32>() {
-                character = c.saturating_sub(1); // LSP uses 0-based columns
+            // Builtins and constants resolve to no binding
+            None if occurrence.namespace == Namespace::Value
+                && get_signature(&occurrence.name).is_none()
+                && !is_constant(&occurrence.name)
+                && !SPECIAL_FORMS.contains(&occurrence.name.as_str()) =>
+            {
+                (
+                    DiagnosticSeverity::ERROR,
+                    "undefined-name",
+                    format!("Cannot find name '{}'", occurrence.name),
+                )
             }
-        }
+            _ => continue,
+        };
+        diagnostics.push(Diagnostic {
+            range: doc.range_from_span(&occurrence.span),
+            severity: Some(severity),
+            code: Some(NumberOrString::String(code.to_string())),
+            source: Some("achronyme".to_string()),
+            message,
+            ..Default::default()
+        });
     }
-
-    (line, character, message)
+    diagnostics
 }
 
 #[cfg(test)]
@@ -66,10 +138,96 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_error_message() {
-        let error = "Parse error:  --> 2:10\n  |\n2 | let x = ;\n  |          ^---";
-        let (line, col, _msg) = parse_error_message(error);
-        assert_eq!(line, 1); // 0-based
-        assert_eq!(col, 9); // 0-based
+    fn test_reports_every_syntax_error() {
+        let doc = Document::new("let a = 1\nlet b = * 2\nlet c = a\nlet d = (".to_string());
+        let diagnostics = compute_diagnostics(&doc);
+        let starts: Vec<_> = diagnostics.iter().map(|d| d.range.start).collect();
+        assert_eq!(starts, [Position::new(1, 8), Position::new(3, 9)]);
+        assert!(diagnostics
+            .iter()
+            .all(|d| d.message.starts_with("Syntax error: expected")));
+    }
+
+    #[test]
+    fn test_lint_diagnostics() {
+        let doc = Document::new(
+            "let a = 1\nmut b = 2\na = sqr(b)\nb = sqrt(PI) + i\nlet f = (x) => x + y\nf(if(a, b, 0))"
+                .to_string(),
+        );
+        let found: Vec<_> = lint_diagnostics(&doc)
+            .into_iter()
+            .map(|d| (d.range.start, d.message))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    Position::new(2, 0),
+                    "Cannot assign to immutable variable 'a'".to_string()
+                ),
+                (Position::new(2, 4), "Cannot find name 'sqr'".to_string()),
+                (Position::new(4, 19), "Cannot find name 'y'".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_diagnostics() {
+        let doc = Document::new(
+            "let n: Number = \"one\"\nlet half = (x: Number) => x / 2\nhalf(n, 2)\nlet m: Number | null = null\nm + 1\nlet b = * 2"
+                .to_string(),
+        );
+        let found: Vec<_> = type_diagnostics(&doc)
+            .into_iter()
+            .map(|d| (d.range.start, d.message))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    Position::new(0, 16),
+                    "Type mismatch: expected Number, found String".to_string()
+                ),
+                (
+                    Position::new(2, 0),
+                    "'half' expects 1 argument, found 2".to_string()
+                ),
+                (
+                    Position::new(4, 0),
+                    "Operator '+' cannot be applied to a value that may be null (Number | null)"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_diagnostics() {
+        let project = crate::workspace::tests::Project::new(
+            "diagnostics",
+            &[
+                ("util.soc", "let a = 1\nlet b = 2\nexport { a, b as c }"),
+                (
+                    "main.soc",
+                    "import { a, b } from \"./util\"\nimport { x } from \"./missing\"",
+                ),
+            ],
+        );
+        let (uri, doc) = project.open("main.soc");
+        let diagnostics = import_diagnostics(&project.workspace, &doc, &uri);
+        let found: Vec<_> = diagnostics
+            .iter()
+            .map(|d| (d.range.start, d.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    Position::new(0, 12),
+                    "Module './util' has no export named 'b'"
+                ),
+                (Position::new(1, 18), "Cannot find module './missing'"),
+            ]
+        );
     }
 }
@@ -337,7 +337,7 @@ pub fn normalize_operators(line: &str) -> String {
             }
 
             // Binary arithmetic operators
-            '+' | '*' | '/' | '%' | '^' => {
+            '+' | '*' | '/' | '%' | '^' | '@' => {
                 // Check if this could be unary (at start or after operator/open paren)
                 let is_unary = result.is_empty()
                     || result.ends_with('(')
@@ -758,6 +758,7 @@ mod tests {
         assert_eq!(normalize_operators("a+b-c"), "a + b - c");
         assert_eq!(normalize_operators("x*y/z"), "x * y / z");
         assert_eq!(normalize_operators("a^2"), "a ^ 2");
+        assert_eq!(normalize_operators("A@B"), "A @ B");
     }
 
     #[test]
@@ -0,0 +1,44 @@
+use achronyme_lsp_core::Access;
+use tower_lsp::lsp_types::*;
+
+use crate::document::Document;
+
+/// Highlight every occurrence of the symbol at the given position,
+/// marking declarations and assignments as writes
+pub fn document_highlight(doc: &Document, position: Position) -> Option<Vec<DocumentHighlight>> {
+    let index = doc.semantic()?;
+    let offset = doc.offset_from_position(position.line, position.character);
+    let symbol = index.symbol_at(offset)?;
+
+    let highlights = index
+        .references(symbol)
+        .map(|o| DocumentHighlight {
+            range: doc.range_from_span(&o.span),
+            kind: Some(match o.access {
+                Access::Declaration | Access::Write => DocumentHighlightKind::WRITE,
+                Access::Read | Access::Export => DocumentHighlightKind::READ,
+            }),
+        })
+        .collect();
+    Some(highlights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_reads_and_writes() {
+        let doc = Document::new("mut n = 0\nn = n + 1".to_string());
+        let highlights = document_highlight(&doc, Position::new(1, 4)).unwrap();
+        let kinds: Vec<_> = highlights.iter().map(|h| h.kind.unwrap()).collect();
+        assert_eq!(
+            kinds,
+            [
+                DocumentHighlightKind::WRITE,
+                DocumentHighlightKind::WRITE,
+                DocumentHighlightKind::READ
+            ]
+        );
+    }
+}
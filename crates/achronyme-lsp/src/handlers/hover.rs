@@ -1,13 +1,19 @@
+use achronyme_lsp_core::get_signature;
 use tower_lsp::lsp_types::*;
 
 use crate::document::Document;
+use crate::workspace::Workspace;
 
 /// Get hover information for a position in the document
-pub fn get_hover(doc: &Document, position: Position) -> Option<Hover> {
-    let word = doc.word_at_position(position.line, position.character)?;
-
-    // Check if it's a builtin function
-    if let Some(info) = get_builtin_info(&word) {
+pub fn get_hover(
+    workspace: &Workspace,
+    doc: &Document,
+    uri: &Url,
+    position: Position,
+) -> Option<Hover> {
+    // Imported names show where the exporting module defines them
+    let offset = doc.offset_from_position(position.line, position.character);
+    if let Some(info) = get_import_info(workspace, doc, uri, offset) {
         return Some(Hover {
             contents: HoverContents::Markup(MarkupContent {
                 kind: MarkupKind::Markdown,
@@ -17,6 +23,8 @@ pub fn get_hover(doc: &Document, position: Position) -> Option<Hover> {
         });
     }
 
+    let word = doc.word_at_position(position.line, position.character)?;
+
     // Check if it's a keyword
     if let Some(info) = get_keyword_info(&word) {
         return Some(Hover {
@@ -28,69 +36,55 @@ pub fn get_hover(doc: &Document, position: Position) -> Option<Hover> {
         });
     }
 
+    // Check if it's a builtin function (`import` is also registered, but
+    // reads as the keyword)
+    if let Some(sig) = get_signature(&word) {
+        return Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: sig.markdown.clone(),
+            }),
+            range: None,
+        });
+    }
+
     // Check if it's a variable in the AST
-    if let Some(ast) = doc.ast() {
-        if let Some(info) = find_variable_info(ast, &word) {
-            return Some(Hover {
-                contents: HoverContents::Markup(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: info,
-                }),
-                range: None,
-            });
-        }
+    if let Some(info) = find_variable_info(doc.ast(), &word) {
+        return Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: info,
+            }),
+            range: None,
+        });
     }
 
     None
 }
 
-/// Get information about builtin functions
-fn get_builtin_info(name: &str) -> Option<String> {
-    match name {
-        // Math functions
-        "sin" => Some("```achronyme\nsin(x: Number) -> Number\n```\nReturns the sine of x (x in radians)".to_string()),
-        "cos" => Some("```achronyme\ncos(x: Number) -> Number\n```\nReturns the cosine of x (x in radians)".to_string()),
-        "tan" => Some("```achronyme\ntan(x: Number) -> Number\n```\nReturns the tangent of x (x in radians)".to_string()),
-        "asin" => Some("```achronyme\nasin(x: Number) -> Number\n```\nReturns the arc sine of x".to_string()),
-        "acos" => Some("```achronyme\nacos(x: Number) -> Number\n```\nReturns the arc cosine of x".to_string()),
-        "atan" => Some("```achronyme\natan(x: Number) -> Number\n```\nReturns the arc tangent of x".to_string()),
-        "atan2" => Some("```achronyme\natan2(y: Number, x: Number) -> Number\n```\nReturns the arc tangent of y/x".to_string()),
-        "sinh" => Some("```achronyme\nsinh(x: Number) -> Number\n```\nReturns the hyperbolic sine of x".to_string()),
-        "cosh" => Some("```achronyme\ncosh(x: Number) -> Number\n```\nReturns the hyperbolic cosine of x".to_string()),
-        "tanh" => Some("```achronyme\ntanh(x: Number) -> Number\n```\nReturns the hyperbolic tangent of x".to_string()),
-        "sqrt" => Some("```achronyme\nsqrt(x: Number) -> Number\n```\nReturns the square root of x".to_string()),
-        "cbrt" => Some("```achronyme\ncbrt(x: Number) -> Number\n```\nReturns the cube root of x".to_string()),
-        "exp" => Some("```achronyme\nexp(x: Number) -> Number\n```\nReturns e raised to the power x".to_string()),
-        "ln" => Some("```achronyme\nln(x: Number) -> Number\n```\nReturns the natural logarithm of x".to_string()),
-        "log" => Some("```achronyme\nlog(x: Number, base: Number) -> Number\n```\nReturns the logarithm of x with given base".to_string()),
-        "log10" => Some("```achronyme\nlog10(x: Number) -> Number\n```\nReturns the base-10 logarithm of x".to_string()),
-        "log2" => Some("```achronyme\nlog2(x: Number) -> Number\n```\nReturns the base-2 logarithm of x".to_string()),
-        "abs" => Some("```achronyme\nabs(x: Number) -> Number\n```\nReturns the absolute value of x".to_string()),
-        "floor" => Some("```achronyme\nfloor(x: Number) -> Number\n```\nReturns the largest integer less than or equal to x".to_string()),
-        "ceil" => Some("```achronyme\nceil(x: Number) -> Number\n```\nReturns the smallest integer greater than or equal to x".to_string()),
-        "round" => Some("```achronyme\nround(x: Number) -> Number\n```\nRounds x to the nearest integer".to_string()),
-        "min" => Some("```achronyme\nmin(a: Number, b: Number) -> Number\n```\nReturns the minimum of two numbers".to_string()),
-        "max" => Some("```achronyme\nmax(a: Number, b: Number) -> Number\n```\nReturns the maximum of two numbers".to_string()),
-
-        // Array functions
-        "len" => Some("```achronyme\nlen(arr: Array) -> Number\n```\nReturns the length of an array".to_string()),
-        "push" => Some("```achronyme\npush(arr: Array, value: Any) -> Array\n```\nReturns a new array with value appended".to_string()),
-        "pop" => Some("```achronyme\npop(arr: Array) -> Array\n```\nReturns a new array with the last element removed".to_string()),
-        "head" => Some("```achronyme\nhead(arr: Array) -> Any\n```\nReturns the first element of the array".to_string()),
-        "tail" => Some("```achronyme\ntail(arr: Array) -> Array\n```\nReturns all elements except the first".to_string()),
-        "map" => Some("```achronyme\nmap(arr: Array, fn: Function) -> Array\n```\nApplies function to each element".to_string()),
-        "filter" => Some("```achronyme\nfilter(arr: Array, predicate: Function) -> Array\n```\nFilters array by predicate".to_string()),
-        "reduce" => Some("```achronyme\nreduce(arr: Array, initial: Any, fn: Function) -> Any\n```\nReduces array to single value".to_string()),
-        "range" => Some("```achronyme\nrange(start: Number, end: Number) -> Array\n```\nCreates an array of numbers from start to end (exclusive)".to_string()),
-
-        // Type functions
-        "type" => Some("```achronyme\ntype(value: Any) -> String\n```\nReturns the type of the value as a string".to_string()),
-
-        // I/O functions
-        "print" => Some("```achronyme\nprint(value: Any) -> Null\n```\nPrints value to stdout".to_string()),
-
-        _ => None,
-    }
+/// Describe an imported name by the line that defines it in its module
+fn get_import_info(
+    workspace: &Workspace,
+    doc: &Document,
+    uri: &Url,
+    offset: usize,
+) -> Option<String> {
+    let found = workspace.imported_definition(uri, doc, offset)?;
+    let module = &found.module.document;
+    let symbol = module.semantic()?.symbol(found.symbol);
+    let (line, _) = module.position_from_offset(symbol.span.start);
+    let definition = module.lines().get(line as usize)?.trim();
+    let file = found
+        .module
+        .uri
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .unwrap_or_default()
+        .to_string();
+    Some(format!(
+        "```achronyme\n{}\n```\nDefined in `{}`",
+        definition, file
+    ))
 }
 
 /// Get information about keywords
@@ -168,3 +162,49 @@ fn find_variable_info(ast: &[achronyme_parser::ast::AstNode], name: &str) -> Opt
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::tests::Project;
+
+    #[test]
+    fn test_hover_shows_imported_definition() {
+        let project = Project::new(
+            "hover",
+            &[
+                (
+                    "util.soc",
+                    "let square = (x: Number): Number => x^2\nexport { square }",
+                ),
+                ("main.soc", "import { square } from \"./util\"\nsquare(4)"),
+            ],
+        );
+        let (uri, doc) = project.open("main.soc");
+        let hover = get_hover(&project.workspace, &doc, &uri, Position::new(1, 2)).unwrap();
+        let HoverContents::Markup(content) = hover.contents else {
+            panic!("expected markup");
+        };
+        assert_eq!(
+            content.value,
+            "```achronyme\nlet square = (x: Number): Number => x^2\n```\nDefined in `util.soc`"
+        );
+    }
+
+    #[test]
+    fn test_hover_shows_builtin_docs() {
+        let project = Project::new(
+            "hover_builtin",
+            &[("main.soc", "filter(x => x > 1, [1, 2])")],
+        );
+        let (uri, doc) = project.open("main.soc");
+        let hover = get_hover(&project.workspace, &doc, &uri, Position::new(0, 2)).unwrap();
+        let HoverContents::Markup(content) = hover.contents else {
+            panic!("expected markup");
+        };
+        assert!(content.value.starts_with(
+            "```achronyme\nfilter(predicate: Function, collection: Vector | Tensor<Number> | String | Generator | Iterator) -> Vector\n```"
+        ));
+        assert!(content.value.contains("**Examples**"));
+    }
+}
@@ -0,0 +1,163 @@
+use achronyme_lsp_core::typecheck::is_informative;
+use achronyme_lsp_core::{get_signature, Call, SemanticIndex};
+use tower_lsp::lsp_types::*;
+
+use crate::document::Document;
+
+/// Inferred types after unannotated `let`/`mut` names and parameter names
+/// before call arguments, within `range`
+pub fn inlay_hints(doc: &Document, range: Range) -> Vec<InlayHint> {
+    let Some(index) = doc.semantic() else {
+        return Vec::new();
+    };
+    let start = doc.offset_from_position(range.start.line, range.start.character);
+    let end = doc.offset_from_position(range.end.line, range.end.character);
+    let visible = |offset: usize| (start..=end).contains(&offset);
+
+    let mut hints = Vec::new();
+    for (id, symbol) in index.symbols().iter().enumerate() {
+        let inferred = doc.types().and_then(|types| types.inferred_type(id));
+        let Some(ty) = inferred.filter(|ty| is_informative(ty)) else {
+            continue;
+        };
+        if visible(symbol.span.end) {
+            hints.push(hint(
+                doc,
+                symbol.span.end,
+                format!(": {}", ty),
+                InlayHintKind::TYPE,
+            ));
+        }
+    }
+
+    for call in index.calls() {
+        if !visible(call.callee.start) {
+            continue;
+        }
+        let parameters = parameter_names(index, call);
+        // Naming the only parameter adds nothing
+        if parameters.len() < 2 {
+            continue;
+        }
+        for (argument, (name, variadic)) in call.arguments.iter().zip(parameters) {
+            // `f(x)` for a parameter named x says it already
+            if doc.text()[argument.clone()] != name {
+                let label = if variadic {
+                    format!("...{}:", name)
+                } else {
+                    format!("{}:", name)
+                };
+                hints.push(hint(doc, argument.start, label, InlayHintKind::PARAMETER));
+            }
+            // The rest of the arguments all belong to the variadic parameter
+            if variadic {
+                break;
+            }
+        }
+    }
+    hints.sort_by_key(|hint| hint.position);
+    hints
+}
+
+/// Parameter names of the called function, with whether each is variadic
+fn parameter_names(index: &SemanticIndex, call: &Call) -> Vec<(String, bool)> {
+    let Some(callee) = index.occurrence_at(call.callee.start) else {
+        return Vec::new();
+    };
+    match callee.symbol {
+        Some(id) => index
+            .symbol(id)
+            .parameters
+            .iter()
+            .flatten()
+            .map(|name| (name.clone(), false))
+            .collect(),
+        // Builtin labels read `x: T`, `n?: T` or `...values: T`
+        None => get_signature(&callee.name)
+            .map(|sig| {
+                sig.parameters
+                    .iter()
+                    .map(|param| {
+                        let name = param.label.split([':', '?']).next().unwrap_or_default();
+                        match name.strip_prefix("...") {
+                            Some(name) => (name.to_string(), true),
+                            None => (name.to_string(), false),
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+fn hint(doc: &Document, offset: usize, label: String, kind: InlayHintKind) -> InlayHint {
+    let (line, character) = doc.position_from_offset(offset);
+    InlayHint {
+        position: Position::new(line, character),
+        label: InlayHintLabel::String(label),
+        kind: Some(kind),
+        text_edits: None,
+        tooltip: None,
+        padding_left: None,
+        padding_right: Some(kind == InlayHintKind::PARAMETER),
+        data: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(source: &str) -> Vec<(Position, String)> {
+        let doc = Document::new(source.to_string());
+        let everything = Range::new(Position::new(0, 0), Position::new(u32::MAX, 0));
+        inlay_hints(&doc, everything)
+            .into_iter()
+            .map(|hint| {
+                let InlayHintLabel::String(label) = hint.label else {
+                    panic!("expected a string label");
+                };
+                (hint.position, label)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_type_hints() {
+        assert_eq!(
+            labels("let n = 2\nmut s: String = 'a'\nlet f = (x: Number) => x * n\nlet y = f(n)"),
+            [
+                (Position::new(0, 5), ": Number".to_string()),
+                (Position::new(2, 5), ": (Number): Number".to_string()),
+                (Position::new(3, 5), ": Number".to_string()),
+            ]
+        );
+        // Unknown types get no hint
+        assert_eq!(labels("let r = foo(1)\nlet g = x => x"), []);
+    }
+
+    #[test]
+    fn test_parameter_hints() {
+        let source = "mut total: Any = 0\n\
+                      let clamp = (value, low, high) => max(low, min(value, high))\n\
+                      total = clamp(12, 0, 10)\n\
+                      total = pow(2, 8) + sqrt(total) + pipe(1, clamp)";
+        let found: Vec<_> = labels(source)
+            .into_iter()
+            .filter(|(_, label)| label.ends_with(':'))
+            .collect();
+        let position = |line, character| Position::new(line, character);
+        assert_eq!(
+            found,
+            [
+                (position(2, 14), "value:".to_string()),
+                (position(2, 18), "low:".to_string()),
+                (position(2, 21), "high:".to_string()),
+                (position(3, 12), "base:".to_string()),
+                (position(3, 15), "exponent:".to_string()),
+                (position(3, 39), "value:".to_string()),
+                (position(3, 42), "...functions:".to_string()),
+            ]
+        );
+    }
+}
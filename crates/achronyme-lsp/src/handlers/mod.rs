@@ -1,8 +1,13 @@
+pub mod code_actions;
 pub mod completion;
 pub mod definition;
 pub mod diagnostics;
 pub mod formatting;
+pub mod highlight;
 pub mod hover;
+pub mod inlay_hints;
 pub mod references;
+pub mod rename;
+pub mod semantic_tokens;
 pub mod signature_help;
 pub mod symbols;
@@ -1,53 +1,27 @@
+use achronyme_lsp_core::Access;
 use tower_lsp::lsp_types::*;
 
 use crate::document::Document;
 
-/// Find all references to a symbol at the given position
-pub fn find_references(doc: &Document, position: Position, uri: Url) -> Option<Vec<Location>> {
-    let word = doc.word_at_position(position.line, position.character)?;
+/// Find all references to the symbol at the given position
+pub fn find_references(
+    doc: &Document,
+    position: Position,
+    uri: Url,
+    include_declaration: bool,
+) -> Option<Vec<Location>> {
+    let index = doc.semantic()?;
+    let offset = doc.offset_from_position(position.line, position.character);
+    let symbol = index.symbol_at(offset)?;
 
-    let mut locations = Vec::new();
-
-    // Search for all occurrences of the word in the document
-    for (line_idx, line) in doc.lines().iter().enumerate() {
-        let mut search_start = 0;
-        while let Some(pos) = line[search_start..].find(&word) {
-            let actual_pos = search_start + pos;
-
-            // Check if it's a whole word match
-            let before_ok = actual_pos == 0
-                || !line
-                    .chars()
-                    .nth(actual_pos - 1)
-                    .map(is_word_char)
-                    .unwrap_or(false);
-
-            let after_ok = actual_pos + word.len() >= line.len()
-                || !line
-                    .chars()
-                    .nth(actual_pos + word.len())
-                    .map(is_word_char)
-                    .unwrap_or(false);
-
-            if before_ok && after_ok {
-                locations.push(Location {
-                    uri: uri.clone(),
-                    range: Range {
-                        start: Position {
-                            line: line_idx as u32,
-                            character: actual_pos as u32,
-                        },
-                        end: Position {
-                            line: line_idx as u32,
-                            character: (actual_pos + word.len()) as u32,
-                        },
-                    },
-                });
-            }
-
-            search_start = actual_pos + word.len();
-        }
-    }
+    let locations: Vec<Location> = index
+        .references(symbol)
+        .filter(|o| include_declaration || o.access != Access::Declaration)
+        .map(|o| Location {
+            uri: uri.clone(),
+            range: doc.range_from_span(&o.span),
+        })
+        .collect();
 
     if locations.is_empty() {
         None
@@ -56,28 +30,43 @@ pub fn find_references(doc: &Document, position: Position, uri: Url) -> Option<V
     }
 }
 
-fn is_word_char(c: char) -> bool {
-    c.is_alphanumeric() || c == '_'
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn references_at(source: &str, position: Position, include_declaration: bool) -> Vec<Range> {
+        let doc = Document::new(source.to_string());
+        let uri = Url::parse("file:///test.soc").unwrap();
+        find_references(&doc, position, uri, include_declaration)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|l| l.range)
+            .collect()
+    }
+
     #[test]
     fn test_find_references() {
-        let doc = Document::new("let x = 1\nlet y = x + 2\nlet z = x * y".to_string());
-        let uri = Url::parse("file:///test.acr").unwrap();
-        let refs = find_references(
-            &doc,
-            Position {
-                line: 0,
-                character: 4,
-            },
-            uri,
+        let refs = references_at(
+            "let x = 1\nlet y = x + 2\nlet z = x * y",
+            Position::new(0, 4),
+            true,
         );
-        assert!(refs.is_some());
-        let refs = refs.unwrap();
         assert_eq!(refs.len(), 3); // x appears 3 times
+        let refs = references_at(
+            "let x = 1\nlet y = x + 2\nlet z = x * y",
+            Position::new(0, 4),
+            false,
+        );
+        assert_eq!(refs.len(), 2);
+    }
+
+    #[test]
+    fn test_references_skip_strings_comments_and_shadowed_names() {
+        let source = "let x = 1\n// x\nlet s = \"x\"\nlet f = (x) => x\nx";
+        let refs = references_at(source, Position::new(4, 0), true);
+        assert_eq!(
+            refs.iter().map(|r| r.start).collect::<Vec<_>>(),
+            [Position::new(0, 4), Position::new(4, 0)]
+        );
     }
 }
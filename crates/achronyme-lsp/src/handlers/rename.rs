@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use achronyme_lsp_core::semantic::{is_valid_identifier, SemanticIndex};
+use achronyme_lsp_core::{Access, SymbolId, SymbolKind};
+use tower_lsp::lsp_types::*;
+
+use crate::document::Document;
+
+/// Check that the position names a renameable (local) symbol and return its range
+pub fn prepare_rename(doc: &Document, position: Position) -> Option<PrepareRenameResponse> {
+    let index = doc.semantic()?;
+    let offset = doc.offset_from_position(position.line, position.character);
+    let occurrence = index.occurrence_at(offset)?;
+    occurrence.symbol?;
+
+    Some(PrepareRenameResponse::RangeWithPlaceholder {
+        range: doc.range_from_span(&occurrence.span),
+        placeholder: occurrence.name.clone(),
+    })
+}
+
+/// Rename the symbol at the given position and every reference to it.
+/// Imported and exported names keep their external name through an alias.
+pub fn rename(
+    doc: &Document,
+    position: Position,
+    new_name: &str,
+    uri: Url,
+) -> Result<Option<WorkspaceEdit>, String> {
+    if !is_valid_identifier(new_name) {
+        return Err(format!("'{}' is not a valid identifier", new_name));
+    }
+    let Some(index) = doc.semantic() else {
+        return Ok(None);
+    };
+    let offset = doc.offset_from_position(position.line, position.character);
+    let Some(id) = index.symbol_at(offset) else {
+        return Ok(None);
+    };
+    let symbol = index.symbol(id);
+
+    // (replaced span, new text, where the new name starts in the new text)
+    let replacements: Vec<(Range<usize>, String, usize)> = index
+        .references(id)
+        .map(|o| match (o.access, symbol.kind) {
+            (Access::Declaration, SymbolKind::Import { aliased: false }) => (
+                o.span.clone(),
+                format!("{} as {}", symbol.name, new_name),
+                symbol.name.len() + 4,
+            ),
+            (Access::Export, _) => (
+                o.span.clone(),
+                format!("{} as {}", new_name, symbol.name),
+                0,
+            ),
+            _ => (o.span.clone(), new_name.to_string(), 0),
+        })
+        .collect();
+    if !keeps_bindings(doc.text(), index, &replacements) {
+        return Err(format!(
+            "renaming '{}' to '{}' would change which binding a name refers to",
+            symbol.name, new_name
+        ));
+    }
+
+    let edits = replacements
+        .into_iter()
+        .map(|(span, new_text, _)| TextEdit {
+            range: doc.range_from_span(&span),
+            new_text,
+        })
+        .collect();
+
+    Ok(Some(WorkspaceEdit {
+        changes: Some(HashMap::from([(uri, edits)])),
+        ..Default::default()
+    }))
+}
+
+/// Apply the replacements, resolve the result again and check that every
+/// identifier still refers to the same symbol: the new name must neither be
+/// captured by a closer binding nor shadow one its references used to see.
+/// Symbol ids follow source order, so they match between the two indexes.
+fn keeps_bindings(
+    text: &str,
+    index: &SemanticIndex,
+    replacements: &[(Range<usize>, String, usize)],
+) -> bool {
+    let mut sorted: Vec<_> = replacements.iter().collect();
+    sorted.sort_by_key(|(span, _, _)| span.start);
+
+    let mut renamed = String::with_capacity(text.len());
+    let mut last = 0;
+    // Old start of each replaced span -> start of the new name in `renamed`
+    let mut moved = HashMap::new();
+    for (span, new_text, name_at) in &sorted {
+        renamed.push_str(&text[last..span.start]);
+        moved.insert(span.start, renamed.len() + name_at);
+        renamed.push_str(new_text);
+        last = span.end;
+    }
+    renamed.push_str(&text[last..]);
+    let Ok(after) = SemanticIndex::build(&renamed) else {
+        return false;
+    };
+
+    let resolved: HashMap<usize, Option<SymbolId>> = after
+        .occurrences()
+        .iter()
+        .map(|o| (o.span.start, o.symbol))
+        .collect();
+    index.occurrences().iter().all(|o| {
+        let start = moved.get(&o.span.start).copied().unwrap_or_else(|| {
+            // Shift by the growth of every replacement before this one
+            let growth: isize = sorted
+                .iter()
+                .take_while(|(span, _, _)| span.end <= o.span.start)
+                .map(|(span, new_text, _)| new_text.len() as isize - span.len() as isize)
+                .sum();
+            (o.span.start as isize + growth) as usize
+        });
+        resolved.get(&start) == Some(&o.symbol)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(source: &str, position: Position, new_name: &str) -> Result<String, String> {
+        let doc = Document::new(source.to_string());
+        let uri = Url::parse("file:///test.soc").unwrap();
+        let edit = rename(&doc, position, new_name, uri.clone())?.ok_or("nothing to rename")?;
+        let mut edits = edit.changes.unwrap().remove(&uri).unwrap();
+        edits.sort_by_key(|e| std::cmp::Reverse(e.range.start));
+        let mut text = source.to_string();
+        for e in edits {
+            let start = doc.offset_from_position(e.range.start.line, e.range.start.character);
+            let end = doc.offset_from_position(e.range.end.line, e.range.end.character);
+            text.replace_range(start..end, &e.new_text);
+        }
+        Ok(text)
+    }
+
+    #[test]
+    fn test_rename_respects_scopes() {
+        let source = "let x = 1\nlet f = (x) => x * 2\nlet s = \"x\"\nx + f(x)";
+        assert_eq!(
+            apply(source, Position::new(0, 4), "count").unwrap(),
+            "let count = 1\nlet f = (x) => x * 2\nlet s = \"x\"\ncount + f(count)"
+        );
+        assert_eq!(
+            apply(source, Position::new(1, 15), "v").unwrap(),
+            "let x = 1\nlet f = (v) => v * 2\nlet s = \"x\"\nx + f(x)"
+        );
+    }
+
+    #[test]
+    fn test_rename_keeps_import_and_export_names() {
+        let source = "import { mean } from \"stats\"\nlet m = mean([1])\nexport { m }";
+        assert_eq!(
+            apply(source, Position::new(1, 9), "avg").unwrap(),
+            "import { mean as avg } from \"stats\"\nlet m = avg([1])\nexport { m }"
+        );
+        assert_eq!(
+            apply(source, Position::new(1, 4), "total").unwrap(),
+            "import { mean } from \"stats\"\nlet total = mean([1])\nexport { total as m }"
+        );
+    }
+
+    #[test]
+    fn test_rename_rejects_captured_names() {
+        // `y` would capture the reference to `x` inside `f`
+        let source = "let x = 1\nlet f = (y) => x + y";
+        let err = apply(source, Position::new(0, 4), "y").unwrap_err();
+        assert!(err.contains("would change which binding"), "{}", err);
+        // ... and renaming the parameter to `x` would hide the outer `x`
+        assert!(apply(source, Position::new(1, 9), "x").is_err());
+        // Shadowing a builtin that is called in scope
+        assert!(apply("let x = 1\nsin(x)", Position::new(0, 4), "sin").is_err());
+        assert_eq!(
+            apply(source, Position::new(0, 4), "z").unwrap(),
+            "let z = 1\nlet f = (y) => z + y"
+        );
+    }
+
+    #[test]
+    fn test_rename_rejects_builtins_and_bad_names() {
+        let doc = Document::new("let x = sin(1)".to_string());
+        assert!(prepare_rename(&doc, Position::new(0, 9)).is_none());
+        assert!(prepare_rename(&doc, Position::new(0, 4)).is_some());
+        let err = apply("let x = 1", Position::new(0, 4), "let").unwrap_err();
+        assert!(err.contains("not a valid identifier"), "{}", err);
+    }
+}
@@ -0,0 +1,219 @@
+use std::ops::Range as Span;
+
+use achronyme_lsp_core::{get_signature, is_constant, Access, Namespace, SymbolKind};
+use tower_lsp::lsp_types::*;
+
+use crate::document::Document;
+
+/// Token types, in legend order
+const TOKEN_TYPES: &[SemanticTokenType] = &[
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::PARAMETER,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::ENUM_MEMBER,
+];
+
+const FUNCTION: u32 = 0;
+const VARIABLE: u32 = 1;
+const PARAMETER: u32 = 2;
+const TYPE: u32 = 3;
+const PROPERTY: u32 = 4;
+const ENUM_MEMBER: u32 = 5;
+
+/// Bits of the modifier set, in legend order
+const DECLARATION: u32 = 1 << 0;
+const READONLY: u32 = 1 << 1;
+const DEFAULT_LIBRARY: u32 = 1 << 2;
+const MUTABLE: u32 = 1 << 3;
+
+/// The token types and modifiers `semantic_tokens` uses
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: vec![
+            SemanticTokenModifier::DECLARATION,
+            SemanticTokenModifier::READONLY,
+            SemanticTokenModifier::DEFAULT_LIBRARY,
+            // Not a standard modifier; `let` bindings are `readonly` instead
+            SemanticTokenModifier::new("mutable"),
+        ],
+    }
+}
+
+/// Classify every name in the document
+///
+/// Builtins are default-library functions, `let` bindings readonly variables
+/// and `mut` bindings mutable ones; bindings of lambdas are functions.
+/// Parameters, type names, data type variants and record fields get their
+/// own types. Keywords and literals are left to the editor's grammar.
+pub fn semantic_tokens(doc: &Document) -> Vec<SemanticToken> {
+    let Some(index) = doc.semantic() else {
+        return Vec::new();
+    };
+    let mut tokens: Vec<(Span<usize>, u32, u32)> = Vec::new();
+    for occurrence in index.occurrences() {
+        let declaration = if occurrence.access == Access::Declaration {
+            DECLARATION
+        } else {
+            0
+        };
+        let (token_type, modifiers) = match occurrence.symbol {
+            _ if occurrence.namespace == Namespace::Type => (TYPE, declaration),
+            Some(id) => {
+                let symbol = index.symbol(id);
+                let token_type = match symbol.kind {
+                    SymbolKind::Parameter => PARAMETER,
+                    SymbolKind::Variant => ENUM_MEMBER,
+                    _ if symbol.parameters.is_some() => FUNCTION,
+                    _ => VARIABLE,
+                };
+                let mutability = match symbol.kind {
+                    SymbolKind::Mut => MUTABLE,
+                    SymbolKind::Parameter => 0,
+                    _ => READONLY,
+                };
+                (token_type, declaration | mutability)
+            }
+            // `PI` and `E` are also registered as functions
+            None if is_constant(&occurrence.name) => (VARIABLE, READONLY | DEFAULT_LIBRARY),
+            None if get_signature(&occurrence.name).is_some() => (FUNCTION, DEFAULT_LIBRARY),
+            // Undefined names stay uncoloured
+            None => continue,
+        };
+        tokens.push((occurrence.span.clone(), token_type, modifiers));
+    }
+    tokens.extend(
+        index
+            .fields()
+            .iter()
+            .map(|span| (span.clone(), PROPERTY, 0)),
+    );
+    tokens.extend(
+        index
+            .type_names()
+            .iter()
+            .map(|span| (span.clone(), TYPE, DEFAULT_LIBRARY)),
+    );
+    tokens.sort_by_key(|(span, _, _)| span.start);
+
+    // Each token is relative to the previous one
+    let mut encoded = Vec::with_capacity(tokens.len());
+    let mut previous = Position::new(0, 0);
+    for (span, token_type, modifiers) in tokens {
+        let range = doc.range_from_span(&span);
+        let delta_line = range.start.line - previous.line;
+        let delta_start = if delta_line == 0 {
+            range.start.character - previous.character
+        } else {
+            range.start.character
+        };
+        encoded.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length: range.end.character - range.start.character,
+            token_type,
+            token_modifiers_bitset: modifiers,
+        });
+        previous = range.start;
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decode tokens back to (text, type, modifiers)
+    fn classify(source: &str) -> Vec<(String, SemanticTokenType, u32)> {
+        let doc = Document::new(source.to_string());
+        let mut position = Position::new(0, 0);
+        semantic_tokens(&doc)
+            .into_iter()
+            .map(|token| {
+                if token.delta_line > 0 {
+                    position = Position::new(position.line + token.delta_line, token.delta_start);
+                } else {
+                    position.character += token.delta_start;
+                }
+                let start = doc.offset_from_position(position.line, position.character);
+                let end =
+                    doc.offset_from_position(position.line, position.character + token.length);
+                (
+                    source[start..end].to_string(),
+                    TOKEN_TYPES[token.token_type as usize].clone(),
+                    token.token_modifiers_bitset,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_classifies_names() {
+        let source = "type P = { x: Number }\n\
+                      let p: P = { x: 2 }\n\
+                      mut n = p.x\n\
+                      let sq = (v: Vector) => map(x => x^2, v)\n\
+                      n = sq([PI])";
+        let found = classify(source);
+        let t =
+            |text: &str, ty: SemanticTokenType, modifiers: u32| (text.to_string(), ty, modifiers);
+        use SemanticTokenType as T;
+        assert_eq!(
+            found,
+            [
+                t("P", T::TYPE, DECLARATION),
+                t("x", T::PROPERTY, 0),
+                t("Number", T::TYPE, DEFAULT_LIBRARY),
+                t("p", T::VARIABLE, DECLARATION | READONLY),
+                t("P", T::TYPE, 0),
+                t("x", T::PROPERTY, 0),
+                t("n", T::VARIABLE, DECLARATION | MUTABLE),
+                t("p", T::VARIABLE, READONLY),
+                t("x", T::PROPERTY, 0),
+                t("sq", T::FUNCTION, DECLARATION | READONLY),
+                t("v", T::PARAMETER, DECLARATION),
+                t("Vector", T::TYPE, DEFAULT_LIBRARY),
+                t("map", T::FUNCTION, DEFAULT_LIBRARY),
+                t("x", T::PARAMETER, DECLARATION),
+                t("x", T::PARAMETER, 0),
+                t("v", T::PARAMETER, 0),
+                t("n", T::VARIABLE, MUTABLE),
+                t("sq", T::FUNCTION, READONLY),
+                t("PI", T::VARIABLE, READONLY | DEFAULT_LIBRARY),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_classifies_variants() {
+        let found =
+            classify("type T = Leaf | Node { v: T }\nmatch Leaf { Node { v } => v, Leaf => 0 }");
+        let variants: Vec<_> = found
+            .into_iter()
+            .filter(|(_, ty, _)| *ty == SemanticTokenType::ENUM_MEMBER)
+            .map(|(text, _, modifiers)| (text, modifiers))
+            .collect();
+        assert_eq!(
+            variants,
+            [
+                ("Leaf".to_string(), DECLARATION | READONLY),
+                ("Node".to_string(), DECLARATION | READONLY),
+                ("Leaf".to_string(), READONLY),
+                ("Node".to_string(), READONLY),
+                ("Leaf".to_string(), READONLY),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokens_are_relative_across_lines() {
+        let doc = Document::new("let a = 1\n  let bb = a".to_string());
+        let tokens: Vec<_> = semantic_tokens(&doc)
+            .into_iter()
+            .map(|t| (t.delta_line, t.delta_start, t.length))
+            .collect();
+        assert_eq!(tokens, [(0, 4, 1), (1, 6, 2), (0, 5, 1)]);
+    }
+}
@@ -1,4 +1,4 @@
-use achronyme_lsp_core::get_signature;
+use achronyme_lsp_core::{get_signature, pipe_slot};
 use tower_lsp::lsp_types::*;
 
 use crate::document::Document;
@@ -9,18 +9,30 @@ pub fn get_signature_help(doc: &Document, position: Position) -> Option<Signatur
     let offset = doc.offset_from_position(position.line, position.character);
 
     // Find the function call we're inside
-    let (func_name, param_index) = find_function_call_context(text, offset)?;
+    let (func_name, mut param_index) = find_function_call_context(text, offset)?;
 
     // Get the function signature from the shared core crate
     let func_sig = get_signature(&func_name)?;
 
     // Build LSP SignatureHelp
+    let piped = piped_parameters(text, offset, func_sig.parameters.len());
+    if piped.inserted && param_index >= piped.parameters[0] {
+        param_index += 1;
+    }
     let parameters: Vec<ParameterInformation> = func_sig
         .parameters
         .iter()
-        .map(|p| ParameterInformation {
-            label: ParameterLabel::Simple(p.label.clone()),
-            documentation: Some(Documentation::String(p.documentation.clone())),
+        .enumerate()
+        .map(|(i, p)| {
+            let documentation = if piped.parameters.contains(&i) {
+                format!("Supplied by `|>`. {}", p.documentation)
+            } else {
+                p.documentation.clone()
+            };
+            ParameterInformation {
+                label: ParameterLabel::Simple(p.label.clone()),
+                documentation: Some(Documentation::String(documentation)),
+            }
         })
         .collect();
 
@@ -44,6 +56,90 @@ pub fn get_signature_help(doc: &Document, position: Position) -> Option<Signatur
 /// Find the function name and active parameter index at the cursor position
 fn find_function_call_context(text: &str, cursor_offset: usize) -> Option<(String, usize)> {
     let chars: Vec<char> = text.chars().collect();
+    let (func_start, open_paren) = find_call(&chars, cursor_offset)?;
+    let func_name: String = chars[func_start..]
+        .iter()
+        .take_while(|c| is_identifier_char(**c))
+        .collect();
+
+    // Count parameters (commas) to determine active parameter
+    let param_index = count_parameters_before_cursor(text, open_paren, cursor_offset);
+
+    Some((func_name, param_index))
+}
+
+/// The parameters that the value on the left of a `|>` fills in the call at
+/// the cursor
+#[derive(Debug, Default, PartialEq)]
+struct Piped {
+    parameters: Vec<usize>,
+    /// Whether the value is inserted among the written arguments rather than
+    /// replacing `_` ones, which shifts the parameters after it
+    inserted: bool,
+}
+
+/// Finds the parameters whose argument is `_`, or else the one
+/// `pipe_slot` gives, as the compiler does
+fn piped_parameters(text: &str, cursor_offset: usize, parameter_count: usize) -> Piped {
+    let chars: Vec<char> = text.chars().collect();
+    let Some((func_start, open_paren)) = find_call(&chars, cursor_offset) else {
+        return Piped::default();
+    };
+    let before: String = chars[..func_start].iter().collect();
+    if !before.trim_end().ends_with("|>") || parameter_count == 0 {
+        return Piped::default();
+    }
+    let func_name: String = chars[func_start..open_paren].iter().collect();
+
+    // Split the arguments at top-level commas, up to the closing parenthesis
+    let mut arguments = vec![String::new()];
+    let mut depth = 0;
+    let mut in_string = false;
+    for ch in &chars[open_paren + 1..] {
+        match *ch {
+            '"' => in_string = !in_string,
+            '(' | '[' | '{' if !in_string => depth += 1,
+            ')' | ']' | '}' if !in_string => {
+                if depth == 0 {
+                    break;
+                }
+                depth -= 1;
+            }
+            ',' if !in_string && depth == 0 => {
+                arguments.push(String::new());
+                continue;
+            }
+            _ => {}
+        }
+        if let Some(argument) = arguments.last_mut() {
+            argument.push(*ch);
+        }
+    }
+    let placeholders: Vec<usize> = arguments
+        .iter()
+        .enumerate()
+        .filter(|(_, argument)| argument.trim() == "_")
+        .map(|(i, _)| i)
+        .collect();
+    if !placeholders.is_empty() {
+        return Piped {
+            parameters: placeholders,
+            inserted: false,
+        };
+    }
+
+    // The call isn't finished yet, so count the arguments still to be
+    // written alongside the value
+    let written = arguments.len().max(parameter_count - 1);
+    Piped {
+        parameters: vec![pipe_slot(Some(func_name.trim()), written)],
+        inserted: true,
+    }
+}
+
+/// The start of the function name and the opening parenthesis of the call
+/// the cursor is in
+fn find_call(chars: &[char], cursor_offset: usize) -> Option<(usize, usize)> {
     let cursor = cursor_offset.min(chars.len());
 
     // Search backwards from cursor to find the opening parenthesis
@@ -82,12 +178,7 @@ fn find_function_call_context(text: &str, cursor_offset: usize) -> Option<(Strin
         return None;
     }
 
-    let func_name: String = chars[func_start..func_end].iter().collect();
-
-    // Count parameters (commas) to determine active parameter
-    let param_index = count_parameters_before_cursor(text, open_paren, cursor);
-
-    Some((func_name, param_index))
+    Some((func_start, open_paren))
 }
 
 /// Count the number of commas (parameters) before the cursor, respecting nesting
@@ -103,7 +194,7 @@ fn count_parameters_before_cursor(text: &str, func_start: usize, cursor: usize)
     let start = func_start + 1;
     let end = cursor.min(chars.len());
 
-    for ch in chars.iter().take(end).skip(start) {
+    for (i, ch) in chars.iter().enumerate().take(end).skip(start) {
         if escape_next {
             escape_next = false;
             continue;
@@ -116,15 +207,14 @@ fn count_parameters_before_cursor(text: &str, func_start: usize, cursor: usize)
 
         match *ch {
             '"' => in_string = !in_string,
-            '|' if !in_string => {
+            // `|>` pipes a value, it doesn't delimit lambda parameters
+            '|' if !in_string && chars.get(i + 1) != Some(&'>') => {
                 // Toggle lambda state (|params| body)
                 in_lambda = !in_lambda;
             }
             '(' | '[' | '{' if !in_string => depth += 1,
-            ')' | ']' | '}' if !in_string => {
-                if depth > 0 {
-                    depth -= 1;
-                }
+            ')' | ']' | '}' if !in_string && depth > 0 => {
+                depth -= 1;
             }
             ',' if !in_string && depth == 0 && !in_lambda => count += 1,
             _ => {}
@@ -198,13 +288,34 @@ mod tests {
         assert_eq!(count, 1);
     }
 
+    #[test]
+    fn test_count_parameters_with_pipe() {
+        let text = "func(xs |> map(f), ";
+        let count = count_parameters_before_cursor(text, 4, 19);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_piped_parameters() {
+        let slots = |text: &str, offset, count| piped_parameters(text, offset, count).parameters;
+        assert_eq!(slots("xs |> reduce(f, ", 16, 3), [2]);
+        assert_eq!(slots("xs\n  |> reduce(f, ", 18, 3), [2]);
+        assert_eq!(slots("xs |> map(", 10, 2), [1]);
+        assert_eq!(slots("x |> json_stringify(", 20, 2), [0]);
+        assert_eq!(slots("x |> round(", 11, 1), [0]);
+        assert_eq!(slots("2 |> pow(_, 3)", 10, 2), [0]);
+        assert_eq!(slots("f(x) |> g(a, _, h(_), _)", 12, 4), [1, 3]);
+        assert!(slots("reduce(f, ", 10, 3).is_empty());
+        assert!(slots("xs |> map(x => pow(", 19, 2).is_empty());
+    }
+
     #[test]
     fn test_get_function_signature_sin() {
         let sig = get_signature("sin");
         assert!(sig.is_some());
         let sig = sig.unwrap();
         assert_eq!(sig.name, "sin");
-        assert_eq!(sig.signature, "sin(x: Number) -> Number");
+        assert_eq!(sig.signature, "sin(x: Number | Vector) -> Number | Vector");
         assert_eq!(sig.parameters.len(), 1);
     }
 
@@ -215,7 +326,7 @@ mod tests {
         let sig = sig.unwrap();
         assert_eq!(sig.parameters.len(), 2);
         assert_eq!(sig.parameters[0].label, "base: Number");
-        assert_eq!(sig.parameters[1].label, "exp: Number");
+        assert_eq!(sig.parameters[1].label, "exponent: Number");
     }
 
     #[test]
@@ -246,6 +357,46 @@ mod tests {
         assert_eq!(help.active_parameter, Some(0));
     }
 
+    #[test]
+    fn test_signature_help_marks_piped_parameter() {
+        let doc = Document::new("[1, 2] |> map(".to_string());
+        let position = Position {
+            line: 0,
+            character: 14,
+        };
+        let help = get_signature_help(&doc, position).unwrap();
+        assert_eq!(help.active_parameter, Some(0));
+        let parameters = help.signatures[0].parameters.clone().unwrap();
+        let documentation: Vec<_> = parameters
+            .into_iter()
+            .map(|p| match p.documentation {
+                Some(Documentation::String(text)) => text.starts_with("Supplied by `|>`"),
+                _ => false,
+            })
+            .collect();
+        assert_eq!(documentation, [false, true]);
+    }
+
+    #[test]
+    fn test_signature_help_skips_piped_first_parameter() {
+        let doc = Document::new("x |> json_stringify(".to_string());
+        let position = Position {
+            line: 0,
+            character: 20,
+        };
+        let help = get_signature_help(&doc, position).unwrap();
+        assert_eq!(help.active_parameter, Some(1));
+        let parameters = help.signatures[0].parameters.clone().unwrap();
+        let documentation: Vec<_> = parameters
+            .into_iter()
+            .map(|p| match p.documentation {
+                Some(Documentation::String(text)) => text.starts_with("Supplied by `|>`"),
+                _ => false,
+            })
+            .collect();
+        assert_eq!(documentation, [true, false]);
+    }
+
     #[test]
     fn test_signature_help_second_parameter() {
         let doc = Document::new("pow(2, ".to_string());
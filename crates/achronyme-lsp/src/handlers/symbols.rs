@@ -1,10 +1,11 @@
 use tower_lsp::lsp_types::*;
 
 use crate::document::Document;
+use crate::workspace::Workspace;
 
 /// Get document symbols (outline) from the AST
 pub fn get_document_symbols(doc: &Document) -> Option<DocumentSymbolResponse> {
-    let ast = doc.ast()?;
+    let ast = doc.ast();
     let source = doc.text();
 
     let symbols = extract_symbols(ast, source);
@@ -16,6 +17,48 @@ pub fn get_document_symbols(doc: &Document) -> Option<DocumentSymbolResponse> {
     }
 }
 
+/// Top-level bindings, type aliases and data types of every workspace file whose name
+/// contains `query` (case-insensitive)
+pub fn workspace_symbols(workspace: &Workspace, query: &str) -> Vec<SymbolInformation> {
+    use achronyme_lsp_core::SymbolKind as Binding;
+
+    let query = query.to_lowercase();
+    let mut symbols = Vec::new();
+    for uri in workspace.files() {
+        let Some(module) = workspace.load(&uri) else {
+            continue;
+        };
+        let Some(index) = module.document.semantic() else {
+            continue;
+        };
+        for symbol in index.symbols() {
+            let kind = match symbol.kind {
+                Binding::Let | Binding::Mut => SymbolKind::VARIABLE,
+                Binding::TypeAlias => SymbolKind::TYPE_PARAMETER,
+                Binding::DataType => SymbolKind::ENUM,
+                Binding::Variant => SymbolKind::ENUM_MEMBER,
+                _ => continue,
+            };
+            if !symbol.top_level || !symbol.name.to_lowercase().contains(&query) {
+                continue;
+            }
+            #[allow(deprecated)]
+            symbols.push(SymbolInformation {
+                name: symbol.name.clone(),
+                kind,
+                tags: None,
+                deprecated: None,
+                location: Location {
+                    uri: uri.clone(),
+                    range: module.document.range_from_span(&symbol.span),
+                },
+                container_name: None,
+            });
+        }
+    }
+    symbols
+}
+
 /// Extract symbols from the AST
 fn extract_symbols(ast: &[achronyme_parser::ast::AstNode], source: &str) -> Vec<SymbolInformation> {
     use achronyme_parser::ast::AstNode;
@@ -100,6 +143,31 @@ fn extract_symbols(ast: &[achronyme_parser::ast::AstNode], source: &str) -> Vec<
                     });
                 }
             }
+            AstNode::DataType { name, .. } => {
+                if let Some((line, col)) = find_symbol_location(&lines, name, "type") {
+                    #[allow(deprecated)]
+                    symbols.push(SymbolInformation {
+                        name: name.clone(),
+                        kind: SymbolKind::ENUM,
+                        tags: None,
+                        deprecated: None,
+                        location: Location {
+                            uri: Url::parse("file:///").unwrap(),
+                            range: Range {
+                                start: Position {
+                                    line,
+                                    character: col,
+                                },
+                                end: Position {
+                                    line,
+                                    character: col + name.len() as u32,
+                                },
+                            },
+                        },
+                        container_name: None,
+                    });
+                }
+            }
             AstNode::Sequence { statements } => {
                 let nested = extract_symbols(statements, source);
                 symbols.extend(nested);
@@ -150,4 +218,31 @@ mod tests {
         assert_eq!(symbols[1].name, "counter");
         assert_eq!(symbols[2].name, "Point");
     }
+
+    #[test]
+    fn test_workspace_symbols() {
+        let project = crate::workspace::tests::Project::new(
+            "workspace_symbols",
+            &[
+                (
+                    "a.soc",
+                    "let mean = xs => do { let total = sum(xs); total / len(xs) }",
+                ),
+                ("lib/b.soc", "type Meaning = Number\nmut meanwhile = 0"),
+            ],
+        );
+        let found: Vec<_> = workspace_symbols(&project.workspace, "MEAN")
+            .into_iter()
+            .map(|s| (s.name, s.location.uri))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("mean".to_string(), project.uri("a.soc")),
+                ("Meaning".to_string(), project.uri("lib/b.soc")),
+                ("meanwhile".to_string(), project.uri("lib/b.soc")),
+            ]
+        );
+        assert!(workspace_symbols(&project.workspace, "total").is_empty());
+    }
 }
@@ -5,6 +5,7 @@ mod capabilities;
 mod document;
 mod handlers;
 mod server;
+mod workspace;
 
 #[derive(Parser)]
 #[command(name = "achronyme-lsp")]
@@ -1,4 +1,3 @@
-use dashmap::DashMap;
 use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer};
@@ -6,10 +5,11 @@ use tower_lsp::{Client, LanguageServer};
 use crate::capabilities;
 use crate::document::Document;
 use crate::handlers;
+use crate::workspace::Workspace;
 
 pub struct Backend {
     client: Client,
-    documents: DashMap<Url, Document>,
+    workspace: Workspace,
     debug: bool,
 }
 
@@ -17,11 +17,36 @@ impl Backend {
     pub fn new(client: Client, debug: bool) -> Self {
         Self {
             client,
-            documents: DashMap::new(),
+            workspace: Workspace::new(),
             debug,
         }
     }
 
+    /// Publish parse, lint, type and import diagnostics for an open document
+    async fn publish_diagnostics(&self, uri: Url) {
+        let Some(doc) = self.workspace.document(&uri) else {
+            return;
+        };
+        let mut diagnostics = handlers::diagnostics::compute_diagnostics(&doc);
+        diagnostics.extend(handlers::diagnostics::lint_diagnostics(&doc));
+        diagnostics.extend(handlers::diagnostics::type_diagnostics(&doc));
+        diagnostics.extend(handlers::diagnostics::import_diagnostics(
+            &self.workspace,
+            &doc,
+            &uri,
+        ));
+        self.client
+            .publish_diagnostics(uri, diagnostics, None)
+            .await;
+    }
+
+    /// Re-check every open document; an edit may change what others import
+    async fn publish_all_diagnostics(&self) {
+        for uri in self.workspace.open_uris() {
+            self.publish_diagnostics(uri).await;
+        }
+    }
+
     async fn log_debug(&self, message: &str) {
         if self.debug {
             self.client
@@ -33,9 +58,20 @@ impl Backend {
 
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
-    async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
         self.log_debug("Initializing Achronyme LSP server").await;
 
+        // Non-relative imports resolve against the workspace root
+        #[allow(deprecated)]
+        let root = params
+            .workspace_folders
+            .as_ref()
+            .and_then(|folders| folders.first())
+            .map(|folder| folder.uri.clone())
+            .or(params.root_uri)
+            .and_then(|uri| uri.to_file_path().ok());
+        self.workspace.set_root(root);
+
         Ok(InitializeResult {
             capabilities: capabilities::server_capabilities(),
             server_info: Some(ServerInfo {
@@ -58,46 +94,40 @@ impl LanguageServer for Backend {
     }
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
-        let uri = params.text_document.uri.clone();
-        let text = params.text_document.text.clone();
+        let uri = params.text_document.uri;
 
         self.log_debug(&format!("Document opened: {}", uri)).await;
 
-        let document = Document::new(text);
-        let diagnostics = handlers::diagnostics::compute_diagnostics(&document);
-
-        self.documents.insert(uri.clone(), document);
-
-        self.client
-            .publish_diagnostics(uri, diagnostics, None)
-            .await;
+        self.workspace
+            .open(uri, Document::new(params.text_document.text));
+        self.publish_all_diagnostics().await;
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
-        let uri = params.text_document.uri.clone();
+        let uri = params.text_document.uri;
 
         self.log_debug(&format!("Document changed: {}", uri)).await;
 
-        if let Some(mut doc) = self.documents.get_mut(&uri) {
-            // Apply changes (for full sync, we just replace the entire text)
-            for change in params.content_changes {
-                doc.update_text(change.text);
-            }
-
-            let diagnostics = handlers::diagnostics::compute_diagnostics(&doc);
-
-            drop(doc); // Release the lock before async call
-
-            self.client
-                .publish_diagnostics(uri, diagnostics, None)
-                .await;
+        let updated = self.workspace.update(&uri, |doc| {
+            // Ranged edits under incremental sync; a change without a range
+            // carries the whole text
+            doc.apply_changes(params.content_changes);
+        });
+        if updated.is_some() {
+            self.publish_all_diagnostics().await;
         }
     }
 
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        self.log_debug(&format!("Document saved: {}", params.text_document.uri))
+            .await;
+        self.publish_all_diagnostics().await;
+    }
+
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
         let uri = params.text_document.uri;
         self.log_debug(&format!("Document closed: {}", uri)).await;
-        self.documents.remove(&uri);
+        self.workspace.close(&uri);
     }
 
     async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
@@ -107,8 +137,13 @@ impl LanguageServer for Backend {
         self.log_debug(&format!("Hover request at {:?}", position))
             .await;
 
-        if let Some(doc) = self.documents.get(uri) {
-            Ok(handlers::hover::get_hover(&doc, position))
+        if let Some(doc) = self.workspace.document(uri) {
+            Ok(handlers::hover::get_hover(
+                &self.workspace,
+                &doc,
+                uri,
+                position,
+            ))
         } else {
             Ok(None)
         }
@@ -124,8 +159,9 @@ impl LanguageServer for Backend {
         self.log_debug(&format!("Go to definition at {:?}", position))
             .await;
 
-        if let Some(doc) = self.documents.get(uri) {
+        if let Some(doc) = self.workspace.document(uri) {
             Ok(handlers::definition::get_definition(
+                &self.workspace,
                 &doc,
                 position,
                 uri.clone(),
@@ -142,17 +178,58 @@ impl LanguageServer for Backend {
         self.log_debug(&format!("Find references at {:?}", position))
             .await;
 
-        if let Some(doc) = self.documents.get(uri) {
+        if let Some(doc) = self.workspace.document(uri) {
             Ok(handlers::references::find_references(
                 &doc,
                 position,
                 uri.clone(),
+                params.context.include_declaration,
             ))
         } else {
             Ok(None)
         }
     }
 
+    async fn document_highlight(
+        &self,
+        params: DocumentHighlightParams,
+    ) -> Result<Option<Vec<DocumentHighlight>>> {
+        let uri = &params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        if let Some(doc) = self.workspace.document(uri) {
+            Ok(handlers::highlight::document_highlight(&doc, position))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        if let Some(doc) = self.workspace.document(&params.text_document.uri) {
+            Ok(handlers::rename::prepare_rename(&doc, params.position))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let uri = &params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+
+        self.log_debug(&format!("Rename at {:?} to {}", position, params.new_name))
+            .await;
+
+        if let Some(doc) = self.workspace.document(uri) {
+            handlers::rename::rename(&doc, position, &params.new_name, uri.clone())
+                .map_err(tower_lsp::jsonrpc::Error::invalid_params)
+        } else {
+            Ok(None)
+        }
+    }
+
     async fn document_symbol(
         &self,
         params: DocumentSymbolParams,
@@ -162,13 +239,26 @@ impl LanguageServer for Backend {
         self.log_debug(&format!("Document symbols for: {}", uri))
             .await;
 
-        if let Some(doc) = self.documents.get(uri) {
+        if let Some(doc) = self.workspace.document(uri) {
             Ok(handlers::symbols::get_document_symbols(&doc))
         } else {
             Ok(None)
         }
     }
 
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> Result<Option<Vec<SymbolInformation>>> {
+        self.log_debug(&format!("Workspace symbols for: {}", params.query))
+            .await;
+
+        Ok(Some(handlers::symbols::workspace_symbols(
+            &self.workspace,
+            &params.query,
+        )))
+    }
+
     async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
         let uri = &params.text_document_position.text_document.uri;
         let position = params.text_document_position.position;
@@ -176,8 +266,8 @@ impl LanguageServer for Backend {
         self.log_debug(&format!("Completion request at {:?}", position))
             .await;
 
-        if let Some(doc) = self.documents.get(uri) {
-            let items = handlers::completion::get_completions(&doc, position);
+        if let Some(doc) = self.workspace.document(uri) {
+            let items = handlers::completion::get_completions(&self.workspace, &doc, uri, position);
             Ok(Some(CompletionResponse::Array(items)))
         } else {
             Ok(None)
@@ -190,7 +280,7 @@ impl LanguageServer for Backend {
         self.log_debug(&format!("Formatting request for: {}", uri))
             .await;
 
-        if let Some(doc) = self.documents.get(uri) {
+        if let Some(doc) = self.workspace.document(uri) {
             let edits = handlers::formatting::format_document(&doc, &params.options);
             Ok(Some(edits))
         } else {
@@ -205,10 +295,55 @@ impl LanguageServer for Backend {
         self.log_debug(&format!("Signature help request at {:?}", position))
             .await;
 
-        if let Some(doc) = self.documents.get(uri) {
+        if let Some(doc) = self.workspace.document(uri) {
             Ok(handlers::signature_help::get_signature_help(&doc, position))
         } else {
             Ok(None)
         }
     }
+
+    async fn semantic_tokens_full(
+        &self,
+        params: SemanticTokensParams,
+    ) -> Result<Option<SemanticTokensResult>> {
+        let uri = &params.text_document.uri;
+
+        self.log_debug(&format!("Semantic tokens for: {}", uri))
+            .await;
+
+        if let Some(doc) = self.workspace.document(uri) {
+            Ok(Some(SemanticTokensResult::Tokens(SemanticTokens {
+                result_id: None,
+                data: handlers::semantic_tokens::semantic_tokens(&doc),
+            })))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
+        if let Some(doc) = self.workspace.document(&params.text_document.uri) {
+            Ok(Some(handlers::inlay_hints::inlay_hints(&doc, params.range)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let uri = &params.text_document.uri;
+
+        self.log_debug(&format!("Code actions at {:?}", params.range))
+            .await;
+
+        if let Some(doc) = self.workspace.document(uri) {
+            Ok(Some(handlers::code_actions::code_actions(
+                &self.workspace,
+                &doc,
+                uri,
+                &params.context.diagnostics,
+            )))
+        } else {
+            Ok(None)
+        }
+    }
 }
@@ -0,0 +1,370 @@
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use achronyme_lsp_core::{Import, ImportItem, SymbolId, SymbolKind};
+use achronyme_parser::modules::resolve_module_path;
+use dashmap::DashMap;
+use tower_lsp::lsp_types::Url;
+
+use crate::document::Document;
+
+/// Imports are followed through at most this many re-exports
+const MAX_REEXPORT_DEPTH: usize = 16;
+
+/// Open documents plus the modules they import from disk
+#[derive(Default)]
+pub struct Workspace {
+    /// Documents open in the editor; these win over the files on disk
+    documents: DashMap<Url, Arc<Document>>,
+    /// Modules read from disk, reloaded when their mtime changes
+    disk: DashMap<PathBuf, DiskModule>,
+    /// Non-relative imports resolve against this directory
+    root: RwLock<Option<PathBuf>>,
+}
+
+struct DiskModule {
+    modified: Option<SystemTime>,
+    document: Arc<Document>,
+}
+
+/// A loaded module
+pub struct Module {
+    pub uri: Url,
+    pub document: Arc<Document>,
+}
+
+/// Where an imported name is defined
+pub struct ImportedDefinition {
+    pub module: Module,
+    pub symbol: SymbolId,
+}
+
+impl Workspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_root(&self, root: Option<PathBuf>) {
+        *self.root.write().unwrap() = root;
+    }
+
+    pub fn root(&self) -> Option<PathBuf> {
+        self.root.read().unwrap().clone()
+    }
+
+    pub fn open(&self, uri: Url, document: Document) {
+        self.documents.insert(uri, Arc::new(document));
+    }
+
+    pub fn close(&self, uri: &Url) {
+        self.documents.remove(uri);
+    }
+
+    /// An open document
+    pub fn document(&self, uri: &Url) -> Option<Arc<Document>> {
+        self.documents.get(uri).map(|doc| Arc::clone(&doc))
+    }
+
+    /// Edit an open document in place
+    pub fn update(&self, uri: &Url, edit: impl FnOnce(&mut Document)) -> Option<Arc<Document>> {
+        let mut entry = self.documents.get_mut(uri)?;
+        edit(Arc::make_mut(&mut entry));
+        Some(Arc::clone(&entry))
+    }
+
+    pub fn open_uris(&self) -> Vec<Url> {
+        self.documents.iter().map(|e| e.key().clone()).collect()
+    }
+
+    /// The file `import ... from "module_path"` in `importer` refers to.
+    ///
+    /// Follows the VM: `./` and `../` paths are relative to the importing
+    /// file, anything else to the working directory, which for an editor is
+    /// the workspace root (or the importing file's directory without one).
+    pub fn resolve(&self, importer: &Url, module_path: &str) -> Option<Url> {
+        let importer = importer.to_file_path().ok()?;
+        let path = resolve_module_path(module_path, Some(&importer));
+        let path = if path.is_absolute() {
+            path
+        } else {
+            let base = self
+                .root()
+                .or_else(|| importer.parent().map(Path::to_path_buf))?;
+            base.join(path)
+        };
+        Url::from_file_path(normalize(&path)).ok()
+    }
+
+    /// Load a module, preferring the open document over the file on disk
+    pub fn load(&self, uri: &Url) -> Option<Module> {
+        if let Some(document) = self.document(uri) {
+            return Some(Module {
+                uri: uri.clone(),
+                document,
+            });
+        }
+
+        let path = uri.to_file_path().ok()?;
+        let modified = std::fs::metadata(&path).ok()?.modified().ok();
+        if let Some(cached) = self.disk.get(&path) {
+            if cached.modified == modified {
+                return Some(Module {
+                    uri: uri.clone(),
+                    document: Arc::clone(&cached.document),
+                });
+            }
+        }
+        let text = std::fs::read_to_string(&path).ok()?;
+        let document = Arc::new(Document::new(text));
+        self.disk.insert(
+            path,
+            DiskModule {
+                modified,
+                document: Arc::clone(&document),
+            },
+        );
+        Some(Module {
+            uri: uri.clone(),
+            document,
+        })
+    }
+
+    /// The module an import statement of `importer` refers to
+    pub fn import_target(&self, importer: &Url, import: &Import) -> Option<Module> {
+        self.load(&self.resolve(importer, &import.module)?)
+    }
+
+    /// The definition behind the import item or imported name at `offset`
+    pub fn imported_definition(
+        &self,
+        uri: &Url,
+        doc: &Document,
+        offset: usize,
+    ) -> Option<ImportedDefinition> {
+        let index = doc.semantic()?;
+        let (import, item) = index.import_item_at(offset).or_else(|| {
+            let symbol = index.symbol_at(offset)?;
+            find_import_item(index.imports(), symbol)
+        })?;
+        let module = self.import_target(uri, import)?;
+        self.follow_export(module, &item.name, 0)
+    }
+
+    /// The binding a module exports as `name`, through re-exports of imports
+    fn follow_export(
+        &self,
+        module: Module,
+        name: &str,
+        depth: usize,
+    ) -> Option<ImportedDefinition> {
+        let index = module.document.semantic()?;
+        let symbol = index.export(name)?.symbol?;
+        if let SymbolKind::Import { .. } = index.symbol(symbol).kind {
+            if depth < MAX_REEXPORT_DEPTH {
+                if let Some((import, item)) = find_import_item(index.imports(), symbol) {
+                    if let Some(next) = self.import_target(&module.uri, import) {
+                        if let Some(found) = self.follow_export(next, &item.name, depth + 1) {
+                            return Some(found);
+                        }
+                    }
+                }
+            }
+        }
+        Some(ImportedDefinition { module, symbol })
+    }
+
+    /// Every Achronyme file in the workspace plus all open documents
+    pub fn files(&self) -> Vec<Url> {
+        let mut files = Vec::new();
+        if let Some(root) = self.root() {
+            collect_sources(&root, &mut files);
+        }
+        let mut uris: Vec<Url> = files
+            .into_iter()
+            .filter_map(|path| Url::from_file_path(path).ok())
+            .collect();
+        for uri in self.open_uris() {
+            if !uris.contains(&uri) {
+                uris.push(uri);
+            }
+        }
+        uris
+    }
+}
+
+fn find_import_item(imports: &[Import], symbol: SymbolId) -> Option<(&Import, &ImportItem)> {
+    imports.iter().find_map(|import| {
+        let item = import.items.iter().find(|item| item.symbol == symbol)?;
+        Some((import, item))
+    })
+}
+
+/// Recursively find `.soc` files, skipping hidden and build directories
+fn collect_sources(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.filter_map(Result::ok).collect();
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if path.is_dir() {
+            if !name.starts_with('.') && name != "target" && name != "node_modules" {
+                collect_sources(&path, files);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "soc") {
+            files.push(path);
+        }
+    }
+}
+
+/// Remove `.` and `..` components without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A scratch directory of module files, removed on drop
+    pub(crate) struct Project {
+        pub root: PathBuf,
+        pub workspace: Workspace,
+    }
+
+    impl Project {
+        pub fn new(name: &str, files: &[(&str, &str)]) -> Self {
+            let root =
+                std::env::temp_dir().join(format!("achronyme_lsp_{}_{}", std::process::id(), name));
+            let _ = std::fs::remove_dir_all(&root);
+            for (path, text) in files {
+                let path = root.join(path);
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(path, text).unwrap();
+            }
+            let workspace = Workspace::new();
+            workspace.set_root(Some(root.clone()));
+            Self { root, workspace }
+        }
+
+        pub fn uri(&self, path: &str) -> Url {
+            Url::from_file_path(self.root.join(path)).unwrap()
+        }
+
+        /// Open a project file in the workspace and return it
+        pub fn open(&self, path: &str) -> (Url, Arc<Document>) {
+            let uri = self.uri(path);
+            let text = std::fs::read_to_string(self.root.join(path)).unwrap();
+            self.workspace.open(uri.clone(), Document::new(text));
+            let doc = self.workspace.document(&uri).unwrap();
+            (uri, doc)
+        }
+    }
+
+    impl Drop for Project {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.root);
+        }
+    }
+
+    #[test]
+    fn test_resolve_like_the_vm() {
+        let project = Project::new("resolve", &[]);
+        let importer = project.uri("app/main.soc");
+        let ws = &project.workspace;
+        assert_eq!(
+            ws.resolve(&importer, "./util"),
+            Some(project.uri("app/util.soc"))
+        );
+        assert_eq!(
+            ws.resolve(&importer, "../lib/math.soc"),
+            Some(project.uri("lib/math.soc"))
+        );
+        assert_eq!(
+            ws.resolve(&importer, "lib/stats"),
+            Some(project.uri("lib/stats.soc"))
+        );
+
+        ws.set_root(None);
+        assert_eq!(
+            ws.resolve(&importer, "stats"),
+            Some(project.uri("app/stats.soc"))
+        );
+    }
+
+    #[test]
+    fn test_open_documents_win_over_disk() {
+        let project = Project::new("open_wins", &[("util.soc", "let a = 1\nexport { a }")]);
+        let uri = project.uri("util.soc");
+        let exports = |ws: &Workspace| -> Vec<String> {
+            let module = ws.load(&uri).unwrap();
+            let index = module.document.semantic().unwrap();
+            index.exports().iter().map(|e| e.name.clone()).collect()
+        };
+        assert_eq!(exports(&project.workspace), ["a"]);
+
+        project.workspace.open(
+            uri.clone(),
+            Document::new("let b = 2\nexport { b }".to_string()),
+        );
+        assert_eq!(exports(&project.workspace), ["b"]);
+
+        project.workspace.close(&uri);
+        assert_eq!(exports(&project.workspace), ["a"]);
+    }
+
+    #[test]
+    fn test_follow_reexports() {
+        let project = Project::new(
+            "reexport",
+            &[
+                ("core.soc", "let square = x => x^2\nexport { square }"),
+                (
+                    "lib.soc",
+                    "import { square as sq } from \"./core\"\nexport { sq }",
+                ),
+                ("main.soc", "import { sq } from \"./lib\"\nsq(3)"),
+            ],
+        );
+        let (uri, doc) = project.open("main.soc");
+        let offset = doc.text().find("sq(3)").unwrap();
+        let found = project
+            .workspace
+            .imported_definition(&uri, &doc, offset)
+            .unwrap();
+        assert_eq!(found.module.uri, project.uri("core.soc"));
+        let index = found.module.document.semantic().unwrap();
+        assert_eq!(index.symbol(found.symbol).name, "square");
+    }
+
+    #[test]
+    fn test_files_lists_sources() {
+        let project = Project::new(
+            "files",
+            &[
+                ("a.soc", ""),
+                ("lib/b.soc", ""),
+                ("notes.txt", ""),
+                (".hidden/c.soc", ""),
+            ],
+        );
+        assert_eq!(
+            project.workspace.files(),
+            [project.uri("a.soc"), project.uri("lib/b.soc")]
+        );
+    }
+}
@@ -0,0 +1,2561 @@
+//! Documentation for the built-in functions
+//!
+//! Every function the VM registers has an entry in [`BUILTIN_DOCS`]. The VM
+//! registry attaches it to the function's metadata; the LSP (signature help,
+//! hover, completion, type checking) reads the table directly, so it does
+//! not need to link the VM.
+//!
+//! Parameter and return types use the language's own annotation syntax
+//! (`Number | Vector`, `Tensor<Number>`, `Function`, ...); runtime handles
+//! without a dedicated annotation (`Future`, `Signal`, `DataFrame`, ...) are
+//! written as type references.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::OnceLock;
+
+/// Group a builtin belongs to, in reference-page order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    Math,
+    Strings,
+    Vectors,
+    Io,
+    Statistics,
+    LinearAlgebra,
+    Complex,
+    Utilities,
+    Records,
+    Arrays,
+    HigherOrder,
+    Parallel,
+    Modules,
+    Numerical,
+    Async,
+    Encoding,
+    Binary,
+    Persistence,
+    DataFrame,
+    Network,
+    Environment,
+    Concurrency,
+    Reactive,
+    Gui,
+}
+
+impl Category {
+    /// Every category, in reference-page order
+    pub const ALL: [Category; 24] = [
+        Category::Math,
+        Category::Strings,
+        Category::Vectors,
+        Category::Io,
+        Category::Statistics,
+        Category::LinearAlgebra,
+        Category::Complex,
+        Category::Utilities,
+        Category::Records,
+        Category::Arrays,
+        Category::HigherOrder,
+        Category::Parallel,
+        Category::Modules,
+        Category::Numerical,
+        Category::Async,
+        Category::Encoding,
+        Category::Binary,
+        Category::Persistence,
+        Category::DataFrame,
+        Category::Network,
+        Category::Environment,
+        Category::Concurrency,
+        Category::Reactive,
+        Category::Gui,
+    ];
+
+    /// Section title
+    pub fn title(self) -> &'static str {
+        match self {
+            Category::Math => "Math",
+            Category::Strings => "Strings",
+            Category::Vectors => "Vectors",
+            Category::Io => "Console I/O",
+            Category::Statistics => "Statistics",
+            Category::LinearAlgebra => "Linear Algebra",
+            Category::Complex => "Complex Numbers",
+            Category::Utilities => "Utilities",
+            Category::Records => "Records",
+            Category::Arrays => "Arrays",
+            Category::HigherOrder => "Higher-Order Functions",
+            Category::Parallel => "Parallelism",
+            Category::Modules => "Modules",
+            Category::Numerical => "Numerical Analysis",
+            Category::Async => "Async, Tasks and Files",
+            Category::Encoding => "JSON and CSV",
+            Category::Binary => "Binary Data",
+            Category::Persistence => "Environment Persistence",
+            Category::DataFrame => "DataFrames",
+            Category::Network => "Network",
+            Category::Environment => "Environment Variables",
+            Category::Concurrency => "Concurrency",
+            Category::Reactive => "Reactivity",
+            Category::Gui => "GUI",
+        }
+    }
+
+    /// Lowercase identifier used in JSON output
+    pub fn id(self) -> &'static str {
+        match self {
+            Category::Math => "math",
+            Category::Strings => "strings",
+            Category::Vectors => "vectors",
+            Category::Io => "io",
+            Category::Statistics => "statistics",
+            Category::LinearAlgebra => "linear_algebra",
+            Category::Complex => "complex",
+            Category::Utilities => "utilities",
+            Category::Records => "records",
+            Category::Arrays => "arrays",
+            Category::HigherOrder => "higher_order",
+            Category::Parallel => "parallel",
+            Category::Modules => "modules",
+            Category::Numerical => "numerical",
+            Category::Async => "async",
+            Category::Encoding => "encoding",
+            Category::Binary => "binary",
+            Category::Persistence => "persistence",
+            Category::DataFrame => "dataframe",
+            Category::Network => "network",
+            Category::Environment => "environment",
+            Category::Concurrency => "concurrency",
+            Category::Reactive => "reactive",
+            Category::Gui => "gui",
+        }
+    }
+}
+
+/// Whether a parameter must be passed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    Required,
+    /// May be omitted; only trailing parameters are optional
+    Optional,
+    /// Takes any number of arguments; always the last parameter
+    Variadic,
+}
+
+/// A documented parameter
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    pub name: &'static str,
+    /// Type annotation, e.g. `Number | Vector`
+    pub ty: &'static str,
+    pub doc: &'static str,
+    pub kind: ParamKind,
+}
+
+impl Param {
+    /// `x: Number`, `n?: Number` or `...values: Any`
+    pub fn label(&self) -> String {
+        match self.kind {
+            ParamKind::Required => format!("{}: {}", self.name, self.ty),
+            ParamKind::Optional => format!("{}?: {}", self.name, self.ty),
+            ParamKind::Variadic => format!("...{}: {}", self.name, self.ty),
+        }
+    }
+}
+
+/// Documentation for one builtin
+#[derive(Debug)]
+pub struct BuiltinDoc {
+    pub name: &'static str,
+    pub category: Category,
+    pub params: &'static [Param],
+    /// Parameter that `value |> f(...)` fills with `value` when no argument
+    /// is `_`: 0 for most builtins, the collection for `map`, `reduce`, ...
+    pub pipe_arg: usize,
+    /// Return type annotation
+    pub returns: &'static str,
+    /// One or two sentences in Markdown
+    pub summary: &'static str,
+    /// Snippets of Achronyme code, each parseable on its own
+    pub examples: &'static [&'static str],
+}
+
+impl BuiltinDoc {
+    /// `atan2(y: Number, x: Number) -> Number`
+    pub fn signature(&self) -> String {
+        let params: Vec<String> = self.params.iter().map(Param::label).collect();
+        format!("{}({}) -> {}", self.name, params.join(", "), self.returns)
+    }
+
+    /// Fewest arguments a call can pass
+    pub fn min_args(&self) -> usize {
+        self.params
+            .iter()
+            .filter(|p| p.kind == ParamKind::Required)
+            .count()
+    }
+
+    /// Most arguments a call can pass, `None` if variadic
+    pub fn max_args(&self) -> Option<usize> {
+        if self.params.iter().any(|p| p.kind == ParamKind::Variadic) {
+            None
+        } else {
+            Some(self.params.len())
+        }
+    }
+
+    /// Signature, summary, parameters and examples as Markdown
+    pub fn markdown(&self) -> String {
+        let mut out = format!(
+            "```achronyme\n{}\n```\n\n{}\n",
+            self.signature(),
+            self.summary
+        );
+        if !self.params.is_empty() {
+            out.push_str("\n**Parameters**\n\n");
+            for param in self.params {
+                let _ = writeln!(out, "- `{}`: {}", param.label(), param.doc);
+            }
+        }
+        if !self.examples.is_empty() {
+            out.push_str("\n**Examples**\n\n```achronyme\n");
+            for example in self.examples {
+                out.push_str(example);
+                out.push('\n');
+            }
+            out.push_str("```\n");
+        }
+        out
+    }
+}
+
+/// Documentation for a builtin by name
+pub fn lookup(name: &str) -> Option<&'static BuiltinDoc> {
+    static INDEX: OnceLock<HashMap<&'static str, &'static BuiltinDoc>> = OnceLock::new();
+    INDEX
+        .get_or_init(|| BUILTIN_DOCS.iter().map(|doc| (doc.name, doc)).collect())
+        .get(name)
+        .copied()
+}
+
+const fn p(name: &'static str, ty: &'static str, doc: &'static str) -> Param {
+    Param {
+        name,
+        ty,
+        doc,
+        kind: ParamKind::Required,
+    }
+}
+
+const fn opt(name: &'static str, ty: &'static str, doc: &'static str) -> Param {
+    Param {
+        name,
+        ty,
+        doc,
+        kind: ParamKind::Optional,
+    }
+}
+
+const fn rest(name: &'static str, ty: &'static str, doc: &'static str) -> Param {
+    Param {
+        name,
+        ty,
+        doc,
+        kind: ParamKind::Variadic,
+    }
+}
+
+// Types that recur across the table
+const NUM_OR_VEC: &str = "Number | Vector";
+const NUMERIC: &str = "Vector | Tensor<Number>";
+const COLLECTION: &str = "Vector | Tensor<Number> | String";
+const ITERABLE: &str = "Vector | Tensor<Number> | String | Generator | Iterator";
+const LAZY_VECTOR: &str = "Vector | Generator | Iterator";
+const STYLE: &str = "String | null";
+
+use Category::*;
+
+/// Documentation for every registered builtin, in registration order
+pub static BUILTIN_DOCS: &[BuiltinDoc] = &[
+    // ========================================================================
+    // Math
+    // ========================================================================
+    BuiltinDoc {
+        name: "sin",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "Angle in radians")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Sine of `x`. Vectors are mapped element-wise.",
+        examples: &["sin(PI / 2)  // 1"],
+    },
+    BuiltinDoc {
+        name: "cos",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "Angle in radians")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Cosine of `x`. Vectors are mapped element-wise.",
+        examples: &["cos(0)  // 1"],
+    },
+    BuiltinDoc {
+        name: "tan",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "Angle in radians")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Tangent of `x`. Vectors are mapped element-wise.",
+        examples: &["tan(PI / 4)  // ≈ 1"],
+    },
+    BuiltinDoc {
+        name: "asin",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "Value between -1 and 1")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Arc sine of `x`, in radians.",
+        examples: &["asin(1)  // 1.5707..."],
+    },
+    BuiltinDoc {
+        name: "acos",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "Value between -1 and 1")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Arc cosine of `x`, in radians.",
+        examples: &["acos(1)  // 0"],
+    },
+    BuiltinDoc {
+        name: "atan",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "Any number")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Arc tangent of `x`, in radians.",
+        examples: &["atan(1)  // 0.7853..."],
+    },
+    BuiltinDoc {
+        name: "atan2",
+        category: Math,
+        params: &[p("y", "Number", "Y coordinate"), p("x", "Number", "X coordinate")],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Angle of the point `(x, y)` from the positive x axis, in radians, \
+                  using the signs of both arguments to pick the quadrant.",
+        examples: &["atan2(1, -1)  // 2.3561..."],
+    },
+    BuiltinDoc {
+        name: "sinh",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "Any number")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Hyperbolic sine of `x`.",
+        examples: &["sinh(0)  // 0"],
+    },
+    BuiltinDoc {
+        name: "cosh",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "Any number")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Hyperbolic cosine of `x`.",
+        examples: &["cosh(0)  // 1"],
+    },
+    BuiltinDoc {
+        name: "tanh",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "Any number")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Hyperbolic tangent of `x`.",
+        examples: &["tanh(0)  // 0"],
+    },
+    BuiltinDoc {
+        name: "exp",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "The exponent")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "`e` raised to the power `x`.",
+        examples: &["exp(1)  // 2.7182..."],
+    },
+    BuiltinDoc {
+        name: "ln",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "A positive number")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Natural (base `e`) logarithm of `x`.",
+        examples: &["ln(E)  // 1"],
+    },
+    BuiltinDoc {
+        name: "log",
+        category: Math,
+        params: &[
+            p("x", "Number", "A positive number"),
+            p("base", "Number", "Base of the logarithm"),
+        ],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Logarithm of `x` in the given base.",
+        examples: &["log(8, 2)  // 3"],
+    },
+    BuiltinDoc {
+        name: "log10",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "A positive number")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Base-10 logarithm of `x`.",
+        examples: &["log10(1000)  // 3"],
+    },
+    BuiltinDoc {
+        name: "log2",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "A positive number")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Base-2 logarithm of `x`.",
+        examples: &["log2(8)  // 3"],
+    },
+    BuiltinDoc {
+        name: "floor",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "The number to round down")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Largest integer less than or equal to `x`.",
+        examples: &["floor(2.7)  // 2"],
+    },
+    BuiltinDoc {
+        name: "ceil",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "The number to round up")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Smallest integer greater than or equal to `x`.",
+        examples: &["ceil(2.1)  // 3"],
+    },
+    BuiltinDoc {
+        name: "round",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "The number to round")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Nearest integer to `x`; halves round away from zero.",
+        examples: &["round(2.5)  // 3"],
+    },
+    BuiltinDoc {
+        name: "trunc",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "The number to truncate")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Integer part of `x`, rounding toward zero.",
+        examples: &["trunc(-2.7)  // -2"],
+    },
+    BuiltinDoc {
+        name: "sqrt",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "A non-negative number")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Square root of `x` (`NaN` for negative numbers).",
+        examples: &["sqrt(16)  // 4"],
+    },
+    BuiltinDoc {
+        name: "abs",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "Any number")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Absolute value of `x`. Use `magnitude` for complex numbers.",
+        examples: &["abs(-3)  // 3"],
+    },
+    BuiltinDoc {
+        name: "pow",
+        category: Math,
+        params: &[p("base", "Number", "The base"), p("exponent", "Number", "The exponent")],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "`base` raised to `exponent`, the same as `base ^ exponent`.",
+        examples: &["pow(2, 10)  // 1024"],
+    },
+    BuiltinDoc {
+        name: "min",
+        category: Math,
+        params: &[rest("values", "Number", "One or more numbers")],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Smallest of the arguments.",
+        examples: &["min(4, 1, 3)  // 1"],
+    },
+    BuiltinDoc {
+        name: "max",
+        category: Math,
+        params: &[rest("values", "Number", "One or more numbers")],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Largest of the arguments.",
+        examples: &["max(4, 1, 3)  // 4"],
+    },
+    BuiltinDoc {
+        name: "sign",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "Any number")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "`-1`, `0` or `1` depending on the sign of `x`.",
+        examples: &["sign(-3)  // -1"],
+    },
+    BuiltinDoc {
+        name: "deg",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "Angle in radians")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Converts radians to degrees.",
+        examples: &["deg(PI)  // 180"],
+    },
+    BuiltinDoc {
+        name: "rad",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "Angle in degrees")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Converts degrees to radians.",
+        examples: &["rad(180)  // 3.1415..."],
+    },
+    BuiltinDoc {
+        name: "cbrt",
+        category: Math,
+        params: &[p("x", NUM_OR_VEC, "Any number")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Cube root of `x`.",
+        examples: &["cbrt(27)  // 3"],
+    },
+    BuiltinDoc {
+        name: "PI",
+        category: Math,
+        params: &[],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "π as a function; the constant `PI` is usually more convenient.",
+        examples: &["PI()  // 3.1415..."],
+    },
+    BuiltinDoc {
+        name: "E",
+        category: Math,
+        params: &[],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Euler's number as a function; the constant `E` is usually more convenient.",
+        examples: &["E()  // 2.7182..."],
+    },
+    BuiltinDoc {
+        name: "set_precision",
+        category: Math,
+        params: &[p("decimals", "Number", "Decimal places used when formatting numbers")],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "Sets how many decimal places numbers are printed with.",
+        examples: &["set_precision(3)"],
+    },
+    // ========================================================================
+    // Strings
+    // ========================================================================
+    BuiltinDoc {
+        name: "len",
+        category: Strings,
+        params: &[p("value", "String | Vector | DataFrame | Bytes", "The value to measure")],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Length of a string (in bytes), vector or byte buffer, or the number \
+                  of rows of a DataFrame.",
+        examples: &["len([1, 2, 3])  // 3", "len(\"abc\")  // 3"],
+    },
+    BuiltinDoc {
+        name: "char_at",
+        category: Strings,
+        params: &[p("s", "String", "The string"), p("index", "Number", "Zero-based character index")],
+        pipe_arg: 0,
+        returns: "String",
+        summary: "The character at `index`; errors when out of bounds.",
+        examples: &["char_at(\"abc\", 1)  // \"b\""],
+    },
+    BuiltinDoc {
+        name: "upper",
+        category: Strings,
+        params: &[p("s", "String", "The string")],
+        pipe_arg: 0,
+        returns: "String",
+        summary: "`s` converted to uppercase.",
+        examples: &["upper(\"abc\")  // \"ABC\""],
+    },
+    BuiltinDoc {
+        name: "lower",
+        category: Strings,
+        params: &[p("s", "String", "The string")],
+        pipe_arg: 0,
+        returns: "String",
+        summary: "`s` converted to lowercase.",
+        examples: &["lower(\"ABC\")  // \"abc\""],
+    },
+    BuiltinDoc {
+        name: "trim",
+        category: Strings,
+        params: &[p("s", "String", "The string")],
+        pipe_arg: 0,
+        returns: "String",
+        summary: "`s` without leading and trailing whitespace.",
+        examples: &["trim(\"  hi  \")  // \"hi\""],
+    },
+    BuiltinDoc {
+        name: "trim_start",
+        category: Strings,
+        params: &[p("s", "String", "The string")],
+        pipe_arg: 0,
+        returns: "String",
+        summary: "`s` without leading whitespace.",
+        examples: &["trim_start(\"  hi\")  // \"hi\""],
+    },
+    BuiltinDoc {
+        name: "trim_end",
+        category: Strings,
+        params: &[p("s", "String", "The string")],
+        pipe_arg: 0,
+        returns: "String",
+        summary: "`s` without trailing whitespace.",
+        examples: &["trim_end(\"hi  \")  // \"hi\""],
+    },
+    BuiltinDoc {
+        name: "contains",
+        category: Strings,
+        params: &[p("s", "String", "The string to search"), p("needle", "String", "The text to look for")],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "Whether `needle` occurs in `s`.",
+        examples: &["contains(\"hello\", \"ell\")  // true"],
+    },
+    BuiltinDoc {
+        name: "starts_with",
+        category: Strings,
+        params: &[p("s", "String", "The string"), p("prefix", "String", "The expected prefix")],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "Whether `s` begins with `prefix`.",
+        examples: &["starts_with(\"hello\", \"he\")  // true"],
+    },
+    BuiltinDoc {
+        name: "ends_with",
+        category: Strings,
+        params: &[p("s", "String", "The string"), p("suffix", "String", "The expected suffix")],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "Whether `s` ends with `suffix`.",
+        examples: &["ends_with(\"hello\", \"lo\")  // true"],
+    },
+    BuiltinDoc {
+        name: "replace",
+        category: Strings,
+        params: &[
+            p("s", "String", "The string"),
+            p("from", "String", "Text to replace"),
+            p("to", "String", "Replacement text"),
+        ],
+        pipe_arg: 0,
+        returns: "String",
+        summary: "`s` with every occurrence of `from` replaced by `to`.",
+        examples: &["replace(\"a-b-c\", \"-\", \"+\")  // \"a+b+c\""],
+    },
+    BuiltinDoc {
+        name: "split",
+        category: Strings,
+        params: &[p("s", "String", "The string"), p("delimiter", "String", "Separator between parts")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "The parts of `s` between occurrences of `delimiter`.",
+        examples: &["split(\"a,b,c\", \",\")  // [\"a\", \"b\", \"c\"]"],
+    },
+    BuiltinDoc {
+        name: "join",
+        category: Strings,
+        params: &[p("parts", "Vector", "Vector of strings"), p("separator", "String", "Text placed between parts")],
+        pipe_arg: 0,
+        returns: "String",
+        summary: "Concatenates the strings in `parts` with `separator` between them.",
+        examples: &["join([\"a\", \"b\"], \"-\")  // \"a-b\""],
+    },
+    BuiltinDoc {
+        name: "substring",
+        category: Strings,
+        params: &[
+            p("s", "String", "The string"),
+            p("start", "Number", "First character index (inclusive)"),
+            p("end", "Number", "Last character index (exclusive)"),
+        ],
+        pipe_arg: 0,
+        returns: "String",
+        summary: "The characters of `s` from `start` up to `end`.",
+        examples: &["substring(\"hello\", 1, 3)  // \"el\""],
+    },
+    BuiltinDoc {
+        name: "concat",
+        category: Strings,
+        params: &[p("a", "String", "First string"), p("b", "String", "Second string")],
+        pipe_arg: 0,
+        returns: "String",
+        summary: "`a` followed by `b`. Use `concat_vec` for vectors.",
+        examples: &["concat(\"foo\", \"bar\")  // \"foobar\""],
+    },
+    // ========================================================================
+    // Vectors
+    // ========================================================================
+    BuiltinDoc {
+        name: "push",
+        category: Vectors,
+        params: &[p("v", "Vector", "The vector to modify"), p("value", "Any", "The element to append")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "Appends `value` to `v` in place and returns `v`, so calls can be chained.",
+        examples: &["push([1, 2], 3)  // [1, 2, 3]"],
+    },
+    BuiltinDoc {
+        name: "pop",
+        category: Vectors,
+        params: &[p("v", "Vector", "A non-empty vector")],
+        pipe_arg: 0,
+        returns: "Any",
+        summary: "Removes and returns the last element of `v`.",
+        examples: &["pop([1, 2, 3])  // 3"],
+    },
+    BuiltinDoc {
+        name: "insert",
+        category: Vectors,
+        params: &[
+            p("v", "Vector", "The vector to modify"),
+            p("index", "Number", "Position of the new element (at most `len(v)`)"),
+            p("value", "Any", "The element to insert"),
+        ],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "Inserts `value` at `index` in place and returns `v`.",
+        examples: &["insert([1, 3], 1, 2)  // [1, 2, 3]"],
+    },
+    BuiltinDoc {
+        name: "remove",
+        category: Vectors,
+        params: &[p("v", "Vector", "The vector to modify"), p("index", "Number", "Position of the element")],
+        pipe_arg: 0,
+        returns: "Any",
+        summary: "Removes and returns the element at `index`.",
+        examples: &["remove([1, 2, 3], 0)  // 1"],
+    },
+    BuiltinDoc {
+        name: "slice",
+        category: Vectors,
+        params: &[
+            p("v", "Vector", "The source vector"),
+            p("start", "Number", "First index (inclusive)"),
+            opt("end", "Number", "Last index (exclusive); defaults to the length"),
+        ],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "A new vector with the elements from `start` to `end`; out-of-range \
+                  bounds are clamped.",
+        examples: &["slice([1, 2, 3, 4], 1, 3)  // [2, 3]"],
+    },
+    BuiltinDoc {
+        name: "concat_vec",
+        category: Vectors,
+        params: &[p("a", "Vector", "First vector"), p("b", "Vector", "Second vector")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "A new vector with the elements of `a` followed by those of `b`.",
+        examples: &["concat_vec([1], [2, 3])  // [1, 2, 3]"],
+    },
+    BuiltinDoc {
+        name: "reverse",
+        category: Vectors,
+        params: &[p("v", "Vector", "The vector to reverse")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "Reverses `v` in place and returns it.",
+        examples: &["reverse([1, 2, 3])  // [3, 2, 1]"],
+    },
+    BuiltinDoc {
+        name: "sort",
+        category: Vectors,
+        params: &[p("v", "Vector", "Vector of numbers or of strings")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "Sorts `v` in ascending order in place and returns it.",
+        examples: &["sort([3, 1, 2])  // [1, 2, 3]"],
+    },
+    BuiltinDoc {
+        name: "first",
+        category: Vectors,
+        params: &[p("v", "Vector", "The vector")],
+        pipe_arg: 0,
+        returns: "Any",
+        summary: "The first element of `v`, or `null` if it is empty.",
+        examples: &["first([1, 2, 3])  // 1"],
+    },
+    BuiltinDoc {
+        name: "last",
+        category: Vectors,
+        params: &[p("v", "Vector", "The vector")],
+        pipe_arg: 0,
+        returns: "Any",
+        summary: "The last element of `v`, or `null` if it is empty.",
+        examples: &["last([1, 2, 3])  // 3"],
+    },
+    BuiltinDoc {
+        name: "is_empty",
+        category: Vectors,
+        params: &[p("value", "Vector | String", "The vector or string")],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "Whether `value` has no elements.",
+        examples: &["is_empty([])  // true"],
+    },
+    // ========================================================================
+    // Console I/O
+    // ========================================================================
+    BuiltinDoc {
+        name: "print",
+        category: Io,
+        params: &[rest("values", "Any", "One or more values")],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "Prints the values separated by spaces, followed by a newline.",
+        examples: &["print(\"x =\", 42)"],
+    },
+    BuiltinDoc {
+        name: "println",
+        category: Io,
+        params: &[rest("values", "Any", "Values to print; none prints an empty line")],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "Like `print`, but also accepts no arguments.",
+        examples: &["println()"],
+    },
+    BuiltinDoc {
+        name: "input",
+        category: Io,
+        params: &[opt("prompt", "String", "Text shown before reading")],
+        pipe_arg: 0,
+        returns: "String",
+        summary: "Reads a line from standard input, without the trailing newline.",
+        examples: &["let name = input(\"Name: \")"],
+    },
+    // ========================================================================
+    // Statistics
+    // ========================================================================
+    BuiltinDoc {
+        name: "sum",
+        category: Statistics,
+        params: &[p(
+            "values",
+            "Vector | Tensor<Number> | Generator | Iterator",
+            "Numbers (or complex numbers) to add",
+        )],
+        pipe_arg: 0,
+        returns: "Number | Complex",
+        summary: "Sum of the elements. A generator or iterator is run to the end.",
+        examples: &["sum([1, 2, 3])  // 6"],
+    },
+    BuiltinDoc {
+        name: "mean",
+        category: Statistics,
+        params: &[p("values", NUMERIC, "A non-empty collection of numbers")],
+        pipe_arg: 0,
+        returns: "Number | Complex",
+        summary: "Arithmetic mean of the elements.",
+        examples: &["mean([1, 2, 3])  // 2"],
+    },
+    BuiltinDoc {
+        name: "std",
+        category: Statistics,
+        params: &[p("values", NUMERIC, "At least two numbers")],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Sample standard deviation (divides by `n - 1`).",
+        examples: &["std([1, 2, 3])  // 1"],
+    },
+    // ========================================================================
+    // Linear algebra
+    // ========================================================================
+    BuiltinDoc {
+        name: "dot",
+        category: LinearAlgebra,
+        params: &[p("a", "Vector", "First vector"), p("b", "Vector", "Second vector of the same length")],
+        pipe_arg: 0,
+        returns: "Number | Complex",
+        summary: "Dot product of two vectors.",
+        examples: &["dot([1, 2], [3, 4])  // 11"],
+    },
+    BuiltinDoc {
+        name: "cross",
+        category: LinearAlgebra,
+        params: &[p("a", "Vector", "First 3D vector"), p("b", "Vector", "Second 3D vector")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "Cross product of two 3D vectors.",
+        examples: &["cross([1, 0, 0], [0, 1, 0])  // [0, 0, 1]"],
+    },
+    BuiltinDoc {
+        name: "norm",
+        category: LinearAlgebra,
+        params: &[p("v", "Vector", "Numeric or complex vector")],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Euclidean length of `v`.",
+        examples: &["norm([3, 4])  // 5"],
+    },
+    BuiltinDoc {
+        name: "normalize",
+        category: LinearAlgebra,
+        params: &[p("v", "Vector", "Numeric or complex vector")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "`v` scaled to unit length.",
+        examples: &["normalize([3, 4])  // [0.6, 0.8]"],
+    },
+    BuiltinDoc {
+        name: "transpose",
+        category: LinearAlgebra,
+        params: &[p("m", "Tensor<Number>", "A 2-D tensor")],
+        pipe_arg: 0,
+        returns: "Tensor<Number>",
+        summary: "Transpose of a matrix.",
+        examples: &["transpose(m)"],
+    },
+    BuiltinDoc {
+        name: "reshape",
+        category: LinearAlgebra,
+        params: &[
+            p("t", "Tensor<Number> | Vector", "A tensor or a numeric vector"),
+            p("shape", "Vector", "Size of each dimension"),
+        ],
+        pipe_arg: 0,
+        returns: "Tensor<Number>",
+        summary: "The elements of `t`, in row-major order, as a tensor of the given shape.",
+        examples: &["reshape([1, 2, 3, 4, 5, 6], [2, 3])  // 2×3 matrix"],
+    },
+    BuiltinDoc {
+        name: "det",
+        category: LinearAlgebra,
+        params: &[p("m", "Tensor<Number>", "A square 2-D tensor")],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Determinant of a square matrix.",
+        examples: &["det(m)"],
+    },
+    BuiltinDoc {
+        name: "trace",
+        category: LinearAlgebra,
+        params: &[p("m", "Tensor<Number>", "A square 2-D tensor")],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Sum of the diagonal of a square matrix.",
+        examples: &["trace(m)"],
+    },
+    // ========================================================================
+    // Complex numbers
+    // ========================================================================
+    BuiltinDoc {
+        name: "complex",
+        category: Complex,
+        params: &[p("re", "Number", "Real part"), p("im", "Number", "Imaginary part")],
+        pipe_arg: 0,
+        returns: "Complex",
+        summary: "The complex number `re + im·i`.",
+        examples: &["complex(3, 4)  // 3+4i"],
+    },
+    BuiltinDoc {
+        name: "real",
+        category: Complex,
+        params: &[p("z", "Number | Complex | Vector", "A number or vector of numbers")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Real part of `z`, element-wise for vectors.",
+        examples: &["real(complex(3, 4))  // 3"],
+    },
+    BuiltinDoc {
+        name: "imag",
+        category: Complex,
+        params: &[p("z", "Number | Complex | Vector", "A number or vector of numbers")],
+        pipe_arg: 0,
+        returns: NUM_OR_VEC,
+        summary: "Imaginary part of `z`, element-wise for vectors.",
+        examples: &["imag(complex(3, 4))  // 4"],
+    },
+    BuiltinDoc {
+        name: "conj",
+        category: Complex,
+        params: &[p("z", "Number | Complex | Vector", "A number or vector of numbers")],
+        pipe_arg: 0,
+        returns: "Number | Complex | Vector",
+        summary: "Complex conjugate of `z`, element-wise for vectors.",
+        examples: &["conj(complex(1, 2))  // 1-2i"],
+    },
+    BuiltinDoc {
+        name: "arg",
+        category: Complex,
+        params: &[p("z", "Number | Complex", "A real or complex number")],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Argument (phase angle) of `z`, in radians.",
+        examples: &["arg(complex(0, 1))  // 1.5707..."],
+    },
+    BuiltinDoc {
+        name: "magnitude",
+        category: Complex,
+        params: &[p("z", "Number | Complex", "A real or complex number")],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Magnitude (modulus) of `z`.",
+        examples: &["magnitude(complex(3, 4))  // 5"],
+    },
+    BuiltinDoc {
+        name: "phase",
+        category: Complex,
+        params: &[p("z", "Number | Complex", "A real or complex number")],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Phase of `z` in radians; `0` or `π` for real numbers.",
+        examples: &["phase(-1)  // 3.1415..."],
+    },
+    BuiltinDoc {
+        name: "polar",
+        category: Complex,
+        params: &[p("r", "Number", "Magnitude"), p("theta", "Number", "Phase in radians")],
+        pipe_arg: 0,
+        returns: "Complex",
+        summary: "The complex number with magnitude `r` and phase `theta`.",
+        examples: &["polar(2, 0)  // 2+0i"],
+    },
+    BuiltinDoc {
+        name: "to_polar",
+        category: Complex,
+        params: &[p("z", "Number | Complex", "A real or complex number")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "`[magnitude, phase]` of `z`.",
+        examples: &["to_polar(complex(3, 4))  // [5, 0.9272...]"],
+    },
+    // ========================================================================
+    // Utilities
+    // ========================================================================
+    BuiltinDoc {
+        name: "typeof",
+        category: Utilities,
+        params: &[p("value", "Any", "Any value")],
+        pipe_arg: 0,
+        returns: "String",
+        summary: "Name of the runtime type of `value` (`\"Number\"`, `\"Vector\"`, ...).",
+        examples: &["typeof([1, 2])  // \"Vector\""],
+    },
+    BuiltinDoc {
+        name: "str",
+        category: Utilities,
+        params: &[p("value", "Any", "Any value")],
+        pipe_arg: 0,
+        returns: "String",
+        summary: "`value` formatted as a string.",
+        examples: &["str(42)  // \"42\""],
+    },
+    BuiltinDoc {
+        name: "isnan",
+        category: Utilities,
+        params: &[p("x", NUM_OR_VEC, "Number or vector of numbers")],
+        pipe_arg: 0,
+        returns: "Boolean | Vector",
+        summary: "Whether `x` is NaN, element-wise for vectors.",
+        examples: &["isnan(0 / 0)  // true"],
+    },
+    BuiltinDoc {
+        name: "isinf",
+        category: Utilities,
+        params: &[p("x", NUM_OR_VEC, "Number or vector of numbers")],
+        pipe_arg: 0,
+        returns: "Boolean | Vector",
+        summary: "Whether `x` is positive or negative infinity, element-wise for vectors.",
+        examples: &["isinf(1 / 0)  // true"],
+    },
+    BuiltinDoc {
+        name: "isfinite",
+        category: Utilities,
+        params: &[p("x", NUM_OR_VEC, "Number or vector of numbers")],
+        pipe_arg: 0,
+        returns: "Boolean | Vector",
+        summary: "Whether `x` is neither infinite nor NaN, element-wise for vectors.",
+        examples: &["isfinite(1)  // true"],
+    },
+    BuiltinDoc {
+        name: "describe",
+        category: Utilities,
+        params: &[p("value", "Any", "Any value")],
+        pipe_arg: 0,
+        returns: "String",
+        summary: "A detailed description of `value` including its type and structure, \
+                  for debugging.",
+        examples: &["describe(42)  // \"Number(42)\""],
+    },
+    // ========================================================================
+    // Records
+    // ========================================================================
+    BuiltinDoc {
+        name: "keys",
+        category: Records,
+        params: &[p("record", "Record", "The record")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "Field names of `record`, sorted.",
+        examples: &["keys({b: 1, a: 2})  // [\"a\", \"b\"]"],
+    },
+    BuiltinDoc {
+        name: "values",
+        category: Records,
+        params: &[p("record", "Record", "The record")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "Field values of `record`, in the order of `keys(record)`.",
+        examples: &["values({a: 1})  // [1]"],
+    },
+    BuiltinDoc {
+        name: "has_field",
+        category: Records,
+        params: &[p("record", "Record", "The record"), p("name", "String", "Field name")],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "Whether `record` has a field called `name`.",
+        examples: &["has_field({a: 1}, \"a\")  // true"],
+    },
+    // ========================================================================
+    // Arrays
+    // ========================================================================
+    BuiltinDoc {
+        name: "range",
+        category: Arrays,
+        params: &[
+            p("start", "Number", "First value"),
+            p("end", "Number", "Stop value (exclusive)"),
+            opt("step", "Number", "Increment, may be negative; defaults to 1"),
+        ],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "The numbers from `start` up to (but not including) `end`.",
+        examples: &["range(0, 5)  // [0, 1, 2, 3, 4]", "range(5, 0, -2)  // [5, 3, 1]"],
+    },
+    BuiltinDoc {
+        name: "product",
+        category: Arrays,
+        params: &[p("values", "Vector", "Numbers (or complex numbers) to multiply")],
+        pipe_arg: 0,
+        returns: "Number | Complex",
+        summary: "Product of the elements.",
+        examples: &["product([2, 3, 4])  // 24"],
+    },
+    BuiltinDoc {
+        name: "zip",
+        category: Arrays,
+        params: &[
+            p("a", LAZY_VECTOR, "First vector"),
+            p("b", LAZY_VECTOR, "Second vector"),
+        ],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "Pairs up the elements of `a` and `b`; stops at the shorter one. Lazy when \
+                  either side is a generator or iterator.",
+        examples: &["zip([1, 2], [3, 4])  // [[1, 3], [2, 4]]"],
+    },
+    BuiltinDoc {
+        name: "flatten",
+        category: Arrays,
+        params: &[
+            p("v", "Vector", "Nested vector"),
+            opt("depth", "Number", "Levels to flatten; defaults to 1"),
+        ],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "Flattens nested vectors up to `depth` levels.",
+        examples: &["flatten([[1, 2], [3]])  // [1, 2, 3]", "flatten([[[1]], [[2]]], 2)  // [1, 2]"],
+    },
+    BuiltinDoc {
+        name: "take",
+        category: Arrays,
+        params: &[
+            p("v", LAZY_VECTOR, "The source vector"),
+            p("n", "Number", "Number of elements"),
+        ],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "The first `n` elements of `v`. Lazy for a generator or iterator, so it can \
+                  cut an infinite one short.",
+        examples: &["take([1, 2, 3, 4], 2)  // [1, 2]"],
+    },
+    BuiltinDoc {
+        name: "drop",
+        category: Arrays,
+        params: &[p("v", "Vector", "The source vector"), p("n", "Number", "Number of elements")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "`v` without its first `n` elements.",
+        examples: &["drop([1, 2, 3, 4], 2)  // [3, 4]"],
+    },
+    BuiltinDoc {
+        name: "unique",
+        category: Arrays,
+        params: &[p("v", "Vector", "The source vector")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "`v` without duplicates, keeping the first occurrence of each element.",
+        examples: &["unique([1, 2, 2, 1, 3])  // [1, 2, 3]"],
+    },
+    BuiltinDoc {
+        name: "chunk",
+        category: Arrays,
+        params: &[p("v", "Vector", "The source vector"), p("size", "Number", "Elements per chunk")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "Splits `v` into vectors of `size` elements; the last may be shorter.",
+        examples: &["chunk([1, 2, 3, 4, 5], 2)  // [[1, 2], [3, 4], [5]]"],
+    },
+    // ========================================================================
+    // Higher-order functions
+    // ========================================================================
+    BuiltinDoc {
+        name: "map",
+        category: HigherOrder,
+        params: &[
+            p("callback", "Function", "Called with each element"),
+            p("collection", ITERABLE, "The elements to transform"),
+        ],
+        pipe_arg: 1,
+        returns: "Vector | Tensor<Number>",
+        summary: "Applies `callback` to every element. A tensor stays a tensor when every \
+                  result is a number; a generator or iterator gives a lazy iterator.",
+        examples: &["map(x => x * 2, [1, 2, 3])  // [2, 4, 6]"],
+    },
+    BuiltinDoc {
+        name: "filter",
+        category: HigherOrder,
+        params: &[
+            p("predicate", "Function", "Returns true for elements to keep"),
+            p("collection", ITERABLE, "The elements to test"),
+        ],
+        pipe_arg: 1,
+        returns: "Vector",
+        summary: "The elements for which `predicate` returns true. A generator or iterator \
+                  is filtered lazily.",
+        examples: &["filter(x => x % 2 == 0, [1, 2, 3, 4])  // [2, 4]"],
+    },
+    BuiltinDoc {
+        name: "reduce",
+        category: HigherOrder,
+        params: &[
+            p("callback", "Function", "Called with the accumulator and each element"),
+            p("initial", "Any", "Starting accumulator"),
+            p("collection", ITERABLE, "The elements to fold"),
+        ],
+        pipe_arg: 2,
+        returns: "Any",
+        summary: "Folds the collection into one value, left to right.",
+        examples: &["reduce((acc, x) => acc + x, 0, [1, 2, 3])  // 6"],
+    },
+    BuiltinDoc {
+        name: "pipe",
+        category: HigherOrder,
+        params: &[
+            p("value", "Any", "The initial value"),
+            rest("functions", "Function", "Functions applied left to right"),
+        ],
+        pipe_arg: 0,
+        returns: "Any",
+        summary: "Passes `value` through each function in turn.",
+        examples: &["pipe(5, x => x * 2, x => x + 1)  // 11"],
+    },
+    BuiltinDoc {
+        name: "any",
+        category: HigherOrder,
+        params: &[
+            p("predicate", "Function", "Returns true for a match"),
+            p("collection", ITERABLE, "The elements to test"),
+        ],
+        pipe_arg: 1,
+        returns: "Boolean",
+        summary: "Whether `predicate` holds for some element, stopping at the first match.",
+        examples: &["any(x => x > 2, [1, 2, 3])  // true"],
+    },
+    BuiltinDoc {
+        name: "all",
+        category: HigherOrder,
+        params: &[
+            p("predicate", "Function", "Returns true for a match"),
+            p("collection", ITERABLE, "The elements to test"),
+        ],
+        pipe_arg: 1,
+        returns: "Boolean",
+        summary: "Whether `predicate` holds for every element, stopping at the first failure.",
+        examples: &["all(x => x > 0, [1, 2, 3])  // true"],
+    },
+    BuiltinDoc {
+        name: "find",
+        category: HigherOrder,
+        params: &[
+            p("predicate", "Function", "Returns true for the wanted element"),
+            p("collection", ITERABLE, "The elements to search"),
+        ],
+        pipe_arg: 1,
+        returns: "Any",
+        summary: "The first element matching `predicate`, or `null`.",
+        examples: &["find(x => x > 1, [1, 2, 3])  // 2"],
+    },
+    BuiltinDoc {
+        name: "findIndex",
+        category: HigherOrder,
+        params: &[
+            p("predicate", "Function", "Returns true for the wanted element"),
+            p("collection", ITERABLE, "The elements to search"),
+        ],
+        pipe_arg: 1,
+        returns: "Number | null",
+        summary: "Index of the first element matching `predicate`, or `null`.",
+        examples: &["findIndex(x => x > 1, [1, 2, 3])  // 1"],
+    },
+    BuiltinDoc {
+        name: "count",
+        category: HigherOrder,
+        params: &[
+            p("predicate", "Function", "Returns true for elements to count"),
+            p("collection", ITERABLE, "The elements to test"),
+        ],
+        pipe_arg: 1,
+        returns: "Number",
+        summary: "How many elements match `predicate`.",
+        examples: &["count(x => x > 1, [1, 2, 3])  // 2"],
+    },
+    BuiltinDoc {
+        name: "iter",
+        category: HigherOrder,
+        params: &[p(
+            "collection",
+            ITERABLE,
+            "What to iterate; an iterator is returned as is",
+        )],
+        pipe_arg: 0,
+        returns: "Iterator",
+        summary: "A lazy iterator. Its methods `map`, `filter`, `take_while`, `take`, \
+                  `enumerate`, `zip`, `chain`, `window` and `step_by` build new iterators \
+                  without running anything; `collect`, `for` loops and reductions such as \
+                  `reduce` and `sum` pull the values. A range \
+                  (`iter(0..n)`) is counted through instead of built as a vector.",
+        examples: &[
+            "iter([1, 2, 3]).map(x => x * 10).collect()  // [10, 20, 30]",
+            "collect(take(iter(0..1e12), 3))  // [0, 1, 2]",
+            "iter(\"abc\").enumerate().collect()  // [[0, \"a\"], [1, \"b\"], [2, \"c\"]]",
+        ],
+    },
+    BuiltinDoc {
+        name: "collect",
+        category: HigherOrder,
+        params: &[p("iterator", ITERABLE, "The values to gather")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "Runs an iterator or generator to the end and returns its values.",
+        examples: &["collect(iter([1, 2, 3, 4]).step_by(2))  // [1, 3]"],
+    },
+    // ========================================================================
+    // Parallelism
+    // ========================================================================
+    BuiltinDoc {
+        name: "par_map",
+        category: Parallel,
+        params: &[
+            p("callback", "Function", "A pure function called with each element"),
+            p("collection", COLLECTION, "The elements to transform"),
+        ],
+        pipe_arg: 1,
+        returns: "Vector | Tensor<Number>",
+        summary: "Like `map`, with the callback running on the worker pool.",
+        examples: &["par_map(x => x ^ 2, range(0, 1000))"],
+    },
+    BuiltinDoc {
+        name: "par_reduce",
+        category: Parallel,
+        params: &[
+            p("callback", "Function", "An associative combining function"),
+            p("initial", "Any", "Identity of `callback`"),
+            p("collection", COLLECTION, "The elements to fold"),
+        ],
+        pipe_arg: 2,
+        returns: "Any",
+        summary: "Parallel `reduce`: chunks are folded on separate threads and the partial \
+                  results combined in order.",
+        examples: &["par_reduce((a, b) => a + b, 0, range(0, 1000))"],
+    },
+    BuiltinDoc {
+        name: "par_for",
+        category: Parallel,
+        params: &[
+            p("collection", COLLECTION, "The elements to visit"),
+            p("callback", "Function", "Called with each element"),
+        ],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "Calls `callback` for every element on the worker pool, in no particular \
+                  order; returns once all calls finish.",
+        examples: &["par_for(range(0, 4), i => print(i))"],
+    },
+    BuiltinDoc {
+        name: "set_threads",
+        category: Parallel,
+        params: &[p("n", "Number", "Thread count; 0 restores one per core")],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Resizes the worker pool used by `par_*` and large tensor kernels and returns \
+                  the resulting thread count.",
+        examples: &["set_threads(4)"],
+    },
+    // ========================================================================
+    // Modules
+    // ========================================================================
+    BuiltinDoc {
+        name: "import",
+        category: Modules,
+        params: &[p("path", "String", "Module path, relative (`./util`) or from the working directory")],
+        pipe_arg: 0,
+        returns: "Future",
+        summary: "Loads a `.soc` module and resolves to its exports record. Used by \
+                  `import ... from` statements.",
+        examples: &["import { mean } from \"./stats\""],
+    },
+    // ========================================================================
+    // Numerical analysis
+    // ========================================================================
+    BuiltinDoc {
+        name: "diff",
+        category: Numerical,
+        params: &[
+            p("f", "Function", "Function of one number"),
+            p("x", "Number", "Point of evaluation"),
+            opt("h", "Number", "Step size; defaults to 1e-8"),
+        ],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "First derivative of `f` at `x` by centered differences.",
+        examples: &["diff(x => x ^ 2, 3)  // ≈ 6"],
+    },
+    BuiltinDoc {
+        name: "diff2",
+        category: Numerical,
+        params: &[
+            p("f", "Function", "Function of one number"),
+            p("x", "Number", "Point of evaluation"),
+            opt("h", "Number", "Step size; defaults to 1e-5"),
+        ],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Second derivative of `f` at `x`.",
+        examples: &["diff2(x => x ^ 3, 2)  // ≈ 12"],
+    },
+    BuiltinDoc {
+        name: "diff3",
+        category: Numerical,
+        params: &[
+            p("f", "Function", "Function of one number"),
+            p("x", "Number", "Point of evaluation"),
+            opt("h", "Number", "Step size; defaults to 1e-4"),
+        ],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Third derivative of `f` at `x`.",
+        examples: &["diff3(x => x ^ 4, 2)  // ≈ 48"],
+    },
+    BuiltinDoc {
+        name: "gradient",
+        category: Numerical,
+        params: &[
+            p("f", "Function", "Function from a vector to a number"),
+            p("point", "Vector", "Point of evaluation"),
+            opt("h", "Number", "Step size; defaults to 1e-8"),
+        ],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "Vector of partial derivatives of `f` at `point`.",
+        examples: &["gradient(v => v[0] ^ 2 + v[1] ^ 2, [1, 2])  // ≈ [2, 4]"],
+    },
+    BuiltinDoc {
+        name: "integral",
+        category: Numerical,
+        params: &[
+            p("f", "Function", "Function to integrate"),
+            p("a", "Number", "Lower bound"),
+            p("b", "Number", "Upper bound"),
+            opt("n", "Number", "Subdivisions; defaults to 1000"),
+        ],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Definite integral of `f` over `[a, b]` by the trapezoidal rule.",
+        examples: &["integral(x => x ^ 2, 0, 1)  // ≈ 0.3333"],
+    },
+    BuiltinDoc {
+        name: "simpson",
+        category: Numerical,
+        params: &[
+            p("f", "Function", "Function to integrate"),
+            p("a", "Number", "Lower bound"),
+            p("b", "Number", "Upper bound"),
+            opt("n", "Number", "Subdivisions, rounded up to even; defaults to 1000"),
+        ],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Definite integral of `f` over `[a, b]` by Simpson's 1/3 rule.",
+        examples: &["simpson(x => x ^ 2, 0, 1)  // ≈ 0.3333"],
+    },
+    BuiltinDoc {
+        name: "romberg",
+        category: Numerical,
+        params: &[
+            p("f", "Function", "Function to integrate"),
+            p("a", "Number", "Lower bound"),
+            p("b", "Number", "Upper bound"),
+            opt("max_iter", "Number", "Extrapolation steps; defaults to 10"),
+        ],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Definite integral of `f` over `[a, b]` by Romberg integration.",
+        examples: &["romberg(x => x ^ 2, 0, 1)  // ≈ 0.3333"],
+    },
+    BuiltinDoc {
+        name: "quad",
+        category: Numerical,
+        params: &[
+            p("f", "Function", "Function to integrate"),
+            p("a", "Number", "Lower bound"),
+            p("b", "Number", "Upper bound"),
+            opt("tol", "Number", "Tolerance; defaults to 1e-10"),
+        ],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Definite integral of `f` over `[a, b]` by adaptive Simpson quadrature.",
+        examples: &["quad(x => x ^ 2, 0, 1)  // ≈ 0.3333"],
+    },
+    BuiltinDoc {
+        name: "solve",
+        category: Numerical,
+        params: &[
+            p("f", "Function", "Function whose root is wanted"),
+            p("a", "Number", "Lower end of the bracket"),
+            p("b", "Number", "Upper end; `f(a)` and `f(b)` must differ in sign"),
+            opt("tol", "Number", "Tolerance; defaults to 1e-10"),
+        ],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "A root of `f` in `[a, b]` by bisection.",
+        examples: &["solve(x => x ^ 2 - 4, 0, 3)  // ≈ 2"],
+    },
+    BuiltinDoc {
+        name: "newton",
+        category: Numerical,
+        params: &[
+            p("f", "Function", "Function whose root is wanted"),
+            p("x0", "Number", "Initial guess"),
+            opt("tol", "Number", "Tolerance; defaults to 1e-10"),
+            opt("max_iter", "Number", "Iteration limit; defaults to 100"),
+        ],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "A root of `f` by Newton-Raphson with a numerical derivative.",
+        examples: &["newton(x => x ^ 2 - 4, 1)  // ≈ 2"],
+    },
+    BuiltinDoc {
+        name: "secant",
+        category: Numerical,
+        params: &[
+            p("f", "Function", "Function whose root is wanted"),
+            p("x0", "Number", "First initial guess"),
+            p("x1", "Number", "Second initial guess"),
+            opt("tol", "Number", "Tolerance; defaults to 1e-10"),
+        ],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "A root of `f` by the secant method.",
+        examples: &["secant(x => x ^ 2 - 4, 1, 3)  // ≈ 2"],
+    },
+    // ========================================================================
+    // Async, tasks and files
+    // ========================================================================
+    BuiltinDoc {
+        name: "sleep",
+        category: Async,
+        params: &[p("ms", "Number", "Delay in milliseconds")],
+        pipe_arg: 0,
+        returns: "Future",
+        summary: "A future that resolves to `null` after `ms` milliseconds.",
+        examples: &["await sleep(100)"],
+    },
+    BuiltinDoc {
+        name: "spawn",
+        category: Async,
+        params: &[
+            p("f", "Function", "Function to run concurrently"),
+            rest("args", "Any", "Arguments passed to `f`"),
+        ],
+        pipe_arg: 0,
+        returns: "Task",
+        summary: "Runs `f` as a new task. Inside `task_group` the task joins the group. \
+                  The task can be awaited, `.join()`ed or `.cancel()`led.",
+        examples: &["let t = spawn(x => x * 2, 21)\nawait t  // 42"],
+    },
+    BuiltinDoc {
+        name: "read_file",
+        category: Async,
+        params: &[p("path", "String", "File to read")],
+        pipe_arg: 0,
+        returns: "Future",
+        summary: "Resolves to the contents of a text file.",
+        examples: &["let text = await read_file(\"notes.txt\")"],
+    },
+    BuiltinDoc {
+        name: "write_file",
+        category: Async,
+        params: &[p("path", "String", "File to write"), p("content", "String", "New contents")],
+        pipe_arg: 0,
+        returns: "Future",
+        summary: "Writes `content` to a file, replacing it.",
+        examples: &["await write_file(\"out.txt\", \"hello\")"],
+    },
+    BuiltinDoc {
+        name: "append_file",
+        category: Async,
+        params: &[p("path", "String", "File to append to"), p("content", "String", "Text to append")],
+        pipe_arg: 0,
+        returns: "Future",
+        summary: "Appends `content` to a file, creating it if needed.",
+        examples: &["await append_file(\"log.txt\", \"done\\n\")"],
+    },
+    BuiltinDoc {
+        name: "delete_file",
+        category: Async,
+        params: &[p("path", "String", "File to delete")],
+        pipe_arg: 0,
+        returns: "Future",
+        summary: "Deletes a file.",
+        examples: &["await delete_file(\"out.txt\")"],
+    },
+    BuiltinDoc {
+        name: "exists",
+        category: Async,
+        params: &[p("path", "String", "Path to check")],
+        pipe_arg: 0,
+        returns: "Future",
+        summary: "Resolves to whether the path exists.",
+        examples: &["if (await exists(\"config.json\")) { print(\"found\") }"],
+    },
+    BuiltinDoc {
+        name: "read_bytes",
+        category: Async,
+        params: &[p("path", "String", "File to read")],
+        pipe_arg: 0,
+        returns: "Future",
+        summary: "Resolves to the contents of a file as `Bytes`.",
+        examples: &["let data = await read_bytes(\"dump.bin\")"],
+    },
+    BuiltinDoc {
+        name: "write_bytes",
+        category: Async,
+        params: &[
+            p("path", "String", "File to write"),
+            p("data", "Bytes | String | Vector", "Anything `bytes()` accepts"),
+        ],
+        pipe_arg: 0,
+        returns: "Future",
+        summary: "Writes binary data to a file, replacing it.",
+        examples: &["await write_bytes(\"out.bin\", bytes([1, 2, 3]))"],
+    },
+    BuiltinDoc {
+        name: "all_tasks",
+        category: Async,
+        params: &[p("awaitables", "Vector", "Tasks and futures")],
+        pipe_arg: 0,
+        returns: "Future",
+        summary: "Resolves to every result, in order. The first error wins and the tasks still \
+                  running are cancelled.",
+        examples: &["await all_tasks([spawn(fetch_a), spawn(fetch_b)])"],
+    },
+    BuiltinDoc {
+        name: "any_task",
+        category: Async,
+        params: &[p("awaitables", "Vector", "Tasks and futures")],
+        pipe_arg: 0,
+        returns: "Future",
+        summary: "Resolves to the first successful result and cancels the rest; an \
+                  `AggregateError` if every one fails.",
+        examples: &["await any_task([spawn(fetch_a), spawn(fetch_b)])"],
+    },
+    BuiltinDoc {
+        name: "race",
+        category: Async,
+        params: &[p("awaitables", "Vector", "Tasks and futures")],
+        pipe_arg: 0,
+        returns: "Future",
+        summary: "Resolves to whichever finishes first, value or error; the rest are cancelled.",
+        examples: &["await race([spawn(fetch_a), spawn(fetch_b)])"],
+    },
+    BuiltinDoc {
+        name: "timeout",
+        category: Async,
+        params: &[
+            p("ms", "Number", "Time limit in milliseconds"),
+            p("awaitable", "Task | Future", "The work to wait for"),
+        ],
+        pipe_arg: 0,
+        returns: "Future",
+        summary: "Resolves to the result, or to a `TimeoutError` after `ms` milliseconds \
+                  (cancelling a task).",
+        examples: &["await timeout(1000, spawn(slow_job))"],
+    },
+    BuiltinDoc {
+        name: "task_group",
+        category: Async,
+        params: &[p("f", "Function", "Scope whose spawned tasks join the group")],
+        pipe_arg: 0,
+        returns: "Task",
+        summary: "Runs `f` and waits for every task it spawns. If any fails, the others are \
+                  cancelled and the group resolves to that error.",
+        examples: &["await task_group(() => do {\n    spawn(job_a)\n    spawn(job_b)\n})"],
+    },
+    // ========================================================================
+    // JSON and CSV
+    // ========================================================================
+    BuiltinDoc {
+        name: "json_parse",
+        category: Encoding,
+        params: &[
+            p("text", "String", "JSON text"),
+            opt("options", "Record", "`{revive: true}` restores tagged tensors and complex numbers, including their special floats"),
+        ],
+        pipe_arg: 0,
+        returns: "Any",
+        summary: "Parses JSON into records, vectors, strings, numbers, booleans and `null`.",
+        examples: &["json_parse(\"[1, 2]\")  // [1, 2]"],
+    },
+    BuiltinDoc {
+        name: "json_stringify",
+        category: Encoding,
+        params: &[
+            p("value", "Any", "The value to serialize"),
+            opt(
+                "options",
+                "Boolean | Record",
+                "`pretty`, or a record with `pretty`, `indent`, `tensors` and `special_floats`",
+            ),
+        ],
+        pipe_arg: 0,
+        returns: "String",
+        summary: "Serializes a value as JSON.",
+        examples: &["json_stringify({a: [true, null]})  // \"{\\\"a\\\":[true,null]}\""],
+    },
+    BuiltinDoc {
+        name: "csv_parse",
+        category: Encoding,
+        params: &[
+            p("text", "String", "CSV text"),
+            opt("has_headers", "Boolean", "Whether the first row names the columns; defaults to true"),
+        ],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "Parses CSV text into a vector of records (with headers) or of rows.",
+        examples: &["csv_parse(\"a,b\\n1,2\")  // [{a: 1, b: 2}]"],
+    },
+    BuiltinDoc {
+        name: "read_csv",
+        category: Encoding,
+        params: &[
+            p("path", "String", "CSV file"),
+            opt("options", "Record", "`delimiter`, `header`, `skip` and `types`"),
+        ],
+        pipe_arg: 0,
+        returns: "DataFrame",
+        summary: "Reads a CSV file into a DataFrame, streaming it and inferring numeric columns.",
+        examples: &["let df = read_csv(\"data.csv\", {delimiter: \";\"})"],
+    },
+    BuiltinDoc {
+        name: "write_csv",
+        category: Encoding,
+        params: &[
+            p("path", "String", "Destination file"),
+            p("data", "Vector | Tensor<Number> | DataFrame", "Records, rows, a 1-D or 2-D tensor, or a frame"),
+            opt("options", "Record", "`delimiter` and `header`"),
+        ],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "Writes tabular data to a CSV file.",
+        examples: &["write_csv(\"out.csv\", [{x: 1, y: 2}])"],
+    },
+    BuiltinDoc {
+        name: "csv_rows",
+        category: Encoding,
+        params: &[
+            p("path", "String", "CSV file"),
+            opt("options", "Record", "Same options as `read_csv`"),
+        ],
+        pipe_arg: 0,
+        returns: "Generator",
+        summary: "Lazily yields the rows of a CSV file, one per iteration.",
+        examples: &["for (row in csv_rows(\"big.csv\")) { print(row) }"],
+    },
+    // ========================================================================
+    // Binary data
+    // ========================================================================
+    BuiltinDoc {
+        name: "bytes",
+        category: Binary,
+        params: &[p("value", "String | Vector | Tensor<Number> | Bytes", "UTF-8 text or integers in 0..=255")],
+        pipe_arg: 0,
+        returns: "Bytes",
+        summary: "A byte buffer. `Bytes` values have `len`, `slice`, `to_vector`, `to_string` \
+                  and `unpack` methods.",
+        examples: &["bytes(\"hi\").to_vector()  // [104, 105]"],
+    },
+    BuiltinDoc {
+        name: "pack",
+        category: Binary,
+        params: &[
+            p("format", "String", "Layout such as `\"<f32, i16[3]\"` or `\"temp:f32\"`"),
+            p("values", "Vector | Record", "A vector for unnamed layouts, a record for named ones"),
+        ],
+        pipe_arg: 0,
+        returns: "Bytes",
+        summary: "Encodes values with a fixed binary layout.",
+        examples: &["pack(\"<u16, f32\", [1, 2.5])"],
+    },
+    BuiltinDoc {
+        name: "unpack",
+        category: Binary,
+        params: &[
+            p("format", "String", "The layout"),
+            p("data", "Bytes", "Buffer to decode"),
+            opt("offset", "Number", "Byte offset to start at; defaults to 0"),
+        ],
+        pipe_arg: 0,
+        returns: "Vector | Record",
+        summary: "Decodes one record of a fixed binary layout. Array items become tensors.",
+        examples: &["unpack(\"<u16, f32\", data)"],
+    },
+    BuiltinDoc {
+        name: "pack_size",
+        category: Binary,
+        params: &[p("format", "String", "The layout")],
+        pipe_arg: 0,
+        returns: "Number",
+        summary: "Size in bytes of one record of the layout.",
+        examples: &["pack_size(\"f32, i16[3]\")  // 10"],
+    },
+    BuiltinDoc {
+        name: "save_npy",
+        category: Binary,
+        params: &[
+            p("path", "String", "Destination `.npy` file"),
+            p("array", "Tensor<Number> | Tensor<Complex> | Vector", "The array to save"),
+            opt("dtype", "String", "NumPy type string such as `\"f4\"` or `\"<i2\"`"),
+        ],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "Writes an array in NumPy's `.npy` format (`<f8`, or `<c16` for complex data, \
+                  unless `dtype` says otherwise).",
+        examples: &["save_npy(\"x.npy\", data)"],
+    },
+    BuiltinDoc {
+        name: "load_npy",
+        category: Binary,
+        params: &[
+            p("path", "String", "A `.npy` file"),
+            opt("with_dtype", "Boolean", "Return `{array, dtype}` with the stored dtype"),
+        ],
+        pipe_arg: 0,
+        returns: "Tensor<Number> | Tensor<Complex> | Record",
+        summary: "Reads a NumPy array of any numeric or boolean dtype. Pass the returned \
+                  `dtype` to `save_npy` to write the array back in its original type.",
+        examples: &[
+            "let x = load_npy(\"x.npy\")",
+            "let m = load_npy(\"mask.npy\", true)  // {array, dtype: \"|u1\"}",
+        ],
+    },
+    BuiltinDoc {
+        name: "save_npz",
+        category: Binary,
+        params: &[
+            p("path", "String", "Destination `.npz` file"),
+            p("arrays", "Record", "Arrays (or `{array, dtype}` records) by name"),
+            opt("compressed", "Boolean", "Deflate the entries, like `numpy.savez_compressed`"),
+        ],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "Writes several named arrays to a NumPy `.npz` archive. An `{array, dtype}` \
+                  entry is stored with that dtype.",
+        examples: &["save_npz(\"data.npz\", {x: xs, y: ys}, true)"],
+    },
+    BuiltinDoc {
+        name: "load_npz",
+        category: Binary,
+        params: &[
+            p("path", "String", "A `.npz` file"),
+            opt("with_dtype", "Boolean", "Return each entry as `{array, dtype}`"),
+        ],
+        pipe_arg: 0,
+        returns: "Record",
+        summary: "Reads a NumPy `.npz` archive into a record of arrays. Pass the record read \
+                  with `with_dtype` to `save_npz` to write the arrays back in their original types.",
+        examples: &[
+            "let arrays = load_npz(\"data.npz\")",
+            "save_npz(\"copy.npz\", load_npz(\"data.npz\", true))",
+        ],
+    },
+    // ========================================================================
+    // Environment persistence
+    // ========================================================================
+    BuiltinDoc {
+        name: "save_env",
+        category: Persistence,
+        params: &[
+            p("path", "String", "Destination `.ach` archive"),
+            opt(
+                "options",
+                "Record",
+                "`include_only`, `exclude`, `description`, `tags` and `allow_overwrite`",
+            ),
+        ],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "Saves the script's top-level variables and the global bindings to an `.ach` \
+                  archive. Functions and runtime handles are skipped with a warning.",
+        examples: &["save_env(\"session.ach\", {exclude: [\"tmp_*\"]})"],
+    },
+    BuiltinDoc {
+        name: "restore_env",
+        category: Persistence,
+        params: &[
+            p("path", "String", "An `.ach` archive"),
+            opt("options", "Record", "`mode` (`\"merge\"`, `\"replace\"`, `\"namespace\"`), `overwrite`, filters and checks"),
+        ],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "Loads an `.ach` archive into the global bindings.",
+        examples: &["restore_env(\"session.ach\", {mode: \"replace\"})"],
+    },
+    BuiltinDoc {
+        name: "env_info",
+        category: Persistence,
+        params: &[p("path", "String", "An `.ach` archive")],
+        pipe_arg: 0,
+        returns: "Record",
+        summary: "Metadata and binding names of an archive, without restoring it.",
+        examples: &["env_info(\"session.ach\").bindings"],
+    },
+    BuiltinDoc {
+        name: "env_bindings",
+        category: Persistence,
+        params: &[],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "Sorted names of the global bindings.",
+        examples: &["env_bindings()"],
+    },
+    BuiltinDoc {
+        name: "get_global",
+        category: Persistence,
+        params: &[p("name", "String", "Global name")],
+        pipe_arg: 0,
+        returns: "Any",
+        summary: "The value of a global binding.",
+        examples: &["get_global(\"x\")"],
+    },
+    BuiltinDoc {
+        name: "set_global",
+        category: Persistence,
+        params: &[p("name", "String", "Global name"), p("value", "Any", "New value")],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "Creates or replaces a global binding.",
+        examples: &["set_global(\"x\", 42)"],
+    },
+    // ========================================================================
+    // DataFrames
+    // ========================================================================
+    BuiltinDoc {
+        name: "dataframe",
+        category: DataFrame,
+        params: &[p("data", "Record | Vector | DataFrame", "A record of columns or a vector of records")],
+        pipe_arg: 0,
+        returns: "DataFrame",
+        summary: "Builds a DataFrame. Frames have `select`, `filter`, `group_by`, `agg`, \
+                  `join`, `sort_by`, `head`, `describe` and conversion methods.",
+        examples: &["let df = dataframe({x: [1, 2], y: [\"a\", \"b\"]})"],
+    },
+    BuiltinDoc {
+        name: "df_from_csv",
+        category: DataFrame,
+        params: &[
+            p("text", "String", "CSV text"),
+            opt("has_headers", "Boolean", "Whether the first row names the columns; defaults to true"),
+        ],
+        pipe_arg: 0,
+        returns: "DataFrame",
+        summary: "Parses CSV text into a DataFrame; numeric columns become tensors.",
+        examples: &["df_from_csv(\"x,y\\n1,2\")"],
+    },
+    BuiltinDoc {
+        name: "df_from_json",
+        category: DataFrame,
+        params: &[p("text", "String", "An array of objects or an object of arrays")],
+        pipe_arg: 0,
+        returns: "DataFrame",
+        summary: "Parses JSON text into a DataFrame.",
+        examples: &["df_from_json(\"[{\\\"x\\\": 1}]\")"],
+    },
+    // ========================================================================
+    // Network
+    // ========================================================================
+    BuiltinDoc {
+        name: "http_get",
+        category: Network,
+        params: &[p("url", "String", "The URL to fetch")],
+        pipe_arg: 0,
+        returns: "Future",
+        summary: "Performs an HTTP GET request and resolves to the response body.",
+        examples: &["let body = await http_get(\"https://example.com\")"],
+    },
+    BuiltinDoc {
+        name: "http_post",
+        category: Network,
+        params: &[
+            p("url", "String", "The URL to post to"),
+            p("body", "String", "Request body"),
+            opt("headers", "Record", "Extra request headers"),
+        ],
+        pipe_arg: 0,
+        returns: "Future",
+        summary: "Performs an HTTP POST request and resolves to the response body.",
+        examples: &["await http_post(url, json_stringify(data), {Authorization: token})"],
+    },
+    // ========================================================================
+    // Environment variables
+    // ========================================================================
+    BuiltinDoc {
+        name: "env_get",
+        category: Environment,
+        params: &[p("key", "String", "Variable name")],
+        pipe_arg: 0,
+        returns: "String | null",
+        summary: "The value of an environment variable, or `null` if it is unset.",
+        examples: &["env_get(\"HOME\")"],
+    },
+    BuiltinDoc {
+        name: "env_set",
+        category: Environment,
+        params: &[p("key", "String", "Variable name"), p("value", "String", "New value")],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "Sets an environment variable for this process.",
+        examples: &["env_set(\"MODE\", \"debug\")"],
+    },
+    BuiltinDoc {
+        name: "env_vars",
+        category: Environment,
+        params: &[],
+        pipe_arg: 0,
+        returns: "Record",
+        summary: "Every environment variable as a record.",
+        examples: &["keys(env_vars())"],
+    },
+    BuiltinDoc {
+        name: "env_load",
+        category: Environment,
+        params: &[opt("path", "String", "File to read; defaults to `.env`")],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "Loads `KEY=value` lines into the environment; false if the file was not found.",
+        examples: &["env_load()"],
+    },
+    // ========================================================================
+    // Concurrency
+    // ========================================================================
+    BuiltinDoc {
+        name: "channel",
+        category: Concurrency,
+        params: &[opt("capacity", "Number", "Bound with back-pressure; unbounded if omitted")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "A `[sender, receiver]` pair. `tx.send(v)` and `rx.recv()` return futures; \
+                  `recv` resolves to `null` once the channel is closed and drained.",
+        examples: &["let [tx, rx] = channel(10)"],
+    },
+    BuiltinDoc {
+        name: "broadcast",
+        category: Concurrency,
+        params: &[p("capacity", "Number", "Messages kept for slow receivers")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "A `[sender, receiver]` pair where every receiver gets every message; \
+                  `tx.subscribe()` adds receivers.",
+        examples: &["let [tx, rx] = broadcast(16)"],
+    },
+    BuiltinDoc {
+        name: "watch",
+        category: Concurrency,
+        params: &[p("initial", "Any", "Starting value")],
+        pipe_arg: 0,
+        returns: "Vector",
+        summary: "A `[sender, receiver]` pair holding one latest value; `rx.get()` reads it \
+                  and `rx.recv()` waits for a change.",
+        examples: &["let [tx, rx] = watch(0)"],
+    },
+    BuiltinDoc {
+        name: "select",
+        category: Concurrency,
+        params: &[
+            p("cases", "Vector", "Receivers, futures and tasks"),
+            opt("default", "Any", "Value to resolve to immediately if nothing is ready"),
+        ],
+        pipe_arg: 0,
+        returns: "Future",
+        summary: "Resolves to `{index, value}` for whichever case is ready first; only the \
+                  winning receiver gives up a message.",
+        examples: &["let r = await select([rx_a, rx_b])"],
+    },
+    BuiltinDoc {
+        name: "AsyncMutex",
+        category: Concurrency,
+        params: &[p("initial", "Any", "The protected value")],
+        pipe_arg: 0,
+        returns: "AsyncMutex",
+        summary: "A mutex for tasks: `await m.lock()` yields a guard with `get()` and `set(v)`.",
+        examples: &["let m = AsyncMutex(0)"],
+    },
+    // ========================================================================
+    // Reactivity
+    // ========================================================================
+    BuiltinDoc {
+        name: "signal",
+        category: Reactive,
+        params: &[opt("initial", "Any", "Starting value; defaults to null")],
+        pipe_arg: 0,
+        returns: "Signal",
+        summary: "A reactive value. Reading `.value` inside an effect or computed subscribes \
+                  to it; `.set(v)` notifies subscribers and `.peek()` reads without tracking.",
+        examples: &["let count = signal(0)\ncount.set(count.value + 1)"],
+    },
+    BuiltinDoc {
+        name: "effect",
+        category: Reactive,
+        params: &[p("callback", "Function", "Side effect to run")],
+        pipe_arg: 0,
+        returns: "Effect",
+        summary: "Runs `callback` now and again whenever a signal it read changes; \
+                  `.dispose()` stops it.",
+        examples: &["effect(() => print(count.value))"],
+    },
+    BuiltinDoc {
+        name: "computed",
+        category: Reactive,
+        params: &[p("f", "Function", "Derives a value from other signals")],
+        pipe_arg: 0,
+        returns: "Signal",
+        summary: "A read-only signal recomputed lazily when its dependencies change.",
+        examples: &["let double = computed(() => count.value * 2)"],
+    },
+    BuiltinDoc {
+        name: "batch",
+        category: Reactive,
+        params: &[p("f", "Function", "Code that sets several signals")],
+        pipe_arg: 0,
+        returns: "Any",
+        summary: "Runs `f` and defers effects until it returns, so each runs at most once.",
+        examples: &["batch(() => do {\n    a.set(1)\n    b.set(2)\n})"],
+    },
+    BuiltinDoc {
+        name: "untrack",
+        category: Reactive,
+        params: &[p("f", "Function", "Code whose reads are not tracked")],
+        pipe_arg: 0,
+        returns: "Any",
+        summary: "Runs `f` without subscribing the current effect or computed to the signals \
+                  it reads.",
+        examples: &["untrack(() => other.value)"],
+    },
+    // ========================================================================
+    // GUI
+    // ========================================================================
+    BuiltinDoc {
+        name: "gui_run",
+        category: Gui,
+        params: &[
+            p("render", "Function", "Draws the UI; called whenever it needs a new frame"),
+            opt(
+                "options",
+                "Record",
+                "`width`, `height`, `title`, `continuous`, `halt_on_error` and `on_error`",
+            ),
+        ],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "Opens a window and runs the GUI event loop until it is closed.",
+        examples: &["gui_run(() => ui_label(\"Hello\", null), {title: \"Demo\"})"],
+    },
+    BuiltinDoc {
+        name: "gui_test",
+        category: Gui,
+        params: &[
+            p("render", "Function", "The render function under test"),
+            p("actions", "Vector", "Clicks, inputs, frames and checks to apply in order"),
+            opt("options", "Record", "`width` and `height`; defaults to 800x600"),
+        ],
+        pipe_arg: 0,
+        returns: "String",
+        summary: "Runs a render function headlessly and returns a text snapshot of the \
+                  widgets drawn in the final frame.",
+        examples: &["gui_test(app, [{click: \"Save\"}])"],
+    },
+    BuiltinDoc {
+        name: "ui_state",
+        category: Gui,
+        params: &[p("key", "String", "Identifies the state slot"), p("initial", "Any", "Starting value")],
+        pipe_arg: 0,
+        returns: "Signal",
+        summary: "Component-local state: the same key returns the same signal on every frame \
+                  for as long as it keeps being drawn.",
+        examples: &["let open = ui_state(\"menu_open\", false)"],
+    },
+    BuiltinDoc {
+        name: "ui_label",
+        category: Gui,
+        params: &[p("text", "String", "Text to show"), p("style", STYLE, "Utility classes")],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "Displays text.",
+        examples: &["ui_label(\"Hello\", \"text-2xl font-bold\")"],
+    },
+    BuiltinDoc {
+        name: "ui_button",
+        category: Gui,
+        params: &[p("text", "String", "Button caption"), p("style", STYLE, "Utility classes")],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "A button; true in the frame it is clicked.",
+        examples: &["if (ui_button(\"Save\", \"\")) { save() }"],
+    },
+    BuiltinDoc {
+        name: "ui_box",
+        category: Gui,
+        params: &[
+            p("style", "String | Record", "Utility classes, or a record with `style` and `children`"),
+            p("children", "Function | null", "Draws the contents"),
+        ],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "A layout container.",
+        examples: &["ui_box(\"flex-row gap-4\", () => do {\n    ui_label(\"A\", null)\n    ui_label(\"B\", null)\n})"],
+    },
+    BuiltinDoc {
+        name: "ui_plot",
+        category: Gui,
+        params: &[p("title", "String", "Plot title"), p("options", "Record", "Series, axes and sizing")],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "Draws a scientific plot of line, scatter and other series.",
+        examples: &["ui_plot(\"Signal\", {series: [{data: points, kind: \"line\"}]})"],
+    },
+    BuiltinDoc {
+        name: "plot_save",
+        category: Gui,
+        params: &[p("path", "String", "`.svg` or `.png` file"), p("options", "Record", "Same options as `ui_plot`")],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "Renders a plot to an image file without opening a window.",
+        examples: &["plot_save(\"plot.svg\", {series: [{data: points}]})"],
+    },
+    BuiltinDoc {
+        name: "ui_text_input",
+        category: Gui,
+        params: &[p("value", "Signal", "String signal holding the text"), p("style", STYLE, "Utility classes")],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "A single-line text field bound to a signal; true when edited.",
+        examples: &["ui_text_input(name, \"w-full\")"],
+    },
+    BuiltinDoc {
+        name: "ui_slider",
+        category: Gui,
+        params: &[
+            p("value", "Signal", "Number signal"),
+            p("min", "Number", "Lowest value"),
+            p("max", "Number", "Highest value"),
+            p("style", STYLE, "Utility classes"),
+        ],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "A numeric slider bound to a signal; true when moved.",
+        examples: &["ui_slider(volume, 0, 100, \"w-full\")"],
+    },
+    BuiltinDoc {
+        name: "ui_checkbox",
+        category: Gui,
+        params: &[
+            p("checked", "Signal", "Boolean signal"),
+            p("label", "String", "Text next to the box"),
+            p("style", STYLE, "Utility classes"),
+        ],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "A checkbox bound to a signal; true when toggled.",
+        examples: &["ui_checkbox(enabled, \"Enable\", \"\")"],
+    },
+    BuiltinDoc {
+        name: "ui_combobox",
+        category: Gui,
+        params: &[
+            p("selected", "Signal", "String signal holding the choice"),
+            p("options", "Vector", "The choices"),
+            p("style", STYLE, "Utility classes"),
+        ],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "A drop-down list bound to a signal; true when the choice changes.",
+        examples: &["ui_combobox(unit, [\"m\", \"km\"], \"w-[200px]\")"],
+    },
+    BuiltinDoc {
+        name: "ui_radio",
+        category: Gui,
+        params: &[
+            p("selected", "Signal", "Signal holding the current choice"),
+            p("value", "Any", "Value this option stands for"),
+            p("label", "String", "Text next to the button"),
+            p("style", STYLE, "Utility classes"),
+        ],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "A radio option that sets `selected` to `value` when clicked.",
+        examples: &["ui_radio(mode, \"fast\", \"Fast\", \"\")"],
+    },
+    BuiltinDoc {
+        name: "ui_tabs",
+        category: Gui,
+        params: &[
+            p("titles", "Vector", "Tab captions"),
+            p("active", "Signal", "Index of the open tab"),
+            p("content", "Function", "Called with the open tab's index"),
+            p("style", STYLE, "Utility classes"),
+        ],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "A tab bar with the content of the open tab below it.",
+        examples: &["ui_tabs([\"General\", \"Logs\"], tab, i => ui_label(str(i), null), \"\")"],
+    },
+    BuiltinDoc {
+        name: "ui_collapsing",
+        category: Gui,
+        params: &[
+            p("title", "String", "Header text"),
+            p("children", "Function", "Draws the contents"),
+            p("style", STYLE, "Utility classes"),
+        ],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "A section that expands and collapses when its header is clicked.",
+        examples: &["ui_collapsing(\"Advanced\", () => ui_label(\"...\", null), \"\")"],
+    },
+    BuiltinDoc {
+        name: "ui_scroll_area",
+        category: Gui,
+        params: &[p("children", "Function", "Draws the contents"), p("style", STYLE, "Utility classes")],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "A scrollable region.",
+        examples: &["ui_scroll_area(() => ui_label(log, null), \"h-[200px]\")"],
+    },
+    BuiltinDoc {
+        name: "ui_progress_bar",
+        category: Gui,
+        params: &[p("progress", "Number", "Fraction done, from 0 to 1"), p("style", STYLE, "Utility classes")],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "A progress indicator.",
+        examples: &["ui_progress_bar(0.75, \"w-full\")"],
+    },
+    BuiltinDoc {
+        name: "ui_separator",
+        category: Gui,
+        params: &[p("style", STYLE, "Utility classes")],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "A horizontal divider.",
+        examples: &["ui_separator(\"my-2\")"],
+    },
+    BuiltinDoc {
+        name: "ui_quit",
+        category: Gui,
+        params: &[],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "Closes the application window.",
+        examples: &["if (ui_button(\"Exit\", \"\")) { ui_quit() }"],
+    },
+    BuiltinDoc {
+        name: "ui_table",
+        category: Gui,
+        params: &[
+            p("data", "DataFrame | Vector", "A frame or a vector of records"),
+            p("columns", "Vector | null", "Column names to show, or null for all"),
+            p("style", STYLE, "Utility classes; `h-[..]` sets the height"),
+        ],
+        pipe_arg: 0,
+        returns: "Number | null",
+        summary: "A sortable, virtualized table; returns the index of the clicked row.",
+        examples: &["let row = ui_table(people, [\"name\", \"age\"], \"h-[240px]\")"],
+    },
+    BuiltinDoc {
+        name: "ui_code_editor",
+        category: Gui,
+        params: &[p("source", "Signal", "String signal holding the code"), p("style", STYLE, "Utility classes")],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "A multiline code editor bound to a signal; true when edited.",
+        examples: &["ui_code_editor(source, \"h-[200px]\")"],
+    },
+    BuiltinDoc {
+        name: "ui_image",
+        category: Gui,
+        params: &[
+            p("source", "String | Tensor<Number> | Vector", "PNG, JPEG, WebP, GIF or SVG path, or values in 0..1 shaped [h, w], [h, w, 3] or [h, w, 4]"),
+            p("style", STYLE, "Utility classes"),
+        ],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "Shows a PNG, JPEG, WebP, GIF or SVG file or an image stored in a tensor.",
+        examples: &["ui_image(\"assets/logo.png\", \"w-[120px]\")"],
+    },
+    BuiltinDoc {
+        name: "ui_menu_bar",
+        category: Gui,
+        params: &[p("children", "Function", "Draws the menus"), p("style", STYLE, "Utility classes")],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "A menu bar holding `ui_menu`s.",
+        examples: &["ui_menu_bar(() => ui_menu(\"File\", file_menu, \"\"), \"\")"],
+    },
+    BuiltinDoc {
+        name: "ui_menu",
+        category: Gui,
+        params: &[
+            p("title", "String", "Menu caption"),
+            p("children", "Function", "Draws the items"),
+            p("style", STYLE, "Utility classes"),
+        ],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "A drop-down menu; menus can be nested.",
+        examples: &["ui_menu(\"File\", () => ui_menu_item(\"Open\", \"\"), \"\")"],
+    },
+    BuiltinDoc {
+        name: "ui_menu_item",
+        category: Gui,
+        params: &[p("text", "String", "Item caption"), p("style", STYLE, "Utility classes")],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "A menu entry; true when clicked, which also closes the menu.",
+        examples: &["if (ui_menu_item(\"Quit\", \"\")) { ui_quit() }"],
+    },
+    BuiltinDoc {
+        name: "ui_modal",
+        category: Gui,
+        params: &[
+            p("open", "Signal", "Boolean signal; the dialog shows while it is true"),
+            p("title", "String", "Dialog title"),
+            p("children", "Function", "Draws the contents"),
+            p("style", STYLE, "Utility classes"),
+        ],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "A modal dialog. Escape or a click outside sets `open` to false and \
+                  returns true.",
+        examples: &["ui_modal(confirm, \"Delete file?\", body, \"w-[320px]\")"],
+    },
+    BuiltinDoc {
+        name: "ui_file_picker",
+        category: Gui,
+        params: &[p("path", "Signal", "String signal holding the path"), p("style", STYLE, "Utility classes")],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "A button opening a file browser; true when a file is picked.",
+        examples: &["ui_file_picker(path, \"\")"],
+    },
+    BuiltinDoc {
+        name: "ui_color_picker",
+        category: Gui,
+        params: &[p("color", "Signal", "String signal holding a hex color"), p("style", STYLE, "Utility classes")],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "A swatch opening a color picker; true when the color changes.",
+        examples: &["ui_color_picker(color, \"\")"],
+    },
+    BuiltinDoc {
+        name: "ui_date_picker",
+        category: Gui,
+        params: &[p("date", "Signal", "String signal holding a `YYYY-MM-DD` date"), p("style", STYLE, "Utility classes")],
+        pipe_arg: 0,
+        returns: "Boolean",
+        summary: "A button opening a calendar; true when a date is picked.",
+        examples: &["ui_date_picker(day, \"\")"],
+    },
+    BuiltinDoc {
+        name: "ui_toast",
+        category: Gui,
+        params: &[
+            p("message", "String", "Notification text"),
+            p("kind", "String | null", "`\"info\"`, `\"success\"`, `\"warning\"` or `\"error\"`"),
+        ],
+        pipe_arg: 0,
+        returns: "null",
+        summary: "Shows a notification in the top-right corner for a few seconds.",
+        examples: &["ui_toast(\"Saved\", \"success\")"],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_and_arg_counts() {
+        let slice = lookup("slice").unwrap();
+        assert_eq!(
+            slice.signature(),
+            "slice(v: Vector, start: Number, end?: Number) -> Vector"
+        );
+        assert_eq!((slice.min_args(), slice.max_args()), (2, Some(3)));
+
+        let pipe = lookup("pipe").unwrap();
+        assert_eq!(
+            pipe.signature(),
+            "pipe(value: Any, ...functions: Function) -> Any"
+        );
+        assert_eq!((pipe.min_args(), pipe.max_args()), (1, None));
+
+        assert!(lookup("nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_markdown_lists_parameters_and_examples() {
+        let text = lookup("atan2").unwrap().markdown();
+        assert!(text.starts_with("```achronyme\natan2(y: Number, x: Number) -> Number\n```"));
+        assert!(text.contains("- `y: Number`: Y coordinate"));
+        assert!(text.contains("atan2(1, -1)"));
+    }
+}
@@ -0,0 +1,65 @@
+//! Static metadata about the language's builtins
+//!
+//! The VM and the editor tooling both need to know which builtins exist and
+//! how they are called. This crate holds that knowledge as plain data with
+//! no dependencies, so the LSP can use it without linking the VM runtime.
+//!
+//! - [`docs`]: signature and documentation of every builtin function
+//! - [`methods`]: methods of the primitive types (`"abc".upper()`, `z.re`)
+//! - [`is_constant`]: the predefined constants (`PI`, `i`, `NaN`, ...)
+//! - [`pipe_slot`]: the argument a `|>` pipeline passes its value as
+
+pub mod docs;
+pub mod methods;
+
+/// Where `value |> f(args)` passes `value` when no argument is `_`
+///
+/// The value is the first argument, except for builtins whose
+/// [`docs::BuiltinDoc::pipe_arg`] names another one (`map`, `filter`,
+/// `reduce`, ...): `xs |> reduce(f, 0)` is `reduce(f, 0, xs)`. `builtin` is
+/// the name of the called builtin, `None` for any other function, and
+/// `written` the number of arguments in the call.
+pub fn pipe_slot(builtin: Option<&str>, written: usize) -> usize {
+    builtin
+        .and_then(docs::lookup)
+        .map_or(0, |doc| doc.pipe_arg)
+        .min(written)
+}
+
+/// Check if a name is a predefined constant
+pub fn is_constant(name: &str) -> bool {
+    matches!(
+        name,
+        "PI" | "E" | "PHI" | "SQRT2" | "SQRT3" | "LN2" | "LN10" | "i" | "Infinity" | "NaN"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipe_slot_follows_pipe_arg() {
+        // xs |> map(f) is map(f, xs), xs |> reduce(f, 0) is reduce(f, 0, xs)
+        assert_eq!(pipe_slot(Some("map"), 1), 1);
+        assert_eq!(pipe_slot(Some("filter"), 1), 1);
+        assert_eq!(pipe_slot(Some("reduce"), 2), 2);
+        // x |> round(2) is round(x, 2)
+        assert_eq!(pipe_slot(Some("round"), 1), 0);
+        // User functions and short calls
+        assert_eq!(pipe_slot(None, 2), 0);
+        assert_eq!(pipe_slot(Some("reduce"), 1), 1);
+    }
+
+    #[test]
+    fn test_pipe_arg_is_a_parameter() {
+        for doc in docs::BUILTIN_DOCS {
+            assert!(
+                doc.pipe_arg == 0 || doc.pipe_arg < doc.params.len(),
+                "{}: pipe_arg {} out of range",
+                doc.name,
+                doc.pipe_arg
+            );
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Methods of the primitive types
+//!
+//! The VM dispatches these through its intrinsic registry; a VM test checks
+//! that the registry and these lists agree.
+
+/// A primitive type that has methods
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Receiver {
+    Number,
+    Boolean,
+    Complex,
+    String,
+    Record,
+}
+
+impl Receiver {
+    /// Every receiver type
+    pub const ALL: [Receiver; 5] = [
+        Receiver::Number,
+        Receiver::Boolean,
+        Receiver::Complex,
+        Receiver::String,
+        Receiver::Record,
+    ];
+
+    /// Names of the type's methods
+    pub fn methods(self) -> &'static [&'static str] {
+        match self {
+            Receiver::Number => &["abs", "ceil", "floor", "round", "sqrt", "str"],
+            Receiver::Boolean => &[],
+            Receiver::Complex => &["re", "im", "conj", "abs", "mag", "arg", "phase"],
+            Receiver::String => &[
+                "len",
+                "trim",
+                "upper",
+                "lower",
+                "split",
+                "replace",
+                "contains",
+                "starts_with",
+                "ends_with",
+            ],
+            Receiver::Record => &["keys", "values", "has"],
+        }
+    }
+
+    /// Whether the type has a method called `name`
+    pub fn has_method(self, name: &str) -> bool {
+        self.methods().contains(&name)
+    }
+}
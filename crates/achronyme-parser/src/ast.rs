@@ -7,6 +7,7 @@ pub enum BinaryOp {
     Divide,
     Power,
     Modulo,
+    MatMul, // @
 
     // Comparison
     Gt,  // >
@@ -19,6 +20,9 @@ pub enum BinaryOp {
     // Logical
     And, // &&
     Or,  // ||
+
+    // Pipeline
+    Pipe, // |>
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -171,6 +175,12 @@ pub enum AstNode {
         name: String,
         type_definition: TypeAnnotation,
     },
+    // Algebraic data type: type Name = Variant { fields } | Variant | ...
+    // Declares the type and one constructor per variant
+    DataType {
+        name: String,
+        variants: Vec<VariantDecl>,
+    },
     // Yield statement: yield expr
     // Suspends generator execution and returns the value
     Yield {
@@ -243,6 +253,11 @@ pub enum AstNode {
     Await {
         future: Box<AstNode>,
     },
+    /// Placeholder for a statement that failed to parse, produced only by
+    /// `parse_recovering` so tools can keep working on broken source
+    Error {
+        message: String,
+    },
 }
 
 /// Represents a part of an interpolated string
@@ -280,6 +295,21 @@ pub enum IndexArg {
     },
 }
 
+/// One variant of an algebraic data type: Circle { r: Number }
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantDecl {
+    pub name: String,
+    pub fields: Vec<(String, TypeAnnotation)>, // In declaration order (constructor argument order)
+}
+
+impl AstNode {
+    /// Whether this call argument is the `_` placeholder of a pipeline,
+    /// which `value |> f(..., _)` replaces with `value`
+    pub fn is_pipe_placeholder(&self) -> bool {
+        matches!(self, AstNode::VariableRef(name) if name == "_")
+    }
+}
+
 /// Represents an import item - can be a simple identifier or an aliased import
 /// Examples: foo, foo as bar
 #[derive(Debug, Clone, PartialEq)]
@@ -320,6 +350,12 @@ pub enum Pattern {
 
     /// Type pattern: matches by runtime type (Number, String, Error)
     Type(String),
+
+    /// Variant pattern: matches an ADT variant and destructures its fields (Circle { r })
+    Variant {
+        name: String,
+        fields: Vec<(String, Pattern, Option<Box<AstNode>>)>, // Same shape as Record fields
+    },
 }
 
 /// Literal pattern variants
@@ -1,11 +1,15 @@
 pub mod ast;
+pub mod modules;
 pub mod parser;
 pub mod pest_parser;
+pub mod recovery;
 pub mod type_annotation;
 
 // Re-export commonly used items
 pub use ast::{
-    ArrayElement, AstNode, MatchArm, Pattern, RecordFieldOrSpread, StringPart, VectorPatternElement,
+    ArrayElement, AstNode, MatchArm, Pattern, RecordFieldOrSpread, StringPart, VariantDecl,
+    VectorPatternElement,
 };
-pub use pest_parser::parse;
-pub use type_annotation::TypeAnnotation;
+pub use pest_parser::{parse, parse_type};
+pub use recovery::{parse_recovering, RecoveredParse, SyntaxError};
+pub use type_annotation::{Dimension, TypeAnnotation};
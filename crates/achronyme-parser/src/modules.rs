@@ -0,0 +1,52 @@
+//! Module path resolution shared by the VM's `import` and the tooling
+
+use std::path::{Path, PathBuf};
+
+/// Map an `import ... from "path"` string to a file path.
+///
+/// The `.soc` extension is added when missing. Paths starting with `./` or
+/// `../` are relative to the importing module's directory (when known);
+/// other paths are returned as written, i.e. relative to the working
+/// directory.
+pub fn resolve_module_path(module_path: &str, importer: Option<&Path>) -> PathBuf {
+    let file_path = if module_path.ends_with(".soc") {
+        module_path.to_string()
+    } else {
+        format!("{}.soc", module_path)
+    };
+
+    if is_relative_module(&file_path) {
+        if let Some(parent) = importer.and_then(Path::parent) {
+            return parent.join(file_path);
+        }
+    }
+    PathBuf::from(file_path)
+}
+
+/// Whether a module path is relative to the importing module
+pub fn is_relative_module(module_path: &str) -> bool {
+    module_path.starts_with("./") || module_path.starts_with("../")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_module_path() {
+        let importer = Path::new("src/app/main.soc");
+        assert_eq!(
+            resolve_module_path("./util", Some(importer)),
+            Path::new("src/app/./util.soc")
+        );
+        assert_eq!(
+            resolve_module_path("../lib/math.soc", Some(importer)),
+            Path::new("src/app/../lib/math.soc")
+        );
+        assert_eq!(
+            resolve_module_path("stats", Some(importer)),
+            Path::new("stats.soc")
+        );
+        assert_eq!(resolve_module_path("./util", None), Path::new("./util.soc"));
+    }
+}
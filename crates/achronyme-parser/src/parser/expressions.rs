@@ -4,13 +4,14 @@ use crate::pest_parser::Rule;
 use pest::iterators::Pair;
 
 impl AstParser {
-    pub(super) fn build_ast_from_expr(&mut self, pair: Pair<Rule>) -> Result<AstNode, String> {
+    pub(crate) fn build_ast_from_expr(&mut self, pair: Pair<Rule>) -> Result<AstNode, String> {
         let rule = pair.as_rule();
         match rule {
             Rule::expr => {
                 let inner = pair.into_inner().next().ok_or("Empty expression")?;
                 self.build_ast_from_expr(inner)
             }
+            Rule::pipeline => self.build_binary_op(pair),
             Rule::standalone_range => self.build_standalone_range(pair),
             Rule::logical_or => self.build_binary_op(pair),
             Rule::logical_and => self.build_binary_op(pair),
@@ -126,7 +127,11 @@ impl AstParser {
         for p in pairs.iter() {
             // Check if this is an operator rule
             match p.as_rule() {
-                Rule::add_op | Rule::mult_op | Rule::logical_and_op | Rule::logical_or_op => {
+                Rule::add_op
+                | Rule::mult_op
+                | Rule::logical_and_op
+                | Rule::logical_or_op
+                | Rule::pipe_op => {
                     let s = p.as_str();
                     let op = match s {
                         "+" => BinaryOp::Add,
@@ -134,8 +139,10 @@ impl AstParser {
                         "*" => BinaryOp::Multiply,
                         "/" => BinaryOp::Divide,
                         "%" => BinaryOp::Modulo,
+                        "@" => BinaryOp::MatMul,
                         "&&" => BinaryOp::And,
                         "||" => BinaryOp::Or,
+                        "|>" => BinaryOp::Pipe,
                         _ => unreachable!(),
                     };
                     operators.push(op);
@@ -1,6 +1,7 @@
 use crate::ast::AstNode;
 use crate::pest_parser::Rule;
 use pest::iterators::Pair;
+use std::ops::Range;
 
 pub mod collections;
 pub mod control_flow;
@@ -41,6 +42,34 @@ impl AstParser {
         Ok(statements)
     }
 
+    /// Build each top-level statement separately, keeping its byte range
+    pub(crate) fn parse_program_statements(
+        &mut self,
+        pair: Pair<Rule>,
+    ) -> Vec<(Range<usize>, Result<AstNode, String>)> {
+        let mut statements = Vec::new();
+        let statement_pairs = pair
+            .into_inner()
+            .flat_map(|top| match top.as_rule() {
+                Rule::top_level_expr => top.into_inner().collect(),
+                Rule::statement => vec![top],
+                _ => vec![],
+            })
+            .flat_map(|inner| match inner.as_rule() {
+                Rule::sequence => inner.into_inner().collect(),
+                _ => vec![inner],
+            })
+            .filter(|p| p.as_rule() == Rule::statement);
+        for statement in statement_pairs {
+            let span = statement.as_span();
+            statements.push((
+                span.start()..span.end(),
+                self.build_ast_from_statement(statement),
+            ));
+        }
+        statements
+    }
+
     // Build AST from top_level_expr (either sequence or statement)
     fn build_ast_from_top_level_expr(&mut self, pair: Pair<Rule>) -> Result<AstNode, String> {
         let inner = pair.into_inner().next().ok_or("Empty top_level_expr")?;
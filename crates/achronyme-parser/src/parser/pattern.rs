@@ -77,6 +77,7 @@ impl AstParser {
             Rule::wildcard_pattern => Ok(Pattern::Wildcard),
             Rule::variable_pattern => self.build_variable_pattern(inner),
             Rule::type_pattern => Ok(Pattern::Type(inner.as_str().to_string())),
+            Rule::variant_pattern => self.build_variant_pattern(inner),
             Rule::record_pattern => self.build_record_pattern(inner),
             Rule::vector_pattern => self.build_vector_pattern(inner),
             _ => Err(format!("Unexpected pattern rule: {:?}", inner.as_rule())),
@@ -125,8 +126,32 @@ impl AstParser {
         Ok(Pattern::Variable(ident.as_str().to_string()))
     }
 
+    /// Build a variant pattern: Circle { r } or Rect { w: width, h }
+    fn build_variant_pattern(&mut self, pair: Pair<Rule>) -> Result<Pattern, String> {
+        let mut inner = pair.into_inner();
+        let name = inner
+            .next()
+            .ok_or("Missing variant name in pattern")?
+            .as_str()
+            .to_string();
+        let record = inner.next().ok_or("Missing fields in variant pattern")?;
+        let fields = self.build_record_pattern_fields(record)?;
+
+        Ok(Pattern::Variant { name, fields })
+    }
+
     /// Build a record pattern: { field1: pattern1, field2: pattern2 }
     pub(super) fn build_record_pattern(&mut self, pair: Pair<Rule>) -> Result<Pattern, String> {
+        let fields = self.build_record_pattern_fields(pair)?;
+        Ok(Pattern::Record { fields })
+    }
+
+    /// Build the fields of a record pattern
+    #[allow(clippy::type_complexity)]
+    fn build_record_pattern_fields(
+        &mut self,
+        pair: Pair<Rule>,
+    ) -> Result<Vec<(String, Pattern, Option<Box<AstNode>>)>, String> {
         let mut fields = Vec::new();
 
         for field_pair in pair.into_inner() {
@@ -136,7 +161,7 @@ impl AstParser {
             }
         }
 
-        Ok(Pattern::Record { fields })
+        Ok(fields)
     }
 
     /// Build a single record pattern field: name: pattern = default OR name = default OR name (shorthand)
@@ -1,4 +1,4 @@
-use crate::ast::{AstNode, CompoundOp, ImportItem};
+use crate::ast::{AstNode, CompoundOp, ImportItem, VariantDecl};
 use crate::parser::AstParser;
 use crate::pest_parser::Rule;
 use pest::iterators::Pair;
@@ -12,6 +12,7 @@ impl AstParser {
             Rule::export_statement => self.build_export_statement(inner),
             Rule::let_statement => self.build_let_statement(inner),
             Rule::mut_statement => self.build_mut_statement(inner),
+            Rule::adt_statement => self.build_adt_statement(inner),
             Rule::type_alias_statement => self.build_type_alias_statement(inner),
             Rule::return_statement => self.build_return_statement(inner),
             Rule::yield_statement => self.build_yield_statement(inner),
@@ -349,6 +350,72 @@ impl AstParser {
         })
     }
 
+    pub(super) fn build_adt_statement(&mut self, pair: Pair<Rule>) -> Result<AstNode, String> {
+        let mut inner = pair.into_inner();
+
+        // Grammar: "type" ~ identifier ~ "=" ~ adt_variant ~ ("|" ~ adt_variant)*
+        let name = inner
+            .next()
+            .ok_or("Missing identifier in type declaration")?
+            .as_str()
+            .to_string();
+
+        let mut variants: Vec<VariantDecl> = Vec::new();
+        for variant_pair in inner {
+            let mut variant_inner = variant_pair.into_inner();
+            let variant_name = variant_inner
+                .next()
+                .ok_or("Missing variant name in type declaration")?
+                .as_str()
+                .to_string();
+            if variants.iter().any(|v| v.name == variant_name) {
+                return Err(format!(
+                    "Duplicate variant '{}' in type '{}'",
+                    variant_name, name
+                ));
+            }
+
+            // Fields keep their declaration order: it is the constructor's argument order
+            let mut fields = Vec::new();
+            let field_pairs = variant_inner
+                .next()
+                .into_iter()
+                .flat_map(|r| r.into_inner());
+            for field_pair in field_pairs {
+                let mut field_inner = field_pair.into_inner();
+                let field_name = field_inner.next().ok_or("Empty variant field")?;
+                if field_name.as_rule() == Rule::mut_keyword {
+                    return Err(format!(
+                        "Fields of variant '{}' cannot be mutable",
+                        variant_name
+                    ));
+                }
+                let type_pair = field_inner.next().ok_or("Missing type in variant field")?;
+                if type_pair.as_rule() == Rule::optional_marker {
+                    return Err(format!(
+                        "Fields of variant '{}' cannot be optional",
+                        variant_name
+                    ));
+                }
+                let field_name = field_name.as_str().to_string();
+                if fields.iter().any(|(existing, _)| *existing == field_name) {
+                    return Err(format!(
+                        "Duplicate field '{}' in variant '{}'",
+                        field_name, variant_name
+                    ));
+                }
+                fields.push((field_name, self.parse_type_annotation(type_pair)?));
+            }
+
+            variants.push(VariantDecl {
+                name: variant_name,
+                fields,
+            });
+        }
+
+        Ok(AstNode::DataType { name, variants })
+    }
+
     pub(super) fn build_yield_statement(&mut self, pair: Pair<Rule>) -> Result<AstNode, String> {
         let mut inner = pair.into_inner();
 
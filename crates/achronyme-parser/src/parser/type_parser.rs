@@ -4,7 +4,7 @@
 // Supports:
 // - Simple types: Number, Boolean, String, Complex, Edge
 // - Union types: Number | String | null
-// - Tensor types: Tensor<Number>, Tensor<Complex, [2,3]>
+// - Tensor types: Tensor<Number>, Tensor<Complex, [2,3]>, Tensor<n, k>
 // - Record types: {name: String, mut age: Number}
 // - Function types: (Number, Number) => Number
 // - Any and null types
@@ -12,10 +12,25 @@
 use crate::ast::AstNode;
 use crate::parser::AstParser;
 use crate::pest_parser::Rule;
-use crate::type_annotation::TypeAnnotation;
+use crate::type_annotation::{Dimension, TypeAnnotation};
 use pest::iterators::Pair;
 use std::collections::HashMap;
 
+/// Type names that can't be shape variables
+const BUILTIN_TYPE_NAMES: &[&str] = &[
+    "Number",
+    "Boolean",
+    "String",
+    "Complex",
+    "Generator",
+    "Function",
+    "Error",
+    "Vector",
+    "Tensor",
+    "Edge",
+    "Any",
+];
+
 // Type alias for parameter parsing result
 type ParsedParam = (String, Option<TypeAnnotation>, Option<Box<AstNode>>);
 type ParsedParams = Vec<ParsedParam>;
@@ -23,7 +38,7 @@ type ParsedParams = Vec<ParsedParam>;
 impl AstParser {
     /// Parse a type annotation from a Pest pair
     /// Entry point for all type annotation parsing
-    pub(super) fn parse_type_annotation(
+    pub(crate) fn parse_type_annotation(
         &mut self,
         pair: Pair<Rule>,
     ) -> Result<TypeAnnotation, String> {
@@ -148,13 +163,23 @@ impl AstParser {
         Ok(TypeAnnotation::Union(types))
     }
 
-    /// Parse tensor types: Tensor<Number> or Tensor<Complex, [2,3]>
+    /// Parse tensor types: Tensor<Number>, Tensor<Complex, [2,3]> or Tensor<n, k>
     fn parse_tensor_type(&mut self, pair: Pair<Rule>) -> Result<TypeAnnotation, String> {
         let mut inner = pair.into_inner();
 
-        // First element is the element type
-        let element_type = inner.next().ok_or("Missing element type in Tensor")?;
-        let element_type = Box::new(self.parse_type_annotation(element_type)?);
+        // First element is the element type, or the dimensions of a tensor of numbers
+        let first = inner.next().ok_or("Missing element type in Tensor")?;
+        if first.as_rule() == Rule::tensor_dimensions {
+            let shape = first
+                .into_inner()
+                .map(|dim_pair| self.parse_dimension(dim_pair))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(TypeAnnotation::Tensor {
+                element_type: Box::new(TypeAnnotation::Number),
+                shape: Some(shape),
+            });
+        }
+        let element_type = Box::new(self.parse_type_annotation(first)?);
 
         // Optional shape specification
         let shape = if let Some(shape_pair) = inner.next() {
@@ -169,8 +194,8 @@ impl AstParser {
         })
     }
 
-    /// Parse shape specification: [2, 3] or [_, _]
-    fn parse_shape_spec(&mut self, pair: Pair<Rule>) -> Result<Vec<Option<usize>>, String> {
+    /// Parse shape specification: [2, 3], [_, _] or [n, k]
+    fn parse_shape_spec(&mut self, pair: Pair<Rule>) -> Result<Vec<Dimension>, String> {
         let mut dims = Vec::new();
 
         for dim_pair in pair.into_inner() {
@@ -181,18 +206,25 @@ impl AstParser {
         Ok(dims)
     }
 
-    /// Parse a single dimension: number or _
-    fn parse_dimension(&mut self, pair: Pair<Rule>) -> Result<Option<usize>, String> {
+    /// Parse a single dimension: number, _ or a shape variable
+    fn parse_dimension(&mut self, pair: Pair<Rule>) -> Result<Dimension, String> {
         let dim_str = pair.as_str();
 
         if dim_str == "_" {
-            Ok(None) // Unknown dimension
-        } else {
-            // Parse as number
+            Ok(Dimension::Unknown)
+        } else if dim_str.starts_with(|c: char| c.is_ascii_digit()) {
             dim_str
                 .parse::<usize>()
-                .map(Some)
+                .map(Dimension::Fixed)
                 .map_err(|e| format!("Invalid dimension '{}': {}", dim_str, e))
+        } else if BUILTIN_TYPE_NAMES.contains(&dim_str) {
+            // `Tensor<Complex, n>` is missing the brackets around the shape
+            Err(format!(
+                "Expected a dimension, found the type '{}'; write the shape in brackets: Tensor<{}, [...]>",
+                dim_str, dim_str
+            ))
+        } else {
+            Ok(Dimension::Var(dim_str.to_string()))
         }
     }
 
@@ -476,7 +508,7 @@ mod tests {
                 shape,
             } => {
                 assert_eq!(*element_type, TypeAnnotation::Number);
-                assert_eq!(shape, Some(vec![Some(2), Some(3)]));
+                assert_eq!(shape, Some(vec![Dimension::Fixed(2), Dimension::Fixed(3)]));
             }
             _ => panic!("Expected Tensor type, got {:?}", ty),
         }
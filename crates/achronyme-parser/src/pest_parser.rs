@@ -7,11 +7,13 @@
 // The grammar is defined in grammar.pest.
 // ============================================================================
 
+use pest::iterators::Pair;
 use pest::Parser;
 use pest_derive::Parser;
 
 use crate::ast::AstNode;
 use crate::parser::AstParser;
+use crate::type_annotation::TypeAnnotation;
 
 // ============================================================================
 // Parser Definition
@@ -36,6 +38,54 @@ pub fn parse(input: &str) -> Result<Vec<AstNode>, String> {
     AstParser::new().parse_program(program_pair)
 }
 
+/// Parse a type annotation on its own, e.g. `Number | null` or `(Vector): Number`
+pub fn parse_type(input: &str) -> Result<TypeAnnotation, String> {
+    let input = input.trim();
+    let pair = SOCParser::parse(Rule::type_annotation, input)
+        .map_err(|e| format!("Parse error: {}", e))?
+        .next()
+        .ok_or("Empty type annotation")?;
+    if pair.as_str().trim_end() != input {
+        return Err(format!("Unexpected input after type: {}", input));
+    }
+    type_from_pair(pair)
+}
+
+/// Convert a `type_annotation` pair of an already parsed program
+pub fn type_from_pair(pair: Pair<Rule>) -> Result<TypeAnnotation, String> {
+    AstParser::new().parse_type_annotation(pair)
+}
+
+/// Whether a call argument pair is the `_` placeholder of a pipeline, the
+/// pest counterpart of [`AstNode::is_pipe_placeholder`]
+pub fn is_pipe_placeholder(argument: &Pair<Rule>) -> bool {
+    // Only `_`, possibly parenthesized, can be one; skip building the rest
+    let text = argument.as_str();
+    text.trim_matches(|c: char| c == '(' || c == ')' || c.is_whitespace()) == "_"
+        && AstParser::new()
+            .build_ast_from_expr(argument.clone())
+            .is_ok_and(|node| node.is_pipe_placeholder())
+}
+
+/// The name pairs of a `type_annotation` pair that is a union of type names
+/// only, the pest counterpart of [`TypeAnnotation::reference_union`]
+pub fn reference_union(annotation: Pair<Rule>) -> Option<Vec<Pair<Rule>>> {
+    let union = annotation.into_inner().next()?;
+    if union.as_rule() != Rule::union_type {
+        return None;
+    }
+    union
+        .into_inner()
+        .map(|member| {
+            let reference = member.into_inner().next()?;
+            if reference.as_rule() != Rule::type_reference {
+                return None;
+            }
+            reference.into_inner().next()
+        })
+        .collect()
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -85,6 +135,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_matmul() {
+        // Same precedence as `*`: (a @ b) * c
+        let result = parse("a @ b * c").unwrap();
+        match &result[0] {
+            AstNode::BinaryOp { op, left, .. } => {
+                assert!(matches!(op, BinaryOp::Multiply));
+                assert!(matches!(
+                    **left,
+                    AstNode::BinaryOp {
+                        op: BinaryOp::MatMul,
+                        ..
+                    }
+                ));
+            }
+            _ => panic!("Expected binary op at top level"),
+        }
+    }
+
     #[test]
     fn test_parse_power() {
         let result = parse("2^3^4").unwrap();
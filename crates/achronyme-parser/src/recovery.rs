@@ -0,0 +1,427 @@
+//! Error-tolerant parsing for editors and other tools
+//!
+//! A statement that fails to parse is blanked out (its characters replaced
+//! by spaces, newlines kept, so byte offsets don't move) and the source is
+//! parsed again. Every syntax error is reported, and the statements around
+//! them still produce AST nodes. The compiler keeps using the strict
+//! [`crate::parse`].
+//!
+//! Statements are found from the layout of the text: a line starts a new
+//! statement when no bracket is open and it doesn't continue the previous
+//! line (a trailing operator, `=` or `,`, a leading `|` or `|>`, or a stray
+//! closing bracket). A line at column 0
+//! starting with `let`, `mut`, `type`, `import` or `export` always starts
+//! one, so an unclosed bracket doesn't swallow the rest of the file. An error
+//! inside a `{ ... }` block blanks just its line when that is enough to make
+//! the enclosing statement parse.
+
+use std::ops::Range;
+
+use pest::error::{Error, InputLocation};
+use pest::Parser;
+
+use crate::ast::AstNode;
+use crate::parser::AstParser;
+use crate::pest_parser::{Rule, SOCParser};
+
+/// Give up (and blank everything left) after this many errors
+const MAX_ERRORS: usize = 64;
+
+/// Keywords that start a statement when written at column 0
+const STATEMENT_KEYWORDS: [&str; 5] = ["let", "mut", "type", "import", "export"];
+
+/// A syntax error found by [`parse_recovering`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxError {
+    pub message: String,
+    /// Byte range in the source
+    pub span: Range<usize>,
+}
+
+/// The result of [`parse_recovering`]
+#[derive(Debug, Clone, Default)]
+pub struct RecoveredParse {
+    /// The program, shaped like the output of [`crate::parse`], with an
+    /// [`AstNode::Error`] in place of each statement that failed
+    pub nodes: Vec<AstNode>,
+    /// Syntax errors in source order
+    pub errors: Vec<SyntaxError>,
+    /// The source with the failed statements blanked out; it parses without
+    /// errors and every byte offset matches the original
+    pub masked: String,
+}
+
+/// Parse a program, recovering from syntax errors statement by statement
+pub fn parse_recovering(source: &str) -> RecoveredParse {
+    let (masked, mut errors) = mask_errors(source);
+
+    let mut statements: Vec<(usize, AstNode)> = errors
+        .iter()
+        .map(|e| {
+            (
+                e.span.start,
+                AstNode::Error {
+                    message: e.message.clone(),
+                },
+            )
+        })
+        .collect();
+    if let Some(program) = SOCParser::parse(Rule::program, &masked)
+        .ok()
+        .and_then(|mut pairs| pairs.next())
+    {
+        for (span, result) in AstParser::new().parse_program_statements(program) {
+            match result {
+                Ok(node) => statements.push((span.start, node)),
+                Err(message) => {
+                    statements.push((
+                        span.start,
+                        AstNode::Error {
+                            message: message.clone(),
+                        },
+                    ));
+                    errors.push(SyntaxError { message, span });
+                }
+            }
+        }
+    }
+    statements.sort_by_key(|(start, _)| *start);
+    errors.sort_by_key(|e| e.span.start);
+
+    let mut statements: Vec<AstNode> = statements.into_iter().map(|(_, node)| node).collect();
+    let nodes = if statements.len() > 1 {
+        vec![AstNode::Sequence { statements }]
+    } else {
+        statements.pop().into_iter().collect()
+    };
+    RecoveredParse {
+        nodes,
+        errors,
+        masked,
+    }
+}
+
+/// Blank out failing statements until the source parses, collecting errors
+pub fn mask_errors(source: &str) -> (String, Vec<SyntaxError>) {
+    let mut masked = source.to_string();
+    let mut errors = Vec::new();
+    while let Err(error) = SOCParser::parse(Rule::program, &masked) {
+        let (range, error) = culprit(&masked, error);
+        errors.push(error);
+        if errors.len() == MAX_ERRORS || masked[range.clone()].trim().is_empty() {
+            let len = masked.len();
+            blank(&mut masked, 0..len);
+            break;
+        }
+        blank(&mut masked, range);
+    }
+    (masked, errors)
+}
+
+/// The range to blank for a failed parse of `text`, and the error to report
+fn culprit(text: &str, error: Error<Rule>) -> (Range<usize>, SyntaxError) {
+    let position = error_position(&error);
+    let statements = statement_ranges(text);
+    let containing = |offset: usize| statements.iter().rposition(|r| r.start <= offset);
+
+    // The statement the parser stopped in, then the ones before the last
+    // token it accepted: an unclosed bracket fails further down the file
+    let stopped = containing(position);
+    let mut candidates: Vec<usize> = stopped.into_iter().collect();
+    if let Some(i) = containing(anchor_before(text, position)) {
+        candidates.extend((0..=i).rev().filter(|&j| Some(j) != stopped));
+    }
+
+    for &i in &candidates {
+        let range = statements[i].clone();
+        let statement = &text[range.clone()];
+        let Err(local) = SOCParser::parse(Rule::program, statement) else {
+            continue;
+        };
+        let local_position = range.start + error_position(&local);
+        let error = syntax_error(text, local_position, &local);
+
+        // Inside a block, blanking just the broken line may be enough
+        let line =
+            inner_line(text, anchor_before(text, local_position), range.clone()).filter(|line| {
+                let mut fixed = statement.to_string();
+                blank(&mut fixed, line.start - range.start..line.end - range.start);
+                SOCParser::parse(Rule::program, &fixed).is_ok()
+            });
+        return (line.unwrap_or(range), error);
+    }
+
+    let range = match candidates.first() {
+        Some(&i) => statements[i].clone(),
+        None => 0..text.len(),
+    };
+    (range, syntax_error(text, position, &error))
+}
+
+fn error_position(error: &Error<Rule>) -> usize {
+    match error.location {
+        InputLocation::Pos(pos) => pos,
+        InputLocation::Span((start, _)) => start,
+    }
+}
+
+fn syntax_error(text: &str, position: usize, error: &Error<Rule>) -> SyntaxError {
+    // A statement cut short at the end of a line is reported at its last
+    // token rather than on the next line
+    let anchor = anchor_before(text, position);
+    let position = if text[anchor..position].contains('\n') {
+        anchor
+    } else {
+        position
+    };
+    let renamed = error.clone().renamed_rules(|rule| match rule {
+        Rule::EOI => "end of input".to_string(),
+        Rule::expr | Rule::unary | Rule::power | Rule::primary => "expression".to_string(),
+        Rule::destructuring_pattern => "pattern".to_string(),
+        Rule::record_field_or_spread => "record field".to_string(),
+        Rule::pipe_op => "|>".to_string(),
+        other => format!("{:?}", other).replace('_', " "),
+    });
+    let width = text[position..].chars().next().map_or(0, char::len_utf8);
+    SyntaxError {
+        message: renamed.variant.message().to_string(),
+        span: position..position + width,
+    }
+}
+
+/// The last non-blank character before `position`, or `position` itself
+fn anchor_before(text: &str, position: usize) -> usize {
+    text[..position]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !c.is_whitespace())
+        .map_or(position, |(i, _)| i)
+}
+
+/// The line holding `offset` when it is a statement inside a `{ ... }` block
+/// of the statement `within`, without its indentation
+fn inner_line(text: &str, offset: usize, within: Range<usize>) -> Option<Range<usize>> {
+    let line_start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+    if line_start <= within.start {
+        return None;
+    }
+    let line_end = text[offset..]
+        .find('\n')
+        .map_or(text.len(), |i| offset + i)
+        .min(within.end);
+
+    let mut open = Vec::new();
+    for (_, c) in code_chars(&text[within.start..line_start]) {
+        match c {
+            '(' | '[' | '{' => open.push(c),
+            ')' | ']' | '}' => {
+                open.pop();
+            }
+            _ => {}
+        }
+    }
+    if open.last() != Some(&'{') {
+        return None;
+    }
+    let indent = text[line_start..line_end].len() - text[line_start..line_end].trim_start().len();
+    Some(line_start + indent..line_end)
+}
+
+/// Byte ranges of the top-level statements of `text`, including the blank
+/// lines that follow each one
+fn statement_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut starts = Vec::new();
+    let mut open = Vec::new();
+    let mut line_start = true;
+    let mut previous: Option<char> = None;
+
+    for (offset, c) in code_chars(text) {
+        if c == '\n' {
+            line_start = true;
+            continue;
+        }
+        if c.is_whitespace() {
+            continue;
+        }
+        if line_start {
+            line_start = false;
+            let keyword = (offset == 0 || text.as_bytes()[offset - 1] == b'\n')
+                && starts_with_keyword(&text[offset..]);
+            if keyword {
+                open.clear();
+            }
+            if keyword || (open.is_empty() && !continues(previous, c)) {
+                starts.push(offset);
+            }
+        } else if previous == Some(';') && open.is_empty() {
+            starts.push(offset);
+        }
+        match c {
+            '(' | '[' | '{' => open.push(c),
+            ')' | ']' | '}' => {
+                open.pop();
+            }
+            _ => {}
+        }
+        previous = Some(c);
+    }
+
+    let ends = starts.iter().skip(1).copied().chain([text.len()]);
+    starts.iter().zip(ends).map(|(&s, e)| s..e).collect()
+}
+
+/// Whether a line starting with `next` continues the line ending in `previous`
+fn continues(previous: Option<char>, next: char) -> bool {
+    previous.is_some_and(|p| "+-*/^%=,&|.<>:?".contains(p)) || ")]}|".contains(next)
+}
+
+fn starts_with_keyword(text: &str) -> bool {
+    STATEMENT_KEYWORDS.iter().any(|keyword| {
+        text.strip_prefix(keyword)
+            .is_some_and(|rest| !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+    })
+}
+
+/// Characters outside comments and string literals (a literal shows up as
+/// its opening quote), with their byte offsets
+fn code_chars(text: &str) -> Vec<(usize, char)> {
+    let mut chars = Vec::new();
+    let mut iter = text.char_indices().peekable();
+    while let Some((offset, c)) = iter.next() {
+        match c {
+            '/' if iter.peek().is_some_and(|&(_, next)| next == '/') => {
+                while iter.next_if(|&(_, c)| c != '\n').is_some() {}
+            }
+            '"' | '\'' => {
+                chars.push((offset, c));
+                while let Some((_, inner)) = iter.next() {
+                    match inner {
+                        '\\' => {
+                            iter.next();
+                        }
+                        '\n' if c == '"' => break,
+                        _ if inner == c => break,
+                        _ => {}
+                    }
+                }
+            }
+            _ => chars.push((offset, c)),
+        }
+    }
+    chars
+}
+
+/// Replace everything but newlines in `range` with spaces, byte for byte
+fn blank(text: &mut String, range: Range<usize>) {
+    let blanked: String = text[range.clone()]
+        .chars()
+        .map(|c| match c {
+            '\n' => "\n".to_string(),
+            _ => " ".repeat(c.len_utf8()),
+        })
+        .collect();
+    text.replace_range(range, &blanked);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_lines(source: &str) -> Vec<usize> {
+        parse_recovering(source)
+            .errors
+            .iter()
+            .map(|e| source[..e.span.start].matches('\n').count() + 1)
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_source_matches_strict_parse() {
+        for source in [
+            "",
+            "42",
+            "let x = 1\nlet y = x + 1\ny",
+            "let f = x => do {\n  x + 1\n}",
+        ] {
+            let recovered = parse_recovering(source);
+            assert!(recovered.errors.is_empty());
+            assert_eq!(recovered.nodes, crate::parse(source).unwrap());
+            assert_eq!(recovered.masked, source);
+        }
+    }
+
+    #[test]
+    fn test_reports_every_broken_statement() {
+        let source = "let a = 1\nlet b = * 2\nlet c = a + 1\nlet d = (3\nlet e = [1, 2\nlet f = 4";
+        assert_eq!(error_lines(source), [2, 4, 5]);
+        assert_eq!(error_lines("let a = (\n\n"), [1]);
+
+        let recovered = parse_recovering(source);
+        let AstNode::Sequence { statements } = &recovered.nodes[0] else {
+            panic!("expected a sequence, got {:?}", recovered.nodes);
+        };
+        let shape: Vec<_> = statements
+            .iter()
+            .map(|s| match s {
+                AstNode::VariableDecl { name, .. } => name.as_str(),
+                AstNode::Error { .. } => "<error>",
+                other => panic!("unexpected {:?}", other),
+            })
+            .collect();
+        assert_eq!(shape, ["a", "<error>", "c", "<error>", "<error>", "f"]);
+    }
+
+    #[test]
+    fn test_masked_source_keeps_offsets() {
+        let source = "let a = \"π\"\nlet s = \"é\" +\nlet ok = 2";
+        let recovered = parse_recovering(source);
+        assert_eq!(recovered.masked.len(), source.len());
+        assert_eq!(recovered.masked.find("let ok"), source.find("let ok"));
+        assert!(recovered.masked.contains("let a = \"π\""));
+        assert!(!recovered.masked.contains("\"é\""));
+        assert!(crate::parse(&recovered.masked).is_ok());
+    }
+
+    #[test]
+    fn test_error_inside_block_keeps_the_function() {
+        let source = "let f = (x) => do {\n  let y = x * 2\n  let z = y +* 1\n  y\n}\nf(2)";
+        let recovered = parse_recovering(source);
+        assert_eq!(error_lines(source), [3]);
+        assert!(recovered.masked.contains("let f = (x) => do {"));
+        assert!(recovered.masked.contains("let y = x * 2"));
+        assert!(!recovered.masked.contains("let z"));
+    }
+
+    #[test]
+    fn test_unclosed_block_does_not_swallow_the_file() {
+        let source = "let f = x => do {\n  x + 1\n\nlet g = 2\ng";
+        let recovered = parse_recovering(source);
+        assert_eq!(recovered.errors.len(), 1);
+        assert!(!recovered.masked.contains("let f"));
+        assert!(recovered.masked.contains("let g = 2\ng"));
+    }
+
+    #[test]
+    fn test_continuation_lines_stay_with_their_statement() {
+        let source = "let total = 1 +\n  2\nlet bad = )\nxs\n  |> map(f)\n  |> sum(";
+        assert_eq!(error_lines(source), [3, 6]);
+        let masked = parse_recovering(source).masked;
+        assert!(masked.contains("let total = 1 +\n  2"));
+        assert!(!masked.contains("xs"));
+    }
+
+    #[test]
+    fn test_error_messages_use_readable_rule_names() {
+        let recovered = parse_recovering("let x = ");
+        assert_eq!(recovered.errors.len(), 1);
+        assert_eq!(recovered.errors[0].message, "expected expression");
+        let recovered = parse_recovering("let 2 = 1");
+        assert_eq!(
+            recovered.errors[0].message,
+            "expected identifier or pattern"
+        );
+        let recovered = parse_recovering("let y = [1, 2");
+        assert_eq!(recovered.errors.len(), 1);
+        assert!(recovered.errors[0].message.ends_with("or |>"));
+    }
+}
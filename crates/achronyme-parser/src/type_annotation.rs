@@ -31,13 +31,14 @@ pub enum TypeAnnotation {
     Complex,
 
     /// Tensor type with optional element type and shape
-    /// shape: None = unknown rank, Some(vec) = known rank with optional dimensions
+    /// shape: None = unknown rank, Some(vec) = known rank
     /// Example: Tensor<Number> has shape=None
-    /// Example: Tensor<Number, [2, 3]> has shape=Some(vec![Some(2), Some(3)])
-    /// Example: Tensor<Number, [_, _]> has shape=Some(vec![None, None])
+    /// Example: Tensor<Number, [2, 3]> has shape=Some(vec![Fixed(2), Fixed(3)])
+    /// Example: Tensor<Number, [_, n]> has shape=Some(vec![Unknown, Var("n")])
+    /// Example: Tensor<n, k> is Tensor<Number, [n, k]>
     Tensor {
         element_type: Box<TypeAnnotation>,
-        shape: Option<Vec<Option<usize>>>,
+        shape: Option<Vec<Dimension>>,
     },
 
     /// Vector type (heterogeneous array)
@@ -94,6 +95,28 @@ pub enum TypeAnnotation {
     TypeReference(String),
 }
 
+/// One dimension of a tensor shape
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Dimension {
+    /// A fixed size: `3`
+    Fixed(usize),
+    /// A shape variable: the same size wherever the name appears in a
+    /// signature, e.g. `n` in `(Tensor<n, n>): Number`
+    Var(String),
+    /// Any size: `_`
+    Unknown,
+}
+
+impl std::fmt::Display for Dimension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Dimension::Fixed(size) => write!(f, "{}", size),
+            Dimension::Var(name) => write!(f, "{}", name),
+            Dimension::Unknown => write!(f, "_"),
+        }
+    }
+}
+
 impl std::fmt::Display for TypeAnnotation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -117,7 +140,7 @@ impl std::fmt::Display for TypeAnnotation {
                 Some(dims) => {
                     let dims_str = dims
                         .iter()
-                        .map(|d| d.map_or("_".to_string(), |n| n.to_string()))
+                        .map(|d| d.to_string())
                         .collect::<Vec<_>>()
                         .join(", ");
                     write!(f, "Tensor<{}, [{}]>", element_type, dims_str)
@@ -126,22 +149,22 @@ impl std::fmt::Display for TypeAnnotation {
 
             TypeAnnotation::Record { fields } => {
                 if fields.is_empty() {
-                    write!(f, "{{}}")
-                } else {
-                    let fields_str = fields
-                        .iter()
-                        .map(|(name, (is_mut, is_optional, ty))| {
-                            let optional_marker = if *is_optional { "?" } else { "" };
-                            if *is_mut {
-                                format!("mut {}{}: {}", name, optional_marker, ty)
-                            } else {
-                                format!("{}{}: {}", name, optional_marker, ty)
-                            }
-                        })
-                        .collect::<Vec<_>>()
-                        .join(", ");
-                    write!(f, "{{{}}}", fields_str)
+                    return write!(f, "{{}}");
                 }
+                // Sorted, so the same type always prints the same
+                let mut names: Vec<&String> = fields.keys().collect();
+                names.sort();
+                let fields_str = names
+                    .into_iter()
+                    .map(|name| {
+                        let (is_mut, is_optional, ty) = &fields[name];
+                        let mut_keyword = if *is_mut { "mut " } else { "" };
+                        let optional_marker = if *is_optional { "?" } else { "" };
+                        format!("{}{}{}: {}", mut_keyword, name, optional_marker, ty)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "{{ {} }}", fields_str)
             }
 
             TypeAnnotation::Function {
@@ -153,13 +176,17 @@ impl std::fmt::Display for TypeAnnotation {
                     .map(|p| p.as_ref().map_or("Any".to_string(), |t| t.to_string()))
                     .collect::<Vec<_>>()
                     .join(", ");
-                write!(f, "({}) => {}", params_str, return_type)
+                write!(f, "({}): {}", params_str, return_type)
             }
 
             TypeAnnotation::Union(types) => {
+                // `((Number): Number) | null`, not a function returning a union
                 let types_str = types
                     .iter()
-                    .map(|t| t.to_string())
+                    .map(|t| match t {
+                        TypeAnnotation::Function { .. } => format!("({})", t),
+                        _ => t.to_string(),
+                    })
                     .collect::<Vec<_>>()
                     .join(" | ");
                 write!(f, "{}", types_str)
@@ -171,6 +198,23 @@ impl std::fmt::Display for TypeAnnotation {
 }
 
 impl TypeAnnotation {
+    /// The names of a union written with type names only: `Red | Green`
+    ///
+    /// `type Color = Red | Green` declares field-less variants when none of
+    /// the names is a known type.
+    pub fn reference_union(&self) -> Option<Vec<&str>> {
+        let TypeAnnotation::Union(members) = self else {
+            return None;
+        };
+        members
+            .iter()
+            .map(|member| match member {
+                TypeAnnotation::TypeReference(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Check if this type is assignable from another type (for type checking)
     /// This is a simplified version - full checking happens in the evaluator
     pub fn is_assignable_from(&self, other: &TypeAnnotation) -> bool {
@@ -234,6 +278,25 @@ mod tests {
         assert_eq!(union.to_string(), "Number | String");
     }
 
+    #[test]
+    fn test_type_to_string_reads_as_an_annotation() {
+        let function = TypeAnnotation::Function {
+            params: vec![Some(TypeAnnotation::Number), None],
+            return_type: Box::new(TypeAnnotation::Boolean),
+        };
+        assert_eq!(function.to_string(), "(Number, Any): Boolean");
+        let optional = TypeAnnotation::Union(vec![function, TypeAnnotation::Null]);
+        assert_eq!(optional.to_string(), "((Number, Any): Boolean) | null");
+
+        let record = TypeAnnotation::Record {
+            fields: HashMap::from([
+                ("name".to_string(), (false, false, TypeAnnotation::String)),
+                ("count".to_string(), (true, true, TypeAnnotation::Number)),
+            ]),
+        };
+        assert_eq!(record.to_string(), "{ mut count?: Number, name: String }");
+    }
+
     #[test]
     fn test_union_assignability() {
         let union = TypeAnnotation::Union(vec![TypeAnnotation::Number, TypeAnnotation::String]);
@@ -152,3 +152,52 @@ fn test_newline_in_expression_ignored() {
     let result = parse(code);
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_pipeline_continues_on_next_line() {
+    let code = "let total = xs\n  |> map(f)\n  |> sum()\ntotal";
+    let ast = parse(code).unwrap();
+    let AstNode::Sequence { statements } = &ast[0] else {
+        panic!("Expected Sequence, got {:?}", ast[0]);
+    };
+    assert_eq!(statements.len(), 2);
+
+    // Left-associative: (xs |> map(f)) |> sum()
+    let AstNode::VariableDecl { initializer, .. } = &statements[0] else {
+        panic!("Expected VariableDecl, got {:?}", statements[0]);
+    };
+    let AstNode::BinaryOp {
+        op: BinaryOp::Pipe,
+        left,
+        right,
+    } = initializer.as_ref()
+    else {
+        panic!("Expected a pipe, got {:?}", initializer);
+    };
+    assert!(matches!(
+        right.as_ref(),
+        AstNode::CallExpression { callee, args }
+            if **callee == AstNode::VariableRef("sum".to_string()) && args.is_empty()
+    ));
+    assert!(matches!(
+        left.as_ref(),
+        AstNode::BinaryOp {
+            op: BinaryOp::Pipe,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_pipeline_binds_looser_than_ranges() {
+    let ast = parse("1..4 |> sum").unwrap();
+    let AstNode::BinaryOp {
+        op: BinaryOp::Pipe,
+        left,
+        ..
+    } = &ast[0]
+    else {
+        panic!("Expected a pipe, got {:?}", ast[0]);
+    };
+    assert!(matches!(left.as_ref(), AstNode::RangeExpr { .. }));
+}
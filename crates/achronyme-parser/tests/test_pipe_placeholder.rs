@@ -0,0 +1,45 @@
+/// Tests for the `_` placeholder of pipelines
+///
+/// The compiler checks call arguments on the AST and the editor on the
+/// parse tree; both must agree on what counts as a placeholder.
+use achronyme_parser::pest_parser::{is_pipe_placeholder, Rule, SOCParser};
+use achronyme_parser::{parse, AstNode};
+use pest::Parser;
+
+fn ast_says(argument: &str) -> bool {
+    match parse(argument).unwrap().as_slice() {
+        [node] => node.is_pipe_placeholder(),
+        other => panic!("Expected one expression, got {:?}", other),
+    }
+}
+
+fn tree_says(argument: &str) -> bool {
+    let pair = SOCParser::parse(Rule::expr, argument)
+        .unwrap()
+        .next()
+        .unwrap();
+    is_pipe_placeholder(&pair)
+}
+
+#[test]
+fn test_placeholder_forms() {
+    for (argument, expected) in [
+        ("_", true),
+        ("(_)", true),
+        ("( _ )", true),
+        ("-_", false),
+        ("!_", false),
+        ("_x", false),
+        ("[_]", false),
+        ("f(_)", false),
+    ] {
+        assert_eq!(ast_says(argument), expected, "AST: {}", argument);
+        assert_eq!(tree_says(argument), expected, "parse tree: {}", argument);
+    }
+}
+
+#[test]
+fn test_only_variable_refs_are_placeholders() {
+    assert!(AstNode::VariableRef("_".to_string()).is_pipe_placeholder());
+    assert!(!AstNode::StringLiteral("_".to_string()).is_pipe_placeholder());
+}
@@ -1,4 +1,8 @@
-use achronyme_parser::{ast::*, parse, type_annotation::TypeAnnotation};
+use achronyme_parser::{
+    ast::*,
+    parse, parse_type,
+    type_annotation::{Dimension, TypeAnnotation},
+};
 
 // ============================================================================
 // Variable Declaration Tests
@@ -282,7 +286,7 @@ fn test_let_with_tensor_type() {
                 shape,
             }) => {
                 assert_eq!(**element_type, TypeAnnotation::Number);
-                assert_eq!(shape, &Some(vec![Some(2), Some(3)]));
+                assert_eq!(shape, &Some(vec![Dimension::Fixed(2), Dimension::Fixed(3)]));
             }
             _ => panic!("Expected Tensor type annotation"),
         },
@@ -426,3 +430,155 @@ fn test_higher_order_function_with_types() {
         _ => panic!("Expected VariableDecl"),
     }
 }
+
+#[test]
+fn test_tensor_shape_variables() {
+    let tensor = |dims: Vec<Dimension>| TypeAnnotation::Tensor {
+        element_type: Box::new(TypeAnnotation::Number),
+        shape: Some(dims),
+    };
+    let var = |name: &str| Dimension::Var(name.to_string());
+
+    assert_eq!(
+        parse_type("Tensor<Number, [n, _, 3]>"),
+        Ok(tensor(vec![
+            var("n"),
+            Dimension::Unknown,
+            Dimension::Fixed(3)
+        ]))
+    );
+    // Just the dimensions is a tensor of numbers
+    assert_eq!(
+        parse_type("Tensor<n, k>"),
+        Ok(tensor(vec![var("n"), var("k")]))
+    );
+    assert_eq!(
+        parse_type("Tensor<2, _>"),
+        Ok(tensor(vec![Dimension::Fixed(2), Dimension::Unknown]))
+    );
+    assert_eq!(
+        parse_type("Tensor<n, k>").unwrap().to_string(),
+        "Tensor<Number, [n, k]>"
+    );
+    // A single dimension is a vector of numbers; a capitalized name stays a type
+    assert_eq!(
+        parse_type("Tensor<3>"),
+        Ok(tensor(vec![Dimension::Fixed(3)]))
+    );
+    assert_eq!(parse_type("Tensor<n>"), Ok(tensor(vec![var("n")])));
+    assert_eq!(
+        parse_type("Tensor<_>"),
+        Ok(tensor(vec![Dimension::Unknown]))
+    );
+    assert_eq!(
+        parse_type("Tensor<Point>"),
+        Ok(TypeAnnotation::Tensor {
+            element_type: Box::new(TypeAnnotation::TypeReference("Point".to_string())),
+            shape: None,
+        })
+    );
+    // A type where a dimension belongs is a missing pair of brackets
+    assert!(parse_type("Tensor<Complex, n>").is_err());
+
+    let result = parse("let mul = (a: Tensor<n, k>, b: Tensor<k, m>): Tensor<n, m> => a");
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_parse_type_on_its_own() {
+    assert_eq!(
+        parse_type("Number | null"),
+        Ok(TypeAnnotation::Union(vec![
+            TypeAnnotation::Number,
+            TypeAnnotation::Null
+        ]))
+    );
+    assert_eq!(
+        parse_type("Tensor<Number>"),
+        Ok(TypeAnnotation::Tensor {
+            element_type: Box::new(TypeAnnotation::Number),
+            shape: None,
+        })
+    );
+    assert!(parse_type("Number Number").is_err());
+
+    // Printing a type gives back an annotation for the same type
+    for source in [
+        "{ mut count?: Number, name: String }",
+        "((Number): Number, Vector): Vector",
+        "((Number): Boolean) | null",
+        "Tensor<Complex, [2, _]>",
+    ] {
+        let ty = parse_type(source).unwrap();
+        assert_eq!(ty.to_string(), source);
+        assert_eq!(parse_type(&ty.to_string()), Ok(ty));
+    }
+}
+
+#[test]
+fn test_data_type_declaration() {
+    let result =
+        parse("type Shape = Circle { r: Number } | Rect { w: Number, h: Number }").unwrap();
+    match &result[0] {
+        AstNode::DataType { name, variants } => {
+            assert_eq!(name, "Shape");
+            assert_eq!(variants.len(), 2);
+            assert_eq!(variants[0].name, "Circle");
+            assert_eq!(
+                variants[1].fields,
+                vec![
+                    ("w".to_string(), TypeAnnotation::Number),
+                    ("h".to_string(), TypeAnnotation::Number)
+                ]
+            );
+        }
+        other => panic!("Expected DataType, got {:?}", other),
+    }
+
+    // Variants without fields, one per line
+    let result = parse("type Tree =\n  | Leaf\n  | Node { left: Tree, right: Tree }").unwrap();
+    match &result[0] {
+        AstNode::DataType { variants, .. } => {
+            assert_eq!(variants[0].name, "Leaf");
+            assert!(variants[0].fields.is_empty());
+            assert_eq!(variants[1].fields.len(), 2);
+        }
+        other => panic!("Expected DataType, got {:?}", other),
+    }
+
+    // Without any fields it is still a union alias
+    let result = parse("type Result = Success | Failure").unwrap();
+    assert!(matches!(&result[0], AstNode::TypeAlias { .. }));
+    let result = parse("type Point = { x: Number } | null").unwrap();
+    assert!(matches!(&result[0], AstNode::TypeAlias { .. }));
+
+    assert!(parse("type Shape = Circle { r: Number } | Circle { d: Number }").is_err());
+    assert!(parse("type Cell = Full { mut value: Number } | Empty").is_err());
+}
+
+#[test]
+fn test_variant_pattern() {
+    let result =
+        parse("match s { Circle { r } => r, Rect { w: width, h } => width * h, Empty => 0 }")
+            .unwrap();
+    match &result[0] {
+        AstNode::Match { arms, .. } => {
+            match &arms[0].pattern {
+                Pattern::Variant { name, fields } => {
+                    assert_eq!(name, "Circle");
+                    assert_eq!(fields[0].0, "r");
+                }
+                other => panic!("Expected variant pattern, got {:?}", other),
+            }
+            match &arms[1].pattern {
+                Pattern::Variant { fields, .. } => {
+                    assert_eq!(fields[0].1, Pattern::Variable("width".to_string()));
+                }
+                other => panic!("Expected variant pattern, got {:?}", other),
+            }
+            // A bare variant name is left for the compiler to resolve
+            assert_eq!(arms[2].pattern, Pattern::Variable("Empty".to_string()));
+        }
+        other => panic!("Expected match, got {:?}", other),
+    }
+}
@@ -0,0 +1,800 @@
+//! Columnar tabular data
+//!
+//! A `DataFrame` is an ordered set of named, equally sized columns. Numeric
+//! columns are stored as rank-1 `RealTensor`s so that tensor kernels and HOFs
+//! can operate on them directly; everything else is stored as strings.
+//!
+//! All operations are non-destructive and return new frames.
+
+use crate::tensor::RealTensor;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A single typed column
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    /// Numeric column backed by a rank-1 tensor
+    Number(RealTensor),
+    /// Text column
+    String(Vec<String>),
+}
+
+/// A single cell, used when reading rows out of a frame
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Number(f64),
+    String(String),
+}
+
+/// Hashable form of a cell, used for grouping and joins
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum CellKey {
+    Number(u64),
+    String(String),
+    /// A NaN (missing) number
+    Missing,
+}
+
+impl From<&Cell> for CellKey {
+    fn from(cell: &Cell) -> Self {
+        match cell {
+            // Normalize -0.0 so that it groups with 0.0
+            Cell::Number(n) if n.is_nan() => CellKey::Missing,
+            Cell::Number(n) => CellKey::Number(if *n == 0.0 { 0u64 } else { n.to_bits() }),
+            Cell::String(s) => CellKey::String(s.clone()),
+        }
+    }
+}
+
+/// Errors produced by DataFrame operations
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataFrameError {
+    /// A column has a different number of rows than the rest of the frame
+    LengthMismatch {
+        column: String,
+        expected: usize,
+        got: usize,
+    },
+    /// A referenced column does not exist
+    UnknownColumn(String),
+    /// The same column name was given twice
+    DuplicateColumn(String),
+    /// Unknown aggregation name
+    UnknownAggregation(String),
+}
+
+impl fmt::Display for DataFrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataFrameError::LengthMismatch {
+                column,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Column '{}' has {} rows, expected {}",
+                column, got, expected
+            ),
+            DataFrameError::UnknownColumn(name) => write!(f, "Unknown column '{}'", name),
+            DataFrameError::DuplicateColumn(name) => write!(f, "Duplicate column '{}'", name),
+            DataFrameError::UnknownAggregation(name) => {
+                write!(f, "Unknown aggregation '{}'", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DataFrameError {}
+
+/// Join strategy for `DataFrame::join`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinKind {
+    /// Keep only rows with a match on both sides
+    Inner,
+    /// Keep every left row; unmatched right columns are NaN / empty
+    Left,
+}
+
+/// Built-in column aggregations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    Count,
+    Std,
+    First,
+    Last,
+}
+
+impl Aggregation {
+    /// Parse an aggregation from its script-level name
+    pub fn from_name(name: &str) -> Result<Self, DataFrameError> {
+        match name {
+            "sum" => Ok(Aggregation::Sum),
+            "mean" => Ok(Aggregation::Mean),
+            "min" => Ok(Aggregation::Min),
+            "max" => Ok(Aggregation::Max),
+            "count" => Ok(Aggregation::Count),
+            "std" => Ok(Aggregation::Std),
+            "first" => Ok(Aggregation::First),
+            "last" => Ok(Aggregation::Last),
+            _ => Err(DataFrameError::UnknownAggregation(name.to_string())),
+        }
+    }
+
+    /// Script-level name of the aggregation
+    pub fn name(&self) -> &'static str {
+        match self {
+            Aggregation::Sum => "sum",
+            Aggregation::Mean => "mean",
+            Aggregation::Min => "min",
+            Aggregation::Max => "max",
+            Aggregation::Count => "count",
+            Aggregation::Std => "std",
+            Aggregation::First => "first",
+            Aggregation::Last => "last",
+        }
+    }
+
+    /// Apply the aggregation to a set of rows of a column
+    pub fn apply(&self, column: &Column, rows: &[usize]) -> Cell {
+        match (self, column) {
+            (Aggregation::Count, _) => Cell::Number(rows.len() as f64),
+            (Aggregation::First, col) => rows
+                .first()
+                .map(|&r| col.cell(r))
+                .unwrap_or(Cell::Number(f64::NAN)),
+            (Aggregation::Last, col) => rows
+                .last()
+                .map(|&r| col.cell(r))
+                .unwrap_or(Cell::Number(f64::NAN)),
+            (_, Column::String(_)) => Cell::Number(f64::NAN),
+            (agg, Column::Number(t)) => {
+                let values: Vec<f64> = rows.iter().map(|&r| t.data[r]).collect();
+                Cell::Number(aggregate_numbers(*agg, &values))
+            }
+        }
+    }
+}
+
+fn aggregate_numbers(agg: Aggregation, values: &[f64]) -> f64 {
+    let n = values.len() as f64;
+    match agg {
+        Aggregation::Sum => values.iter().sum(),
+        Aggregation::Mean => {
+            if values.is_empty() {
+                f64::NAN
+            } else {
+                values.iter().sum::<f64>() / n
+            }
+        }
+        Aggregation::Min => values.iter().copied().fold(f64::NAN, f64::min),
+        Aggregation::Max => values.iter().copied().fold(f64::NAN, f64::max),
+        Aggregation::Count => n,
+        Aggregation::Std => {
+            // Sample standard deviation, matching `std()`
+            if values.len() < 2 {
+                return f64::NAN;
+            }
+            let mean = values.iter().sum::<f64>() / n;
+            let var = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            var.sqrt()
+        }
+        Aggregation::First => values.first().copied().unwrap_or(f64::NAN),
+        Aggregation::Last => values.last().copied().unwrap_or(f64::NAN),
+    }
+}
+
+impl Column {
+    /// Build a numeric column from raw values
+    pub fn numbers(data: Vec<f64>) -> Self {
+        Column::Number(RealTensor::vector(data))
+    }
+
+    /// Build a column from cells, choosing the numeric representation when
+    /// every cell is a number
+    pub fn from_cells(cells: Vec<Cell>) -> Self {
+        if cells.iter().all(|c| matches!(c, Cell::Number(_))) {
+            Column::numbers(
+                cells
+                    .into_iter()
+                    .map(|c| match c {
+                        Cell::Number(n) => n,
+                        Cell::String(_) => unreachable!(),
+                    })
+                    .collect(),
+            )
+        } else {
+            Column::String(
+                cells
+                    .into_iter()
+                    .map(|c| match c {
+                        Cell::Number(n) => n.to_string(),
+                        Cell::String(s) => s,
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    /// Number of rows in the column
+    pub fn len(&self) -> usize {
+        match self {
+            Column::Number(t) => t.data.len(),
+            Column::String(v) => v.len(),
+        }
+    }
+
+    /// Check if the column has no rows
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Name of the column's element type
+    pub fn dtype(&self) -> &'static str {
+        match self {
+            Column::Number(_) => "Number",
+            Column::String(_) => "String",
+        }
+    }
+
+    /// Read a single cell
+    pub fn cell(&self, row: usize) -> Cell {
+        match self {
+            Column::Number(t) => Cell::Number(t.data[row]),
+            Column::String(v) => Cell::String(v[row].clone()),
+        }
+    }
+
+    /// Gather the given rows into a new column
+    pub fn take(&self, rows: &[usize]) -> Column {
+        match self {
+            Column::Number(t) => Column::numbers(rows.iter().map(|&r| t.data[r]).collect()),
+            Column::String(v) => Column::String(rows.iter().map(|&r| v[r].clone()).collect()),
+        }
+    }
+
+    /// Gather rows, emitting a missing value (NaN / empty string) for `None`
+    fn take_optional(&self, rows: &[Option<usize>]) -> Column {
+        match self {
+            Column::Number(t) => Column::numbers(
+                rows.iter()
+                    .map(|r| r.map(|r| t.data[r]).unwrap_or(f64::NAN))
+                    .collect(),
+            ),
+            Column::String(v) => Column::String(
+                rows.iter()
+                    .map(|r| r.map(|r| v[r].clone()).unwrap_or_default())
+                    .collect(),
+            ),
+        }
+    }
+
+    fn compare_rows(&self, a: usize, b: usize) -> Ordering {
+        match self {
+            Column::Number(t) => t.data[a]
+                .partial_cmp(&t.data[b])
+                // NaN sorts last
+                .unwrap_or_else(|| t.data[a].is_nan().cmp(&t.data[b].is_nan())),
+            Column::String(v) => v[a].cmp(&v[b]),
+        }
+    }
+}
+
+/// Named, typed columns of equal length
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DataFrame {
+    names: Vec<String>,
+    columns: Vec<Column>,
+    /// Grouping keys set by `group_by`, consumed by `agg`
+    group_keys: Vec<String>,
+}
+
+impl DataFrame {
+    /// Create a frame from `(name, column)` pairs
+    pub fn new(columns: Vec<(String, Column)>) -> Result<Self, DataFrameError> {
+        let mut df = DataFrame::default();
+        for (name, column) in columns {
+            df.push_column(name, column)?;
+        }
+        Ok(df)
+    }
+
+    /// Append a column, validating its name and length
+    pub fn push_column(&mut self, name: String, column: Column) -> Result<(), DataFrameError> {
+        if self.names.contains(&name) {
+            return Err(DataFrameError::DuplicateColumn(name));
+        }
+        if let Some(first) = self.columns.first() {
+            if first.len() != column.len() {
+                return Err(DataFrameError::LengthMismatch {
+                    column: name,
+                    expected: first.len(),
+                    got: column.len(),
+                });
+            }
+        }
+        self.names.push(name);
+        self.columns.push(column);
+        Ok(())
+    }
+
+    /// Number of rows
+    pub fn nrows(&self) -> usize {
+        self.columns.first().map(Column::len).unwrap_or(0)
+    }
+
+    /// Number of columns
+    pub fn ncols(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Column names in order
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Iterate over `(name, column)` pairs in order
+    pub fn columns(&self) -> impl Iterator<Item = (&String, &Column)> {
+        self.names.iter().zip(self.columns.iter())
+    }
+
+    /// Look up a column by name
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.names
+            .iter()
+            .position(|n| n == name)
+            .map(|i| &self.columns[i])
+    }
+
+    fn require(&self, name: &str) -> Result<&Column, DataFrameError> {
+        self.column(name)
+            .ok_or_else(|| DataFrameError::UnknownColumn(name.to_string()))
+    }
+
+    /// Read a whole row as `(name, cell)` pairs
+    pub fn row(&self, index: usize) -> Vec<(String, Cell)> {
+        self.columns()
+            .map(|(name, col)| (name.clone(), col.cell(index)))
+            .collect()
+    }
+
+    /// Grouping keys set by the last `group_by`
+    pub fn group_keys(&self) -> &[String] {
+        &self.group_keys
+    }
+
+    /// Keep only the named columns, in the given order
+    pub fn select(&self, names: &[String]) -> Result<DataFrame, DataFrameError> {
+        let mut columns = Vec::with_capacity(names.len());
+        for name in names {
+            columns.push((name.clone(), self.require(name)?.clone()));
+        }
+        DataFrame::new(columns)
+    }
+
+    /// Gather the given rows into a new frame
+    pub fn take_rows(&self, rows: &[usize]) -> DataFrame {
+        DataFrame {
+            names: self.names.clone(),
+            columns: self.columns.iter().map(|c| c.take(rows)).collect(),
+            group_keys: Vec::new(),
+        }
+    }
+
+    /// First `n` rows
+    pub fn head(&self, n: usize) -> DataFrame {
+        let rows: Vec<usize> = (0..n.min(self.nrows())).collect();
+        self.take_rows(&rows)
+    }
+
+    /// Stable sort by one or more columns
+    pub fn sort_by(&self, by: &[String], descending: bool) -> Result<DataFrame, DataFrameError> {
+        let keys: Vec<&Column> = by
+            .iter()
+            .map(|name| self.require(name))
+            .collect::<Result<_, _>>()?;
+        let mut rows: Vec<usize> = (0..self.nrows()).collect();
+        rows.sort_by(|&a, &b| {
+            let ord = keys
+                .iter()
+                .map(|col| col.compare_rows(a, b))
+                .find(|o| *o != Ordering::Equal)
+                .unwrap_or(Ordering::Equal);
+            if descending {
+                ord.reverse()
+            } else {
+                ord
+            }
+        });
+        Ok(self.take_rows(&rows))
+    }
+
+    /// Mark the frame as grouped by the given key columns
+    pub fn group_by(&self, keys: &[String]) -> Result<DataFrame, DataFrameError> {
+        for key in keys {
+            self.require(key)?;
+        }
+        let mut grouped = self.clone();
+        grouped.group_keys = keys.to_vec();
+        Ok(grouped)
+    }
+
+    /// Row indices of each group, in order of first appearance
+    ///
+    /// An ungrouped frame yields a single group holding every row. Rows
+    /// whose key is NaN (missing) form one group of their own.
+    pub fn groups(&self) -> Result<Vec<Vec<usize>>, DataFrameError> {
+        if self.group_keys.is_empty() {
+            return Ok(vec![(0..self.nrows()).collect()]);
+        }
+        let key_cols: Vec<&Column> = self
+            .group_keys
+            .iter()
+            .map(|k| self.require(k))
+            .collect::<Result<_, _>>()?;
+
+        let mut index: HashMap<Vec<CellKey>, usize> = HashMap::new();
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for row in 0..self.nrows() {
+            let key: Vec<CellKey> = key_cols
+                .iter()
+                .map(|c| CellKey::from(&c.cell(row)))
+                .collect();
+            let slot = *index.entry(key).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[slot].push(row);
+        }
+        Ok(groups)
+    }
+
+    /// Frame holding one row per group with the key columns only
+    ///
+    /// Callers append aggregated columns to the result.
+    pub fn group_key_frame(&self, groups: &[Vec<usize>]) -> Result<DataFrame, DataFrameError> {
+        let firsts: Vec<usize> = groups.iter().filter_map(|g| g.first().copied()).collect();
+        let mut out = DataFrame::default();
+        for key in &self.group_keys {
+            out.push_column(key.clone(), self.require(key)?.take(&firsts))?;
+        }
+        Ok(out)
+    }
+
+    /// Aggregate columns per group with built-in aggregations
+    ///
+    /// `specs` lists `(output name, source column, aggregation)`.
+    pub fn agg(
+        &self,
+        specs: &[(String, String, Aggregation)],
+    ) -> Result<DataFrame, DataFrameError> {
+        let groups = self.groups()?;
+        let mut out = self.group_key_frame(&groups)?;
+        for (output, source, agg) in specs {
+            let column = self.require(source)?;
+            let cells = groups.iter().map(|rows| agg.apply(column, rows)).collect();
+            out.push_column(output.clone(), Column::from_cells(cells))?;
+        }
+        Ok(out)
+    }
+
+    /// Join with another frame on equally named key columns
+    ///
+    /// Non-key columns of `other` that clash with names in `self` get a
+    /// `_right` suffix (numbered if that is taken too). A NaN (missing) key
+    /// matches nothing, not even another NaN.
+    pub fn join(
+        &self,
+        other: &DataFrame,
+        on: &[String],
+        kind: JoinKind,
+    ) -> Result<DataFrame, DataFrameError> {
+        let left_keys: Vec<&Column> = on
+            .iter()
+            .map(|k| self.require(k))
+            .collect::<Result<_, _>>()?;
+        let right_keys: Vec<&Column> = on
+            .iter()
+            .map(|k| other.require(k))
+            .collect::<Result<_, _>>()?;
+
+        let key_of = |cols: &[&Column], row: usize| -> Vec<CellKey> {
+            cols.iter().map(|c| CellKey::from(&c.cell(row))).collect()
+        };
+
+        let mut right_index: HashMap<Vec<CellKey>, Vec<usize>> = HashMap::new();
+        for row in 0..other.nrows() {
+            let key = key_of(&right_keys, row);
+            if !key.contains(&CellKey::Missing) {
+                right_index.entry(key).or_default().push(row);
+            }
+        }
+
+        let mut left_rows = Vec::new();
+        let mut right_rows: Vec<Option<usize>> = Vec::new();
+        for row in 0..self.nrows() {
+            match right_index.get(&key_of(&left_keys, row)) {
+                Some(matches) => {
+                    for &r in matches {
+                        left_rows.push(row);
+                        right_rows.push(Some(r));
+                    }
+                }
+                None if kind == JoinKind::Left => {
+                    left_rows.push(row);
+                    right_rows.push(None);
+                }
+                None => {}
+            }
+        }
+
+        let mut out = self.take_rows(&left_rows);
+        for (name, column) in other.columns() {
+            if on.contains(name) {
+                continue;
+            }
+            let out_name = if out.names.contains(name) {
+                out.unused_name(format!("{}_right", name))
+            } else {
+                name.clone()
+            };
+            out.push_column(out_name, column.take_optional(&right_rows))?;
+        }
+        Ok(out)
+    }
+
+    /// Summary statistics of every numeric column
+    ///
+    /// Returns a frame with a `stat` column (count, mean, std, min, max)
+    /// followed by one column per numeric input column. If the input has a
+    /// `stat` column already, the label column gets a numbered name.
+    pub fn describe(&self) -> Result<DataFrame, DataFrameError> {
+        const STATS: [Aggregation; 5] = [
+            Aggregation::Count,
+            Aggregation::Mean,
+            Aggregation::Std,
+            Aggregation::Min,
+            Aggregation::Max,
+        ];
+        let all_rows: Vec<usize> = (0..self.nrows()).collect();
+        let mut columns = vec![(
+            self.unused_name("stat".to_string()),
+            Column::String(STATS.iter().map(|s| s.name().to_string()).collect()),
+        )];
+        for (name, column) in self.columns() {
+            if let Column::Number(_) = column {
+                let cells = STATS.iter().map(|s| s.apply(column, &all_rows)).collect();
+                columns.push((name.clone(), Column::from_cells(cells)));
+            }
+        }
+        DataFrame::new(columns)
+    }
+
+    /// `base`, or `base` with the first numeric suffix no column uses yet
+    fn unused_name(&self, base: String) -> String {
+        if !self.names.contains(&base) {
+            return base;
+        }
+        (2..)
+            .map(|n| format!("{}_{}", base, n))
+            .find(|name| !self.names.contains(name))
+            .expect("some suffix is free")
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cell::Number(n) => write!(f, "{}", n),
+            Cell::String(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Maximum number of rows printed by `Display`
+const DISPLAY_ROWS: usize = 10;
+
+impl fmt::Display for DataFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let shown = self.nrows().min(DISPLAY_ROWS);
+        let mut cells: Vec<Vec<String>> = vec![self.names.clone()];
+        for row in 0..shown {
+            cells.push(
+                self.columns
+                    .iter()
+                    .map(|c| c.cell(row).to_string())
+                    .collect(),
+            );
+        }
+        let widths: Vec<usize> = (0..self.ncols())
+            .map(|c| {
+                cells
+                    .iter()
+                    .map(|r| r[c].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        writeln!(f, "DataFrame({} x {})", self.nrows(), self.ncols())?;
+        for (i, row) in cells.iter().enumerate() {
+            let line: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, w)| format!("{:>width$}", cell, width = w))
+                .collect();
+            write!(f, "{}", line.join("  "))?;
+            if i + 1 < cells.len() {
+                writeln!(f)?;
+            }
+        }
+        if self.nrows() > shown {
+            write!(f, "\n... {} more rows", self.nrows() - shown)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> DataFrame {
+        DataFrame::new(vec![
+            (
+                "city".to_string(),
+                Column::String(vec!["a".into(), "b".into(), "a".into(), "c".into()]),
+            ),
+            (
+                "price".to_string(),
+                Column::numbers(vec![3.0, 1.0, 5.0, 2.0]),
+            ),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_length_mismatch() {
+        let err = DataFrame::new(vec![
+            ("x".to_string(), Column::numbers(vec![1.0, 2.0])),
+            ("y".to_string(), Column::numbers(vec![1.0])),
+        ])
+        .unwrap_err();
+        assert!(matches!(err, DataFrameError::LengthMismatch { .. }));
+    }
+
+    #[test]
+    fn test_sort_and_head() {
+        let sorted = sample().sort_by(&["price".to_string()], true).unwrap();
+        assert_eq!(
+            sorted.column("price"),
+            Some(&Column::numbers(vec![5.0, 3.0, 2.0, 1.0]))
+        );
+        assert_eq!(sorted.head(2).nrows(), 2);
+    }
+
+    #[test]
+    fn test_group_agg() {
+        let df = sample().group_by(&["city".to_string()]).unwrap();
+        let out = df
+            .agg(&[("price".to_string(), "price".to_string(), Aggregation::Sum)])
+            .unwrap();
+        assert_eq!(
+            out.column("city"),
+            Some(&Column::String(vec!["a".into(), "b".into(), "c".into()]))
+        );
+        assert_eq!(
+            out.column("price"),
+            Some(&Column::numbers(vec![8.0, 1.0, 2.0]))
+        );
+    }
+
+    #[test]
+    fn test_join() {
+        let right = DataFrame::new(vec![
+            (
+                "city".to_string(),
+                Column::String(vec!["a".into(), "b".into()]),
+            ),
+            ("pop".to_string(), Column::numbers(vec![10.0, 20.0])),
+        ])
+        .unwrap();
+
+        let inner = sample()
+            .join(&right, &["city".to_string()], JoinKind::Inner)
+            .unwrap();
+        assert_eq!(inner.nrows(), 3);
+
+        let left = sample()
+            .join(&right, &["city".to_string()], JoinKind::Left)
+            .unwrap();
+        assert_eq!(left.nrows(), 4);
+        match left.column("pop") {
+            Some(Column::Number(t)) => assert!(t.data[3].is_nan()),
+            other => panic!("Expected numeric column, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_describe() {
+        let summary = sample().describe().unwrap();
+        assert_eq!(summary.names(), &["stat".to_string(), "price".to_string()]);
+        assert_eq!(summary.column("price").unwrap().cell(0), Cell::Number(4.0));
+    }
+
+    #[test]
+    fn test_describe_keeps_stat_column() {
+        let df =
+            DataFrame::new(vec![("stat".to_string(), Column::numbers(vec![1.0, 3.0]))]).unwrap();
+        let summary = df.describe().unwrap();
+        assert_eq!(summary.names(), &["stat_2".to_string(), "stat".to_string()]);
+        assert_eq!(summary.column("stat").unwrap().cell(1), Cell::Number(2.0));
+    }
+
+    #[test]
+    fn test_join_suffix_avoids_existing_columns() {
+        let left = DataFrame::new(vec![
+            ("id".to_string(), Column::numbers(vec![1.0])),
+            ("x".to_string(), Column::numbers(vec![2.0])),
+            ("x_right".to_string(), Column::numbers(vec![3.0])),
+        ])
+        .unwrap();
+        let right = DataFrame::new(vec![
+            ("id".to_string(), Column::numbers(vec![1.0])),
+            ("x".to_string(), Column::numbers(vec![4.0])),
+        ])
+        .unwrap();
+        let out = left
+            .join(&right, &["id".to_string()], JoinKind::Inner)
+            .unwrap();
+        assert_eq!(out.column("x_right_2").unwrap().cell(0), Cell::Number(4.0));
+    }
+
+    #[test]
+    fn test_nan_keys_do_not_join() {
+        let left = DataFrame::new(vec![
+            ("id".to_string(), Column::numbers(vec![1.0, f64::NAN])),
+            ("x".to_string(), Column::numbers(vec![2.0, 3.0])),
+        ])
+        .unwrap();
+        let right = DataFrame::new(vec![
+            ("id".to_string(), Column::numbers(vec![f64::NAN, 1.0])),
+            ("y".to_string(), Column::numbers(vec![4.0, 5.0])),
+        ])
+        .unwrap();
+
+        let inner = left
+            .join(&right, &["id".to_string()], JoinKind::Inner)
+            .unwrap();
+        assert_eq!(inner.nrows(), 1);
+        assert_eq!(inner.column("y").unwrap().cell(0), Cell::Number(5.0));
+
+        let outer = left
+            .join(&right, &["id".to_string()], JoinKind::Left)
+            .unwrap();
+        assert_eq!(outer.nrows(), 2);
+        match outer.column("y").unwrap().cell(1) {
+            Cell::Number(n) => assert!(n.is_nan()),
+            other => panic!("Expected number, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_nan_keys_group_as_missing() {
+        let df = DataFrame::new(vec![
+            (
+                "k".to_string(),
+                Column::numbers(vec![f64::NAN, 1.0, -f64::NAN, 1.0]),
+            ),
+            ("v".to_string(), Column::numbers(vec![1.0, 2.0, 3.0, 4.0])),
+        ])
+        .unwrap()
+        .group_by(&["k".to_string()])
+        .unwrap();
+        assert_eq!(df.groups().unwrap(), vec![vec![0, 2], vec![1, 3]]);
+    }
+}
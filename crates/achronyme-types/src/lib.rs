@@ -1,7 +1,9 @@
 pub mod complex;
+pub mod dataframe;
 pub mod environment;
 pub mod function;
 pub mod lambda_evaluator;
+pub mod parallel;
 pub mod sync;
 pub mod tensor;
 pub mod value;
@@ -0,0 +1,99 @@
+//! Shared worker pool for data-parallel work
+//!
+//! Element-wise tensor kernels and the `par_*` builtins run on a rayon
+//! work-stealing pool. By default that is rayon's global pool (one thread
+//! per core); `set_threads(n)` swaps in a dedicated pool of `n` threads.
+
+use parking_lot::{const_rwlock, RwLock};
+use rayon::prelude::*;
+use std::sync::Arc;
+
+/// Below this many elements, threading overhead outweighs the gain
+pub const PARALLEL_THRESHOLD: usize = 1 << 15;
+
+/// Dedicated pool installed by `set_threads`; None = rayon's global pool
+static POOL: RwLock<Option<Arc<rayon::ThreadPool>>> = const_rwlock(None);
+
+/// Use a pool of `n` worker threads, or go back to the default with `n == 0`
+pub fn set_threads(n: usize) -> Result<(), String> {
+    let pool = if n == 0 {
+        None
+    } else {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(n)
+            .build()
+            .map_err(|e| format!("Failed to create thread pool: {}", e))?;
+        Some(Arc::new(pool))
+    };
+    *POOL.write() = pool;
+    Ok(())
+}
+
+/// Number of worker threads parallel work is spread over
+pub fn threads() -> usize {
+    match POOL.read().as_ref() {
+        Some(pool) => pool.current_num_threads(),
+        None => rayon::current_num_threads(),
+    }
+}
+
+/// Run `f` inside the configured pool, so rayon iterators in it use that pool
+pub fn install<R, F>(f: F) -> R
+where
+    R: Send,
+    F: FnOnce() -> R + Send,
+{
+    let pool = POOL.read().clone();
+    match pool {
+        Some(pool) => pool.install(f),
+        None => f(),
+    }
+}
+
+/// `data.iter().map(f)`, split across the pool for large inputs
+pub fn map<T, U, F>(data: &[T], f: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync + Send,
+{
+    if data.len() < PARALLEL_THRESHOLD {
+        return data.iter().map(f).collect();
+    }
+    install(|| data.par_iter().map(f).collect())
+}
+
+/// Element-wise `f(a[i], b[i])`, split across the pool for large inputs
+pub fn zip_map<T, U, F>(a: &[T], b: &[T], f: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T, &T) -> U + Sync + Send,
+{
+    if a.len() < PARALLEL_THRESHOLD {
+        return a.iter().zip(b.iter()).map(|(x, y)| f(x, y)).collect();
+    }
+    install(|| {
+        a.par_iter()
+            .zip(b.par_iter())
+            .map(|(x, y)| f(x, y))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kernels_match_sequential() {
+        let n = PARALLEL_THRESHOLD + 7;
+        let a: Vec<f64> = (0..n).map(|i| i as f64).collect();
+        let b: Vec<f64> = (0..n).map(|i| (n - i) as f64).collect();
+
+        let sums = zip_map(&a, &b, |x, y| x + y);
+        assert!(sums.iter().all(|&s| s == n as f64));
+        let doubled = map(&a, |x| x * 2.0);
+        assert_eq!(doubled[n - 1], 2.0 * (n - 1) as f64);
+    }
+}
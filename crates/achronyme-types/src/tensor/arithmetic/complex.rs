@@ -1,4 +1,5 @@
 use crate::complex::Complex;
+use crate::parallel;
 use crate::tensor::core::{ComplexTensor, RealTensor, Tensor, TensorError};
 
 // ============================================================================
@@ -10,12 +11,7 @@ impl ComplexTensor {
     pub fn add(&self, other: &ComplexTensor) -> Result<ComplexTensor, TensorError> {
         // Fast path: if shapes are identical, use direct element-wise operation
         if self.shape == other.shape {
-            let data: Vec<Complex> = self
-                .data
-                .iter()
-                .zip(other.data.iter())
-                .map(|(a, b)| *a + *b)
-                .collect();
+            let data = parallel::zip_map(&self.data, &other.data, |a, b| *a + *b);
             return ComplexTensor::new(data, self.shape.clone());
         }
 
@@ -46,12 +42,7 @@ impl ComplexTensor {
     pub fn sub(&self, other: &ComplexTensor) -> Result<ComplexTensor, TensorError> {
         // Fast path: if shapes are identical, use direct element-wise operation
         if self.shape == other.shape {
-            let data: Vec<Complex> = self
-                .data
-                .iter()
-                .zip(other.data.iter())
-                .map(|(a, b)| *a - *b)
-                .collect();
+            let data = parallel::zip_map(&self.data, &other.data, |a, b| *a - *b);
             return ComplexTensor::new(data, self.shape.clone());
         }
 
@@ -82,12 +73,7 @@ impl ComplexTensor {
     pub fn mul(&self, other: &ComplexTensor) -> Result<ComplexTensor, TensorError> {
         // Fast path: if shapes are identical, use direct element-wise operation
         if self.shape == other.shape {
-            let data: Vec<Complex> = self
-                .data
-                .iter()
-                .zip(other.data.iter())
-                .map(|(a, b)| *a * *b)
-                .collect();
+            let data = parallel::zip_map(&self.data, &other.data, |a, b| *a * *b);
             return ComplexTensor::new(data, self.shape.clone());
         }
 
@@ -118,12 +104,7 @@ impl ComplexTensor {
     pub fn div(&self, other: &ComplexTensor) -> Result<ComplexTensor, TensorError> {
         // Fast path: if shapes are identical, use direct element-wise operation
         if self.shape == other.shape {
-            let data: Vec<Complex> = self
-                .data
-                .iter()
-                .zip(other.data.iter())
-                .map(|(a, b)| *a / *b)
-                .collect();
+            let data = parallel::zip_map(&self.data, &other.data, |a, b| *a / *b);
             return ComplexTensor::new(data, self.shape.clone());
         }
 
@@ -152,19 +133,19 @@ impl ComplexTensor {
 
     /// Scalar addition
     pub fn add_scalar(&self, scalar: Complex) -> ComplexTensor {
-        let data: Vec<Complex> = self.data.iter().map(|x| *x + scalar).collect();
+        let data = parallel::map(&self.data, |x| *x + scalar);
         ComplexTensor::new(data, self.shape.clone()).unwrap()
     }
 
     /// Scalar subtraction (tensor - scalar)
     pub fn sub_scalar(&self, scalar: Complex) -> ComplexTensor {
-        let data: Vec<Complex> = self.data.iter().map(|x| *x - scalar).collect();
+        let data = parallel::map(&self.data, |x| *x - scalar);
         ComplexTensor::new(data, self.shape.clone()).unwrap()
     }
 
     /// Scalar multiplication
     pub fn mul_scalar(&self, scalar: Complex) -> ComplexTensor {
-        let data: Vec<Complex> = self.data.iter().map(|x| *x * scalar).collect();
+        let data = parallel::map(&self.data, |x| *x * scalar);
         ComplexTensor::new(data, self.shape.clone()).unwrap()
     }
 
@@ -173,13 +154,13 @@ impl ComplexTensor {
         if scalar.re == 0.0 && scalar.im == 0.0 {
             return Err("Division by zero".to_string());
         }
-        let data: Vec<Complex> = self.data.iter().map(|x| *x / scalar).collect();
+        let data = parallel::map(&self.data, |x| *x / scalar);
         Ok(ComplexTensor::new(data, self.shape.clone()).unwrap())
     }
 
     /// Negate all elements
     pub fn negate(&self) -> ComplexTensor {
-        let data: Vec<Complex> = self.data.iter().map(|x| -*x).collect();
+        let data = parallel::map(&self.data, |x| -*x);
         ComplexTensor::new(data, self.shape.clone()).unwrap()
     }
 
@@ -1,3 +1,4 @@
+use crate::parallel;
 use crate::tensor::core::{RealTensor, Tensor, TensorError};
 
 // ============================================================================
@@ -9,12 +10,7 @@ impl RealTensor {
     pub fn add(&self, other: &RealTensor) -> Result<RealTensor, TensorError> {
         // Fast path: if shapes are identical, use direct element-wise operation
         if self.shape == other.shape {
-            let data: Vec<f64> = self
-                .data
-                .iter()
-                .zip(other.data.iter())
-                .map(|(a, b)| a + b)
-                .collect();
+            let data = parallel::zip_map(&self.data, &other.data, |a, b| a + b);
             return RealTensor::new(data, self.shape.clone());
         }
 
@@ -45,12 +41,7 @@ impl RealTensor {
     pub fn sub(&self, other: &RealTensor) -> Result<RealTensor, TensorError> {
         // Fast path: if shapes are identical, use direct element-wise operation
         if self.shape == other.shape {
-            let data: Vec<f64> = self
-                .data
-                .iter()
-                .zip(other.data.iter())
-                .map(|(a, b)| a - b)
-                .collect();
+            let data = parallel::zip_map(&self.data, &other.data, |a, b| a - b);
             return RealTensor::new(data, self.shape.clone());
         }
 
@@ -81,12 +72,7 @@ impl RealTensor {
     pub fn mul(&self, other: &RealTensor) -> Result<RealTensor, TensorError> {
         // Fast path: if shapes are identical, use direct element-wise operation
         if self.shape == other.shape {
-            let data: Vec<f64> = self
-                .data
-                .iter()
-                .zip(other.data.iter())
-                .map(|(a, b)| a * b)
-                .collect();
+            let data = parallel::zip_map(&self.data, &other.data, |a, b| a * b);
             return RealTensor::new(data, self.shape.clone());
         }
 
@@ -117,12 +103,7 @@ impl RealTensor {
     pub fn div(&self, other: &RealTensor) -> Result<RealTensor, TensorError> {
         // Fast path: if shapes are identical, use direct element-wise operation
         if self.shape == other.shape {
-            let data: Vec<f64> = self
-                .data
-                .iter()
-                .zip(other.data.iter())
-                .map(|(a, b)| a / b)
-                .collect();
+            let data = parallel::zip_map(&self.data, &other.data, |a, b| a / b);
             return RealTensor::new(data, self.shape.clone());
         }
 
@@ -151,19 +132,19 @@ impl RealTensor {
 
     /// Scalar addition
     pub fn add_scalar(&self, scalar: f64) -> RealTensor {
-        let data: Vec<f64> = self.data.iter().map(|x| x + scalar).collect();
+        let data = parallel::map(&self.data, |x| x + scalar);
         RealTensor::new(data, self.shape.clone()).unwrap()
     }
 
     /// Scalar subtraction (tensor - scalar)
     pub fn sub_scalar(&self, scalar: f64) -> RealTensor {
-        let data: Vec<f64> = self.data.iter().map(|x| x - scalar).collect();
+        let data = parallel::map(&self.data, |x| x - scalar);
         RealTensor::new(data, self.shape.clone()).unwrap()
     }
 
     /// Scalar multiplication
     pub fn mul_scalar(&self, scalar: f64) -> RealTensor {
-        let data: Vec<f64> = self.data.iter().map(|x| x * scalar).collect();
+        let data = parallel::map(&self.data, |x| x * scalar);
         RealTensor::new(data, self.shape.clone()).unwrap()
     }
 
@@ -172,13 +153,13 @@ impl RealTensor {
         if scalar == 0.0 {
             return Err("Division by zero".to_string());
         }
-        let data: Vec<f64> = self.data.iter().map(|x| x / scalar).collect();
+        let data = parallel::map(&self.data, |x| x / scalar);
         Ok(RealTensor::new(data, self.shape.clone()).unwrap())
     }
 
     /// Negate all elements
     pub fn negate(&self) -> RealTensor {
-        let data: Vec<f64> = self.data.iter().map(|x| -x).collect();
+        let data = parallel::map(&self.data, |x| -x);
         RealTensor::new(data, self.shape.clone()).unwrap()
     }
 }
@@ -66,4 +66,39 @@ impl ComplexTensor {
 
         Ok(sum)
     }
+
+    /// Matrix multiplication
+    pub fn matmul(&self, other: &ComplexTensor) -> Result<ComplexTensor, TensorError> {
+        if !self.is_matrix() || !other.is_matrix() {
+            return Err(TensorError::DimensionMismatch {
+                expected: vec![0, 0],
+                got: self.shape.clone(),
+            });
+        }
+
+        let m = self.shape[0];
+        let n = self.shape[1];
+        let p = other.shape[1];
+
+        if n != other.shape[0] {
+            return Err(TensorError::DimensionMismatch {
+                expected: vec![n, 0],
+                got: other.shape.clone(),
+            });
+        }
+
+        let mut data = vec![Complex::new(0.0, 0.0); m * p];
+
+        for i in 0..m {
+            for j in 0..p {
+                let mut sum = Complex::new(0.0, 0.0);
+                for k in 0..n {
+                    sum = sum + self.data[i * n + k] * other.data[k * p + j];
+                }
+                data[i * p + j] = sum;
+            }
+        }
+
+        ComplexTensor::new(data, vec![m, p])
+    }
 }
@@ -282,6 +282,20 @@ fn test_matrix_multiplication() {
     assert_eq!(*c.get(&[1, 1]).unwrap(), 154.0);
 }
 
+#[test]
+fn test_complex_matrix_multiplication() {
+    let i = Complex::new(0.0, 1.0);
+    let one = Complex::new(1.0, 0.0);
+    let a = ComplexTensor::new(vec![one, i], vec![1, 2]).unwrap();
+    let b = ComplexTensor::new(vec![i, one], vec![2, 1]).unwrap();
+
+    // 1*i + i*1
+    let c = a.matmul(&b).unwrap();
+    assert_eq!(c.shape(), &[1, 1]);
+    assert_eq!(*c.get(&[0, 0]).unwrap(), Complex::new(0.0, 2.0));
+    assert!(a.matmul(&a).is_err());
+}
+
 #[test]
 fn test_complex_hermitian() {
     let m = ComplexTensor::zeros(vec![2, 2]);
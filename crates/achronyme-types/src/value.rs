@@ -1,6 +1,7 @@
 use crate::complex::Complex;
+use crate::dataframe::DataFrame;
 use crate::function::Function;
-use crate::sync::{shared, Arc, RwLock, Shared};
+use crate::sync::{shared, Arc, Mutex, RwLock, Shared};
 use crate::tensor::{ComplexTensor, RealTensor};
 use futures::future::{FutureExt, Shared as FuturesShared};
 use std::any::Any;
@@ -40,6 +41,19 @@ impl std::fmt::Debug for VmFuture {
     }
 }
 
+/// Handle to a spawned task: its (shared) result plus a way to cancel it
+#[derive(Clone)]
+pub struct TaskHandle {
+    pub result: VmFuture,
+    pub abort: tokio::task::AbortHandle,
+}
+
+impl std::fmt::Debug for TaskHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Task(done: {})", self.abort.is_finished())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Value {
     Number(f64),
@@ -112,28 +126,87 @@ pub enum Value {
         receiver: Box<Value>,
         method_name: String,
     },
-    /// Channel Sender
-    Sender(Arc<tokio::sync::mpsc::UnboundedSender<Value>>),
-    /// Channel Receiver
-    Receiver(Arc<tokio::sync::Mutex<tokio::sync::mpsc::UnboundedReceiver<Value>>>),
+    /// Channel Sender (mpsc, broadcast or watch)
+    Sender(Arc<ChannelSender>),
+    /// Channel Receiver (mpsc, broadcast or watch)
+    Receiver(Arc<tokio::sync::Mutex<ChannelReceiver>>),
     /// Async Mutex
     AsyncMutex(std::sync::Arc<tokio::sync::Mutex<Value>>),
     /// Mutex Guard (owned)
     MutexGuard(Shared<tokio::sync::OwnedMutexGuard<Value>>),
-    /// Reactive Signal
+    /// Reactive Signal (plain or computed)
     Signal(Shared<SignalState>),
+    /// Handle to a running effect (returned by `effect()`, used to dispose it)
+    Effect(Shared<EffectState>),
+    /// Columnar table of named, typed columns (immutable, shared)
+    DataFrame(Arc<DataFrame>),
+    /// Immutable byte buffer (file contents, packed binary records)
+    Bytes(Arc<Vec<u8>>),
+    /// Spawned task (awaitable, cancellable)
+    Task(TaskHandle),
+    /// Value of an algebraic data type: one variant with its fields (immutable)
+    Variant(Arc<VariantValue>),
+}
+
+/// A constructed variant of a `type Name = A {..} | B {..}` declaration
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantValue {
+    /// Name of the declared type (e.g. `Shape`)
+    pub type_name: String,
+    /// Name of the variant (e.g. `Circle`)
+    pub variant: String,
+    /// Field values in declaration order
+    pub fields: Vec<(String, Value)>,
+}
+
+impl VariantValue {
+    /// Value of a field, if the variant has it
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value)
+    }
+
+    /// `Circle { r: 2 }`, or just `Leaf` for a variant without fields,
+    /// formatting each field value with `format_field`
+    pub fn format_with(&self, format_field: impl Fn(&Value) -> String) -> String {
+        if self.fields.is_empty() {
+            return self.variant.clone();
+        }
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, format_field(value)))
+            .collect();
+        format!("{} {{ {} }}", self.variant, fields.join(", "))
+    }
 }
 
 /// State of a reactive signal
 #[derive(Debug, Clone)]
 pub struct SignalState {
     pub value: Value,
-    /// List of effects that depend on this signal
+    /// List of effects and computations that depend on this signal
     /// Use Weak references to avoid cycles (Signal -> Effect -> Closure -> Signal)
     pub subscribers: Vec<std::sync::Weak<RwLock<EffectState>>>,
+    /// For computed signals: the computation that derives `value`.
+    /// Strong, so the computation lives exactly as long as the signal.
+    pub computation: Option<Shared<EffectState>>,
 }
 
-/// State of an effect
+/// Freshness of a reactive computation during propagation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReactiveState {
+    /// Up to date
+    Clean,
+    /// An upstream computed signal may have changed; check before running
+    Check,
+    /// A direct dependency changed; must re-run
+    Dirty,
+}
+
+/// State of an effect or of the computation behind a computed signal
 #[derive(Debug, Clone)]
 pub struct EffectState {
     /// The function to execute when dependencies change
@@ -143,6 +216,133 @@ pub struct EffectState {
     /// even if they are not referenced elsewhere (though arguably if a signal is dropped,
     /// the effect shouldn't run anymore).
     pub dependencies: Vec<Shared<SignalState>>,
+    /// Signal written by this computation (None for plain effects)
+    pub target: Option<std::sync::Weak<RwLock<SignalState>>>,
+    pub state: ReactiveState,
+    /// Disposed effects never run again
+    pub disposed: bool,
+    /// Inbox of the VM that created this effect; updates coming from other
+    /// VMs are posted there instead of running the effect in place.
+    /// Empty for computed signals, which run wherever they are read.
+    pub owner: std::sync::Weak<EffectInbox>,
+    /// Effects created while this one was running; disposed before it
+    /// re-runs or is disposed itself
+    pub children: Vec<Shared<EffectState>>,
+}
+
+/// Sending half of a channel. Every copy of a Sender value shares it,
+/// so `close()` takes the inner sender away for all of them.
+#[derive(Debug)]
+pub struct ChannelSender(pub Mutex<Option<SenderKind>>);
+
+#[derive(Debug, Clone)]
+pub enum SenderKind {
+    Unbounded(tokio::sync::mpsc::UnboundedSender<Value>),
+    Bounded(tokio::sync::mpsc::Sender<Value>),
+    Broadcast(tokio::sync::broadcast::Sender<Value>),
+    Watch(Arc<tokio::sync::watch::Sender<Value>>),
+}
+
+impl ChannelSender {
+    pub fn new(kind: SenderKind) -> Self {
+        ChannelSender(Mutex::new(Some(kind)))
+    }
+
+    /// The live sender, or None once closed
+    pub fn get(&self) -> Option<SenderKind> {
+        self.0.lock().clone()
+    }
+
+    pub fn close(&self) {
+        self.0.lock().take();
+    }
+}
+
+/// Receiving half of a channel
+#[derive(Debug)]
+pub enum ChannelReceiver {
+    Unbounded(tokio::sync::mpsc::UnboundedReceiver<Value>),
+    Bounded(tokio::sync::mpsc::Receiver<Value>),
+    Broadcast(tokio::sync::broadcast::Receiver<Value>),
+    Watch(tokio::sync::watch::Receiver<Value>),
+    /// A broadcast or watch receiver that was closed
+    Closed,
+}
+
+impl ChannelReceiver {
+    /// Wait for the next message; None once the channel is closed and drained.
+    /// Broadcast receivers that fall behind skip the messages they missed.
+    pub async fn recv(&mut self) -> Option<Value> {
+        match self {
+            ChannelReceiver::Unbounded(rx) => rx.recv().await,
+            ChannelReceiver::Bounded(rx) => rx.recv().await,
+            ChannelReceiver::Broadcast(rx) => loop {
+                match rx.recv().await {
+                    Ok(value) => return Some(value),
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+                }
+            },
+            ChannelReceiver::Watch(rx) => match rx.changed().await {
+                Ok(()) => Some(rx.borrow_and_update().clone()),
+                Err(_) => None,
+            },
+            ChannelReceiver::Closed => None,
+        }
+    }
+
+    /// Next message if one is available right now
+    pub fn try_recv(&mut self) -> Option<Value> {
+        match self {
+            ChannelReceiver::Unbounded(rx) => rx.try_recv().ok(),
+            ChannelReceiver::Bounded(rx) => rx.try_recv().ok(),
+            ChannelReceiver::Broadcast(rx) => loop {
+                match rx.try_recv() {
+                    Ok(value) => return Some(value),
+                    Err(tokio::sync::broadcast::error::TryRecvError::Lagged(_)) => continue,
+                    Err(_) => return None,
+                }
+            },
+            ChannelReceiver::Watch(rx) => match rx.has_changed() {
+                Ok(true) => Some(rx.borrow_and_update().clone()),
+                _ => None,
+            },
+            ChannelReceiver::Closed => None,
+        }
+    }
+
+    /// Stop accepting messages. mpsc receivers can still drain what is buffered.
+    pub fn close(&mut self) {
+        match self {
+            ChannelReceiver::Unbounded(rx) => rx.close(),
+            ChannelReceiver::Bounded(rx) => rx.close(),
+            _ => *self = ChannelReceiver::Closed,
+        }
+    }
+}
+
+/// Effects invalidated by another VM or task, waiting for their owning VM
+#[derive(Debug, Default)]
+pub struct EffectInbox {
+    pub queue: Mutex<Vec<Shared<EffectState>>>,
+    /// Woken whenever something is posted, for owners that sleep between
+    /// polls (e.g. a GUI event loop) rather than at an `await`
+    pub waker: Mutex<Option<std::task::Waker>>,
+}
+
+impl EffectInbox {
+    /// Post an invalidated effect and wake the owner
+    pub fn post(&self, effect: Shared<EffectState>) {
+        self.queue.lock().push(effect);
+        self.notify();
+    }
+
+    /// Wake the owner, if it registered a waker
+    pub fn notify(&self) {
+        if let Some(waker) = self.waker.lock().as_ref() {
+            waker.wake_by_ref();
+        }
+    }
 }
 
 // Conversiones automáticas con From/Into
@@ -411,6 +611,11 @@ impl PartialEq for Value {
             (Value::AsyncMutex(a), Value::AsyncMutex(b)) => std::sync::Arc::ptr_eq(a, b),
             (Value::MutexGuard(a), Value::MutexGuard(b)) => Arc::ptr_eq(a, b),
             (Value::Signal(a), Value::Signal(b)) => Arc::ptr_eq(a, b),
+            (Value::Effect(a), Value::Effect(b)) => Arc::ptr_eq(a, b),
+            (Value::Task(a), Value::Task(b)) => a.abort.id() == b.abort.id(),
+            (Value::DataFrame(a), Value::DataFrame(b)) => a == b,
+            (Value::Bytes(a), Value::Bytes(b)) => a == b,
+            (Value::Variant(a), Value::Variant(b)) => a == b,
             _ => false,
         }
     }
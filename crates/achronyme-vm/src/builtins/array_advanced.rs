@@ -12,7 +12,7 @@
 
 use crate::error::VmError;
 use crate::value::Value;
-use crate::vm::VM;
+use crate::vm::{LazyIterator, VM};
 use achronyme_types::complex::Complex;
 use achronyme_types::sync::shared;
 use std::collections::HashSet;
@@ -79,6 +79,7 @@ pub fn vm_product(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
 /// Combine two arrays element-wise into pairs
 ///
 /// Example: zip([1, 2, 3], [4, 5, 6]) -> [[1, 4], [2, 5], [3, 6]]
+/// With a generator or iterator on either side the result is a lazy iterator.
 pub fn vm_zip(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     if args.len() != 2 {
         return Err(VmError::Runtime(format!(
@@ -87,6 +88,15 @@ pub fn vm_zip(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
         )));
     }
 
+    // Generators and iterators are zipped lazily
+    if LazyIterator::is_lazy(&args[0]) || LazyIterator::is_lazy(&args[1]) {
+        return Ok(LazyIterator::Zip {
+            left: LazyIterator::from_value(&args[0])?,
+            right: LazyIterator::from_value(&args[1])?,
+        }
+        .into_value());
+    }
+
     match (&args[0], &args[1]) {
         (Value::Vector(v1), Value::Vector(v2)) => {
             let vec1 = v1.read();
@@ -174,6 +184,7 @@ fn flatten_recursive(vec: &[Value], depth: usize) -> Vec<Value> {
 /// Take the first n elements from an array
 ///
 /// Example: take([1, 2, 3, 4, 5], 3) -> [1, 2, 3]
+/// Generators and iterators give a lazy iterator stopping after n values.
 pub fn vm_take(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     if args.len() != 2 {
         return Err(VmError::Runtime(format!(
@@ -183,8 +194,13 @@ pub fn vm_take(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     }
 
     match (&args[0], &args[1]) {
+        (source, Value::Number(n)) if LazyIterator::is_lazy(source) => Ok(LazyIterator::Take {
+            source: LazyIterator::from_value(source)?,
+            remaining: *n as usize,
+        }
+        .into_value()),
         (Value::Vector(rc), Value::Number(n)) => {
-            let n = (*n as usize).max(0);
+            let n = *n as usize;
             let vec = rc.read();
             let result = vec.iter().take(n).cloned().collect();
             Ok(Value::Vector(shared(result)))
@@ -210,7 +226,7 @@ pub fn vm_drop(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
 
     match (&args[0], &args[1]) {
         (Value::Vector(rc), Value::Number(n)) => {
-            let n = (*n as usize).max(0);
+            let n = *n as usize;
             let vec = rc.read();
             let result = vec.iter().skip(n).cloned().collect();
             Ok(Value::Vector(shared(result)))
@@ -1,10 +1,10 @@
 //! Async built-in functions
 
-use crate::bytecode::Closure;
+use crate::builtins::tasks::spawn_child;
 use crate::error::VmError;
 use crate::value::Value;
-use crate::vm::{CallFrame, VM};
-use achronyme_types::function::Function;
+use crate::vm::VM;
+use achronyme_types::sync::Arc;
 use achronyme_types::value::VmFuture;
 use std::time::Duration;
 
@@ -45,8 +45,9 @@ pub fn vm_sleep(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     Ok(Value::Future(VmFuture::new(future)))
 }
 
-/// spawn(func, ...args) -> Future
+/// spawn(func, ...args) -> Task
 /// Spawns a new task running the given function.
+/// Inside a `task_group()` scope the task joins that group.
 pub fn vm_spawn(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     if args.is_empty() {
         return Err(VmError::Runtime(
@@ -54,69 +55,11 @@ pub fn vm_spawn(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
         ));
     }
 
-    let func = args[0].clone();
-    let func_args = args[1..].to_vec();
-
-    // Create child VM
-    let mut child_vm = vm.new_child();
-
-    // Set up call frame in child VM
-    match &func {
-        Value::Function(Function::VmClosure(closure_any)) => {
-            let closure = closure_any
-                .downcast_ref::<Closure>()
-                .ok_or(VmError::Runtime("Invalid VmClosure type".to_string()))?;
-
-            // Create frame
-            let mut new_frame = CallFrame::new(closure.prototype.clone(), None);
-            new_frame.upvalues = closure.upvalues.clone();
-
-            // Copy arguments
-            for (i, arg) in func_args.iter().enumerate() {
-                if i >= 256 {
-                    return Err(VmError::Runtime("Too many arguments (max 256)".into()));
-                }
-                new_frame.registers.set(i as u8, arg.clone())?;
-            }
-
-            child_vm.frames.push(new_frame);
-        }
-        _ => {
-            return Err(VmError::TypeError {
-                operation: "spawn".to_string(),
-                expected: "Function".to_string(),
-                got: format!("{:?}", func),
-            })
-        }
+    let task = spawn_child(vm, &args[0], &args[1..], "spawn", None)?;
+    if let Some(group) = &vm.task_group {
+        group.add(&task);
     }
-
-    // Spawn future on Tokio runtime (multi-threaded)
-    // The future runs the VM and returns the result
-    let future = async move { child_vm.run().await };
-
-    // We use tokio::spawn because Value is now Send (Arc<RwLock>)
-    let handle = tokio::spawn(future);
-
-    // Wrap the handle in a VmFuture so it can be awaited in Achronyme
-    let vm_future = async move {
-        match handle.await {
-            Ok(Ok(v)) => v, // Task succeeded and VM returned Value
-            Ok(Err(e)) => Value::Error {
-                // Task succeeded but VM returned Error
-                message: e.to_string(),
-                kind: Some("RuntimeError".into()),
-                source: None,
-            },
-            Err(e) => Value::Error {
-                // Task failed (panic/cancellation)
-                message: format!("Task execution error: {}", e),
-                kind: Some("TaskError".into()),
-                source: None,
-            },
-        }
-    };
-
-    Ok(Value::Future(VmFuture::new(vm_future)))
+    Ok(Value::Task(task))
 }
 
 /// read_file(path) -> Future
@@ -200,6 +143,78 @@ pub fn vm_write_file(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     Ok(Value::Future(VmFuture::new(future)))
 }
 
+/// read_bytes(path) -> Future
+/// Asynchronously reads a file and returns its content as Bytes.
+pub fn vm_read_bytes(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 1 {
+        return Err(VmError::Runtime(format!(
+            "read_bytes() expects 1 argument, got {}",
+            args.len()
+        )));
+    }
+
+    let path = match &args[0] {
+        Value::String(s) => s.to_string(),
+        _ => {
+            return Err(VmError::TypeError {
+                operation: "read_bytes".to_string(),
+                expected: "String".to_string(),
+                got: format!("{:?}", args[0]),
+            })
+        }
+    };
+
+    let future = async move {
+        match tokio::fs::read(&path).await {
+            Ok(content) => Value::Bytes(Arc::new(content)),
+            Err(e) => Value::Error {
+                message: format!("Failed to read file '{}': {}", path, e),
+                kind: Some("IOError".into()),
+                source: None,
+            },
+        }
+    };
+
+    Ok(Value::Future(VmFuture::new(future)))
+}
+
+/// write_bytes(path, data) -> Future
+/// Asynchronously writes Bytes (or anything `bytes()` accepts) to a file.
+pub fn vm_write_bytes(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 2 {
+        return Err(VmError::Runtime(format!(
+            "write_bytes() expects 2 arguments, got {}",
+            args.len()
+        )));
+    }
+
+    let path = match &args[0] {
+        Value::String(s) => s.to_string(),
+        _ => {
+            return Err(VmError::TypeError {
+                operation: "write_bytes".to_string(),
+                expected: "String".to_string(),
+                got: format!("{:?}", args[0]),
+            })
+        }
+    };
+
+    let content = crate::builtins::binary::to_bytes(&args[1], "write_bytes")?;
+
+    let future = async move {
+        match tokio::fs::write(&path, content).await {
+            Ok(_) => Value::Null,
+            Err(e) => Value::Error {
+                message: format!("Failed to write file '{}': {}", path, e),
+                kind: Some("IOError".into()),
+                source: None,
+            },
+        }
+    };
+
+    Ok(Value::Future(VmFuture::new(future)))
+}
+
 /// append_file(path, content) -> Future
 /// Asynchronously appends content to a file.
 pub fn vm_append_file(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
@@ -0,0 +1,620 @@
+//! Binary data built-ins
+//!
+//! This module provides the `Bytes` value type and structured binary codecs:
+//! - bytes: Build a byte buffer from a string, numeric vector or tensor
+//! - pack / unpack: Encode and decode fixed-layout binary records
+//! - pack_size: Size in bytes of a fixed layout
+//! - Methods: len, slice, to_vector, to_string, unpack
+//!
+//! Layouts are written as an optional byte-order prefix (`<` little-endian,
+//! the default, or `>`/`!` big-endian) followed by comma- or space-separated
+//! items: `f32`, `i16[3]`, `u8[*]` (all remaining bytes), `pad[2]`, or named
+//! items such as `temp:f32` which make `unpack` return a record.
+
+use crate::error::VmError;
+use crate::value::Value;
+use crate::vm::VM;
+use achronyme_types::sync::{shared, Arc};
+use achronyme_types::tensor::RealTensor;
+use std::collections::HashMap;
+
+// ============================================================================
+// Scalar Codec
+// ============================================================================
+
+/// Fixed-size numeric type used by pack/unpack and the NPY codec
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Scalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    F32,
+    F64,
+}
+
+macro_rules! decode_as {
+    ($ty:ty, $bytes:expr, $big:expr) => {{
+        let raw: [u8; std::mem::size_of::<$ty>()] = $bytes
+            .try_into()
+            .expect("caller passes exactly size() bytes");
+        (if $big {
+            <$ty>::from_be_bytes(raw)
+        } else {
+            <$ty>::from_le_bytes(raw)
+        }) as f64
+    }};
+}
+
+macro_rules! encode_as {
+    ($ty:ty, $value:expr, $big:expr, $out:expr) => {{
+        let value = $value as $ty;
+        if $big {
+            $out.extend_from_slice(&value.to_be_bytes());
+        } else {
+            $out.extend_from_slice(&value.to_le_bytes());
+        }
+    }};
+}
+
+impl Scalar {
+    /// Parse a layout type name (`i8`, `u16`, `f32`, ...)
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "i8" => Scalar::I8,
+            "u8" => Scalar::U8,
+            "i16" => Scalar::I16,
+            "u16" => Scalar::U16,
+            "i32" => Scalar::I32,
+            "u32" => Scalar::U32,
+            "i64" => Scalar::I64,
+            "u64" => Scalar::U64,
+            "f32" => Scalar::F32,
+            "f64" => Scalar::F64,
+            _ => return None,
+        })
+    }
+
+    /// Size of one value in bytes
+    pub(crate) fn size(self) -> usize {
+        match self {
+            Scalar::I8 | Scalar::U8 => 1,
+            Scalar::I16 | Scalar::U16 => 2,
+            Scalar::I32 | Scalar::U32 | Scalar::F32 => 4,
+            Scalar::I64 | Scalar::U64 | Scalar::F64 => 8,
+        }
+    }
+
+    /// Decode one value from exactly `size()` bytes
+    pub(crate) fn decode(self, bytes: &[u8], big_endian: bool) -> f64 {
+        match self {
+            Scalar::I8 => decode_as!(i8, bytes, big_endian),
+            Scalar::U8 => decode_as!(u8, bytes, big_endian),
+            Scalar::I16 => decode_as!(i16, bytes, big_endian),
+            Scalar::U16 => decode_as!(u16, bytes, big_endian),
+            Scalar::I32 => decode_as!(i32, bytes, big_endian),
+            Scalar::U32 => decode_as!(u32, bytes, big_endian),
+            Scalar::I64 => decode_as!(i64, bytes, big_endian),
+            Scalar::U64 => decode_as!(u64, bytes, big_endian),
+            Scalar::F32 => decode_as!(f32, bytes, big_endian),
+            Scalar::F64 => decode_as!(f64, bytes, big_endian),
+        }
+    }
+
+    /// Append one value; integers are truncated toward zero and saturate
+    pub(crate) fn encode(self, value: f64, big_endian: bool, out: &mut Vec<u8>) {
+        match self {
+            Scalar::I8 => encode_as!(i8, value, big_endian, out),
+            Scalar::U8 => encode_as!(u8, value, big_endian, out),
+            Scalar::I16 => encode_as!(i16, value, big_endian, out),
+            Scalar::U16 => encode_as!(u16, value, big_endian, out),
+            Scalar::I32 => encode_as!(i32, value, big_endian, out),
+            Scalar::U32 => encode_as!(u32, value, big_endian, out),
+            Scalar::I64 => encode_as!(i64, value, big_endian, out),
+            Scalar::U64 => encode_as!(u64, value, big_endian, out),
+            Scalar::F32 => encode_as!(f32, value, big_endian, out),
+            Scalar::F64 => encode_as!(f64, value, big_endian, out),
+        }
+    }
+}
+
+// ============================================================================
+// Layouts
+// ============================================================================
+
+/// Largest fixed part of a layout, in bytes
+const MAX_LAYOUT_SIZE: usize = 1 << 30;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Count {
+    One,
+    Fixed(usize),
+    /// `[*]`: every remaining element (last item only)
+    Rest,
+}
+
+#[derive(Debug, Clone)]
+struct Item {
+    name: Option<String>,
+    /// None for padding bytes
+    scalar: Option<Scalar>,
+    count: Count,
+}
+
+impl Item {
+    fn elem_size(&self) -> usize {
+        self.scalar.map_or(1, Scalar::size)
+    }
+
+    /// Size in bytes of a fixed-count item, or None if it overflows
+    fn byte_len(&self) -> Option<usize> {
+        match self.count {
+            Count::One => Some(self.elem_size()),
+            Count::Fixed(n) => n.checked_mul(self.elem_size()),
+            Count::Rest => Some(0),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Layout {
+    big_endian: bool,
+    items: Vec<Item>,
+    named: bool,
+}
+
+impl Layout {
+    fn parse(format: &str, operation: &str) -> Result<Self, VmError> {
+        let invalid = |msg: String| VmError::Runtime(format!("{}(): {}", operation, msg));
+
+        let format = format.trim();
+        let (big_endian, body) = match format.chars().next() {
+            Some('<') => (false, &format[1..]),
+            Some('>') | Some('!') => (true, &format[1..]),
+            _ => (false, format),
+        };
+
+        let mut items = Vec::new();
+        for token in body
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|t| !t.is_empty())
+        {
+            let (name, spec) = match token.split_once(':') {
+                Some((name, spec)) => (Some(name.to_string()), spec),
+                None => (None, token),
+            };
+            let (type_name, count) = match spec.split_once('[') {
+                Some((type_name, rest)) => {
+                    let inner = rest
+                        .strip_suffix(']')
+                        .ok_or_else(|| invalid(format!("unclosed '[' in '{}'", token)))?;
+                    let count = if inner == "*" {
+                        Count::Rest
+                    } else {
+                        Count::Fixed(inner.parse().map_err(|_| {
+                            invalid(format!("invalid count '{}' in '{}'", inner, token))
+                        })?)
+                    };
+                    (type_name, count)
+                }
+                None => (spec, Count::One),
+            };
+            let scalar = match type_name {
+                "pad" => None,
+                _ => Some(
+                    Scalar::from_name(type_name)
+                        .ok_or_else(|| invalid(format!("unknown type '{}'", type_name)))?,
+                ),
+            };
+            if scalar.is_none() && name.is_some() {
+                return Err(invalid(format!("padding cannot be named ('{}')", token)));
+            }
+            items.push(Item {
+                name,
+                scalar,
+                count,
+            });
+        }
+
+        if items.is_empty() {
+            return Err(invalid("empty layout".to_string()));
+        }
+        if items[..items.len() - 1]
+            .iter()
+            .any(|item| item.count == Count::Rest)
+        {
+            return Err(invalid(
+                "'[*]' is only allowed on the last item".to_string(),
+            ));
+        }
+        let size = items
+            .iter()
+            .try_fold(0usize, |acc, item| acc.checked_add(item.byte_len()?));
+        if size.is_none_or(|size| size > MAX_LAYOUT_SIZE) {
+            return Err(invalid(format!(
+                "layout is larger than {} bytes",
+                MAX_LAYOUT_SIZE
+            )));
+        }
+        let values: Vec<&Item> = items.iter().filter(|i| i.scalar.is_some()).collect();
+        let named = values.iter().any(|i| i.name.is_some());
+        if named && values.iter().any(|i| i.name.is_none()) {
+            return Err(invalid(
+                "either every item or no item must be named".to_string(),
+            ));
+        }
+
+        Ok(Layout {
+            big_endian,
+            items,
+            named,
+        })
+    }
+
+    /// Size in bytes, or None when the layout ends with `[*]`
+    ///
+    /// `parse` has checked that the sum fits in `MAX_LAYOUT_SIZE`.
+    fn fixed_size(&self) -> Option<usize> {
+        self.items.iter().try_fold(0, |acc, item| match item.count {
+            Count::Rest => None,
+            _ => Some(acc + item.byte_len()?),
+        })
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn bytes_arg<'a>(value: &'a Value, operation: &str) -> Result<&'a Arc<Vec<u8>>, VmError> {
+    match value {
+        Value::Bytes(b) => Ok(b),
+        _ => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: "Bytes".to_string(),
+            got: format!("{:?}", value),
+        }),
+    }
+}
+
+fn string_arg<'a>(value: &'a Value, operation: &str) -> Result<&'a str, VmError> {
+    match value {
+        Value::String(s) => Ok(s),
+        _ => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: "String".to_string(),
+            got: format!("{:?}", value),
+        }),
+    }
+}
+
+fn index_arg(value: Option<&Value>, default: usize, operation: &str) -> Result<usize, VmError> {
+    match value {
+        None | Some(Value::Null) => Ok(default),
+        Some(Value::Number(n)) if *n >= 0.0 && n.fract() == 0.0 => Ok(*n as usize),
+        Some(other) => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: "non-negative integer".to_string(),
+            got: format!("{:?}", other),
+        }),
+    }
+}
+
+/// Flatten a number, numeric vector or real tensor into values
+fn numbers_of(value: &Value, operation: &str) -> Result<Vec<f64>, VmError> {
+    match value {
+        Value::Number(n) => Ok(vec![*n]),
+        Value::Boolean(b) => Ok(vec![if *b { 1.0 } else { 0.0 }]),
+        Value::Tensor(t) => Ok(t.data().to_vec()),
+        Value::Vector(v) => v
+            .read()
+            .iter()
+            .map(|item| match item {
+                Value::Number(n) => Ok(*n),
+                _ => Err(VmError::TypeError {
+                    operation: operation.to_string(),
+                    expected: "Number".to_string(),
+                    got: format!("{:?}", item),
+                }),
+            })
+            .collect(),
+        _ => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: "Number, Vector<Number> or Tensor".to_string(),
+            got: format!("{:?}", value),
+        }),
+    }
+}
+
+/// Convert a value to a byte buffer
+pub(crate) fn to_bytes(value: &Value, operation: &str) -> Result<Vec<u8>, VmError> {
+    match value {
+        Value::Bytes(b) => Ok(b.as_ref().clone()),
+        Value::String(s) => Ok(s.as_bytes().to_vec()),
+        Value::Vector(_) | Value::Tensor(_) => numbers_of(value, operation)?
+            .into_iter()
+            .map(|n| {
+                if (0.0..=255.0).contains(&n) && n.fract() == 0.0 {
+                    Ok(n as u8)
+                } else {
+                    Err(VmError::Runtime(format!(
+                        "{}(): byte values must be integers in 0..=255, got {}",
+                        operation, n
+                    )))
+                }
+            })
+            .collect(),
+        _ => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: "String, Vector<Number>, Tensor or Bytes".to_string(),
+            got: format!("{:?}", value),
+        }),
+    }
+}
+
+fn unpack_layout(
+    layout: &Layout,
+    data: &[u8],
+    offset: usize,
+    operation: &str,
+) -> Result<Value, VmError> {
+    if offset > data.len() {
+        return Err(VmError::Runtime(format!(
+            "{}(): offset {} is past the end of {} bytes",
+            operation,
+            offset,
+            data.len()
+        )));
+    }
+    let mut pos = offset;
+    let mut fields: Vec<(Option<String>, Value)> = Vec::new();
+
+    for item in &layout.items {
+        let size = item.elem_size();
+        let remaining = data.len() - pos;
+        let count = match item.count {
+            Count::One => 1,
+            Count::Fixed(n) => n,
+            Count::Rest => remaining / size,
+        };
+        let len = count
+            .checked_mul(size)
+            .filter(|len| *len <= remaining)
+            .ok_or_else(|| {
+                VmError::Runtime(format!(
+                    "{}(): need {} x {} bytes at offset {}, only {} left",
+                    operation, count, size, pos, remaining
+                ))
+            })?;
+
+        if let Some(scalar) = item.scalar {
+            let values: Vec<f64> = data[pos..pos + len]
+                .chunks_exact(size)
+                .map(|chunk| scalar.decode(chunk, layout.big_endian))
+                .collect();
+            let value = if item.count == Count::One {
+                Value::Number(values[0])
+            } else {
+                Value::Tensor(RealTensor::vector(values))
+            };
+            fields.push((item.name.clone(), value));
+        }
+        pos += len;
+    }
+
+    if layout.named {
+        let map: HashMap<String, Value> = fields
+            .into_iter()
+            .map(|(name, value)| (name.unwrap_or_default(), value))
+            .collect();
+        Ok(Value::Record(shared(map)))
+    } else {
+        Ok(Value::Vector(shared(
+            fields.into_iter().map(|(_, value)| value).collect(),
+        )))
+    }
+}
+
+fn pack_layout(layout: &Layout, values: &Value, operation: &str) -> Result<Vec<u8>, VmError> {
+    let value_items: Vec<&Item> = layout.items.iter().filter(|i| i.scalar.is_some()).collect();
+    let inputs: Vec<Value> = match values {
+        Value::Record(rc) if layout.named => {
+            let map = rc.read();
+            value_items
+                .iter()
+                .map(|item| {
+                    let name = item.name.as_deref().unwrap_or_default();
+                    map.get(name).cloned().ok_or_else(|| {
+                        VmError::Runtime(format!("{}(): missing field '{}'", operation, name))
+                    })
+                })
+                .collect::<Result<_, _>>()?
+        }
+        Value::Vector(v) if !layout.named => v.read().clone(),
+        _ => {
+            return Err(VmError::TypeError {
+                operation: operation.to_string(),
+                expected: if layout.named {
+                    "Record (named layout)".to_string()
+                } else {
+                    "Vector (unnamed layout)".to_string()
+                },
+                got: format!("{:?}", values),
+            })
+        }
+    };
+    if inputs.len() != value_items.len() {
+        return Err(VmError::Runtime(format!(
+            "{}(): layout has {} values, got {}",
+            operation,
+            value_items.len(),
+            inputs.len()
+        )));
+    }
+
+    let mut out = Vec::new();
+    let mut inputs = inputs.iter();
+    for item in &layout.items {
+        let Some(scalar) = item.scalar else {
+            let n = if let Count::Fixed(n) = item.count {
+                n
+            } else {
+                1
+            };
+            out.resize(out.len() + n, 0);
+            continue;
+        };
+        let input = inputs.next().expect("input count checked above");
+        let numbers = numbers_of(input, operation)?;
+        match item.count {
+            Count::One if numbers.len() != 1 => {
+                return Err(VmError::Runtime(format!(
+                    "{}(): expected a single number for {}, got {} values",
+                    operation,
+                    item.name.as_deref().unwrap_or("scalar item"),
+                    numbers.len()
+                )))
+            }
+            Count::Fixed(n) if numbers.len() != n => {
+                return Err(VmError::Runtime(format!(
+                    "{}(): expected {} values for {}, got {}",
+                    operation,
+                    n,
+                    item.name.as_deref().unwrap_or("array item"),
+                    numbers.len()
+                )))
+            }
+            _ => {}
+        }
+        for n in numbers {
+            scalar.encode(n, layout.big_endian, &mut out);
+        }
+    }
+    Ok(out)
+}
+
+// ============================================================================
+// Built-ins
+// ============================================================================
+
+/// bytes(value) -> Bytes
+///
+/// Accepts a String (UTF-8), a vector or tensor of integers in 0..=255, or
+/// another Bytes value.
+pub fn vm_bytes(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 1 {
+        return Err(VmError::Runtime(format!(
+            "bytes() expects 1 argument, got {}",
+            args.len()
+        )));
+    }
+    Ok(Value::Bytes(Arc::new(to_bytes(&args[0], "bytes")?)))
+}
+
+/// pack(format, values) -> Bytes
+///
+/// `values` is a vector for unnamed layouts and a record for named ones;
+/// array items take a vector or tensor of the declared length.
+pub fn vm_pack(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 2 {
+        return Err(VmError::Runtime(format!(
+            "pack() expects 2 arguments, got {}",
+            args.len()
+        )));
+    }
+    let layout = Layout::parse(string_arg(&args[0], "pack")?, "pack")?;
+    let out = pack_layout(&layout, &args[1], "pack")?;
+    Ok(Value::Bytes(Arc::new(out)))
+}
+
+/// unpack(format, bytes, offset?) -> Vector or Record
+///
+/// Scalars decode to numbers and array items to 1-D tensors.
+pub fn vm_unpack(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() < 2 || args.len() > 3 {
+        return Err(VmError::Runtime(format!(
+            "unpack() expects 2 or 3 arguments, got {}",
+            args.len()
+        )));
+    }
+    let layout = Layout::parse(string_arg(&args[0], "unpack")?, "unpack")?;
+    let data = bytes_arg(&args[1], "unpack")?;
+    let offset = index_arg(args.get(2), 0, "unpack")?;
+    unpack_layout(&layout, data, offset, "unpack")
+}
+
+/// pack_size(format) -> Number
+///
+/// Size of one record in bytes; useful for stepping through a dump.
+pub fn vm_pack_size(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 1 {
+        return Err(VmError::Runtime(format!(
+            "pack_size() expects 1 argument, got {}",
+            args.len()
+        )));
+    }
+    let layout = Layout::parse(string_arg(&args[0], "pack_size")?, "pack_size")?;
+    layout
+        .fixed_size()
+        .map(|n| Value::Number(n as f64))
+        .ok_or_else(|| {
+            VmError::Runtime("pack_size(): layout ending in '[*]' has no fixed size".to_string())
+        })
+}
+
+// ============================================================================
+// Methods
+// ============================================================================
+
+/// Bytes.len() -> Number
+pub fn vm_bytes_len(_vm: &mut VM, receiver: &Value, _args: &[Value]) -> Result<Value, VmError> {
+    let data = bytes_arg(receiver, "len")?;
+    Ok(Value::Number(data.len() as f64))
+}
+
+/// Bytes.slice(start, end?) -> Bytes
+pub fn vm_bytes_slice(_vm: &mut VM, receiver: &Value, args: &[Value]) -> Result<Value, VmError> {
+    let data = bytes_arg(receiver, "slice")?;
+    let start = index_arg(args.first(), 0, "slice")?.min(data.len());
+    let end = index_arg(args.get(1), data.len(), "slice")?.clamp(start, data.len());
+    Ok(Value::Bytes(Arc::new(data[start..end].to_vec())))
+}
+
+/// Bytes.to_vector() -> Vector<Number>
+pub fn vm_bytes_to_vector(
+    _vm: &mut VM,
+    receiver: &Value,
+    _args: &[Value],
+) -> Result<Value, VmError> {
+    let data = bytes_arg(receiver, "to_vector")?;
+    Ok(Value::Vector(shared(
+        data.iter().map(|b| Value::Number(*b as f64)).collect(),
+    )))
+}
+
+/// Bytes.to_string() -> String (UTF-8)
+pub fn vm_bytes_to_string(
+    _vm: &mut VM,
+    receiver: &Value,
+    _args: &[Value],
+) -> Result<Value, VmError> {
+    let data = bytes_arg(receiver, "to_string")?;
+    String::from_utf8(data.as_ref().clone())
+        .map(Value::String)
+        .map_err(|e| VmError::Runtime(format!("to_string(): invalid UTF-8: {}", e)))
+}
+
+/// Bytes.unpack(format, offset?) -> Vector or Record
+pub fn vm_bytes_unpack(_vm: &mut VM, receiver: &Value, args: &[Value]) -> Result<Value, VmError> {
+    let data = bytes_arg(receiver, "unpack")?;
+    let format = args
+        .first()
+        .ok_or_else(|| VmError::Runtime("unpack() expects a format argument".to_string()))?;
+    let layout = Layout::parse(string_arg(format, "unpack")?, "unpack")?;
+    let offset = index_arg(args.get(1), 0, "unpack")?;
+    unpack_layout(&layout, data, offset, "unpack")
+}
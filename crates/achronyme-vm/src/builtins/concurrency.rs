@@ -1,34 +1,184 @@
-//! Concurrency built-ins (Channels)
+//! Concurrency built-ins (Channels, select, AsyncMutex)
 
 use crate::error::VmError;
 use crate::value::Value;
 use crate::vm::VM;
 use achronyme_types::sync::{shared, Arc};
-use achronyme_types::value::VmFuture;
-use tokio::sync::mpsc;
+use achronyme_types::value::{ChannelReceiver, ChannelSender, SenderKind, VmFuture};
+use futures::future::BoxFuture;
+use futures::stream::{FuturesUnordered, StreamExt};
+use futures::FutureExt;
+use std::collections::HashMap;
+use tokio::sync::{broadcast, mpsc, watch};
+
+fn channel_pair(sender: SenderKind, receiver: ChannelReceiver) -> Value {
+    let sender = Value::Sender(Arc::new(ChannelSender::new(sender)));
+    let receiver = Value::Receiver(Arc::new(tokio::sync::Mutex::new(receiver)));
+    Value::Vector(shared(vec![sender, receiver]))
+}
+
+fn capacity_arg(value: &Value, operation: &str) -> Result<usize, VmError> {
+    match value {
+        Value::Number(n) if *n >= 1.0 && n.fract() == 0.0 => Ok(*n as usize),
+        Value::Number(n) => Err(VmError::Runtime(format!(
+            "{}() capacity must be a positive integer, got {}",
+            operation, n
+        ))),
+        _ => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: "Number".to_string(),
+            got: format!("{:?}", value),
+        }),
+    }
+}
+
+fn channel_closed() -> Value {
+    Value::Error {
+        message: "Channel closed".to_string(),
+        kind: Some("ChannelError".into()),
+        source: None,
+    }
+}
 
 /// channel() -> [Sender, Receiver]
-/// Creates an unbounded mpsc channel.
+/// channel(capacity) -> [Sender, Receiver]
+/// Creates an mpsc channel: unbounded, or bounded with back-pressure
+/// (`send` waits while `capacity` messages are queued).
 pub fn vm_channel(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
-    if !args.is_empty() {
+    match args {
+        [] => {
+            let (tx, rx) = mpsc::unbounded_channel();
+            Ok(channel_pair(
+                SenderKind::Unbounded(tx),
+                ChannelReceiver::Unbounded(rx),
+            ))
+        }
+        [capacity] => {
+            let (tx, rx) = mpsc::channel(capacity_arg(capacity, "channel")?);
+            Ok(channel_pair(
+                SenderKind::Bounded(tx),
+                ChannelReceiver::Bounded(rx),
+            ))
+        }
+        _ => Err(VmError::Runtime(format!(
+            "channel() expects 0 or 1 arguments (capacity), got {}",
+            args.len()
+        ))),
+    }
+}
+
+/// broadcast(capacity) -> [Sender, Receiver]
+/// Every receiver gets every message; more receivers via `tx.subscribe()`.
+/// A receiver that falls more than `capacity` messages behind skips ahead.
+pub fn vm_broadcast(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let (tx, rx) = broadcast::channel(capacity_arg(&args[0], "broadcast")?);
+    Ok(channel_pair(
+        SenderKind::Broadcast(tx),
+        ChannelReceiver::Broadcast(rx),
+    ))
+}
+
+/// watch(initial) -> [Sender, Receiver]
+/// Holds a single latest value; `recv()` waits for the next change.
+pub fn vm_watch(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let (tx, rx) = watch::channel(args[0].clone());
+    Ok(channel_pair(
+        SenderKind::Watch(Arc::new(tx)),
+        ChannelReceiver::Watch(rx),
+    ))
+}
+
+/// Awaitable form of a `select` case
+fn select_case(value: &Value) -> Result<BoxFuture<'static, Value>, VmError> {
+    match value {
+        Value::Receiver(rx_arc) => {
+            let rx_arc = rx_arc.clone();
+            Ok(async move { rx_arc.lock().await.recv().await.unwrap_or(Value::Null) }.boxed())
+        }
+        Value::Future(f) => Ok(f.0.clone().boxed()),
+        Value::Task(task) => Ok(task.result.0.clone().boxed()),
+        _ => Err(VmError::TypeError {
+            operation: "select".to_string(),
+            expected: "Receiver, Future or Task".to_string(),
+            got: format!("{:?}", value),
+        }),
+    }
+}
+
+fn select_result(index: Value, value: Value) -> Value {
+    let mut record = HashMap::new();
+    record.insert("index".to_string(), index);
+    record.insert("value".to_string(), value);
+    Value::Record(shared(record))
+}
+
+/// select(cases) -> Future<{index, value}>
+/// select(cases, default) -> Future<{index, value}>
+/// Waits for whichever receiver, future or task in `cases` is ready first.
+/// Messages are only taken from the receiver that wins. With a default,
+/// resolves immediately to `{index: null, value: default}` if none is ready.
+pub fn vm_select(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(VmError::Runtime(format!(
+            "select() expects 1 or 2 arguments (cases, default), got {}",
+            args.len()
+        )));
+    }
+
+    let cases = match &args[0] {
+        Value::Vector(v) => v.read().clone(),
+        other => {
+            return Err(VmError::TypeError {
+                operation: "select".to_string(),
+                expected: "Vector".to_string(),
+                got: format!("{:?}", other),
+            })
+        }
+    };
+    if cases.is_empty() {
         return Err(VmError::Runtime(
-            "channel() expects 0 arguments".to_string(),
+            "select() expects at least one case".to_string(),
         ));
     }
 
-    let (tx, rx) = mpsc::unbounded_channel();
-
-    let sender = Value::Sender(Arc::new(tx));
-    let receiver = Value::Receiver(Arc::new(tokio::sync::Mutex::new(rx)));
+    let mut pending = FuturesUnordered::new();
+    for (i, case) in cases.iter().enumerate() {
+        pending.push(select_case(case)?.map(move |v| (i, v)));
+    }
 
-    // Return as a vector [sender, receiver]
-    let vec = vec![sender, receiver];
-    Ok(Value::Vector(shared(vec)))
+    match args.get(1) {
+        Some(default) => {
+            let result = match pending.next().now_or_never() {
+                Some(Some((i, value))) => select_result(Value::Number(i as f64), value),
+                _ => select_result(Value::Null, default.clone()),
+            };
+            Ok(Value::Future(VmFuture::new(async move { result })))
+        }
+        None => {
+            let future = async move {
+                let (i, value) = pending.next().await.unwrap_or((0, Value::Null));
+                select_result(Value::Number(i as f64), value)
+            };
+            Ok(Value::Future(VmFuture::new(future)))
+        }
+    }
 }
 
 // === Sender Methods ===
 
+fn sender_of<'a>(sender_val: &'a Value, method: &str) -> Result<&'a ChannelSender, VmError> {
+    match sender_val {
+        Value::Sender(tx) => Ok(tx),
+        _ => Err(VmError::TypeError {
+            operation: method.to_string(),
+            expected: "Sender".to_string(),
+            got: format!("{:?}", sender_val),
+        }),
+    }
+}
+
 /// Sender.send(value) -> Future<Null>
+/// On a bounded channel the future waits until there is room.
 pub fn vm_sender_send(_vm: &mut VM, sender_val: &Value, args: &[Value]) -> Result<Value, VmError> {
     if args.len() != 1 {
         return Err(VmError::Runtime(format!(
@@ -38,38 +188,104 @@ pub fn vm_sender_send(_vm: &mut VM, sender_val: &Value, args: &[Value]) -> Resul
     }
 
     let message = args[0].clone();
+    let sender = sender_of(sender_val, "send")?.get();
 
-    match sender_val {
-        Value::Sender(tx_arc) => {
-            // We just send immediately for unbounded channel
-            // UnboundedSender is thread-safe and we can clone it from Arc
-            let tx = (**tx_arc).clone();
+    let future = async move {
+        let sent = match sender {
+            Some(SenderKind::Unbounded(tx)) => tx.send(message).is_ok(),
+            Some(SenderKind::Bounded(tx)) => tx.send(message).await.is_ok(),
+            Some(SenderKind::Broadcast(tx)) => tx.send(message).is_ok(),
+            Some(SenderKind::Watch(tx)) => {
+                tx.send_replace(message);
+                true
+            }
+            None => false,
+        };
+        if sent {
+            Value::Null
+        } else {
+            channel_closed()
+        }
+    };
 
-            // We return a Future to allow 'await tx.send(val)' in the script
-            // This maintains consistency and allows future switch to bounded channels (which need await)
-            let future = async move {
-                match tx.send(message) {
-                    Ok(_) => Value::Null,
-                    Err(e) => Value::Error {
-                        message: format!("Channel closed: {}", e),
-                        kind: Some("ChannelError".into()),
-                        source: None,
-                    },
-                }
-            };
+    Ok(Value::Future(VmFuture::new(future)))
+}
 
-            Ok(Value::Future(VmFuture::new(future)))
+/// Sender.try_send(value) -> Boolean
+/// Sends without waiting; false if the channel is full or closed.
+pub fn vm_sender_try_send(
+    _vm: &mut VM,
+    sender_val: &Value,
+    args: &[Value],
+) -> Result<Value, VmError> {
+    if args.len() != 1 {
+        return Err(VmError::Runtime(format!(
+            "try_send() expects 1 argument (value), got {}",
+            args.len()
+        )));
+    }
+
+    let message = args[0].clone();
+    let sent = match sender_of(sender_val, "try_send")?.get() {
+        Some(SenderKind::Unbounded(tx)) => tx.send(message).is_ok(),
+        Some(SenderKind::Bounded(tx)) => tx.try_send(message).is_ok(),
+        Some(SenderKind::Broadcast(tx)) => tx.send(message).is_ok(),
+        Some(SenderKind::Watch(tx)) => {
+            tx.send_replace(message);
+            true
         }
+        None => false,
+    };
+    Ok(Value::Boolean(sent))
+}
+
+/// Sender.close() -> Null
+/// Closes the channel for every copy of this sender; receivers finish
+/// once the buffered messages are drained.
+pub fn vm_sender_close(
+    _vm: &mut VM,
+    sender_val: &Value,
+    _args: &[Value],
+) -> Result<Value, VmError> {
+    sender_of(sender_val, "close")?.close();
+    Ok(Value::Null)
+}
+
+/// Sender.subscribe() -> Receiver (broadcast and watch channels)
+pub fn vm_sender_subscribe(
+    _vm: &mut VM,
+    sender_val: &Value,
+    _args: &[Value],
+) -> Result<Value, VmError> {
+    let receiver = match sender_of(sender_val, "subscribe")?.get() {
+        Some(SenderKind::Broadcast(tx)) => ChannelReceiver::Broadcast(tx.subscribe()),
+        Some(SenderKind::Watch(tx)) => ChannelReceiver::Watch(tx.subscribe()),
+        Some(_) => {
+            return Err(VmError::Runtime(
+                "subscribe() is only available on broadcast and watch channels".to_string(),
+            ))
+        }
+        None => ChannelReceiver::Closed,
+    };
+    Ok(Value::Receiver(Arc::new(tokio::sync::Mutex::new(receiver))))
+}
+
+// === Receiver Methods ===
+
+fn receiver_of<'a>(
+    receiver_val: &'a Value,
+    method: &str,
+) -> Result<&'a Arc<tokio::sync::Mutex<ChannelReceiver>>, VmError> {
+    match receiver_val {
+        Value::Receiver(rx) => Ok(rx),
         _ => Err(VmError::TypeError {
-            operation: "send".to_string(),
-            expected: "Sender".to_string(),
-            got: format!("{:?}", sender_val),
+            operation: method.to_string(),
+            expected: "Receiver".to_string(),
+            got: format!("{:?}", receiver_val),
         }),
     }
 }
 
-// === Receiver Methods ===
-
 /// Receiver.recv() -> Future<Value>
 /// Returns value or null if closed
 pub fn vm_receiver_recv(
@@ -84,29 +300,78 @@ pub fn vm_receiver_recv(
         )));
     }
 
-    match receiver_val {
-        Value::Receiver(rx_arc) => {
-            let rx_arc = rx_arc.clone();
+    let rx_arc = receiver_of(receiver_val, "recv")?.clone();
+    let future = async move {
+        // Lock the receiver mutex
+        let mut rx = rx_arc.lock().await;
+        rx.recv().await.unwrap_or(Value::Null) // null: channel closed
+    };
 
-            let future = async move {
-                // Lock the receiver mutex
-                let mut rx = rx_arc.lock().await;
-                match rx.recv().await {
-                    Some(val) => val,
-                    None => Value::Null, // Channel closed
-                }
-            };
+    Ok(Value::Future(VmFuture::new(future)))
+}
 
-            Ok(Value::Future(VmFuture::new(future)))
-        }
-        _ => Err(VmError::TypeError {
-            operation: "recv".to_string(),
-            expected: "Receiver".to_string(),
-            got: format!("{:?}", receiver_val),
-        }),
+/// Future resolving to an iterator result `{value, done}` for `for (msg in rx)`
+pub(crate) fn receiver_next(rx_arc: &Arc<tokio::sync::Mutex<ChannelReceiver>>) -> Value {
+    let rx_arc = rx_arc.clone();
+    let future = async move {
+        let next = rx_arc.lock().await.recv().await;
+        let mut result = HashMap::new();
+        result.insert("done".to_string(), Value::Boolean(next.is_none()));
+        result.insert("value".to_string(), next.unwrap_or(Value::Null));
+        Value::Record(shared(result))
+    };
+    Value::Future(VmFuture::new(future))
+}
+
+/// Receiver.try_recv() -> Value
+/// The next message if one is waiting, otherwise null.
+pub fn vm_receiver_try_recv(
+    _vm: &mut VM,
+    receiver_val: &Value,
+    _args: &[Value],
+) -> Result<Value, VmError> {
+    let rx_arc = receiver_of(receiver_val, "try_recv")?;
+    // Someone else is blocked in recv(): nothing for us right now
+    let message = match rx_arc.try_lock() {
+        Ok(mut rx) => rx.try_recv(),
+        Err(_) => None,
+    };
+    Ok(message.unwrap_or(Value::Null))
+}
+
+/// Receiver.get() -> Value (watch channels: the current value)
+pub fn vm_receiver_get(
+    _vm: &mut VM,
+    receiver_val: &Value,
+    _args: &[Value],
+) -> Result<Value, VmError> {
+    let rx_arc = receiver_of(receiver_val, "get")?;
+    let rx = rx_arc
+        .try_lock()
+        .map_err(|_| VmError::Runtime("get(): receiver is busy in recv()".to_string()))?;
+    match &*rx {
+        ChannelReceiver::Watch(watch_rx) => Ok(watch_rx.borrow().clone()),
+        _ => Err(VmError::Runtime(
+            "get() is only available on watch channels".to_string(),
+        )),
     }
 }
 
+/// Receiver.close() -> Null
+/// Stops the channel from accepting new messages; buffered ones can still be received.
+pub fn vm_receiver_close(
+    _vm: &mut VM,
+    receiver_val: &Value,
+    _args: &[Value],
+) -> Result<Value, VmError> {
+    let rx_arc = receiver_of(receiver_val, "close")?.clone();
+    let future = async move {
+        rx_arc.lock().await.close();
+        Value::Null
+    };
+    Ok(Value::Future(VmFuture::new(future)))
+}
+
 // === AsyncMutex Methods ===
 
 /// AsyncMutex(initial_value) -> AsyncMutex
@@ -0,0 +1,585 @@
+//! File-based CSV built-ins
+//!
+//! This module provides streaming CSV I/O on top of the `csv` crate:
+//! - read_csv: Read a file into a DataFrame, inferring numeric columns
+//! - write_csv: Write records, row vectors, 2-D tensors or frames to a file
+//! - csv_rows: Lazily iterate over the rows of a file in `for` loops
+//!
+//! Files are read one record at a time; numeric columns are accumulated
+//! directly as `f64` buffers and become tensors without an intermediate
+//! `Vector<Value>`.
+
+use crate::error::VmError;
+use crate::value::Value;
+use crate::vm::execution::iterators::NativeIterator;
+use crate::vm::VM;
+use achronyme_types::dataframe::{Column, DataFrame};
+use achronyme_types::sync::{shared, Arc, RwLock};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+
+// ============================================================================
+// Options
+// ============================================================================
+
+/// Column type requested through the `types` option
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum FieldType {
+    Number,
+    String,
+}
+
+/// Options shared by read_csv, csv_rows and write_csv
+#[derive(Debug, Clone)]
+pub(crate) struct CsvOptions {
+    pub delimiter: u8,
+    pub header: bool,
+    pub skip: usize,
+    pub types: HashMap<String, FieldType>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            header: true,
+            skip: 0,
+            types: HashMap::new(),
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Parse an optional `{delimiter, header, types, skip}` record
+    pub(crate) fn from_value(value: Option<&Value>, operation: &str) -> Result<Self, VmError> {
+        let mut options = CsvOptions::default();
+        let rc = match value {
+            None | Some(Value::Null) => return Ok(options),
+            Some(Value::Record(rc)) => rc,
+            Some(other) => {
+                return Err(VmError::TypeError {
+                    operation: operation.to_string(),
+                    expected: "options Record".to_string(),
+                    got: format!("{:?}", other),
+                })
+            }
+        };
+
+        for (key, value) in rc.read().iter() {
+            match (key.as_str(), value) {
+                ("delimiter", Value::String(s)) if s.len() == 1 && s.is_ascii() => {
+                    options.delimiter = s.as_bytes()[0];
+                }
+                ("header", Value::Boolean(b)) => options.header = *b,
+                ("skip", Value::Number(n)) if *n >= 0.0 && n.fract() == 0.0 => {
+                    options.skip = *n as usize;
+                }
+                ("types", Value::Record(types)) => {
+                    for (column, ty) in types.read().iter() {
+                        let ty = match ty {
+                            Value::String(s) if s == "number" => FieldType::Number,
+                            Value::String(s) if s == "string" => FieldType::String,
+                            _ => {
+                                return Err(VmError::Runtime(format!(
+                                    "{}(): type of column '{}' must be \"number\" or \"string\"",
+                                    operation, column
+                                )))
+                            }
+                        };
+                        options.types.insert(column.clone(), ty);
+                    }
+                }
+                ("delimiter" | "header" | "skip" | "types", _) => {
+                    return Err(VmError::Runtime(format!(
+                        "{}(): invalid value for option '{}': {:?}",
+                        operation, key, value
+                    )))
+                }
+                _ => {
+                    return Err(VmError::Runtime(format!(
+                        "{}(): unknown option '{}'",
+                        operation, key
+                    )))
+                }
+            }
+        }
+        Ok(options)
+    }
+
+    pub(crate) fn reader<R: Read>(&self, source: R) -> csv::Reader<R> {
+        csv::ReaderBuilder::new()
+            .has_headers(self.header)
+            .delimiter(self.delimiter)
+            .from_reader(source)
+    }
+}
+
+fn csv_error(operation: &str, e: csv::Error) -> VmError {
+    VmError::Runtime(format!("{}(): {}", operation, e))
+}
+
+fn path_arg(value: &Value, operation: &str) -> Result<String, VmError> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        _ => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: "String path".to_string(),
+            got: format!("{:?}", value),
+        }),
+    }
+}
+
+/// Open a file for CSV reading, discarding the first `skip` lines
+fn open_reader(
+    path: &str,
+    options: &CsvOptions,
+    operation: &str,
+) -> Result<csv::Reader<BufReader<File>>, VmError> {
+    let file = File::open(path).map_err(|e| {
+        VmError::Runtime(format!("{}(): failed to open '{}': {}", operation, path, e))
+    })?;
+    let mut source = BufReader::new(file);
+    let mut line = Vec::new();
+    for _ in 0..options.skip {
+        line.clear();
+        let read = source.read_until(b'\n', &mut line).map_err(|e| {
+            VmError::Runtime(format!("{}(): failed to read '{}': {}", operation, path, e))
+        })?;
+        if read == 0 {
+            break;
+        }
+    }
+    Ok(options.reader(source))
+}
+
+// ============================================================================
+// Column Inference
+// ============================================================================
+
+/// Accumulates one column while records stream in
+///
+/// Columns start out numeric and fall back to text on the first field that
+/// does not parse. Only fields whose text would not survive a round trip
+/// through `f64` formatting are kept verbatim, so the fallback is lossless
+/// without holding every field twice.
+enum ColumnBuilder {
+    Inferred {
+        values: Vec<f64>,
+        verbatim: HashMap<usize, String>,
+        any_number: bool,
+    },
+    Number(Vec<f64>),
+    Text(Vec<String>),
+}
+
+impl ColumnBuilder {
+    fn new(forced: Option<FieldType>) -> Self {
+        match forced {
+            None => ColumnBuilder::Inferred {
+                values: Vec::new(),
+                verbatim: HashMap::new(),
+                any_number: false,
+            },
+            Some(FieldType::Number) => ColumnBuilder::Number(Vec::new()),
+            Some(FieldType::String) => ColumnBuilder::Text(Vec::new()),
+        }
+    }
+
+    /// Append a field; fails only for unparsable fields in forced numeric columns
+    fn push(&mut self, field: &str) -> Result<(), String> {
+        match self {
+            ColumnBuilder::Number(values) => {
+                values.push(
+                    parse_number(field).ok_or_else(|| format!("'{}' is not a number", field))?,
+                );
+            }
+            ColumnBuilder::Text(values) => values.push(field.to_string()),
+            ColumnBuilder::Inferred {
+                values,
+                verbatim,
+                any_number,
+            } => match parse_number(field) {
+                Some(n) => {
+                    if n.is_nan() || n.to_string() != field {
+                        verbatim.insert(values.len(), field.to_string());
+                    }
+                    *any_number |= !field.trim().is_empty();
+                    values.push(n);
+                }
+                None => {
+                    let mut text: Vec<String> = values
+                        .iter()
+                        .enumerate()
+                        .map(|(i, n)| verbatim.remove(&i).unwrap_or_else(|| n.to_string()))
+                        .collect();
+                    text.push(field.to_string());
+                    *self = ColumnBuilder::Text(text);
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Column {
+        match self {
+            ColumnBuilder::Inferred {
+                values, any_number, ..
+            } if any_number => Column::numbers(values),
+            // Every field was empty: keep the column as text
+            ColumnBuilder::Inferred { values, .. } => {
+                Column::String(vec![String::new(); values.len()])
+            }
+            ColumnBuilder::Number(values) => Column::numbers(values),
+            ColumnBuilder::Text(values) => Column::String(values),
+        }
+    }
+}
+
+/// Parse a numeric field; empty fields are missing values (NaN)
+fn parse_number(field: &str) -> Option<f64> {
+    let trimmed = field.trim();
+    if trimmed.is_empty() {
+        Some(f64::NAN)
+    } else {
+        trimmed.parse::<f64>().ok()
+    }
+}
+
+/// Convert a single field for row-wise access
+fn field_to_value(field: &str, forced: Option<FieldType>) -> Result<Value, String> {
+    match forced {
+        Some(FieldType::String) => Ok(Value::String(field.to_string())),
+        Some(FieldType::Number) => parse_number(field)
+            .map(Value::Number)
+            .ok_or_else(|| format!("'{}' is not a number", field)),
+        None => Ok(match field.trim().parse::<f64>() {
+            Ok(n) => Value::Number(n),
+            Err(_) => Value::String(field.to_string()),
+        }),
+    }
+}
+
+/// Column names of a reader: its header row, or `col0`, `col1`, ...
+fn column_names<R: Read>(
+    reader: &mut csv::Reader<R>,
+    options: &CsvOptions,
+    operation: &str,
+) -> Result<Option<Vec<String>>, VmError> {
+    if !options.header {
+        return Ok(None);
+    }
+    let headers = reader.headers().map_err(|e| csv_error(operation, e))?;
+    Ok(Some(headers.iter().map(|h| h.to_string()).collect()))
+}
+
+/// Stream every record of `reader` into a DataFrame
+pub(crate) fn read_frame<R: Read>(
+    reader: &mut csv::Reader<R>,
+    options: &CsvOptions,
+    operation: &str,
+) -> Result<DataFrame, VmError> {
+    let mut names = column_names(reader, options, operation)?.unwrap_or_default();
+    let mut builders: Vec<ColumnBuilder> = names
+        .iter()
+        .map(|name| ColumnBuilder::new(options.types.get(name).copied()))
+        .collect();
+
+    let mut record = csv::StringRecord::new();
+    let mut row = 0;
+    while reader
+        .read_record(&mut record)
+        .map_err(|e| csv_error(operation, e))?
+    {
+        if !options.header && builders.is_empty() {
+            names = (0..record.len()).map(|i| format!("col{}", i)).collect();
+            builders = names
+                .iter()
+                .map(|name| ColumnBuilder::new(options.types.get(name).copied()))
+                .collect();
+        }
+        for ((builder, field), name) in builders.iter_mut().zip(record.iter()).zip(&names) {
+            builder.push(field).map_err(|e| {
+                VmError::Runtime(format!(
+                    "{}(): row {}, column '{}': {}",
+                    operation, row, name, e
+                ))
+            })?;
+        }
+        row += 1;
+    }
+
+    let columns = names
+        .into_iter()
+        .zip(builders)
+        .map(|(name, builder)| (name, builder.finish()))
+        .collect();
+    DataFrame::new(columns).map_err(|e| VmError::Runtime(format!("{}(): {}", operation, e)))
+}
+
+// ============================================================================
+// Row Iterator
+// ============================================================================
+
+/// Lazily yields the rows of a CSV file
+///
+/// Rows are records keyed by header when the file has one, otherwise
+/// vectors. A malformed record is an error and ends the iteration.
+#[derive(Debug)]
+pub(crate) struct CsvRowIterator {
+    reader: csv::Reader<BufReader<File>>,
+    names: Option<Vec<String>>,
+    types: HashMap<String, FieldType>,
+    record: csv::StringRecord,
+    done: bool,
+}
+
+impl CsvRowIterator {
+    /// Get the next row, or None once the file is exhausted
+    ///
+    /// A malformed record or a field that doesn't fit its forced type is an
+    /// error; the iterator is exhausted afterwards.
+    pub fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if self.done {
+            return Ok(None);
+        }
+        match self.read_row() {
+            Ok(Some(row)) => Ok(Some(row)),
+            Ok(None) => {
+                self.done = true;
+                Ok(None)
+            }
+            Err(message) => {
+                self.done = true;
+                Err(VmError::Runtime(format!("csv_rows(): {}", message)))
+            }
+        }
+    }
+
+    fn read_row(&mut self) -> Result<Option<Value>, String> {
+        if !self
+            .reader
+            .read_record(&mut self.record)
+            .map_err(|e| e.to_string())?
+        {
+            return Ok(None);
+        }
+
+        match &self.names {
+            Some(names) => {
+                let mut map = HashMap::with_capacity(names.len());
+                for (name, field) in names.iter().zip(self.record.iter()) {
+                    let forced = self.types.get(name).copied();
+                    let value = field_to_value(field, forced)
+                        .map_err(|e| format!("column '{}': {}", name, e))?;
+                    map.insert(name.clone(), value);
+                }
+                Ok(Some(Value::Record(shared(map))))
+            }
+            None => {
+                let row = self
+                    .record
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| {
+                        let forced = self.types.get(&format!("col{}", i)).copied();
+                        field_to_value(field, forced)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Some(Value::Vector(shared(row))))
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Built-ins
+// ============================================================================
+
+/// read_csv(path, options?) -> DataFrame
+///
+/// Options: `delimiter` (single character, default ","), `header` (default
+/// true), `skip` (lines to discard before the header) and `types`
+/// (`{column: "number" | "string"}` to override inference).
+pub fn vm_read_csv(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(VmError::Runtime(format!(
+            "read_csv() expects 1 or 2 arguments, got {}",
+            args.len()
+        )));
+    }
+    let path = path_arg(&args[0], "read_csv")?;
+    let options = CsvOptions::from_value(args.get(1), "read_csv")?;
+
+    let mut reader = open_reader(&path, &options, "read_csv")?;
+    let df = read_frame(&mut reader, &options, "read_csv")?;
+    Ok(Value::DataFrame(Arc::new(df)))
+}
+
+/// csv_rows(path, options?) -> Generator
+///
+/// Accepts the same options as `read_csv` and yields one row per iteration.
+pub fn vm_csv_rows(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(VmError::Runtime(format!(
+            "csv_rows() expects 1 or 2 arguments, got {}",
+            args.len()
+        )));
+    }
+    let path = path_arg(&args[0], "csv_rows")?;
+    let options = CsvOptions::from_value(args.get(1), "csv_rows")?;
+
+    let mut reader = open_reader(&path, &options, "csv_rows")?;
+    let names = column_names(&mut reader, &options, "csv_rows")?;
+    let iter = NativeIterator::Csv(Box::new(CsvRowIterator {
+        reader,
+        names,
+        types: options.types,
+        record: csv::StringRecord::new(),
+        done: false,
+    }));
+    let iter_arc: Arc<dyn Any + Send + Sync> = Arc::new(RwLock::new(iter));
+    Ok(Value::Generator(iter_arc))
+}
+
+/// write_csv(path, data, options?) -> Null
+///
+/// `data` may be a `Vector<Record>` (header from the sorted field names), a
+/// `Vector<Vector>` of rows, a 1-D or 2-D Tensor, or a DataFrame. Options:
+/// `delimiter` and `header` (set to false to omit the header row).
+pub fn vm_write_csv(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() < 2 || args.len() > 3 {
+        return Err(VmError::Runtime(format!(
+            "write_csv() expects 2 or 3 arguments, got {}",
+            args.len()
+        )));
+    }
+    let path = path_arg(&args[0], "write_csv")?;
+    if let Some(Value::Record(rc)) = args.get(2) {
+        // `types` and `skip` only make sense when reading
+        if let Some(key) = ["skip", "types"]
+            .iter()
+            .find(|k| rc.read().contains_key(**k))
+        {
+            return Err(VmError::Runtime(format!(
+                "write_csv(): option '{}' only applies when reading",
+                key
+            )));
+        }
+    }
+    let options = CsvOptions::from_value(args.get(2), "write_csv")?;
+
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .flexible(true)
+        .from_path(&path)
+        .map_err(|e| csv_error("write_csv", e))?;
+    let err = |e: csv::Error| csv_error("write_csv", e);
+
+    match &args[1] {
+        Value::DataFrame(df) => {
+            if options.header {
+                writer.write_record(df.names()).map_err(err)?;
+            }
+            crate::builtins::dataframe::write_frame_rows(&mut writer, df).map_err(err)?;
+        }
+        Value::Tensor(t) if t.rank() <= 2 => {
+            let cols = if t.rank() == 2 { t.shape()[1] } else { 1 };
+            for row in t.data().chunks(cols.max(1)) {
+                writer
+                    .write_record(row.iter().map(|n| format_number(*n)))
+                    .map_err(err)?;
+            }
+        }
+        Value::Vector(rows) => {
+            let rows = rows.read();
+            match rows.first() {
+                Some(Value::Record(_)) => {
+                    // Columns are the union of every record's fields, sorted
+                    let mut names = std::collections::BTreeSet::new();
+                    for row in rows.iter() {
+                        let Value::Record(rc) = row else {
+                            return Err(row_type_error(row));
+                        };
+                        names.extend(rc.read().keys().cloned());
+                    }
+                    let names: Vec<String> = names.into_iter().collect();
+                    if options.header {
+                        writer.write_record(&names).map_err(err)?;
+                    }
+                    for row in rows.iter() {
+                        let Value::Record(rc) = row else {
+                            return Err(row_type_error(row));
+                        };
+                        let map = rc.read();
+                        let fields = names
+                            .iter()
+                            .map(|name| map.get(name).map_or(Ok(String::new()), format_field))
+                            .collect::<Result<Vec<_>, _>>()?;
+                        writer.write_record(&fields).map_err(err)?;
+                    }
+                }
+                _ => {
+                    for row in rows.iter() {
+                        let fields = match row {
+                            Value::Vector(v) => v
+                                .read()
+                                .iter()
+                                .map(format_field)
+                                .collect::<Result<Vec<_>, _>>()?,
+                            Value::Tensor(t) if t.rank() <= 1 => {
+                                t.data().iter().map(|n| format_number(*n)).collect()
+                            }
+                            _ => return Err(row_type_error(row)),
+                        };
+                        writer.write_record(&fields).map_err(err)?;
+                    }
+                }
+            }
+        }
+        other => {
+            return Err(VmError::TypeError {
+                operation: "write_csv".to_string(),
+                expected: "Vector<Record>, Vector<Vector>, 2-D Tensor or DataFrame".to_string(),
+                got: format!("{:?}", other),
+            })
+        }
+    }
+
+    writer
+        .flush()
+        .map_err(|e| VmError::Runtime(format!("write_csv(): failed to write '{}': {}", path, e)))?;
+    Ok(Value::Null)
+}
+
+fn row_type_error(row: &Value) -> VmError {
+    VmError::TypeError {
+        operation: "write_csv".to_string(),
+        expected: "rows of the same kind (Record or Vector)".to_string(),
+        got: format!("{:?}", row),
+    }
+}
+
+/// Format a number as a CSV field; NaN is written as an empty field
+fn format_number(n: f64) -> String {
+    if n.is_nan() {
+        String::new()
+    } else {
+        n.to_string()
+    }
+}
+
+fn format_field(value: &Value) -> Result<String, VmError> {
+    match value {
+        Value::Number(n) => Ok(format_number(*n)),
+        Value::String(s) => Ok(s.clone()),
+        Value::Boolean(b) => Ok(b.to_string()),
+        Value::Null => Ok(String::new()),
+        _ => Err(VmError::TypeError {
+            operation: "write_csv".to_string(),
+            expected: "Number, String, Boolean or null field".to_string(),
+            got: format!("{:?}", value),
+        }),
+    }
+}
@@ -0,0 +1,527 @@
+//! DataFrame built-ins
+//!
+//! This module provides the script-facing API for `achronyme_types::dataframe`:
+//! - dataframe: Build a frame from a record of columns or a vector of records
+//! - df_from_csv / df_from_json: Parse text into a frame
+//! - Methods: select, filter, group_by, agg, join, sort_by, head, describe,
+//!   columns, nrows, to_records, to_csv, to_json
+//!
+//! Numeric columns are exposed as `Tensor` values, text columns as
+//! `Vector<String>`, so `df.price` can be passed straight to HOFs and
+//! statistics functions.
+
+use crate::builtins::csv_io::{read_frame, CsvOptions};
+use crate::builtins::hof::is_truthy;
+use crate::error::VmError;
+use crate::value::Value;
+use crate::vm::VM;
+use achronyme_types::dataframe::{Aggregation, Cell, Column, DataFrame, DataFrameError, JoinKind};
+use achronyme_types::sync::{shared, Arc};
+use std::collections::HashMap;
+
+// ============================================================================
+// Conversions
+// ============================================================================
+
+fn df_error(e: DataFrameError) -> VmError {
+    VmError::Runtime(format!("DataFrame error: {}", e))
+}
+
+/// Convert a cell to a script value
+pub(crate) fn cell_to_value(cell: Cell) -> Value {
+    match cell {
+        Cell::Number(n) => Value::Number(n),
+        Cell::String(s) => Value::String(s),
+    }
+}
+
+fn value_to_cell(value: &Value, operation: &str) -> Result<Cell, VmError> {
+    match value {
+        Value::Number(n) => Ok(Cell::Number(*n)),
+        Value::Boolean(b) => Ok(Cell::Number(if *b { 1.0 } else { 0.0 })),
+        Value::Null => Ok(Cell::Number(f64::NAN)),
+        Value::String(s) => Ok(Cell::String(s.clone())),
+        _ => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: "Number, Boolean, String or null cell".to_string(),
+            got: format!("{:?}", value),
+        }),
+    }
+}
+
+/// Convert a column to a script value (`Tensor` or `Vector<String>`)
+pub(crate) fn column_to_value(column: &Column) -> Value {
+    match column {
+        Column::Number(t) => Value::Tensor(t.clone()),
+        Column::String(v) => {
+            Value::Vector(shared(v.iter().map(|s| Value::String(s.clone())).collect()))
+        }
+    }
+}
+
+/// Convert a script value (Vector or rank-1 Tensor) to a column
+fn value_to_column(value: &Value, operation: &str) -> Result<Column, VmError> {
+    match value {
+        Value::Tensor(t) if t.rank() <= 1 => Ok(Column::numbers(t.data().to_vec())),
+        Value::Vector(v) => {
+            let cells = v
+                .read()
+                .iter()
+                .map(|item| value_to_cell(item, operation))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Column::from_cells(cells))
+        }
+        _ => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: "Vector or 1-D Tensor column".to_string(),
+            got: format!("{:?}", value),
+        }),
+    }
+}
+
+/// Build a frame from rows given as records
+///
+/// Columns are ordered by name; every row must have the same fields.
+fn frame_from_records(rows: &[Value], operation: &str) -> Result<DataFrame, VmError> {
+    let mut names: Vec<String> = Vec::new();
+    let mut cells: Vec<Vec<Cell>> = Vec::new();
+
+    for (i, row) in rows.iter().enumerate() {
+        let Value::Record(rc) = row else {
+            return Err(VmError::TypeError {
+                operation: operation.to_string(),
+                expected: "Vector<Record>".to_string(),
+                got: format!("{:?}", row),
+            });
+        };
+        let map = rc.read();
+        if i == 0 {
+            names = map.keys().cloned().collect();
+            names.sort();
+            cells = vec![Vec::with_capacity(rows.len()); names.len()];
+        } else if map.len() != names.len() {
+            return Err(VmError::Runtime(format!(
+                "{}(): row {} has {} fields, expected {}",
+                operation,
+                i,
+                map.len(),
+                names.len()
+            )));
+        }
+        for (col, name) in names.iter().enumerate() {
+            let value = map.get(name).ok_or_else(|| {
+                VmError::Runtime(format!(
+                    "{}(): row {} is missing field '{}'",
+                    operation, i, name
+                ))
+            })?;
+            cells[col].push(value_to_cell(value, operation)?);
+        }
+    }
+
+    DataFrame::new(
+        names
+            .into_iter()
+            .zip(cells)
+            .map(|(name, col)| (name, Column::from_cells(col)))
+            .collect(),
+    )
+    .map_err(df_error)
+}
+
+/// Build a frame from a record of columns, a vector of records, or a frame
+pub(crate) fn frame_from_value(value: &Value, operation: &str) -> Result<DataFrame, VmError> {
+    match value {
+        Value::DataFrame(df) => Ok((**df).clone()),
+        Value::Record(rc) => {
+            let map = rc.read();
+            let mut names: Vec<&String> = map.keys().collect();
+            names.sort();
+            let columns = names
+                .into_iter()
+                .map(|name| Ok((name.clone(), value_to_column(&map[name], operation)?)))
+                .collect::<Result<Vec<_>, VmError>>()?;
+            DataFrame::new(columns).map_err(df_error)
+        }
+        Value::Vector(v) => frame_from_records(&v.read(), operation),
+        _ => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: "Record of columns, Vector<Record> or DataFrame".to_string(),
+            got: format!("{:?}", value),
+        }),
+    }
+}
+
+/// Convert one row to a record keyed by column name
+pub(crate) fn row_record(df: &DataFrame, row: usize) -> Value {
+    let map: HashMap<String, Value> = df
+        .row(row)
+        .into_iter()
+        .map(|(name, cell)| (name, cell_to_value(cell)))
+        .collect();
+    Value::Record(shared(map))
+}
+
+/// Convert a frame to a `Vector<Record>`
+pub(crate) fn frame_to_records(df: &DataFrame) -> Value {
+    Value::Vector(shared((0..df.nrows()).map(|r| row_record(df, r)).collect()))
+}
+
+fn receiver_frame<'a>(receiver: &'a Value, method: &str) -> Result<&'a Arc<DataFrame>, VmError> {
+    match receiver {
+        Value::DataFrame(df) => Ok(df),
+        _ => Err(VmError::TypeError {
+            operation: method.to_string(),
+            expected: "DataFrame".to_string(),
+            got: format!("{:?}", receiver),
+        }),
+    }
+}
+
+/// Accept either a single column name or a vector of names
+fn column_names(value: &Value, operation: &str) -> Result<Vec<String>, VmError> {
+    match value {
+        Value::String(s) => Ok(vec![s.clone()]),
+        Value::Vector(v) => v
+            .read()
+            .iter()
+            .map(|item| match item {
+                Value::String(s) => Ok(s.clone()),
+                _ => Err(VmError::TypeError {
+                    operation: operation.to_string(),
+                    expected: "column name String".to_string(),
+                    got: format!("{:?}", item),
+                }),
+            })
+            .collect(),
+        _ => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: "String or Vector<String>".to_string(),
+            got: format!("{:?}", value),
+        }),
+    }
+}
+
+fn expect_args(method: &str, args: &[Value], min: usize, max: usize) -> Result<(), VmError> {
+    if args.len() < min || args.len() > max {
+        let expected = if min == max {
+            format!("{}", min)
+        } else {
+            format!("{} to {}", min, max)
+        };
+        return Err(VmError::Runtime(format!(
+            "{}() expects {} arguments, got {}",
+            method,
+            expected,
+            args.len()
+        )));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Constructors
+// ============================================================================
+
+/// dataframe(data) -> DataFrame
+///
+/// `data` is a record of equally sized columns (`{x: [1, 2], y: ["a", "b"]}`),
+/// a `Vector<Record>` such as the output of `csv_parse`, or another DataFrame.
+/// Columns are ordered alphabetically when built from records.
+pub fn vm_dataframe(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 1 {
+        return Err(VmError::Runtime(format!(
+            "dataframe() expects 1 argument, got {}",
+            args.len()
+        )));
+    }
+    let df = frame_from_value(&args[0], "dataframe")?;
+    Ok(Value::DataFrame(Arc::new(df)))
+}
+
+/// df_from_csv(text, has_headers?) -> DataFrame
+///
+/// Columns whose non-empty fields all parse as numbers become numeric
+/// (empty fields become NaN); everything else is kept as text. Without
+/// headers, columns are named `col0`, `col1`, ...
+pub fn vm_df_from_csv(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("df_from_csv", args, 1, 2)?;
+
+    let text = match &args[0] {
+        Value::String(s) => s,
+        _ => {
+            return Err(VmError::TypeError {
+                operation: "df_from_csv".to_string(),
+                expected: "String".to_string(),
+                got: format!("{:?}", args[0]),
+            })
+        }
+    };
+    let options = CsvOptions {
+        header: !matches!(args.get(1), Some(Value::Boolean(false))),
+        ..CsvOptions::default()
+    };
+
+    let mut reader = options.reader(text.as_bytes());
+    let df = read_frame(&mut reader, &options, "df_from_csv")?;
+    Ok(Value::DataFrame(Arc::new(df)))
+}
+
+/// df_from_json(text) -> DataFrame
+///
+/// Accepts either an array of objects or an object of arrays.
+pub fn vm_df_from_json(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 1 {
+        return Err(VmError::Runtime(format!(
+            "df_from_json() expects 1 argument, got {}",
+            args.len()
+        )));
+    }
+    let parsed = crate::builtins::encoding::vm_json_parse(vm, args)?;
+    let df = frame_from_value(&parsed, "df_from_json")?;
+    Ok(Value::DataFrame(Arc::new(df)))
+}
+
+// ============================================================================
+// Methods
+// ============================================================================
+
+/// DataFrame.columns() -> Vector<String>
+pub fn vm_df_columns(_vm: &mut VM, receiver: &Value, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("columns", args, 0, 0)?;
+    let df = receiver_frame(receiver, "columns")?;
+    Ok(Value::Vector(shared(
+        df.names()
+            .iter()
+            .map(|n| Value::String(n.clone()))
+            .collect(),
+    )))
+}
+
+/// DataFrame.nrows() -> Number
+pub fn vm_df_nrows(_vm: &mut VM, receiver: &Value, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("nrows", args, 0, 0)?;
+    let df = receiver_frame(receiver, "nrows")?;
+    Ok(Value::Number(df.nrows() as f64))
+}
+
+/// DataFrame.select(names) -> DataFrame
+pub fn vm_df_select(_vm: &mut VM, receiver: &Value, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("select", args, 1, 1)?;
+    let df = receiver_frame(receiver, "select")?;
+    let names = column_names(&args[0], "select")?;
+    Ok(Value::DataFrame(Arc::new(
+        df.select(&names).map_err(df_error)?,
+    )))
+}
+
+/// DataFrame.filter(predicate) -> DataFrame
+///
+/// The predicate receives each row as a record.
+pub fn vm_df_filter(vm: &mut VM, receiver: &Value, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("filter", args, 1, 1)?;
+    let df = receiver_frame(receiver, "filter")?.clone();
+    let mut keep = Vec::new();
+    for row in 0..df.nrows() {
+        let result = vm.call_value(&args[0], &[row_record(&df, row)])?;
+        if is_truthy(&result) {
+            keep.push(row);
+        }
+    }
+    Ok(Value::DataFrame(Arc::new(df.take_rows(&keep))))
+}
+
+/// DataFrame.sort_by(names, descending?) -> DataFrame
+pub fn vm_df_sort_by(_vm: &mut VM, receiver: &Value, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("sort_by", args, 1, 2)?;
+    let df = receiver_frame(receiver, "sort_by")?;
+    let names = column_names(&args[0], "sort_by")?;
+    let descending = matches!(args.get(1), Some(Value::Boolean(true)));
+    Ok(Value::DataFrame(Arc::new(
+        df.sort_by(&names, descending).map_err(df_error)?,
+    )))
+}
+
+/// DataFrame.head(n?) -> DataFrame (default 5 rows)
+pub fn vm_df_head(_vm: &mut VM, receiver: &Value, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("head", args, 0, 1)?;
+    let df = receiver_frame(receiver, "head")?;
+    let n = match args.first() {
+        None => 5,
+        Some(Value::Number(n)) if *n >= 0.0 => *n as usize,
+        Some(other) => {
+            return Err(VmError::TypeError {
+                operation: "head".to_string(),
+                expected: "non-negative Number".to_string(),
+                got: format!("{:?}", other),
+            })
+        }
+    };
+    Ok(Value::DataFrame(Arc::new(df.head(n))))
+}
+
+/// DataFrame.describe() -> DataFrame
+pub fn vm_df_describe(_vm: &mut VM, receiver: &Value, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("describe", args, 0, 0)?;
+    let df = receiver_frame(receiver, "describe")?;
+    Ok(Value::DataFrame(Arc::new(df.describe().map_err(df_error)?)))
+}
+
+/// DataFrame.group_by(names) -> DataFrame (grouped, for use with agg)
+pub fn vm_df_group_by(_vm: &mut VM, receiver: &Value, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("group_by", args, 1, 1)?;
+    let df = receiver_frame(receiver, "group_by")?;
+    let names = column_names(&args[0], "group_by")?;
+    Ok(Value::DataFrame(Arc::new(
+        df.group_by(&names).map_err(df_error)?,
+    )))
+}
+
+/// DataFrame.agg(spec) -> DataFrame
+///
+/// `spec` maps a column to an aggregation: a name (`"sum"`, `"mean"`, `"min"`,
+/// `"max"`, `"count"`, `"std"`, `"first"`, `"last"`), a vector of names
+/// (producing `col_name` outputs), or a function receiving the group's
+/// column. On an ungrouped frame the whole frame is one group.
+pub fn vm_df_agg(vm: &mut VM, receiver: &Value, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("agg", args, 1, 1)?;
+    let df = receiver_frame(receiver, "agg")?.clone();
+    let spec = match &args[0] {
+        Value::Record(rc) => {
+            let map = rc.read();
+            let mut entries: Vec<(String, Value)> =
+                map.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            entries
+        }
+        other => {
+            return Err(VmError::TypeError {
+                operation: "agg".to_string(),
+                expected: "Record mapping columns to aggregations".to_string(),
+                got: format!("{:?}", other),
+            })
+        }
+    };
+
+    let groups = df.groups().map_err(df_error)?;
+    let mut out = df.group_key_frame(&groups).map_err(df_error)?;
+
+    for (source, how) in spec {
+        let column = df
+            .column(&source)
+            .ok_or_else(|| df_error(DataFrameError::UnknownColumn(source.clone())))?;
+        let mut outputs: Vec<(String, Column)> = Vec::new();
+        match &how {
+            Value::String(name) => {
+                let agg = Aggregation::from_name(name).map_err(df_error)?;
+                let cells = groups.iter().map(|rows| agg.apply(column, rows)).collect();
+                outputs.push((source.clone(), Column::from_cells(cells)));
+            }
+            Value::Vector(names) => {
+                for name in column_names(&Value::Vector(names.clone()), "agg")? {
+                    let agg = Aggregation::from_name(&name).map_err(df_error)?;
+                    let cells = groups.iter().map(|rows| agg.apply(column, rows)).collect();
+                    outputs.push((format!("{}_{}", source, name), Column::from_cells(cells)));
+                }
+            }
+            Value::Function(_) => {
+                let mut cells = Vec::with_capacity(groups.len());
+                for rows in &groups {
+                    let group_column = column_to_value(&column.take(rows));
+                    let result = vm.call_value(&how, &[group_column])?;
+                    cells.push(value_to_cell(&result, "agg")?);
+                }
+                outputs.push((source.clone(), Column::from_cells(cells)));
+            }
+            other => {
+                return Err(VmError::TypeError {
+                    operation: "agg".to_string(),
+                    expected: "aggregation name, Vector of names or Function".to_string(),
+                    got: format!("{:?}", other),
+                })
+            }
+        }
+        for (name, col) in outputs {
+            out.push_column(name, col).map_err(df_error)?;
+        }
+    }
+
+    Ok(Value::DataFrame(Arc::new(out)))
+}
+
+/// DataFrame.join(other, on, how?) -> DataFrame
+///
+/// `how` is `"inner"` (default) or `"left"`.
+pub fn vm_df_join(_vm: &mut VM, receiver: &Value, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("join", args, 2, 3)?;
+    let df = receiver_frame(receiver, "join")?;
+    let other = frame_from_value(&args[0], "join")?;
+    let on = column_names(&args[1], "join")?;
+    let kind = match args.get(2) {
+        None => JoinKind::Inner,
+        Some(Value::String(s)) if s == "inner" => JoinKind::Inner,
+        Some(Value::String(s)) if s == "left" => JoinKind::Left,
+        Some(other) => {
+            return Err(VmError::TypeError {
+                operation: "join".to_string(),
+                expected: "\"inner\" or \"left\"".to_string(),
+                got: format!("{:?}", other),
+            })
+        }
+    };
+    Ok(Value::DataFrame(Arc::new(
+        df.join(&other, &on, kind).map_err(df_error)?,
+    )))
+}
+
+/// DataFrame.to_records() -> Vector<Record>
+pub fn vm_df_to_records(_vm: &mut VM, receiver: &Value, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("to_records", args, 0, 0)?;
+    let df = receiver_frame(receiver, "to_records")?;
+    Ok(frame_to_records(df))
+}
+
+/// DataFrame.to_csv() -> String
+pub fn vm_df_to_csv(_vm: &mut VM, receiver: &Value, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("to_csv", args, 0, 0)?;
+    let df = receiver_frame(receiver, "to_csv")?;
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let csv_error = |e: csv::Error| VmError::Runtime(format!("CSV write error: {}", e));
+    writer.write_record(df.names()).map_err(csv_error)?;
+    write_frame_rows(&mut writer, df).map_err(csv_error)?;
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| VmError::Runtime(format!("CSV write error: {}", e)))?;
+    String::from_utf8(bytes)
+        .map(Value::String)
+        .map_err(|e| VmError::Runtime(format!("CSV write error: {}", e)))
+}
+
+/// Write every row of a frame; missing numbers (NaN) become empty fields
+pub(crate) fn write_frame_rows<W: std::io::Write>(
+    writer: &mut csv::Writer<W>,
+    df: &DataFrame,
+) -> Result<(), csv::Error> {
+    for row in 0..df.nrows() {
+        let fields: Vec<String> = df
+            .row(row)
+            .into_iter()
+            .map(|(_, cell)| match cell {
+                Cell::Number(n) if n.is_nan() => String::new(),
+                other => other.to_string(),
+            })
+            .collect();
+        writer.write_record(&fields)?;
+    }
+    Ok(())
+}
+
+/// DataFrame.to_json(pretty?) -> String (array of row objects)
+pub fn vm_df_to_json(vm: &mut VM, receiver: &Value, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("to_json", args, 0, 1)?;
+    let df = receiver_frame(receiver, "to_json")?;
+    let mut full_args = vec![frame_to_records(df)];
+    full_args.extend_from_slice(args);
+    crate::builtins::encoding::vm_json_stringify(vm, &full_args)
+}
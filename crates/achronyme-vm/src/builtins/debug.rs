@@ -134,6 +134,7 @@ fn describe_value(value: &Value, indent: usize) -> String {
 
         Value::Generator(_) => "Generator".to_string(),
         Value::Future(_) => "Future".to_string(),
+        Value::Task(handle) => format!("Task(done: {})", handle.abort.is_finished()),
 
         Value::GeneratorYield(inner) => {
             format!("GeneratorYield({})", describe_value(inner, indent))
@@ -187,5 +188,47 @@ fn describe_value(value: &Value, indent: usize) -> String {
             let state = state_rc.read();
             format!("Signal(value: {})", describe_value(&state.value, indent))
         }
+        Value::Effect(state_rc) => {
+            let state = state_rc.read();
+            format!(
+                "Effect(dependencies: {}, disposed: {})",
+                state.dependencies.len(),
+                state.disposed
+            )
+        }
+
+        Value::DataFrame(df) => {
+            let columns: Vec<String> = df
+                .columns()
+                .map(|(name, col)| format!("{}: {}", name, col.dtype()))
+                .collect();
+            format!(
+                "DataFrame({} rows, columns: [{}])",
+                df.nrows(),
+                columns.join(", ")
+            )
+        }
+
+        Value::Bytes(bytes) => format!("Bytes({} bytes)", bytes.len()),
+        Value::Variant(variant) => {
+            let fields: Vec<String> = variant
+                .fields
+                .iter()
+                .map(|(name, val)| {
+                    format!(
+                        "\n{}  {}: {}",
+                        indent_str,
+                        name,
+                        describe_value(val, indent + 1)
+                    )
+                })
+                .collect();
+            format!(
+                "Variant({}.{}{})",
+                variant.type_name,
+                variant.variant,
+                fields.join("")
+            )
+        }
     }
 }
@@ -0,0 +1,80 @@
+//! Documentation for the built-in functions
+//!
+//! The table itself lives in `achronyme_metadata::docs` so that tools such as
+//! the LSP can read it without linking the VM. The registry attaches each
+//! entry to the function's metadata, and `achronyme symbols --builtins` and
+//! the generated reference page (`docs/language/builtins-reference.md`) read
+//! it from there.
+
+use std::fmt::Write;
+
+use super::registry::BuiltinRegistry;
+
+pub use achronyme_metadata::docs::*;
+
+/// The builtin reference page, grouped by category
+///
+/// `docs/language/builtins-reference.md` is this output; a test keeps it in
+/// sync (run it with `UPDATE_BUILTIN_REFERENCE=1` to rewrite the file).
+pub fn reference_markdown(registry: &BuiltinRegistry) -> String {
+    let mut out = String::from(
+        "# Built-in Function Reference\n\n\
+         <!-- Generated from crates/achronyme-metadata/src/docs.rs; do not edit by hand. -->\n\n\
+         Every function below is available without an import. Types use the\n\
+         [gradual type system](advanced-topics/gradual-type-system.md) syntax; `?` marks an\n\
+         optional parameter and `...` a variadic one.\n",
+    );
+
+    let mut by_category: Vec<(Category, Vec<&BuiltinDoc>)> = Category::ALL
+        .iter()
+        .map(|&category| (category, Vec::new()))
+        .collect();
+    for doc in registry.functions.iter().filter_map(|meta| meta.doc) {
+        if let Some((_, docs)) = by_category.iter_mut().find(|(c, _)| *c == doc.category) {
+            docs.push(doc);
+        }
+    }
+    by_category.retain(|(_, docs)| !docs.is_empty());
+
+    out.push_str("\n## Contents\n\n");
+    for (category, docs) in &by_category {
+        let _ = writeln!(
+            out,
+            "- [{}](#{}) ({})",
+            category.title(),
+            anchor(category.title()),
+            docs.len()
+        );
+    }
+
+    for (category, docs) in &by_category {
+        let _ = write!(out, "\n## {}\n", category.title());
+        for doc in docs {
+            let _ = write!(out, "\n### {}\n\n{}", doc.name, doc.markdown());
+        }
+    }
+    out
+}
+
+/// GitHub-style heading anchor
+fn anchor(title: &str) -> String {
+    title
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_ascii_alphanumeric() || c == '-' => Some(c.to_ascii_lowercase()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor() {
+        assert_eq!(anchor("Async, Tasks and Files"), "async-tasks-and-files");
+        assert_eq!(anchor("Higher-Order Functions"), "higher-order-functions");
+    }
+}
@@ -1,20 +1,126 @@
 //! Encoding and Parsing built-ins (JSON, CSV)
+//!
+//! JSON serialization covers every data value: tensors become nested arrays
+//! (or a tagged `{"$type": "Tensor", shape, data}` object), complex numbers
+//! use a tagged `{"$type": "Complex", re, im}` object, and NaN/Infinity are
+//! handled according to the `special_floats` option. `json_parse` can revive
+//! the tagged forms so data round-trips losslessly.
 
 use crate::error::VmError;
 use crate::value::Value;
 use crate::vm::VM;
+use achronyme_types::complex::Complex;
 use achronyme_types::sync::shared;
+use achronyme_types::tensor::{ComplexTensor, RealTensor};
+use serde::Serialize;
+use serde_json::json;
 use std::collections::HashMap;
 
 // ============================================================================
 // JSON
 // ============================================================================
 
-/// json_parse(string) -> Value
+/// Key that marks a tagged object (`{"$type": "Tensor", ...}`)
+const TYPE_TAG: &str = "$type";
+
+/// How NaN and ±Infinity are written, since JSON has no literal for them
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SpecialFloats {
+    /// Write `null` (default)
+    Null,
+    /// Write the strings "NaN", "Infinity" and "-Infinity"
+    String,
+    /// Refuse to serialize
+    Error,
+}
+
+/// Options accepted by json_stringify
+#[derive(Debug, Clone, Copy)]
+struct JsonWriteOptions {
+    indent: Option<usize>,
+    tagged_tensors: bool,
+    special_floats: SpecialFloats,
+}
+
+impl JsonWriteOptions {
+    /// Parse `pretty?` (Boolean) or `{pretty, indent, tensors, special_floats}`
+    fn from_value(value: Option<&Value>) -> Result<Self, VmError> {
+        let mut options = JsonWriteOptions {
+            indent: None,
+            tagged_tensors: false,
+            special_floats: SpecialFloats::Null,
+        };
+        let rc = match value {
+            None | Some(Value::Null) | Some(Value::Boolean(false)) => return Ok(options),
+            Some(Value::Boolean(true)) => {
+                options.indent = Some(2);
+                return Ok(options);
+            }
+            Some(Value::Record(rc)) => rc,
+            Some(other) => {
+                return Err(VmError::TypeError {
+                    operation: "json_stringify".to_string(),
+                    expected: "Boolean or options Record".to_string(),
+                    got: format!("{:?}", other),
+                })
+            }
+        };
+
+        for (key, value) in rc.read().iter() {
+            match (key.as_str(), value) {
+                ("pretty", Value::Boolean(b)) => {
+                    if *b {
+                        options.indent.get_or_insert(2);
+                    }
+                }
+                ("indent", Value::Number(n)) if *n >= 0.0 && n.fract() == 0.0 => {
+                    options.indent = Some(*n as usize);
+                }
+                ("tensors", Value::String(s)) if s == "nested" || s == "tagged" => {
+                    options.tagged_tensors = s == "tagged";
+                }
+                ("special_floats", Value::String(s)) => {
+                    options.special_floats = match s.as_str() {
+                        "null" => SpecialFloats::Null,
+                        "string" => SpecialFloats::String,
+                        "error" => SpecialFloats::Error,
+                        _ => {
+                            return Err(VmError::Runtime(format!(
+                                "json_stringify(): special_floats must be \"null\", \"string\" or \"error\", got \"{}\"",
+                                s
+                            )))
+                        }
+                    };
+                }
+                ("pretty" | "indent" | "tensors" | "special_floats", _) => {
+                    return Err(VmError::Runtime(format!(
+                        "json_stringify(): invalid value for option '{}': {:?}",
+                        key, value
+                    )))
+                }
+                _ => {
+                    return Err(VmError::Runtime(format!(
+                        "json_stringify(): unknown option '{}'",
+                        key
+                    )))
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// json_parse(string, options?) -> Value
+///
+/// With `{revive: true}`, tagged Tensor/ComplexTensor/Complex objects are
+/// turned back into values, reading the strings "NaN", "Infinity" and
+/// "-Infinity" in their payloads as numbers. Those strings anywhere else stay
+/// strings, so a plain NaN written with `special_floats: "string"` does not
+/// round-trip.
 pub fn vm_json_parse(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
-    if args.len() != 1 {
+    if args.is_empty() || args.len() > 2 {
         return Err(VmError::Runtime(
-            "json_parse() expects 1 argument".to_string(),
+            "json_parse() expects 1 or 2 arguments".to_string(),
         ));
     }
 
@@ -29,35 +135,63 @@ pub fn vm_json_parse(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
         }
     };
 
+    let revive = match args.get(1) {
+        None | Some(Value::Null) => false,
+        Some(Value::Record(rc)) => {
+            let map = rc.read();
+            if let Some(key) = map.keys().find(|k| k.as_str() != "revive") {
+                return Err(VmError::Runtime(format!(
+                    "json_parse(): unknown option '{}'",
+                    key
+                )));
+            }
+            matches!(map.get("revive"), Some(Value::Boolean(true)))
+        }
+        Some(other) => {
+            return Err(VmError::TypeError {
+                operation: "json_parse".to_string(),
+                expected: "options Record".to_string(),
+                got: format!("{:?}", other),
+            })
+        }
+    };
+
     let parsed: serde_json::Value = serde_json::from_str(json_str)
         .map_err(|e| VmError::Runtime(format!("JSON parse error: {}", e)))?;
 
-    Ok(json_to_value(parsed))
+    if revive {
+        revive_value(parsed)
+    } else {
+        Ok(json_to_value(parsed))
+    }
 }
 
-/// json_stringify(value, pretty?) -> String
+/// json_stringify(value, options?) -> String
+///
+/// `options` is either `pretty` (Boolean) or a record with `pretty`,
+/// `indent` (spaces, implies pretty), `tensors` ("nested" or "tagged") and
+/// `special_floats` ("null", "string" or "error").
 pub fn vm_json_stringify(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
-    if args.is_empty() {
+    if args.is_empty() || args.len() > 2 {
         return Err(VmError::Runtime(
-            "json_stringify() expects at least 1 argument".to_string(),
+            "json_stringify() expects 1 or 2 arguments".to_string(),
         ));
     }
 
-    let pretty = if args.len() > 1 {
-        match args[1] {
-            Value::Boolean(b) => b,
-            _ => false,
-        }
-    } else {
-        false
-    };
-
-    let json_val = value_to_json(&args[0])?;
+    let options = JsonWriteOptions::from_value(args.get(1))?;
+    let json_val = value_to_json(&args[0], &options)?;
 
-    let result = if pretty {
-        serde_json::to_string_pretty(&json_val)
-    } else {
-        serde_json::to_string(&json_val)
+    let result = match options.indent {
+        Some(width) => {
+            let indent = vec![b' '; width];
+            let formatter = serde_json::ser::PrettyFormatter::with_indent(&indent);
+            let mut buf = Vec::new();
+            let mut serializer = serde_json::Serializer::with_formatter(&mut buf, formatter);
+            json_val
+                .serialize(&mut serializer)
+                .map(|_| String::from_utf8_lossy(&buf).into_owned())
+        }
+        None => serde_json::to_string(&json_val),
     };
 
     match result {
@@ -96,26 +230,221 @@ fn json_to_value(json: serde_json::Value) -> Value {
     }
 }
 
-fn value_to_json(val: &Value) -> Result<serde_json::Value, VmError> {
-    match val {
-        Value::Null => Ok(serde_json::Value::Null),
-        Value::Boolean(b) => Ok(serde_json::Value::Bool(*b)),
-        Value::Number(n) => {
-            // Handle Infinity/NaN because JSON spec doesn't support them
-            if n.is_infinite() || n.is_nan() {
-                Ok(serde_json::Value::Null)
+/// Like `json_to_value`, but restores tagged objects. Special float strings
+/// are only read inside their payloads; elsewhere they stay strings.
+fn revive_value(json: serde_json::Value) -> Result<Value, VmError> {
+    match json {
+        serde_json::Value::Array(arr) => {
+            let vec = arr
+                .into_iter()
+                .map(revive_value)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Value::Vector(shared(vec)))
+        }
+        serde_json::Value::Object(map) => match map.get(TYPE_TAG).and_then(|t| t.as_str()) {
+            Some("Complex") => Ok(Value::Complex(tagged_complex(&map)?)),
+            Some("Tensor") => {
+                let data = tagged_numbers(&map, "data")?;
+                let shape = tagged_shape(&map, data.len())?;
+                RealTensor::new(data, shape)
+                    .map(Value::Tensor)
+                    .map_err(|e| VmError::Runtime(format!("json_parse(): {}", e)))
+            }
+            Some("ComplexTensor") => {
+                let re = tagged_numbers(&map, "re")?;
+                let im = tagged_numbers(&map, "im")?;
+                if re.len() != im.len() {
+                    return Err(VmError::Runtime(
+                        "json_parse(): ComplexTensor 're' and 'im' differ in length".to_string(),
+                    ));
+                }
+                let shape = tagged_shape(&map, re.len())?;
+                let data = re.into_iter().zip(im).map(|(re, im)| Complex::new(re, im));
+                ComplexTensor::new(data.collect(), shape)
+                    .map(Value::ComplexTensor)
+                    .map_err(|e| VmError::Runtime(format!("json_parse(): {}", e)))
+            }
+            _ => {
+                let mut records = HashMap::new();
+                for (k, v) in map {
+                    records.insert(k, revive_value(v)?);
+                }
+                Ok(Value::Record(shared(records)))
+            }
+        },
+        other => Ok(json_to_value(other)),
+    }
+}
+
+fn special_float(s: &str) -> Option<f64> {
+    match s {
+        "NaN" => Some(f64::NAN),
+        "Infinity" => Some(f64::INFINITY),
+        "-Infinity" => Some(f64::NEG_INFINITY),
+        _ => None,
+    }
+}
+
+/// Read a number from a tagged object; null and special strings are accepted
+fn tagged_number(json: &serde_json::Value) -> Option<f64> {
+    match json {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::Null => Some(f64::NAN),
+        serde_json::Value::String(s) => special_float(s),
+        _ => None,
+    }
+}
+
+fn tagged_field<'a>(
+    map: &'a serde_json::Map<String, serde_json::Value>,
+    field: &str,
+) -> Result<&'a serde_json::Value, VmError> {
+    map.get(field).ok_or_else(|| {
+        VmError::Runtime(format!(
+            "json_parse(): tagged {} is missing '{}'",
+            map[TYPE_TAG], field
+        ))
+    })
+}
+
+fn tagged_numbers(
+    map: &serde_json::Map<String, serde_json::Value>,
+    field: &str,
+) -> Result<Vec<f64>, VmError> {
+    tagged_field(map, field)?
+        .as_array()
+        .and_then(|items| items.iter().map(tagged_number).collect::<Option<Vec<_>>>())
+        .ok_or_else(|| {
+            VmError::Runtime(format!(
+                "json_parse(): '{}' of tagged {} must be an array of numbers",
+                field, map[TYPE_TAG]
+            ))
+        })
+}
+
+/// Read the shape of a tagged tensor and check it against its element count
+/// before any tensor is built, so a huge or mismatched shape is an error.
+fn tagged_shape(
+    map: &serde_json::Map<String, serde_json::Value>,
+    len: usize,
+) -> Result<Vec<usize>, VmError> {
+    let shape = tagged_numbers(map, "shape")?
+        .into_iter()
+        .map(|n| {
+            if n >= 0.0 && n.fract() == 0.0 && n <= usize::MAX as f64 {
+                Ok(n as usize)
             } else {
-                serde_json::Number::from_f64(*n)
-                    .map(serde_json::Value::Number)
-                    .ok_or_else(|| VmError::Runtime("Invalid number for JSON".to_string()))
+                Err(VmError::Runtime(format!(
+                    "json_parse(): shape of tagged {} must hold non-negative integers, got {}",
+                    map[TYPE_TAG], n
+                )))
             }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let count = shape
+        .iter()
+        .try_fold(1usize, |acc, &dim| acc.checked_mul(dim))
+        .ok_or_else(|| {
+            VmError::Runtime(format!(
+                "json_parse(): shape {:?} of tagged {} is too large",
+                shape, map[TYPE_TAG]
+            ))
+        })?;
+    if count != len {
+        return Err(VmError::Runtime(format!(
+            "json_parse(): shape {:?} of tagged {} needs {} elements, got {}",
+            shape, map[TYPE_TAG], count, len
+        )));
+    }
+    Ok(shape)
+}
+
+fn tagged_complex(map: &serde_json::Map<String, serde_json::Value>) -> Result<Complex, VmError> {
+    let part = |field: &str| {
+        tagged_number(tagged_field(map, field)?).ok_or_else(|| {
+            VmError::Runtime(format!(
+                "json_parse(): '{}' of tagged Complex must be a number",
+                field
+            ))
+        })
+    };
+    Ok(Complex::new(part("re")?, part("im")?))
+}
+
+fn number_to_json(n: f64, options: &JsonWriteOptions) -> Result<serde_json::Value, VmError> {
+    if let Some(number) = serde_json::Number::from_f64(n) {
+        return Ok(serde_json::Value::Number(number));
+    }
+    // JSON has no literal for NaN or Infinity
+    match options.special_floats {
+        SpecialFloats::Null => Ok(serde_json::Value::Null),
+        SpecialFloats::String => Ok(serde_json::Value::String(
+            if n.is_nan() {
+                "NaN"
+            } else if n > 0.0 {
+                "Infinity"
+            } else {
+                "-Infinity"
+            }
+            .to_string(),
+        )),
+        SpecialFloats::Error => Err(VmError::Runtime(format!(
+            "JSON stringify error: {} cannot be represented in JSON",
+            n
+        ))),
+    }
+}
+
+fn complex_to_json(c: &Complex, options: &JsonWriteOptions) -> Result<serde_json::Value, VmError> {
+    Ok(json!({
+        TYPE_TAG: "Complex",
+        "re": number_to_json(c.re, options)?,
+        "im": number_to_json(c.im, options)?,
+    }))
+}
+
+/// Convert row-major tensor data into nested arrays following `shape`
+fn nested_json<T>(
+    data: &[T],
+    shape: &[usize],
+    leaf: &impl Fn(&T) -> Result<serde_json::Value, VmError>,
+) -> Result<serde_json::Value, VmError> {
+    match shape.split_first() {
+        None => data
+            .first()
+            .map(leaf)
+            .unwrap_or(Ok(serde_json::Value::Null)),
+        Some((&dim, rest)) => {
+            let stride: usize = rest.iter().product();
+            let items = (0..dim)
+                .map(|i| nested_json(&data[i * stride..(i + 1) * stride], rest, leaf))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(serde_json::Value::Array(items))
         }
+    }
+}
+
+fn numbers_to_json(
+    data: impl Iterator<Item = f64>,
+    options: &JsonWriteOptions,
+) -> Result<serde_json::Value, VmError> {
+    data.map(|n| number_to_json(n, options))
+        .collect::<Result<Vec<_>, _>>()
+        .map(serde_json::Value::Array)
+}
+
+fn value_to_json(val: &Value, options: &JsonWriteOptions) -> Result<serde_json::Value, VmError> {
+    match val {
+        Value::Null => Ok(serde_json::Value::Null),
+        Value::Boolean(b) => Ok(serde_json::Value::Bool(*b)),
+        Value::Number(n) => number_to_json(*n, options),
         Value::String(s) => Ok(serde_json::Value::String(s.clone())),
+        Value::Complex(c) => complex_to_json(c, options),
         Value::Vector(v) => {
             let v = v.read();
             let mut arr = Vec::with_capacity(v.len());
             for item in v.iter() {
-                arr.push(value_to_json(item)?);
+                arr.push(value_to_json(item, options)?);
             }
             Ok(serde_json::Value::Array(arr))
         }
@@ -123,17 +452,32 @@ fn value_to_json(val: &Value) -> Result<serde_json::Value, VmError> {
             let r = r.read();
             let mut map = serde_json::Map::new();
             for (k, v) in r.iter() {
-                map.insert(k.clone(), value_to_json(v)?);
+                map.insert(k.clone(), value_to_json(v, options)?);
             }
             Ok(serde_json::Value::Object(map))
         }
-        // Tensors, Complex, etc. need custom handling or conversion
-        Value::Tensor(_t) => {
-            // Convert tensor to nested arrays
-            // Simplified: flatten for now or implement robust tensor serialization
-            // TODO: Better tensor serialization
-            Ok(serde_json::Value::String("<Tensor>".to_string()))
+        Value::Tensor(t) if options.tagged_tensors => Ok(json!({
+            TYPE_TAG: "Tensor",
+            "shape": t.shape(),
+            "data": numbers_to_json(t.data().iter().copied(), options)?,
+        })),
+        Value::Tensor(t) => {
+            nested_json(t.data(), t.shape(), &|n: &f64| number_to_json(*n, options))
+        }
+        Value::ComplexTensor(t) if options.tagged_tensors => Ok(json!({
+            TYPE_TAG: "ComplexTensor",
+            "shape": t.shape(),
+            "re": numbers_to_json(t.data().iter().map(|c| c.re), options)?,
+            "im": numbers_to_json(t.data().iter().map(|c| c.im), options)?,
+        })),
+        Value::ComplexTensor(t) => nested_json(t.data(), t.shape(), &|c: &Complex| {
+            complex_to_json(c, options)
+        }),
+        Value::DataFrame(df) => {
+            value_to_json(&crate::builtins::dataframe::frame_to_records(df), options)
         }
+        Value::MutableRef(r) => value_to_json(&r.read(), options),
+        // Non-data values (functions, futures, ...) keep their debug text
         _ => Ok(serde_json::Value::String(format!("{:?}", val))),
     }
 }
@@ -112,28 +112,26 @@ pub fn vm_env_load(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
 
     let reader = io::BufReader::new(file);
 
-    for line in reader.lines() {
-        if let Ok(l) = line {
-            let trimmed = l.trim();
-            // Skip comments and empty lines
-            if trimmed.is_empty() || trimmed.starts_with('#') {
-                continue;
-            }
+    for l in reader.lines().map_while(Result::ok) {
+        let trimmed = l.trim();
+        // Skip comments and empty lines
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
 
-            if let Some((key, value)) = trimmed.split_once('=') {
-                let key = key.trim();
-                let mut value = value.trim();
+        if let Some((key, value)) = trimmed.split_once('=') {
+            let key = key.trim();
+            let mut value = value.trim();
 
-                // Remove optional quotes
-                if (value.starts_with('"') && value.ends_with('"'))
-                    || (value.starts_with('‘') && value.ends_with('’'))
-                {
-                    value = &value[1..value.len() - 1];
-                }
+            // Remove optional quotes
+            if (value.starts_with('"') && value.ends_with('"'))
+                || (value.starts_with('‘') && value.ends_with('’'))
+            {
+                value = &value[1..value.len() - 1];
+            }
 
-                if !key.is_empty() {
-                    env::set_var(key, value);
-                }
+            if !key.is_empty() {
+                env::set_var(key, value);
             }
         }
     }
@@ -2,8 +2,17 @@ use crate::error::VmError;
 use crate::value::Value;
 use crate::vm::VM;
 use achronyme_gui::components;
-use achronyme_gui::runner::{run_native, Evaluator};
+use achronyme_gui::export::{self, ImageFormat};
+use achronyme_gui::harness::Harness;
+use achronyme_gui::runner::egui;
+use achronyme_gui::runner::{run_native, ErrorHandling, Evaluator, ScriptError};
+use achronyme_types::sync::Arc;
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::task::{Wake, Waker};
+
+use super::reactive::{self, RenderTracker};
 
 // --- Thread-Local VM Access (The Architecture Fix) ---
 // Instead of cloning the VM (which loses context and causes weird type errors),
@@ -11,13 +20,35 @@ use std::cell::RefCell;
 // This allows true re-entrancy: the GUI render loop runs "inside" the VM's execution context.
 
 thread_local! {
-    static ACTIVE_VM: RefCell<Option<*mut VM>> = RefCell::new(None);
+    static ACTIVE_VM: RefCell<Option<*mut VM>> = const { RefCell::new(None) };
+    static SESSION: RefCell<Option<GuiSession>> = const { RefCell::new(None) };
+}
+
+/// State kept across the frames of one `gui_run`/`gui_test`
+#[derive(Default)]
+struct GuiSession {
+    /// Signals read by the last frame; created with the first frame
+    tracker: Option<Rc<RenderTracker>>,
+    /// `ui_state` slots by key, flagged when the current frame uses them
+    slots: HashMap<String, (Value, bool)>,
+}
+
+/// Asks the window for a new frame; the render function re-runs there
+struct RepaintWaker(egui::Context);
+
+impl Wake for RepaintWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.request_repaint();
+    }
 }
 
 struct VmGuiEvaluator;
 
-impl Evaluator for VmGuiEvaluator {
-    fn call(&self, func: &Value, args: Vec<Value>) -> Result<Value, anyhow::Error> {
+impl VmGuiEvaluator {
+    fn with_vm<R>(
+        &self,
+        f: impl FnOnce(&mut VM) -> Result<R, anyhow::Error>,
+    ) -> Result<R, anyhow::Error> {
         ACTIVE_VM.with(|cell| {
             if let Some(vm_ptr) = *cell.borrow() {
                 // SAFETY: This is safe because:
@@ -27,10 +58,7 @@ impl Evaluator for VmGuiEvaluator {
                 //    We use unsafe to bypass Rust's borrow checker preventing multiple &mut to the same object on the stack,
                 //    but conceptually this is a recursive call on the same thread.
                 let vm = unsafe { &mut *vm_ptr };
-
-                // Execute synchronously using the REAL VM
-                vm.call_value(func, &args)
-                    .map_err(|e| anyhow::anyhow!("{:?}", e))
+                f(vm)
             } else {
                 Err(anyhow::anyhow!("GUI Evaluator called without active VM"))
             }
@@ -38,8 +66,119 @@ impl Evaluator for VmGuiEvaluator {
     }
 }
 
+/// Execute synchronously using the REAL VM
+fn call_on(vm: &mut VM, func: &Value, args: &[Value]) -> Result<Value, anyhow::Error> {
+    let depth = vm.frames.len();
+    vm.call_value(func, args).map_err(|e| {
+        // A failed call leaves its frames behind: read them as
+        // the stack, then unwind so the next frame starts clean
+        let stack = vm.frames[depth.min(vm.frames.len())..]
+            .iter()
+            .map(|frame| frame.function.name.clone())
+            .collect();
+        vm.frames.truncate(depth);
+        anyhow::Error::new(ScriptError {
+            message: error_message(&e),
+            stack,
+        })
+    })
+}
+
+impl Evaluator for VmGuiEvaluator {
+    fn call(&self, func: &Value, args: Vec<Value>) -> Result<Value, anyhow::Error> {
+        self.with_vm(|vm| call_on(vm, func, &args))
+    }
+
+    /// Run the frame as a reactive observer: changing any signal it read,
+    /// posting an effect to this VM or finishing a task spawned from it
+    /// wakes the window.
+    fn render(
+        &self,
+        ctx: &egui::Context,
+        render_fn: &Value,
+        args: Vec<Value>,
+    ) -> Result<Value, anyhow::Error> {
+        self.with_vm(|vm| {
+            let tracker = SESSION.with(|cell| {
+                let mut cell = cell.borrow_mut();
+                let session = cell.get_or_insert_with(GuiSession::default);
+                for slot in session.slots.values_mut() {
+                    slot.1 = false;
+                }
+                session
+                    .tracker
+                    .get_or_insert_with(|| {
+                        let waker = Waker::from(Arc::new(RepaintWaker(ctx.clone())));
+                        *vm.reactive.inbox().waker.lock() = Some(waker.clone());
+                        Rc::new(RenderTracker::new(waker))
+                    })
+                    .clone()
+            });
+
+            // Effects other tasks queued on this VM run before the frame
+            reactive::run_scheduled_effects(vm).map_err(|e| {
+                anyhow::Error::new(ScriptError {
+                    message: error_message(&e),
+                    stack: Vec::new(),
+                })
+            })?;
+
+            let value = tracker.track(vm, |vm| call_on(vm, render_fn, &args))?;
+
+            // State of components that weren't drawn this frame is dropped
+            SESSION.with(|cell| {
+                if let Some(session) = cell.borrow_mut().as_mut() {
+                    session.slots.retain(|_, slot| slot.1);
+                }
+            });
+            Ok(value)
+        })
+    }
+}
+
+/// Expose `vm` to the GUI evaluator and children callbacks while `f` runs,
+/// with a fresh GUI session
+fn with_active_vm<R>(vm: &mut VM, f: impl FnOnce() -> R) -> R {
+    let prev_waker = vm.reactive.inbox().waker.lock().clone();
+    let prev = ACTIVE_VM.with(|cell| cell.replace(Some(vm as *mut VM)));
+    let prev_session = SESSION.with(|cell| cell.replace(Some(GuiSession::default())));
+    let result = f();
+    SESSION.with(|cell| *cell.borrow_mut() = prev_session);
+    ACTIVE_VM.with(|cell| *cell.borrow_mut() = prev);
+    *vm.reactive.inbox().waker.lock() = prev_waker;
+    result
+}
+
 // --- Helpers ---
 
+/// User-facing text for an error escaping the render function
+fn error_message(error: &VmError) -> String {
+    match error {
+        VmError::UncaughtException(Value::Error {
+            message,
+            kind: Some(kind),
+            ..
+        }) => format!("{}: {}", kind, message),
+        VmError::UncaughtException(Value::Error { message, .. }) => message.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Call a children callback on the active VM, passing its error up
+fn call_children(children: &Value, args: &[Value]) -> Result<(), VmError> {
+    if !matches!(children, Value::Function(_)) {
+        return Ok(());
+    }
+    ACTIVE_VM.with(|cell| match *cell.borrow() {
+        Some(vm_ptr) => {
+            let vm = unsafe { &mut *vm_ptr };
+            // Recursive execution on the same VM
+            vm.call_value(children, args).map(|_| ())
+        }
+        None => Ok(()),
+    })
+}
+
 fn value_as_f64(v: &Value) -> Option<f64> {
     match v {
         Value::Number(n) => Some(*n),
@@ -61,6 +200,8 @@ pub fn vm_gui_run(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     let mut width = 800.0;
     let mut height = 600.0;
     let mut title = "Achronyme App".to_string();
+    let mut errors = ErrorHandling::default();
+    let mut continuous = false;
 
     if let Some(Value::Record(r)) = args.get(1) {
         let opts = r.read();
@@ -73,6 +214,15 @@ pub fn vm_gui_run(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
         if let Some(Value::String(t)) = opts.get("title") {
             title = t.clone();
         }
+        if let Some(Value::Boolean(halt)) = opts.get("halt_on_error") {
+            errors.halt = *halt;
+        }
+        if let Some(handler @ Value::Function(_)) = opts.get("on_error") {
+            errors.on_error = Some(handler.clone());
+        }
+        if let Some(Value::Boolean(c)) = opts.get("continuous") {
+            continuous = *c;
+        }
     }
 
     let viewport = achronyme_gui::runner::egui::ViewportBuilder::default()
@@ -83,17 +233,16 @@ pub fn vm_gui_run(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
         ..Default::default()
     };
 
-    // Store VM pointer in thread-local storage
-    ACTIVE_VM.with(|cell| {
-        *cell.borrow_mut() = Some(vm as *mut VM);
-    });
-
-    // Run GUI (Blocking)
-    let result = run_native(&title, render_fn, Box::new(VmGuiEvaluator), native_options);
-
-    // Clear VM pointer
-    ACTIVE_VM.with(|cell| {
-        *cell.borrow_mut() = None;
+    // Run GUI (Blocking) with the VM pointer in thread-local storage
+    let result = with_active_vm(vm, || {
+        run_native(
+            &title,
+            render_fn,
+            Box::new(VmGuiEvaluator),
+            native_options,
+            errors,
+            continuous,
+        )
     });
 
     match result {
@@ -102,9 +251,184 @@ pub fn vm_gui_run(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     }
 }
 
+/// A scripted interaction for `gui_test`
+enum GuiAction {
+    Click(String),
+    Input(String, String),
+    Slider(String, f64),
+    Frames(usize),
+    Check(Value),
+    Redraw(bool),
+}
+
+impl GuiAction {
+    fn from_value(value: &Value) -> Result<Self, VmError> {
+        let invalid = || {
+            VmError::TypeError {
+            operation: "gui_test".to_string(),
+            expected:
+                "action record ({click}, {input, text}, {slider, value}, {frames}, {check} or {redraw})"
+                    .to_string(),
+            got: format!("{:?}", value),
+        }
+        };
+        let Value::Record(r) = value else {
+            return Err(invalid());
+        };
+        let r = r.read();
+        let string = |key: &str| match r.get(key) {
+            Some(Value::String(s)) => Some(s.clone()),
+            _ => None,
+        };
+
+        if let Some(target) = string("click") {
+            Ok(GuiAction::Click(target))
+        } else if let (Some(target), Some(text)) = (string("input"), string("text")) {
+            Ok(GuiAction::Input(target, text))
+        } else if let (Some(target), Some(Value::Number(n))) = (string("slider"), r.get("value")) {
+            Ok(GuiAction::Slider(target, *n))
+        } else if let Some(Value::Number(n)) = r.get("frames") {
+            Ok(GuiAction::Frames(*n as usize))
+        } else if let Some(f @ Value::Function(_)) = r.get("check") {
+            Ok(GuiAction::Check(f.clone()))
+        } else if let Some(Value::Boolean(expected)) = r.get("redraw") {
+            Ok(GuiAction::Redraw(*expected))
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+/// gui_test(render_fn, actions, options?) -> String
+///
+/// Run a GUI render function headlessly, apply `actions` in order and return
+/// a text snapshot of the widgets drawn in the final frame. Actions target
+/// widgets by label or by id in draw order (`"slider#0"`):
+/// `{click: target}`, `{input: target, text: "..."}`,
+/// `{slider: target, value: n}`, `{frames: n}`, `{check: fn}`, which is
+/// called with the current snapshot and fails the test by returning false,
+/// and `{redraw: bool}`, which asserts whether the UI asked for a new frame.
+/// `options` accepts `width` and `height` (default 800x600).
+pub fn vm_gui_test(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() < 2 || args.len() > 3 {
+        return Err(VmError::Runtime(format!(
+            "gui_test() expects 2 or 3 arguments, got {}",
+            args.len()
+        )));
+    }
+    let render_fn = args[0].clone();
+    let actions = match &args[1] {
+        Value::Vector(v) => v
+            .read()
+            .iter()
+            .map(GuiAction::from_value)
+            .collect::<Result<Vec<_>, _>>()?,
+        other => {
+            return Err(VmError::TypeError {
+                operation: "gui_test".to_string(),
+                expected: "Vector of actions".to_string(),
+                got: format!("{:?}", other),
+            })
+        }
+    };
+
+    let mut size = egui::vec2(800.0, 600.0);
+    if let Some(Value::Record(r)) = args.get(2) {
+        let opts = r.read();
+        if let Some(Value::Number(w)) = opts.get("width") {
+            size.x = *w as f32;
+        }
+        if let Some(Value::Number(h)) = opts.get("height") {
+            size.y = *h as f32;
+        }
+    }
+
+    let gui_error = |step: usize, e: anyhow::Error| {
+        VmError::Runtime(format!("gui_test(): action {}: {}", step, e))
+    };
+
+    with_active_vm(vm, || {
+        let mut harness = Harness::new(render_fn, Rc::new(Box::new(VmGuiEvaluator)), size);
+        harness
+            .step()
+            .map_err(|e| VmError::Runtime(format!("gui_test(): first frame: {}", e)))?;
+
+        for (step, action) in actions.iter().enumerate() {
+            match action {
+                GuiAction::Click(target) => harness.click(target),
+                GuiAction::Input(target, text) => harness.type_text(target, text),
+                GuiAction::Slider(target, value) => harness.drag_slider(target, *value),
+                GuiAction::Frames(n) => harness.run(*n),
+                GuiAction::Check(check) => {
+                    let snapshot = Value::String(harness.snapshot());
+                    let passed = ACTIVE_VM.with(|cell| match *cell.borrow() {
+                        Some(vm_ptr) => {
+                            let vm = unsafe { &mut *vm_ptr };
+                            vm.call_value(check, &[snapshot])
+                        }
+                        None => Ok(Value::Null),
+                    })?;
+                    if matches!(passed, Value::Boolean(false)) {
+                        return Err(VmError::Runtime(format!(
+                            "gui_test(): check at action {} failed; widgets:\n{}",
+                            step,
+                            harness.snapshot()
+                        )));
+                    }
+                    Ok(())
+                }
+                GuiAction::Redraw(expected) => {
+                    if harness.wants_redraw() != *expected {
+                        let what = if *expected { "a" } else { "no" };
+                        Err(anyhow::anyhow!("expected {} redraw request", what))
+                    } else {
+                        Ok(())
+                    }
+                }
+            }
+            .map_err(|e| gui_error(step, e))?;
+        }
+
+        Ok(Value::String(harness.snapshot()))
+    })
+}
+
+/// ui_state(key, initial) -> Signal
+///
+/// Local state for a component: the same `key` returns the same signal on
+/// every frame, starting at `initial`. The slot is dropped after a frame
+/// that doesn't ask for it (e.g. its component was hidden), so it starts
+/// over when drawn again.
+pub fn vm_ui_state(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let key = match &args[0] {
+        Value::String(key) => key.clone(),
+        other => {
+            return Err(VmError::TypeError {
+                operation: "ui_state".to_string(),
+                expected: "String key".to_string(),
+                got: format!("{:?}", other),
+            })
+        }
+    };
+
+    SESSION.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        let session = cell.as_mut().ok_or_else(|| {
+            VmError::Runtime("ui_state() can only be used while a GUI is running".to_string())
+        })?;
+        if let Some(slot) = session.slots.get_mut(&key) {
+            slot.1 = true;
+            return Ok(slot.0.clone());
+        }
+        let signal = reactive::vm_signal(vm, &args[1..2])?;
+        session.slots.insert(key, (signal.clone(), true));
+        Ok(signal)
+    })
+}
+
 pub fn vm_ui_label(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     let text = args
-        .get(0)
+        .first()
         .cloned()
         .unwrap_or(Value::String("".to_string()));
     let style = args.get(1).cloned().unwrap_or(Value::Null);
@@ -121,7 +445,7 @@ pub fn vm_ui_label(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
 
 pub fn vm_ui_button(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     let text = args
-        .get(0)
+        .first()
         .cloned()
         .unwrap_or(Value::String("".to_string()));
     let style = args.get(1).cloned().unwrap_or(Value::Null);
@@ -136,7 +460,7 @@ pub fn vm_ui_button(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
 }
 
 pub fn vm_ui_text_input(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
-    let signal = args.get(0).cloned().unwrap_or(Value::Null);
+    let signal = args.first().cloned().unwrap_or(Value::Null);
     let style = args.get(1).cloned().unwrap_or(Value::Null);
 
     if let Value::Signal(sig_rc) = &signal {
@@ -166,9 +490,9 @@ pub fn vm_ui_text_input(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
 }
 
 pub fn vm_ui_slider(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
-    let signal = args.get(0).cloned().unwrap_or(Value::Null);
-    let min = args.get(1).and_then(|v| value_as_f64(v)).unwrap_or(0.0);
-    let max = args.get(2).and_then(|v| value_as_f64(v)).unwrap_or(100.0);
+    let signal = args.first().cloned().unwrap_or(Value::Null);
+    let min = args.get(1).and_then(value_as_f64).unwrap_or(0.0);
+    let max = args.get(2).and_then(value_as_f64).unwrap_or(100.0);
     let style = args.get(3).cloned().unwrap_or(Value::Null);
 
     if let Value::Signal(sig_rc) = &signal {
@@ -191,7 +515,7 @@ pub fn vm_ui_slider(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
 }
 
 pub fn vm_ui_box(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
-    let props = args.get(0).cloned().unwrap_or(Value::Null);
+    let props = args.first().cloned().unwrap_or(Value::Null);
 
     let mut style = Value::Null;
     let mut children = Value::Null;
@@ -211,25 +535,17 @@ pub fn vm_ui_box(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
         }
     }
 
+    let mut result = Ok(());
     components::container(&style, || {
-        if let Value::Function(_) = children {
-            // Access the active VM from thread local
-            ACTIVE_VM.with(|cell| {
-                if let Some(vm_ptr) = *cell.borrow() {
-                    let vm = unsafe { &mut *vm_ptr };
-                    // Recursive execution on the same VM
-                    let _ = vm.call_value(&children, &[]);
-                }
-            });
-        }
+        result = call_children(&children, &[]);
     });
 
-    Ok(Value::Null)
+    result.map(|_| Value::Null)
 }
 
 pub fn vm_ui_plot(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     let title = args
-        .get(0)
+        .first()
         .cloned()
         .unwrap_or(Value::String("Plot".to_string()));
     let options = args.get(1).cloned().unwrap_or(Value::Null);
@@ -243,8 +559,34 @@ pub fn vm_ui_plot(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     Ok(Value::Null)
 }
 
+/// plot_save(path, options) -> Null
+///
+/// Render a plot without a window or GPU. Takes the same options record as
+/// `ui_plot` (plus `title`, `width`, `height`, ...). The image format comes
+/// from `options.format` or the file extension (`.svg` / `.png`).
+pub fn vm_plot_save(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let path = match &args[0] {
+        Value::String(s) => s.clone(),
+        other => {
+            return Err(VmError::TypeError {
+                operation: "plot_save".to_string(),
+                expected: "String path".to_string(),
+                got: format!("{:?}", other),
+            })
+        }
+    };
+    let options = &args[1];
+
+    let bytes = ImageFormat::detect(&path, options)
+        .and_then(|format| export::render(options, format))
+        .map_err(|e| VmError::Runtime(format!("plot_save(): {}", e)))?;
+    std::fs::write(&path, bytes)
+        .map_err(|e| VmError::Runtime(format!("plot_save(): '{}': {}", path, e)))?;
+    Ok(Value::Null)
+}
+
 pub fn vm_ui_checkbox(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
-    let signal = args.get(0).cloned().unwrap_or(Value::Null);
+    let signal = args.first().cloned().unwrap_or(Value::Null);
     let label = args
         .get(1)
         .cloned()
@@ -283,7 +625,7 @@ pub fn vm_ui_checkbox(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
 }
 
 pub fn vm_ui_combobox(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
-    let signal = args.get(0).cloned().unwrap_or(Value::Null);
+    let signal = args.first().cloned().unwrap_or(Value::Null);
     let options = args.get(1).cloned().unwrap_or(Value::Null);
     let style = args.get(2).cloned().unwrap_or(Value::Null);
 
@@ -325,7 +667,7 @@ pub fn vm_ui_combobox(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
 }
 
 pub fn vm_ui_radio(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
-    let signal = args.get(0).cloned().unwrap_or(Value::Null);
+    let signal = args.first().cloned().unwrap_or(Value::Null);
     let value_to_select = args.get(1).cloned().unwrap_or(Value::Null);
     let label = args
         .get(2)
@@ -371,7 +713,7 @@ pub fn vm_ui_radio(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
 }
 
 pub fn vm_ui_tabs(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
-    let titles = args.get(0).cloned().unwrap_or(Value::Null);
+    let titles = args.first().cloned().unwrap_or(Value::Null);
     let signal = args.get(1).cloned().unwrap_or(Value::Null);
     let content_fn = args.get(2).cloned().unwrap_or(Value::Null);
     let style = args.get(3).cloned().unwrap_or(Value::Null);
@@ -413,21 +755,13 @@ pub fn vm_ui_tabs(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
         current_idx = idx;
     }
 
-    if let Value::Function(_) = content_fn {
-        ACTIVE_VM.with(|cell| {
-            if let Some(vm_ptr) = *cell.borrow() {
-                let vm = unsafe { &mut *vm_ptr };
-                let _ = vm.call_value(&content_fn, &[Value::Number(current_idx as f64)]);
-            }
-        });
-    }
-
+    call_children(&content_fn, &[Value::Number(current_idx as f64)])?;
     Ok(Value::Null)
 }
 
 pub fn vm_ui_collapsing(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     let title = args
-        .get(0)
+        .first()
         .cloned()
         .unwrap_or(Value::String("".to_string()));
     let children = args.get(1).cloned().unwrap_or(Value::Null);
@@ -438,38 +772,26 @@ pub fn vm_ui_collapsing(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError>
         v => format!("{:?}", v),
     };
 
+    let mut result = Ok(());
     components::collapsing(&title_str, &style, || {
-        if let Value::Function(_) = children {
-            ACTIVE_VM.with(|cell| {
-                if let Some(vm_ptr) = *cell.borrow() {
-                    let vm = unsafe { &mut *vm_ptr };
-                    let _ = vm.call_value(&children, &[]);
-                }
-            });
-        }
+        result = call_children(&children, &[]);
     });
-    Ok(Value::Null)
+    result.map(|_| Value::Null)
 }
 
 pub fn vm_ui_scroll_area(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
-    let children = args.get(0).cloned().unwrap_or(Value::Null);
+    let children = args.first().cloned().unwrap_or(Value::Null);
     let style = args.get(1).cloned().unwrap_or(Value::Null);
 
+    let mut result = Ok(());
     components::scroll_area(&style, || {
-        if let Value::Function(_) = children {
-            ACTIVE_VM.with(|cell| {
-                if let Some(vm_ptr) = *cell.borrow() {
-                    let vm = unsafe { &mut *vm_ptr };
-                    let _ = vm.call_value(&children, &[]);
-                }
-            });
-        }
+        result = call_children(&children, &[]);
     });
-    Ok(Value::Null)
+    result.map(|_| Value::Null)
 }
 
 pub fn vm_ui_progress_bar(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
-    let value = args.get(0).cloned().unwrap_or(Value::Number(0.0));
+    let value = args.first().cloned().unwrap_or(Value::Number(0.0));
     let style = args.get(1).cloned().unwrap_or(Value::Null);
 
     let progress = match value {
@@ -482,7 +804,7 @@ pub fn vm_ui_progress_bar(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError
 }
 
 pub fn vm_ui_separator(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
-    let style = args.get(0).cloned().unwrap_or(Value::Null);
+    let style = args.first().cloned().unwrap_or(Value::Null);
     components::separator(&style);
     Ok(Value::Null)
 }
@@ -491,3 +813,170 @@ pub fn vm_ui_quit(_vm: &mut VM, _args: &[Value]) -> Result<Value, VmError> {
     components::quit();
     Ok(Value::Null)
 }
+
+/// Run a string-editing widget against a signal (or a plain value, shown
+/// read-only), writing the result back into the signal when it changes.
+fn edit_string(
+    vm: &mut VM,
+    target: &Value,
+    edit: impl FnOnce(&mut String) -> bool,
+) -> Result<Value, VmError> {
+    let as_string = |v: &Value| match v {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        v => format!("{:?}", v),
+    };
+    match target {
+        Value::Signal(sig_rc) => {
+            let mut text = as_string(&sig_rc.read().value);
+            let changed = edit(&mut text);
+            if changed {
+                reactive::set_signal_value(vm, sig_rc, Value::String(text))?;
+            }
+            Ok(Value::Boolean(changed))
+        }
+        other => {
+            edit(&mut as_string(other));
+            Ok(Value::Null)
+        }
+    }
+}
+
+fn string_arg(v: &Value) -> String {
+    match v {
+        Value::String(s) => s.clone(),
+        v => format!("{:?}", v),
+    }
+}
+
+/// ui_table(data, columns, style) -> Number | null
+///
+/// Show a DataFrame or a Vector of Records as a table with sortable
+/// columns. `columns` (a Vector of names, or null for all) picks the
+/// columns. Returns the index of the clicked row, or null.
+pub fn vm_ui_table(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let columns = match &args[1] {
+        Value::Null => None,
+        Value::Vector(v) => Some(v.read().iter().map(string_arg).collect()),
+        other => {
+            return Err(VmError::TypeError {
+                operation: "ui_table".to_string(),
+                expected: "Vector of column names or null".to_string(),
+                got: format!("{:?}", other),
+            })
+        }
+    };
+    let data = components::TableData::from_value(&args[0], columns)
+        .map_err(|e| VmError::Runtime(format!("ui_table(): {}", e)))?;
+    Ok(match components::table(&data, &args[2]) {
+        Some(row) => Value::Number(row as f64),
+        None => Value::Null,
+    })
+}
+
+pub fn vm_ui_code_editor(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let style = &args[1];
+    edit_string(vm, &args[0], |text| components::code_editor(text, style))
+}
+
+/// ui_image(source, style) -> Null
+///
+/// Show a PNG, JPEG, WebP, GIF or SVG file (path) or a Tensor (or nested
+/// Vector) of values in 0..1 shaped [h, w], [h, w, 3] or [h, w, 4].
+pub fn vm_ui_image(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    // Nested numeric Vectors are promoted to a Tensor
+    let promoted = match &args[0] {
+        Value::Vector(_) => args[0].try_to_tensor(),
+        _ => None,
+    };
+    let source = match promoted.as_ref().unwrap_or(&args[0]) {
+        Value::String(path) => components::ImageSource::File(path),
+        Value::Tensor(t) => components::ImageSource::Tensor(t),
+        other => {
+            return Err(VmError::TypeError {
+                operation: "ui_image".to_string(),
+                expected: "String path, Tensor or rectangular numeric Vector".to_string(),
+                got: format!("{:?}", other),
+            })
+        }
+    };
+    components::image(&source, &args[1])
+        .map_err(|e| VmError::Runtime(format!("ui_image(): {}", e)))?;
+    Ok(Value::Null)
+}
+
+pub fn vm_ui_menu_bar(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let mut result = Ok(());
+    components::menu_bar(&args[1], || {
+        result = call_children(&args[0], &[]);
+    });
+    result.map(|_| Value::Null)
+}
+
+pub fn vm_ui_menu(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let title = string_arg(&args[0]);
+    let mut result = Ok(());
+    components::menu(&title, &args[2], || {
+        result = call_children(&args[1], &[]);
+    });
+    result.map(|_| Value::Null)
+}
+
+pub fn vm_ui_menu_item(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let text = string_arg(&args[0]);
+    Ok(Value::Boolean(components::menu_item(&text, &args[1])))
+}
+
+/// ui_modal(open, title, children, style) -> Boolean
+///
+/// Show a modal dialog while the `open` signal is true. Escape or a click
+/// outside sets it to false and returns true.
+pub fn vm_ui_modal(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let title = string_arg(&args[1]);
+    let mut open = match &args[0] {
+        Value::Signal(sig_rc) => matches!(sig_rc.read().value, Value::Boolean(true)),
+        other => matches!(other, Value::Boolean(true)),
+    };
+
+    let mut result = Ok(());
+    let closed = components::modal(&mut open, &title, &args[3], || {
+        result = call_children(&args[2], &[]);
+    });
+    result?;
+
+    if closed {
+        if let Value::Signal(sig_rc) = &args[0] {
+            reactive::set_signal_value(vm, sig_rc, Value::Boolean(false))?;
+        }
+    }
+    Ok(Value::Boolean(closed))
+}
+
+pub fn vm_ui_file_picker(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let style = &args[1];
+    edit_string(vm, &args[0], |path| components::file_picker(path, style))
+}
+
+pub fn vm_ui_color_picker(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let style = &args[1];
+    edit_string(vm, &args[0], |hex| components::color_picker(hex, style))
+}
+
+pub fn vm_ui_date_picker(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let style = &args[1];
+    edit_string(vm, &args[0], |date| components::date_picker(date, style))
+}
+
+/// ui_toast(message, kind) -> Null
+///
+/// Show a notification for a few seconds. `kind` is "info", "success",
+/// "warning" or "error" (null for "info").
+pub fn vm_ui_toast(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let message = string_arg(&args[0]);
+    let kind = match &args[1] {
+        Value::Null => String::new(),
+        other => string_arg(other),
+    };
+    components::toast(&message, &kind);
+    Ok(Value::Null)
+}
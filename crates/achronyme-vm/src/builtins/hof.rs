@@ -14,10 +14,10 @@
 use crate::error::VmError;
 use crate::value::Value;
 use crate::vm::VM;
-use crate::vm::{VmBuilder, VmIterator};
+use crate::vm::{LazyIterator, VmBuilder, VmIterator};
 
 /// Helper function to check if a value is truthy
-fn is_truthy(value: &Value) -> bool {
+pub(crate) fn is_truthy(value: &Value) -> bool {
     match value {
         Value::Boolean(b) => *b,
         Value::Null => false,
@@ -32,6 +32,7 @@ fn is_truthy(value: &Value) -> bool {
 ///
 /// Transforms each element of the collection using the callback function.
 /// Type preservation: Tensor -> Tensor (if all results are numbers), else Vector.
+/// Generators and iterators give a lazy iterator instead.
 ///
 /// # Examples
 /// ```achronyme
@@ -49,6 +50,15 @@ pub fn vm_map(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     let callback = &args[0];
     let collection = &args[1];
 
+    // Generators and iterators are mapped lazily
+    if LazyIterator::is_lazy(collection) {
+        return Ok(LazyIterator::Map {
+            source: LazyIterator::from_value(collection)?,
+            callback: callback.clone(),
+        }
+        .into_value());
+    }
+
     // Create iterator
     let mut iter = VmIterator::from_value(collection)?;
 
@@ -68,7 +78,8 @@ pub fn vm_map(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
 /// filter(predicate, collection) -> Vector
 ///
 /// Selects elements from the collection that match the predicate.
-/// Always returns a Vector (type cannot be preserved due to filtering).
+/// Returns a Vector (type cannot be preserved due to filtering), or a lazy
+/// iterator for generators and iterators.
 ///
 /// # Examples
 /// ```achronyme
@@ -86,6 +97,20 @@ pub fn vm_filter(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     let predicate = &args[0];
     let collection = &args[1];
 
+    // DataFrames filter rows (the predicate receives each row as a record)
+    if let Value::DataFrame(_) = collection {
+        return crate::builtins::dataframe::vm_df_filter(vm, collection, &args[..1]);
+    }
+
+    // Generators and iterators are filtered lazily
+    if LazyIterator::is_lazy(collection) {
+        return Ok(LazyIterator::Filter {
+            source: LazyIterator::from_value(collection)?,
+            predicate: predicate.clone(),
+        }
+        .into_value());
+    }
+
     let mut iter = VmIterator::from_value(collection)?;
     let mut builder = VmBuilder::new_vector(); // Always returns vector
 
@@ -123,9 +148,9 @@ pub fn vm_reduce(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     let mut accumulator = args[1].clone();
     let collection = &args[2];
 
-    let mut iter = VmIterator::from_value(collection)?;
+    let iter = LazyIterator::from_value(collection)?;
 
-    while let Some(item) = iter.next() {
+    while let Some(item) = LazyIterator::pull(&iter, vm)? {
         accumulator = vm.call_value(callback, &[accumulator, item])?;
     }
 
@@ -179,9 +204,9 @@ pub fn vm_any(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     let predicate = &args[0];
     let collection = &args[1];
 
-    let mut iter = VmIterator::from_value(collection)?;
+    let iter = LazyIterator::from_value(collection)?;
 
-    while let Some(item) = iter.next() {
+    while let Some(item) = LazyIterator::pull(&iter, vm)? {
         let matches = vm.call_value(predicate, &[item])?;
 
         if is_truthy(&matches) {
@@ -213,9 +238,9 @@ pub fn vm_all(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     let predicate = &args[0];
     let collection = &args[1];
 
-    let mut iter = VmIterator::from_value(collection)?;
+    let iter = LazyIterator::from_value(collection)?;
 
-    while let Some(item) = iter.next() {
+    while let Some(item) = LazyIterator::pull(&iter, vm)? {
         let matches = vm.call_value(predicate, &[item])?;
 
         if !is_truthy(&matches) {
@@ -246,9 +271,9 @@ pub fn vm_find(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     let predicate = &args[0];
     let collection = &args[1];
 
-    let mut iter = VmIterator::from_value(collection)?;
+    let iter = LazyIterator::from_value(collection)?;
 
-    while let Some(item) = iter.next() {
+    while let Some(item) = LazyIterator::pull(&iter, vm)? {
         let matches = vm.call_value(predicate, std::slice::from_ref(&item))?;
 
         if is_truthy(&matches) {
@@ -280,10 +305,10 @@ pub fn vm_find_index(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     let predicate = &args[0];
     let collection = &args[1];
 
-    let mut iter = VmIterator::from_value(collection)?;
+    let iter = LazyIterator::from_value(collection)?;
     let mut index = 0;
 
-    while let Some(item) = iter.next() {
+    while let Some(item) = LazyIterator::pull(&iter, vm)? {
         let matches = vm.call_value(predicate, &[item])?;
 
         if is_truthy(&matches) {
@@ -316,10 +341,10 @@ pub fn vm_count(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     let predicate = &args[0];
     let collection = &args[1];
 
-    let mut iter = VmIterator::from_value(collection)?;
+    let iter = LazyIterator::from_value(collection)?;
     let mut count = 0;
 
-    while let Some(item) = iter.next() {
+    while let Some(item) = LazyIterator::pull(&iter, vm)? {
         let matches = vm.call_value(predicate, &[item])?;
 
         if is_truthy(&matches) {
@@ -192,6 +192,15 @@ fn format_value(value: &Value, vm: &VM) -> String {
         }
         Value::Function(_) => "<function>".to_string(),
         Value::Generator(_) => "<generator>".to_string(),
+        Value::Iterator(_) => "<iterator>".to_string(),
+        Value::Range {
+            start,
+            end,
+            inclusive,
+        } => {
+            let op = if *inclusive { "..=" } else { ".." };
+            format!("{}{}{}", format_value(start, vm), op, format_value(end, vm))
+        }
         Value::Error { message, .. } => format!("Error: {}", message),
         _ => format!("{:?}", value), // Fallback for other types
     }
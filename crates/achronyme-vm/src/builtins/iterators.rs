@@ -0,0 +1,141 @@
+//! Lazy iterator functions
+//!
+//! This module exposes the iterator protocol backed by `vm::LazyIterator`:
+//! - iter: Lazy iterator over a collection or generator
+//! - collect: Drain an iterator into a vector
+//!
+//! plus the adapters only available as iterator methods (`it.enumerate()`).
+//! `map`, `filter`, `take` and `zip` are the HOF built-ins, which stay lazy
+//! when given a generator or iterator.
+
+use crate::error::VmError;
+use crate::value::Value;
+use crate::vm::{LazyIterator, VM};
+use achronyme_types::sync::shared;
+use std::collections::{HashMap, VecDeque};
+
+/// iter(collection) -> Iterator
+///
+/// Vectors, strings, tensors and generators get a new lazy iterator; an
+/// iterator is returned unchanged.
+pub fn vm_iter(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 1 {
+        return Err(VmError::Runtime(format!(
+            "iter() expects 1 argument, got {}",
+            args.len()
+        )));
+    }
+
+    Ok(Value::Iterator(LazyIterator::from_value(&args[0])?))
+}
+
+/// collect(iterator) -> Vector
+///
+/// Runs the iterator to the end. Collections are copied into a vector.
+pub fn vm_collect(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 1 {
+        return Err(VmError::Runtime(format!(
+            "collect() expects 1 argument, got {}",
+            args.len()
+        )));
+    }
+
+    let iter = LazyIterator::from_value(&args[0])?;
+    let items = LazyIterator::collect(&iter, vm)?;
+    Ok(Value::Vector(shared(items)))
+}
+
+/// it.next() -> {value, done}
+///
+/// Same result record as `generator.next()`.
+pub fn vm_iter_next(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let iter = LazyIterator::from_value(&args[0])?;
+    let (value, done) = match LazyIterator::pull(&iter, vm)? {
+        Some(value) => (value, false),
+        None => (Value::Null, true),
+    };
+    let mut result = HashMap::new();
+    result.insert("value".to_string(), value);
+    result.insert("done".to_string(), Value::Boolean(done));
+    Ok(Value::Record(shared(result)))
+}
+
+/// it.take_while(predicate) -> Iterator
+pub fn vm_iter_take_while(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("take_while", args, 2)?;
+    Ok(LazyIterator::TakeWhile {
+        source: LazyIterator::from_value(&args[0])?,
+        predicate: args[1].clone(),
+        done: false,
+    }
+    .into_value())
+}
+
+/// it.enumerate() -> Iterator of [index, value]
+pub fn vm_iter_enumerate(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("enumerate", args, 1)?;
+    Ok(LazyIterator::Enumerate {
+        source: LazyIterator::from_value(&args[0])?,
+        index: 0,
+    }
+    .into_value())
+}
+
+/// it.chain(other) -> Iterator
+pub fn vm_iter_chain(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("chain", args, 2)?;
+    Ok(LazyIterator::Chain {
+        first: LazyIterator::from_value(&args[0])?,
+        second: LazyIterator::from_value(&args[1])?,
+    }
+    .into_value())
+}
+
+/// it.window(size) -> Iterator of vectors
+pub fn vm_iter_window(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("window", args, 2)?;
+    let size = positive_count("window", &args[1])?;
+    Ok(LazyIterator::Window {
+        source: LazyIterator::from_value(&args[0])?,
+        size,
+        buffer: VecDeque::with_capacity(size),
+    }
+    .into_value())
+}
+
+/// it.step_by(step) -> Iterator
+pub fn vm_iter_step_by(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("step_by", args, 2)?;
+    Ok(LazyIterator::StepBy {
+        source: LazyIterator::from_value(&args[0])?,
+        step: positive_count("step_by", &args[1])?,
+        started: false,
+    }
+    .into_value())
+}
+
+/// Check a method's argument count (the receiver included)
+fn expect_args(name: &str, args: &[Value], count: usize) -> Result<(), VmError> {
+    if args.len() != count {
+        return Err(VmError::Runtime(format!(
+            "{}() expects {} argument{}, got {}",
+            name,
+            count - 1,
+            if count == 2 { "" } else { "s" },
+            args.len() - 1
+        )));
+    }
+    Ok(())
+}
+
+/// A size or step: a whole number of at least 1
+fn positive_count(name: &str, value: &Value) -> Result<usize, VmError> {
+    match value {
+        Value::Number(n) if *n >= 1.0 && n.fract() == 0.0 => Ok(*n as usize),
+        _ => Err(VmError::TypeError {
+            operation: name.to_string(),
+            expected: "positive integer".to_string(),
+            got: format!("{:?}", value),
+        }),
+    }
+}
@@ -6,6 +6,7 @@
 //! - norm: Euclidean norm (magnitude) of a vector
 //! - normalize: Normalize a vector to unit length
 //! - transpose: Transpose a 2D matrix
+//! - reshape: A tensor of a given shape from a tensor or a numeric vector
 //! - det: Determinant of a square matrix
 //! - trace: Trace (sum of diagonal elements) of a square matrix
 
@@ -277,6 +278,56 @@ pub fn vm_transpose(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     }
 }
 
+/// Rearrange the elements of a tensor or numeric vector into a tensor of
+/// the given shape, in row-major order
+pub fn vm_reshape(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 2 {
+        return Err(VmError::Runtime(format!(
+            "reshape() expects 2 arguments, got {}",
+            args.len()
+        )));
+    }
+
+    let shape = match &args[1] {
+        Value::Vector(dims) => dims
+            .read()
+            .iter()
+            .map(|dim| match dim {
+                Value::Number(n) if *n >= 0.0 && n.fract() == 0.0 => Some(*n as usize),
+                _ => None,
+            })
+            .collect::<Option<Vec<usize>>>(),
+        _ => None,
+    };
+    let Some(shape) = shape else {
+        return Err(VmError::TypeError {
+            operation: "reshape".to_string(),
+            expected: "Vector of non-negative integers as the shape".to_string(),
+            got: format!("{:?}", args[1]),
+        });
+    };
+
+    let result = match &args[0] {
+        Value::Tensor(tensor) => tensor.reshape(shape).map(Value::Tensor),
+        Value::ComplexTensor(tensor) => tensor.reshape(shape).map(Value::ComplexTensor),
+        Value::Vector(vec) if Value::is_numeric_vector(vec) => match Value::to_real_tensor(vec) {
+            Ok(tensor) => tensor.reshape(shape).map(Value::Tensor),
+            Err(_) => Value::to_complex_tensor(vec)
+                .map_err(|_| VmError::Runtime("reshape() expects a numeric vector".to_string()))?
+                .reshape(shape)
+                .map(Value::ComplexTensor),
+        },
+        _ => {
+            return Err(VmError::TypeError {
+                operation: "reshape".to_string(),
+                expected: "Tensor or numeric Vector".to_string(),
+                got: format!("{:?}", args[0]),
+            })
+        }
+    };
+    result.map_err(|e| VmError::Runtime(format!("reshape(): {}", e)))
+}
+
 /// Calculate determinant of a square matrix
 pub fn vm_det(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     if args.len() != 1 {
@@ -531,6 +582,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_reshape() {
+        let mut vm = setup_vm();
+        let numbers = |values: &[f64]| {
+            Value::Vector(shared(values.iter().map(|n| Value::Number(*n)).collect()))
+        };
+
+        let result = vm_reshape(
+            &mut vm,
+            &[
+                numbers(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]),
+                numbers(&[2.0, 3.0]),
+            ],
+        )
+        .unwrap();
+        match result {
+            Value::Tensor(t) => {
+                assert_eq!(t.shape(), &[2, 3]);
+                assert_eq!(t.data(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+            }
+            _ => panic!("Expected Tensor"),
+        }
+
+        let err = vm_reshape(&mut vm, &[numbers(&[1.0, 2.0, 3.0]), numbers(&[2.0, 2.0])]);
+        assert!(err.unwrap_err().to_string().contains("Cannot reshape"));
+    }
+
     #[test]
     fn test_det_2x2() {
         use achronyme_types::tensor::RealTensor;
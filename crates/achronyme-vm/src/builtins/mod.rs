@@ -10,27 +10,45 @@
 //! - Complex: Complex number operations
 //! - Utils: Type inspection, conversion, special value checks
 //! - Records: Object/map operations
+//! - Iterators: Lazy iterator protocol (iter, collect)
+//! - CSV I/O: Streaming file reads/writes and row iteration
+//! - DataFrame: Columnar tabular data
+//! - Binary: Bytes buffers, pack/unpack, NPY/NPZ array files
+//! - Persistence: Saving and restoring globals as `.ach` archives
+//! - Parallel: par_map/par_reduce/par_for on a worker pool
+//!
+//! Every registered function is documented in [`docs`]; the tests below fail
+//! when a registration has no entry or the entry disagrees with its arity.
 
 pub mod array_advanced;
 pub mod async_ops;
+pub mod binary;
 pub mod complex;
 pub mod concurrency;
+pub mod csv_io;
+pub mod dataframe;
 pub mod debug;
+pub mod docs;
 pub mod encoding;
 pub mod env;
 pub mod gui;
 pub mod hof;
 pub mod io;
+pub mod iterators;
 pub mod linalg;
 pub mod math;
 pub mod module_system;
 pub mod net;
+pub mod npy;
 pub mod numerical;
+pub mod parallel;
+pub mod persist;
 pub mod reactive;
 pub mod records;
 pub mod registry;
 pub mod statistics;
 pub mod string;
+pub mod tasks;
 pub mod utils;
 pub mod vector;
 
@@ -169,6 +187,7 @@ pub fn create_builtin_registry() -> BuiltinRegistry {
     registry.register("norm", linalg::vm_norm, 1);
     registry.register("normalize", linalg::vm_normalize, 1);
     registry.register("transpose", linalg::vm_transpose, 1);
+    registry.register("reshape", linalg::vm_reshape, 2);
     registry.register("det", linalg::vm_det, 1);
     registry.register("trace", linalg::vm_trace, 1);
 
@@ -237,6 +256,21 @@ pub fn create_builtin_registry() -> BuiltinRegistry {
     registry.register("findIndex", hof::vm_find_index, 2);
     registry.register("count", hof::vm_count, 2);
 
+    // ========================================================================
+    // Lazy Iterators
+    // ========================================================================
+
+    registry.register("iter", iterators::vm_iter, 1);
+    registry.register("collect", iterators::vm_collect, 1);
+
+    // ========================================================================
+    // Data-Parallel Functions (rayon worker pool)
+    // ========================================================================
+    registry.register("par_map", parallel::vm_par_map, 2);
+    registry.register("par_reduce", parallel::vm_par_reduce, 3);
+    registry.register("par_for", parallel::vm_par_for, 2);
+    registry.register("set_threads", parallel::vm_set_threads, 1);
+
     // ========================================================================
     // Module System
     // ========================================================================
@@ -275,14 +309,60 @@ pub fn create_builtin_registry() -> BuiltinRegistry {
     registry.register("append_file", async_ops::vm_append_file, 2);
     registry.register("delete_file", async_ops::vm_delete_file, 1);
     registry.register("exists", async_ops::vm_exists, 1);
+    registry.register("read_bytes", async_ops::vm_read_bytes, 1);
+    registry.register("write_bytes", async_ops::vm_write_bytes, 2);
+
+    // ========================================================================
+    // Structured Concurrency
+    // ========================================================================
+    registry.register("all_tasks", tasks::vm_all_tasks, 1);
+    registry.register("any_task", tasks::vm_any_task, 1);
+    registry.register("race", tasks::vm_race, 1);
+    registry.register("timeout", tasks::vm_timeout, 2);
+    registry.register("task_group", tasks::vm_task_group, 1);
 
     // ========================================================================
     // Encoding Functions (JSON, CSV)
     // ========================================================================
 
-    registry.register("json_parse", encoding::vm_json_parse, 1);
+    registry.register("json_parse", encoding::vm_json_parse, -1); // 1 or 2 args
     registry.register("json_stringify", encoding::vm_json_stringify, -1); // 1 or 2 args
     registry.register("csv_parse", encoding::vm_csv_parse, -1); // 1 or 2 args
+    registry.register("read_csv", csv_io::vm_read_csv, -1); // 1 or 2 args
+    registry.register("write_csv", csv_io::vm_write_csv, -1); // 2 or 3 args
+    registry.register("csv_rows", csv_io::vm_csv_rows, -1); // 1 or 2 args
+
+    // ========================================================================
+    // Binary Data Functions (Bytes, pack/unpack, NPY/NPZ)
+    // ========================================================================
+
+    registry.register("bytes", binary::vm_bytes, 1);
+    registry.register("pack", binary::vm_pack, 2);
+    registry.register("unpack", binary::vm_unpack, -1); // 2 or 3 args
+    registry.register("pack_size", binary::vm_pack_size, 1);
+    registry.register("save_npy", npy::vm_save_npy, -1); // 2 or 3 args
+    registry.register("load_npy", npy::vm_load_npy, -1); // 1 or 2 args
+    registry.register("save_npz", npy::vm_save_npz, -1); // 2 or 3 args
+    registry.register("load_npz", npy::vm_load_npz, -1); // 1 or 2 args
+
+    // ========================================================================
+    // Environment Persistence Functions (.ach archives)
+    // ========================================================================
+
+    registry.register("save_env", persist::vm_save_env, -1); // 1 or 2 args
+    registry.register("restore_env", persist::vm_restore_env, -1); // 1 or 2 args
+    registry.register("env_info", persist::vm_env_info, 1);
+    registry.register("env_bindings", persist::vm_env_bindings, 0);
+    registry.register("get_global", persist::vm_get_global, 1);
+    registry.register("set_global", persist::vm_set_global, 2);
+
+    // ========================================================================
+    // DataFrame Functions
+    // ========================================================================
+
+    registry.register("dataframe", dataframe::vm_dataframe, 1);
+    registry.register("df_from_csv", dataframe::vm_df_from_csv, -1); // 1 or 2 args
+    registry.register("df_from_json", dataframe::vm_df_from_json, 1);
 
     // ========================================================================
     // Network Functions
@@ -304,7 +384,10 @@ pub fn create_builtin_registry() -> BuiltinRegistry {
     // Concurrency Functions
     // ========================================================================
 
-    registry.register("channel", concurrency::vm_channel, 0);
+    registry.register("channel", concurrency::vm_channel, -1);
+    registry.register("broadcast", concurrency::vm_broadcast, 1);
+    registry.register("watch", concurrency::vm_watch, 1);
+    registry.register("select", concurrency::vm_select, -1);
     registry.register("AsyncMutex", concurrency::vm_mutex, 1);
 
     // ========================================================================
@@ -313,16 +396,22 @@ pub fn create_builtin_registry() -> BuiltinRegistry {
 
     registry.register("signal", reactive::vm_signal, -1); // 0 or 1 args
     registry.register("effect", reactive::vm_effect, 1);
+    registry.register("computed", reactive::vm_computed, 1);
+    registry.register("batch", reactive::vm_batch, 1);
+    registry.register("untrack", reactive::vm_untrack, 1);
 
     // ========================================================================
     // GUI Functions (Phase 6)
     // ========================================================================
 
     registry.register("gui_run", gui::vm_gui_run, -1);
+    registry.register("gui_test", gui::vm_gui_test, -1); // 2 or 3 args
+    registry.register("ui_state", gui::vm_ui_state, 2);
     registry.register("ui_label", gui::vm_ui_label, 2);
     registry.register("ui_button", gui::vm_ui_button, 2);
     registry.register("ui_box", gui::vm_ui_box, 2);
     registry.register("ui_plot", gui::vm_ui_plot, 2);
+    registry.register("plot_save", gui::vm_plot_save, 2);
     registry.register("ui_text_input", gui::vm_ui_text_input, 2);
     registry.register("ui_slider", gui::vm_ui_slider, 4);
     registry.register("ui_checkbox", gui::vm_ui_checkbox, 3);
@@ -334,6 +423,17 @@ pub fn create_builtin_registry() -> BuiltinRegistry {
     registry.register("ui_progress_bar", gui::vm_ui_progress_bar, 2);
     registry.register("ui_separator", gui::vm_ui_separator, 1);
     registry.register("ui_quit", gui::vm_ui_quit, 0);
+    registry.register("ui_table", gui::vm_ui_table, 3);
+    registry.register("ui_code_editor", gui::vm_ui_code_editor, 2);
+    registry.register("ui_image", gui::vm_ui_image, 2);
+    registry.register("ui_menu_bar", gui::vm_ui_menu_bar, 2);
+    registry.register("ui_menu", gui::vm_ui_menu, 3);
+    registry.register("ui_menu_item", gui::vm_ui_menu_item, 2);
+    registry.register("ui_modal", gui::vm_ui_modal, 4);
+    registry.register("ui_file_picker", gui::vm_ui_file_picker, 2);
+    registry.register("ui_color_picker", gui::vm_ui_color_picker, 2);
+    registry.register("ui_date_picker", gui::vm_ui_date_picker, 2);
+    registry.register("ui_toast", gui::vm_ui_toast, 2);
 
     registry
 }
@@ -358,10 +458,10 @@ mod tests {
         // Verify we have a good number of core functions
         // Math: ~30, String: ~11, Vector: ~9, I/O: 3, Stats: 3, LinAlg: 7,
         // Complex: 9, Utils: 5, Debug: 1, Records: 3, Array Advanced: 8,
-        // HOF: 9, Module: 1, Numerical: 11, Async: 7, Encoding: 3, Network: 2,
-        // Env: 4, Concurrency: 2, Reactive: 2, GUI: 16
-        // Total: ~146 functions
-        assert!(registry.len() > 100 && registry.len() < 200);
+        // HOF: 9, Parallel: 4, Module: 1, Numerical: 11, Async: 9, Tasks: 3, Encoding: 6, Binary: 8,
+        // Persistence: 6, DataFrame: 3, Network: 2, Env: 4, Concurrency: 5, Reactive: 8, GUI: 31
+        // Total: ~200 functions
+        assert!(registry.len() > 100 && registry.len() < 256);
     }
 
     #[test]
@@ -380,4 +480,127 @@ mod tests {
         assert_eq!(print_meta.name, "print");
         assert_eq!(print_meta.arity, -1);
     }
+
+    #[test]
+    fn test_every_builtin_is_documented() {
+        let registry = create_builtin_registry();
+
+        let undocumented: Vec<&str> = registry
+            .functions
+            .iter()
+            .filter(|meta| meta.doc.is_none())
+            .map(|meta| meta.name.as_str())
+            .collect();
+        assert!(
+            undocumented.is_empty(),
+            "builtins without an entry in crates/achronyme-metadata/src/docs.rs: {:?}",
+            undocumented
+        );
+
+        let unregistered: Vec<&str> = docs::BUILTIN_DOCS
+            .iter()
+            .filter(|doc| registry.get_id(doc.name).is_none())
+            .map(|doc| doc.name)
+            .collect();
+        assert!(
+            unregistered.is_empty(),
+            "documented but not registered: {:?}",
+            unregistered
+        );
+        assert_eq!(docs::BUILTIN_DOCS.len(), registry.len());
+    }
+
+    #[test]
+    fn test_builtin_docs_match_arity() {
+        use docs::ParamKind;
+
+        let registry = create_builtin_registry();
+        for meta in &registry.functions {
+            let doc = meta.doc.unwrap();
+            if meta.arity >= 0 {
+                assert_eq!(
+                    (doc.min_args(), doc.max_args()),
+                    (meta.arity as usize, Some(meta.arity as usize)),
+                    "{}: documented parameters disagree with arity {}",
+                    meta.name,
+                    meta.arity
+                );
+            } else {
+                assert_ne!(
+                    doc.max_args(),
+                    Some(doc.min_args()),
+                    "{} is variadic but documents a fixed parameter list",
+                    meta.name
+                );
+            }
+
+            // Required parameters come first, a variadic one last
+            let kinds: Vec<ParamKind> = doc.params.iter().map(|p| p.kind).collect();
+            let mut sorted = kinds.clone();
+            sorted.sort_by_key(|kind| match kind {
+                ParamKind::Required => 0,
+                ParamKind::Optional => 1,
+                ParamKind::Variadic => 2,
+            });
+            assert_eq!(kinds, sorted, "{}: parameter order", meta.name);
+            assert!(
+                kinds.iter().filter(|k| **k == ParamKind::Variadic).count() <= 1,
+                "{}: more than one variadic parameter",
+                meta.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_builtin_docs_are_well_formed() {
+        for doc in docs::BUILTIN_DOCS {
+            assert!(!doc.summary.is_empty(), "{}: empty summary", doc.name);
+            assert!(!doc.examples.is_empty(), "{}: no examples", doc.name);
+
+            let types = doc.params.iter().map(|p| p.ty).chain([doc.returns]);
+            for ty in types {
+                let source = format!("let x: {} = null", ty);
+                assert!(
+                    achronyme_parser::parse(&source).is_ok(),
+                    "{}: `{}` is not a valid type annotation",
+                    doc.name,
+                    ty
+                );
+            }
+            for param in doc.params {
+                assert!(
+                    !param.doc.is_empty(),
+                    "{}.{}: empty doc",
+                    doc.name,
+                    param.name
+                );
+            }
+            for example in doc.examples {
+                assert!(
+                    achronyme_parser::parse(example).is_ok(),
+                    "{}: example does not parse: {}",
+                    doc.name,
+                    example
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_builtin_reference_is_up_to_date() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../docs/language/builtins-reference.md");
+        let generated = docs::reference_markdown(&create_builtin_registry());
+
+        if std::env::var_os("UPDATE_BUILTIN_REFERENCE").is_some() {
+            std::fs::write(&path, &generated).unwrap();
+            return;
+        }
+        let on_disk = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            on_disk == generated,
+            "{} is stale; rerun this test with UPDATE_BUILTIN_REFERENCE=1",
+            path.display()
+        );
+    }
 }
@@ -42,24 +42,13 @@ pub fn vm_import(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
         }
     };
 
-    // Convert path to file path (add .soc extension if missing)
-    let mut file_path = if module_path.ends_with(".soc") {
-        module_path.to_string()
-    } else {
-        format!("{}.soc", module_path)
-    };
-
-    // Resolve relative paths based on current module
-    use std::path::Path;
-    if let Some(current_module) = &vm.current_module {
-        // If the path starts with ./ or ../, it's relative to the current module
-        if file_path.starts_with("./") || file_path.starts_with("../") {
-            if let Some(parent) = Path::new(current_module).parent() {
-                let resolved = parent.join(&file_path);
-                file_path = resolved.to_string_lossy().to_string();
-            }
-        }
-    }
+    // Add the .soc extension and resolve ./ and ../ against the current module
+    let file_path = achronyme_parser::modules::resolve_module_path(
+        module_path,
+        vm.current_module.as_deref().map(std::path::Path::new),
+    )
+    .to_string_lossy()
+    .to_string();
 
     let file_path_captured = file_path.clone();
 
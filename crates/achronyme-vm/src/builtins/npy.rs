@@ -0,0 +1,582 @@
+//! NumPy array file built-ins
+//!
+//! This module reads and writes NumPy's `.npy` and `.npz` formats:
+//! - save_npy / load_npy: One array per file
+//! - save_npz / load_npz: A zip archive of named arrays (a record in Achronyme)
+//!
+//! Real tensors are written as `<f8` and complex tensors as `<c16` unless a
+//! dtype is requested. Loading accepts any numeric or boolean dtype in either
+//! byte order and C or Fortran order; real data is widened to `f64`, and
+//! `load_npy(path, true)` / `load_npz(path, true)` also return the stored
+//! dtypes so the arrays can be written back unchanged.
+
+use crate::builtins::binary::Scalar;
+use crate::error::VmError;
+use crate::value::Value;
+use crate::vm::VM;
+use achronyme_types::complex::Complex;
+use achronyme_types::sync::shared;
+use achronyme_types::tensor::{ComplexTensor, RealTensor};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+// ============================================================================
+// Dtypes
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Real(Scalar),
+    Bool,
+    /// Complex with the given component type (f32 for c8, f64 for c16)
+    Complex(Scalar),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Dtype {
+    kind: Kind,
+    big_endian: bool,
+}
+
+impl Dtype {
+    /// Parse a NumPy type string such as `<f8`, `>i2`, `|u1` or `<c16`
+    fn parse(descr: &str) -> Result<Self, String> {
+        let (big_endian, code) = match descr.chars().next() {
+            Some('<') | Some('|') => (false, &descr[1..]),
+            Some('>') => (true, &descr[1..]),
+            Some('=') => (cfg!(target_endian = "big"), &descr[1..]),
+            _ => (cfg!(target_endian = "big"), descr),
+        };
+        let kind = match code {
+            "b1" => Kind::Bool,
+            "c8" => Kind::Complex(Scalar::F32),
+            "c16" => Kind::Complex(Scalar::F64),
+            _ => Kind::Real(
+                scalar_from_code(code).ok_or_else(|| format!("unsupported dtype '{}'", descr))?,
+            ),
+        };
+        Ok(Dtype { kind, big_endian })
+    }
+
+    fn item_size(self) -> usize {
+        match self.kind {
+            Kind::Real(s) => s.size(),
+            Kind::Bool => 1,
+            Kind::Complex(s) => 2 * s.size(),
+        }
+    }
+
+    fn descr(self) -> String {
+        let code = match self.kind {
+            Kind::Real(s) => scalar_code(s).to_string(),
+            Kind::Bool => "b1".to_string(),
+            Kind::Complex(s) => format!("c{}", 2 * s.size()),
+        };
+        let order = if self.item_size() == 1 {
+            '|'
+        } else if self.big_endian {
+            '>'
+        } else {
+            '<'
+        };
+        format!("{}{}", order, code)
+    }
+}
+
+fn scalar_code(scalar: Scalar) -> &'static str {
+    match scalar {
+        Scalar::I8 => "i1",
+        Scalar::U8 => "u1",
+        Scalar::I16 => "i2",
+        Scalar::U16 => "u2",
+        Scalar::I32 => "i4",
+        Scalar::U32 => "u4",
+        Scalar::I64 => "i8",
+        Scalar::U64 => "u8",
+        Scalar::F32 => "f4",
+        Scalar::F64 => "f8",
+    }
+}
+
+fn scalar_from_code(code: &str) -> Option<Scalar> {
+    [
+        Scalar::I8,
+        Scalar::U8,
+        Scalar::I16,
+        Scalar::U16,
+        Scalar::I32,
+        Scalar::U32,
+        Scalar::I64,
+        Scalar::U64,
+        Scalar::F32,
+        Scalar::F64,
+    ]
+    .into_iter()
+    .find(|s| scalar_code(*s) == code)
+}
+
+// ============================================================================
+// Encoding
+// ============================================================================
+
+/// Convert an array-like value to a real or complex tensor
+fn array_arg(value: &Value, operation: &str) -> Result<Value, VmError> {
+    match value {
+        Value::Tensor(_) | Value::ComplexTensor(_) => Ok(value.clone()),
+        Value::Number(_) | Value::Complex(_) | Value::Vector(_) => {
+            let promoted = match value {
+                Value::Complex(c) => Some(Value::ComplexTensor(
+                    ComplexTensor::new(vec![*c], vec![]).expect("scalar shape"),
+                )),
+                _ => value.try_to_tensor(),
+            };
+            promoted.ok_or_else(|| VmError::TypeError {
+                operation: operation.to_string(),
+                expected: "rectangular numeric array".to_string(),
+                got: format!("{:?}", value),
+            })
+        }
+        _ => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: "Tensor, ComplexTensor or numeric Vector".to_string(),
+            got: format!("{:?}", value),
+        }),
+    }
+}
+
+/// Serialize a tensor to `.npy` bytes
+fn encode_npy(array: &Value, dtype: Option<Dtype>, operation: &str) -> Result<Vec<u8>, VmError> {
+    let (shape, default_kind) = match array {
+        Value::Tensor(t) => (t.shape().to_vec(), Kind::Real(Scalar::F64)),
+        Value::ComplexTensor(t) => (t.shape().to_vec(), Kind::Complex(Scalar::F64)),
+        _ => unreachable!("array_arg returns tensors"),
+    };
+    let dtype = dtype.unwrap_or(Dtype {
+        kind: default_kind,
+        big_endian: false,
+    });
+
+    let shape_text = match shape.len() {
+        1 => format!("({},)", shape[0]),
+        _ => format!(
+            "({})",
+            shape
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let mut header = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}",
+        dtype.descr(),
+        shape_text
+    );
+    // Pad so the data starts on a 64-byte boundary (header ends with '\n')
+    let prefix_len = MAGIC.len() + 2 + 2;
+    let unpadded = prefix_len + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+    let header_len = u16::try_from(header.len())
+        .map_err(|_| VmError::Runtime(format!("{}(): array has too many dimensions", operation)))?;
+
+    let mut out = Vec::with_capacity(prefix_len + header.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&[1, 0]);
+    out.extend_from_slice(&header_len.to_le_bytes());
+    out.extend_from_slice(header.as_bytes());
+
+    let big = dtype.big_endian;
+    match (array, dtype.kind) {
+        (Value::Tensor(t), Kind::Real(s)) => {
+            out.reserve(t.size() * s.size());
+            t.data().iter().for_each(|n| s.encode(*n, big, &mut out));
+        }
+        (Value::Tensor(t), Kind::Bool) => {
+            out.extend(t.data().iter().map(|n| u8::from(*n != 0.0)));
+        }
+        (Value::Tensor(t), Kind::Complex(s)) => {
+            for n in t.data() {
+                s.encode(*n, big, &mut out);
+                s.encode(0.0, big, &mut out);
+            }
+        }
+        (Value::ComplexTensor(t), Kind::Complex(s)) => {
+            for c in t.data() {
+                s.encode(c.re, big, &mut out);
+                s.encode(c.im, big, &mut out);
+            }
+        }
+        _ => {
+            return Err(VmError::Runtime(format!(
+                "{}(): cannot store a complex array as '{}'",
+                operation,
+                dtype.descr()
+            )))
+        }
+    }
+    Ok(out)
+}
+
+// ============================================================================
+// Decoding
+// ============================================================================
+
+/// Find `'key':` in a header dict and return the text after it
+fn header_value<'a>(header: &'a str, key: &str) -> Result<&'a str, String> {
+    for quote in ['\'', '"'] {
+        let pattern = format!("{q}{key}{q}:", q = quote, key = key);
+        if let Some(pos) = header.find(&pattern) {
+            return Ok(header[pos + pattern.len()..].trim_start());
+        }
+    }
+    Err(format!("header is missing '{}'", key))
+}
+
+fn parse_header(header: &str) -> Result<(Dtype, bool, Vec<usize>), String> {
+    let descr_text = header_value(header, "descr")?;
+    let quote = descr_text
+        .chars()
+        .next()
+        .filter(|c| *c == '\'' || *c == '"')
+        .ok_or("structured dtypes are not supported")?;
+    let descr = descr_text[1..]
+        .split(quote)
+        .next()
+        .ok_or("unterminated descr")?;
+    let dtype = Dtype::parse(descr)?;
+
+    let fortran_order = header_value(header, "fortran_order")?.starts_with("True");
+
+    let shape_text = header_value(header, "shape")?;
+    let inner = shape_text
+        .strip_prefix('(')
+        .and_then(|s| s.split(')').next())
+        .ok_or("malformed shape")?;
+    let shape = inner
+        .split(',')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(|d| d.trim_end_matches('L').parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| format!("malformed shape '({})'", inner))?;
+
+    Ok((dtype, fortran_order, shape))
+}
+
+/// Reorder column-major data into row-major order
+fn fortran_to_c<T: Copy>(data: Vec<T>, shape: &[usize]) -> Vec<T> {
+    if shape.len() < 2 {
+        return data;
+    }
+    let mut out = Vec::with_capacity(data.len());
+    let mut index = vec![0usize; shape.len()];
+    for _ in 0..data.len() {
+        // Column-major offset of the current row-major index
+        let mut offset = 0;
+        let mut stride = 1;
+        for (i, dim) in index.iter().zip(shape) {
+            offset += i * stride;
+            stride *= dim;
+        }
+        out.push(data[offset]);
+        // Advance the row-major index (last axis fastest)
+        for axis in (0..shape.len()).rev() {
+            index[axis] += 1;
+            if index[axis] < shape[axis] {
+                break;
+            }
+            index[axis] = 0;
+        }
+    }
+    out
+}
+
+/// Parse `.npy` bytes into a Tensor or ComplexTensor and the file's dtype
+fn decode_npy(bytes: &[u8]) -> Result<(Value, Dtype), String> {
+    if bytes.len() < 10 || &bytes[..6] != MAGIC {
+        return Err("not a .npy file".to_string());
+    }
+    let (header_len, header_start) = match bytes[6] {
+        1 => (u16::from_le_bytes([bytes[8], bytes[9]]) as usize, 10),
+        2 | 3 if bytes.len() >= 12 => (
+            u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize,
+            12,
+        ),
+        v => return Err(format!("unsupported .npy version {}", v)),
+    };
+    let data_start = header_start + header_len;
+    let header = bytes
+        .get(header_start..data_start)
+        .and_then(|h| std::str::from_utf8(h).ok())
+        .ok_or("truncated header")?;
+    let (dtype, fortran_order, shape) = parse_header(header)?;
+
+    let count = shape
+        .iter()
+        .try_fold(1usize, |acc, dim| acc.checked_mul(*dim))
+        .ok_or("shape is too large")?;
+    let item_size = dtype.item_size();
+    let data = count
+        .checked_mul(item_size)
+        .and_then(|len| bytes.get(data_start..).filter(|rest| rest.len() == len))
+        .ok_or_else(|| {
+            format!(
+                "expected {} elements of {} bytes, found {} data bytes",
+                count,
+                item_size,
+                bytes.len().saturating_sub(data_start)
+            )
+        })?;
+    let big = dtype.big_endian;
+
+    let array = match dtype.kind {
+        Kind::Real(s) => {
+            let values: Vec<f64> = data
+                .chunks_exact(item_size)
+                .map(|c| s.decode(c, big))
+                .collect();
+            let values = if fortran_order {
+                fortran_to_c(values, &shape)
+            } else {
+                values
+            };
+            RealTensor::new(values, shape)
+                .map(Value::Tensor)
+                .map_err(|e| e.to_string())
+        }
+        Kind::Bool => {
+            let values: Vec<f64> = data.iter().map(|b| f64::from(u8::from(*b != 0))).collect();
+            let values = if fortran_order {
+                fortran_to_c(values, &shape)
+            } else {
+                values
+            };
+            RealTensor::new(values, shape)
+                .map(Value::Tensor)
+                .map_err(|e| e.to_string())
+        }
+        Kind::Complex(s) => {
+            let half = s.size();
+            let values: Vec<Complex> = data
+                .chunks_exact(item_size)
+                .map(|c| Complex::new(s.decode(&c[..half], big), s.decode(&c[half..], big)))
+                .collect();
+            let values = if fortran_order {
+                fortran_to_c(values, &shape)
+            } else {
+                values
+            };
+            ComplexTensor::new(values, shape)
+                .map(Value::ComplexTensor)
+                .map_err(|e| e.to_string())
+        }
+    }?;
+    Ok((array, dtype))
+}
+
+// ============================================================================
+// Built-ins
+// ============================================================================
+
+fn path_arg(value: &Value, operation: &str) -> Result<String, VmError> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        _ => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: "String path".to_string(),
+            got: format!("{:?}", value),
+        }),
+    }
+}
+
+fn io_error(operation: &str, path: &str, e: impl std::fmt::Display) -> VmError {
+    VmError::Runtime(format!("{}(): '{}': {}", operation, path, e))
+}
+
+/// Parse an optional dtype string argument
+fn dtype_arg(value: Option<&Value>, operation: &str) -> Result<Option<Dtype>, VmError> {
+    match value {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Dtype::parse(s)
+            .map(Some)
+            .map_err(|e| VmError::Runtime(format!("{}(): {}", operation, e))),
+        Some(other) => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: "dtype String".to_string(),
+            got: format!("{:?}", other),
+        }),
+    }
+}
+
+/// Parse the optional `with_dtype` flag
+fn with_dtype_arg(value: Option<&Value>, operation: &str) -> Result<bool, VmError> {
+    match value {
+        None | Some(Value::Null) => Ok(false),
+        Some(Value::Boolean(b)) => Ok(*b),
+        Some(other) => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: "Boolean".to_string(),
+            got: format!("{:?}", other),
+        }),
+    }
+}
+
+/// The `{array, dtype}` record returned when the dtype is requested
+fn array_record(array: Value, dtype: Dtype) -> Value {
+    let mut record = HashMap::new();
+    record.insert("array".to_string(), array);
+    record.insert("dtype".to_string(), Value::String(dtype.descr()));
+    Value::Record(shared(record))
+}
+
+/// save_npy(path, array, dtype?) -> Null
+///
+/// `dtype` is a NumPy type string (`"f4"`, `"<i2"`, `">f8"`, `"c8"`, ...).
+pub fn vm_save_npy(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() < 2 || args.len() > 3 {
+        return Err(VmError::Runtime(format!(
+            "save_npy() expects 2 or 3 arguments, got {}",
+            args.len()
+        )));
+    }
+    let path = path_arg(&args[0], "save_npy")?;
+    let array = array_arg(&args[1], "save_npy")?;
+    let dtype = dtype_arg(args.get(2), "save_npy")?;
+
+    let bytes = encode_npy(&array, dtype, "save_npy")?;
+    std::fs::write(&path, bytes).map_err(|e| io_error("save_npy", &path, e))?;
+    Ok(Value::Null)
+}
+
+/// load_npy(path, with_dtype?) -> Tensor | ComplexTensor | {array, dtype}
+///
+/// With `with_dtype` true the result is a record holding the array and the
+/// file's dtype string, which `save_npy` accepts to write it back as stored.
+pub fn vm_load_npy(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(VmError::Runtime(format!(
+            "load_npy() expects 1 or 2 arguments, got {}",
+            args.len()
+        )));
+    }
+    let path = path_arg(&args[0], "load_npy")?;
+    let with_dtype = with_dtype_arg(args.get(1), "load_npy")?;
+    let bytes = std::fs::read(&path).map_err(|e| io_error("load_npy", &path, e))?;
+    let (array, dtype) = decode_npy(&bytes).map_err(|e| io_error("load_npy", &path, e))?;
+    Ok(if with_dtype {
+        array_record(array, dtype)
+    } else {
+        array
+    })
+}
+
+/// An npz entry: an array, or an `{array, dtype}` record to store it as `dtype`
+fn npz_entry(value: &Value) -> Result<(Value, Option<Dtype>), VmError> {
+    let Value::Record(rc) = value else {
+        return Ok((array_arg(value, "save_npz")?, None));
+    };
+    let record = rc.read();
+    let array = record.get("array").ok_or_else(|| {
+        VmError::Runtime("save_npz(): entry records need an 'array' field".to_string())
+    })?;
+    Ok((
+        array_arg(array, "save_npz")?,
+        dtype_arg(record.get("dtype"), "save_npz")?,
+    ))
+}
+
+/// save_npz(path, arrays, compressed?) -> Null
+///
+/// `arrays` is a record of name -> array; each entry is stored as
+/// `<name>.npy`, matching `numpy.savez` (or `savez_compressed` when
+/// `compressed` is true). An entry may also be an `{array, dtype}` record,
+/// as returned by `load_npz(path, true)`, to store it with that dtype.
+pub fn vm_save_npz(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() < 2 || args.len() > 3 {
+        return Err(VmError::Runtime(format!(
+            "save_npz() expects 2 or 3 arguments, got {}",
+            args.len()
+        )));
+    }
+    let path = path_arg(&args[0], "save_npz")?;
+    let arrays = match &args[1] {
+        Value::Record(rc) => rc.read().clone(),
+        other => {
+            return Err(VmError::TypeError {
+                operation: "save_npz".to_string(),
+                expected: "Record of arrays".to_string(),
+                got: format!("{:?}", other),
+            })
+        }
+    };
+    let method = if matches!(args.get(2), Some(Value::Boolean(true))) {
+        zip::CompressionMethod::Deflated
+    } else {
+        zip::CompressionMethod::Stored
+    };
+
+    let file = std::fs::File::create(&path).map_err(|e| io_error("save_npz", &path, e))?;
+    let mut archive = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default().compression_method(method);
+
+    let mut names: Vec<&String> = arrays.keys().collect();
+    names.sort();
+    for name in names {
+        let (array, dtype) = npz_entry(&arrays[name])?;
+        let bytes = encode_npy(&array, dtype, "save_npz")?;
+        archive
+            .start_file(format!("{}.npy", name), options)
+            .map_err(|e| io_error("save_npz", &path, e))?;
+        archive
+            .write_all(&bytes)
+            .map_err(|e| io_error("save_npz", &path, e))?;
+    }
+    archive
+        .finish()
+        .map_err(|e| io_error("save_npz", &path, e))?;
+    Ok(Value::Null)
+}
+
+/// load_npz(path, with_dtype?) -> Record
+///
+/// With `with_dtype` true each entry is an `{array, dtype}` record, which
+/// `save_npz` accepts to write the archive back as stored.
+pub fn vm_load_npz(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(VmError::Runtime(format!(
+            "load_npz() expects 1 or 2 arguments, got {}",
+            args.len()
+        )));
+    }
+    let path = path_arg(&args[0], "load_npz")?;
+    let with_dtype = with_dtype_arg(args.get(1), "load_npz")?;
+    let file = std::fs::File::open(&path).map_err(|e| io_error("load_npz", &path, e))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| io_error("load_npz", &path, e))?;
+
+    let mut arrays = HashMap::new();
+    for i in 0..archive.len() {
+        let mut entry = archive
+            .by_index(i)
+            .map_err(|e| io_error("load_npz", &path, e))?;
+        let Some(name) = entry.name().strip_suffix(".npy").map(str::to_string) else {
+            continue;
+        };
+        // entry.size() comes from the archive, so it isn't used to preallocate
+        let mut bytes = Vec::new();
+        entry
+            .read_to_end(&mut bytes)
+            .map_err(|e| io_error("load_npz", &path, e))?;
+        let (array, dtype) = decode_npy(&bytes)
+            .map_err(|e| io_error("load_npz", &path, format!("{}: {}", name, e)))?;
+        let entry = if with_dtype {
+            array_record(array, dtype)
+        } else {
+            array
+        };
+        arrays.insert(name, entry);
+    }
+    Ok(Value::Record(shared(arrays)))
+}
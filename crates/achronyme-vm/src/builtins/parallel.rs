@@ -0,0 +1,146 @@
+//! Data-parallel built-ins
+//!
+//! `par_map`, `par_reduce` and `par_for` run a closure over a collection on
+//! the shared rayon pool (see `achronyme_types::parallel`). Every worker gets
+//! its own child VM, so the closure should be pure: writes to captured
+//! mutable state are thread-safe but happen in no particular order.
+
+use crate::error::VmError;
+use crate::value::Value;
+use crate::vm::{VmBuilder, VmIterator, VM};
+use achronyme_types::parallel;
+use achronyme_types::sync::Mutex;
+use rayon::prelude::*;
+
+/// Materialize a collection (or numeric Range) into its elements
+fn collect_items(collection: &Value, operation: &str) -> Result<Vec<Value>, VmError> {
+    if let Value::Range {
+        start,
+        end,
+        inclusive,
+    } = collection
+    {
+        return match (start.as_ref(), end.as_ref()) {
+            (Value::Number(start), Value::Number(end)) => {
+                let (start, end) = (*start as i64, *end as i64);
+                let end = if *inclusive { end + 1 } else { end };
+                Ok((start..end).map(|i| Value::Number(i as f64)).collect())
+            }
+            _ => Err(VmError::TypeError {
+                operation: operation.to_string(),
+                expected: "numeric Range".to_string(),
+                got: format!("{:?}", collection),
+            }),
+        };
+    }
+
+    let mut iter = VmIterator::from_value(collection)?;
+    let mut items = Vec::new();
+    while let Some(item) = iter.next() {
+        items.push(item);
+    }
+    Ok(items)
+}
+
+/// Call `func` on every element on the worker pool, keeping input order
+fn run_parallel(vm: &VM, func: &Value, items: Vec<Value>) -> Result<Vec<Value>, VmError> {
+    // Workers derive their VMs from this template (VM is Send but not Sync)
+    let template = Mutex::new(vm.new_child());
+    parallel::install(|| {
+        items
+            .into_par_iter()
+            .map_init(
+                || template.lock().new_child(),
+                |worker, item| worker.call_value(func, &[item]),
+            )
+            .collect()
+    })
+}
+
+/// par_map(callback, collection) -> collection
+///
+/// Parallel `map`: same result (and type preservation) as `map`, with the
+/// callback running on several threads.
+pub fn vm_par_map(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let callback = &args[0];
+    let collection = &args[1];
+
+    let items = collect_items(collection, "par_map")?;
+    let results = run_parallel(vm, callback, items)?;
+
+    let mut builder = VmBuilder::from_hint(collection);
+    for result in results {
+        builder.push(result)?;
+    }
+    builder.finalize()
+}
+
+/// par_reduce(callback, initial, collection) -> Any
+///
+/// Parallel `reduce`: each worker folds a contiguous chunk starting from
+/// `initial`, then the partial results are folded together in order.
+/// The callback must be associative and `initial` its identity
+/// (e.g. `(a, b) => a + b` with `0`).
+pub fn vm_par_reduce(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let callback = &args[0];
+    let initial = &args[1];
+    let items = collect_items(&args[2], "par_reduce")?;
+    if items.is_empty() {
+        return Ok(initial.clone());
+    }
+
+    let chunk_size = items.len().div_ceil(parallel::threads() * 4);
+    let template = Mutex::new(vm.new_child());
+    let partials: Vec<Value> = parallel::install(|| {
+        items
+            .par_chunks(chunk_size)
+            .map_init(
+                || template.lock().new_child(),
+                |worker, chunk| {
+                    let mut acc = initial.clone();
+                    for item in chunk {
+                        acc = worker.call_value(callback, &[acc, item.clone()])?;
+                    }
+                    Ok(acc)
+                },
+            )
+            .collect::<Result<_, VmError>>()
+    })?;
+
+    let mut partials = partials.into_iter();
+    let mut acc = partials.next().unwrap_or_else(|| initial.clone());
+    for partial in partials {
+        acc = vm.call_value(callback, &[acc, partial])?;
+    }
+    Ok(acc)
+}
+
+/// par_for(range_or_collection, callback) -> Null
+///
+/// Parallel loop: calls `callback(i)` for every element, in no particular
+/// order. Returns once all iterations are done; the first error is reported.
+pub fn vm_par_for(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let items = collect_items(&args[0], "par_for")?;
+    run_parallel(vm, &args[1], items)?;
+    Ok(Value::Null)
+}
+
+/// set_threads(n) -> Number
+///
+/// Size the worker pool used by `par_*` and large tensor kernels.
+/// `set_threads(0)` restores the default (one thread per core).
+/// Returns the resulting number of threads.
+pub fn vm_set_threads(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let n = match args[0] {
+        Value::Number(n) if n >= 0.0 && n.fract() == 0.0 => n as usize,
+        _ => {
+            return Err(VmError::TypeError {
+                operation: "set_threads".to_string(),
+                expected: "non-negative integer".to_string(),
+                got: format!("{:?}", args[0]),
+            })
+        }
+    };
+    parallel::set_threads(n).map_err(VmError::Runtime)?;
+    Ok(Value::Number(parallel::threads() as f64))
+}
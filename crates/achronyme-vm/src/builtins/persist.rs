@@ -0,0 +1,1046 @@
+//! Workspace persistence built-ins (`.ach` archives)
+//!
+//! This module implements the archive format described in
+//! `docs/design/ach-format-specification.md`:
+//! - save_env / restore_env: Save the VM globals and the script's top-level
+//!   variables, and load them back as globals
+//! - env_info: Read an archive's metadata without restoring it
+//! - env_bindings / get_global / set_global: Inspect and edit the globals
+//!
+//! Layout: a 64-byte header, a metadata record, the bindings section and a
+//! SHA-256 checksum of everything before it. All integers are little-endian.
+//! Lambdas and runtime handles (futures, channels, signals, ...) cannot be
+//! serialized; they are skipped with a warning.
+
+use crate::error::VmError;
+use crate::value::Value;
+use crate::vm::VM;
+use achronyme_types::complex::Complex;
+use achronyme_types::dataframe::{Column, DataFrame};
+use achronyme_types::function::Function;
+use achronyme_types::sync::{shared, Arc};
+use achronyme_types::tensor::{ComplexTensor, RealTensor};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MAGIC: [u8; 4] = *b"ACH\0";
+const FORMAT_VERSION_MAJOR: u16 = 1;
+const FORMAT_VERSION_MINOR: u16 = 0;
+const HEADER_SIZE: usize = 64;
+const CHECKSUM_SIZE: usize = 32;
+const RUNTIME_VERSION: &str = concat!("v", env!("CARGO_PKG_VERSION"));
+
+// Header flags
+const FLAG_METADATA: u32 = 1 << 0;
+const FLAG_COMPRESSED: u32 = 1 << 3;
+
+// Value type tags
+const TAG_NUMBER: u8 = 0x01;
+const TAG_BOOLEAN: u8 = 0x02;
+const TAG_STRING: u8 = 0x03;
+const TAG_COMPLEX: u8 = 0x04;
+const TAG_VECTOR: u8 = 0x05;
+const TAG_TENSOR: u8 = 0x06;
+const TAG_COMPLEX_TENSOR: u8 = 0x07;
+const TAG_RECORD: u8 = 0x08;
+const TAG_FUNCTION: u8 = 0x09;
+const TAG_MUTABLE_REF: u8 = 0x0B;
+const TAG_NULL: u8 = 0x0C;
+const TAG_BYTES: u8 = 0x0D;
+const TAG_DATAFRAME: u8 = 0x0E;
+
+// DataFrame column kinds
+const COLUMN_NUMBER: u8 = 0x01;
+const COLUMN_STRING: u8 = 0x02;
+
+const FUNCTION_BUILTIN: u8 = 0x01;
+
+/// Deepest nesting of vectors, records and references accepted when decoding
+const MAX_DEPTH: usize = 128;
+
+// ============================================================================
+// Value serialization
+// ============================================================================
+
+/// A value with no archive representation; carries its type name
+struct Unsupported(&'static str);
+
+fn unsupported_kind(value: &Value) -> &'static str {
+    match value {
+        Value::Function(_) => "function",
+        Value::Generator(_) => "generator",
+        Value::Future(_) => "future",
+        Value::Task(_) => "task",
+        Value::Iterator(_) => "iterator",
+        Value::Builder(_) => "builder",
+        Value::Sender(_) | Value::Receiver(_) => "channel",
+        Value::AsyncMutex(_) | Value::MutexGuard(_) => "mutex",
+        Value::Signal(_) => "signal",
+        Value::Effect(_) => "effect",
+        Value::Error { .. } => "error",
+        _ => "internal value",
+    }
+}
+
+fn write_len(out: &mut Vec<u8>, len: usize) -> Result<(), Unsupported> {
+    let len = u32::try_from(len).map_err(|_| Unsupported("value larger than 4 GiB"))?;
+    out.extend_from_slice(&len.to_le_bytes());
+    Ok(())
+}
+
+fn write_str(out: &mut Vec<u8>, s: &str) -> Result<(), Unsupported> {
+    write_len(out, s.len())?;
+    out.extend_from_slice(s.as_bytes());
+    Ok(())
+}
+
+fn write_shape(out: &mut Vec<u8>, shape: &[usize]) -> Result<(), Unsupported> {
+    write_len(out, shape.len())?;
+    for &dim in shape {
+        write_len(out, dim)?;
+    }
+    write_len(out, shape.iter().product())
+}
+
+/// Serialize `value` (tag first). Record fields that cannot be serialized are
+/// dropped and reported in `warnings`; anything else fails the whole value.
+fn encode_value(
+    value: &Value,
+    path: &str,
+    out: &mut Vec<u8>,
+    warnings: &mut Vec<String>,
+) -> Result<(), Unsupported> {
+    match value {
+        Value::Null => out.push(TAG_NULL),
+        Value::Number(n) => {
+            out.push(TAG_NUMBER);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        Value::Boolean(b) => {
+            out.push(TAG_BOOLEAN);
+            out.push(*b as u8);
+        }
+        Value::String(s) => {
+            out.push(TAG_STRING);
+            write_str(out, s)?;
+        }
+        Value::Complex(c) => {
+            out.push(TAG_COMPLEX);
+            out.extend_from_slice(&c.re.to_le_bytes());
+            out.extend_from_slice(&c.im.to_le_bytes());
+        }
+        Value::Vector(rc) => {
+            let items = rc.read();
+            out.push(TAG_VECTOR);
+            write_len(out, items.len())?;
+            for (i, item) in items.iter().enumerate() {
+                encode_value(item, &format!("{}[{}]", path, i), out, warnings)?;
+            }
+        }
+        Value::Tensor(t) => {
+            out.push(TAG_TENSOR);
+            write_shape(out, t.shape())?;
+            for x in t.data() {
+                out.extend_from_slice(&x.to_le_bytes());
+            }
+        }
+        Value::ComplexTensor(t) => {
+            out.push(TAG_COMPLEX_TENSOR);
+            write_shape(out, t.shape())?;
+            for c in t.data() {
+                out.extend_from_slice(&c.re.to_le_bytes());
+                out.extend_from_slice(&c.im.to_le_bytes());
+            }
+        }
+        Value::Record(rc) => {
+            let fields = rc.read();
+            let mut names: Vec<&String> = fields.keys().collect();
+            names.sort();
+
+            let mut body = Vec::new();
+            let mut count = 0;
+            for name in names {
+                let field_path = format!("{}.{}", path, name);
+                let mut field = Vec::new();
+                write_str(&mut field, name)?;
+                match encode_value(&fields[name], &field_path, &mut field, warnings) {
+                    Ok(()) => {
+                        body.extend_from_slice(&field);
+                        count += 1;
+                    }
+                    Err(Unsupported(kind)) => {
+                        warnings.push(format!("field '{}' ({}) not saved", field_path, kind))
+                    }
+                }
+            }
+            out.push(TAG_RECORD);
+            write_len(out, count)?;
+            out.extend_from_slice(&body);
+        }
+        Value::Function(Function::Builtin(name)) => {
+            out.push(TAG_FUNCTION);
+            out.push(FUNCTION_BUILTIN);
+            write_str(out, name)?;
+        }
+        Value::MutableRef(rc) => {
+            out.push(TAG_MUTABLE_REF);
+            encode_value(&rc.read(), path, out, warnings)?;
+        }
+        Value::Bytes(b) => {
+            out.push(TAG_BYTES);
+            write_len(out, b.len())?;
+            out.extend_from_slice(b);
+        }
+        Value::DataFrame(df) => {
+            out.push(TAG_DATAFRAME);
+            write_len(out, df.ncols())?;
+            write_len(out, df.nrows())?;
+            for (name, column) in df.columns() {
+                write_str(out, name)?;
+                match column {
+                    Column::Number(t) => {
+                        out.push(COLUMN_NUMBER);
+                        for x in t.data() {
+                            out.extend_from_slice(&x.to_le_bytes());
+                        }
+                    }
+                    Column::String(cells) => {
+                        out.push(COLUMN_STRING);
+                        for cell in cells {
+                            write_str(out, cell)?;
+                        }
+                    }
+                }
+            }
+            write_len(out, df.group_keys().len())?;
+            for key in df.group_keys() {
+                write_str(out, key)?;
+            }
+        }
+        other => return Err(Unsupported(unsupported_kind(other))),
+    }
+    Ok(())
+}
+
+/// Cursor over an archive buffer
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| "archive is truncated".to_string())?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        Ok(self.take(N)?.try_into().expect("slice has length N"))
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn len(&mut self) -> Result<usize, String> {
+        self.u32().map(|n| n as usize)
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        self.array().map(u64::from_le_bytes)
+    }
+
+    fn f64(&mut self) -> Result<f64, String> {
+        self.array().map(f64::from_le_bytes)
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = self.len()?;
+        String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|_| "archive contains invalid UTF-8".to_string())
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    /// Read a tensor shape and its element count, checking that `count`
+    /// elements of `elem_size` bytes fit in the rest of the buffer
+    fn shape(&mut self, elem_size: usize) -> Result<(Vec<usize>, usize), String> {
+        let ndim = self.len()?;
+        let shape = (0..ndim)
+            .map(|_| self.len())
+            .collect::<Result<Vec<_>, _>>()?;
+        let count = self.len()?;
+        let product = shape
+            .iter()
+            .try_fold(1usize, |acc, dim| acc.checked_mul(*dim));
+        if product != Some(count) {
+            return Err("tensor shape does not match its element count".to_string());
+        }
+        if count
+            .checked_mul(elem_size)
+            .is_none_or(|size| size > self.remaining())
+        {
+            return Err("archive is truncated".to_string());
+        }
+        Ok((shape, count))
+    }
+}
+
+fn decode_value(reader: &mut Reader, depth: usize) -> Result<Value, String> {
+    let tag = reader.u8()?;
+    decode_tagged(tag, reader, depth)
+}
+
+/// Decode a value whose tag has been read; `depth` counts the enclosing
+/// containers
+fn decode_tagged(tag: u8, reader: &mut Reader, depth: usize) -> Result<Value, String> {
+    if depth > MAX_DEPTH {
+        return Err(format!("values are nested more than {} deep", MAX_DEPTH));
+    }
+    Ok(match tag {
+        TAG_NULL => Value::Null,
+        TAG_NUMBER => Value::Number(reader.f64()?),
+        TAG_BOOLEAN => Value::Boolean(reader.u8()? != 0),
+        TAG_STRING => Value::String(reader.string()?),
+        TAG_COMPLEX => Value::Complex(Complex::new(reader.f64()?, reader.f64()?)),
+        TAG_VECTOR => {
+            let len = reader.len()?;
+            let items = (0..len)
+                .map(|_| decode_value(reader, depth + 1))
+                .collect::<Result<Vec<_>, _>>()?;
+            Value::Vector(shared(items))
+        }
+        TAG_TENSOR => {
+            let (shape, count) = reader.shape(8)?;
+            let data = (0..count)
+                .map(|_| reader.f64())
+                .collect::<Result<Vec<_>, _>>()?;
+            Value::Tensor(RealTensor::new(data, shape).map_err(|e| e.to_string())?)
+        }
+        TAG_COMPLEX_TENSOR => {
+            let (shape, count) = reader.shape(16)?;
+            let data = (0..count)
+                .map(|_| Ok(Complex::new(reader.f64()?, reader.f64()?)))
+                .collect::<Result<Vec<_>, String>>()?;
+            Value::ComplexTensor(ComplexTensor::new(data, shape).map_err(|e| e.to_string())?)
+        }
+        TAG_RECORD => {
+            let len = reader.len()?;
+            let mut fields = HashMap::with_capacity(len.min(4096));
+            for _ in 0..len {
+                let name = reader.string()?;
+                fields.insert(name, decode_value(reader, depth + 1)?);
+            }
+            Value::Record(shared(fields))
+        }
+        TAG_FUNCTION => match reader.u8()? {
+            FUNCTION_BUILTIN => Value::Function(Function::Builtin(reader.string()?)),
+            other => return Err(format!("unsupported function kind 0x{:02X}", other)),
+        },
+        TAG_MUTABLE_REF => Value::MutableRef(shared(decode_value(reader, depth + 1)?)),
+        TAG_BYTES => {
+            let len = reader.len()?;
+            Value::Bytes(Arc::new(reader.take(len)?.to_vec()))
+        }
+        TAG_DATAFRAME => Value::DataFrame(Arc::new(decode_dataframe(reader)?)),
+        other => return Err(format!("unknown value tag 0x{:02X}", other)),
+    })
+}
+
+fn decode_dataframe(reader: &mut Reader) -> Result<DataFrame, String> {
+    let ncols = reader.len()?;
+    let nrows = reader.len()?;
+    let mut columns = Vec::with_capacity(ncols.min(4096));
+    for _ in 0..ncols {
+        let name = reader.string()?;
+        let column = match reader.u8()? {
+            COLUMN_NUMBER => {
+                if nrows
+                    .checked_mul(8)
+                    .is_none_or(|size| size > reader.remaining())
+                {
+                    return Err("archive is truncated".to_string());
+                }
+                Column::numbers((0..nrows).map(|_| reader.f64()).collect::<Result<_, _>>()?)
+            }
+            COLUMN_STRING => Column::String(
+                (0..nrows)
+                    .map(|_| reader.string())
+                    .collect::<Result<_, _>>()?,
+            ),
+            other => return Err(format!("unknown DataFrame column kind 0x{:02X}", other)),
+        };
+        columns.push((name, column));
+    }
+    let keys = (0..reader.len()?)
+        .map(|_| reader.string())
+        .collect::<Result<Vec<_>, _>>()?;
+    DataFrame::new(columns)
+        .and_then(|df| df.group_by(&keys))
+        .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Archive layout
+// ============================================================================
+
+/// One serialized binding: the value's tag and its payload (without the tag)
+struct Binding {
+    name: String,
+    tag: u8,
+    data: Vec<u8>,
+}
+
+struct Metadata {
+    created_at: String,
+    description: Option<String>,
+    tags: Vec<String>,
+}
+
+fn metadata_record(
+    metadata: &Metadata,
+    num_bindings: usize,
+    bindings_offset: usize,
+    bindings_size: usize,
+) -> Value {
+    let description = match &metadata.description {
+        Some(s) => Value::String(s.clone()),
+        None => Value::Null,
+    };
+    let tags = metadata.tags.iter().cloned().map(Value::String).collect();
+    let fields: HashMap<String, Value> = [
+        (
+            "created_by",
+            Value::String(format!("Achronyme {}", RUNTIME_VERSION)),
+        ),
+        ("created_at", Value::String(metadata.created_at.clone())),
+        (
+            "platform",
+            Value::String(format!(
+                "{} {}",
+                std::env::consts::OS,
+                std::env::consts::ARCH
+            )),
+        ),
+        ("num_bindings", Value::Number(num_bindings as f64)),
+        ("num_constants", Value::Number(0.0)),
+        ("num_functions", Value::Number(0.0)),
+        ("bindings_offset", Value::Number(bindings_offset as f64)),
+        ("bindings_size", Value::Number(bindings_size as f64)),
+        ("description", description),
+        ("tags", Value::Vector(shared(tags))),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_string(), v))
+    .collect();
+    Value::Record(shared(fields))
+}
+
+fn write_archive(bindings: &[Binding], metadata: &Metadata) -> Result<Vec<u8>, Unsupported> {
+    let mut section = Vec::new();
+    write_len(&mut section, bindings.len())?;
+    for binding in bindings {
+        write_str(&mut section, &binding.name)?;
+        section.push(binding.tag);
+        write_len(&mut section, binding.data.len())?;
+        section.extend_from_slice(&binding.data);
+    }
+
+    // Metadata numbers are fixed-width, so its size does not depend on the
+    // offsets it records: encode once to measure, then for real.
+    let encode_metadata = |offset: usize| -> Result<Vec<u8>, Unsupported> {
+        let mut out = Vec::new();
+        let record = metadata_record(metadata, bindings.len(), offset, section.len());
+        encode_value(&record, "metadata", &mut out, &mut Vec::new())?;
+        Ok(out)
+    };
+    let probe = encode_metadata(0)?;
+    let meta = encode_metadata(HEADER_SIZE + 4 + probe.len())?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut runtime_version = [0u8; 16];
+    let len = RUNTIME_VERSION.len().min(runtime_version.len());
+    runtime_version[..len].copy_from_slice(&RUNTIME_VERSION.as_bytes()[..len]);
+
+    let mut out = Vec::with_capacity(HEADER_SIZE + meta.len() + section.len() + CHECKSUM_SIZE);
+    out.extend_from_slice(&MAGIC);
+    out.extend_from_slice(&FORMAT_VERSION_MAJOR.to_le_bytes());
+    out.extend_from_slice(&FORMAT_VERSION_MINOR.to_le_bytes());
+    out.extend_from_slice(&FLAG_METADATA.to_le_bytes());
+    out.extend_from_slice(&timestamp.to_le_bytes());
+    out.extend_from_slice(&runtime_version);
+    out.push(0); // compression: none
+    out.resize(HEADER_SIZE, 0);
+
+    write_len(&mut out, meta.len())?;
+    out.extend_from_slice(&meta);
+    out.extend_from_slice(&section);
+
+    let checksum = Sha256::digest(&out);
+    out.extend_from_slice(&checksum);
+    Ok(out)
+}
+
+/// A parsed archive: header fields, metadata record and raw bindings
+struct Archive {
+    runtime_version: String,
+    metadata: HashMap<String, Value>,
+    bindings: Vec<Binding>,
+}
+
+fn read_archive(bytes: &[u8], verify_checksum: bool) -> Result<Archive, String> {
+    if bytes.len() < HEADER_SIZE + CHECKSUM_SIZE || bytes[..4] != MAGIC {
+        return Err("not an .ach archive".to_string());
+    }
+    let (content, checksum) = bytes.split_at(bytes.len() - CHECKSUM_SIZE);
+    if verify_checksum && Sha256::digest(content)[..] != *checksum {
+        return Err("checksum mismatch (the archive is corrupted)".to_string());
+    }
+
+    let mut reader = Reader::new(content);
+    reader.take(MAGIC.len())?;
+    let major = reader.u16()?;
+    let _minor = reader.u16()?;
+    if major != FORMAT_VERSION_MAJOR {
+        return Err(format!("unsupported format version {}", major));
+    }
+    let flags = reader.u32()?;
+    let _created = reader.u64()?;
+    let version: [u8; 16] = reader.array()?;
+    let compression = reader.u8()?;
+    if compression != 0 || flags & FLAG_COMPRESSED != 0 {
+        return Err("compressed archives are not supported".to_string());
+    }
+    reader.pos = HEADER_SIZE;
+
+    let runtime_version = String::from_utf8_lossy(&version)
+        .trim_end_matches('\0')
+        .to_string();
+
+    let metadata = if flags & FLAG_METADATA != 0 {
+        let len = reader.len()?;
+        let mut meta = Reader::new(reader.take(len)?);
+        match decode_value(&mut meta, 0)? {
+            Value::Record(rc) => rc.read().clone(),
+            _ => return Err("metadata section is not a record".to_string()),
+        }
+    } else {
+        HashMap::new()
+    };
+
+    let count = reader.len()?;
+    let mut bindings = Vec::with_capacity(count.min(4096));
+    for _ in 0..count {
+        let name = reader.string()?;
+        let tag = reader.u8()?;
+        let size = reader.len()?;
+        let data = reader.take(size)?.to_vec();
+        bindings.push(Binding { name, tag, data });
+    }
+
+    Ok(Archive {
+        runtime_version,
+        metadata,
+        bindings,
+    })
+}
+
+/// Format a Unix timestamp as ISO 8601 (UTC)
+fn iso8601(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+
+    // Civil-from-days (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+// ============================================================================
+// Options
+// ============================================================================
+
+fn string_list(value: &Value, operation: &str, option: &str) -> Result<Vec<String>, VmError> {
+    let invalid = || {
+        VmError::Runtime(format!(
+            "{}(): option '{}' must be a vector of strings",
+            operation, option
+        ))
+    };
+    match value {
+        Value::Vector(rc) => rc
+            .read()
+            .iter()
+            .map(|v| match v {
+                Value::String(s) => Ok(s.clone()),
+                _ => Err(invalid()),
+            })
+            .collect(),
+        _ => Err(invalid()),
+    }
+}
+
+/// Match `name` against a pattern where `*` stands for any run of characters
+fn glob_match(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, rest)) => {
+            let Some(tail) = name.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=tail.len())
+                .filter(|&i| tail.is_char_boundary(i))
+                .any(|i| glob_match(rest, &tail[i..]))
+        }
+    }
+}
+
+/// The `include_only` / `exclude` filters shared by save and restore
+#[derive(Default)]
+struct NameFilter {
+    include_only: Option<Vec<String>>,
+    exclude: Vec<String>,
+}
+
+impl NameFilter {
+    fn set(&mut self, key: &str, value: &Value, operation: &str) -> Result<bool, VmError> {
+        match key {
+            "include_only" if matches!(value, Value::Null) => self.include_only = None,
+            "include_only" => self.include_only = Some(string_list(value, operation, key)?),
+            "exclude" => self.exclude = string_list(value, operation, key)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn accepts(&self, name: &str) -> bool {
+        self.include_only
+            .as_ref()
+            .is_none_or(|names| names.iter().any(|n| n == name))
+            && !self.exclude.iter().any(|p| glob_match(p, name))
+    }
+}
+
+fn options_record<'a>(
+    value: Option<&'a Value>,
+    operation: &str,
+) -> Result<Option<&'a achronyme_types::sync::Shared<HashMap<String, Value>>>, VmError> {
+    match value {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Record(rc)) => Ok(Some(rc)),
+        Some(other) => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: "options Record".to_string(),
+            got: format!("{:?}", other),
+        }),
+    }
+}
+
+fn invalid_option(operation: &str, key: &str, value: &Value) -> VmError {
+    VmError::Runtime(format!(
+        "{}(): invalid value for option '{}': {:?}",
+        operation, key, value
+    ))
+}
+
+#[derive(Default)]
+struct SaveOptions {
+    filter: NameFilter,
+    description: Option<String>,
+    tags: Vec<String>,
+    allow_overwrite: bool,
+}
+
+impl SaveOptions {
+    fn from_value(value: Option<&Value>) -> Result<Self, VmError> {
+        let mut options = SaveOptions::default();
+        let Some(rc) = options_record(value, "save_env")? else {
+            return Ok(options);
+        };
+        for (key, value) in rc.read().iter() {
+            if options.filter.set(key, value, "save_env")? {
+                continue;
+            }
+            match (key.as_str(), value) {
+                ("description", Value::String(s)) => options.description = Some(s.clone()),
+                ("description", Value::Null) => options.description = None,
+                ("tags", _) => options.tags = string_list(value, "save_env", key)?,
+                ("allow_overwrite", Value::Boolean(b)) => options.allow_overwrite = *b,
+                ("description" | "allow_overwrite", _) => {
+                    return Err(invalid_option("save_env", key, value))
+                }
+                _ => {
+                    return Err(VmError::Runtime(format!(
+                        "save_env(): unknown option '{}'",
+                        key
+                    )))
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum RestoreMode {
+    Merge,
+    Replace,
+    Namespace,
+}
+
+struct RestoreOptions {
+    mode: RestoreMode,
+    overwrite: bool,
+    namespace: Option<String>,
+    filter: NameFilter,
+    verify_checksum: bool,
+    strict_version: bool,
+}
+
+impl RestoreOptions {
+    fn from_value(value: Option<&Value>) -> Result<Self, VmError> {
+        let mut options = RestoreOptions {
+            mode: RestoreMode::Merge,
+            overwrite: false,
+            namespace: None,
+            filter: NameFilter::default(),
+            verify_checksum: true,
+            strict_version: false,
+        };
+        if let Some(rc) = options_record(value, "restore_env")? {
+            for (key, value) in rc.read().iter() {
+                if options.filter.set(key, value, "restore_env")? {
+                    continue;
+                }
+                match (key.as_str(), value) {
+                    ("mode", Value::String(s)) if s == "merge" => options.mode = RestoreMode::Merge,
+                    ("mode", Value::String(s)) if s == "replace" => {
+                        options.mode = RestoreMode::Replace
+                    }
+                    ("mode", Value::String(s)) if s == "namespace" => {
+                        options.mode = RestoreMode::Namespace
+                    }
+                    ("overwrite", Value::Boolean(b)) => options.overwrite = *b,
+                    ("namespace", Value::String(s)) => options.namespace = Some(s.clone()),
+                    ("namespace", Value::Null) => options.namespace = None,
+                    ("verify_checksum", Value::Boolean(b)) => options.verify_checksum = *b,
+                    ("strict_version", Value::Boolean(b)) => options.strict_version = *b,
+                    (
+                        "mode" | "overwrite" | "namespace" | "verify_checksum" | "strict_version",
+                        _,
+                    ) => return Err(invalid_option("restore_env", key, value)),
+                    _ => {
+                        return Err(VmError::Runtime(format!(
+                            "restore_env(): unknown option '{}'",
+                            key
+                        )))
+                    }
+                }
+            }
+        }
+        if options.mode == RestoreMode::Namespace && options.namespace.is_none() {
+            return Err(VmError::Runtime(
+                "restore_env(): mode \"namespace\" requires the 'namespace' option".to_string(),
+            ));
+        }
+        Ok(options)
+    }
+}
+
+// ============================================================================
+// Built-ins
+// ============================================================================
+
+fn string_arg<'a>(value: &'a Value, operation: &str, what: &str) -> Result<&'a str, VmError> {
+    match value {
+        Value::String(s) => Ok(s),
+        _ => Err(VmError::TypeError {
+            operation: operation.to_string(),
+            expected: format!("String {}", what),
+            got: format!("{:?}", value),
+        }),
+    }
+}
+
+fn archive_error(operation: &str, path: &str, e: impl std::fmt::Display) -> VmError {
+    VmError::Runtime(format!("{}(): '{}': {}", operation, path, e))
+}
+
+/// Values of the script's top-level variables defined so far, read from the
+/// main frame's registers
+fn top_level_values(vm: &VM) -> HashMap<String, Value> {
+    let mut values = HashMap::new();
+    let Some(frame) = vm.frames.first() else {
+        return values;
+    };
+    // Later definitions shadow earlier ones with the same name
+    for binding in &frame.function.top_level_bindings {
+        if binding.defined_at > frame.ip {
+            break;
+        }
+        if let Ok(value) = frame.registers.get(binding.register) {
+            values.insert(binding.name.clone(), value.clone());
+        }
+    }
+    values
+}
+
+/// save_env(path, options?) -> Boolean
+///
+/// Writes the VM globals and the script's top-level variables (which win on
+/// a name clash) to an `.ach` archive. Options: `include_only`,
+/// `exclude` (names or `*` patterns), `description`, `tags` and
+/// `allow_overwrite` (default false).
+pub fn vm_save_env(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(VmError::Runtime(format!(
+            "save_env() expects 1 or 2 arguments, got {}",
+            args.len()
+        )));
+    }
+    let path = string_arg(&args[0], "save_env", "path")?;
+    let options = SaveOptions::from_value(args.get(1))?;
+    if !options.allow_overwrite && std::path::Path::new(path).exists() {
+        return Err(VmError::Runtime(format!(
+            "save_env(): '{}' already exists (pass {{allow_overwrite: true}} to replace it)",
+            path
+        )));
+    }
+
+    let mut globals = vm.globals.read().clone();
+    globals.extend(top_level_values(vm));
+    let mut names: Vec<&String> = globals
+        .keys()
+        .filter(|name| options.filter.accepts(name))
+        .collect();
+    names.sort();
+
+    let mut bindings = Vec::with_capacity(names.len());
+    let mut warnings = Vec::new();
+    for name in names {
+        let mut data = Vec::new();
+        match encode_value(&globals[name], name, &mut data, &mut warnings) {
+            Ok(()) => bindings.push(Binding {
+                name: name.clone(),
+                tag: data[0],
+                data: data.split_off(1),
+            }),
+            Err(Unsupported(kind)) => {
+                warnings.push(format!("variable '{}' ({}) not saved", name, kind))
+            }
+        }
+    }
+    for warning in &warnings {
+        eprintln!("Warning: save_env(): {}", warning);
+    }
+
+    let metadata = Metadata {
+        created_at: iso8601(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        ),
+        description: options.description,
+        tags: options.tags,
+    };
+    let bytes = write_archive(&bindings, &metadata)
+        .map_err(|Unsupported(kind)| archive_error("save_env", path, kind))?;
+    std::fs::write(path, bytes).map_err(|e| archive_error("save_env", path, e))?;
+    Ok(Value::Boolean(true))
+}
+
+/// restore_env(path, options?) -> Boolean
+///
+/// Loads an `.ach` archive into the VM globals. `mode` is `"merge"` (the
+/// default; existing globals win unless `overwrite` is true), `"replace"`
+/// (clear the globals first) or `"namespace"` (bind a single record named by
+/// `namespace`). Also accepts `include_only`, `exclude`, `verify_checksum`
+/// and `strict_version`.
+pub fn vm_restore_env(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(VmError::Runtime(format!(
+            "restore_env() expects 1 or 2 arguments, got {}",
+            args.len()
+        )));
+    }
+    let path = string_arg(&args[0], "restore_env", "path")?;
+    let options = RestoreOptions::from_value(args.get(1))?;
+
+    let bytes = std::fs::read(path).map_err(|e| archive_error("restore_env", path, e))?;
+    let archive = read_archive(&bytes, options.verify_checksum)
+        .map_err(|e| archive_error("restore_env", path, e))?;
+
+    if archive.runtime_version != RUNTIME_VERSION {
+        if options.strict_version {
+            return Err(archive_error(
+                "restore_env",
+                path,
+                format!(
+                    "version mismatch (file: {}, current: {})",
+                    archive.runtime_version, RUNTIME_VERSION
+                ),
+            ));
+        }
+        eprintln!(
+            "Warning: restore_env(): '{}' was created with Achronyme {}, current version is {}",
+            path, archive.runtime_version, RUNTIME_VERSION
+        );
+    }
+
+    // Decode everything before touching the globals so a bad archive leaves
+    // the environment unchanged
+    let mut values = Vec::new();
+    for binding in archive.bindings {
+        if !options.filter.accepts(&binding.name) {
+            continue;
+        }
+        let mut reader = Reader::new(&binding.data);
+        let value = decode_tagged(binding.tag, &mut reader, 0)
+            .map_err(|e| archive_error("restore_env", path, e))?;
+        values.push((binding.name, value));
+    }
+
+    let mut globals = vm.globals.write();
+    match options.mode {
+        RestoreMode::Namespace => {
+            let name = options.namespace.expect("checked in RestoreOptions");
+            let record = Value::Record(shared(values.into_iter().collect()));
+            if options.overwrite || !globals.contains_key(&name) {
+                globals.insert(name, record);
+            }
+        }
+        RestoreMode::Replace => {
+            globals.clear();
+            globals.extend(values);
+        }
+        RestoreMode::Merge => {
+            for (name, value) in values {
+                if options.overwrite || !globals.contains_key(&name) {
+                    globals.insert(name, value);
+                }
+            }
+        }
+    }
+    Ok(Value::Boolean(true))
+}
+
+/// env_info(path) -> Record
+///
+/// Reads an archive's metadata and binding names without restoring it.
+pub fn vm_env_info(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 1 {
+        return Err(VmError::Runtime(format!(
+            "env_info() expects 1 argument, got {}",
+            args.len()
+        )));
+    }
+    let path = string_arg(&args[0], "env_info", "path")?;
+    let bytes = std::fs::read(path).map_err(|e| archive_error("env_info", path, e))?;
+    let archive = read_archive(&bytes, true).map_err(|e| archive_error("env_info", path, e))?;
+
+    let mut info = archive.metadata;
+    for key in ["bindings_offset", "bindings_size"] {
+        info.remove(key);
+    }
+    info.insert(
+        "version".to_string(),
+        Value::String(format!("{}.{}", FORMAT_VERSION_MAJOR, FORMAT_VERSION_MINOR)),
+    );
+    info.insert(
+        "runtime_version".to_string(),
+        Value::String(archive.runtime_version),
+    );
+    info.insert("compressed".to_string(), Value::Boolean(false));
+    info.insert("file_size".to_string(), Value::Number(bytes.len() as f64));
+    let names = archive
+        .bindings
+        .into_iter()
+        .map(|b| Value::String(b.name))
+        .collect();
+    info.insert("bindings".to_string(), Value::Vector(shared(names)));
+    Ok(Value::Record(shared(info)))
+}
+
+/// env_bindings() -> Vector<String>
+///
+/// Sorted names of the VM globals.
+pub fn vm_env_bindings(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if !args.is_empty() {
+        return Err(VmError::Runtime(format!(
+            "env_bindings() expects 0 arguments, got {}",
+            args.len()
+        )));
+    }
+    let mut names: Vec<String> = vm.globals.read().keys().cloned().collect();
+    names.sort();
+    Ok(Value::Vector(shared(
+        names.into_iter().map(Value::String).collect(),
+    )))
+}
+
+/// get_global(name) -> Value
+pub fn vm_get_global(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 1 {
+        return Err(VmError::Runtime(format!(
+            "get_global() expects 1 argument, got {}",
+            args.len()
+        )));
+    }
+    let name = string_arg(&args[0], "get_global", "name")?;
+    vm.get_global(name)
+        .ok_or_else(|| VmError::Runtime(format!("Undefined global variable: '{}'", name)))
+}
+
+/// set_global(name, value) -> Null
+pub fn vm_set_global(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 2 {
+        return Err(VmError::Runtime(format!(
+            "set_global() expects 2 arguments, got {}",
+            args.len()
+        )));
+    }
+    let name = string_arg(&args[0], "set_global", "name")?;
+    vm.set_global(name.to_string(), args[1].clone());
+    Ok(Value::Null)
+}
@@ -1,16 +1,94 @@
-//! Reactive system built-ins (Signals, Effects)
+//! Reactive system built-ins (Signals, Computed signals, Effects)
+//!
+//! Propagation is push-pull and glitch-free:
+//! - Setting a signal marks its direct subscribers `Dirty` and everything
+//!   further downstream `Check`, queueing the effects it reaches.
+//! - Queued effects are flushed once the outermost `set`/`batch` returns.
+//!   Before running, a `Check` node brings its computed dependencies up to
+//!   date (in dependency order) and only re-runs if one of them changed.
+//!
+//! Each node therefore runs at most once per update, so diamond-shaped
+//! dependencies never observe a half-updated graph.
+//!
+//! Tracking state lives on the `VM` running the code, never in thread-locals,
+//! so spawned tasks on other threads cannot see or clobber it. Effects belong
+//! to the VM that created them: when another VM (e.g. a `spawn`ed task)
+//! invalidates one, it is posted to the owner's inbox and runs on the owner
+//! as soon as it is suspended at an `await`.
+//!
+//! An effect created while another effect runs belongs to it, and is
+//! disposed before its parent re-runs or is disposed.
 
 use crate::error::VmError;
 use crate::value::Value;
 use crate::vm::VM;
 use achronyme_types::sync::{shared, Arc, Shared};
-use achronyme_types::value::{EffectState, SignalState};
-use std::cell::RefCell;
+use achronyme_types::value::{EffectInbox, EffectState, ReactiveState, SignalState, VmFuture};
+use futures::FutureExt;
+use std::task::{Poll, Waker};
+
+/// Upper bound on flush rounds; exceeded when effects keep re-triggering
+/// each other (e.g. an effect that sets a signal it reads)
+const MAX_FLUSH_ROUNDS: usize = 10_000;
+
+/// Per-VM reactive runtime state
+#[derive(Debug, Default)]
+pub(crate) struct ReactiveRuntime {
+    /// Computations currently running, innermost last; signals read now
+    /// become dependencies of the top entry (`None` inside `untrack`)
+    observers: Vec<Option<Shared<EffectState>>>,
+    /// Depth of nested `batch()` calls
+    batch_depth: usize,
+    /// Effects waiting to run, in the order they were first invalidated
+    pending: Vec<Shared<EffectState>>,
+    /// True while `flush` is draining `pending`
+    flushing: bool,
+    /// Effects of this VM invalidated by other VMs
+    inbox: Arc<EffectInbox>,
+}
+
+impl ReactiveRuntime {
+    fn observer(&self) -> Option<&Shared<EffectState>> {
+        self.observers.last().and_then(Option::as_ref)
+    }
+
+    pub(crate) fn inbox(&self) -> &Arc<EffectInbox> {
+        &self.inbox
+    }
+}
+
+/// Run `f` with `observer` on top of the VM's observer stack
+fn with_observer<T>(
+    vm: &mut VM,
+    observer: Option<Shared<EffectState>>,
+    f: impl FnOnce(&mut VM) -> T,
+) -> T {
+    vm.reactive.observers.push(observer);
+    let result = f(vm);
+    vm.reactive.observers.pop();
+    result
+}
 
-// Thread-local global tracking context
-// Stores the current effect being executed, if any.
-thread_local! {
-    static TRACKING_CONTEXT: RefCell<Option<Shared<EffectState>>> = RefCell::new(None);
+fn new_node(callback: Value, target: Option<&Shared<SignalState>>) -> Shared<EffectState> {
+    shared(EffectState {
+        callback,
+        dependencies: Vec::new(),
+        target: target.map(Arc::downgrade),
+        state: ReactiveState::Dirty,
+        disposed: false,
+        owner: std::sync::Weak::new(),
+        children: Vec::new(),
+    })
+}
+
+fn callback_arg(args: &[Value], name: &str) -> Result<Value, VmError> {
+    match args {
+        [callback] => Ok(callback.clone()),
+        _ => Err(VmError::Runtime(format!(
+            "{}() expects 1 argument (callback function)",
+            name
+        ))),
+    }
 }
 
 /// signal(initial_value) -> Signal
@@ -25,58 +103,323 @@ pub fn vm_signal(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     let state = SignalState {
         value: initial_value,
         subscribers: Vec::new(),
+        computation: None,
     };
 
     Ok(Value::Signal(shared(state)))
 }
 
-/// effect(callback) -> Null
+/// computed(fn) -> Signal
+/// Creates a read-only signal whose value is derived from other signals.
+/// The function runs lazily on first read and again only after a dependency
+/// changes; the result is memoized in between.
+pub fn vm_computed(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let callback = callback_arg(args, "computed")?;
+
+    let signal = shared(SignalState {
+        value: Value::Null,
+        subscribers: Vec::new(),
+        computation: None,
+    });
+    let node = new_node(callback, Some(&signal));
+    signal.write().computation = Some(node);
+
+    Ok(Value::Signal(signal))
+}
+
+/// effect(callback) -> Effect
 /// Registers a side effect that runs immediately and re-runs when dependencies change.
+/// The returned handle stops it with `.dispose()`.
 pub fn vm_effect(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
-    if args.len() != 1 {
-        return Err(VmError::Runtime(
-            "effect() expects 1 argument (callback function)".to_string(),
-        ));
-    }
+    let callback = callback_arg(args, "effect")?;
 
-    let callback = args[0].clone();
+    let effect_state = new_node(callback, None);
+    effect_state.write().owner = Arc::downgrade(&vm.reactive.inbox);
 
-    // Create effect state
-    let effect_state = shared(EffectState {
-        callback: callback.clone(),
-        dependencies: Vec::new(),
-    });
+    // Owned by the running computation, which disposes it before re-running
+    if let Some(parent) = vm.reactive.observer() {
+        parent.write().children.push(effect_state.clone());
+    }
 
     // Keep effect alive by adding to VM roots
     vm.active_effects.push(effect_state.clone());
 
     // Run the effect immediately to track dependencies
-    run_effect(vm, effect_state)?;
+    update(vm, &effect_state)?;
 
-    Ok(Value::Null)
+    Ok(Value::Effect(effect_state))
 }
 
-/// Helper to run an effect and track dependencies
-fn run_effect(vm: &mut VM, effect: Shared<EffectState>) -> Result<(), VmError> {
-    // 1. Cleanup: Unsubscribe from previous dependencies
-    cleanup_effect(&effect);
-
-    // 2. Set tracking context
-    TRACKING_CONTEXT.with(|ctx| {
-        *ctx.borrow_mut() = Some(effect.clone());
-    });
+/// batch(fn) -> Value
+/// Runs `fn` and defers effects until it returns (or fails), so each runs at
+/// most once.
+pub fn vm_batch(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let callback = callback_arg(args, "batch")?;
 
-    // 3. Execute the callback
-    let callback = effect.read().callback.clone();
+    vm.reactive.batch_depth += 1;
     let result = vm.call_value(&callback, &[]);
+    vm.reactive.batch_depth -= 1;
+    if vm.reactive.batch_depth > 0 {
+        return result;
+    }
 
-    // 4. Clear tracking context
-    TRACKING_CONTEXT.with(|ctx| {
-        *ctx.borrow_mut() = None;
-    });
+    // Writes made before an error still stand, so their effects run before
+    // the error propagates instead of leaking into an unrelated flush
+    let flushed = flush(vm);
+    let value = result?;
+    flushed?;
+    Ok(value)
+}
+
+/// untrack(fn) -> Value
+/// Runs `fn` without subscribing the current effect or computed to the
+/// signals it reads.
+pub fn vm_untrack(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    let callback = callback_arg(args, "untrack")?;
+
+    with_observer(vm, None, |vm| vm.call_value(&callback, &[]))
+}
+
+// === Propagation ===
+
+/// Re-run a node with dependency tracking. For computed nodes, store the new
+/// value and invalidate subscribers if it changed.
+fn update(vm: &mut VM, node: &Shared<EffectState>) -> Result<(), VmError> {
+    // 1. Cleanup: Unsubscribe from previous dependencies and drop the
+    // effects the previous run created
+    cleanup_effect(node);
+    dispose_children(vm, node);
+
+    // Mark clean before running so a write to one of its own dependencies
+    // re-queues it (and trips the loop guard) instead of being lost
+    let callback = {
+        let mut state = node.write();
+        state.state = ReactiveState::Clean;
+        state.callback.clone()
+    };
+
+    // 2. Execute the callback as the current observer
+    let result = with_observer(vm, Some(node.clone()), |vm| vm.call_value(&callback, &[]));
 
     // Propagate error if execution failed
-    result.map(|_| ())
+    let value = result?;
+
+    let target = node.read().target.as_ref().and_then(|t| t.upgrade());
+    if let Some(signal_rc) = target {
+        let mut signal = signal_rc.write();
+        if signal.value != value {
+            signal.value = value;
+            let subscribers = signal.subscribers.clone();
+            drop(signal);
+            for weak_sub in subscribers {
+                if let Some(sub) = weak_sub.upgrade() {
+                    mark(vm, &sub, ReactiveState::Dirty);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Bring a node up to date, re-running it only if a dependency really changed
+fn update_if_necessary(vm: &mut VM, node: &Shared<EffectState>) -> Result<(), VmError> {
+    if node.read().state == ReactiveState::Check {
+        let dependencies = node.read().dependencies.clone();
+        for dep in dependencies {
+            let computation = dep.read().computation.clone();
+            if let Some(computation) = computation {
+                update_if_necessary(vm, &computation)?;
+            }
+            if node.read().state == ReactiveState::Dirty {
+                break;
+            }
+        }
+    }
+
+    let mut state = node.write();
+    match state.state {
+        ReactiveState::Dirty if !state.disposed => {
+            drop(state);
+            update(vm, node)
+        }
+        _ => {
+            state.state = ReactiveState::Clean;
+            Ok(())
+        }
+    }
+}
+
+/// Raise a node's state and propagate `Check` downstream. Effects leaving the
+/// clean state are queued on their owning VM.
+fn mark(vm: &mut VM, node: &Shared<EffectState>, state: ReactiveState) {
+    let mut effect = node.write();
+    if effect.state >= state || effect.disposed {
+        return;
+    }
+    let was_clean = effect.state == ReactiveState::Clean;
+    effect.state = state;
+    let target = effect.target.clone();
+    drop(effect);
+
+    match target {
+        // Computed: its subscribers may have to recompute too
+        Some(weak_signal) => {
+            if !was_clean {
+                return;
+            }
+            if let Some(signal) = weak_signal.upgrade() {
+                let subscribers = signal.read().subscribers.clone();
+                for weak_sub in subscribers {
+                    if let Some(sub) = weak_sub.upgrade() {
+                        mark(vm, &sub, ReactiveState::Check);
+                    }
+                }
+            }
+        }
+        None if was_clean => {
+            let owner = node.read().owner.upgrade();
+            match owner {
+                Some(inbox) if Arc::ptr_eq(&inbox, &vm.reactive.inbox) => {
+                    vm.reactive.pending.push(node.clone())
+                }
+                Some(inbox) => inbox.post(node.clone()),
+                // The owning VM is gone; nothing can run this effect anymore
+                None => {}
+            }
+        }
+        None => {}
+    }
+}
+
+/// Run queued effects until the graph settles
+fn flush(vm: &mut VM) -> Result<(), VmError> {
+    if std::mem::replace(&mut vm.reactive.flushing, true) {
+        // The outer flush picks up anything queued meanwhile
+        return Ok(());
+    }
+
+    let result = (|| {
+        for _ in 0..MAX_FLUSH_ROUNDS {
+            let scheduled = std::mem::take(&mut *vm.reactive.inbox.queue.lock());
+            vm.reactive.pending.extend(scheduled);
+            let pending = std::mem::take(&mut vm.reactive.pending);
+            if pending.is_empty() {
+                return Ok(());
+            }
+            for effect in pending {
+                update_if_necessary(vm, &effect)?;
+            }
+        }
+        Err(VmError::Runtime(
+            "Reactive update loop detected: effects keep re-triggering each other".to_string(),
+        ))
+    })();
+
+    vm.reactive.flushing = false;
+    if result.is_err() {
+        vm.reactive.pending.clear();
+    }
+    result
+}
+
+/// Run effects that other VMs scheduled on this one. Called at safe points
+/// (after an `await` resumes).
+pub(crate) fn run_scheduled_effects(vm: &mut VM) -> Result<(), VmError> {
+    if vm.reactive.batch_depth > 0 || vm.reactive.inbox.queue.lock().is_empty() {
+        return Ok(());
+    }
+    flush(vm)
+}
+
+/// Await `future`, running effects other VMs schedule on this one while it
+/// is pending instead of only once it resolves
+pub(crate) async fn await_running_effects(vm: &mut VM, future: VmFuture) -> Result<Value, VmError> {
+    let inbox = vm.reactive.inbox.clone();
+    let prev_waker = inbox.waker.lock().clone();
+    let mut future = future.0;
+    let result = loop {
+        let can_flush = vm.reactive.batch_depth == 0;
+        let resolved = std::future::poll_fn(|cx| {
+            if let Poll::Ready(value) = future.poll_unpin(cx) {
+                return Poll::Ready(Some(value));
+            }
+            if !can_flush {
+                return Poll::Pending;
+            }
+            // Register before checking so a post racing with us still wakes us
+            *inbox.waker.lock() = Some(cx.waker().clone());
+            if inbox.queue.lock().is_empty() {
+                Poll::Pending
+            } else {
+                Poll::Ready(None)
+            }
+        })
+        .await;
+        match resolved {
+            Some(value) => break Ok(value),
+            None => {
+                if let Err(e) = flush(vm) {
+                    break Err(e);
+                }
+            }
+        }
+    };
+    *inbox.waker.lock() = prev_waker;
+    result
+}
+
+/// Signal dependencies of a GUI render function.
+///
+/// Each frame runs as the observer of a dedicated node that belongs to a
+/// private inbox. When a signal the last frame read changes, the node is
+/// posted there and the inbox's waker asks the window for a new frame;
+/// nothing re-runs by itself.
+pub(crate) struct RenderTracker {
+    node: Shared<EffectState>,
+    inbox: Arc<EffectInbox>,
+}
+
+impl RenderTracker {
+    pub(crate) fn new(waker: Waker) -> Self {
+        let inbox = Arc::new(EffectInbox::default());
+        *inbox.waker.lock() = Some(waker);
+        let node = new_node(Value::Null, None);
+        node.write().owner = Arc::downgrade(&inbox);
+        Self { node, inbox }
+    }
+
+    /// Run one frame, replacing the previous frame's dependencies with the
+    /// signals read now
+    pub(crate) fn track<T>(&self, vm: &mut VM, frame: impl FnOnce(&mut VM) -> T) -> T {
+        cleanup_effect(&self.node);
+        dispose_children(vm, &self.node);
+        self.inbox.queue.lock().clear();
+        self.node.write().state = ReactiveState::Clean;
+        with_observer(vm, Some(self.node.clone()), frame)
+    }
+}
+
+impl Drop for RenderTracker {
+    fn drop(&mut self) {
+        self.node.write().disposed = true;
+        cleanup_effect(&self.node);
+    }
+}
+
+/// Stop an effect and the effects it owns, and release them from the VM
+fn dispose_effect(vm: &mut VM, effect_rc: &Shared<EffectState>) {
+    effect_rc.write().disposed = true;
+    cleanup_effect(effect_rc);
+    dispose_children(vm, effect_rc);
+    vm.active_effects.retain(|e| !Arc::ptr_eq(e, effect_rc));
+}
+
+/// Dispose the effects created by the last run of `effect_rc`
+fn dispose_children(vm: &mut VM, effect_rc: &Shared<EffectState>) {
+    let children = std::mem::take(&mut effect_rc.write().children);
+    for child in &children {
+        dispose_effect(vm, child);
+    }
 }
 
 /// Unsubscribe effect from all its dependencies
@@ -99,54 +442,51 @@ fn cleanup_effect(effect_rc: &Shared<EffectState>) {
     effect.dependencies.clear();
 }
 
+/// Subscribe the running computation (if any) to `signal_rc`
+fn track(vm: &VM, signal_rc: &Shared<SignalState>) {
+    let Some(observer) = vm.reactive.observer() else {
+        return;
+    };
+
+    // Store dependency if not already there (avoid dupes in same run)
+    let mut effect = observer.write();
+    if effect
+        .dependencies
+        .iter()
+        .any(|d| Arc::ptr_eq(d, signal_rc))
+    {
+        return;
+    }
+    effect.dependencies.push(signal_rc.clone());
+    drop(effect);
+
+    // Subscribe current effect to this signal (Weak ref)
+    signal_rc.write().subscribers.push(Arc::downgrade(observer));
+}
+
+/// Current value of a signal, recomputing it first if it is a stale computed
+fn current_value(vm: &mut VM, signal_rc: &Shared<SignalState>) -> Result<Value, VmError> {
+    let computation = signal_rc.read().computation.clone();
+    if let Some(computation) = computation {
+        update_if_necessary(vm, &computation)?;
+    }
+    Ok(signal_rc.read().value.clone())
+}
+
 // === Signal Methods ===
 
 /// Signal.value -> Value (Getter)
-pub fn vm_signal_get(_vm: &mut VM, signal_val: &Value, args: &[Value]) -> Result<Value, VmError> {
+pub fn vm_signal_get(vm: &mut VM, signal_val: &Value, args: &[Value]) -> Result<Value, VmError> {
     if !args.is_empty() {
         return Err(VmError::Runtime("get() expects 0 arguments".to_string()));
     }
 
     match signal_val {
         Value::Signal(state_rc) => {
-            let mut state = state_rc.write();
-
-            // Track dependency if inside an effect
-            TRACKING_CONTEXT.with(|ctx| {
-                if let Some(current_effect) = &*ctx.borrow() {
-                    // Subscribe current effect to this signal (Weak ref)
-                    let weak_effect = Arc::downgrade(current_effect);
-                    state.subscribers.push(weak_effect);
-
-                    // Add signal to effect's dependencies (Strong ref)
-                    // We assume vm_signal_get is called with the signal Value which contains state_rc
-                    // But we have state_rc here. We need to add state_rc to effect.
-
-                    // Note: To avoid double locking effect, we need to be careful.
-                    // current_effect is Shared<EffectState>.
-                    // ctx.borrow() returns Ref<Option<Shared<...>>>.
-                    let effect_rc = current_effect.clone();
-
-                    // Now we can write lock the effect state
-                    // run_effect holds a borrow on effect? No, it drops read lock before call_value.
-                    // So effect is NOT locked when running callback. Safe to write lock.
-
-                    let mut effect = effect_rc.write();
-                    // Store dependency if not already there (avoid dupes in same run)
-                    // We check ptr_eq on the Arc inside dependencies
-                    if let Value::Signal(original_rc) = signal_val {
-                        let already_dep = effect
-                            .dependencies
-                            .iter()
-                            .any(|d| Arc::ptr_eq(d, original_rc));
-                        if !already_dep {
-                            effect.dependencies.push(original_rc.clone());
-                        }
-                    }
-                }
-            });
-
-            Ok(state.value.clone())
+            let value = current_value(vm, state_rc)?;
+            // Track dependency if inside an effect or computed
+            track(vm, state_rc);
+            Ok(value)
         }
         _ => Err(VmError::TypeError {
             operation: "get".to_string(),
@@ -158,16 +498,13 @@ pub fn vm_signal_get(_vm: &mut VM, signal_val: &Value, args: &[Value]) -> Result
 
 /// Signal.peek() -> Value
 /// Returns the current value WITHOUT tracking dependency.
-pub fn vm_signal_peek(_vm: &mut VM, signal_val: &Value, args: &[Value]) -> Result<Value, VmError> {
+pub fn vm_signal_peek(vm: &mut VM, signal_val: &Value, args: &[Value]) -> Result<Value, VmError> {
     if !args.is_empty() {
         return Err(VmError::Runtime("peek() expects 0 arguments".to_string()));
     }
 
     match signal_val {
-        Value::Signal(state_rc) => {
-            let state = state_rc.read();
-            Ok(state.value.clone())
-        }
+        Value::Signal(state_rc) => current_value(vm, state_rc),
         _ => Err(VmError::TypeError {
             operation: "peek".to_string(),
             expected: "Signal".to_string(),
@@ -184,6 +521,12 @@ pub fn set_signal_value(
 ) -> Result<(), VmError> {
     let mut state = signal_rc.write();
 
+    if state.computation.is_some() {
+        return Err(VmError::Runtime(
+            "Cannot set a computed signal; update its dependencies instead".to_string(),
+        ));
+    }
+
     // Only update if value changed
     if state.value != new_value {
         state.value = new_value;
@@ -194,12 +537,16 @@ pub fn set_signal_value(
 
         drop(state); // Release lock
 
-        // Run effects
         for weak_sub in subscribers {
-            if let Some(effect_rc) = weak_sub.upgrade() {
-                run_effect(vm, effect_rc)?;
+            if let Some(sub) = weak_sub.upgrade() {
+                mark(vm, &sub, ReactiveState::Dirty);
             }
         }
+
+        // Run effects unless a batch will do it when it ends
+        if vm.reactive.batch_depth == 0 {
+            flush(vm)?;
+        }
     }
     Ok(())
 }
@@ -224,3 +571,53 @@ pub fn vm_signal_set(vm: &mut VM, signal_val: &Value, args: &[Value]) -> Result<
         }),
     }
 }
+
+// === Effect Methods ===
+
+/// Effect.dispose() -> Null
+/// Stops the effect: it unsubscribes from its signals and never runs again.
+pub fn vm_effect_dispose(
+    vm: &mut VM,
+    effect_val: &Value,
+    args: &[Value],
+) -> Result<Value, VmError> {
+    if !args.is_empty() {
+        return Err(VmError::Runtime(
+            "dispose() expects 0 arguments".to_string(),
+        ));
+    }
+
+    match effect_val {
+        Value::Effect(effect_rc) => {
+            dispose_effect(vm, effect_rc);
+            Ok(Value::Null)
+        }
+        _ => Err(VmError::TypeError {
+            operation: "dispose".to_string(),
+            expected: "Effect".to_string(),
+            got: format!("{:?}", effect_val),
+        }),
+    }
+}
+
+/// Effect.disposed -> Boolean
+pub fn vm_effect_disposed(
+    _vm: &mut VM,
+    effect_val: &Value,
+    args: &[Value],
+) -> Result<Value, VmError> {
+    if !args.is_empty() {
+        return Err(VmError::Runtime(
+            "disposed() expects 0 arguments".to_string(),
+        ));
+    }
+
+    match effect_val {
+        Value::Effect(effect_rc) => Ok(Value::Boolean(effect_rc.read().disposed)),
+        _ => Err(VmError::TypeError {
+            operation: "disposed".to_string(),
+            expected: "Effect".to_string(),
+            got: format!("{:?}", effect_val),
+        }),
+    }
+}
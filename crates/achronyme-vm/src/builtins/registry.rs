@@ -3,6 +3,7 @@
 //! This module provides the infrastructure for registering and looking up
 //! built-in functions by name or numeric ID.
 
+use super::docs::{self, BuiltinDoc};
 use crate::error::VmError;
 use crate::value::Value;
 use crate::vm::VM;
@@ -23,6 +24,8 @@ pub struct BuiltinMetadata {
     pub func: NativeFn,
     /// Expected argument count (-1 for variadic)
     pub arity: i8,
+    /// Signature and documentation, if the name has an entry in `docs::BUILTIN_DOCS`
+    pub doc: Option<&'static BuiltinDoc>,
 }
 
 /// Registry of all built-in functions
@@ -70,6 +73,7 @@ impl BuiltinRegistry {
             name: name.to_string(),
             func,
             arity,
+            doc: docs::lookup(name),
         });
     }
 
@@ -7,11 +7,12 @@
 
 use crate::error::VmError;
 use crate::value::Value;
-use crate::vm::VM;
+use crate::vm::{LazyIterator, VM};
 use achronyme_types::complex::Complex;
+use achronyme_types::sync::shared;
 
-/// Sum all elements in a vector or tensor
-pub fn vm_sum(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+/// Sum all elements in a vector or tensor (generators and iterators are drained)
+pub fn vm_sum(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     if args.len() != 1 {
         return Err(VmError::Runtime(format!(
             "sum() expects 1 argument, got {}",
@@ -19,6 +20,11 @@ pub fn vm_sum(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
         )));
     }
 
+    if LazyIterator::is_lazy(&args[0]) {
+        let items = LazyIterator::collect(&LazyIterator::from_value(&args[0])?, vm)?;
+        return vm_sum(vm, &[Value::Vector(shared(items))]);
+    }
+
     match &args[0] {
         Value::Vector(rc) => {
             let vec = rc.read();
@@ -27,9 +27,11 @@ pub fn vm_len(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
     match &args[0] {
         Value::String(s) => Ok(Value::Number(s.len() as f64)),
         Value::Vector(v) => Ok(Value::Number(v.read().len() as f64)),
+        Value::DataFrame(df) => Ok(Value::Number(df.nrows() as f64)),
+        Value::Bytes(b) => Ok(Value::Number(b.len() as f64)),
         _ => Err(VmError::TypeError {
             operation: "len".to_string(),
-            expected: "String or Vector".to_string(),
+            expected: "String, Vector, DataFrame or Bytes".to_string(),
             got: format!("{:?}", args[0]),
         }),
     }
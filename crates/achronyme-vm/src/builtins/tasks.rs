@@ -0,0 +1,447 @@
+//! Structured concurrency: task handles, future combinators and task groups
+//!
+//! `spawn` returns a `Task` that can be awaited, joined or cancelled.
+//! `all_tasks`/`race`/`any_task`/`timeout` combine awaitables (`all` and
+//! `any` are the higher-order predicates), and `task_group(fn)`
+//! runs a scope whose spawned children are awaited before it completes
+//! and are all cancelled as soon as one of them (or the scope) fails.
+
+use crate::bytecode::Closure;
+use crate::error::VmError;
+use crate::value::Value;
+use crate::vm::{CallFrame, VM};
+use achronyme_types::function::Function;
+use achronyme_types::sync::{shared, Arc, Mutex};
+use achronyme_types::value::{TaskHandle, VmFuture};
+use futures::future::BoxFuture;
+use futures::stream::{FuturesUnordered, StreamExt};
+use futures::FutureExt;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
+
+/// Tasks spawned (directly or transitively) inside a `task_group()` scope
+pub(crate) struct TaskGroup {
+    /// New children, picked up by the group supervisor
+    sender: mpsc::UnboundedSender<TaskHandle>,
+    /// Everything that must be cancelled when the scope is torn down
+    aborts: Mutex<Vec<AbortHandle>>,
+}
+
+impl TaskGroup {
+    fn new() -> (Arc<Self>, mpsc::UnboundedReceiver<TaskHandle>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let group = TaskGroup {
+            sender,
+            aborts: Mutex::new(Vec::new()),
+        };
+        (Arc::new(group), receiver)
+    }
+
+    /// Register a child task with the group
+    pub(crate) fn add(&self, task: &TaskHandle) {
+        self.aborts.lock().push(task.abort.clone());
+        // The receiver only goes away together with the supervisor, which
+        // cancels everything registered above anyway.
+        let _ = self.sender.send(task.clone());
+    }
+
+    fn cancel_all(&self) {
+        for abort in self.aborts.lock().drain(..) {
+            abort.abort();
+        }
+    }
+}
+
+/// Cancels the remaining members of a group when its supervisor stops,
+/// whether it finished, failed or was itself cancelled.
+struct CancelOnDrop(Arc<TaskGroup>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel_all();
+    }
+}
+
+/// Run `func(args...)` in a child VM on the Tokio runtime.
+///
+/// The child inherits the caller's task group unless `group` overrides it.
+pub(crate) fn spawn_child(
+    vm: &mut VM,
+    func: &Value,
+    args: &[Value],
+    operation: &str,
+    group: Option<Arc<TaskGroup>>,
+) -> Result<TaskHandle, VmError> {
+    let mut child_vm = vm.new_child();
+    if group.is_some() {
+        child_vm.task_group = group;
+    }
+
+    // Set up call frame in child VM
+    match func {
+        Value::Function(Function::VmClosure(closure_any)) => {
+            let closure = closure_any
+                .downcast_ref::<Closure>()
+                .ok_or(VmError::Runtime("Invalid VmClosure type".to_string()))?;
+
+            let mut new_frame = CallFrame::new(closure.prototype.clone(), None);
+            new_frame.upvalues = closure.upvalues.clone();
+
+            for (i, arg) in args.iter().enumerate() {
+                if i >= 256 {
+                    return Err(VmError::Runtime("Too many arguments (max 256)".into()));
+                }
+                new_frame.registers.set(i as u8, arg.clone())?;
+            }
+
+            child_vm.frames.push(new_frame);
+        }
+        _ => {
+            return Err(VmError::TypeError {
+                operation: operation.to_string(),
+                expected: "Function".to_string(),
+                got: format!("{:?}", func),
+            })
+        }
+    }
+
+    // We use tokio::spawn because Value is Send (Arc<RwLock>)
+    // Finishing wakes the spawning VM's owner, so e.g. a GUI window that
+    // shows the task's state redraws
+    let parent_inbox = vm.reactive.inbox().clone();
+    let handle = tokio::spawn(async move {
+        let result = child_vm.run().await;
+        parent_inbox.notify();
+        result
+    });
+    let abort = handle.abort_handle();
+
+    // Wrap the handle in a VmFuture so it can be awaited in Achronyme
+    let result = async move {
+        match handle.await {
+            Ok(Ok(v)) => v, // Task succeeded and VM returned Value
+            Ok(Err(e)) => Value::Error {
+                // Task succeeded but VM returned Error
+                message: e.to_string(),
+                kind: Some("RuntimeError".into()),
+                source: None,
+            },
+            Err(e) if e.is_cancelled() => cancelled_error(),
+            Err(e) => Value::Error {
+                // Task panicked
+                message: format!("Task execution error: {}", e),
+                kind: Some("TaskError".into()),
+                source: None,
+            },
+        }
+    };
+
+    Ok(TaskHandle {
+        result: VmFuture::new(result),
+        abort,
+    })
+}
+
+fn cancelled_error() -> Value {
+    Value::Error {
+        message: "Task was cancelled".to_string(),
+        kind: Some("CancelledError".into()),
+        source: None,
+    }
+}
+
+fn is_error(value: &Value) -> bool {
+    matches!(value, Value::Error { .. })
+}
+
+/// An awaitable (or plain) value taking part in a combinator
+struct Pending {
+    future: BoxFuture<'static, Value>,
+    abort: Option<AbortHandle>,
+}
+
+fn to_pending(value: &Value, operation: &str) -> Result<Pending, VmError> {
+    match value {
+        Value::Future(f) => Ok(Pending {
+            future: f.0.clone().boxed(),
+            abort: None,
+        }),
+        Value::Task(task) => Ok(Pending {
+            future: task.result.0.clone().boxed(),
+            abort: Some(task.abort.clone()),
+        }),
+        Value::Generator(_) => Err(VmError::Runtime(format!(
+            "{}() cannot run an async call directly; wrap it with spawn()",
+            operation
+        ))),
+        // Plain values are already resolved
+        other => Ok(Pending {
+            future: futures::future::ready(other.clone()).boxed(),
+            abort: None,
+        }),
+    }
+}
+
+/// Check a combinator's argument count
+fn expect_args(name: &str, args: &[Value], count: usize) -> Result<(), VmError> {
+    if args.len() != count {
+        return Err(VmError::Runtime(format!(
+            "{}() expects {} argument{}, got {}",
+            name,
+            count,
+            if count == 1 { "" } else { "s" },
+            args.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Convert a Vector of awaitables, splitting futures from their abort handles
+fn collect_pending(
+    value: &Value,
+    operation: &str,
+) -> Result<(Vec<BoxFuture<'static, Value>>, Vec<AbortHandle>), VmError> {
+    let items = match value {
+        Value::Vector(v) => v.read().clone(),
+        _ => {
+            return Err(VmError::TypeError {
+                operation: operation.to_string(),
+                expected: "Vector of tasks or futures".to_string(),
+                got: format!("{:?}", value),
+            })
+        }
+    };
+
+    let mut futures = Vec::with_capacity(items.len());
+    let mut aborts = Vec::new();
+    for item in &items {
+        let pending = to_pending(item, operation)?;
+        futures.push(pending.future);
+        aborts.extend(pending.abort);
+    }
+    Ok((futures, aborts))
+}
+
+fn abort_all(aborts: &[AbortHandle]) {
+    for abort in aborts {
+        abort.abort();
+    }
+}
+
+/// all_tasks([tasks/futures]) -> Future<Vector>
+/// Resolves to all results in order. Fails fast: the first Error is returned
+/// and every task still running is cancelled.
+pub fn vm_all_tasks(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("all_tasks", args, 1)?;
+    let (futures, aborts) = collect_pending(&args[0], "all_tasks")?;
+    let count = futures.len();
+
+    let future = async move {
+        let mut pending: FuturesUnordered<_> = futures
+            .into_iter()
+            .enumerate()
+            .map(|(i, f)| f.map(move |v| (i, v)))
+            .collect();
+        let mut results = vec![Value::Null; count];
+        while let Some((i, value)) = pending.next().await {
+            if is_error(&value) {
+                abort_all(&aborts);
+                return value;
+            }
+            results[i] = value;
+        }
+        Value::Vector(shared(results))
+    };
+
+    Ok(Value::Future(VmFuture::new(future)))
+}
+
+/// race([tasks/futures]) -> Future
+/// Resolves to whichever finishes first (value or Error); the rest are cancelled.
+pub fn vm_race(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("race", args, 1)?;
+    let (futures, aborts) = collect_pending(&args[0], "race")?;
+    if futures.is_empty() {
+        return Err(VmError::Runtime(
+            "race() expects at least one task or future".to_string(),
+        ));
+    }
+
+    let future = async move {
+        let mut pending: FuturesUnordered<_> = futures.into_iter().collect();
+        let winner = pending.next().await.unwrap_or(Value::Null);
+        abort_all(&aborts);
+        winner
+    };
+
+    Ok(Value::Future(VmFuture::new(future)))
+}
+
+/// any_task([tasks/futures]) -> Future
+/// Resolves to the first successful result and cancels the rest.
+/// If every one fails, resolves to an AggregateError wrapping the last failure.
+pub fn vm_any_task(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("any_task", args, 1)?;
+    let (futures, aborts) = collect_pending(&args[0], "any_task")?;
+    if futures.is_empty() {
+        return Err(VmError::Runtime(
+            "any_task() expects at least one task or future".to_string(),
+        ));
+    }
+    let count = futures.len();
+
+    let future = async move {
+        let mut pending: FuturesUnordered<_> = futures.into_iter().collect();
+        let mut last_error = Value::Null;
+        while let Some(value) = pending.next().await {
+            if !is_error(&value) {
+                abort_all(&aborts);
+                return value;
+            }
+            last_error = value;
+        }
+        Value::Error {
+            message: format!("All {} tasks failed", count),
+            kind: Some("AggregateError".into()),
+            source: Some(Box::new(last_error)),
+        }
+    };
+
+    Ok(Value::Future(VmFuture::new(future)))
+}
+
+/// timeout(ms, task_or_future) -> Future
+/// Resolves to the result, or to a TimeoutError after `ms` milliseconds
+/// (cancelling the task if it was one).
+pub fn vm_timeout(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("timeout", args, 2)?;
+    let ms = match args[0] {
+        Value::Number(n) if n >= 0.0 => n,
+        Value::Number(_) => {
+            return Err(VmError::Runtime(
+                "timeout() duration cannot be negative".to_string(),
+            ))
+        }
+        _ => {
+            return Err(VmError::TypeError {
+                operation: "timeout".to_string(),
+                expected: "Number".to_string(),
+                got: format!("{:?}", args[0]),
+            })
+        }
+    };
+    let pending = to_pending(&args[1], "timeout")?;
+
+    let future = async move {
+        match tokio::time::timeout(Duration::from_millis(ms as u64), pending.future).await {
+            Ok(value) => value,
+            Err(_) => {
+                if let Some(abort) = pending.abort {
+                    abort.abort();
+                }
+                Value::Error {
+                    message: format!("Timed out after {} ms", ms),
+                    kind: Some("TimeoutError".into()),
+                    source: None,
+                }
+            }
+        }
+    };
+
+    Ok(Value::Future(VmFuture::new(future)))
+}
+
+/// task_group(fn) -> Task
+/// Runs `fn` in a new scope. Every task spawned inside it joins the group;
+/// the group resolves to `fn`'s result once all of them have finished.
+/// If the scope or any child fails, the remaining children are cancelled
+/// and the group resolves to that Error. Cancelling the group cancels them too.
+pub fn vm_task_group(vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
+    expect_args("task_group", args, 1)?;
+    let (group, mut children) = TaskGroup::new();
+    let body = spawn_child(vm, &args[0], &[], "task_group", Some(group.clone()))?;
+    group.aborts.lock().push(body.abort.clone());
+
+    let supervisor = async move {
+        let _guard = CancelOnDrop(group);
+        let mut pending = FuturesUnordered::new();
+        pending.push(body.result.0.map(|v| (true, v)).boxed());
+        let mut result = Value::Null;
+
+        loop {
+            tokio::select! {
+                biased;
+                Some(child) = children.recv() => {
+                    pending.push(child.result.0.map(|v| (false, v)).boxed());
+                }
+                next = pending.next() => match next {
+                    Some((is_body, value)) => {
+                        if is_error(&value) {
+                            return value;
+                        }
+                        if is_body {
+                            result = value;
+                        }
+                    }
+                    None => match children.try_recv() {
+                        Ok(child) => pending.push(child.result.0.map(|v| (false, v)).boxed()),
+                        Err(_) => return result,
+                    },
+                },
+            }
+        }
+    };
+
+    let handle = tokio::spawn(supervisor);
+    let abort = handle.abort_handle();
+    let result = async move {
+        match handle.await {
+            Ok(value) => value,
+            Err(_) => cancelled_error(),
+        }
+    };
+
+    let task = TaskHandle {
+        result: VmFuture::new(result),
+        abort,
+    };
+    // A nested group is itself a child of the enclosing one
+    if let Some(outer) = &vm.task_group {
+        outer.add(&task);
+    }
+    Ok(Value::Task(task))
+}
+
+// === Task Methods ===
+
+fn task_handle<'a>(receiver: &'a Value, method: &str) -> Result<&'a TaskHandle, VmError> {
+    match receiver {
+        Value::Task(task) => Ok(task),
+        _ => Err(VmError::TypeError {
+            operation: method.to_string(),
+            expected: "Task".to_string(),
+            got: format!("{:?}", receiver),
+        }),
+    }
+}
+
+/// Task.join() -> Future (same as awaiting the task itself)
+pub fn vm_task_join(_vm: &mut VM, receiver: &Value, _args: &[Value]) -> Result<Value, VmError> {
+    let task = task_handle(receiver, "join")?;
+    Ok(Value::Future(task.result.clone()))
+}
+
+/// Task.cancel() -> Boolean (false if the task had already finished)
+pub fn vm_task_cancel(_vm: &mut VM, receiver: &Value, _args: &[Value]) -> Result<Value, VmError> {
+    let task = task_handle(receiver, "cancel")?;
+    let running = !task.abort.is_finished();
+    task.abort.abort();
+    Ok(Value::Boolean(running))
+}
+
+/// Task.is_done() -> Boolean
+pub fn vm_task_is_done(_vm: &mut VM, receiver: &Value, _args: &[Value]) -> Result<Value, VmError> {
+    let task = task_handle(receiver, "is_done")?;
+    Ok(Value::Boolean(task.abort.is_finished()))
+}
@@ -21,6 +21,11 @@ pub fn vm_typeof(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
         )));
     }
 
+    // Values of declared data types report the type, not the variant
+    if let Value::Variant(variant) = &args[0] {
+        return Ok(Value::String(variant.type_name.clone()));
+    }
+
     let type_name = match &args[0] {
         Value::Number(_) => "Number",
         Value::Boolean(_) => "Boolean",
@@ -44,6 +49,11 @@ pub fn vm_typeof(_vm: &mut VM, args: &[Value]) -> Result<Value, VmError> {
         Value::AsyncMutex(_) => "AsyncMutex",
         Value::MutexGuard(_) => "MutexGuard",
         Value::Signal(_) => "Signal",
+        Value::Effect(_) => "Effect",
+        Value::DataFrame(_) => "DataFrame",
+        Value::Bytes(_) => "Bytes",
+        Value::Task(_) => "Task",
+        Value::Iterator(_) => "Iterator",
         _ => "Internal",
     };
 
@@ -223,6 +233,15 @@ fn format_value(value: &Value) -> String {
         Value::EarlyReturn(_) => "<early-return>".to_string(),
         Value::MutableRef(_) => "<mutable-ref>".to_string(),
         Value::Generator(_) => "<generator>".to_string(),
+        Value::Iterator(_) => "<iterator>".to_string(),
+        Value::Range {
+            start,
+            end,
+            inclusive,
+        } => {
+            let op = if *inclusive { "..=" } else { ".." };
+            format!("{}{}{}", format_value(start), op, format_value(end))
+        }
         Value::Future(_) => "<future>".to_string(),
         Value::Error { .. } => "Error".to_string(),
         Value::BoundMethod { method_name, .. } => format!("<method {}>", method_name),
@@ -234,6 +253,11 @@ fn format_value(value: &Value) -> String {
             let state = rc.read();
             format!("Signal({})", format_value(&state.value))
         }
+        Value::Effect(_) => "<effect>".to_string(),
+        Value::Task(_) => "<task>".to_string(),
+        Value::DataFrame(df) => df.to_string(),
+        Value::Bytes(bytes) => format!("<bytes: {}>", bytes.len()),
+        Value::Variant(variant) => variant.format_with(format_value),
         _ => format!("{:?}", value),
     }
 }
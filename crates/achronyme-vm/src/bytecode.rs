@@ -106,6 +106,19 @@ pub struct FunctionPrototype {
     /// Each entry is Option<func_idx> where func_idx points to a zero-parameter
     /// function that computes the default value
     pub param_defaults: Vec<Option<usize>>,
+
+    /// Variables defined by top-level statements (main function only)
+    pub top_level_bindings: Vec<TopLevelBinding>,
+}
+
+/// A top-level variable and the register holding it
+#[derive(Debug, Clone)]
+pub struct TopLevelBinding {
+    pub name: String,
+    pub register: u8,
+    /// Position of the first instruction after the definition; the binding
+    /// holds its value once the main frame has reached it
+    pub defined_at: usize,
 }
 
 impl FunctionPrototype {
@@ -123,6 +136,7 @@ impl FunctionPrototype {
             is_async: false,
             debug_info: None,
             param_defaults: Vec::new(),
+            top_level_bindings: Vec::new(),
         }
     }
 
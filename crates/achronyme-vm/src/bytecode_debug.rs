@@ -94,7 +94,7 @@ fn disassemble_instruction(instruction: u32, func: &FunctionPrototype) {
                 .unwrap_or("???");
             println!("SETFIELD    R{} \"{}\" R{}", a, field_name, c);
         }
-        OpCode::GetField => {
+        OpCode::GetField | OpCode::GetMethod => {
             let a = decode_a(instruction);
             let b = decode_b(instruction);
             let c = decode_c(instruction);
@@ -110,7 +110,12 @@ fn disassemble_instruction(instruction: u32, func: &FunctionPrototype) {
                     }
                 })
                 .unwrap_or("???");
-            println!("GETFIELD    R{} R{} \"{}\"", a, b, field_name);
+            let mnemonic = if opcode == OpCode::GetField {
+                "GETFIELD"
+            } else {
+                "GETMETHOD"
+            };
+            println!("{:<11} R{} R{} \"{}\"", mnemonic, a, b, field_name);
         }
         OpCode::Closure => {
             let a = decode_a(instruction);
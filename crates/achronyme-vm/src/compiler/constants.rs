@@ -27,14 +27,7 @@ pub(crate) fn get_constant(name: &str) -> Option<Value> {
     }
 }
 
-/// Check if a name is a predefined constant
-#[allow(dead_code)]
-pub(crate) fn is_constant(name: &str) -> bool {
-    matches!(
-        name,
-        "PI" | "E" | "PHI" | "SQRT2" | "SQRT3" | "LN2" | "LN10" | "i" | "Infinity" | "NaN"
-    )
-}
+pub use achronyme_metadata::is_constant;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,185 @@
+//! Algebraic data types: declarations and variant constructors
+//!
+//! `type Shape = Circle { r: Number } | Rect { w: Number, h: Number }`
+//! declares `Shape` and one constructor per variant. Constructors take the
+//! fields positionally (`Rect(2, 3)`); a variant without fields is a value
+//! on its own (`Leaf`). A union of names that are not known types,
+//! `type Color = Red | Green`, declares field-less variants the same way.
+
+use crate::compiler::registers::RegResult;
+use crate::compiler::Compiler;
+use crate::error::CompileError;
+use crate::opcode::{instruction::*, OpCode};
+use crate::value::Value;
+use achronyme_parser::ast::{AstNode, VariantDecl};
+use achronyme_parser::TypeAnnotation;
+use achronyme_types::sync::shared;
+
+impl Compiler {
+    /// Register a data type declaration (no code generation)
+    pub(crate) fn declare_data_type(
+        &mut self,
+        name: &str,
+        variants: &[VariantDecl],
+    ) -> Result<(), CompileError> {
+        for variant in variants {
+            if let Some((other, _)) = self.lookup_variant(&variant.name) {
+                if other != name {
+                    return Err(CompileError::Error(format!(
+                        "Variant '{}' is already declared by type '{}'",
+                        variant.name, other
+                    )));
+                }
+            }
+        }
+        self.data_types.insert(name.to_string(), variants.to_vec());
+        Ok(())
+    }
+
+    /// The variants of `type Name = A | B` when none of the names is a known
+    /// type, `None` when the declaration is a type alias
+    pub(crate) fn fieldless_variants(
+        &self,
+        name: &str,
+        type_definition: &TypeAnnotation,
+    ) -> Result<Option<Vec<VariantDecl>>, CompileError> {
+        let Some(names) = type_definition.reference_union() else {
+            return Ok(None);
+        };
+        let known = |n: &str| self.type_registry.contains_key(n) || self.data_types.contains_key(n);
+        if names.iter().any(|n| known(n)) {
+            return Ok(None);
+        }
+        let mut variants: Vec<VariantDecl> = Vec::new();
+        for variant_name in names {
+            if variants.iter().any(|v| v.name == variant_name) {
+                return Err(CompileError::Error(format!(
+                    "Duplicate variant '{}' in type '{}'",
+                    variant_name, name
+                )));
+            }
+            variants.push(VariantDecl {
+                name: variant_name.to_string(),
+                fields: Vec::new(),
+            });
+        }
+        Ok(Some(variants))
+    }
+
+    /// The data type declaring a variant, and the variant itself
+    pub(crate) fn lookup_variant(&self, name: &str) -> Option<(&str, &VariantDecl)> {
+        self.data_types.iter().find_map(|(type_name, variants)| {
+            variants
+                .iter()
+                .find(|variant| variant.name == name)
+                .map(|variant| (type_name.as_str(), variant))
+        })
+    }
+
+    /// Like `lookup_variant`, unless a variable shadows the constructor
+    pub(crate) fn variant_in_scope(&self, name: &str) -> Option<(String, VariantDecl)> {
+        if self.symbols.has(name) {
+            return None;
+        }
+        self.lookup_variant(name)
+            .map(|(type_name, variant)| (type_name.to_string(), variant.clone()))
+    }
+
+    /// Compile a constructor call: `Rect(2, 3)`
+    pub(crate) fn compile_variant_construction(
+        &mut self,
+        type_name: &str,
+        variant: &VariantDecl,
+        args: &[AstNode],
+    ) -> Result<RegResult, CompileError> {
+        if args.len() != variant.fields.len() {
+            return Err(CompileError::Error(format!(
+                "{} expects {} argument{}, got {}",
+                variant.name,
+                variant.fields.len(),
+                if variant.fields.len() == 1 { "" } else { "s" },
+                args.len()
+            )));
+        }
+
+        // Field values go to consecutive registers, in declaration order
+        let fields_start = if args.is_empty() {
+            0
+        } else {
+            self.registers.allocate_many(args.len())?
+        };
+        for ((arg, (_, field_type)), reg) in args.iter().zip(&variant.fields).zip(fields_start..) {
+            let arg_res = self.compile_expression(arg)?;
+            if arg_res.reg() != reg {
+                self.emit_move(reg, arg_res.reg());
+            }
+            if arg_res.is_temp() {
+                self.registers.free(arg_res.reg());
+            }
+
+            let type_name = self.type_annotation_to_string(field_type);
+            if type_name != "Any" {
+                let type_idx = self.add_string(type_name)?;
+                self.emit(encode_abx(OpCode::TypeAssert.as_u8(), reg, type_idx as u16));
+            }
+        }
+
+        // Descriptor: [type name, variant name, field names...]
+        let mut descriptor = vec![
+            Value::String(type_name.to_string()),
+            Value::String(variant.name.clone()),
+        ];
+        descriptor.extend(
+            variant
+                .fields
+                .iter()
+                .map(|(field, _)| Value::String(field.clone())),
+        );
+        let desc_idx = self.add_constant(Value::Vector(shared(descriptor)))?;
+        if desc_idx > u8::MAX as usize {
+            return Err(CompileError::TooManyConstants);
+        }
+
+        let dst = self.registers.allocate()?;
+        self.emit(encode_abc(
+            OpCode::NewVariant.as_u8(),
+            dst,
+            fields_start,
+            desc_idx as u8,
+        ));
+
+        for reg in (fields_start..).take(args.len()) {
+            self.registers.free(reg);
+        }
+
+        Ok(RegResult::temp(dst))
+    }
+
+    /// Compile a variant name used as a value: the variant itself when it
+    /// has no fields, otherwise its constructor as a function
+    pub(crate) fn compile_variant_ref(
+        &mut self,
+        type_name: &str,
+        variant: &VariantDecl,
+    ) -> Result<RegResult, CompileError> {
+        if variant.fields.is_empty() {
+            return self.compile_variant_construction(type_name, variant, &[]);
+        }
+
+        // Circle  =>  (r) => Circle(r)
+        let params: Vec<_> = variant
+            .fields
+            .iter()
+            .map(|(field, _)| (field.clone(), None, None))
+            .collect();
+        let body = AstNode::CallExpression {
+            callee: Box::new(AstNode::VariableRef(variant.name.clone())),
+            args: variant
+                .fields
+                .iter()
+                .map(|(field, _)| AstNode::VariableRef(field.clone()))
+                .collect(),
+        };
+        self.compile_lambda(&params, &body)
+    }
+}
@@ -188,6 +188,25 @@ impl Compiler {
         &mut self,
         record: &AstNode,
         field: &str,
+    ) -> Result<RegResult, CompileError> {
+        self.compile_member(OpCode::GetField, record, field)
+    }
+
+    /// Compile the callee of a method call (e.g., df.head in df.head(1)),
+    /// where intrinsic methods take precedence over DataFrame columns
+    pub(crate) fn compile_method_access(
+        &mut self,
+        record: &AstNode,
+        field: &str,
+    ) -> Result<RegResult, CompileError> {
+        self.compile_member(OpCode::GetMethod, record, field)
+    }
+
+    fn compile_member(
+        &mut self,
+        opcode: OpCode,
+        record: &AstNode,
+        field: &str,
     ) -> Result<RegResult, CompileError> {
         // Compile the record being accessed
         let rec_res = self.compile_expression(record)?;
@@ -198,9 +217,9 @@ impl Compiler {
         // Allocate result register
         let result_reg = self.registers.allocate()?;
 
-        // Emit GetField: R[result] = R[rec][K[field_idx]]
+        // Emit GetField/GetMethod: R[result] = R[rec][K[field_idx]]
         self.emit(encode_abc(
-            OpCode::GetField.as_u8(),
+            opcode.as_u8(),
             result_reg,
             rec_res.reg(),
             field_idx as u8,
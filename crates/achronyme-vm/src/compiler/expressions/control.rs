@@ -7,12 +7,13 @@ use crate::error::CompileError;
 use crate::opcode::{instruction::*, OpCode};
 use achronyme_parser::ast::AstNode;
 use achronyme_types::sync::shared;
+use std::borrow::Cow;
 
 impl Compiler {
     /// Compile a pattern for match expressions (refutable context)
     /// This handles nested patterns with literals/types by emitting match checks
     /// and collecting jump instructions to the next arm on failure
-    fn compile_pattern_for_match(
+    pub(crate) fn compile_pattern_for_match(
         &mut self,
         pattern: &achronyme_parser::ast::Pattern,
         target_reg: u8,
@@ -54,12 +55,21 @@ impl Compiler {
                 Ok(())
             }
 
+            Pattern::Variable(name) if self.lookup_variant(name).is_some() => {
+                // A bare variant name matches that variant
+                self.compile_variant_pattern(name, &[], target_reg, next_arm_jumps)
+            }
+
             Pattern::Variable(name) => {
                 // Variable pattern always matches and binds the value
                 self.symbols.define(name.clone(), target_reg)?;
                 Ok(())
             }
 
+            Pattern::Variant { name, fields } => {
+                self.compile_variant_pattern(name, fields, target_reg, next_arm_jumps)
+            }
+
             Pattern::Wildcard => {
                 // Wildcard always matches
                 Ok(())
@@ -200,8 +210,9 @@ impl Compiler {
                 ));
 
                 // Now check nested patterns and field existence
-                let mut reg_idx = dst_start;
-                for (field_name, nested_pattern, default_value) in fields {
+                for (reg_idx, (field_name, nested_pattern, default_value)) in
+                    (dst_start..).zip(fields.iter())
+                {
                     // If there's no default value, the field must exist (not be null)
                     // Skip this check if the nested pattern is explicitly checking for null
                     let is_null_pattern = matches!(
@@ -241,8 +252,6 @@ impl Compiler {
                     if needs_field_binding {
                         self.symbols.define(field_name.clone(), reg_idx)?;
                     }
-
-                    reg_idx += 1;
                 }
 
                 Ok(())
@@ -383,8 +392,21 @@ impl Compiler {
             // 2. If pattern matches AND guard passes (if present), execute body
             // 3. Otherwise, jump to next arm
 
+            // A bare variant name is a variant pattern, not a binding
+            let pattern = match &arm.pattern {
+                achronyme_parser::ast::Pattern::Variable(name)
+                    if self.lookup_variant(name).is_some() =>
+                {
+                    Cow::Owned(achronyme_parser::ast::Pattern::Variant {
+                        name: name.clone(),
+                        fields: Vec::new(),
+                    })
+                }
+                pattern => Cow::Borrowed(pattern),
+            };
+
             // Compile pattern matching
-            match &arm.pattern {
+            match pattern.as_ref() {
                 achronyme_parser::ast::Pattern::Wildcard => {
                     // Wildcard always matches, no test needed
                     // Compile guard if present
@@ -682,9 +704,46 @@ impl Compiler {
                         self.patch_jump(jump);
                     }
                 }
+
+                achronyme_parser::ast::Pattern::Variant { name, fields } => {
+                    // Check the variant, then destructure its fields
+                    let mut next_arm_jumps = Vec::new();
+                    self.compile_variant_pattern(
+                        name,
+                        fields,
+                        value_res.reg(),
+                        &mut next_arm_jumps,
+                    )?;
+
+                    // Check guard if present
+                    if let Some(guard) = &arm.guard {
+                        let guard_res = self.compile_expression(guard)?;
+                        next_arm_jumps.push(self.emit_jump_if_false(guard_res.reg(), 0));
+                        if guard_res.is_temp() {
+                            self.registers.free(guard_res.reg());
+                        }
+                    }
+
+                    // Compile body
+                    let body_res = self.compile_expression(&arm.body)?;
+                    self.emit_move(result_reg, body_res.reg());
+                    if body_res.is_temp() {
+                        self.registers.free(body_res.reg());
+                    }
+
+                    // Jump to end
+                    end_jumps.push(self.emit_jump(0));
+
+                    // Patch all jumps to next arm
+                    for jump in next_arm_jumps {
+                        self.patch_jump(jump);
+                    }
+                }
             }
         }
 
+        self.check_match_exhaustiveness(arms);
+
         // Patch all end jumps
         for jump in end_jumps {
             self.patch_jump(jump);
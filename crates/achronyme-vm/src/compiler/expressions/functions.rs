@@ -67,6 +67,8 @@ impl Compiler {
             parent: None, // We don't need parent for simple compilation
             builtins: self.builtins.clone(), // Share the built-ins registry
             type_registry: self.type_registry.clone(), // Share the type registry
+            data_types: self.data_types.clone(),
+            warnings: Vec::new(),
             exported_values: std::collections::HashMap::new(),
             exported_types: std::collections::HashMap::new(),
             exports_reg: None, // Lambdas don't have exports
@@ -242,6 +244,7 @@ impl Compiler {
 
         // Set register count based on actual usage
         child_compiler.function.register_count = child_compiler.registers.max_used();
+        self.warnings.append(&mut child_compiler.warnings);
 
         // Add nested function to parent's function list
         let func_idx = self.function.functions.len();
@@ -273,6 +276,9 @@ impl Compiler {
             if let Some(builtin_idx) = self.builtins.get_id(name) {
                 return self.compile_builtin_call(builtin_idx, args);
             }
+            if let Some((type_name, variant)) = self.variant_in_scope(name) {
+                return self.compile_variant_construction(&type_name, &variant, args);
+            }
         }
 
         // ARREGLO CRÍTICO:
@@ -388,11 +394,17 @@ impl Compiler {
                 if let Some(builtin_idx) = self.builtins.get_id(name) {
                     return self.compile_builtin_call(builtin_idx, args);
                 }
+                if let Some((type_name, variant)) = self.variant_in_scope(name) {
+                    return self.compile_variant_construction(&type_name, &variant, args);
+                }
             }
         }
 
         // 1. Compilar el callee (la expresión que resulta en la función)
-        let func_expr_res = self.compile_expression(callee)?;
+        let func_expr_res = match callee {
+            AstNode::FieldAccess { record, field } => self.compile_method_access(record, field)?,
+            _ => self.compile_expression(callee)?,
+        };
 
         // 2. Reservar Call Frame Seguro (Func + Args)
         let call_frame_start = self.registers.allocate_many(1 + args.len())?;
@@ -690,9 +702,18 @@ impl Compiler {
         };
 
         // Compile arguments into consecutive registers starting at result_reg + 1
+        // iter() counts through a range instead of expanding it
+        let lazy_range = self.builtins.get_id("iter") == Some(builtin_idx);
         let mut arg_results = Vec::new();
         for arg in args {
-            let arg_res = self.compile_expression(arg)?;
+            let arg_res = match arg {
+                AstNode::RangeExpr {
+                    start,
+                    end,
+                    inclusive,
+                } if lazy_range => self.compile_range_value(start, end, *inclusive)?,
+                _ => self.compile_expression(arg)?,
+            };
             arg_results.push(arg_res);
         }
 
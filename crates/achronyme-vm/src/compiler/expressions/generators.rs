@@ -38,6 +38,8 @@ impl Compiler {
             parent: None,
             builtins: self.builtins.clone(),
             type_registry: self.type_registry.clone(), // Share the type registry
+            data_types: self.data_types.clone(),
+            warnings: Vec::new(),
             exported_values: std::collections::HashMap::new(),
             exported_types: std::collections::HashMap::new(),
             exports_reg: None, // Generators don't have exports
@@ -88,6 +90,7 @@ impl Compiler {
 
         // Set register count
         child_compiler.function.register_count = child_compiler.registers.max_used();
+        self.warnings.append(&mut child_compiler.warnings);
 
         // Add to current function's nested functions list
         let func_idx = self.function.functions.len();
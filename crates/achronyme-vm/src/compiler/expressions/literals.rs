@@ -164,7 +164,8 @@ impl Compiler {
     }
 
     /// Compile range expression (0..5 or 0..=5)
-    /// Ranges are expanded into vectors at compile time for simplicity
+    /// Small ranges with literal ends are expanded into vectors at compile
+    /// time; any other range becomes a lazy `Value::Range`
     pub(crate) fn compile_range(
         &mut self,
         start: &AstNode,
@@ -173,50 +174,21 @@ impl Compiler {
     ) -> Result<RegResult, CompileError> {
         use crate::value::Value;
 
-        // Try to evaluate start and end as constants
-        let start_val = match start {
-            AstNode::Number(n) => *n,
-            AstNode::UnaryOp {
-                op: achronyme_parser::ast::UnaryOp::Negate,
-                operand,
-            } => match operand.as_ref() {
-                AstNode::Number(n) => -*n,
-                _ => {
-                    return Err(CompileError::Error(
-                        "Range start must be a number literal".to_string(),
-                    ))
-                }
-            },
-            _ => {
-                return Err(CompileError::Error(
-                    "Range start must be a number literal".to_string(),
-                ))
-            }
-        };
-
-        let end_val = match end {
-            AstNode::Number(n) => *n,
-            AstNode::UnaryOp {
-                op: achronyme_parser::ast::UnaryOp::Negate,
-                operand,
-            } => match operand.as_ref() {
-                AstNode::Number(n) => -*n,
-                _ => {
-                    return Err(CompileError::Error(
-                        "Range end must be a number literal".to_string(),
-                    ))
-                }
-            },
-            _ => {
-                return Err(CompileError::Error(
-                    "Range end must be a number literal".to_string(),
-                ))
-            }
+        let (Some(start_val), Some(end_val)) = (literal_number(start), literal_number(end)) else {
+            return self.compile_range_value(start, end, inclusive);
         };
 
         // Generate the range values
         let start_int = start_val as i64;
         let end_int = end_val as i64;
+        let range_end = if inclusive {
+            end_int.saturating_add(1)
+        } else {
+            end_int
+        };
+        if range_end.saturating_sub(start_int) > MAX_EXPANDED_RANGE {
+            return self.compile_range_value(start, end, inclusive);
+        }
 
         // Create vector register
         let vec_reg = self.registers.allocate()?;
@@ -225,8 +197,6 @@ impl Compiler {
         self.emit(encode_abc(OpCode::NewVector.as_u8(), vec_reg, 0, 0));
 
         // Push each value in the range
-        let range_end = if inclusive { end_int + 1 } else { end_int };
-
         for i in start_int..range_end {
             // Load the number as a constant
             let val_reg = self.registers.allocate()?;
@@ -243,6 +213,36 @@ impl Compiler {
         Ok(RegResult::temp(vec_reg))
     }
 
+    /// Compile a range into a `Value::Range` instead of expanding it, so
+    /// `0..1e12` can be counted through lazily
+    pub(crate) fn compile_range_value(
+        &mut self,
+        start: &AstNode,
+        end: &AstNode,
+        inclusive: bool,
+    ) -> Result<RegResult, CompileError> {
+        let start_res = self.compile_expression(start)?;
+        let end_res = self.compile_expression(end)?;
+        let range_reg = self.registers.allocate()?;
+        let opcode = if inclusive {
+            OpCode::RangeIn
+        } else {
+            OpCode::RangeEx
+        };
+        self.emit(encode_abc(
+            opcode.as_u8(),
+            range_reg,
+            start_res.reg(),
+            end_res.reg(),
+        ));
+        for res in [start_res, end_res] {
+            if res.is_temp() {
+                self.registers.free(res.reg());
+            }
+        }
+        Ok(RegResult::temp(range_reg))
+    }
+
     /// Compile interpolated string expression
     /// Converts `'Hello ${name}!'` into string concatenation
     pub(crate) fn compile_interpolated_string(
@@ -338,3 +338,22 @@ impl Compiler {
         Ok(RegResult::temp(result_reg.unwrap()))
     }
 }
+
+/// Longest range with literal ends that is expanded into a vector at compile
+/// time; longer ones stay lazy
+const MAX_EXPANDED_RANGE: i64 = 4096;
+
+/// The value of a number literal, possibly negated
+fn literal_number(node: &AstNode) -> Option<f64> {
+    match node {
+        AstNode::Number(n) => Some(*n),
+        AstNode::UnaryOp {
+            op: achronyme_parser::ast::UnaryOp::Negate,
+            operand,
+        } => match operand.as_ref() {
+            AstNode::Number(n) => Some(-*n),
+            _ => None,
+        },
+        _ => None,
+    }
+}
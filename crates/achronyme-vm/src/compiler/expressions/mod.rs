@@ -146,6 +146,7 @@ impl Compiler {
                             | AstNode::Import { .. }
                             | AstNode::Export { .. }
                             | AstNode::TypeAlias { .. }
+                            | AstNode::DataType { .. }
                             | AstNode::Return { .. }
                     );
 
@@ -175,6 +176,11 @@ impl Compiler {
                 }
             }
 
+            // Only produced by the recovering parser used by tooling
+            AstNode::Error { message } => {
+                Err(CompileError::Error(format!("Syntax error: {}", message)))
+            }
+
             _ => Err(CompileError::Error(format!(
                 "Expression compilation not yet implemented for {:?}",
                 node
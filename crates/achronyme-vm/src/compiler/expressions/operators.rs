@@ -4,8 +4,12 @@ use crate::compiler::registers::RegResult;
 use crate::compiler::Compiler;
 use crate::error::CompileError;
 use crate::opcode::{instruction::*, OpCode};
+use achronyme_metadata::pipe_slot;
 use achronyme_parser::ast::{AstNode, BinaryOp, UnaryOp};
 
+/// Local name of the value on the left of `|>`
+const PIPE_VALUE: &str = "|>";
+
 impl Compiler {
     /// Compile binary operation
     pub(crate) fn compile_binary_op(
@@ -18,6 +22,7 @@ impl Compiler {
         match op {
             BinaryOp::And => return self.compile_and(left, right),
             BinaryOp::Or => return self.compile_or(left, right),
+            BinaryOp::Pipe => return self.compile_pipe(left, right),
             _ => {}
         }
 
@@ -32,6 +37,7 @@ impl Compiler {
             BinaryOp::Multiply => OpCode::Mul,
             BinaryOp::Divide => OpCode::Div,
             BinaryOp::Modulo => OpCode::Mod,
+            BinaryOp::MatMul => OpCode::MatMul,
             BinaryOp::Power => OpCode::Pow,
             BinaryOp::Eq => OpCode::Eq,
             BinaryOp::Neq => OpCode::Ne,
@@ -39,7 +45,7 @@ impl Compiler {
             BinaryOp::Lte => OpCode::Le,
             BinaryOp::Gt => OpCode::Gt,
             BinaryOp::Gte => OpCode::Ge,
-            BinaryOp::And | BinaryOp::Or => unreachable!("Handled above"),
+            BinaryOp::And | BinaryOp::Or | BinaryOp::Pipe => unreachable!("Handled above"),
         };
 
         self.emit(encode_abc(
@@ -130,6 +136,71 @@ impl Compiler {
         Ok(RegResult::temp(result_reg))
     }
 
+    /// Compile a pipeline step: value |> function
+    /// - `x |> round(2)`: the value becomes the first argument, `round(x, 2)`
+    /// - `xs |> map(f)`: builtins whose metadata sets `pipe_arg` take it there
+    ///   instead, `map(f, xs)` (see `achronyme_metadata::pipe_slot`)
+    /// - `x |> pow(2, _)`: the value replaces each `_` argument, `pow(2, x)`
+    /// - `x |> f`: anything else is called with the value, `f(x)`
+    ///
+    /// The value is evaluated first and only once.
+    fn compile_pipe(&mut self, left: &AstNode, right: &AstNode) -> Result<RegResult, CompileError> {
+        let value_res = self.compile_expression(left)?;
+
+        // Name the value for the duration of the call; the name can't clash
+        // with user variables since it isn't an identifier
+        let shadowed = self.symbols.get(PIPE_VALUE).ok();
+        self.symbols
+            .define(PIPE_VALUE.to_string(), value_res.reg())?;
+
+        let value = AstNode::VariableRef(PIPE_VALUE.to_string());
+        let with_value = |args: &[AstNode], builtin: Option<&str>| -> Vec<AstNode> {
+            if args.iter().any(AstNode::is_pipe_placeholder) {
+                return args
+                    .iter()
+                    .map(|arg| {
+                        if arg.is_pipe_placeholder() {
+                            value.clone()
+                        } else {
+                            arg.clone()
+                        }
+                    })
+                    .collect();
+            }
+            let mut args = args.to_vec();
+            args.insert(pipe_slot(builtin, args.len()), value.clone());
+            args
+        };
+        let builtin = |compiler: &Self, name: &str| {
+            let shadowed =
+                compiler.symbols.has(name) || compiler.symbols.get_upvalue(name).is_some();
+            (!shadowed && compiler.builtins.get_id(name).is_some()).then(|| name.to_string())
+        };
+        let result = match right {
+            AstNode::FunctionCall { name, args } => {
+                let args = with_value(args, builtin(self, name).as_deref());
+                self.compile_function_call(name, &args, false)
+            }
+            AstNode::CallExpression { callee, args } => {
+                let builtin = match callee.as_ref() {
+                    AstNode::VariableRef(name) => builtin(self, name),
+                    _ => None,
+                };
+                self.compile_call_expression(callee, &with_value(args, builtin.as_deref()), false)
+            }
+            _ => self.compile_call_expression(right, std::slice::from_ref(&value), false),
+        };
+
+        match shadowed {
+            Some(reg) => self.symbols.define(PIPE_VALUE.to_string(), reg)?,
+            None => self.symbols.undefine(PIPE_VALUE),
+        }
+        if value_res.is_temp() {
+            self.registers.free(value_res.reg());
+        }
+        result
+    }
+
     /// Compile unary operation
     pub(crate) fn compile_unary_op(
         &mut self,
@@ -23,6 +23,11 @@ impl Compiler {
             return Ok(RegResult::var(var_reg));
         }
 
+        // A data type variant: the value itself or its constructor
+        if let Some((type_name, variant)) = self.variant_in_scope(name) {
+            return self.compile_variant_ref(&type_name, &variant);
+        }
+
         // If not a local variable or upvalue, check if it's a predefined constant
         if let Some(const_value) = constants::get_constant(name) {
             let reg = self.registers.allocate()?;
@@ -31,6 +36,12 @@ impl Compiler {
             return Ok(RegResult::temp(reg));
         }
 
+        if name == "_" {
+            return Err(CompileError::Error(
+                "'_' can only be used as an argument of a call on the right of |>".to_string(),
+            ));
+        }
+
         // Variable not found
         Err(CompileError::Error(format!("Undefined variable: {}", name)))
     }
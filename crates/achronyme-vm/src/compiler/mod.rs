@@ -1,11 +1,11 @@
 //! Bytecode compiler (AST to bytecode)
 
 use crate::builtins::registry::BuiltinRegistry;
-use crate::bytecode::{BytecodeModule, ConstantPool, FunctionPrototype};
-use crate::error::CompileError;
+use crate::bytecode::{BytecodeModule, ConstantPool, FunctionPrototype, TopLevelBinding};
+use crate::error::{CompileError, CompileWarning};
 use crate::opcode::{instruction::*, OpCode};
 use crate::value::Value;
-use achronyme_parser::ast::AstNode;
+use achronyme_parser::ast::{AstNode, VariantDecl};
 use achronyme_parser::type_annotation::TypeAnnotation;
 use achronyme_types::sync::Arc;
 use std::collections::HashMap;
@@ -13,12 +13,15 @@ use std::collections::HashMap;
 // Module structure
 mod constants;
 mod context;
+mod data_types;
 mod expressions;
 mod patterns;
 pub(crate) mod registers;
 mod statements;
 pub(crate) mod symbols;
 
+pub use constants::is_constant;
+
 // Internal imports
 use context::LoopContext;
 use registers::{RegResult, RegisterAllocator};
@@ -51,6 +54,12 @@ pub struct Compiler {
     /// Type registry for storing type aliases
     pub(crate) type_registry: HashMap<String, TypeAnnotation>,
 
+    /// Declared data types (type name -> variants)
+    pub(crate) data_types: HashMap<String, Vec<VariantDecl>>,
+
+    /// Warnings found so far, including those of nested functions
+    pub(crate) warnings: Vec<CompileWarning>,
+
     /// Exported values (name -> register index)
     pub(crate) exported_values: HashMap<String, u8>,
 
@@ -76,6 +85,8 @@ impl Compiler {
             parent: None,
             builtins: Arc::new(crate::builtins::create_builtin_registry()),
             type_registry: HashMap::new(),
+            data_types: HashMap::new(),
+            warnings: Vec::new(),
             exported_values: HashMap::new(),
             exported_types: HashMap::new(),
             exports_reg: None,
@@ -94,9 +105,14 @@ impl Compiler {
             self.exports_reg = Some(exports_reg);
         }
 
-        // Compile all nodes (usually just one Sequence node)
+        // Compile the statements of the top-level Sequence one by one so the
+        // bindings each one introduces can be recorded
+        let statements = nodes.iter().flat_map(|node| match node {
+            AstNode::Sequence { statements } => statements.as_slice(),
+            other => std::slice::from_ref(other),
+        });
         let mut last_res: Option<RegResult> = None;
-        for node in nodes {
+        for node in statements {
             // Check if it's the last statement that returns a value
             // Yield can be both statement and expression, but we treat it as expression
             let is_expression = !matches!(
@@ -106,9 +122,11 @@ impl Compiler {
                     | AstNode::LetDestructuring { .. }
                     | AstNode::MutableDestructuring { .. }
                     | AstNode::Assignment { .. }
+                    | AstNode::CompoundAssignment { .. }
                     | AstNode::Import { .. }
                     | AstNode::Export { .. }
                     | AstNode::TypeAlias { .. }
+                    | AstNode::DataType { .. }
                     | AstNode::Return { .. }
             );
 
@@ -124,7 +142,9 @@ impl Compiler {
                 let res = self.compile_expression(node)?;
                 last_res = Some(res);
             } else {
+                let before = self.symbols.locals();
                 self.compile_statement(node)?;
+                self.record_top_level_bindings(&before);
             }
         }
 
@@ -153,6 +173,31 @@ impl Compiler {
         Ok(module)
     }
 
+    /// Record the variables a top-level statement defined (or shadowed), so
+    /// `save_env` can find them in the main frame's registers
+    fn record_top_level_bindings(&mut self, before: &HashMap<String, u8>) {
+        let defined_at = self.function.code.len();
+        let mut added: Vec<(String, u8)> = self
+            .symbols
+            .locals()
+            .into_iter()
+            .filter(|(name, reg)| before.get(name) != Some(reg))
+            .collect();
+        added.sort();
+        self.function
+            .top_level_bindings
+            .extend(added.into_iter().map(|(name, register)| TopLevelBinding {
+                name,
+                register,
+                defined_at,
+            }));
+    }
+
+    /// Warnings from the last compilation, e.g. non-exhaustive matches
+    pub fn warnings(&self) -> &[CompileWarning] {
+        &self.warnings
+    }
+
     // ===== Helper methods =====
 
     /// Add constant to pool
@@ -318,6 +363,8 @@ impl Compiler {
             TypeAnnotation::String => "String".to_string(),
             TypeAnnotation::Complex => "Complex".to_string(),
             TypeAnnotation::Vector => "Vector".to_string(),
+            // The VM reads the shape back from the annotation
+            TypeAnnotation::Tensor { shape: Some(_), .. } => type_ann.to_string(),
             TypeAnnotation::Tensor { .. } => "Tensor".to_string(),
             TypeAnnotation::Record { .. } => "Record".to_string(),
             TypeAnnotation::Function { .. } => "Function".to_string(),
@@ -328,7 +375,11 @@ impl Compiler {
             TypeAnnotation::Union { .. } => "Any".to_string(), // For now, union types are treated as Any
             TypeAnnotation::Null => "Null".to_string(),
             TypeAnnotation::Any => "Any".to_string(),
-            TypeAnnotation::TypeReference(_) => "Any".to_string(), // Type aliases not yet implemented
+            // Data types are checked by name; type aliases are not yet implemented
+            TypeAnnotation::TypeReference(name) if self.data_types.contains_key(name) => {
+                name.clone()
+            }
+            TypeAnnotation::TypeReference(_) => "Any".to_string(),
         }
     }
 }
@@ -1,11 +1,12 @@
 //! Pattern compilation for destructuring and matching
 
 use crate::compiler::Compiler;
-use crate::error::CompileError;
+use crate::error::{CompileError, CompileWarning};
 use crate::opcode::{instruction::*, OpCode};
 use crate::value::Value;
-use achronyme_parser::ast::{LiteralPattern, Pattern, VectorPatternElement};
+use achronyme_parser::ast::{AstNode, LiteralPattern, MatchArm, Pattern, VectorPatternElement};
 use achronyme_types::sync::shared;
+use std::collections::HashSet;
 
 /// Pattern compilation mode
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -54,6 +55,130 @@ impl Compiler {
             Pattern::Record { fields } => self.compile_record_pattern(fields, target_reg, mode),
 
             Pattern::Type(type_name) => self.compile_type_pattern(type_name, target_reg, mode),
+
+            Pattern::Variant { name, .. } => Err(CompileError::InvalidPattern(format!(
+                "Variant pattern '{}' can only be used in match expressions",
+                name
+            ))),
+        }
+    }
+
+    /// Compile a variant pattern in a match: check the variant, then match
+    /// its fields. Fields always exist, so unlike record patterns there is
+    /// no missing-field check.
+    pub(crate) fn compile_variant_pattern(
+        &mut self,
+        name: &str,
+        fields: &[(String, Pattern, Option<Box<AstNode>>)],
+        target_reg: u8,
+        next_arm_jumps: &mut Vec<usize>,
+    ) -> Result<(), CompileError> {
+        let (type_name, variant) = self
+            .lookup_variant(name)
+            .map(|(type_name, variant)| (type_name.to_string(), variant.clone()))
+            .ok_or_else(|| CompileError::InvalidPattern(format!("Unknown variant '{}'", name)))?;
+        for (field_name, _, default_value) in fields {
+            if !variant.fields.iter().any(|(field, _)| field == field_name) {
+                return Err(CompileError::InvalidPattern(format!(
+                    "Variant '{}' has no field '{}'",
+                    name, field_name
+                )));
+            }
+            if default_value.is_some() {
+                return Err(CompileError::InvalidPattern(format!(
+                    "Field '{}' of variant '{}' always exists and cannot have a default",
+                    field_name, name
+                )));
+            }
+        }
+
+        // MatchType on `Type.Variant` checks the tag
+        let type_idx = self.add_string(format!("{}.{}", type_name, name))?;
+        let match_reg = self.registers.allocate()?;
+        self.emit(encode_abc(
+            OpCode::MatchType.as_u8(),
+            match_reg,
+            target_reg,
+            type_idx as u8,
+        ));
+        next_arm_jumps.push(self.emit_jump_if_false(match_reg, 0));
+        self.registers.free(match_reg);
+
+        if fields.is_empty() {
+            return Ok(());
+        }
+
+        let field_names = fields
+            .iter()
+            .map(|(field_name, _, _)| Value::String(field_name.clone()))
+            .collect();
+        let pattern_idx = self.add_constant(Value::Vector(shared(field_names)))?;
+        let dst_start = self.registers.allocate_many(fields.len())?;
+        self.emit(encode_abc(
+            OpCode::DestructureRec.as_u8(),
+            dst_start,
+            target_reg,
+            pattern_idx as u8,
+        ));
+
+        for (reg_idx, (field_name, pattern, _)) in (dst_start..).zip(fields.iter()) {
+            self.compile_pattern_for_match(pattern, reg_idx, next_arm_jumps)?;
+
+            // Non-binding patterns still bind the field name, as in records
+            let needs_field_binding = matches!(
+                pattern,
+                Pattern::Type(_) | Pattern::Wildcard | Pattern::Literal(_)
+            );
+            if needs_field_binding {
+                self.symbols.define(field_name.clone(), reg_idx)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Warn when a match over a data type's variants can miss some of them.
+    /// An arm covers a variant when it has no guard and none of its field
+    /// patterns can fail; a wildcard or variable arm covers everything.
+    pub(crate) fn check_match_exhaustiveness(&mut self, arms: &[MatchArm]) {
+        let mut type_name: Option<String> = None;
+        let mut covered = HashSet::new();
+
+        for arm in arms {
+            let (name, fields) = match &arm.pattern {
+                Pattern::Variant { name, fields } => (name, fields.as_slice()),
+                Pattern::Variable(name) if self.lookup_variant(name).is_some() => {
+                    (name, [].as_slice())
+                }
+                Pattern::Wildcard | Pattern::Variable(_) if arm.guard.is_none() => return,
+                _ => continue,
+            };
+            let Some((variant_type, _)) = self.lookup_variant(name) else {
+                return;
+            };
+            // Arms over different data types: nothing sensible to report
+            if *type_name.get_or_insert_with(|| variant_type.to_string()) != variant_type {
+                return;
+            }
+            let irrefutable = fields.iter().all(|(_, pattern, _)| match pattern {
+                Pattern::Wildcard => true,
+                Pattern::Variable(name) => self.lookup_variant(name).is_none(),
+                _ => false,
+            });
+            if arm.guard.is_none() && irrefutable {
+                covered.insert(name.as_str());
+            }
+        }
+
+        let Some(type_name) = type_name else { return };
+        let missing: Vec<String> = self.data_types[&type_name]
+            .iter()
+            .filter(|variant| !covered.contains(variant.name.as_str()))
+            .map(|variant| variant.name.clone())
+            .collect();
+        if !missing.is_empty() {
+            self.warnings
+                .push(CompileWarning::NonExhaustiveMatch { type_name, missing });
         }
     }
 
@@ -322,8 +447,7 @@ impl Compiler {
         ));
 
         // Now bind the pattern variables to the extracted values
-        let mut reg_idx = dst_start;
-        for (field_name, pattern, default_value) in fields {
+        for (reg_idx, (field_name, pattern, default_value)) in (dst_start..).zip(fields.iter()) {
             // If there's a default value, handle missing fields
             if let Some(default_expr) = default_value {
                 // Compile the default value expression
@@ -361,8 +485,6 @@ impl Compiler {
                 // Bind the field name to the extracted value
                 self.symbols.define(field_name.clone(), reg_idx)?;
             }
-
-            reg_idx += 1;
         }
 
         Ok(())
@@ -323,6 +323,9 @@ impl Compiler {
                 name,
                 type_definition,
             } => {
+                if let Some(variants) = self.fieldless_variants(name, type_definition)? {
+                    return self.declare_data_type(name, &variants);
+                }
                 // Store the type alias in the compiler's type registry
                 self.type_registry
                     .insert(name.clone(), type_definition.clone());
@@ -330,6 +333,9 @@ impl Compiler {
                 Ok(())
             }
 
+            // Data type - register its variants (constructors compile at their use)
+            AstNode::DataType { name, variants } => self.declare_data_type(name, variants),
+
             // Export - mark values/types for export
             AstNode::Export { items } => {
                 // Check if we're in a module (has exports_reg)
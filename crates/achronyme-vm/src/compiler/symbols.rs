@@ -61,6 +61,11 @@ impl SymbolTable {
         self.symbols.values().any(|&r| r == reg)
     }
 
+    /// Snapshot of the variable → register mapping
+    pub(crate) fn locals(&self) -> HashMap<String, u8> {
+        self.symbols.clone()
+    }
+
     /// Remove a variable from the symbol table
     pub(crate) fn undefine(&mut self, name: &str) {
         self.symbols.remove(name);
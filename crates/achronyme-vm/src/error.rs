@@ -172,3 +172,34 @@ impl fmt::Display for CompileError {
 }
 
 impl std::error::Error for CompileError {}
+
+/// Compiler warnings: the program compiles but is probably wrong
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompileWarning {
+    /// A match on a data type's variants that misses some of them
+    NonExhaustiveMatch {
+        type_name: String,
+        missing: Vec<String>,
+    },
+}
+
+impl fmt::Display for CompileWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompileWarning::NonExhaustiveMatch { type_name, missing } => {
+                let noun = if missing.len() == 1 {
+                    "variant"
+                } else {
+                    "variants"
+                };
+                write!(
+                    f,
+                    "Non-exhaustive match on {}: missing {} {}",
+                    type_name,
+                    noun,
+                    missing.join(", ")
+                )
+            }
+        }
+    }
+}
@@ -37,7 +37,7 @@ pub mod vm;
 // Re-export main types
 pub use bytecode_debug::disassemble_function;
 pub use compiler::Compiler;
-pub use error::{CompileError, VmError};
+pub use error::{CompileError, CompileWarning, VmError};
 pub use opcode::OpCode;
 pub use vm::VM;
 
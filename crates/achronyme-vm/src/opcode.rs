@@ -43,6 +43,8 @@ pub enum OpCode {
     Pow = 15,
     /// Negation: R[A] = -R[B]
     Neg = 16,
+    /// Matrix product: R[A] = R[B] @ R[C]
+    MatMul = 17,
 
     // ===== Comparison =====
     /// Equal: R[A] = R[B] == R[C]
@@ -101,6 +103,12 @@ pub enum OpCode {
     SetFieldMut = 63,
     /// Spread record: spread R[B] into R[A]
     RecordSpread = 64,
+    /// New ADT variant: R[A] = variant K[C] with fields R[B], R[B+1], ...
+    /// K[C] is a vector: [type name, variant name, field names...]
+    NewVariant = 65,
+    /// Get method: R[A] = R[B][K[C]] in call position, where intrinsic
+    /// methods win over DataFrame columns of the same name
+    GetMethod = 66,
 
     // ===== Vectors/Arrays =====
     /// New empty vector: R[A] = []
@@ -259,6 +267,7 @@ impl OpCode {
             14 => Some(OpCode::Mod),
             15 => Some(OpCode::Pow),
             16 => Some(OpCode::Neg),
+            17 => Some(OpCode::MatMul),
             20 => Some(OpCode::Eq),
             21 => Some(OpCode::Ne),
             22 => Some(OpCode::Lt),
@@ -283,6 +292,8 @@ impl OpCode {
             62 => Some(OpCode::SetField),
             63 => Some(OpCode::SetFieldMut),
             64 => Some(OpCode::RecordSpread),
+            65 => Some(OpCode::NewVariant),
+            66 => Some(OpCode::GetMethod),
             70 => Some(OpCode::NewVector),
             71 => Some(OpCode::VecPush),
             72 => Some(OpCode::VecGet),
@@ -362,6 +373,7 @@ impl OpCode {
             OpCode::Mod => "MOD",
             OpCode::Pow => "POW",
             OpCode::Neg => "NEG",
+            OpCode::MatMul => "MATMUL",
             OpCode::Eq => "EQ",
             OpCode::Ne => "NE",
             OpCode::Lt => "LT",
@@ -386,6 +398,8 @@ impl OpCode {
             OpCode::SetField => "SET_FIELD",
             OpCode::SetFieldMut => "SET_FIELD_MUT",
             OpCode::RecordSpread => "RECORD_SPREAD",
+            OpCode::NewVariant => "NEW_VARIANT",
+            OpCode::GetMethod => "GET_METHOD",
             OpCode::NewVector => "NEW_VECTOR",
             OpCode::VecPush => "VEC_PUSH",
             OpCode::VecGet => "VEC_GET",
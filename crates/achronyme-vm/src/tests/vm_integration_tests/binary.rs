@@ -0,0 +1,380 @@
+use super::helpers::{execute, path_literal, temp_path};
+use crate::value::Value;
+use achronyme_types::complex::Complex;
+
+#[test]
+fn test_pack_unpack_big_endian() {
+    let result = execute(r#"unpack(">i16 f32", pack(">i16 f32", [-2, 1.5]))"#).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(-2.0));
+            assert_eq!(vec[1], Value::Number(1.5));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+
+    let result = execute(r#"pack(">u16", [258]).to_vector()"#).unwrap();
+    match result {
+        Value::Vector(v) => {
+            assert_eq!(*v.read(), vec![Value::Number(1.0), Value::Number(2.0)])
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_named_layout_record() {
+    let source = r#"
+        let layout = "<id:u16, temp:f32, pad[2], samples:i8[3]"
+        let data = pack(layout, {id: 7, temp: 20.5, samples: [1, -1, 3]})
+        let reading = data.unpack(layout)
+        [reading.id, reading.temp, sum(reading.samples), len(data), pack_size(layout)]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(7.0));
+            assert_eq!(vec[1], Value::Number(20.5));
+            assert_eq!(vec[2], Value::Number(3.0));
+            assert_eq!(vec[3], Value::Number(11.0));
+            assert_eq!(vec[4], Value::Number(11.0));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_unpack_rest_with_offset() {
+    let result = execute(r#"unpack("<u8[*]", bytes([1, 2, 3]), 1)[0]"#).unwrap();
+    match result {
+        Value::Tensor(t) => assert_eq!(t.data(), &[2.0, 3.0]),
+        _ => panic!("Expected Tensor, got {:?}", result),
+    }
+
+    assert!(execute(r#"unpack("<u32", bytes([1, 2]))"#).is_err());
+}
+
+#[test]
+fn test_unpack_out_of_bounds_is_an_error() {
+    assert!(execute(r#"unpack("u8[*]", bytes("ab"), 10)"#).is_err());
+    assert!(execute(r#"unpack("u8[3]", bytes("ab"), 1)"#).is_err());
+    // The byte count of a huge repeat overflows instead of wrapping
+    assert!(execute(r#"unpack("f64[2305843009213693952]", bytes("ab"))"#).is_err());
+    assert!(execute(r#"unpack("u8[*]", bytes("ab"), 2)"#).is_ok());
+}
+
+#[test]
+fn test_oversized_layouts_are_an_error() {
+    for source in [
+        r#"pack_size("f64[3000000000000000000]")"#,
+        r#"pack_size("u8[18446744073709551615], u8")"#,
+        r#"pack("pad[18446744073709551615]", [])"#,
+        r#"pack("pad[100000000000]", [])"#,
+    ] {
+        let err = execute(source).unwrap_err();
+        assert!(err.contains("layout is larger"), "{}: {}", source, err);
+    }
+    assert!(execute(r#"pack("pad[4]", [])"#).is_ok());
+}
+
+#[test]
+fn test_bytes_value() {
+    let source = r#"
+        let b = bytes("hi!")
+        [len(b), b[0], b.slice(0, 2).to_string(), typeof(b)]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(3.0));
+            assert_eq!(vec[1], Value::Number(104.0));
+            assert_eq!(vec[2], Value::String("hi".to_string()));
+            assert_eq!(vec[3], Value::String("Bytes".to_string()));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_read_write_bytes() {
+    let path = temp_path("raw.bin");
+    let source = format!(
+        r#"
+        await write_bytes("{0}", pack("<u32 f64", [258, 0.25]))
+        let data = await read_bytes("{0}")
+        data.unpack("<u32 f64")
+    "#,
+        path_literal(&path)
+    );
+    let result = execute(&source).unwrap();
+    let raw = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(&raw[..4], &[2, 1, 0, 0]);
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(258.0));
+            assert_eq!(vec[1], Value::Number(0.25));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_npy_round_trip() {
+    let path = temp_path("matrix.npy");
+    let source = format!(
+        r#"
+        save_npy("{0}", [[1, 2, 3], [4, 5, 6]])
+        load_npy("{0}")
+    "#,
+        path_literal(&path)
+    );
+    let result = execute(&source).unwrap();
+    let raw = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(&raw[..8], b"\x93NUMPY\x01\x00");
+    let header_len = u16::from_le_bytes([raw[8], raw[9]]) as usize;
+    assert_eq!((10 + header_len) % 64, 0);
+    let header = std::str::from_utf8(&raw[10..10 + header_len]).unwrap();
+    assert!(header.contains("'descr': '<f8'"));
+    assert!(header.contains("'shape': (2, 3)"));
+
+    match result {
+        Value::Tensor(t) => {
+            assert_eq!(t.shape(), &[2, 3]);
+            assert_eq!(t.data(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        }
+        _ => panic!("Expected Tensor, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_load_npy_fortran_order_big_endian() {
+    let path = temp_path("fortran.npy");
+    let mut header = "{'descr': '>i2', 'fortran_order': True, 'shape': (2, 3), }".to_string();
+    while !(10 + header.len() + 1).is_multiple_of(64) {
+        header.push(' ');
+    }
+    header.push('\n');
+    let mut raw = b"\x93NUMPY\x01\x00".to_vec();
+    raw.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    raw.extend_from_slice(header.as_bytes());
+    // Column-major [[1, 2, 3], [4, 5, 6]]
+    for n in [1i16, 4, 2, 5, 3, 6] {
+        raw.extend_from_slice(&n.to_be_bytes());
+    }
+    std::fs::write(&path, raw).unwrap();
+
+    let result = execute(&format!(r#"load_npy("{}")"#, path_literal(&path))).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    match result {
+        Value::Tensor(t) => {
+            assert_eq!(t.shape(), &[2, 3]);
+            assert_eq!(t.data(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        }
+        _ => panic!("Expected Tensor, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_npy_complex_and_dtype() {
+    let complex_path = temp_path("complex.npy");
+    let f4_path = temp_path("single.npy");
+    let source = format!(
+        r#"
+        save_npy("{0}", [1+2i, 3])
+        save_npy("{1}", [0.5, 2], "f4")
+        [load_npy("{0}"), load_npy("{1}")]
+    "#,
+        path_literal(&complex_path),
+        path_literal(&f4_path)
+    );
+    let result = execute(&source).unwrap();
+    let f4_size = std::fs::metadata(&f4_path).unwrap().len();
+    std::fs::remove_file(&complex_path).ok();
+    std::fs::remove_file(&f4_path).ok();
+
+    assert_eq!(f4_size, 128 + 8);
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            match &vec[0] {
+                Value::ComplexTensor(t) => {
+                    assert_eq!(t.data()[0], Complex::new(1.0, 2.0));
+                    assert_eq!(t.data()[1], Complex::new(3.0, 0.0));
+                }
+                other => panic!("Expected ComplexTensor, got {:?}", other),
+            }
+            match &vec[1] {
+                Value::Tensor(t) => assert_eq!(t.data(), &[0.5, 2.0]),
+                other => panic!("Expected Tensor, got {:?}", other),
+            }
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_npy_keeps_dtype_through_round_trip() {
+    let source_path = temp_path("mask.npy");
+    let copy_path = temp_path("mask_copy.npy");
+    let source = format!(
+        r#"
+        save_npy("{0}", [[1, 0], [255, 7]], "u1")
+        let m = load_npy("{0}", true)
+        save_npy("{1}", m.array, m.dtype)
+        [m.dtype, load_npy("{1}")]
+    "#,
+        path_literal(&source_path),
+        path_literal(&copy_path)
+    );
+    let result = execute(&source).unwrap();
+    let original = std::fs::read(&source_path).unwrap();
+    let copy = std::fs::read(&copy_path).unwrap();
+    std::fs::remove_file(&source_path).ok();
+    std::fs::remove_file(&copy_path).ok();
+
+    assert_eq!(original, copy);
+    assert_eq!(copy.len(), 128 + 4);
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::String("|u1".to_string()));
+            match &vec[1] {
+                Value::Tensor(t) => {
+                    assert_eq!(t.shape(), &[2, 2]);
+                    assert_eq!(t.data(), &[1.0, 0.0, 255.0, 7.0]);
+                }
+                other => panic!("Expected Tensor, got {:?}", other),
+            }
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_load_npy_rejects_mismatched_size() {
+    let path = temp_path("huge.npy");
+    let mut header =
+        "{'descr': '<f8', 'fortran_order': False, 'shape': (4294967296, 4294967296, 2), }"
+            .to_string();
+    while !(10 + header.len() + 1).is_multiple_of(64) {
+        header.push(' ');
+    }
+    header.push('\n');
+    let mut raw = b"\x93NUMPY\x01\x00".to_vec();
+    raw.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    raw.extend_from_slice(header.as_bytes());
+    raw.extend_from_slice(&[0; 16]);
+    std::fs::write(&path, raw).unwrap();
+
+    let result = execute(&format!(r#"load_npy("{}")"#, path_literal(&path)));
+    std::fs::remove_file(&path).ok();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_load_npz_ignores_declared_entry_size() {
+    let path = temp_path("lying.npz");
+    execute(&format!(
+        r#"save_npz("{}", {{x: [1, 2]}}, false)"#,
+        path_literal(&path)
+    ))
+    .unwrap();
+
+    // Claim a ~4 GiB uncompressed size in the local and central headers
+    let mut raw = std::fs::read(&path).unwrap();
+    for (signature, field) in [(b"PK\x03\x04", 22), (b"PK\x01\x02", 24)] {
+        let at = raw.windows(4).position(|w| w == signature).unwrap();
+        raw[at + field..at + field + 4].copy_from_slice(&0xFFFF_FFF0u32.to_le_bytes());
+    }
+    std::fs::write(&path, raw).unwrap();
+
+    let result = execute(&format!(r#"load_npz("{}").x"#, path_literal(&path)));
+    std::fs::remove_file(&path).ok();
+    match result {
+        Ok(Value::Tensor(t)) => assert_eq!(t.data(), &[1.0, 2.0]),
+        other => panic!("Expected Tensor, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_npz_round_trip() {
+    for compressed in [false, true] {
+        let path = temp_path(&format!("arrays_{}.npz", compressed));
+        let source = format!(
+            r#"
+            save_npz("{}", {{x: [1, 2], m: [[1, 2], [3, 4]]}}, {})
+            let z = load_npz("{}")
+            [z.x, z.m]
+        "#,
+            path_literal(&path),
+            compressed,
+            path_literal(&path)
+        );
+        let result = execute(&source).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        match result {
+            Value::Vector(v) => {
+                let vec = v.read();
+                match (&vec[0], &vec[1]) {
+                    (Value::Tensor(x), Value::Tensor(m)) => {
+                        assert_eq!(x.data(), &[1.0, 2.0]);
+                        assert_eq!(m.shape(), &[2, 2]);
+                        assert_eq!(m.data(), &[1.0, 2.0, 3.0, 4.0]);
+                    }
+                    other => panic!("Expected Tensors, got {:?}", other),
+                }
+            }
+            _ => panic!("Expected Vector, got {:?}", result),
+        }
+    }
+}
+
+#[test]
+fn test_npz_keeps_dtypes_through_round_trip() {
+    let source_path = temp_path("typed.npz");
+    let copy_path = temp_path("typed_copy.npz");
+    let source = format!(
+        r#"
+        save_npz("{0}", {{
+            i: {{array: [1, -2, 300000], dtype: "i4"}},
+            f: {{array: [0.5, 1.5], dtype: "f4"}},
+            d: [1, 2]
+        }})
+        save_npz("{1}", load_npz("{0}", true))
+        let z = load_npz("{1}", true)
+        [z.i.dtype, z.f.dtype, z.d.dtype, z.i.array, z.f.array]
+    "#,
+        path_literal(&source_path),
+        path_literal(&copy_path)
+    );
+    let result = execute(&source).unwrap();
+    std::fs::remove_file(&source_path).ok();
+    std::fs::remove_file(&copy_path).ok();
+
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::String("<i4".to_string()));
+            assert_eq!(vec[1], Value::String("<f4".to_string()));
+            assert_eq!(vec[2], Value::String("<f8".to_string()));
+            match (&vec[3], &vec[4]) {
+                (Value::Tensor(i), Value::Tensor(f)) => {
+                    assert_eq!(i.data(), &[1.0, -2.0, 300000.0]);
+                    assert_eq!(f.data(), &[0.5, 1.5]);
+                }
+                other => panic!("Expected Tensors, got {:?}", other),
+            }
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
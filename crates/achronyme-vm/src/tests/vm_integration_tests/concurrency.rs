@@ -1,4 +1,4 @@
-use super::helpers::execute_async;
+use super::helpers::{execute, execute_async};
 use crate::value::Value;
 
 #[test]
@@ -67,3 +67,128 @@ fn test_async_mutex() {
         assert_eq!(result, Value::Number(100.0));
     });
 }
+
+#[test]
+fn test_bounded_channel_back_pressure() {
+    let source = r#"
+        let [tx, rx] = channel(2)
+        let first = tx.try_send(1)
+        let second = tx.try_send(2)
+        let full = tx.try_send(3)
+        let got = rx.try_recv()
+        let again = tx.try_send(4)
+        tx.close()
+        let rest = []
+        for (msg in rx) {
+            push(rest, msg)
+        }
+        [first, second, full, got, again, rest, rx.try_recv(), await tx.send(5)]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Boolean(true));
+            assert_eq!(vec[1], Value::Boolean(true));
+            assert_eq!(vec[2], Value::Boolean(false));
+            assert_eq!(vec[3], Value::Number(1.0));
+            assert_eq!(vec[4], Value::Boolean(true));
+            match &vec[5] {
+                Value::Vector(rest) => {
+                    assert_eq!(*rest.read(), vec![Value::Number(2.0), Value::Number(4.0)])
+                }
+                other => panic!("Expected Vector, got {:?}", other),
+            }
+            assert_eq!(vec[6], Value::Null);
+            assert!(matches!(vec[7], Value::Error { .. }));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_producer_consumer_iteration() {
+    let source = r#"
+        let [tx, rx] = channel(1)
+        spawn(async () => do {
+            for (i in [1, 2, 3, 4]) {
+                await tx.send(i)
+            }
+            tx.close()
+        })
+        mut total = 0
+        for (msg in rx) {
+            total = total + msg
+        }
+        total
+    "#;
+    assert_eq!(execute(source).unwrap(), Value::Number(10.0));
+}
+
+#[test]
+fn test_broadcast_and_watch() {
+    let source = r#"
+        let [tx, rx1] = broadcast(4)
+        let rx2 = tx.subscribe()
+        await tx.send("hello")
+        let both = [await rx1.recv(), await rx2.recv()]
+
+        let [state_tx, state_rx] = watch("idle")
+        let initial = state_rx.get()
+        await state_tx.send("busy")
+        await state_tx.send("done")
+        let latest = await state_rx.recv()
+        [both, initial, latest, state_rx.try_recv()]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            match &vec[0] {
+                Value::Vector(both) => assert_eq!(
+                    *both.read(),
+                    vec![
+                        Value::String("hello".to_string()),
+                        Value::String("hello".to_string())
+                    ]
+                ),
+                other => panic!("Expected Vector, got {:?}", other),
+            }
+            assert_eq!(vec[1], Value::String("idle".to_string()));
+            assert_eq!(vec[2], Value::String("done".to_string()));
+            assert_eq!(vec[3], Value::Null);
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_select() {
+    let source = r#"
+        let [tx1, rx1] = channel()
+        let [tx2, rx2] = channel()
+        let idle = await select([rx1, rx2], "nothing")
+        spawn(async () => do {
+            await sleep(5)
+            await tx2.send("second")
+        })
+        let picked = await select([rx1, rx2, sleep(1000)])
+        let timer = await select([rx1, sleep(5)])
+        await tx1.send("later")
+        [idle.index, idle.value, picked.index, picked.value, timer.index, rx1.try_recv()]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Null);
+            assert_eq!(vec[1], Value::String("nothing".to_string()));
+            assert_eq!(vec[2], Value::Number(1.0));
+            assert_eq!(vec[3], Value::String("second".to_string()));
+            assert_eq!(vec[4], Value::Number(1.0));
+            // The losing receiver case did not swallow a message
+            assert_eq!(vec[5], Value::String("later".to_string()));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
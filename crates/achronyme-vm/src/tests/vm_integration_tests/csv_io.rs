@@ -0,0 +1,209 @@
+use super::helpers::{execute, path_literal, temp_path};
+use crate::builtins::csv_io::vm_write_csv;
+use crate::value::Value;
+use crate::vm::VM;
+use achronyme_types::tensor::RealTensor;
+
+#[test]
+fn test_read_csv_infers_tensor_columns() {
+    let path = temp_path("read.csv");
+    std::fs::write(&path, "# exported\nid;name;score\n007;a;1.5\n8;b;\n9;c;3\n").unwrap();
+
+    let source = format!(
+        r#"
+        let df = read_csv("{}", {{delimiter: ";", skip: 1, types: {{id: "string"}}}})
+        [df.score, df.id, typeof(df.score)]
+    "#,
+        path_literal(&path)
+    );
+    let result = execute(&source).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            match &vec[0] {
+                Value::Tensor(t) => {
+                    assert_eq!(t.data()[0], 1.5);
+                    assert!(t.data()[1].is_nan());
+                    assert_eq!(t.data()[2], 3.0);
+                }
+                other => panic!("Expected Tensor, got {:?}", other),
+            }
+            match &vec[1] {
+                Value::Vector(ids) => {
+                    assert_eq!(ids.read()[0], Value::String("007".to_string()))
+                }
+                other => panic!("Expected Vector, got {:?}", other),
+            }
+            assert_eq!(vec[2], Value::String("Tensor".to_string()));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_read_csv_falls_back_to_text_losslessly() {
+    let path = temp_path("fallback.csv");
+    std::fs::write(&path, "code\n1.50\n2\nx\n").unwrap();
+
+    let source = format!(r#"read_csv("{}").code"#, path_literal(&path));
+    let result = execute(&source).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::String("1.50".to_string()));
+            assert_eq!(vec[1], Value::String("2".to_string()));
+            assert_eq!(vec[2], Value::String("x".to_string()));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_write_csv_records_round_trip() {
+    let path = temp_path("records.csv");
+    let source = format!(
+        r#"
+        let p = "{}"
+        write_csv(p, [{{b: "x", a: 1}}, {{b: "y", a: 2}}])
+        let df = read_csv(p)
+        [df.columns(), sum(df.a)]
+    "#,
+        path_literal(&path)
+    );
+    let result = execute(&source).unwrap();
+    let text = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(text, "a,b\n1,x\n2,y\n");
+    match result {
+        Value::Vector(v) => assert_eq!(v.read()[1], Value::Number(3.0)),
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_write_csv_vector_rows() {
+    let path = temp_path("rows.csv");
+    let source = format!(
+        r#"write_csv("{}", [["a", 1], ["b", true]], {{delimiter: "\t"}})"#,
+        path_literal(&path)
+    );
+    execute(&source).unwrap();
+    let text = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(text, "a\t1\nb\ttrue\n");
+}
+
+#[test]
+fn test_write_csv_matrix() {
+    let path = temp_path("matrix.csv");
+    let matrix = RealTensor::new(vec![1.0, 2.0, 3.0, f64::NAN], vec![2, 2]).unwrap();
+    let args = [Value::String(path_literal(&path)), Value::Tensor(matrix)];
+    vm_write_csv(&mut VM::new(), &args).unwrap();
+    let text = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(text, "1,2\n3,\n");
+}
+
+#[test]
+fn test_csv_rows_raises_on_bad_field() {
+    let path = temp_path("rows_bad.csv");
+    std::fs::write(&path, "x\n1\nabc\n").unwrap();
+
+    let source = format!(
+        r#"
+        mut total = 0
+        for (row in csv_rows("{}", {{types: {{x: "number"}}}})) {{
+            total = total + row.x
+        }}
+        total
+    "#,
+        path_literal(&path)
+    );
+    let result = execute(&source);
+    std::fs::remove_file(&path).ok();
+
+    let err = result.unwrap_err();
+    assert!(err.contains("csv_rows()"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_write_csv_uses_every_record_field() {
+    let path = temp_path("union.csv");
+    let source = format!(
+        r#"write_csv("{}", [{{a: 1}}, {{a: 2, b: 3}}])"#,
+        path_literal(&path)
+    );
+    execute(&source).unwrap();
+    let written = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(written, "a,b\n1,\n2,3\n");
+}
+
+#[test]
+fn test_write_csv_rejects_read_options() {
+    let path = temp_path("read_options.csv");
+    let source = format!(
+        r#"write_csv("{}", [[1, 2]], {{skip: 1}})"#,
+        path_literal(&path)
+    );
+    let err = execute(&source).unwrap_err();
+
+    assert!(!path.exists());
+    assert!(
+        err.contains("only applies when reading"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn test_csv_rows_in_for_loop() {
+    let path = temp_path("rows_iter.csv");
+    std::fs::write(&path, "x,label\n1,a\n2,b\n3,c\n").unwrap();
+
+    let source = format!(
+        r#"
+        mut total = 0
+        mut labels = ""
+        for (row in csv_rows("{}")) {{
+            total = total + row.x
+            labels = labels + row.label
+        }}
+        [total, labels]
+    "#,
+        path_literal(&path)
+    );
+    let result = execute(&source).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(6.0));
+            assert_eq!(vec[1], Value::String("abc".to_string()));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_for_loop_over_dataframe_rows() {
+    let source = r#"
+        let df = dataframe({x: [1, 2, 3], y: [10, 20, 30]})
+        mut total = 0
+        for (row in df) {
+            total = total + row.x * row.y
+        }
+        total
+    "#;
+    let result = execute(source).unwrap();
+    assert_eq!(result, Value::Number(140.0));
+}
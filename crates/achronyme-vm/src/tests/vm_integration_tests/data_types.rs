@@ -0,0 +1,227 @@
+use super::helpers::execute;
+use crate::compiler::Compiler;
+use crate::error::CompileWarning;
+use crate::value::Value;
+
+const SHAPE: &str = r#"
+    type Shape = Circle { r: Number } | Rect { w: Number, h: Number } | Empty
+"#;
+
+fn run(body: &str) -> Result<Value, String> {
+    execute(&format!("{}\n{}", SHAPE, body))
+}
+
+fn warnings(body: &str) -> Vec<CompileWarning> {
+    let ast = achronyme_parser::parse(&format!("{}\n{}", SHAPE, body)).unwrap();
+    let mut compiler = Compiler::new("<test>".to_string());
+    compiler.compile(&ast).unwrap();
+    compiler.warnings().to_vec()
+}
+
+#[test]
+fn test_variant_construction_and_fields() {
+    let result = run("let s = Rect(2, 3)\ns.w * s.h").unwrap();
+    assert_eq!(result, Value::Number(6.0));
+}
+
+#[test]
+fn test_variant_typeof_and_equality() {
+    let result =
+        run("[typeof(Circle(1)), typeof(Empty), Circle(1) == Circle(1), Circle(1) == Circle(2)]");
+    let Value::Vector(items) = result.unwrap() else {
+        panic!("expected a vector");
+    };
+    assert_eq!(
+        *items.read(),
+        [
+            Value::String("Shape".to_string()),
+            Value::String("Shape".to_string()),
+            Value::Boolean(true),
+            Value::Boolean(false)
+        ]
+    );
+}
+
+#[test]
+fn test_variant_arity_error() {
+    let err = run("Rect(1)").unwrap_err();
+    assert!(err.contains("Rect expects 2 arguments, got 1"), "{}", err);
+}
+
+#[test]
+fn test_variant_field_type_assert() {
+    let err = run(r#"Circle("big")"#).unwrap_err();
+    assert!(err.contains("expected Number"), "{}", err);
+}
+
+#[test]
+fn test_variant_fields_are_immutable() {
+    let err = run("let s = Circle(1)\ns.r = 2").unwrap_err();
+    assert!(err.contains("variant fields are immutable"), "{}", err);
+}
+
+#[test]
+fn test_data_type_annotation() {
+    assert_eq!(
+        run("let s: Shape = Empty\ntypeof(s)").unwrap(),
+        Value::String("Shape".to_string())
+    );
+    let err = run("let s: Shape = 1").unwrap_err();
+    assert!(err.contains("expected Shape"), "{}", err);
+}
+
+#[test]
+fn test_match_on_variants() {
+    let source = r#"
+        let area = s => match s {
+            Circle { r } => 3 * r ^ 2,
+            Rect { w, h } if (w == h) => w ^ 2,
+            Rect { w: width, h } => width * h,
+            Empty => 0
+        }
+        [area(Circle(2)), area(Rect(2, 3)), area(Rect(3, 3)), area(Empty)]
+    "#;
+    let Value::Vector(items) = run(source).unwrap() else {
+        panic!("expected a vector");
+    };
+    assert_eq!(
+        *items.read(),
+        [
+            Value::Number(12.0),
+            Value::Number(6.0),
+            Value::Number(9.0),
+            Value::Number(0.0)
+        ]
+    );
+}
+
+#[test]
+fn test_match_nested_variant_patterns() {
+    let source = r#"
+        type Tree = Leaf | Node { left: Tree, value: Number, right: Tree }
+        let describe = t => match t {
+            Node { left: Leaf, right: Leaf, value } => value,
+            Node { value: 0 } => -1,
+            Node { left } => rec(left),
+            Leaf => 0
+        }
+        [describe(Node(Leaf, 5, Leaf)), describe(Node(Leaf, 0, Node(Leaf, 1, Leaf))), describe(Node(Node(Leaf, 7, Leaf), 1, Leaf))]
+    "#;
+    let Value::Vector(items) = execute(source).unwrap() else {
+        panic!("expected a vector");
+    };
+    assert_eq!(
+        *items.read(),
+        [Value::Number(5.0), Value::Number(-1.0), Value::Number(7.0)]
+    );
+}
+
+#[test]
+fn test_constructor_as_function() {
+    let result = run("map(s => s.r, map(Circle, [1, 2, 3]))").unwrap();
+    let Value::Vector(items) = result else {
+        panic!("expected a vector");
+    };
+    assert_eq!(
+        *items.read(),
+        [Value::Number(1.0), Value::Number(2.0), Value::Number(3.0)]
+    );
+}
+
+#[test]
+fn test_variable_shadows_constructor() {
+    assert_eq!(
+        run("let Circle = r => r * 10\nCircle(2)").unwrap(),
+        Value::Number(20.0)
+    );
+}
+
+#[test]
+fn test_unknown_field_in_variant_pattern() {
+    let err = run("match Circle(1) { Circle { radius } => radius, _ => 0 }").unwrap_err();
+    assert!(err.contains("radius"), "{}", err);
+}
+
+#[test]
+fn test_duplicate_variant_across_types() {
+    let err = run("type Other = Circle { d: Number } | Square { s: Number }").unwrap_err();
+    assert!(err.contains("already declared by type 'Shape'"), "{}", err);
+}
+
+#[test]
+fn test_non_exhaustive_match_warning() {
+    let found = warnings(
+        r#"
+        let f = s => match s {
+            Circle { r: 1 } => 1,
+            Rect { w, h } if (w > h) => 2,
+            Rect { w } => w
+        }
+    "#,
+    );
+    assert_eq!(
+        found,
+        [CompileWarning::NonExhaustiveMatch {
+            type_name: "Shape".to_string(),
+            missing: vec!["Circle".to_string(), "Empty".to_string()],
+        }]
+    );
+    assert_eq!(
+        found[0].to_string(),
+        "Non-exhaustive match on Shape: missing variants Circle, Empty"
+    );
+}
+
+#[test]
+fn test_exhaustive_match_has_no_warning() {
+    assert!(warnings("match Empty { Circle { r } => r, Rect { w } => w, Empty => 0 }").is_empty());
+    assert!(warnings("match Empty { Circle { r } => r, _ => 0 }").is_empty());
+    assert!(warnings("match Empty { Empty => 0, other => 1 }").is_empty());
+}
+
+#[test]
+fn test_union_of_unknown_names_declares_variants() {
+    let source = r#"
+        type Color = Red | Green | Blue
+        let c: Color = Green
+        let name = match c { Red => "red", Green => "green", Blue => "blue" }
+        [name, typeof(c), c == Green, c == Blue]
+    "#;
+    match execute(source).unwrap() {
+        Value::Vector(v) => assert_eq!(
+            *v.read(),
+            vec![
+                Value::String("green".to_string()),
+                Value::String("Color".to_string()),
+                Value::Boolean(true),
+                Value::Boolean(false)
+            ]
+        ),
+        other => panic!("Expected Vector, got {:?}", other),
+    }
+
+    assert_eq!(
+        warnings("type Color = Red | Green\nmatch Red { Red => 1 }"),
+        [CompileWarning::NonExhaustiveMatch {
+            type_name: "Color".to_string(),
+            missing: vec!["Green".to_string()],
+        }]
+    );
+
+    let err = execute("type Color = Red | Red").unwrap_err();
+    assert!(err.contains("Duplicate variant 'Red'"), "{}", err);
+}
+
+#[test]
+fn test_union_of_known_types_stays_an_alias() {
+    let source = r#"
+        type P = { x: Number }
+        type Q = { y: Number }
+        type PQ = P | Q
+        let v: PQ = { y: 2 }
+        v.y
+    "#;
+    assert_eq!(execute(source).unwrap(), Value::Number(2.0));
+    let err = execute("type P = { x: Number }\ntype PQ = P | Other\nOther").unwrap_err();
+    assert!(err.contains("Undefined variable: Other"), "{}", err);
+}
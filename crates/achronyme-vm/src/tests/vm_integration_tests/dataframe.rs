@@ -0,0 +1,183 @@
+use super::helpers::execute;
+use crate::value::Value;
+
+#[test]
+fn test_dataframe_column_access() {
+    let source = r#"
+        let df = dataframe({city: ["a", "b", "a"], price: [3, 1, 5]})
+        [sum(df.price), len(df), typeof(df.price)]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(9.0));
+            assert_eq!(vec[1], Value::Number(3.0));
+            assert_eq!(vec[2], Value::String("Tensor".to_string()));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_dataframe_unknown_column_lists_columns() {
+    let err = execute(r#"dataframe({city: ["a"], price: [3]}).no_such_col"#).unwrap_err();
+    assert!(
+        err.contains("Unknown column 'no_such_col' (columns: city, price)"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_dataframe_methods_win_over_same_named_columns() {
+    let source = r#"
+        let df = dataframe({head: [1, 2, 3], filter: [4, 5, 6]})
+        let top = df.head(1).filter(row => row.head > 0)
+        [len(top), sum(df.head), sum(df.filter)]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(1.0));
+            assert_eq!(vec[1], Value::Number(6.0));
+            assert_eq!(vec[2], Value::Number(15.0));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_dataframe_filter_and_sort() {
+    let source = r#"
+        let df = dataframe([
+            {name: "x", score: 10},
+            {name: "y", score: 30},
+            {name: "z", score: 20}
+        ])
+        let top = df.filter(row => row.score > 15).sort_by("score", true)
+        top.name
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec.len(), 2);
+            assert_eq!(vec[0], Value::String("y".to_string()));
+            assert_eq!(vec[1], Value::String("z".to_string()));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_dataframe_filter_uses_hof_truthiness() {
+    // Empty strings are falsy, as in filter() over a vector
+    let source = r#"
+        let df = dataframe({name: ["x", "", "z"]})
+        [df.filter(row => row.name).name, filter(s => s, ["x", "", "z"])]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            let kept = ["x", "z"].map(|s| Value::String(s.to_string()));
+            for names in vec.iter() {
+                match names {
+                    Value::Vector(names) => assert_eq!(*names.read(), kept),
+                    other => panic!("Expected Vector, got {:?}", other),
+                }
+            }
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_dataframe_group_by_agg() {
+    let source = r#"
+        let df = dataframe({city: ["a", "b", "a"], price: [3, 1, 5]})
+        let out = df.group_by("city").agg({price: "mean"})
+        out.price
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Tensor(t) => assert_eq!(t.data(), &[4.0, 1.0]),
+        _ => panic!("Expected Tensor, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_dataframe_agg_with_function() {
+    let source = r#"
+        let df = dataframe({g: [1, 1, 2], v: [2, 3, 4]})
+        let out = df.group_by("g").agg({v: col => reduce((a, b) => a * b, 1, col)})
+        out.v
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Tensor(t) => assert_eq!(t.data(), &[6.0, 4.0]),
+        _ => panic!("Expected Tensor, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_dataframe_join() {
+    let source = r#"
+        let sales = dataframe({id: [1, 2, 3], amount: [10, 20, 30]})
+        let names = dataframe({id: [1, 3], name: ["one", "three"]})
+        let joined = sales.join(names, "id")
+        [joined.nrows(), joined.columns()]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(2.0));
+            match &vec[1] {
+                Value::Vector(cols) => assert_eq!(cols.read().len(), 3),
+                other => panic!("Expected Vector, got {:?}", other),
+            }
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_dataframe_csv_round_trip() {
+    let source = r#"
+        let df = df_from_csv("x,label\n1,a\n2,b\n")
+        let text = df.select(["label", "x"]).to_csv()
+        text
+    "#;
+    let result = execute(source).unwrap();
+    assert_eq!(result, Value::String("label,x\na,1\nb,2\n".to_string()));
+}
+
+#[test]
+fn test_dataframe_malformed_csv_error() {
+    let err = execute(r#"df_from_csv("a,b\n1,2\n3\n")"#).unwrap_err();
+    assert!(
+        err.contains("df_from_csv(): CSV error: record 2 (line: 3, byte: 8)"),
+        "{}",
+        err
+    );
+    assert!(!err.contains("CSV error: CSV error"), "{}", err);
+}
+
+#[test]
+fn test_dataframe_from_json() {
+    let source = r#"
+        let df = df_from_json("[{\"a\": 1, \"b\": \"x\"}, {\"a\": 2, \"b\": \"y\"}]")
+        df.describe().a
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Tensor(t) => {
+            assert_eq!(t.data()[0], 2.0); // count
+            assert_eq!(t.data()[1], 1.5); // mean
+        }
+        _ => panic!("Expected Tensor, got {:?}", result),
+    }
+}
@@ -0,0 +1,167 @@
+use super::helpers::execute;
+use crate::builtins::encoding::{vm_json_parse, vm_json_stringify};
+use crate::value::Value;
+use crate::vm::VM;
+use achronyme_types::complex::Complex;
+use achronyme_types::sync::shared;
+use achronyme_types::tensor::RealTensor;
+use std::collections::HashMap;
+
+fn options(entries: &[(&str, Value)]) -> Value {
+    let map: HashMap<String, Value> = entries
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect();
+    Value::Record(shared(map))
+}
+
+fn matrix() -> Value {
+    Value::Tensor(RealTensor::new(vec![1.0, 2.5, f64::NAN, 4.0], vec![2, 2]).unwrap())
+}
+
+#[test]
+fn test_json_stringify_tensor_as_nested_arrays() {
+    let mut vm = VM::new();
+    let result = vm_json_stringify(&mut vm, &[matrix()]).unwrap();
+    assert_eq!(result, Value::String("[[1.0,2.5],[null,4.0]]".to_string()));
+}
+
+#[test]
+fn test_json_tagged_tensor_round_trip() {
+    let mut vm = VM::new();
+    let opts = options(&[
+        ("tensors", Value::String("tagged".to_string())),
+        ("special_floats", Value::String("string".to_string())),
+    ]);
+    let text = vm_json_stringify(&mut vm, &[matrix(), opts]).unwrap();
+    let revive = options(&[("revive", Value::Boolean(true))]);
+    let result = vm_json_parse(&mut vm, &[text, revive]).unwrap();
+
+    match result {
+        Value::Tensor(t) => {
+            assert_eq!(t.shape(), &[2, 2]);
+            assert_eq!(t.data()[1], 2.5);
+            assert!(t.data()[2].is_nan());
+        }
+        _ => panic!("Expected Tensor, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_json_revive_keeps_plain_strings() {
+    let result = execute(
+        r#"
+        let r = json_parse("{\"note\": \"NaN\", \"c\": {\"$type\": \"Complex\", \"re\": \"Infinity\", \"im\": 0}}", {revive: true})
+        [r.note, r.c]
+    "#,
+    )
+    .unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::String("NaN".to_string()));
+            assert_eq!(vec[1], Value::Complex(Complex::new(f64::INFINITY, 0.0)));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_json_revive_rejects_invalid_shapes() {
+    for shape in ["[-1]", "[1.5]", "[\"NaN\"]"] {
+        let source = format!(
+            r#"json_parse("{{\"$type\": \"Tensor\", \"shape\": {}, \"data\": []}}", {{revive: true}})"#,
+            shape.replace('"', "\\\"")
+        );
+        let err = execute(&source).unwrap_err();
+        assert!(err.contains("non-negative integers"), "{}: {}", shape, err);
+    }
+}
+
+#[test]
+fn test_json_revive_rejects_shapes_that_do_not_match_data() {
+    let cases = [
+        (
+            r#"{\"$type\": \"Tensor\", \"shape\": [4294967296, 4294967296, 2], \"data\": [1]}"#,
+            "too large",
+        ),
+        (
+            r#"{\"$type\": \"Tensor\", \"shape\": [2, 2], \"data\": [1, 2, 3]}"#,
+            "needs 4 elements, got 3",
+        ),
+        (
+            r#"{\"$type\": \"ComplexTensor\", \"shape\": [4294967296, 4294967296, 2], \"re\": [1], \"im\": [0]}"#,
+            "too large",
+        ),
+        (
+            r#"{\"$type\": \"ComplexTensor\", \"shape\": [3], \"re\": [1], \"im\": [0]}"#,
+            "needs 3 elements, got 1",
+        ),
+    ];
+    for (json, expected) in cases {
+        let source = format!(r#"json_parse("{}", {{revive: true}})"#, json);
+        let err = execute(&source).unwrap_err();
+        assert!(err.contains(expected), "{}: {}", json, err);
+    }
+}
+
+#[test]
+fn test_json_complex_round_trip() {
+    let result = execute(r#"json_parse(json_stringify([3+4i, 1]), {revive: true})"#).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Complex(Complex::new(3.0, 4.0)));
+            assert_eq!(vec[1], Value::Number(1.0));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+
+    // Without revive the tagged object stays a record
+    let result = execute(r#"json_parse(json_stringify(3+4i)).re"#).unwrap();
+    assert_eq!(result, Value::Number(3.0));
+}
+
+#[test]
+fn test_json_special_floats() {
+    let mut vm = VM::new();
+    let values = Value::Vector(shared(vec![
+        Value::Number(f64::NAN),
+        Value::Number(f64::NEG_INFINITY),
+    ]));
+
+    let text = vm_json_stringify(&mut vm, std::slice::from_ref(&values)).unwrap();
+    assert_eq!(text, Value::String("[null,null]".to_string()));
+
+    let opts = options(&[("special_floats", Value::String("string".to_string()))]);
+    let text = vm_json_stringify(&mut vm, &[values.clone(), opts]).unwrap();
+    assert_eq!(text, Value::String(r#"["NaN","-Infinity"]"#.to_string()));
+
+    let opts = options(&[("special_floats", Value::String("error".to_string()))]);
+    assert!(vm_json_stringify(&mut vm, &[values, opts]).is_err());
+}
+
+#[test]
+fn test_json_stringify_indent() {
+    let result = execute(r#"json_stringify({a: [1]}, {indent: 4})"#).unwrap();
+    assert_eq!(
+        result,
+        Value::String("{\n    \"a\": [\n        1.0\n    ]\n}".to_string())
+    );
+}
+
+#[test]
+fn test_json_stringify_writes_functions_as_text() {
+    let result = execute(r#"typeof(json_parse(json_stringify({f: x => x})).f)"#).unwrap();
+    assert_eq!(result, Value::String("String".to_string()));
+}
+
+#[test]
+fn test_json_stringify_reports_mistyped_special_floats() {
+    let err = execute(r#"json_stringify(1, {special_floats: true})"#).unwrap_err();
+    assert!(
+        err.contains("invalid value for option 'special_floats'"),
+        "{}",
+        err
+    );
+}
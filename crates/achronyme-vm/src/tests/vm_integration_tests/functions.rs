@@ -105,3 +105,123 @@ fn test_lambda_with_multiple_params() {
     let result = execute(source).unwrap();
     assert_eq!(result, Value::Number(6.0));
 }
+
+#[test]
+fn test_callback_with_nested_calls() {
+    // A builtin-invoked callback must return its own value, not that of the
+    // first function it calls
+    let source = r#"
+        let g = (x) => x * 10
+        let h = (x) => do {
+            if (x > 100) { 1 }
+        }
+        let f = (x) => do {
+            g(x)
+            h(x)
+            x + 1
+        }
+        let r = map(f, [1, 2])
+        r[0] + r[1]
+    "#;
+    let result = execute(source).unwrap();
+    assert_eq!(result, Value::Number(5.0));
+}
+
+#[test]
+fn test_pipe_passes_collection_to_higher_order_builtins() {
+    let source = r#"
+        [1, 2, 3, 4, 5]
+            |> map(x => x * 10)
+            |> filter(x => x > 15)
+            |> reduce((acc, x) => acc + x, 0)
+    "#;
+    let result = execute(source).unwrap();
+    assert_eq!(result, Value::Number(140.0));
+}
+
+#[test]
+fn test_pipe_passes_first_argument() {
+    let source = r#"
+        let scale = (x, factor = 10) => x * factor
+        [
+            {a: 1} |> json_stringify(true),
+            {a: 1} |> json_stringify(),
+            2.5 |> round(),
+            [3, 1, 2] |> sort() |> json_stringify,
+            4 |> scale(2),
+            4 |> scale()
+        ]
+    "#;
+    let Value::Vector(items) = execute(source).unwrap() else {
+        panic!("expected a vector");
+    };
+    assert_eq!(
+        *items.read(),
+        [
+            Value::String("{\n  \"a\": 1.0\n}".to_string()),
+            Value::String("{\"a\":1.0}".to_string()),
+            Value::Number(3.0),
+            Value::String("[1.0,2.0,3.0]".to_string()),
+            Value::Number(8.0),
+            Value::Number(40.0)
+        ]
+    );
+}
+
+#[test]
+fn test_pipe_placeholders() {
+    let source = r#"
+        let add = (a, b) => a + b
+        [2 |> pow(_, 10), 3 |> add(_, _), 1..4 |> sum]
+    "#;
+    let Value::Vector(items) = execute(source).unwrap() else {
+        panic!("expected a vector");
+    };
+    assert_eq!(
+        *items.read(),
+        [
+            Value::Number(1024.0),
+            Value::Number(6.0),
+            Value::Number(6.0)
+        ]
+    );
+}
+
+#[test]
+fn test_pipe_into_expressions() {
+    // Lambdas, method calls and nested pipes
+    let source = r#"
+        let counter = { step: 2, bump: (x, by) => x + by }
+        let double = x => x * 2
+        let a = 4 |> (x => x + 1) |> double
+        let b = 1 |> counter.bump(counter.step)
+        let c = [1, 2] |> map(y => y |> double)
+        [a, b, c[1]]
+    "#;
+    let Value::Vector(items) = execute(source).unwrap() else {
+        panic!("expected a vector");
+    };
+    assert_eq!(
+        *items.read(),
+        [Value::Number(10.0), Value::Number(3.0), Value::Number(4.0)]
+    );
+}
+
+#[test]
+fn test_pipe_binds_loosest() {
+    // (2 + 3) |> add(_, _), then (1..=3) |> sum
+    let source = r#"
+        let add = (a, b) => a + b
+        [2 + 3 |> add(_, _), 1..=3 |> sum()]
+    "#;
+    let Value::Vector(items) = execute(source).unwrap() else {
+        panic!("expected a vector");
+    };
+    assert_eq!(*items.read(), [Value::Number(10.0), Value::Number(6.0)]);
+}
+
+#[test]
+fn test_placeholder_outside_pipe() {
+    let err = execute("let f = x => x\nf(_)").unwrap_err();
+    assert!(err.contains("'_' can only be used"), "{}", err);
+}
@@ -1,5 +1,7 @@
 use super::helpers::execute;
+use crate::compiler::Compiler;
 use crate::value::Value;
+use crate::vm::VM;
 
 // ============================================================================
 // WEEK 17: GRADUAL TYPE SYSTEM TESTS
@@ -93,3 +95,42 @@ fn test_type_assert_boolean_fails() {
     assert!(err_msg.contains("expected Boolean"));
     assert!(err_msg.contains("got Number"));
 }
+
+#[test]
+fn test_tensor_shape_assertion() {
+    let source = r#"
+        let square = (m: Tensor<n, n>) => m
+        let t: Tensor<Number, [2, _]> = square(reshape([1, 2, 3, 4], [2, 2]))
+        t
+    "#;
+    assert!(matches!(execute(source).unwrap(), Value::Tensor(_)));
+
+    let source = r#"
+        let square = (m: Tensor<n, n>) => m
+        square(reshape([1, 2, 3, 4, 5, 6], [2, 3]))
+    "#;
+    let err_msg = execute(source).unwrap_err();
+    assert!(err_msg.contains("expected Tensor<Number, [n, n]>, got Tensor<Number, [2, 3]>"));
+}
+
+#[test]
+fn test_tensor_annotations_are_parsed_once() {
+    let source = r#"
+        let square = (m: Tensor<n, n>) => m
+        map((k) => square(reshape([1, 2, 3, 4], [2, 2])), [1, 2, 3])
+    "#;
+    let ast = achronyme_parser::parse(source).unwrap();
+    let module = Compiler::new("<test>".to_string()).compile(&ast).unwrap();
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let mut vm = VM::new();
+    tokio::task::LocalSet::new()
+        .block_on(&rt, vm.execute(module))
+        .unwrap();
+
+    let cache = vm.tensor_types.read();
+    assert_eq!(cache.len(), 1);
+    assert!(cache.values().all(Option::is_some));
+}
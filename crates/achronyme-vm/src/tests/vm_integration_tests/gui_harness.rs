@@ -0,0 +1,155 @@
+use super::helpers::execute;
+use crate::value::Value;
+
+fn snapshot(source: &str) -> String {
+    match execute(source) {
+        Ok(Value::String(s)) => s,
+        other => panic!("Expected snapshot String, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_gui_test_click_updates_state() {
+    let source = r#"
+        let count = signal(0)
+        let render = (ui) => do {
+            ui_label("Count: " + str(count.value), "")
+            if (ui_button("Increment", "")) {
+                count.set(count.value + 1)
+            }
+        }
+        gui_test(render, [{click: "Increment"}, {click: "Increment"}])
+    "#;
+    assert_eq!(
+        snapshot(source),
+        "label \"Count: 2\"\nbutton \"Increment\"\n"
+    );
+}
+
+#[test]
+fn test_gui_test_text_input_slider_and_checkbox() {
+    let source = r#"
+        let name = signal("")
+        let volume = signal(50)
+        let muted = signal(false)
+        let render = (ui) => do {
+            ui_text_input(name, "")
+            ui_box("p-2", () => do {
+                ui_slider(volume, 0, 100, "")
+                ui_checkbox(muted, "Muted", "")
+            })
+        }
+        gui_test(render, [
+            {input: "text_input#0", text: "Ada"},
+            {slider: "slider#0", value: 30},
+            {click: "Muted"}
+        ])
+    "#;
+    assert_eq!(
+        snapshot(source),
+        "text_input #0 = \"Ada\"\nbox #0\n  slider #0 = 30\n  checkbox \"Muted\" = true\n"
+    );
+}
+
+#[test]
+fn test_gui_test_tabs_combobox_and_checks() {
+    let source = r#"
+        let tab = signal(0)
+        let unit = signal("m")
+        let render = (ui) => do {
+            ui_tabs(["Input", "Output"], tab, (i) => ui_label("Tab " + str(i), ""), "")
+            ui_combobox(unit, ["m", "km", "mi"], "")
+        }
+        gui_test(render, [
+            {click: "Output"},
+            {check: (s) => s.contains("label \"Tab 1\"")},
+            {click: "combobox#0"},
+            {click: "km"}
+        ])
+    "#;
+    let snap = snapshot(source);
+    assert!(snap.contains("tab \"Output\" = selected"), "{}", snap);
+    assert!(snap.contains("combobox #0 = \"km\""), "{}", snap);
+}
+
+#[test]
+fn test_gui_test_reports_failures() {
+    let err =
+        execute(r#"gui_test((ui) => ui_button("Ok", ""), [{check: (s) => s.contains("Cancel")}])"#)
+            .unwrap_err();
+    assert!(err.contains("check at action 0 failed"), "{}", err);
+    assert!(err.contains("button \"Ok\""), "{}", err);
+
+    let err = execute(r#"gui_test((ui) => ui_button("Ok", ""), [{click: "Cancel"}])"#).unwrap_err();
+    assert!(err.contains("no widget 'Cancel'"), "{}", err);
+
+    let err = execute(r#"gui_test((ui) => do { throw "boom" }, [])"#).unwrap_err();
+    assert!(
+        err.contains("first frame") && err.contains("boom"),
+        "{}",
+        err
+    );
+
+    // Errors inside container children are no longer swallowed
+    let err =
+        execute(r#"gui_test((ui) => ui_box("p-2", () => do { throw "inner" }), [])"#).unwrap_err();
+    assert!(err.contains("inner"), "{}", err);
+}
+
+#[test]
+fn test_ui_state_is_keyed_and_dropped_when_hidden() {
+    let source = r#"
+        let counter = (name) => do {
+            let n = ui_state(name + "/count", 0)
+            if (ui_button(name + ": " + str(n.value), "")) {
+                n.set(n.value + 1)
+            }
+        }
+        let show_b = signal(true)
+        let render = (ui) => do {
+            counter("a")
+            if (show_b.value) { counter("b") }
+            ui_checkbox(show_b, "Show b", "")
+        }
+        gui_test(render, [
+            {click: "a: 0"},
+            {click: "a: 1"},
+            {click: "b: 0"},
+            {check: (s) => s.contains("b: 1")},
+            {click: "Show b"},
+            {click: "Show b"}
+        ])
+    "#;
+    assert_eq!(
+        snapshot(source),
+        "button \"a: 2\"\nbutton \"b: 0\"\ncheckbox \"Show b\" = true\n"
+    );
+
+    let err = execute(r#"ui_state("k", 0)"#).unwrap_err();
+    assert!(err.contains("while a GUI is running"), "{}", err);
+}
+
+#[test]
+fn test_redraw_only_when_read_signals_change() {
+    let source = r#"
+        let count = signal(0)
+        let unrelated = signal(0)
+        let total = computed(() => count.value * 2)
+        let render = (ui) => ui_label("Total: " + str(total.value), "")
+        gui_test(render, [
+            {frames: 2},
+            {redraw: false},
+            {check: (s) => do { unrelated.set(1); true }},
+            {redraw: false},
+            {check: (s) => do { count.set(1); true }},
+            {redraw: true},
+            {frames: 2},
+            {redraw: false}
+        ])
+    "#;
+    assert_eq!(snapshot(source), "label \"Total: 2\"\n");
+
+    let err = execute(r#"gui_test((ui) => ui_label("x", ""), [{frames: 2}, {redraw: true}])"#)
+        .unwrap_err();
+    assert!(err.contains("expected a redraw request"), "{}", err);
+}
@@ -0,0 +1,251 @@
+use super::helpers::{execute, path_literal, temp_path};
+use crate::value::Value;
+
+fn snapshot(source: &str) -> String {
+    match execute(source) {
+        Ok(Value::String(s)) => s,
+        other => panic!("Expected snapshot String, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_table_sorts_by_clicked_column() {
+    let source = r#"
+        let people = [{name: "Bo", age: 41}, {name: "Al", age: 29}, {name: "Cy", age: 35}]
+        let picked = signal(-1)
+        let render = (ui) => do {
+            let row = ui_table(people, ["name", "age"], "")
+            if (row != null) {
+                picked.set(row)
+            }
+            ui_label("Picked " + str(picked.value), "")
+        }
+        gui_test(render, [
+            {click: "age"},
+            {click: "age"},
+            {click: "Al | 29"}
+        ])
+    "#;
+    assert_eq!(
+        snapshot(source),
+        "table #0 = 3 rows\n  column \"name\"\n  column \"age\" = desc\n  row \"Bo | 41\"\n  row \"Cy | 35\"\n  row \"Al | 29\"\nlabel \"Picked 1\"\n"
+    );
+}
+
+#[test]
+fn test_table_from_dataframe() {
+    let source = r#"
+        let df = dataframe({city: ["b", "a"], price: [3.5, 1]})
+        gui_test((ui) => ui_table(df, null, ""), [{click: "city"}])
+    "#;
+    let snap = snapshot(source);
+    assert!(
+        snap.contains(
+            "column \"city\" = asc\n  column \"price\"\n  row \"a | 1\"\n  row \"b | 3.5\"\n"
+        ),
+        "{}",
+        snap
+    );
+}
+
+#[test]
+fn test_table_rejects_unknown_column() {
+    let err = execute(r#"gui_test((ui) => ui_table([{a: 1}], ["b"], ""), [])"#).unwrap_err();
+    assert!(err.contains("Unknown column 'b'"), "{}", err);
+}
+
+#[test]
+fn test_menu_item_click() {
+    let source = r#"
+        let status = signal("idle")
+        let render = (ui) => do {
+            ui_menu_bar(() => do {
+                ui_menu("File", () => do {
+                    if (ui_menu_item("Save", "")) {
+                        status.set("saved")
+                    }
+                }, "")
+            }, "")
+            ui_label(status.value, "")
+        }
+        gui_test(render, [
+            {click: "File"},
+            {check: (s) => s.contains("menu_item \"Save\"")},
+            {click: "Save"}
+        ])
+    "#;
+    assert_eq!(
+        snapshot(source),
+        "menu_bar #0\n  menu \"File\" = closed\nlabel \"saved\"\n"
+    );
+}
+
+#[test]
+fn test_modal_opens_and_closes() {
+    let source = r#"
+        let open = signal(false)
+        let render = (ui) => do {
+            if (ui_button("Delete", "")) {
+                open.set(true)
+            }
+            ui_modal(open, "Confirm", () => do {
+                ui_label("Are you sure?", "")
+                if (ui_button("Cancel", "")) {
+                    open.set(false)
+                }
+            }, "")
+        }
+        gui_test(render, [
+            {click: "Delete"},
+            {check: (s) => s.contains("modal \"Confirm\"\n  label \"Are you sure?\"")},
+            {click: "Cancel"}
+        ])
+    "#;
+    assert_eq!(snapshot(source), "button \"Delete\"\n");
+}
+
+#[test]
+fn test_date_picker_selects_day() {
+    let source = r#"
+        let date = signal("2024-02-10")
+        let render = (ui) => ui_date_picker(date, "")
+        gui_test(render, [
+            {click: "date_picker#0"},
+            {check: (s) => s.contains("label \"February 2024\"")},
+            {click: ">"},
+            {check: (s) => s.contains("label \"March 2024\"")},
+            {click: "15"}
+        ])
+    "#;
+    assert_eq!(snapshot(source), "date_picker #0 = \"2024-03-15\"\n");
+}
+
+#[test]
+fn test_code_editor_and_color_picker_bind_signals() {
+    let source = r##"
+        let code = signal("x")
+        let color = signal("#ff0000")
+        let render = (ui) => do {
+            ui_code_editor(code, "h-[100px]")
+            ui_color_picker(color, "")
+        }
+        gui_test(render, [{input: "code_editor#0", text: " = 1"}])
+    "##;
+    let snap = snapshot(source);
+    assert!(snap.contains("code_editor #0 = \"x = 1\""), "{}", snap);
+    assert!(snap.contains("color_picker #0 = \"#ff0000\""), "{}", snap);
+}
+
+#[test]
+fn test_image_from_tensor() {
+    let source = r#"
+        let pixels = [[0, 0.5, 1], [1, 0.5, 0]]
+        gui_test((ui) => ui_image(pixels, "w-[60px]"), [])
+    "#;
+    assert_eq!(snapshot(source), "image #0 = 3x2\n");
+}
+
+#[test]
+fn test_image_from_png_file() {
+    let path = temp_path("ui_image.png");
+    let source = format!(
+        r#"
+        plot_save("{0}", {{ width: 80, height: 64, series: [{{ data: [1, 2, 3] }}] }})
+        gui_test((ui) => ui_image("{0}", ""), [])
+    "#,
+        path_literal(&path)
+    );
+    let snap = snapshot(&source);
+    assert_eq!(snap, format!("image {:?} = 80x64\n", path_literal(&path)));
+}
+
+#[test]
+fn test_image_from_svg_file() {
+    let path = temp_path("ui_image.svg");
+    std::fs::write(
+        &path,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="40" height="30"><rect width="40" height="30" fill="red"/></svg>"#,
+    )
+    .unwrap();
+    let source = format!(
+        r#"gui_test((ui) => ui_image("{}", ""), [])"#,
+        path_literal(&path)
+    );
+    let snap = snapshot(&source);
+    assert_eq!(snap, format!("image {:?} = 40x30\n", path_literal(&path)));
+}
+
+#[test]
+fn test_image_from_jpeg_and_webp_files() {
+    let pixels = image::RgbImage::from_pixel(24, 16, image::Rgb([200, 40, 40]));
+    for name in ["ui_image.jpg", "ui_image.webp"] {
+        let path = temp_path(name);
+        pixels.save(&path).unwrap();
+        let source = format!(
+            r#"gui_test((ui) => ui_image("{}", ""), [])"#,
+            path_literal(&path)
+        );
+        let snap = snapshot(&source);
+        assert_eq!(snap, format!("image {:?} = 24x16\n", path_literal(&path)));
+    }
+}
+
+#[test]
+fn test_image_missing_file_errors() {
+    let err = execute(r#"gui_test((ui) => ui_image("no/such/image.png", ""), [])"#).unwrap_err();
+    assert!(
+        err.contains("cannot load image 'no/such/image.png'"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_image_rejects_bad_shape() {
+    let err = execute(r#"gui_test((ui) => ui_image([1, 2, 3], ""), [])"#).unwrap_err();
+    assert!(err.contains("image tensor must be"), "{}", err);
+}
+
+#[test]
+fn test_toast_is_shown_then_expires() {
+    let source = r#"
+        let render = (ui) => do {
+            if (ui_button("Save", "")) {
+                ui_toast("Saved!", "success")
+            }
+        }
+        gui_test(render, [
+            {click: "Save"},
+            {check: (s) => s.contains("toast \"Saved!\" = success")},
+            {frames: 300}
+        ])
+    "#;
+    assert_eq!(snapshot(source), "button \"Save\"\n");
+}
+
+#[test]
+fn test_file_picker_browses_and_picks() {
+    let dir = temp_path("picker");
+    std::fs::create_dir_all(dir.join("data")).unwrap();
+    std::fs::write(dir.join("data").join("notes.txt"), "hi").unwrap();
+    std::fs::write(dir.join("readme.md"), "hi").unwrap();
+    let source = format!(
+        r#"
+        let file = signal("{}/readme.md")
+        gui_test((ui) => ui_file_picker(file, ""), [
+            {{click: "file_picker#0"}},
+            {{check: (s) => s.contains("  file \"data/\"\n  file \"readme.md\"")}},
+            {{click: "data/"}},
+            {{click: "notes.txt"}}
+        ])
+    "#,
+        path_literal(&dir)
+    );
+    let snap = snapshot(&source);
+    assert!(
+        snap.contains("data") && snap.ends_with("notes.txt\"\n"),
+        "{}",
+        snap
+    );
+    assert!(!snap.contains("file \""), "{}", snap);
+}
@@ -3,6 +3,7 @@
 use crate::compiler::Compiler;
 use crate::value::Value;
 use crate::vm::VM;
+use std::path::{Path, PathBuf};
 
 /// Helper to compile and execute source code (Async)
 pub async fn execute_async(source: &str) -> Result<Value, String> {
@@ -34,3 +35,13 @@ pub fn execute(source: &str) -> Result<Value, String> {
 
     local.block_on(&rt, execute_async(source))
 }
+
+/// Path for a scratch file unique to this test process
+pub fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("achronyme_{}_{}", std::process::id(), name))
+}
+
+/// Render a path for embedding in a source string literal
+pub fn path_literal(path: &Path) -> String {
+    path.display().to_string().replace('\\', "/")
+}
@@ -0,0 +1,211 @@
+use super::helpers::execute;
+use crate::value::Value;
+
+const NATURALS: &str = r#"
+    let naturals = () => generate {
+        mut n = 0
+        while (true) {
+            yield n
+            n = n + 1
+        }
+    }
+"#;
+
+fn run(body: &str) -> Result<Value, String> {
+    execute(&format!("{}\n{}", NATURALS, body))
+}
+
+/// The numbers of a vector result
+fn numbers(body: &str) -> Vec<f64> {
+    let Value::Vector(items) = run(body).unwrap() else {
+        panic!("expected a vector");
+    };
+    let items = items.read();
+    items
+        .iter()
+        .map(|item| match item {
+            Value::Number(n) => *n,
+            other => panic!("expected a number, got {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_infinite_generator_map_take_while() {
+    let result = numbers("naturals().map(x => x * x).take_while(x => x < 30).collect()");
+    assert_eq!(result, [0.0, 1.0, 4.0, 9.0, 16.0, 25.0]);
+}
+
+#[test]
+fn test_hof_builtins_stay_lazy_on_generators() {
+    let result = numbers("collect(take(filter(x => x % 3 == 0, naturals()), 4))");
+    assert_eq!(result, [0.0, 3.0, 6.0, 9.0]);
+}
+
+#[test]
+fn test_adapters_only_run_when_consumed() {
+    // The callback would fail on the first value if it ran eagerly
+    let result = run("let it = iter([1, 2]).map(x => x.missing.field)\ntypeof(it)").unwrap();
+    assert_eq!(result, Value::String("Iterator".to_string()));
+}
+
+#[test]
+fn test_iter_over_vector() {
+    assert_eq!(
+        numbers("iter([1, 2, 3]).map(x => x * 10).collect()"),
+        [10.0, 20.0, 30.0]
+    );
+}
+
+#[test]
+fn test_iter_counts_through_range() {
+    // Expanding the range first would never finish
+    assert_eq!(numbers("collect(take(iter(0..1e12), 3))"), [0.0, 1.0, 2.0]);
+    assert_eq!(
+        numbers("let n = 4\niter(1..=n).map(x => x * 2).collect()"),
+        [2.0, 4.0, 6.0, 8.0]
+    );
+    assert!(numbers("collect(iter(3..1))").is_empty());
+    assert_eq!(numbers("collect(iter(1..=2.5))"), [1.0, 2.0]);
+}
+
+#[test]
+fn test_bound_range_stays_lazy() {
+    assert_eq!(
+        numbers("let r = 0..1e12\ncollect(take(iter(r), 3))"),
+        [0.0, 1.0, 2.0]
+    );
+    assert_eq!(
+        numbers("let n = 3\nmut seen = []\nfor (i in 0..=n) { push(seen, i) }\nseen"),
+        [0.0, 1.0, 2.0, 3.0]
+    );
+    // Other built-ins get the vector the range stands for
+    assert_eq!(run("let n = 4\nsum(1..=n)").unwrap(), Value::Number(10.0));
+    let err = run("sum(0..1e12)").unwrap_err();
+    assert!(err.contains("use iter()"), "{}", err);
+}
+
+#[test]
+fn test_iterators_and_ranges_print_readably() {
+    assert_eq!(
+        run("str(iter([1, 2]))").unwrap(),
+        Value::String("<iterator>".to_string())
+    );
+    assert_eq!(
+        run("str(0..=1e12)").unwrap(),
+        Value::String("0..=1000000000000".to_string())
+    );
+}
+
+#[test]
+fn test_enumerate() {
+    let result = run(r#"iter("ab").enumerate().collect()"#).unwrap();
+    let Value::Vector(pairs) = result else {
+        panic!("expected a vector");
+    };
+    let pairs = pairs.read();
+    assert_eq!(pairs.len(), 2);
+    let Value::Vector(second) = &pairs[1] else {
+        panic!("expected a pair");
+    };
+    assert_eq!(
+        *second.read(),
+        [Value::Number(1.0), Value::String("b".to_string())]
+    );
+}
+
+#[test]
+fn test_zip_with_infinite_generator() {
+    let result = numbers("iter([5, 6, 7]).zip(naturals()).map(p => p[0] * p[1]).collect()");
+    assert_eq!(result, [0.0, 6.0, 14.0]);
+}
+
+#[test]
+fn test_chain() {
+    let result = numbers("iter([1, 2]).chain(naturals().take(2)).collect()");
+    assert_eq!(result, [1.0, 2.0, 0.0, 1.0]);
+}
+
+#[test]
+fn test_window() {
+    let result = numbers("iter([1, 2, 3, 4]).window(3).map(w => sum(w)).collect()");
+    assert_eq!(result, [6.0, 9.0]);
+}
+
+#[test]
+fn test_window_larger_than_source_is_empty() {
+    assert!(numbers("iter([1, 2]).window(3).collect()").is_empty());
+}
+
+#[test]
+fn test_step_by() {
+    let result = numbers("naturals().step_by(3).take(4).collect()");
+    assert_eq!(result, [0.0, 3.0, 6.0, 9.0]);
+}
+
+#[test]
+fn test_step_by_rejects_zero() {
+    assert!(run("iter([1, 2]).step_by(0)").is_err());
+}
+
+#[test]
+fn test_for_loop_consumes_iterator() {
+    let source = r#"
+        mut total = 0
+        for (x in naturals().map(x => x * 2).take(4)) {
+            total = total + x
+        }
+        total
+    "#;
+    assert_eq!(run(source).unwrap(), Value::Number(12.0));
+}
+
+#[test]
+fn test_reductions_consume_iterators() {
+    assert_eq!(run("sum(naturals().take(5))").unwrap(), Value::Number(10.0));
+    assert_eq!(
+        run("naturals().take(4).reduce((acc, x) => acc + x, 100)").unwrap(),
+        Value::Number(106.0)
+    );
+    assert_eq!(
+        run("find(x => x * x > 50, naturals())").unwrap(),
+        Value::Number(8.0)
+    );
+    assert_eq!(
+        run("naturals().any(x => x > 10)").unwrap(),
+        Value::Boolean(true)
+    );
+}
+
+#[test]
+fn test_adapters_share_their_source() {
+    // Advancing the mapped iterator advances the one it was built from
+    let source = r#"
+        let it = iter([1, 2, 3, 4])
+        let doubled = it.map(x => x * 2)
+        doubled.next()
+        it.collect()
+    "#;
+    let Value::Vector(items) = run(source).unwrap() else {
+        panic!("expected a vector");
+    };
+    assert_eq!(
+        *items.read(),
+        [Value::Number(2.0), Value::Number(3.0), Value::Number(4.0)]
+    );
+}
+
+#[test]
+fn test_iterator_next_record() {
+    let result = run("let it = iter([7])\n[it.next().value, it.next().done]").unwrap();
+    let Value::Vector(items) = result else {
+        panic!("expected a vector");
+    };
+    assert_eq!(*items.read(), [Value::Number(7.0), Value::Boolean(true)]);
+}
+
+#[test]
+fn test_vector_map_stays_eager() {
+    let result = run("typeof(map(x => x + 1, [1, 2]))").unwrap();
+    assert_eq!(result, Value::String("Vector".to_string()));
+}
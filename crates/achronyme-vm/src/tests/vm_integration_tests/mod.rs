@@ -2,15 +2,27 @@ pub mod helpers;
 
 pub mod async_await;
 pub mod basic_operations;
+mod binary;
 mod complex_numbers;
 mod concurrency;
 mod control_flow;
+mod csv_io;
 mod data_structures;
+mod data_types;
+mod dataframe;
+mod encoding;
 pub mod exceptions;
 pub mod functions;
 pub mod generators;
 pub mod gradual_types;
+mod gui_harness;
+mod gui_widgets;
+mod iterators;
 pub mod operators;
+mod parallel;
 pub mod pattern_matching;
+mod persist;
+mod plot_export;
 mod reactive;
+mod tasks;
 pub mod tco;
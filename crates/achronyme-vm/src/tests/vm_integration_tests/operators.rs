@@ -230,3 +230,34 @@ fn test_ieee754_finite_divided_by_infinity() {
         _ => panic!("Expected Number(0)"),
     }
 }
+
+// ============================================================================
+// Tensor Operator Tests
+// ============================================================================
+
+#[test]
+fn test_matmul_operator() {
+    let result =
+        execute("reshape([1, 2, 3, 4, 5, 6], [2, 3]) @ reshape([1, 0, 0, 1, 1, 1], [3, 2])")
+            .unwrap();
+    match result {
+        Value::Tensor(t) => {
+            assert_eq!(t.shape(), &[2, 2]);
+            assert_eq!(t.data(), &[4.0, 5.0, 10.0, 11.0]);
+        }
+        _ => panic!("Expected Tensor"),
+    }
+
+    let err = execute("let a = reshape([1, 2, 3, 4, 5, 6], [2, 3])\na @ a").unwrap_err();
+    assert!(err.contains("inner dimensions 3 and 2 differ"));
+}
+
+#[test]
+fn test_tensor_broadcasting() {
+    let result = execute("reshape([1, 2, 3, 4], [2, 2]) * reshape([10, 100], [2]) + 1").unwrap();
+    match result {
+        Value::Tensor(t) => assert_eq!(t.data(), &[11.0, 201.0, 31.0, 401.0]),
+        _ => panic!("Expected Tensor"),
+    }
+    assert!(execute("reshape([1, 2, 3, 4], [2, 2]) + reshape([1, 2, 3], [3])").is_err());
+}
@@ -0,0 +1,79 @@
+use super::helpers::execute;
+use crate::value::Value;
+
+#[test]
+fn test_par_map_matches_map() {
+    let source = r#"
+        let xs = range(0, 5000)
+        let f = x => x * x + 1
+        let seq = map(f, xs)
+        let par = par_map(f, xs)
+        [seq, par, par_map(s => s + "!", ["a", "b"])]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            match (&vec[0], &vec[1]) {
+                (Value::Vector(seq), Value::Vector(par)) => {
+                    assert_eq!(par.read().len(), 5000);
+                    assert_eq!(*seq.read(), *par.read());
+                }
+                (Value::Tensor(seq), Value::Tensor(par)) => assert_eq!(seq, par),
+                other => panic!("Expected matching collections, got {:?}", other),
+            }
+            match &vec[2] {
+                Value::Vector(strs) => assert_eq!(
+                    *strs.read(),
+                    vec![
+                        Value::String("a!".to_string()),
+                        Value::String("b!".to_string())
+                    ]
+                ),
+                other => panic!("Expected Vector, got {:?}", other),
+            }
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_par_reduce_and_errors() {
+    let source = r#"
+        let total = par_reduce((a, b) => a + b, 0, range(1, 1001))
+        let empty = par_reduce((a, b) => a + b, 0, [])
+        [total, empty]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(500500.0));
+            assert_eq!(vec[1], Value::Number(0.0));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+
+    assert!(execute(r#"par_map(x => x + undefined_name, [1, 2])"#).is_err());
+}
+
+#[test]
+fn test_par_for_and_set_threads() {
+    let source = r#"
+        let threads = set_threads(2)
+        let seen = []
+        par_for(0..100, i => push(seen, i))
+        set_threads(0)
+        [threads, len(seen), sum(seen)]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(2.0));
+            assert_eq!(vec[1], Value::Number(100.0));
+            assert_eq!(vec[2], Value::Number(4950.0));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
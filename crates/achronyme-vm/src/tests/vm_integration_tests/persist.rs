@@ -0,0 +1,311 @@
+use super::helpers::{execute, path_literal, temp_path};
+use crate::builtins::persist::{vm_restore_env, vm_save_env};
+use crate::value::Value;
+use crate::vm::VM;
+use achronyme_types::complex::Complex;
+use achronyme_types::function::Function;
+use achronyme_types::tensor::RealTensor;
+
+#[test]
+fn test_save_restore_round_trip() {
+    let path = temp_path("session.ach");
+    let save = format!(
+        r#"
+        set_global("n", 42)
+        set_global("label", "run 1")
+        set_global("z", 3+4i)
+        set_global("data", {{xs: [1, true, null], nested: {{ok: false}}}})
+        save_env("{}")
+    "#,
+        path_literal(&path)
+    );
+    assert_eq!(execute(&save).unwrap(), Value::Boolean(true));
+
+    let restore = format!(
+        r#"
+        restore_env("{}")
+        let data = get_global("data")
+        [get_global("n"), get_global("label"), get_global("z"),
+         data.xs[1], data.nested.ok, env_bindings()]
+    "#,
+        path_literal(&path)
+    );
+    let result = execute(&restore).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(42.0));
+            assert_eq!(vec[1], Value::String("run 1".to_string()));
+            assert_eq!(vec[2], Value::Complex(Complex::new(3.0, 4.0)));
+            assert_eq!(vec[3], Value::Boolean(true));
+            assert_eq!(vec[4], Value::Boolean(false));
+            match &vec[5] {
+                Value::Vector(names) => assert_eq!(names.read().len(), 4),
+                other => panic!("Expected Vector, got {:?}", other),
+            }
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_archive_layout() {
+    let path = temp_path("layout.ach");
+    let mut vm = VM::new();
+    vm.set_global(
+        "m".to_string(),
+        Value::Tensor(RealTensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3]).unwrap()),
+    );
+    vm.set_global(
+        "f".to_string(),
+        Value::Function(Function::Builtin("sin".to_string())),
+    );
+    vm_save_env(
+        &mut vm,
+        &[Value::String(path.to_string_lossy().to_string())],
+    )
+    .unwrap();
+    let raw = std::fs::read(&path).unwrap();
+
+    assert_eq!(&raw[..4], b"ACH\0");
+    assert_eq!(u16::from_le_bytes([raw[4], raw[5]]), 1);
+    // Tensor payload: [ndim=2][2][3][count=6] followed by six f64 values
+    let payload: Vec<u8> = [2u32, 2, 3, 6]
+        .iter()
+        .flat_map(|n| n.to_le_bytes())
+        .chain(1.0f64.to_le_bytes())
+        .collect();
+    assert!(raw.windows(payload.len()).any(|w| w == payload.as_slice()));
+
+    let mut restored = VM::new();
+    vm_restore_env(
+        &mut restored,
+        &[Value::String(path.to_string_lossy().to_string())],
+    )
+    .unwrap();
+    std::fs::remove_file(&path).ok();
+
+    match restored.get_global("m") {
+        Some(Value::Tensor(t)) => {
+            assert_eq!(t.shape(), &[2, 3]);
+            assert_eq!(t.data(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        }
+        other => panic!("Expected Tensor, got {:?}", other),
+    }
+    match restored.get_global("f") {
+        Some(Value::Function(Function::Builtin(name))) => assert_eq!(name, "sin"),
+        other => panic!("Expected builtin function, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_lambdas_are_skipped() {
+    let path = temp_path("lambdas.ach");
+    let source = format!(
+        r#"
+        set_global("g", x => x * 2)
+        set_global("counter", {{mut value: 3, inc: () => 1}})
+        save_env("{}")
+        let info = env_info("{}")
+        [info.bindings, info.num_bindings]
+    "#,
+        path_literal(&path),
+        path_literal(&path)
+    );
+    let result = execute(&source).unwrap();
+
+    let restore = format!(
+        r#"
+        restore_env("{}")
+        let c = get_global("counter")
+        [c.value, keys(c)]
+    "#,
+        path_literal(&path)
+    );
+    let restored = execute(&restore).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            match &vec[0] {
+                Value::Vector(names) => {
+                    assert_eq!(*names.read(), vec![Value::String("counter".to_string())])
+                }
+                other => panic!("Expected Vector, got {:?}", other),
+            }
+            assert_eq!(vec[1], Value::Number(1.0));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+    match restored {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(3.0));
+            match &vec[1] {
+                Value::Vector(keys) => assert_eq!(keys.read().len(), 1),
+                other => panic!("Expected Vector, got {:?}", other),
+            }
+        }
+        _ => panic!("Expected Vector, got {:?}", restored),
+    }
+}
+
+#[test]
+fn test_restore_modes_and_filters() {
+    let path = temp_path("modes.ach");
+    let p = path_literal(&path);
+    let source = format!(
+        r#"
+        set_global("a", 1)
+        set_global("b", 2)
+        set_global("temp_x", 3)
+        save_env("{p}", {{exclude: ["temp_*"], description: "modes", tags: ["t"]}})
+
+        set_global("a", 10)
+        restore_env("{p}")
+        let merged = get_global("a")
+        restore_env("{p}", {{overwrite: true}})
+        let overwritten = get_global("a")
+        restore_env("{p}", {{mode: "namespace", namespace: "old"}})
+        let old = get_global("old")
+        restore_env("{p}", {{mode: "replace", include_only: ["b"]}})
+        [merged, overwritten, old.b, env_bindings(), env_info("{p}").description]
+    "#
+    );
+    let result = execute(&source).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(10.0));
+            assert_eq!(vec[1], Value::Number(1.0));
+            assert_eq!(vec[2], Value::Number(2.0));
+            match &vec[3] {
+                Value::Vector(names) => {
+                    assert_eq!(*names.read(), vec![Value::String("b".to_string())])
+                }
+                other => panic!("Expected Vector, got {:?}", other),
+            }
+            assert_eq!(vec[4], Value::String("modes".to_string()));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_corrupted_archive_and_overwrite_guard() {
+    let path = temp_path("corrupt.ach");
+    let p = path_literal(&path);
+    execute(&format!("set_global(\"x\", 1)\nsave_env(\"{}\")", p)).unwrap();
+
+    // Saving again without allow_overwrite is refused
+    assert!(execute(&format!(r#"save_env("{}")"#, p)).is_err());
+    assert!(execute(&format!(r#"save_env("{}", {{allow_overwrite: true}})"#, p)).is_ok());
+
+    let mut raw = std::fs::read(&path).unwrap();
+    let last = raw.len() - 40;
+    raw[last] ^= 0xFF;
+    std::fs::write(&path, raw).unwrap();
+
+    let err = execute(&format!(r#"restore_env("{}")"#, p)).unwrap_err();
+    std::fs::remove_file(&path).ok();
+    assert!(err.contains("checksum"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_top_level_variables_and_dataframes_are_saved() {
+    let path = temp_path("top_level.ach");
+    let p = path_literal(&path);
+    let save = format!(
+        r#"
+        let xs = [1, 2]
+        let xs = "shadowed"
+        let df = dataframe({{city: ["a", "b"], price: [3, 1]}}).group_by("city")
+        set_global("g", 7)
+        save_env("{p}")
+        let after = 1
+        env_info("{p}").bindings
+    "#
+    );
+    let result = execute(&save).unwrap();
+    let restore = format!(
+        r#"
+        restore_env("{p}")
+        let df = get_global("df")
+        [get_global("xs"), df.price, df.city[1], df.agg({{price: "sum"}}).nrows()]
+    "#
+    );
+    let restored = execute(&restore).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    let names: Vec<Value> = ["df", "g", "xs"]
+        .iter()
+        .map(|n| Value::String(n.to_string()))
+        .collect();
+    match result {
+        Value::Vector(v) => assert_eq!(*v.read(), names),
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+    match restored {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::String("shadowed".to_string()));
+            match &vec[1] {
+                Value::Tensor(t) => assert_eq!(t.data(), &[3.0, 1.0]),
+                other => panic!("Expected Tensor, got {:?}", other),
+            }
+            assert_eq!(vec[2], Value::String("b".to_string()));
+            assert_eq!(vec[3], Value::Number(2.0));
+        }
+        _ => panic!("Expected Vector, got {:?}", restored),
+    }
+}
+
+#[test]
+fn test_restore_rejects_deep_nesting_and_oversized_tensors() {
+    let path = temp_path("hostile.ach");
+    let path_value = Value::String(path.to_string_lossy().to_string());
+
+    let mut nested = Value::Null;
+    for _ in 0..300 {
+        nested = Value::Vector(achronyme_types::sync::shared(vec![nested]));
+    }
+    let mut vm = VM::new();
+    vm.set_global("deep".to_string(), nested);
+    vm_save_env(&mut vm, std::slice::from_ref(&path_value)).unwrap();
+    let err = vm_restore_env(&mut VM::new(), std::slice::from_ref(&path_value)).unwrap_err();
+    assert!(
+        err.to_string().contains("nested"),
+        "unexpected error: {}",
+        err
+    );
+
+    // A one-dimensional tensor claiming 2^32 - 1 elements
+    std::fs::remove_file(&path).unwrap();
+    let mut vm = VM::new();
+    vm.set_global(
+        "m".to_string(),
+        Value::Tensor(RealTensor::new(vec![1.0, 2.0, 3.0], vec![3]).unwrap()),
+    );
+    vm_save_env(&mut vm, std::slice::from_ref(&path_value)).unwrap();
+    let mut raw = std::fs::read(&path).unwrap();
+    let header: Vec<u8> = [1u32, 3, 3].iter().flat_map(|n| n.to_le_bytes()).collect();
+    let at = raw
+        .windows(header.len())
+        .position(|w| w == header.as_slice())
+        .unwrap();
+    raw[at + 4..at + 12].fill(0xFF);
+    std::fs::write(&path, raw).unwrap();
+
+    let restore = format!(
+        r#"restore_env("{}", {{verify_checksum: false}})"#,
+        path_literal(&path)
+    );
+    let err = execute(&restore).unwrap_err();
+    std::fs::remove_file(&path).ok();
+    assert!(err.contains("truncated"), "unexpected error: {}", err);
+}
@@ -0,0 +1,133 @@
+use super::helpers::{execute, path_literal, temp_path};
+
+#[test]
+fn test_plot_save_svg_line_and_scatter() {
+    let path = temp_path("plot_line.svg");
+    let source = format!(
+        r##"
+        plot_save("{}", {{
+            title: "Signals & noise",
+            x_label: "Time (s)",
+            y_label: "Amplitude",
+            series: [
+                {{ name: "Signal A", kind: "line", data: [[0, 1], [1, 2], [2, 1]], color: "#00aa00" }},
+                {{ name: "Samples", kind: "scatter", data: [0.5, 1.5, 1.0], radius: 4 }}
+            ]
+        }})
+    "##,
+        path_literal(&path)
+    );
+    execute(&source).unwrap();
+
+    let svg = std::fs::read_to_string(&path).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("width=\"640\" height=\"480\""));
+    assert!(svg.contains(">Signals &amp; noise</text>"));
+    assert!(svg.contains(">Time (s)</text>"));
+    assert!(svg.contains("rotate(-90"));
+    assert!(svg.contains("stroke=\"#00aa00\""));
+    assert_eq!(svg.matches("<circle").count(), 3 + 1); // points + legend swatch
+    assert!(svg.contains(">Signal A</text>") && svg.contains(">Samples</text>"));
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_plot_save_png_is_rasterized() {
+    let path = temp_path("plot_bars.png");
+    let source = format!(
+        r#"
+        plot_save("{}", {{
+            width: 320,
+            height: 200,
+            title: "Counts",
+            series: [
+                {{ kind: "bar", data: [3, 1, 4, 1, 5] }},
+                {{ kind: "histogram", data: [1, 2, 2, 3, 3, 3], bins: 3 }}
+            ]
+        }})
+    "#,
+        path_literal(&path)
+    );
+    execute(&source).unwrap();
+
+    let png = std::fs::read(&path).unwrap();
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    // IHDR holds the big-endian width and height
+    assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), 320);
+    assert_eq!(u32::from_be_bytes(png[20..24].try_into().unwrap()), 200);
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_plot_save_heatmap_and_contour() {
+    let path = temp_path("plot_field.svg");
+    let source = format!(
+        r#"
+        let axis = [0, 1, 2, 3, 4]
+        let field = map(i => map(j => (i - 2) ^ 2 + (j - 2) ^ 2, axis), axis)
+        plot_save("{}", {{
+            legend: false,
+            series: [
+                {{ kind: "heatmap", data: field, extent: [-1, 1, -1, 1] }},
+                {{ kind: "contour", data: field, levels: [1, 4], color: "white" }}
+            ]
+        }})
+    "#,
+        path_literal(&path)
+    );
+    execute(&source).unwrap();
+
+    let svg = std::fs::read_to_string(&path).unwrap();
+    // Background, 25 cells, frame
+    assert_eq!(svg.matches("<rect").count(), 1 + 25 + 1);
+    assert_eq!(svg.matches("stroke=\"#ffffff\"").count(), 2);
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_plot_save_errors() {
+    let path = temp_path("plot.bmp");
+    let source = format!(r#"plot_save("{}", {{ series: [] }})"#, path_literal(&path));
+    let err = execute(&source).unwrap_err();
+    assert!(err.contains("cannot infer the image format"), "{}", err);
+
+    let path = temp_path("plot_bad.svg");
+    let source = format!(
+        r#"plot_save("{}", {{ series: [{{ kind: "pie", data: [1, 2] }}] }})"#,
+        path_literal(&path)
+    );
+    let err = execute(&source).unwrap_err();
+    assert!(err.contains("unknown series type 'pie'"), "{}", err);
+}
+
+#[test]
+fn test_plot_save_huge_values_and_counts() {
+    // Consecutive ticks would be closer than the float spacing near 1e16
+    let path = temp_path("plot_huge.svg");
+    let source = format!(
+        r#"plot_save("{}", {{ series: [{{ kind: "line", data: [[0, 10000000000000000], [1, 10000000000000004]] }}] }})"#,
+        path_literal(&path)
+    );
+    execute(&source).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    for (series, option) in [
+        (r#"kind: "histogram", data: [1, 2], bins"#, "bins"),
+        (
+            r#"kind: "contour", data: [[0, 1], [1, 0]], levels"#,
+            "levels",
+        ),
+    ] {
+        let source = format!(
+            r#"plot_save("{}", {{ series: [{{ {}: 1000000000000000 }}] }})"#,
+            path_literal(&path),
+            series
+        );
+        let err = execute(&source).unwrap_err();
+        assert!(
+            err.contains(&format!("{} must be an integer from 1 to 10000", option)),
+            "{}",
+            err
+        );
+    }
+}
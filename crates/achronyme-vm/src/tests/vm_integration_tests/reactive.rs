@@ -1,5 +1,7 @@
 use super::helpers::execute;
+use crate::compiler::Compiler;
 use crate::value::Value;
+use crate::vm::VM;
 
 #[test]
 fn test_signal_basic() {
@@ -74,3 +76,318 @@ fn test_computed_simulation() {
     let result = execute(source).unwrap();
     assert_eq!(result, Value::Boolean(true));
 }
+
+#[test]
+fn test_computed_is_lazy_and_memoized() {
+    let source = r#"
+        let a = signal(2)
+        let runs = signal(0)
+        let doubled = computed(() => do {
+            runs.set(runs.peek() + 1)
+            a.value * 2
+        })
+
+        let before_read = runs.peek()
+        let first = doubled.value
+        let second = doubled.value
+        a.set(5)
+        let after_set = runs.peek()
+        [before_read, first, second, after_set, doubled.value, runs.peek()]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(0.0));
+            assert_eq!(vec[1], Value::Number(4.0));
+            assert_eq!(vec[2], Value::Number(4.0));
+            assert_eq!(vec[3], Value::Number(1.0));
+            assert_eq!(vec[4], Value::Number(10.0));
+            assert_eq!(vec[5], Value::Number(2.0));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_diamond_runs_effect_once() {
+    let source = r#"
+        let a = signal(1)
+        let b = computed(() => a.value * 2)
+        let c = computed(() => a.value + 1)
+        let runs = signal(0)
+        let seen = signal(0)
+
+        effect(() => do {
+            seen.set(b.value + c.value)
+            runs.set(runs.peek() + 1)
+        })
+
+        a.set(10)
+        [runs.peek(), seen.peek()]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(2.0));
+            assert_eq!(vec[1], Value::Number(31.0));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_unchanged_computed_skips_effect() {
+    let source = r#"
+        let n = signal(1)
+        let parity = computed(() => n.value % 2)
+        let runs = signal(0)
+
+        effect(() => do {
+            let p = parity.value
+            runs.set(runs.peek() + 1)
+        })
+
+        n.set(3)
+        n.set(5)
+        n.set(6)
+        runs.peek()
+    "#;
+    assert_eq!(execute(source).unwrap(), Value::Number(2.0));
+}
+
+#[test]
+fn test_batch_coalesces_updates() {
+    let source = r#"
+        let first = signal("John")
+        let last = signal("Doe")
+        let runs = signal(0)
+
+        effect(() => do {
+            let name = first.value + " " + last.value
+            runs.set(runs.peek() + 1)
+        })
+
+        let result = batch(() => do {
+            first.set("Jane")
+            last.set("Roe")
+            42
+        })
+        [runs.peek(), result]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(2.0));
+            assert_eq!(vec[1], Value::Number(42.0));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_failed_batch_does_not_leave_effects_queued() {
+    let source = r#"
+        let count = signal(0)
+        let other = signal(0)
+        let runs = signal(0)
+
+        effect(() => do {
+            let c = count.value
+            runs.set(runs.peek() + 1)
+        })
+
+        {
+            fail: () => batch(() => do {
+                count.set(1)
+                throw "aborted"
+            }),
+            poke: () => other.set(1),
+            runs: () => runs.peek()
+        }
+    "#;
+    let ast = achronyme_parser::parse(source).unwrap();
+    let module = Compiler::new("<test>".to_string()).compile(&ast).unwrap();
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let mut vm = VM::new();
+    let handles = tokio::task::LocalSet::new()
+        .block_on(&rt, vm.execute(module))
+        .unwrap();
+    let handle = |name: &str| match &handles {
+        Value::Record(r) => r.read()[name].clone(),
+        other => panic!("Expected Record, got {:?}", other),
+    };
+
+    assert!(vm.call_value(&handle("fail"), &[]).is_err());
+    let after_batch = vm.call_value(&handle("runs"), &[]).unwrap();
+    assert_eq!(after_batch, Value::Number(2.0));
+
+    // An unrelated write must not re-run the aborted batch's effect
+    vm.call_value(&handle("poke"), &[]).unwrap();
+    let after_write = vm.call_value(&handle("runs"), &[]).unwrap();
+    assert_eq!(after_write, Value::Number(2.0));
+}
+
+#[test]
+fn test_untrack_and_dispose() {
+    let source = r#"
+        let tracked = signal(0)
+        let ignored = signal(0)
+        let runs = signal(0)
+
+        let handle = effect(() => do {
+            let t = tracked.value
+            let i = untrack(() => ignored.value)
+            runs.set(runs.peek() + 1)
+        })
+
+        ignored.set(1)
+        let after_untracked = runs.peek()
+        tracked.set(1)
+        let after_tracked = runs.peek()
+        handle.dispose()
+        tracked.set(2)
+        [after_untracked, after_tracked, runs.peek(), handle.disposed()]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(1.0));
+            assert_eq!(vec[1], Value::Number(2.0));
+            assert_eq!(vec[2], Value::Number(2.0));
+            assert_eq!(vec[3], Value::Boolean(true));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_reactive_errors() {
+    // Computed signals are read-only
+    assert!(execute("let c = computed(() => 1)\nc.set(2)").is_err());
+
+    // An effect that writes a signal it reads never settles
+    let source = r#"
+        let count = signal(0)
+        effect(() => count.set(count.value + 1))
+    "#;
+    let err = execute(source).unwrap_err();
+    assert!(err.contains("loop"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_nested_effects_keep_outer_tracking() {
+    let source = r#"
+        let outer_dep = signal(0)
+        let inner_dep = signal(0)
+        let outer_runs = signal(0)
+
+        effect(() => do {
+            effect(() => inner_dep.value)
+            // Read after the inner effect ran: must still subscribe the outer one
+            let o = outer_dep.value
+            outer_runs.set(outer_runs.peek() + 1)
+        })
+
+        outer_dep.set(1)
+        outer_runs.peek()
+    "#;
+    assert_eq!(execute(source).unwrap(), Value::Number(2.0));
+}
+
+#[test]
+fn test_nested_effects_are_owned_by_their_parent() {
+    let source = r#"
+        let outer_dep = signal(0)
+        let inner_dep = signal(0)
+        let inner_runs = signal(0)
+
+        let outer = effect(() => do {
+            let o = outer_dep.value
+            effect(() => do {
+                let i = inner_dep.value
+                inner_runs.set(inner_runs.peek() + 1)
+            })
+        })
+
+        // The re-run disposes the previous inner effect before creating a new one
+        outer_dep.set(3)
+        inner_dep.set(1)
+        let live = inner_runs.peek()
+
+        // Disposing the parent disposes what it created
+        outer.dispose()
+        inner_dep.set(2)
+        [live, inner_runs.peek()]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(3.0));
+            assert_eq!(vec[1], Value::Number(3.0));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_spawned_task_schedules_effect_on_owner() {
+    let source = r#"
+        let count = signal(0)
+        let seen = signal(0)
+
+        effect(() => seen.set(count.value))
+
+        // The effect belongs to this VM: the task only schedules it
+        let seen_in_task = await spawn(() => do {
+            count.set(5)
+            seen.peek()
+        })
+
+        [seen_in_task, seen.peek()]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Number(0.0));
+            assert_eq!(vec[1], Value::Number(5.0));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_spawned_task_effects_run_while_owner_awaits() {
+    let source = r#"
+        let count = signal(0)
+        let seen = []
+        effect(() => push(seen, count.value))
+
+        // Each write runs the effect while we wait, instead of one merged run
+        await spawn(async () => do {
+            count.set(1)
+            await sleep(30)
+            count.set(2)
+            await sleep(30)
+        })
+        seen
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            assert_eq!(
+                *v.read(),
+                vec![Value::Number(0.0), Value::Number(1.0), Value::Number(2.0)]
+            );
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
@@ -0,0 +1,196 @@
+use super::helpers::execute;
+use crate::value::Value;
+
+fn error_kind(value: &Value) -> Option<String> {
+    match value {
+        Value::Error { kind, .. } => kind.clone(),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_spawn_returns_task() {
+    let source = r#"
+        let t = spawn(async (a, b) => do {
+            await sleep(5)
+            a + b
+        }, 2, 3)
+        let before = t.is_done()
+        let joined = await t.join()
+        [typeof(t), before, await t, joined, t.is_done()]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::String("Task".to_string()));
+            assert_eq!(vec[1], Value::Boolean(false));
+            assert_eq!(vec[2], Value::Number(5.0));
+            assert_eq!(vec[3], Value::Number(5.0));
+            assert_eq!(vec[4], Value::Boolean(true));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_cancel_task() {
+    let source = r#"
+        let t = spawn(async () => do {
+            await sleep(10000)
+            1
+        })
+        let was_running = t.cancel()
+        let result = await t
+        [was_running, result, t.is_done(), t.cancel()]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::Boolean(true));
+            assert_eq!(error_kind(&vec[1]).as_deref(), Some("CancelledError"));
+            assert_eq!(vec[2], Value::Boolean(true));
+            assert_eq!(vec[3], Value::Boolean(false));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_all_race_any() {
+    let source = r#"
+        let after = async (ms, v) => do {
+            await sleep(ms)
+            v
+        }
+        let fail = async (ms) => do {
+            await sleep(ms)
+            throw "boom"
+        }
+        let everything = await all_tasks([spawn(after, 20, "a"), spawn(after, 5, "b"), 7])
+        let first = await race([spawn(after, 50, "slow"), spawn(after, 5, "fast")])
+        let ok = await any_task([spawn(fail, 1), spawn(after, 10, "ok")])
+        let none = await any_task([spawn(fail, 1), spawn(fail, 2)])
+        let slow = spawn(after, 10000, "x")
+        let failed = await all_tasks([spawn(fail, 5), slow])
+        [everything, first, ok, none, failed, all(x => x > 0, [1, 2])]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            match &vec[0] {
+                Value::Vector(items) => assert_eq!(
+                    *items.read(),
+                    vec![
+                        Value::String("a".to_string()),
+                        Value::String("b".to_string()),
+                        Value::Number(7.0)
+                    ]
+                ),
+                other => panic!("Expected Vector, got {:?}", other),
+            }
+            assert_eq!(vec[1], Value::String("fast".to_string()));
+            assert_eq!(vec[2], Value::String("ok".to_string()));
+            assert_eq!(error_kind(&vec[3]).as_deref(), Some("AggregateError"));
+            assert_eq!(error_kind(&vec[4]).as_deref(), Some("RuntimeError"));
+            assert_eq!(vec[5], Value::Boolean(true));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_predicates_and_combinators_check_their_arguments() {
+    let err = execute("all([true, false])").unwrap_err();
+    assert!(err.contains("expects 2"), "{}", err);
+    let err = execute("any([spawn(() => 1)])").unwrap_err();
+    assert!(err.contains("expects 2"), "{}", err);
+
+    let err = execute("all_tasks([true], [false])").unwrap_err();
+    assert!(err.contains("expects 1"), "{}", err);
+    let err = execute("race(5)").unwrap_err();
+    assert!(err.contains("Vector of tasks or futures"), "{}", err);
+}
+
+#[test]
+fn test_timeout_cancels_task() {
+    let source = r#"
+        let t = spawn(async () => do {
+            await sleep(10000)
+            1
+        })
+        let timed_out = await timeout(10, t)
+        await sleep(5)
+        let fast = await timeout(1000, spawn(async () => 42))
+        [timed_out, t.is_done(), fast]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(error_kind(&vec[0]).as_deref(), Some("TimeoutError"));
+            assert_eq!(vec[1], Value::Boolean(true));
+            assert_eq!(vec[2], Value::Number(42.0));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_task_group_waits_for_children() {
+    let source = r#"
+        let log = AsyncMutex([])
+        let result = await task_group(async () => do {
+            spawn(async () => do {
+                await sleep(20)
+                let g = await log.lock()
+                push(g.get(), "child")
+            })
+            "body"
+        })
+        let g = await log.lock()
+        [result, len(g.get())]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(vec[0], Value::String("body".to_string()));
+            assert_eq!(vec[1], Value::Number(1.0));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_task_group_cancels_siblings_on_error() {
+    let source = r#"
+        let handles = []
+        let result = await task_group(async () => do {
+            push(handles, spawn(async () => do {
+                await sleep(10000)
+                "never"
+            }))
+            spawn(async () => do {
+                await sleep(5)
+                throw "stage failed"
+            })
+            "body"
+        })
+        await sleep(5)
+        let slow = handles[0]
+        [result, slow.is_done(), await slow]
+    "#;
+    let result = execute(source).unwrap();
+    match result {
+        Value::Vector(v) => {
+            let vec = v.read();
+            assert_eq!(error_kind(&vec[0]).as_deref(), Some("RuntimeError"));
+            assert_eq!(vec[1], Value::Boolean(true));
+            assert_eq!(error_kind(&vec[2]).as_deref(), Some("CancelledError"));
+        }
+        _ => panic!("Expected Vector, got {:?}", result),
+    }
+}
@@ -66,6 +66,14 @@ impl VM {
                 Ok(ExecutionResult::Continue)
             }
 
+            OpCode::MatMul => {
+                let left = self.get_register(b)?;
+                let right = self.get_register(c)?;
+                let result = ValueOperations::matmul_values(left, right)?;
+                self.set_register(a, result)?;
+                Ok(ExecutionResult::Continue)
+            }
+
             OpCode::Neg => {
                 let value = self.get_register(b)?;
                 let result = ValueOperations::neg_value(value)?;
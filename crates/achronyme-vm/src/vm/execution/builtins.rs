@@ -5,8 +5,49 @@
 
 use crate::error::VmError;
 use crate::opcode::instruction::*;
+use crate::value::Value;
 use crate::vm::result::ExecutionResult;
-use crate::vm::VM;
+use crate::vm::{VmIterator, VM};
+use achronyme_types::sync::shared;
+
+/// Built-ins that take a lazy `Range` argument as it is: they count through
+/// it themselves or only describe it
+const RANGE_BUILTINS: &[&str] = &[
+    "iter",
+    "par_map",
+    "par_reduce",
+    "par_for",
+    "print",
+    "str",
+    "typeof",
+    "describe",
+];
+
+/// Longest lazy range a built-in call expands into a vector
+const MAX_EXPANDED_RANGE: f64 = 1e8;
+
+/// Expand a lazy range into the vector a literal range would have built
+fn expand_range(range: &Value, builtin: &str) -> Result<Value, VmError> {
+    if let Value::Range {
+        start: lo, end: hi, ..
+    } = range
+    {
+        if let (Value::Number(lo), Value::Number(hi)) = (lo.as_ref(), hi.as_ref()) {
+            if hi - lo > MAX_EXPANDED_RANGE {
+                return Err(VmError::Runtime(format!(
+                    "{}() cannot expand the range {}..{} into a vector; use iter() to count through it",
+                    builtin, lo, hi
+                )));
+            }
+        }
+    }
+    let mut iter = VmIterator::from_value(range)?;
+    let mut items = Vec::new();
+    while let Some(item) = iter.next() {
+        items.push(item);
+    }
+    Ok(Value::Vector(shared(items)))
+}
 
 impl VM {
     /// Execute CallBuiltin opcode
@@ -38,9 +79,16 @@ impl VM {
         let args_start = (dest as usize + 1) % 256;
         let mut args = Vec::with_capacity(argc as usize);
 
+        let lazy_ranges = RANGE_BUILTINS.contains(&metadata.name.as_str());
         for i in 0..argc {
             let reg_idx = ((args_start + i as usize) % 256) as u8;
-            args.push(self.get_register(reg_idx)?.clone());
+            let arg = self.get_register(reg_idx)?.clone();
+            match arg {
+                Value::Range { .. } if !lazy_ranges => {
+                    args.push(expand_range(&arg, &metadata.name)?)
+                }
+                _ => args.push(arg),
+            }
         }
 
         // Validate arity for non-variadic functions
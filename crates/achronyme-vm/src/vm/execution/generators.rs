@@ -3,10 +3,12 @@
 use crate::error::VmError;
 use crate::opcode::{instruction::*, OpCode};
 use crate::value::Value;
-use crate::vm::execution::iterators::{NativeIterator, StringIterator, VectorIterator};
+use crate::vm::execution::iterators::{
+    DataFrameIterator, NativeIterator, StringIterator, VectorIterator,
+};
 use crate::vm::generator::{VmGeneratorRef, VmGeneratorState};
 use crate::vm::result::ExecutionResult;
-use crate::vm::VM;
+use crate::vm::{LazyIterator, VM};
 use achronyme_types::sync::{shared, Arc, RwLock};
 use std::any::Any;
 
@@ -90,18 +92,26 @@ impl VM {
 
             OpCode::MakeIterator => {
                 // R[A] = MakeIterator(R[B])
-                // Wraps vectors/strings in native iterators, passes generators through
+                // Wraps vectors/strings/dataframes in native iterators, passes generators through
                 let dst = a;
                 let src = decode_b(instruction);
 
                 let value = self.get_register(src)?.clone();
 
                 match value {
-                    // Generators pass through unchanged
-                    Value::Generator(_) => {
+                    // Generators pass through unchanged, as do channel
+                    // receivers (each step awaits the next message) and lazy
+                    // iterators (each step runs their adapters)
+                    Value::Generator(_) | Value::Receiver(_) | Value::Iterator(_) => {
                         self.set_register(dst, value)?;
                     }
 
+                    // Lazy ranges count through their numbers
+                    Value::Range { .. } => {
+                        let iter = LazyIterator::from_value(&value)?;
+                        self.set_register(dst, Value::Iterator(iter))?;
+                    }
+
                     // Vectors get wrapped in VectorIterator
                     Value::Vector(vec_ref) => {
                         let iter = NativeIterator::Vector(VectorIterator::new(vec_ref));
@@ -119,6 +129,14 @@ impl VM {
                         self.set_register(dst, Value::Generator(iter_arc))?;
                     }
 
+                    // DataFrames iterate over their rows as records
+                    Value::DataFrame(df) => {
+                        let iter = NativeIterator::DataFrame(DataFrameIterator::new(df));
+                        let iter_lock = RwLock::new(iter);
+                        let iter_arc: Arc<dyn Any + Send + Sync> = Arc::new(iter_lock);
+                        self.set_register(dst, Value::Generator(iter_arc))?;
+                    }
+
                     // Other types cannot be iterated
                     _ => {
                         return Err(VmError::Runtime(format!(
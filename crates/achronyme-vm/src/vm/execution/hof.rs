@@ -220,7 +220,7 @@ impl VM {
 mod tests {
     use super::*;
     use crate::opcode::OpCode;
-    use achronyme_types::sync::{shared, Arc, RwLock};
+    use achronyme_types::sync::{shared, Arc};
 
     #[test]
     fn test_iter_init_vector() {
@@ -4,8 +4,11 @@
 //! that can be used in for-in loops. These iterators are wrapped as Generator values and
 //! work seamlessly with the existing generator infrastructure.
 
+use crate::builtins::csv_io::CsvRowIterator;
+use crate::error::VmError;
 use crate::value::Value;
-use achronyme_types::sync::Shared;
+use achronyme_types::dataframe::DataFrame;
+use achronyme_types::sync::{Arc, Shared};
 
 /// Iterator for Vector values
 /// Iterates over elements of a vector in order
@@ -77,20 +80,58 @@ impl StringIterator {
     }
 }
 
+/// Iterator for DataFrame values
+/// Iterates over rows, yielding each one as a record keyed by column name
+#[derive(Debug)]
+pub struct DataFrameIterator {
+    /// The source frame
+    source: Arc<DataFrame>,
+    /// Current row index
+    current_index: usize,
+}
+
+impl DataFrameIterator {
+    /// Create a new row iterator
+    pub fn new(frame: Arc<DataFrame>) -> Self {
+        Self {
+            source: frame,
+            current_index: 0,
+        }
+    }
+
+    /// Get the next row from the iterator
+    /// Returns Some(Value::Record) if there are more rows, None if exhausted
+    pub fn next(&mut self) -> Option<Value> {
+        if self.current_index < self.source.nrows() {
+            let row = crate::builtins::dataframe::row_record(&self.source, self.current_index);
+            self.current_index += 1;
+            Some(row)
+        } else {
+            None
+        }
+    }
+}
+
 /// Enum to hold different types of native iterators
 /// Uses type erasure pattern similar to Value::Generator
 #[derive(Debug)]
 pub enum NativeIterator {
     Vector(VectorIterator),
     String(StringIterator),
+    DataFrame(DataFrameIterator),
+    Csv(Box<CsvRowIterator>),
 }
 
 impl NativeIterator {
     /// Get the next value from any native iterator
-    pub fn next(&mut self) -> Option<Value> {
+    ///
+    /// Only file-backed iterators (CSV rows) can fail.
+    pub fn next(&mut self) -> Result<Option<Value>, VmError> {
         match self {
-            NativeIterator::Vector(iter) => iter.next(),
-            NativeIterator::String(iter) => iter.next(),
+            NativeIterator::Vector(iter) => Ok(iter.next()),
+            NativeIterator::String(iter) => Ok(iter.next()),
+            NativeIterator::DataFrame(iter) => Ok(iter.next()),
+            NativeIterator::Csv(iter) => iter.next(),
         }
     }
 }
@@ -98,6 +139,7 @@ impl NativeIterator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use achronyme_types::sync::shared;
 
     #[test]
     fn test_vector_iterator() {
@@ -154,16 +196,16 @@ mod tests {
         let vec = shared(vec![Value::Number(42.0)]);
         let mut iter = NativeIterator::Vector(VectorIterator::new(vec));
 
-        assert_eq!(iter.next(), Some(Value::Number(42.0)));
-        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next().unwrap(), Some(Value::Number(42.0)));
+        assert_eq!(iter.next().unwrap(), None);
     }
 
     #[test]
     fn test_native_iterator_string() {
         let mut iter = NativeIterator::String(StringIterator::new("hi".to_string()));
 
-        assert_eq!(iter.next(), Some(Value::String("h".to_string())));
-        assert_eq!(iter.next(), Some(Value::String("i".to_string())));
-        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next().unwrap(), Some(Value::String("h".to_string())));
+        assert_eq!(iter.next().unwrap(), Some(Value::String("i".to_string())));
+        assert_eq!(iter.next().unwrap(), None);
     }
 }
@@ -115,7 +115,7 @@ impl VM {
                 let rec_value = self.get_register(rec_reg)?.clone();
 
                 match &rec_value {
-                    Value::Record(rec_rc) => {
+                    Value::Record(_) | Value::Variant(_) => {
                         // Get pattern descriptor (vector of field names)
                         let pattern_const = self.get_constant(pattern_idx)?;
                         let field_names = match pattern_const {
@@ -144,18 +144,23 @@ impl VM {
                             }
                         };
 
-                        // Extract all fields at once while we have the borrow
+                        // Extract all fields at once
                         // If a field doesn't exist, use null (for default value support)
-                        let rec_borrowed = rec_rc.read();
-                        let mut values = Vec::new();
-                        for field_name in field_names.iter() {
-                            let value =
-                                rec_borrowed.get(field_name).cloned().unwrap_or(Value::Null); // Use null if field doesn't exist
-                            values.push(value);
-                        }
-
-                        // Drop the borrow before setting registers
-                        drop(rec_borrowed);
+                        let values: Vec<Value> = match &rec_value {
+                            Value::Record(rec_rc) => {
+                                let rec_borrowed = rec_rc.read();
+                                field_names
+                                    .iter()
+                                    .map(|name| rec_borrowed.get(name).cloned())
+                                    .map(|value| value.unwrap_or(Value::Null))
+                                    .collect()
+                            }
+                            Value::Variant(variant) => field_names
+                                .iter()
+                                .map(|name| variant.field(name).cloned().unwrap_or(Value::Null))
+                                .collect(),
+                            _ => unreachable!(),
+                        };
 
                         // Now set the registers
                         for (i, value) in values.into_iter().enumerate() {
@@ -181,6 +186,11 @@ impl VM {
 
     /// Check if a value matches a type name
     fn check_type_match(&self, value: &Value, type_name: &str) -> bool {
+        // Variant patterns match against the qualified name `Type.Variant`
+        if let Value::Variant(variant) = value {
+            return type_name.split_once('.')
+                == Some((variant.type_name.as_str(), variant.variant.as_str()));
+        }
         matches!(
             (value, type_name),
             (Value::Number(_), "Number")
@@ -203,6 +213,10 @@ impl VM {
                 | (Value::AsyncMutex(_), "AsyncMutex")
                 | (Value::MutexGuard(_), "MutexGuard")
                 | (Value::Signal(_), "Signal")
+                | (Value::Effect(_), "Effect")
+                | (Value::DataFrame(_), "DataFrame")
+                | (Value::Bytes(_), "Bytes")
+                | (Value::Task(_), "Task")
         )
     }
 }
@@ -5,7 +5,8 @@ use crate::opcode::{instruction::*, OpCode};
 use crate::value::Value;
 use crate::vm::result::ExecutionResult;
 use crate::vm::VM;
-use achronyme_types::sync::shared;
+use achronyme_types::sync::{shared, Arc};
+use achronyme_types::value::VariantValue;
 use std::collections::HashMap;
 
 impl VM {
@@ -28,7 +29,7 @@ impl VM {
                 Ok(ExecutionResult::Continue)
             }
 
-            OpCode::GetField => {
+            OpCode::GetField | OpCode::GetMethod => {
                 // R[A] = R[B][K[C]]
                 let dst = a;
                 let rec_reg = b;
@@ -47,6 +48,23 @@ impl VM {
                     // Field not found in Record map, proceed to check intrinsics
                 }
 
+                if let Value::Variant(variant) = &rec_value {
+                    if let Some(val) = variant.field(field_name) {
+                        self.set_register(dst, val.clone())?;
+                        return Ok(ExecutionResult::Continue);
+                    }
+                }
+
+                // DataFrame columns are accessed with field syntax (df.price);
+                // a call (df.head(1)) looks for a method first
+                if let (Value::DataFrame(df), OpCode::GetField) = (&rec_value, opcode) {
+                    if let Some(column) = df.column(field_name) {
+                        let column = crate::builtins::dataframe::column_to_value(column);
+                        self.set_register(dst, column)?;
+                        return Ok(ExecutionResult::Continue);
+                    }
+                }
+
                 // 2. Attempt to find intrinsic method
                 if let Some(type_disc) =
                     crate::vm::intrinsics::TypeDiscriminant::from_value(&rec_value)
@@ -103,6 +121,18 @@ impl VM {
                 }
 
                 // 4. Field not found
+                if let Value::DataFrame(df) = &rec_value {
+                    if let Some(column) = df.column(field_name) {
+                        let column = crate::builtins::dataframe::column_to_value(column);
+                        self.set_register(dst, column)?;
+                        return Ok(ExecutionResult::Continue);
+                    }
+                    return Err(VmError::Runtime(format!(
+                        "Unknown column '{}' (columns: {})",
+                        field_name,
+                        df.names().join(", ")
+                    )));
+                }
                 if let Value::Record(_) = rec_value {
                     Err(VmError::Runtime(format!(
                         "Field '{}' not found in record",
@@ -133,6 +163,10 @@ impl VM {
                         rec_borrowed.insert(field_name.to_string(), new_value);
                         Ok(ExecutionResult::Continue)
                     }
+                    Value::Variant(variant) => Err(VmError::Runtime(format!(
+                        "Cannot assign to field '{}' of {}: variant fields are immutable",
+                        field_name, variant.variant
+                    ))),
                     _ => Err(VmError::TypeError {
                         operation: "record field assignment".to_string(),
                         expected: "Record".to_string(),
@@ -141,6 +175,52 @@ impl VM {
                 }
             }
 
+            OpCode::NewVariant => {
+                // R[A] = variant K[C] with fields R[B], R[B+1], ...
+                let dst = a;
+                let fields_start = b;
+                let desc_idx = c as usize;
+
+                let mut names = match self.get_constant(desc_idx)? {
+                    Value::Vector(desc) => desc
+                        .read()
+                        .iter()
+                        .map(|name| match name {
+                            Value::String(s) => Ok(s.clone()),
+                            _ => Err(VmError::Runtime(
+                                "NewVariant descriptor must contain strings".to_string(),
+                            )),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                    _ => {
+                        return Err(VmError::Runtime(
+                            "NewVariant descriptor must be a vector".to_string(),
+                        ))
+                    }
+                };
+                if names.len() < 2 {
+                    return Err(VmError::Runtime(
+                        "NewVariant descriptor needs a type and a variant name".to_string(),
+                    ));
+                }
+                let field_names = names.split_off(2);
+                let variant = names.pop().unwrap_or_default();
+                let type_name = names.pop().unwrap_or_default();
+
+                let mut fields = Vec::with_capacity(field_names.len());
+                for (name, reg) in field_names.into_iter().zip(fields_start..) {
+                    fields.push((name, self.get_register(reg)?.clone()));
+                }
+
+                let value = Value::Variant(Arc::new(VariantValue {
+                    type_name,
+                    variant,
+                    fields,
+                }));
+                self.set_register(dst, value)?;
+                Ok(ExecutionResult::Continue)
+            }
+
             _ => unreachable!("Non-record opcode in record handler"),
         }
     }
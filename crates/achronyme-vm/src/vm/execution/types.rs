@@ -1,5 +1,9 @@
 //! Type checking and assertion execution
 
+use std::collections::HashMap;
+
+use achronyme_parser::{parse_type, Dimension, TypeAnnotation};
+
 use crate::error::VmError;
 use crate::opcode::{instruction::*, OpCode};
 use crate::value::Value;
@@ -44,7 +48,7 @@ impl VM {
                     let error_msg = format!(
                         "Type assertion failed: expected {}, got {}",
                         type_name,
-                        self.value_type_name(&value)
+                        self.describe_value_type(&value, type_name)
                     );
                     let error = Value::Error {
                         message: error_msg,
@@ -73,7 +77,7 @@ impl VM {
             "Record" => matches!(value, Value::Record(_)),
             "Function" => matches!(value, Value::Function(_)),
             "Generator" => matches!(value, Value::Generator(_)),
-            "Future" => matches!(value, Value::Future(_)),
+            "Future" => matches!(value, Value::Future(_) | Value::Task(_)),
             "Iterator" => matches!(value, Value::Iterator(_)),
             "Null" => matches!(value, Value::Null),
             "Range" => matches!(value, Value::Range { .. }),
@@ -85,11 +89,65 @@ impl VM {
             "AsyncMutex" => matches!(value, Value::AsyncMutex(_)),
             "MutexGuard" => matches!(value, Value::MutexGuard(_)),
             "Signal" => matches!(value, Value::Signal(_)),
+            "Effect" => matches!(value, Value::Effect(_)),
+            "DataFrame" => matches!(value, Value::DataFrame(_)),
+            "Bytes" => matches!(value, Value::Bytes(_)),
+            "Task" => matches!(value, Value::Task(_)),
             "Any" => true, // Any type always matches
-            _ => false,    // Unknown type name
+            _ if type_name.starts_with("Tensor<") => self.check_tensor_type(value, type_name),
+            // Declared data types: `type Shape = Circle {..} | Rect {..}`
+            _ => matches!(value, Value::Variant(variant) if variant.type_name == type_name),
         }
     }
 
+    /// Whether a tensor matches a shaped annotation such as
+    /// `Tensor<Number, [2, n]>`: the rank and fixed sizes must agree, and a
+    /// shape variable stands for the same size everywhere it appears.
+    /// Each annotation is parsed once and then read from `tensor_types`.
+    fn check_tensor_type(&self, value: &Value, type_name: &str) -> bool {
+        if !self.tensor_types.read().contains_key(type_name) {
+            self.tensor_types
+                .write()
+                .insert(type_name.to_string(), parse_type(type_name).ok());
+        }
+        let cache = self.tensor_types.read();
+        let Some(TypeAnnotation::Tensor {
+            element_type,
+            shape,
+        }) = &cache[type_name]
+        else {
+            return false;
+        };
+        let actual = match (value, element_type.as_ref()) {
+            (Value::Tensor(t), TypeAnnotation::Number | TypeAnnotation::Any) => t.shape(),
+            (Value::ComplexTensor(t), TypeAnnotation::Complex | TypeAnnotation::Any) => t.shape(),
+            _ => return false,
+        };
+        let Some(dims) = shape else {
+            return true;
+        };
+        let mut sizes = HashMap::new();
+        dims.len() == actual.len()
+            && dims.iter().zip(actual).all(|(dim, &size)| match dim {
+                Dimension::Fixed(expected) => *expected == size,
+                Dimension::Var(name) => *sizes.entry(name).or_insert(size) == size,
+                Dimension::Unknown => true,
+            })
+    }
+
+    /// What a value is, for a failed assertion against `type_name`; shaped
+    /// tensor annotations get the actual shape
+    fn describe_value_type(&self, value: &Value, type_name: &str) -> String {
+        let (element, shape) = match value {
+            Value::Tensor(t) if type_name.starts_with("Tensor<") => ("Number", t.shape()),
+            Value::ComplexTensor(t) if type_name.starts_with("Tensor<") => ("Complex", t.shape()),
+            Value::Variant(variant) => return variant.type_name.clone(),
+            _ => return self.value_type_name(value).to_string(),
+        };
+        let dims: Vec<String> = shape.iter().map(|size| size.to_string()).collect();
+        format!("Tensor<{}, [{}]>", element, dims.join(", "))
+    }
+
     /// Get type name of a value for error messages
     fn value_type_name(&self, value: &Value) -> &'static str {
         match value {
@@ -121,6 +179,11 @@ impl VM {
             Value::AsyncMutex(_) => "AsyncMutex",
             Value::MutexGuard(_) => "MutexGuard",
             Value::Signal(_) => "Signal",
+            Value::Effect(_) => "Effect",
+            Value::DataFrame(_) => "DataFrame",
+            Value::Bytes(_) => "Bytes",
+            Value::Task(_) => "Task",
+            Value::Variant(_) => "Variant",
         }
     }
 }
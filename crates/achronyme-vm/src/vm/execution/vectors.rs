@@ -230,6 +230,25 @@ impl VM {
                         }
                         Ok(ExecutionResult::Continue)
                     }
+                    (Value::Bytes(bytes), Value::Number(idx)) => {
+                        let len = bytes.len();
+                        let index = *idx as isize;
+                        let actual_idx = if index < 0 {
+                            (len as isize + index) as usize
+                        } else {
+                            index as usize
+                        };
+
+                        if actual_idx >= len {
+                            return Err(VmError::Runtime(format!(
+                                "Index out of bounds: {} (length: {})",
+                                index, len
+                            )));
+                        }
+
+                        self.set_register(dst, Value::Number(bytes[actual_idx] as f64))?;
+                        Ok(ExecutionResult::Continue)
+                    }
                     (Value::Vector(_), _) => Err(VmError::TypeError {
                         operation: "vector indexing".to_string(),
                         expected: "Number".to_string(),
@@ -526,8 +545,8 @@ impl VM {
                 }
             }
 
-            OpCode::RangeEx => {
-                // R[A] = R[B]..R[C] (Create Range value)
+            OpCode::RangeEx | OpCode::RangeIn => {
+                // R[A] = R[B]..R[C] or R[B]..=R[C] (Create Range value)
                 let dst = a;
                 let start_reg = b;
                 let end_reg = c;
@@ -538,7 +557,7 @@ impl VM {
                 let range = Value::Range {
                     start: Box::new(start),
                     end: Box::new(end),
-                    inclusive: false,
+                    inclusive: opcode == OpCode::RangeIn,
                 };
                 self.set_register(dst, range)?;
                 Ok(ExecutionResult::Continue)
@@ -27,6 +27,11 @@ pub enum TypeDiscriminant {
     AsyncMutex,
     MutexGuard,
     Signal,
+    Effect,
+    DataFrame,
+    Bytes,
+    Task,
+    Iterator,
 }
 
 impl TypeDiscriminant {
@@ -45,6 +50,11 @@ impl TypeDiscriminant {
             Value::AsyncMutex(_) => Some(TypeDiscriminant::AsyncMutex),
             Value::MutexGuard(_) => Some(TypeDiscriminant::MutexGuard),
             Value::Signal(_) => Some(TypeDiscriminant::Signal),
+            Value::Effect(_) => Some(TypeDiscriminant::Effect),
+            Value::DataFrame(_) => Some(TypeDiscriminant::DataFrame),
+            Value::Bytes(_) => Some(TypeDiscriminant::Bytes),
+            Value::Task(_) => Some(TypeDiscriminant::Task),
+            Value::Iterator(_) => Some(TypeDiscriminant::Iterator),
             _ => None,
         }
     }
@@ -68,6 +78,12 @@ pub struct IntrinsicRegistry {
     methods: HashMap<(TypeDiscriminant, String), IntrinsicFn>,
 }
 
+impl Default for IntrinsicRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl IntrinsicRegistry {
     /// Create a new intrinsic registry with default intrinsics registered
     pub fn new() -> Self {
@@ -350,16 +366,7 @@ impl IntrinsicRegistry {
         );
 
         // === Concurrency Methods ===
-        self.register(
-            TypeDiscriminant::Sender,
-            "send",
-            crate::builtins::concurrency::vm_sender_send,
-        );
-        self.register(
-            TypeDiscriminant::Receiver,
-            "recv",
-            crate::builtins::concurrency::vm_receiver_recv,
-        );
+        self.register_channel_intrinsics();
 
         // === AsyncMutex Methods ===
         self.register(
@@ -396,5 +403,168 @@ impl IntrinsicRegistry {
             "set",
             crate::builtins::reactive::vm_signal_set,
         );
+
+        // === Effect Methods ===
+        self.register(
+            TypeDiscriminant::Effect,
+            "dispose",
+            crate::builtins::reactive::vm_effect_dispose,
+        );
+        self.register(
+            TypeDiscriminant::Effect,
+            "disposed",
+            crate::builtins::reactive::vm_effect_disposed,
+        );
+
+        // === Task Methods ===
+        self.register(
+            TypeDiscriminant::Task,
+            "join",
+            crate::builtins::tasks::vm_task_join,
+        );
+        self.register(
+            TypeDiscriminant::Task,
+            "cancel",
+            crate::builtins::tasks::vm_task_cancel,
+        );
+        self.register(
+            TypeDiscriminant::Task,
+            "is_done",
+            crate::builtins::tasks::vm_task_is_done,
+        );
+
+        // === DataFrame Methods ===
+        self.register_dataframe_intrinsics();
+
+        // === Bytes Methods ===
+        self.register_bytes_intrinsics();
+
+        // === Iterator Methods ===
+        // Lazy adapters and consumers, on iterators and on generators alike
+        // (`gen().map(f).take(3).collect()`)
+        {
+            use crate::builtins::{array_advanced, hof, iterators, statistics};
+
+            let methods: [(&str, IntrinsicFn); 14] = [
+                ("map", adapt_last!(hof::vm_map)),
+                ("filter", adapt_last!(hof::vm_filter)),
+                ("take_while", adapt!(iterators::vm_iter_take_while)),
+                ("take", adapt!(array_advanced::vm_take)),
+                ("enumerate", adapt!(iterators::vm_iter_enumerate)),
+                ("zip", adapt!(array_advanced::vm_zip)),
+                ("chain", adapt!(iterators::vm_iter_chain)),
+                ("window", adapt!(iterators::vm_iter_window)),
+                ("step_by", adapt!(iterators::vm_iter_step_by)),
+                ("collect", adapt!(iterators::vm_collect)),
+                ("reduce", adapt_last!(hof::vm_reduce)),
+                ("any", adapt_last!(hof::vm_any)),
+                ("all", adapt_last!(hof::vm_all)),
+                ("sum", adapt!(statistics::vm_sum)),
+            ];
+            for (name, func) in methods {
+                self.register(TypeDiscriminant::Iterator, name, func);
+                self.register(TypeDiscriminant::Generator, name, func);
+            }
+            self.register(
+                TypeDiscriminant::Iterator,
+                "next",
+                adapt!(iterators::vm_iter_next),
+            );
+        }
+    }
+
+    /// Register channel methods (`tx.send(v)`, `rx.recv()`, ...)
+    fn register_channel_intrinsics(&mut self) {
+        use crate::builtins::concurrency as ch;
+
+        let sender_methods: [(&str, IntrinsicFn); 4] = [
+            ("send", ch::vm_sender_send),
+            ("try_send", ch::vm_sender_try_send),
+            ("close", ch::vm_sender_close),
+            ("subscribe", ch::vm_sender_subscribe),
+        ];
+        for (name, func) in sender_methods {
+            self.register(TypeDiscriminant::Sender, name, func);
+        }
+
+        let receiver_methods: [(&str, IntrinsicFn); 4] = [
+            ("recv", ch::vm_receiver_recv),
+            ("try_recv", ch::vm_receiver_try_recv),
+            ("get", ch::vm_receiver_get),
+            ("close", ch::vm_receiver_close),
+        ];
+        for (name, func) in receiver_methods {
+            self.register(TypeDiscriminant::Receiver, name, func);
+        }
+    }
+
+    /// Register DataFrame methods (`df.select(...)`, `df.group_by(...).agg(...)`, ...)
+    fn register_dataframe_intrinsics(&mut self) {
+        use crate::builtins::dataframe as df;
+
+        let methods: [(&str, IntrinsicFn); 13] = [
+            ("columns", df::vm_df_columns),
+            ("nrows", df::vm_df_nrows),
+            ("select", df::vm_df_select),
+            ("filter", df::vm_df_filter),
+            ("sort_by", df::vm_df_sort_by),
+            ("head", df::vm_df_head),
+            ("describe", df::vm_df_describe),
+            ("group_by", df::vm_df_group_by),
+            ("agg", df::vm_df_agg),
+            ("join", df::vm_df_join),
+            ("to_records", df::vm_df_to_records),
+            ("to_csv", df::vm_df_to_csv),
+            ("to_json", df::vm_df_to_json),
+        ];
+        for (name, func) in methods {
+            self.register(TypeDiscriminant::DataFrame, name, func);
+        }
+    }
+
+    /// Register Bytes methods (`data.slice(0, 4)`, `data.unpack("<f32[*]")`, ...)
+    fn register_bytes_intrinsics(&mut self) {
+        use crate::builtins::binary;
+
+        let methods: [(&str, IntrinsicFn); 5] = [
+            ("len", binary::vm_bytes_len),
+            ("slice", binary::vm_bytes_slice),
+            ("to_vector", binary::vm_bytes_to_vector),
+            ("to_string", binary::vm_bytes_to_string),
+            ("unpack", binary::vm_bytes_unpack),
+        ];
+        for (name, func) in methods {
+            self.register(TypeDiscriminant::Bytes, name, func);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use achronyme_metadata::methods::Receiver;
+
+    #[test]
+    fn test_primitive_methods_match_metadata() {
+        let registry = IntrinsicRegistry::new();
+        for receiver in Receiver::ALL {
+            let discriminant = match receiver {
+                Receiver::Number => TypeDiscriminant::Number,
+                Receiver::Boolean => TypeDiscriminant::Boolean,
+                Receiver::Complex => TypeDiscriminant::Complex,
+                Receiver::String => TypeDiscriminant::String,
+                Receiver::Record => TypeDiscriminant::Record,
+            };
+            let mut registered: Vec<&str> = registry
+                .methods
+                .keys()
+                .filter(|(ty, _)| *ty == discriminant)
+                .map(|(_, name)| name.as_str())
+                .collect();
+            registered.sort();
+            let mut listed = receiver.methods().to_vec();
+            listed.sort();
+            assert_eq!(registered, listed, "{:?}", receiver);
+        }
     }
 }
@@ -2,11 +2,15 @@
 //!
 //! This module provides the foundation for HOF operations by offering:
 //! - VmIterator: Safe, uniform iteration over all collection types
+//! - LazyIterator: On-demand adapter chains over collections and generators
 //! - VmBuilder: Efficient construction of result collections with type preservation
 
+use crate::builtins::hof::is_truthy;
 use crate::error::VmError;
 use crate::value::Value;
-use achronyme_types::sync::{shared, Shared};
+use crate::vm::VM;
+use achronyme_types::sync::{shared, RwLock, Shared};
+use std::collections::VecDeque;
 
 /// VM-level iterator abstraction for safe traversal of collections
 ///
@@ -67,9 +71,32 @@ impl VmIterator {
                 index: 0,
             }),
 
+            Value::Range {
+                start,
+                end,
+                inclusive,
+            } => match (start.as_ref(), end.as_ref()) {
+                // Whole numbers from `start`, like a range built at compile time:
+                // 1..=2.5 is [1, 2]
+                (Value::Number(start), Value::Number(end)) => Ok(VmIterator::Range {
+                    current: start.trunc(),
+                    end: if *inclusive {
+                        end.trunc() + 1.0
+                    } else {
+                        end.trunc()
+                    },
+                    step: 1.0,
+                }),
+                _ => Err(VmError::TypeError {
+                    operation: "iterator creation".to_string(),
+                    expected: "a range of numbers".to_string(),
+                    got: format!("{:?}", value),
+                }),
+            },
+
             _ => Err(VmError::TypeError {
                 operation: "iterator creation".to_string(),
-                expected: "Vector, String, or Tensor".to_string(),
+                expected: "Vector, String, Tensor, or Range".to_string(),
                 got: format!("{:?}", value),
             }),
         }
@@ -150,6 +177,237 @@ impl VmIterator {
     }
 }
 
+/// Shared handle to a lazy iterator, stored in `Value::Iterator`
+pub type LazyIteratorRef = Shared<LazyIterator>;
+
+/// Lazy iterator: a source pulled on demand through a chain of adapters
+///
+/// Nothing is evaluated until a consumer (`collect`, a `for` loop or a
+/// reduction) asks for the next value, so generators that never finish can
+/// still be mapped, filtered and cut short. Adapters hold their source by
+/// reference: advancing an adapter advances the iterator it was built from.
+#[derive(Debug)]
+pub enum LazyIterator {
+    /// A collection traversed by a VmIterator
+    Collection(VmIterator),
+
+    /// A generator (or native iterator), resumed once per value
+    Generator(Value),
+
+    /// Applies a callback to each value
+    Map {
+        source: LazyIteratorRef,
+        callback: Value,
+    },
+
+    /// Keeps the values matching a predicate
+    Filter {
+        source: LazyIteratorRef,
+        predicate: Value,
+    },
+
+    /// Stops at the first value not matching a predicate
+    TakeWhile {
+        source: LazyIteratorRef,
+        predicate: Value,
+        done: bool,
+    },
+
+    /// Stops after `remaining` more values
+    Take {
+        source: LazyIteratorRef,
+        remaining: usize,
+    },
+
+    /// Pairs each value with its position: `[index, value]`
+    Enumerate {
+        source: LazyIteratorRef,
+        index: usize,
+    },
+
+    /// Pairs values from two iterators, stopping when either runs out
+    Zip {
+        left: LazyIteratorRef,
+        right: LazyIteratorRef,
+    },
+
+    /// All values of `first`, then all values of `second`
+    Chain {
+        first: LazyIteratorRef,
+        second: LazyIteratorRef,
+    },
+
+    /// Sliding windows of `size` consecutive values
+    Window {
+        source: LazyIteratorRef,
+        size: usize,
+        buffer: VecDeque<Value>,
+    },
+
+    /// The first value, then every `step`-th one after it
+    StepBy {
+        source: LazyIteratorRef,
+        step: usize,
+        started: bool,
+    },
+}
+
+impl LazyIterator {
+    /// Create a lazy iterator over a Value
+    ///
+    /// A lazy iterator is returned as is (sharing its position); generators
+    /// and collections get a new one.
+    pub fn from_value(value: &Value) -> Result<LazyIteratorRef, VmError> {
+        match value {
+            Value::Iterator(any_ref) => any_ref
+                .clone()
+                .downcast::<RwLock<LazyIterator>>()
+                .map_err(|_| VmError::Runtime("Invalid iterator type".to_string())),
+            Value::Generator(_) => Ok(shared(LazyIterator::Generator(value.clone()))),
+            _ => Ok(shared(LazyIterator::Collection(VmIterator::from_value(
+                value,
+            )?))),
+        }
+    }
+
+    /// Whether a value should be processed lazily (generators and iterators)
+    pub fn is_lazy(value: &Value) -> bool {
+        matches!(value, Value::Iterator(_) | Value::Generator(_))
+    }
+
+    /// Wrap an adapter as a `Value::Iterator`
+    pub fn into_value(self) -> Value {
+        Value::Iterator(shared(self))
+    }
+
+    /// Pull the next value from a shared iterator
+    pub fn pull(iter: &LazyIteratorRef, vm: &mut VM) -> Result<Option<Value>, VmError> {
+        iter.write().next(vm)
+    }
+
+    /// Drain the iterator into a vector
+    pub fn collect(iter: &LazyIteratorRef, vm: &mut VM) -> Result<Vec<Value>, VmError> {
+        let mut items = Vec::new();
+        while let Some(item) = Self::pull(iter, vm)? {
+            items.push(item);
+        }
+        Ok(items)
+    }
+
+    /// Get the next value, returns None when exhausted
+    pub fn next(&mut self, vm: &mut VM) -> Result<Option<Value>, VmError> {
+        match self {
+            LazyIterator::Collection(iter) => Ok(iter.next()),
+
+            LazyIterator::Generator(generator) => vm.next_from_generator(generator),
+
+            LazyIterator::Map { source, callback } => match Self::pull(source, vm)? {
+                Some(item) => Ok(Some(vm.call_value(callback, &[item])?)),
+                None => Ok(None),
+            },
+
+            LazyIterator::Filter { source, predicate } => {
+                while let Some(item) = Self::pull(source, vm)? {
+                    let matches = vm.call_value(predicate, std::slice::from_ref(&item))?;
+                    if is_truthy(&matches) {
+                        return Ok(Some(item));
+                    }
+                }
+                Ok(None)
+            }
+
+            LazyIterator::TakeWhile {
+                source,
+                predicate,
+                done,
+            } => {
+                if *done {
+                    return Ok(None);
+                }
+                if let Some(item) = Self::pull(source, vm)? {
+                    let matches = vm.call_value(predicate, std::slice::from_ref(&item))?;
+                    if is_truthy(&matches) {
+                        return Ok(Some(item));
+                    }
+                }
+                *done = true;
+                Ok(None)
+            }
+
+            LazyIterator::Take { source, remaining } => {
+                if *remaining == 0 {
+                    return Ok(None);
+                }
+                *remaining -= 1;
+                Self::pull(source, vm)
+            }
+
+            LazyIterator::Enumerate { source, index } => match Self::pull(source, vm)? {
+                Some(item) => {
+                    let pair = vec![Value::Number(*index as f64), item];
+                    *index += 1;
+                    Ok(Some(Value::Vector(shared(pair))))
+                }
+                None => Ok(None),
+            },
+
+            LazyIterator::Zip { left, right } => {
+                let Some(a) = Self::pull(left, vm)? else {
+                    return Ok(None);
+                };
+                let Some(b) = Self::pull(right, vm)? else {
+                    return Ok(None);
+                };
+                Ok(Some(Value::Vector(shared(vec![a, b]))))
+            }
+
+            LazyIterator::Chain { first, second } => match Self::pull(first, vm)? {
+                Some(item) => Ok(Some(item)),
+                None => Self::pull(second, vm),
+            },
+
+            LazyIterator::Window {
+                source,
+                size,
+                buffer,
+            } => {
+                // Slide by one, then top the window back up
+                if buffer.len() == *size {
+                    buffer.pop_front();
+                }
+                while buffer.len() < *size {
+                    match Self::pull(source, vm)? {
+                        Some(item) => buffer.push_back(item),
+                        None => {
+                            buffer.clear();
+                            return Ok(None);
+                        }
+                    }
+                }
+                Ok(Some(Value::Vector(shared(
+                    buffer.iter().cloned().collect(),
+                ))))
+            }
+
+            LazyIterator::StepBy {
+                source,
+                step,
+                started,
+            } => {
+                if *started {
+                    for _ in 1..*step {
+                        if Self::pull(source, vm)?.is_none() {
+                            return Ok(None);
+                        }
+                    }
+                }
+                *started = true;
+                Self::pull(source, vm)
+            }
+        }
+    }
+}
+
 /// Builder for constructing collections during HOF operations
 ///
 /// Provides efficient collection building with automatic type preservation
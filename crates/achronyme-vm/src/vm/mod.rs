@@ -1,18 +1,22 @@
 //! Virtual Machine implementation
 
+use crate::builtins::reactive::ReactiveRuntime;
 use crate::builtins::registry::BuiltinRegistry;
+use crate::builtins::tasks::TaskGroup;
 use crate::bytecode::BytecodeModule;
 use crate::error::VmError;
 use crate::opcode::{instruction::*, OpCode};
 use crate::value::Value;
+use achronyme_parser::TypeAnnotation;
 use achronyme_types::sync::{shared, Arc, RwLock, Shared};
+use achronyme_types::value::TaskHandle;
 use std::collections::HashMap;
 
 // Module structure
-mod execution;
+pub(crate) mod execution;
 mod frame;
 mod generator;
-pub(crate) mod intrinsics;
+pub mod intrinsics;
 mod iterator;
 mod ops;
 mod result;
@@ -20,7 +24,7 @@ mod result;
 // Re-export public types
 pub use frame::{CallFrame, RegisterWindow, SuspendedFrame, MAX_REGISTERS};
 pub use generator::{VmGeneratorRef, VmGeneratorState};
-pub use iterator::{VmBuilder, VmIterator};
+pub use iterator::{LazyIterator, LazyIteratorRef, VmBuilder, VmIterator};
 
 // Internal imports
 use frame::CallFrame as InternalCallFrame;
@@ -30,6 +34,12 @@ use result::ExecutionResult;
 /// Maximum call stack depth
 pub const MAX_CALL_DEPTH: usize = 10000;
 
+/// How a frame run by `VM::run_nested` finished
+enum NestedExit {
+    Return(Value),
+    Yield(Value),
+}
+
 /// Virtual Machine
 pub struct VM {
     /// Call stack
@@ -60,6 +70,16 @@ pub struct VM {
 
     /// Root scope for active effects to keep them alive
     pub(crate) active_effects: Vec<Shared<achronyme_types::value::EffectState>>,
+
+    /// Reactive tracking state (observer stack, batching, scheduled effects)
+    pub(crate) reactive: ReactiveRuntime,
+
+    /// Enclosing `task_group()` scope; spawned tasks join it
+    pub(crate) task_group: Option<Arc<TaskGroup>>,
+
+    /// Parsed `Tensor<...>` annotations of type assertions, by their text
+    /// (`None` if the text does not parse as a tensor type)
+    pub(crate) tensor_types: Shared<HashMap<String, Option<TypeAnnotation>>>,
 }
 
 impl VM {
@@ -75,6 +95,9 @@ impl VM {
             precision: None, // Full precision by default
             epsilon: 1e-10,  // Default epsilon threshold
             active_effects: Vec::new(),
+            reactive: ReactiveRuntime::default(),
+            task_group: None,
+            tensor_types: shared(HashMap::new()),
         }
     }
 
@@ -90,6 +113,9 @@ impl VM {
             precision: self.precision,
             epsilon: self.epsilon,
             active_effects: Vec::new(), // Child VM gets its own effect root scope
+            reactive: ReactiveRuntime::default(), // ...and its own effect inbox
+            task_group: self.task_group.clone(),
+            tensor_types: self.tensor_types.clone(),
         }
     }
 
@@ -194,37 +220,9 @@ impl VM {
                     continue;
                 }
                 ExecutionResult::Yield(value) => {
-                    // Pop the generator's frame and save it back
-                    let gen_frame = self.frames.pop().ok_or(VmError::StackUnderflow)?;
-
-                    // If this frame has a generator reference, update the generator state
-                    if let Some(Value::Generator(any_ref)) = gen_frame.generator.as_ref() {
-                        if let Some(state_lock) =
-                            any_ref.downcast_ref::<RwLock<crate::vm::generator::VmGeneratorState>>()
-                        {
-                            let mut state = state_lock.write();
-                            // Save the frame state (clone before modifying to avoid borrow issues)
-                            let mut saved_frame = gen_frame.clone();
-                            saved_frame.generator = None; // Clear to avoid circular reference
-                            state.frame = saved_frame;
-                            drop(state);
-
-                            // Create iterator result object: {value: <yielded>, done: false}
-                            let mut result_map = std::collections::HashMap::new();
-                            result_map.insert("value".to_string(), value);
-                            result_map.insert("done".to_string(), Value::Boolean(false));
-                            let result_record = Value::Record(shared(result_map));
-
-                            // Put iterator result record in the caller's return register
-                            if let Some(return_reg) = gen_frame.return_register {
-                                if let Some(caller_frame) = self.frames.last_mut() {
-                                    caller_frame.registers.set(return_reg, result_record)?;
-                                }
-                            }
-
-                            // Continue execution in caller frame
-                            continue;
-                        }
+                    if self.do_yield(value.clone())? {
+                        // Continue execution in caller frame
+                        continue;
                     }
 
                     // No generator context - just return (shouldn't happen in normal execution)
@@ -232,11 +230,15 @@ impl VM {
                 }
                 ExecutionResult::Await(value, dst_reg) => {
                     match value {
-                        Value::Future(vm_future) => {
-                            // Non-blocking await: yield to Tokio executor
-                            let future = vm_future.0.clone();
-                            let result = future.await;
+                        Value::Future(future) | Value::Task(TaskHandle { result: future, .. }) => {
+                            // Non-blocking await: yield to Tokio executor, running
+                            // effects other tasks invalidate while we wait
+                            let result =
+                                crate::builtins::reactive::await_running_effects(self, future)
+                                    .await?;
                             self.set_register(dst_reg, result)?;
+                            // Effects posted just as the future resolved
+                            crate::builtins::reactive::run_scheduled_effects(self)?;
                         }
                         Value::Generator(_) => {
                             // Awaiting a generator = Resuming/Starting it
@@ -283,6 +285,7 @@ impl VM {
             | OpCode::Div
             | OpCode::Mod
             | OpCode::Pow
+            | OpCode::MatMul
             | OpCode::Neg
             | OpCode::Not => self.execute_arithmetic(opcode, instruction),
 
@@ -312,12 +315,15 @@ impl VM {
             | OpCode::VecSet
             | OpCode::VecSlice
             | OpCode::RangeEx
+            | OpCode::RangeIn
             | OpCode::TensorGet => self.execute_vectors(opcode, instruction),
 
             // Records
-            OpCode::NewRecord | OpCode::GetField | OpCode::SetField => {
-                self.execute_records(opcode, instruction)
-            }
+            OpCode::NewRecord
+            | OpCode::GetField
+            | OpCode::GetMethod
+            | OpCode::SetField
+            | OpCode::NewVariant => self.execute_records(opcode, instruction),
 
             // Pattern Matching
             OpCode::MatchType
@@ -441,69 +447,15 @@ impl VM {
 
                 // Push frame
                 self.frames.push(new_frame);
+                let base_depth = self.frames.len();
 
                 // Execute until this frame returns
-                loop {
-                    // Check if we're still in the function we called
-                    let frame_depth = self.frames.len();
-
-                    // Get current frame
-                    let frame = self.frames.last_mut().ok_or(VmError::StackUnderflow)?;
-
-                    // Fetch instruction
-                    let instruction = match frame.fetch() {
-                        Some(inst) => inst,
-                        None => {
-                            // End of function, return null
-                            if self.frames.len() == frame_depth {
-                                self.frames.pop();
-                                return Ok(Value::Null);
-                            }
-                            break;
-                        }
-                    };
-
-                    // Decode and dispatch
-                    let opcode_byte = decode_opcode(instruction);
-                    let opcode =
-                        OpCode::from_u8(opcode_byte).ok_or(VmError::InvalidOpcode(opcode_byte))?;
-
-                    // Execute instruction
-                    match self.execute_instruction(opcode, instruction)? {
-                        ExecutionResult::Continue => {
-                            // If we've returned from the function we called, extract the result
-                            if self.frames.len() < frame_depth {
-                                // The function returned, but we didn't capture the return value
-                                // For HOF calls, we need to handle returns differently
-                                return Ok(Value::Null);
-                            }
-                            continue;
-                        }
-                        ExecutionResult::Return(value) => {
-                            // Pop the frame we created
-                            if self.frames.len() >= frame_depth {
-                                self.frames.pop();
-                            }
-                            return Ok(value);
-                        }
-                        ExecutionResult::Exception(error) => {
-                            // Propagate exception
-                            return Err(VmError::UncaughtException(error));
-                        }
-                        ExecutionResult::Yield(_) => {
-                            return Err(VmError::Runtime(
-                                "Cannot yield from function called via call_value".into(),
-                            ));
-                        }
-                        ExecutionResult::Await(_, _) => {
-                            return Err(VmError::Runtime(
-                                "Cannot await inside a synchronous call (e.g. callback)".into(),
-                            ));
-                        }
-                    }
+                match self.run_nested(base_depth)? {
+                    NestedExit::Return(value) => Ok(value),
+                    NestedExit::Yield(_) => Err(VmError::Runtime(
+                        "Cannot yield from function called via call_value".into(),
+                    )),
                 }
-
-                Ok(Value::Null)
             }
             _ => Err(VmError::TypeError {
                 operation: "function call".to_string(),
@@ -513,6 +465,130 @@ impl VM {
         }
     }
 
+    /// Run the frame pushed at `base_depth` (and anything it calls) until it
+    /// returns or, for a generator frame, yields
+    ///
+    /// Used by native code that needs a result synchronously: HOF callbacks
+    /// and lazy iterators pulling from generators.
+    fn run_nested(&mut self, base_depth: usize) -> Result<NestedExit, VmError> {
+        loop {
+            if self.frames.len() > MAX_CALL_DEPTH {
+                return Err(VmError::StackOverflow);
+            }
+
+            // Get current frame
+            let frame = self.frames.last_mut().ok_or(VmError::StackUnderflow)?;
+
+            // Fetch instruction
+            let instruction = match frame.fetch() {
+                Some(inst) => inst,
+                None => {
+                    // End of function, return null
+                    let depth = self.frames.len();
+                    self.do_return(Value::Null)?;
+                    if depth == base_depth {
+                        return Ok(NestedExit::Return(Value::Null));
+                    }
+                    // A nested call ran off its end: back to its caller
+                    continue;
+                }
+            };
+
+            // Decode and dispatch
+            let opcode_byte = decode_opcode(instruction);
+            let opcode = OpCode::from_u8(opcode_byte).ok_or(VmError::InvalidOpcode(opcode_byte))?;
+
+            // Execute instruction
+            match self.execute_instruction(opcode, instruction)? {
+                ExecutionResult::Continue => {
+                    if self.frames.len() < base_depth {
+                        // The frame was popped without a value to hand back
+                        return Ok(NestedExit::Return(Value::Null));
+                    }
+                    continue;
+                }
+                ExecutionResult::Return(value) => {
+                    let depth = self.frames.len();
+                    self.do_return(value.clone())?;
+                    if depth == base_depth {
+                        return Ok(NestedExit::Return(value));
+                    }
+                    // A nested call returned: its value went to its caller
+                }
+                ExecutionResult::Exception(error) => {
+                    // Propagate exception
+                    return Err(VmError::UncaughtException(error));
+                }
+                ExecutionResult::Yield(value) => {
+                    let depth = self.frames.len();
+                    if !self.do_yield(value.clone())? {
+                        return Err(VmError::Runtime(
+                            "Cannot yield from function called via call_value".into(),
+                        ));
+                    }
+                    if depth == base_depth {
+                        return Ok(NestedExit::Yield(value));
+                    }
+                }
+                ExecutionResult::Await(_, _) => {
+                    return Err(VmError::Runtime(
+                        "Cannot await inside a synchronous call (e.g. callback)".into(),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Resume a generator from native code, running it to its next yield
+    ///
+    /// Returns `None` once the generator is exhausted. Unlike
+    /// `resume_generator_internal` this doesn't return to the frame loop,
+    /// so lazy iterators can pull values one at a time.
+    pub(crate) fn next_from_generator(
+        &mut self,
+        gen_value: &Value,
+    ) -> Result<Option<Value>, VmError> {
+        use crate::vm::execution::iterators::NativeIterator;
+
+        let Value::Generator(any_ref) = gen_value else {
+            return Err(VmError::TypeError {
+                operation: "generator resume".to_string(),
+                expected: "Generator".to_string(),
+                got: format!("{:?}", gen_value),
+            });
+        };
+
+        if let Some(iter_lock) = any_ref.downcast_ref::<RwLock<NativeIterator>>() {
+            return iter_lock.write().next();
+        }
+
+        let state_lock = any_ref
+            .downcast_ref::<RwLock<VmGeneratorState>>()
+            .ok_or_else(|| {
+                VmError::Runtime(
+                    "Invalid generator type (expected VM generator or native iterator)".to_string(),
+                )
+            })?;
+
+        let state = state_lock.read();
+        if state.is_done() {
+            return Ok(None);
+        }
+        let mut frame = state.frame.clone();
+        drop(state);
+
+        // No return register: the yielded value comes back from run_nested
+        frame.return_register = None;
+        frame.generator = Some(gen_value.clone());
+        self.frames.push(frame);
+        let base_depth = self.frames.len();
+
+        match self.run_nested(base_depth)? {
+            NestedExit::Yield(value) => Ok(Some(value)),
+            NestedExit::Return(_) => Ok(None),
+        }
+    }
+
     /// Set a global variable (for REPL)
     pub fn set_global(&mut self, name: String, value: Value) {
         self.globals.write().insert(name, value);
@@ -538,6 +614,23 @@ impl VM {
     ) -> Result<ExecutionResult, VmError> {
         use crate::vm::execution::iterators::NativeIterator;
 
+        // Receivers resolve asynchronously: await the next {value, done} record
+        if let Value::Receiver(rx) = gen_value {
+            let next = crate::builtins::concurrency::receiver_next(rx);
+            return Ok(ExecutionResult::Await(next, result_reg));
+        }
+
+        // Lazy iterators step synchronously, calling back into the VM
+        if let Value::Iterator(_) = gen_value {
+            let iter = LazyIterator::from_value(gen_value)?;
+            let result = match LazyIterator::pull(&iter, self)? {
+                Some(value) => iterator_result(value, false),
+                None => iterator_result(Value::Null, true),
+            };
+            self.set_register(result_reg, result)?;
+            return Ok(ExecutionResult::Continue);
+        }
+
         // Extract generator from Value
         if let Value::Generator(any_ref) = gen_value {
             // First, try to downcast to native iterator
@@ -545,7 +638,7 @@ impl VM {
                 // Handle native iterator
                 let mut iter = iter_lock.write();
 
-                if let Some(value) = iter.next() {
+                if let Some(value) = iter.next()? {
                     // More elements available: {value: X, done: false}
                     drop(iter); // Release lock
                     let mut result_map = HashMap::new();
@@ -642,6 +735,41 @@ impl VM {
         n.abs() < self.epsilon
     }
 
+    /// Suspend the generator frame on top of the stack after it yielded
+    ///
+    /// The frame is popped and saved back into its generator state, and the
+    /// caller's return register receives `{value, done: false}`. Returns
+    /// false if the frame didn't belong to a generator.
+    fn do_yield(&mut self, value: Value) -> Result<bool, VmError> {
+        // Pop the generator's frame and save it back
+        let gen_frame = self.frames.pop().ok_or(VmError::StackUnderflow)?;
+
+        let Some(Value::Generator(any_ref)) = gen_frame.generator.as_ref() else {
+            return Ok(false);
+        };
+        let Some(state_lock) =
+            any_ref.downcast_ref::<RwLock<crate::vm::generator::VmGeneratorState>>()
+        else {
+            return Ok(false);
+        };
+
+        // Save the frame state (cleared to avoid a circular reference)
+        let mut saved_frame = gen_frame.clone();
+        saved_frame.generator = None;
+        state_lock.write().frame = saved_frame;
+
+        // Put iterator result record in the caller's return register
+        if let Some(return_reg) = gen_frame.return_register {
+            if let Some(caller_frame) = self.frames.last_mut() {
+                caller_frame
+                    .registers
+                    .set(return_reg, iterator_result(value, false))?;
+            }
+        }
+
+        Ok(true)
+    }
+
     /// Perform return from function
     fn do_return(&mut self, value: Value) -> Result<(), VmError> {
         let frame = self.frames.pop().ok_or(VmError::StackUnderflow)?;
@@ -695,6 +823,14 @@ impl VM {
     }
 }
 
+/// Iterator result record: `{value, done}`
+fn iterator_result(value: Value, done: bool) -> Value {
+    let mut result_map = HashMap::new();
+    result_map.insert("value".to_string(), value);
+    result_map.insert("done".to_string(), Value::Boolean(done));
+    Value::Record(shared(result_map))
+}
+
 impl Default for VM {
     fn default() -> Self {
         Self::new()
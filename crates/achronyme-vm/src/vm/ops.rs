@@ -1,7 +1,10 @@
 //! Value operation helpers
 
+use std::borrow::Cow;
+
 use crate::error::VmError;
 use crate::value::Value;
+use achronyme_types::tensor::{ComplexTensor, RealTensor};
 
 /// Implementation of value operations for the VM
 pub(crate) struct ValueOperations;
@@ -12,6 +15,9 @@ impl ValueOperations {
         use achronyme_types::sync::shared;
 
         match (left, right) {
+            _ if Self::is_tensor_arithmetic(left, right) => {
+                Self::tensor_arithmetic('+', left, right)
+            }
             (Value::Number(a), Value::Number(b)) => Ok(Value::Number(a + b)),
             (Value::String(a), Value::String(b)) => Ok(Value::String(format!("{}{}", a, b))),
             (Value::Complex(a), Value::Complex(b)) => Ok(Value::Complex(*a + *b)),
@@ -167,6 +173,9 @@ impl ValueOperations {
         use achronyme_types::sync::shared;
 
         match (left, right) {
+            _ if Self::is_tensor_arithmetic(left, right) => {
+                Self::tensor_arithmetic('-', left, right)
+            }
             (Value::Number(a), Value::Number(b)) => Ok(Value::Number(a - b)),
             (Value::Complex(a), Value::Complex(b)) => Ok(Value::Complex(*a - *b)),
             (Value::Number(a), Value::Complex(b)) => Ok(Value::Complex(Complex::new(*a, 0.0) - *b)),
@@ -312,6 +321,9 @@ impl ValueOperations {
         use achronyme_types::sync::shared;
 
         match (left, right) {
+            _ if Self::is_tensor_arithmetic(left, right) => {
+                Self::tensor_arithmetic('*', left, right)
+            }
             (Value::Number(a), Value::Number(b)) => Ok(Value::Number(a * b)),
             (Value::Complex(a), Value::Complex(b)) => Ok(Value::Complex(*a * *b)),
             (Value::Number(a), Value::Complex(b)) => Ok(Value::Complex(Complex::new(*a, 0.0) * *b)),
@@ -495,6 +507,9 @@ impl ValueOperations {
         use achronyme_types::sync::shared;
 
         match (left, right) {
+            _ if Self::is_tensor_arithmetic(left, right) => {
+                Self::tensor_arithmetic('/', left, right)
+            }
             (Value::Number(a), Value::Number(b)) => {
                 // IEEE 754 semantics: division by zero produces Infinity or NaN
                 // a / 0 = Infinity (if a > 0)
@@ -918,14 +933,109 @@ impl ValueOperations {
         match value {
             Value::Number(n) => Ok(Value::Number(-n)),
             Value::Complex(c) => Ok(Value::Complex(-*c)),
+            Value::Tensor(t) => Ok(Value::Tensor(t.negate())),
+            Value::ComplexTensor(t) => Ok(Value::ComplexTensor(t.negate())),
             _ => Err(VmError::TypeError {
                 operation: "negation".to_string(),
-                expected: "Number or Complex".to_string(),
+                expected: "Number, Complex or Tensor".to_string(),
                 got: format!("-{:?}", value),
             }),
         }
     }
 
+    /// Matrix product `left @ right` of two 2-D tensors
+    pub(crate) fn matmul_values(left: &Value, right: &Value) -> Result<Value, VmError> {
+        let shape = |value: &Value| match value {
+            Value::Tensor(t) => Some(t.shape().to_vec()),
+            Value::ComplexTensor(t) => Some(t.shape().to_vec()),
+            _ => None,
+        };
+        let (Some(left_shape), Some(right_shape)) = (shape(left), shape(right)) else {
+            return Err(VmError::TypeError {
+                operation: "matrix product".to_string(),
+                expected: "Tensor".to_string(),
+                got: format!("{:?} @ {:?}", left, right),
+            });
+        };
+        if left_shape.len() != 2 || right_shape.len() != 2 {
+            return Err(VmError::Runtime(format!(
+                "Matrix product needs 2-D tensors, got shapes {:?} and {:?}",
+                left_shape, right_shape
+            )));
+        }
+        if left_shape[1] != right_shape[0] {
+            return Err(VmError::Runtime(format!(
+                "Matrix product of shapes {:?} and {:?}: inner dimensions {} and {} differ",
+                left_shape, right_shape, left_shape[1], right_shape[0]
+            )));
+        }
+        let result = match (left, right) {
+            (Value::Tensor(a), Value::Tensor(b)) => a.matmul(b).map(Value::Tensor),
+            _ => Self::complex_tensor(left)
+                .matmul(&Self::complex_tensor(right))
+                .map(Value::ComplexTensor),
+        };
+        result.map_err(|e| VmError::Runtime(e.to_string()))
+    }
+
+    /// Whether `left op right` is tensor arithmetic: a tensor with another
+    /// tensor or with a scalar
+    fn is_tensor_arithmetic(left: &Value, right: &Value) -> bool {
+        let is_tensor = |value: &Value| matches!(value, Value::Tensor(_) | Value::ComplexTensor(_));
+        let is_operand = |value: &Value| {
+            is_tensor(value) || matches!(value, Value::Number(_) | Value::Complex(_))
+        };
+        (is_tensor(left) || is_tensor(right)) && is_operand(left) && is_operand(right)
+    }
+
+    /// Element-wise `left op right` with broadcasting; a scalar is a 0-D tensor
+    fn tensor_arithmetic(op: char, left: &Value, right: &Value) -> Result<Value, VmError> {
+        let result = match (Self::real_tensor(left), Self::real_tensor(right)) {
+            (Some(a), Some(b)) => match op {
+                '+' => a.add(&b),
+                '-' => a.sub(&b),
+                '*' => a.mul(&b),
+                _ => a.div(&b),
+            }
+            .map(Value::Tensor),
+            _ => {
+                let a = Self::complex_tensor(left);
+                let b = Self::complex_tensor(right);
+                match op {
+                    '+' => a.add(&b),
+                    '-' => a.sub(&b),
+                    '*' => a.mul(&b),
+                    _ => a.div(&b),
+                }
+                .map(Value::ComplexTensor)
+            }
+        };
+        result.map_err(|e| VmError::Runtime(e.to_string()))
+    }
+
+    fn real_tensor(value: &Value) -> Option<Cow<'_, RealTensor>> {
+        match value {
+            Value::Tensor(t) => Some(Cow::Borrowed(t)),
+            Value::Number(n) => RealTensor::new(vec![*n], vec![]).ok().map(Cow::Owned),
+            _ => None,
+        }
+    }
+
+    fn complex_tensor(value: &Value) -> Cow<'_, ComplexTensor> {
+        use achronyme_types::complex::Complex;
+
+        let scalar = |c: Complex| {
+            ComplexTensor::new(vec![c], vec![]).expect("a 0-D tensor holds one element")
+        };
+        match value {
+            Value::ComplexTensor(t) => Cow::Borrowed(t),
+            Value::Tensor(t) => Cow::Owned(t.to_complex()),
+            Value::Complex(c) => Cow::Owned(scalar(*c)),
+            Value::Number(n) => Cow::Owned(scalar(Complex::from_real(*n))),
+            _ => unreachable!("checked by is_tensor_arithmetic"),
+        }
+    }
+
     pub(crate) fn lt_values(left: &Value, right: &Value) -> Result<Value, VmError> {
         match (left, right) {
             (Value::Number(a), Value::Number(b)) => Ok(Value::Boolean(a < b)),